@@ -0,0 +1,30 @@
+-- Relationship tuples for resource-level (Zanzibar-style) authorization.
+-- A tuple reads as object_type:object_id#relation@subject_type:subject_id[#subject_relation].
+-- subject_relation is '' for direct subjects so the unique index covers both forms.
+
+CREATE TABLE IF NOT EXISTS relation_tuples (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    object_type VARCHAR(64) NOT NULL,
+    object_id VARCHAR(255) NOT NULL,
+    relation VARCHAR(64) NOT NULL,
+    subject_type VARCHAR(64) NOT NULL,
+    subject_id VARCHAR(255) NOT NULL,
+    subject_relation VARCHAR(64) NOT NULL DEFAULT '',
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_relation_tuples_unique
+    ON relation_tuples(org_id, object_type, object_id, relation, subject_type, subject_id, subject_relation);
+
+-- Reverse lookups (list-objects walks from the subject side)
+CREATE INDEX IF NOT EXISTS idx_relation_tuples_subject
+    ON relation_tuples(org_id, subject_type, subject_id, subject_relation);
+
+-- Per-org relationship schema (namespaces and userset rewrites)
+CREATE TABLE IF NOT EXISTS relationship_schemas (
+    org_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    definition JSONB NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,24 @@
+-- Shareable multi-use invite links.
+-- Any authenticated user holding the token can join until the link expires,
+-- is revoked, or reaches max_uses. allowed_domains (lowercased) optionally
+-- restricts redemption to verified emails at those domains.
+
+CREATE TABLE IF NOT EXISTS invite_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL DEFAULT 'member',
+    token_hash VARCHAR(64) NOT NULL,
+    max_uses INTEGER,
+    use_count INTEGER NOT NULL DEFAULT 0,
+    allowed_domains TEXT[] NOT NULL DEFAULT '{}',
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ,
+    CONSTRAINT invite_links_token_unique UNIQUE (token_hash),
+    CONSTRAINT invite_links_role_check CHECK (role IN ('admin', 'member', 'viewer')),
+    CONSTRAINT invite_links_max_uses_check CHECK (max_uses IS NULL OR max_uses > 0),
+    CONSTRAINT invite_links_use_count_check CHECK (use_count >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_invite_links_org ON invite_links(org_id, created_at DESC);
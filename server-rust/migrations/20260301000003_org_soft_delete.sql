@@ -0,0 +1,14 @@
+-- Soft-delete for organizations: deletion enters a grace period during which
+-- members lose access but data is retained and the org can be restored.
+-- A background worker purges orgs once purge_after has passed.
+
+ALTER TABLE organizations ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE organizations ADD COLUMN IF NOT EXISTS purge_after TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_organizations_purge_after
+    ON organizations(purge_after)
+    WHERE purge_after IS NOT NULL;
+
+INSERT INTO system_settings (key, value, category, description) VALUES
+    ('org_deletion_grace_days', '30', 'org', 'Days a deleted organization can be restored before it is purged')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,14 @@
+-- Scoped, expiring and org-bound API keys.
+-- Empty scopes keep the legacy behaviour (full power of the owning user).
+
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS scopes TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS org_id UUID REFERENCES organizations(id) ON DELETE CASCADE;
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ;
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS allowed_ips TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS rate_limit_per_minute INTEGER;
+
+ALTER TABLE api_keys
+    ADD CONSTRAINT api_keys_rate_limit_positive
+    CHECK (rate_limit_per_minute IS NULL OR rate_limit_per_minute > 0);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_org ON api_keys(org_id) WHERE org_id IS NOT NULL;
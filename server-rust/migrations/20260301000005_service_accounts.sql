@@ -0,0 +1,43 @@
+-- Org-owned service accounts (non-human principals).
+-- Each service account is backed by a credential-less users row with the same
+-- ID so memberships, API keys and sessions work unchanged. Deleting the backing
+-- user removes the service account.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS is_service_account BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- DB-03 still applies to humans; service accounts authenticate with client
+-- credentials or API keys instead of a login identifier.
+ALTER TABLE users DROP CONSTRAINT IF EXISTS chk_user_has_auth_identifier;
+ALTER TABLE users
+    ADD CONSTRAINT chk_user_has_auth_identifier
+    CHECK (
+      email IS NOT NULL
+      OR wallet_address IS NOT NULL
+      OR google_id IS NOT NULL
+      OR apple_id IS NOT NULL
+      OR is_service_account
+    );
+
+CREATE TABLE IF NOT EXISTS service_accounts (
+    id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    custom_role_id UUID REFERENCES custom_roles(id) ON DELETE SET NULL,
+    client_id VARCHAR(64) NOT NULL,
+    client_secret_hash VARCHAR(64) NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    CONSTRAINT service_accounts_client_id_unique UNIQUE (client_id),
+    CONSTRAINT service_accounts_org_name_unique UNIQUE (org_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_service_accounts_org ON service_accounts(org_id, created_at);
+
+-- Distinguish human and service account actors in the audit log
+ALTER TABLE audit_logs ADD COLUMN IF NOT EXISTS actor_type VARCHAR(20) NOT NULL DEFAULT 'user';
+ALTER TABLE audit_logs
+    ADD CONSTRAINT audit_logs_actor_type_check
+    CHECK (actor_type IN ('user', 'service_account'));
@@ -0,0 +1,7 @@
+-- Admin impersonation: session lifetime and whether org owners may
+-- impersonate their members (system admins always can).
+
+INSERT INTO system_settings (key, value, category, description) VALUES
+    ('impersonation_duration_mins', '15', 'impersonation', 'Lifetime of an impersonation session in minutes'),
+    ('impersonation_org_owners_enabled', 'false', 'impersonation', 'Allow organization owners to impersonate their members')
+ON CONFLICT (key) DO NOTHING;
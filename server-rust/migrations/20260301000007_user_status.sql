@@ -0,0 +1,15 @@
+-- Account lifecycle states. Suspensions and bans may carry an expiry after
+-- which the account is treated as active again; pending_deletion marks an
+-- account scheduled for removal.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'active';
+ALTER TABLE users ADD COLUMN IF NOT EXISTS status_reason TEXT;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS status_expires_at TIMESTAMPTZ;
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_status_check;
+ALTER TABLE users ADD CONSTRAINT users_status_check
+    CHECK (status IN ('active', 'suspended', 'banned', 'pending_deletion'));
+
+CREATE INDEX IF NOT EXISTS idx_users_status
+    ON users(status)
+    WHERE status <> 'active';
@@ -0,0 +1,29 @@
+-- Self-service account deletion. A deleted account is pending_deletion for a
+-- cooling-off period, after which the purge worker strips its personal data
+-- and marks it deleted. The row is kept because deposits, withdrawals and
+-- credit transactions reference it, so a deleted user needs no login
+-- identifier.
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_status_check;
+ALTER TABLE users ADD CONSTRAINT users_status_check
+    CHECK (status IN ('active', 'suspended', 'banned', 'pending_deletion', 'deleted'));
+
+ALTER TABLE users DROP CONSTRAINT IF EXISTS chk_user_has_auth_identifier;
+ALTER TABLE users
+    ADD CONSTRAINT chk_user_has_auth_identifier
+    CHECK (
+      email IS NOT NULL
+      OR wallet_address IS NOT NULL
+      OR google_id IS NOT NULL
+      OR apple_id IS NOT NULL
+      OR is_service_account
+      OR status = 'deleted'
+    );
+
+CREATE INDEX IF NOT EXISTS idx_users_purge_due
+    ON users(status_expires_at)
+    WHERE status = 'pending_deletion';
+
+INSERT INTO system_settings (key, value, category, description) VALUES
+    ('account_deletion_grace_days', '30', 'account', 'Days a deleted account can be restored before it is purged')
+ON CONFLICT (key) DO NOTHING;
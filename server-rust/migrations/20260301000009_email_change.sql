@@ -0,0 +1,5 @@
+-- Verified email address changes: the confirmation token sent to the new
+-- address carries the new email, and the revert token sent to the old address
+-- carries the previous email, so tokens need to store a payload.
+
+ALTER TABLE verification_tokens ADD COLUMN IF NOT EXISTS payload TEXT;
@@ -0,0 +1,14 @@
+-- Extensible per-user metadata for host apps:
+--   public_metadata  - readable by the user, writable by admins and API keys
+--   private_metadata - visible only to admins and API keys
+--   unsafe_metadata  - readable and writable by the user
+-- Selected public_metadata keys can be copied into access tokens via the
+-- jwt_metadata_claims setting.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS public_metadata JSONB NOT NULL DEFAULT '{}';
+ALTER TABLE users ADD COLUMN IF NOT EXISTS private_metadata JSONB NOT NULL DEFAULT '{}';
+ALTER TABLE users ADD COLUMN IF NOT EXISTS unsafe_metadata JSONB NOT NULL DEFAULT '{}';
+
+INSERT INTO system_settings (key, value, category, description) VALUES
+    ('jwt_metadata_claims', '[]', 'account', 'JSON array of public_metadata keys copied into access tokens as the metadata claim')
+ON CONFLICT (key) DO NOTHING;
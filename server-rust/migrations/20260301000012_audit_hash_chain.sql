@@ -0,0 +1,35 @@
+-- Tamper-evident audit log.
+-- Every entry written from now on gets a gap-free sequence number and commits
+-- to the hash of the previous entry, both globally and within its org.
+-- Checkpoints of the chain head are signed with a server key so a rewritten
+-- tail can be detected too. Entries written before this migration have no
+-- sequence number and are not covered by verification.
+
+ALTER TABLE audit_logs ADD COLUMN IF NOT EXISTS seq BIGINT;
+ALTER TABLE audit_logs ADD COLUMN IF NOT EXISTS prev_hash VARCHAR(64);
+ALTER TABLE audit_logs ADD COLUMN IF NOT EXISTS org_prev_hash VARCHAR(64);
+ALTER TABLE audit_logs ADD COLUMN IF NOT EXISTS content_hash VARCHAR(64);
+ALTER TABLE audit_logs ADD COLUMN IF NOT EXISTS personal_hash VARCHAR(64);
+ALTER TABLE audit_logs ADD COLUMN IF NOT EXISTS entry_hash VARCHAR(64);
+ALTER TABLE audit_logs ADD COLUMN IF NOT EXISTS redacted BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_audit_logs_seq ON audit_logs(seq) WHERE seq IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_audit_logs_org_seq ON audit_logs(org_id, seq) WHERE seq IS NOT NULL;
+
+-- Hashed fields must never change after the write. ON DELETE SET NULL on
+-- these references would rewrite chained rows whenever a user, org or session
+-- is deleted, so the IDs are kept as plain values instead.
+ALTER TABLE audit_logs DROP CONSTRAINT IF EXISTS audit_logs_actor_user_id_fkey;
+ALTER TABLE audit_logs DROP CONSTRAINT IF EXISTS audit_logs_org_id_fkey;
+ALTER TABLE audit_logs DROP CONSTRAINT IF EXISTS fk_audit_logs_session_id;
+
+CREATE TABLE IF NOT EXISTS audit_checkpoints (
+    id UUID PRIMARY KEY,
+    seq BIGINT NOT NULL,
+    entry_hash VARCHAR(64) NOT NULL,
+    public_key VARCHAR(64) NOT NULL,
+    signature VARCHAR(128) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_checkpoints_seq ON audit_checkpoints(seq);
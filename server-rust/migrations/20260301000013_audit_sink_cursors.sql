@@ -0,0 +1,9 @@
+-- Delivery cursors for audit sinks (syslog, NDJSON file, OTLP).
+-- Each sink records the last audit chain sequence number it has delivered,
+-- so entries written during a collector outage are sent once it recovers.
+
+CREATE TABLE IF NOT EXISTS audit_sink_cursors (
+    sink VARCHAR(64) PRIMARY KEY,
+    last_seq BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
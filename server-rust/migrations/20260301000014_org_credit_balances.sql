@@ -0,0 +1,49 @@
+-- Organization-owned credit balances (team wallets)
+--
+-- Credit transactions and holds gain an owner: 'user' (the personal balance
+-- of user_id, as before) or 'org' (the balance of org_id). For org-owned rows
+-- user_id is the member who spent, or the admin who adjusted the balance.
+
+CREATE TABLE IF NOT EXISTS org_credit_balances (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    balance BIGINT NOT NULL DEFAULT 0,
+    held_balance BIGINT NOT NULL DEFAULT 0,
+    currency TEXT NOT NULL DEFAULT 'SOL',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(org_id, currency),
+    CONSTRAINT org_credit_balances_balance_non_negative CHECK (balance >= 0),
+    CONSTRAINT org_credit_balances_held_non_negative CHECK (held_balance >= 0)
+);
+
+ALTER TABLE credit_transactions ADD COLUMN IF NOT EXISTS owner_type TEXT NOT NULL DEFAULT 'user';
+ALTER TABLE credit_transactions ADD COLUMN IF NOT EXISTS org_id UUID;
+ALTER TABLE credit_transactions
+  ADD CONSTRAINT credit_transactions_owner_check
+    CHECK ((owner_type = 'user' AND org_id IS NULL) OR (owner_type = 'org' AND org_id IS NOT NULL));
+
+ALTER TABLE credit_holds ADD COLUMN IF NOT EXISTS owner_type TEXT NOT NULL DEFAULT 'user';
+ALTER TABLE credit_holds ADD COLUMN IF NOT EXISTS org_id UUID;
+ALTER TABLE credit_holds
+  ADD CONSTRAINT credit_holds_owner_check
+    CHECK ((owner_type = 'user' AND org_id IS NULL) OR (owner_type = 'org' AND org_id IS NOT NULL));
+
+-- No FK on org_id: the ledger outlives a purged organization
+CREATE INDEX IF NOT EXISTS idx_credit_transactions_org_created
+  ON credit_transactions(org_id, created_at DESC) WHERE org_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_credit_holds_org
+  ON credit_holds(org_id) WHERE org_id IS NOT NULL AND status = 'pending';
+
+-- Per-member spending rules for the org balance.
+-- spend_allowed overrides the role default (owners and admins may spend,
+-- members may not); monthly_cap limits a member's spend per calendar month
+-- (UTC) in the smallest unit of each currency.
+CREATE TABLE IF NOT EXISTS org_credit_member_settings (
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    spend_allowed BOOLEAN,
+    monthly_cap BIGINT CHECK (monthly_cap IS NULL OR monthly_cap >= 0),
+    updated_by UUID,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (org_id, user_id)
+);
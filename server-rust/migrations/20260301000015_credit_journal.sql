@@ -0,0 +1,57 @@
+-- Double-entry journal for credits
+--
+-- Each credit transaction posts one entry that debits one account and
+-- credits another by the same amount. Balance accounts (user_balance,
+-- org_balance) carry the owner's id; system accounts (deposit_clearing,
+-- revenue, refunds, promotional_liability, adjustments) have no owner.
+-- The reconciliation job recomputes credit_balances / org_credit_balances
+-- from this table and reports any drift.
+
+CREATE TABLE IF NOT EXISTS credit_journal_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    transaction_id UUID REFERENCES credit_transactions(id) ON DELETE CASCADE,
+    currency TEXT NOT NULL,
+    amount BIGINT NOT NULL CHECK (amount >= 0),
+    debit_account TEXT NOT NULL,
+    debit_owner_id UUID,
+    credit_account TEXT NOT NULL,
+    credit_owner_id UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_credit_journal_transaction
+  ON credit_journal_entries(transaction_id) WHERE transaction_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_credit_journal_created
+  ON credit_journal_entries(created_at);
+CREATE INDEX IF NOT EXISTS idx_credit_journal_debit_owner
+  ON credit_journal_entries(debit_account, debit_owner_id) WHERE debit_owner_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_credit_journal_credit_owner
+  ON credit_journal_entries(credit_account, credit_owner_id) WHERE credit_owner_id IS NOT NULL;
+
+-- Post existing transactions with the same rules as CreditJournalEntry::for_transaction
+INSERT INTO credit_journal_entries (transaction_id, currency, amount, debit_account,
+    debit_owner_id, credit_account, credit_owner_id, created_at)
+SELECT t.id,
+       UPPER(t.currency),
+       ABS(t.amount),
+       CASE WHEN t.amount >= 0 THEN c.account ELSE o.account END,
+       CASE WHEN t.amount >= 0 THEN NULL ELSE o.owner_id END,
+       CASE WHEN t.amount >= 0 THEN o.account ELSE c.account END,
+       CASE WHEN t.amount >= 0 THEN o.owner_id ELSE NULL END,
+       t.created_at
+FROM credit_transactions t
+CROSS JOIN LATERAL (
+    SELECT CASE WHEN t.owner_type = 'org' THEN 'org_balance' ELSE 'user_balance' END AS account,
+           CASE WHEN t.owner_type = 'org' THEN t.org_id ELSE t.user_id END AS owner_id
+) o
+CROSS JOIN LATERAL (
+    SELECT CASE
+             WHEN t.tx_type = 'deposit' THEN 'deposit_clearing'
+             WHEN t.tx_type = 'spend' THEN 'revenue'
+             WHEN t.reference_type = 'refund' THEN 'refunds'
+             ELSE 'adjustments'
+           END AS account
+) c
+WHERE NOT EXISTS (
+    SELECT 1 FROM credit_journal_entries j WHERE j.transaction_id = t.id
+);
@@ -0,0 +1,37 @@
+-- Promotional credit grants
+--
+-- A grant adds free credit (sign-up bonus, referral reward, promotion) to a
+-- user's balance and tracks what is left of it. Spends draw from active
+-- grants first, highest priority then soonest expiry; the expiry worker
+-- forfeits whatever is left once a grant expires.
+
+CREATE TABLE IF NOT EXISTS credit_grants (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    currency TEXT NOT NULL,
+    source TEXT NOT NULL,
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    remaining BIGINT NOT NULL CHECK (remaining >= 0 AND remaining <= amount),
+    priority INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ,
+    status TEXT NOT NULL DEFAULT 'active',
+    transaction_id UUID NOT NULL,
+    reason TEXT,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_credit_grants_user_active
+  ON credit_grants(user_id, currency) WHERE status = 'active';
+CREATE INDEX IF NOT EXISTS idx_credit_grants_expiry
+  ON credit_grants(expires_at) WHERE status = 'active' AND expires_at IS NOT NULL;
+
+-- Part of each transaction's amount that moved grant credit (same sign)
+ALTER TABLE credit_transactions
+  ADD COLUMN IF NOT EXISTS grant_amount BIGINT NOT NULL DEFAULT 0;
+
+-- A transaction that mixes paid and grant credit posts two journal entries
+DROP INDEX IF EXISTS idx_credit_journal_transaction;
+CREATE INDEX IF NOT EXISTS idx_credit_journal_transaction
+  ON credit_journal_entries(transaction_id) WHERE transaction_id IS NOT NULL;
@@ -0,0 +1,21 @@
+-- Partial and incremental capture of credit holds
+--
+-- A hold can now be captured in several parts. captured_amount tracks what
+-- has been spent so far; amount - captured_amount is still reserved in
+-- held_balance. A final capture (or release/expiry) returns the remainder.
+
+ALTER TABLE credit_holds
+  ADD COLUMN IF NOT EXISTS captured_amount BIGINT NOT NULL DEFAULT 0;
+
+-- Holds captured before this migration were always captured in full
+UPDATE credit_holds SET captured_amount = amount WHERE status = 'captured';
+
+DO $$ BEGIN
+    ALTER TABLE credit_holds
+        ADD CONSTRAINT credit_holds_captured_amount_check
+            CHECK (captured_amount >= 0 AND captured_amount <= amount);
+EXCEPTION WHEN duplicate_object THEN NULL;
+END $$;
+
+COMMENT ON COLUMN credit_holds.captured_amount IS
+    'Sum of captures so far. Reserved = amount - captured_amount while pending.';
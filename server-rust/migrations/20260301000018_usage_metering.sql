@@ -0,0 +1,58 @@
+-- Usage metering: meter price catalog, per-org price overrides and usage events
+--
+-- Usage events are reported with a per-user idempotency key and billed in
+-- batches by the usage billing worker. A batch covers one meter, payer and
+-- calendar month; its debit uses the idempotency key 'usage_batch:<batch_id>'.
+
+CREATE TABLE IF NOT EXISTS meters (
+    id UUID PRIMARY KEY,
+    name VARCHAR(64) NOT NULL UNIQUE,
+    display_name VARCHAR(255) NOT NULL,
+    unit VARCHAR(32) NOT NULL,
+    currency VARCHAR(16) NOT NULL,
+    -- {"model": "tiered"|"volume", "tiers": [{"upTo", "unitPriceLamports", "flatFeeLamports"}]}
+    pricing JSONB NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS meter_price_overrides (
+    meter_id UUID NOT NULL REFERENCES meters(id) ON DELETE CASCADE,
+    org_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    pricing JSONB NOT NULL,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (meter_id, org_id)
+);
+
+CREATE TABLE IF NOT EXISTS usage_events (
+    id UUID PRIMARY KEY,
+    meter_id UUID NOT NULL REFERENCES meters(id),
+    user_id UUID NOT NULL,
+    org_id UUID,
+    quantity BIGINT NOT NULL CHECK (quantity > 0),
+    idempotency_key VARCHAR(255) NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'billed', 'failed')),
+    batch_id UUID,
+    cost BIGINT,
+    transaction_id UUID,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    billed_at TIMESTAMPTZ,
+    UNIQUE (user_id, idempotency_key)
+);
+
+-- Worker polling
+CREATE INDEX IF NOT EXISTS idx_usage_events_pending
+    ON usage_events(occurred_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_usage_events_batch
+    ON usage_events(batch_id) WHERE batch_id IS NOT NULL;
+-- Period-to-date usage for tier pricing
+CREATE INDEX IF NOT EXISTS idx_usage_events_meter_org
+    ON usage_events(meter_id, org_id, occurred_at) WHERE status = 'billed';
+CREATE INDEX IF NOT EXISTS idx_usage_events_meter_user
+    ON usage_events(meter_id, user_id, occurred_at) WHERE status = 'billed';
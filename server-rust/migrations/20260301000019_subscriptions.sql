@@ -0,0 +1,59 @@
+-- Recurring subscriptions: plan catalog and user/org subscriptions
+--
+-- The subscription renewal worker charges each period's price from the
+-- payer's credits with the idempotency key
+-- 'subscription:<subscription_id>:<period_start_unix>'. Failed charges put the
+-- subscription past due; it is retried daily and canceled once the plan's
+-- grace period has passed.
+
+CREATE TABLE IF NOT EXISTS subscription_plans (
+    id UUID PRIMARY KEY,
+    name VARCHAR(64) NOT NULL UNIQUE,
+    display_name VARCHAR(255) NOT NULL,
+    price_lamports BIGINT NOT NULL CHECK (price_lamports >= 0),
+    currency VARCHAR(16) NOT NULL,
+    interval VARCHAR(8) NOT NULL CHECK (interval IN ('day', 'week', 'month', 'year')),
+    interval_count INTEGER NOT NULL DEFAULT 1 CHECK (interval_count > 0),
+    trial_days INTEGER NOT NULL DEFAULT 0 CHECK (trial_days >= 0),
+    grace_days INTEGER NOT NULL DEFAULT 0 CHECK (grace_days >= 0),
+    entitlements TEXT[] NOT NULL DEFAULT '{}',
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS subscriptions (
+    id UUID PRIMARY KEY,
+    plan_id UUID NOT NULL REFERENCES subscription_plans(id),
+    user_id UUID NOT NULL,
+    org_id UUID REFERENCES organizations(id) ON DELETE CASCADE,
+    status VARCHAR(16) NOT NULL
+        CHECK (status IN ('trialing', 'active', 'past_due', 'canceled')),
+    current_period_start TIMESTAMPTZ NOT NULL,
+    current_period_end TIMESTAMPTZ NOT NULL,
+    trial_end TIMESTAMPTZ,
+    cancel_at_period_end BOOLEAN NOT NULL DEFAULT FALSE,
+    past_due_since TIMESTAMPTZ,
+    dunning_attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL,
+    canceled_at TIMESTAMPTZ,
+    cancel_reason TEXT,
+    last_transaction_id UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One open subscription per payer and plan
+CREATE UNIQUE INDEX IF NOT EXISTS idx_subscriptions_open_user
+    ON subscriptions(plan_id, user_id)
+    WHERE org_id IS NULL AND status <> 'canceled';
+CREATE UNIQUE INDEX IF NOT EXISTS idx_subscriptions_open_org
+    ON subscriptions(plan_id, org_id)
+    WHERE org_id IS NOT NULL AND status <> 'canceled';
+
+-- Worker polling
+CREATE INDEX IF NOT EXISTS idx_subscriptions_due
+    ON subscriptions(next_attempt_at) WHERE status <> 'canceled';
+CREATE INDEX IF NOT EXISTS idx_subscriptions_user ON subscriptions(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_subscriptions_org
+    ON subscriptions(org_id, created_at) WHERE org_id IS NOT NULL;
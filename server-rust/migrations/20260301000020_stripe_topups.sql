@@ -0,0 +1,36 @@
+-- Stripe card top-ups of credit balances
+--
+-- A top-up is created when the user starts a Checkout Session or
+-- PaymentIntent and credited when Stripe's signed webhook reports it paid,
+-- with the idempotency key 'stripe_topup:<topup_id>'. Refunds and disputes
+-- post reversing deposit transactions; credits that were already spent are
+-- recorded in unrecovered_amount.
+
+CREATE TABLE IF NOT EXISTS stripe_topups (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    org_id UUID REFERENCES organizations(id) ON DELETE SET NULL,
+    flow VARCHAR(16) NOT NULL CHECK (flow IN ('checkout', 'payment_intent')),
+    amount_cents BIGINT NOT NULL CHECK (amount_cents > 0),
+    currency VARCHAR(8) NOT NULL,
+    credit_amount BIGINT NOT NULL CHECK (credit_amount > 0),
+    credit_currency VARCHAR(16) NOT NULL,
+    status VARCHAR(16) NOT NULL CHECK (status IN ('pending', 'succeeded', 'failed')),
+    checkout_session_id TEXT UNIQUE,
+    payment_intent_id TEXT UNIQUE,
+    transaction_id UUID,
+    refunded_cents BIGINT NOT NULL DEFAULT 0 CHECK (refunded_cents >= 0),
+    dispute_id TEXT,
+    dispute_status VARCHAR(32),
+    disputed_cents BIGINT NOT NULL DEFAULT 0,
+    disputed_amount BIGINT NOT NULL DEFAULT 0,
+    reversed_amount BIGINT NOT NULL DEFAULT 0,
+    unrecovered_amount BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_stripe_topups_user ON stripe_topups(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_stripe_topups_org
+    ON stripe_topups(org_id, created_at) WHERE org_id IS NOT NULL;
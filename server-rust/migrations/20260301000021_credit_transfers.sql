@@ -0,0 +1,25 @@
+-- Credit transfers between users and organizations
+--
+-- A transfer posts two 'transfer' transactions sharing reference_type
+-- 'credit_transfer' and the transfer ID as reference_id: the debit leg
+-- carries the sender's idempotency key, the credit leg the key
+-- 'transfer_in:<transfer_id>'. Both legs are written in one DB transaction.
+
+-- The tx_type check predates grants; allow every transaction type
+ALTER TABLE credit_transactions
+  DROP CONSTRAINT IF EXISTS credit_transactions_tx_type_check;
+ALTER TABLE credit_transactions
+  ADD CONSTRAINT credit_transactions_tx_type_check
+    CHECK (tx_type IN ('deposit', 'spend', 'adjustment', 'grant', 'expiration', 'transfer'));
+
+-- Daily transfer totals per sender
+CREATE INDEX IF NOT EXISTS idx_credit_transactions_transfers_out
+  ON credit_transactions(user_id, currency, created_at)
+  WHERE tx_type = 'transfer' AND amount < 0;
+
+INSERT INTO system_settings (key, value, category, description) VALUES
+    ('credit_transfer_daily_limit_lamports', '10000000000', 'credit',
+     'Maximum credits a user may transfer per UTC day, per currency (0 = no limit). Default: 10 SOL'),
+    ('credit_transfer_step_up_lamports', '1000000000', 'credit',
+     'Transfers above this amount require recent strong authentication. Default: 1 SOL')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,57 @@
+-- Spending budgets per user, organization member and API key
+--
+-- A budget caps spends and holds per UTC day or calendar month. Usage is a
+-- counter per budget and period that spends and holds increment with a
+-- conditional update, so concurrent requests cannot overshoot hard_limit.
+-- Holds record the counters they consumed; when a hold settles, its
+-- uncaptured amount is given back.
+
+CREATE TABLE IF NOT EXISTS credit_budgets (
+    id UUID PRIMARY KEY,
+    scope VARCHAR(16) NOT NULL CHECK (scope IN ('user', 'org_member', 'api_key')),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    org_id UUID REFERENCES organizations(id) ON DELETE CASCADE,
+    api_key_id UUID REFERENCES api_keys(id) ON DELETE CASCADE,
+    currency VARCHAR(16) NOT NULL,
+    period VARCHAR(16) NOT NULL CHECK (period IN ('daily', 'monthly')),
+    soft_limit BIGINT CHECK (soft_limit > 0),
+    hard_limit BIGINT CHECK (hard_limit > 0),
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (soft_limit IS NOT NULL OR hard_limit IS NOT NULL),
+    CHECK (
+        (scope = 'user' AND user_id IS NOT NULL AND org_id IS NULL AND api_key_id IS NULL)
+        OR (scope = 'org_member' AND user_id IS NOT NULL AND org_id IS NOT NULL AND api_key_id IS NULL)
+        OR (scope = 'api_key' AND api_key_id IS NOT NULL AND user_id IS NULL AND org_id IS NULL)
+    )
+);
+
+-- One budget per subject, currency and period
+CREATE UNIQUE INDEX IF NOT EXISTS idx_credit_budgets_subject ON credit_budgets (
+    scope,
+    COALESCE(user_id, '00000000-0000-0000-0000-000000000000'::uuid),
+    COALESCE(org_id, '00000000-0000-0000-0000-000000000000'::uuid),
+    COALESCE(api_key_id, '00000000-0000-0000-0000-000000000000'::uuid),
+    currency,
+    period
+);
+CREATE INDEX IF NOT EXISTS idx_credit_budgets_user ON credit_budgets(user_id) WHERE user_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_credit_budgets_api_key
+    ON credit_budgets(api_key_id) WHERE api_key_id IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS credit_budget_usage (
+    budget_id UUID NOT NULL REFERENCES credit_budgets(id) ON DELETE CASCADE,
+    period_start TIMESTAMPTZ NOT NULL,
+    used BIGINT NOT NULL DEFAULT 0 CHECK (used >= 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (budget_id, period_start)
+);
+
+CREATE TABLE IF NOT EXISTS credit_budget_hold_reservations (
+    hold_id UUID NOT NULL,
+    budget_id UUID NOT NULL REFERENCES credit_budgets(id) ON DELETE CASCADE,
+    period_start TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (hold_id, budget_id)
+);
@@ -0,0 +1,26 @@
+-- Sequentially numbered invoices for credit deposits
+--
+-- Deposits are invoiced the first time they appear on a statement. Numbers
+-- come from a single counter row that is locked while invoices are issued,
+-- so they are gapless and never reused. Invoices are accounting records and
+-- are kept even if the depositing user or organization is deleted.
+
+CREATE TABLE IF NOT EXISTS credit_invoice_counter (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    last_number BIGINT NOT NULL DEFAULT 0
+);
+INSERT INTO credit_invoice_counter (id, last_number) VALUES (TRUE, 0) ON CONFLICT DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS credit_invoices (
+    number BIGINT PRIMARY KEY CHECK (number > 0),
+    transaction_id UUID NOT NULL UNIQUE,
+    owner_type VARCHAR(16) NOT NULL CHECK (owner_type IN ('user', 'org')),
+    user_id UUID NOT NULL,
+    org_id UUID,
+    amount BIGINT NOT NULL,
+    currency VARCHAR(16) NOT NULL,
+    deposited_at TIMESTAMPTZ NOT NULL,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS idx_credit_invoices_user ON credit_invoices(user_id) WHERE org_id IS NULL;
+CREATE INDEX IF NOT EXISTS idx_credit_invoices_org ON credit_invoices(org_id) WHERE org_id IS NOT NULL;
@@ -3,15 +3,20 @@
 mod webhook;
 
 pub use webhook::{
-    verify_signature, AuthWebhookData, LogoutWebhookData, WebhookCallback, WebhookConfig,
-    WebhookData, WebhookEvent, WebhookPayload,
+    sign_webhook_payload, validate_webhook_destination, verify_signature, AuthWebhookData,
+    LogoutWebhookData, UserStatusWebhookData, WebhookCallback, WebhookConfig, WebhookData,
+    WebhookEvent, WebhookPayload,
 };
 
 use crate::errors::AppError;
 use crate::models::{AuthMethod, AuthUser};
+use crate::repositories::UserStatus;
+use crate::services::TokenContext;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
+use uuid::Uuid;
 
 /// Payload passed to callbacks
 #[derive(Debug, Clone, Serialize)]
@@ -24,6 +29,53 @@ pub struct AuthCallbackPayload {
     pub user_agent: Option<String>,
 }
 
+/// Payload passed when an admin changes a user's account status
+#[derive(Debug, Clone, Serialize)]
+pub struct UserStatusChangePayload {
+    pub user_id: Uuid,
+    pub status: UserStatus,
+    pub previous_status: UserStatus,
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Admin who made the change
+    pub changed_by: Uuid,
+}
+
+/// Why an access token is being issued
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimsTrigger {
+    /// Sign-in with any method
+    Login,
+    /// Switch of the active organization
+    OrgSwitch,
+    /// Refresh token rotation
+    Refresh,
+}
+
+/// Payload passed to the claims-enrichment hook
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenClaimsPayload {
+    pub user_id: Uuid,
+    /// Active organization the token is issued for
+    pub org_id: Option<Uuid>,
+    /// User's role in the active organization
+    pub role: Option<String>,
+    pub trigger: ClaimsTrigger,
+}
+
+impl TokenClaimsPayload {
+    /// Build the payload for a token about to be issued with `context`
+    pub fn new(user_id: Uuid, context: &TokenContext, trigger: ClaimsTrigger) -> Self {
+        Self {
+            user_id,
+            org_id: context.org_id,
+            role: context.role.clone(),
+            trigger,
+        }
+    }
+}
+
 /// Trait for handling authenticated user events.
 /// Implement this to integrate with your application.
 #[async_trait]
@@ -38,6 +90,25 @@ pub trait AuthCallback: Send + Sync {
 
     /// Called when a user logs out.
     async fn on_logout(&self, user_id: &str) -> Result<(), AppError>;
+
+    /// Called when a user is suspended, banned or reactivated.
+    /// Sessions have already been revoked when this runs.
+    async fn on_user_status_changed(
+        &self,
+        _payload: &UserStatusChangePayload,
+    ) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    /// Called before an access token is signed on login, org switch and refresh.
+    /// Returned claims are added under the `ext` claim. Reserved claim names
+    /// are dropped and results over 4 KiB are ignored.
+    async fn enrich_claims(
+        &self,
+        _payload: &TokenClaimsPayload,
+    ) -> Result<Option<Map<String, Value>>, AppError> {
+        Ok(None)
+    }
 }
 
 /// Default callback that does nothing extra
@@ -76,6 +147,8 @@ mod tests {
                 email_verified: true,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                public_metadata: Default::default(),
+                unsafe_metadata: Default::default(),
             },
             method: AuthMethod::Email,
             is_new_user: false,
@@ -112,6 +185,18 @@ mod tests {
         assert!(value.as_object().unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_noop_callback_enrich_claims() {
+        let callback = NoopCallback;
+        let payload = TokenClaimsPayload {
+            user_id: Uuid::new_v4(),
+            org_id: None,
+            role: None,
+            trigger: ClaimsTrigger::Refresh,
+        };
+        assert!(callback.enrich_claims(&payload).await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_noop_callback_on_logout() {
         let callback = NoopCallback;
@@ -145,6 +230,8 @@ mod tests {
                 email_verified: false,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                public_metadata: Default::default(),
+                unsafe_metadata: Default::default(),
             },
             method: AuthMethod::Solana,
             is_new_user: true,
@@ -172,6 +259,8 @@ mod tests {
                 email_verified: true,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                public_metadata: Default::default(),
+                unsafe_metadata: Default::default(),
             },
             method: AuthMethod::Google,
             is_new_user: false,
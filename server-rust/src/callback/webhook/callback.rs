@@ -10,11 +10,12 @@ use std::net::IpAddr;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, error, warn};
 
-use crate::callback::{AuthCallback, AuthCallbackPayload};
+use crate::callback::{AuthCallback, AuthCallbackPayload, UserStatusChangePayload};
 use crate::errors::AppError;
 
 use super::types::{
-    AuthWebhookData, LogoutWebhookData, WebhookConfig, WebhookData, WebhookEvent, WebhookPayload,
+    AuthWebhookData, LogoutWebhookData, UserStatusWebhookData, WebhookConfig, WebhookData,
+    WebhookEvent, WebhookPayload,
 };
 
 type HmacSha256 = Hmac<Sha256>;
@@ -213,67 +214,7 @@ impl WebhookCallback {
     }
 
     async fn validate_destination(&self) -> Result<(), AppError> {
-        const DNS_LOOKUP_TIMEOUT_SECS: u64 = 2;
-
-        let url = url::Url::parse(&self.config.url)
-            .map_err(|e| AppError::Internal(anyhow::anyhow!("Invalid webhook URL: {}", e)))?;
-
-        if url.scheme() != "http" && url.scheme() != "https" {
-            return Err(AppError::Internal(anyhow::anyhow!(
-                "Webhook URL must use http or https scheme"
-            )));
-        }
-
-        let host = url
-            .host()
-            .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Webhook URL must have a host")))?;
-
-        match host {
-            url::Host::Ipv4(ip) => {
-                if is_private_ip(IpAddr::V4(ip)) {
-                    return Err(AppError::Internal(anyhow::anyhow!(
-                        "Webhook URL cannot target private IP addresses"
-                    )));
-                }
-            }
-            url::Host::Ipv6(ip) => {
-                if is_private_ip(IpAddr::V6(ip)) {
-                    return Err(AppError::Internal(anyhow::anyhow!(
-                        "Webhook URL cannot target private IP addresses"
-                    )));
-                }
-            }
-            url::Host::Domain(domain) => {
-                if domain == "localhost"
-                    || domain.ends_with(".local")
-                    || domain.ends_with(".internal")
-                {
-                    return Err(AppError::Internal(anyhow::anyhow!(
-                        "Webhook URL cannot target internal hostnames"
-                    )));
-                }
-
-                let port = url.port_or_known_default().unwrap_or(443);
-                let addrs = tokio::time::timeout(
-                    std::time::Duration::from_secs(DNS_LOOKUP_TIMEOUT_SECS),
-                    tokio::net::lookup_host((domain, port)),
-                )
-                .await
-                .map_err(|_| AppError::Internal(anyhow::anyhow!("Webhook DNS lookup timed out")))?
-                .map_err(|e| {
-                    AppError::Internal(anyhow::anyhow!("Failed to resolve webhook URL: {}", e))
-                })?;
-                for addr in addrs {
-                    if is_private_ip(addr.ip()) {
-                        return Err(AppError::Internal(anyhow::anyhow!(
-                            "Webhook URL resolves to private IP addresses"
-                        )));
-                    }
-                }
-            }
-        }
-
-        Ok(())
+        validate_webhook_destination(&self.config.url).await
     }
 
     /// Sign the payload with HMAC-SHA256
@@ -281,12 +222,7 @@ impl WebhookCallback {
     /// Note: HMAC-SHA256 accepts keys of any size, so new_from_slice only fails
     /// if the algorithm is invalid (which it isn't for Sha256). This unwrap is safe.
     pub fn sign_payload(&self, payload: &str) -> String {
-        // SAFETY: HMAC-SHA256 accepts keys of any length, this cannot fail
-        let mut mac = HmacSha256::new_from_slice(self.config.secret.as_bytes())
-            .expect("HMAC-SHA256 accepts keys of any size");
-        mac.update(payload.as_bytes());
-        let result = mac.finalize();
-        hex::encode(result.into_bytes())
+        sign_webhook_payload(&self.config.secret, payload)
     }
 
     /// Get current Unix timestamp
@@ -299,6 +235,85 @@ impl WebhookCallback {
     }
 }
 
+/// Hex-encoded HMAC-SHA256 of `payload` keyed with `secret`
+///
+/// Receivers verify it against `"{timestamp}.{body}"` with `verify_signature`.
+pub fn sign_webhook_payload(secret: &str, payload: &str) -> String {
+    // SAFETY: HMAC-SHA256 accepts keys of any length, this cannot fail
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC-SHA256 accepts keys of any size");
+    mac.update(payload.as_bytes());
+    let result = mac.finalize();
+    hex::encode(result.into_bytes())
+}
+
+/// Reject webhook URLs that target private or internal addresses.
+///
+/// Resolves domain names so that hostnames pointing at private ranges are
+/// caught too. Called before every request rather than only at configuration
+/// time, which narrows (but does not close) the DNS rebinding window.
+pub async fn validate_webhook_destination(url: &str) -> Result<(), AppError> {
+    const DNS_LOOKUP_TIMEOUT_SECS: u64 = 2;
+
+    let url = url::Url::parse(url)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Invalid webhook URL: {}", e)))?;
+
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(AppError::Internal(anyhow::anyhow!(
+            "Webhook URL must use http or https scheme"
+        )));
+    }
+
+    let host = url
+        .host()
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Webhook URL must have a host")))?;
+
+    match host {
+        url::Host::Ipv4(ip) => {
+            if is_private_ip(IpAddr::V4(ip)) {
+                return Err(AppError::Internal(anyhow::anyhow!(
+                    "Webhook URL cannot target private IP addresses"
+                )));
+            }
+        }
+        url::Host::Ipv6(ip) => {
+            if is_private_ip(IpAddr::V6(ip)) {
+                return Err(AppError::Internal(anyhow::anyhow!(
+                    "Webhook URL cannot target private IP addresses"
+                )));
+            }
+        }
+        url::Host::Domain(domain) => {
+            if domain == "localhost" || domain.ends_with(".local") || domain.ends_with(".internal")
+            {
+                return Err(AppError::Internal(anyhow::anyhow!(
+                    "Webhook URL cannot target internal hostnames"
+                )));
+            }
+
+            let port = url.port_or_known_default().unwrap_or(443);
+            let addrs = tokio::time::timeout(
+                std::time::Duration::from_secs(DNS_LOOKUP_TIMEOUT_SECS),
+                tokio::net::lookup_host((domain, port)),
+            )
+            .await
+            .map_err(|_| AppError::Internal(anyhow::anyhow!("Webhook DNS lookup timed out")))?
+            .map_err(|e| {
+                AppError::Internal(anyhow::anyhow!("Failed to resolve webhook URL: {}", e))
+            })?;
+            for addr in addrs {
+                if is_private_ip(addr.ip()) {
+                    return Err(AppError::Internal(anyhow::anyhow!(
+                        "Webhook URL resolves to private IP addresses"
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn is_private_ip(ip: IpAddr) -> bool {
     match ip {
         IpAddr::V4(v4) => {
@@ -378,6 +393,27 @@ impl AuthCallback for WebhookCallback {
         self.send_webhook(&webhook_payload).await?;
         Ok(())
     }
+
+    async fn on_user_status_changed(
+        &self,
+        payload: &UserStatusChangePayload,
+    ) -> Result<(), AppError> {
+        let webhook_payload = WebhookPayload {
+            event: WebhookEvent::UserStatusChanged,
+            timestamp: Self::current_timestamp(),
+            data: WebhookData::Status(UserStatusWebhookData {
+                user_id: payload.user_id.to_string(),
+                status: payload.status.as_str().to_string(),
+                previous_status: payload.previous_status.as_str().to_string(),
+                reason: payload.reason.clone(),
+                expires_at: payload.expires_at.map(|t| t.to_rfc3339()),
+                changed_by: payload.changed_by.to_string(),
+            }),
+        };
+
+        self.send_webhook(&webhook_payload).await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -6,8 +6,9 @@ mod callback;
 mod types;
 mod verification;
 
-pub use callback::WebhookCallback;
+pub use callback::{sign_webhook_payload, validate_webhook_destination, WebhookCallback};
 pub use types::{
-    AuthWebhookData, LogoutWebhookData, WebhookConfig, WebhookData, WebhookEvent, WebhookPayload,
+    AuthWebhookData, LogoutWebhookData, UserStatusWebhookData, WebhookConfig, WebhookData,
+    WebhookEvent, WebhookPayload,
 };
 pub use verification::verify_signature;
@@ -5,12 +5,113 @@ use std::net::IpAddr;
 use url::Url;
 
 /// Webhook event types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The legacy single-URL `WebhookCallback` only sends the four user events.
+/// Endpoints managed via the admin API can subscribe to any of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum WebhookEvent {
     UserAuthenticated,
     UserRegistered,
     UserLogout,
+    UserStatusChanged,
+    OrgCreated,
+    OrgUpdated,
+    OrgDeleted,
+    MemberAdded,
+    MemberRoleChanged,
+    MemberRemoved,
+    InviteCreated,
+    InviteAccepted,
+    InviteRevoked,
+    MfaEnabled,
+    MfaDisabled,
+    CreditsSpent,
+    CreditsAdjusted,
+    CreditsGranted,
+    CreditsTransferred,
+    SubscriptionCreated,
+    SubscriptionRenewed,
+    SubscriptionPastDue,
+    SubscriptionCanceled,
+    DepositCompleted,
+    DepositReversed,
+    WalletCreated,
+    WalletRecovered,
+    SessionRevoked,
+}
+
+impl WebhookEvent {
+    /// Every event endpoints can subscribe to
+    pub const ALL: &'static [WebhookEvent] = &[
+        Self::UserAuthenticated,
+        Self::UserRegistered,
+        Self::UserLogout,
+        Self::UserStatusChanged,
+        Self::OrgCreated,
+        Self::OrgUpdated,
+        Self::OrgDeleted,
+        Self::MemberAdded,
+        Self::MemberRoleChanged,
+        Self::MemberRemoved,
+        Self::InviteCreated,
+        Self::InviteAccepted,
+        Self::InviteRevoked,
+        Self::MfaEnabled,
+        Self::MfaDisabled,
+        Self::CreditsSpent,
+        Self::CreditsAdjusted,
+        Self::CreditsGranted,
+        Self::CreditsTransferred,
+        Self::SubscriptionCreated,
+        Self::SubscriptionRenewed,
+        Self::SubscriptionPastDue,
+        Self::SubscriptionCanceled,
+        Self::DepositCompleted,
+        Self::DepositReversed,
+        Self::WalletCreated,
+        Self::WalletRecovered,
+        Self::SessionRevoked,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UserAuthenticated => "user_authenticated",
+            Self::UserRegistered => "user_registered",
+            Self::UserLogout => "user_logout",
+            Self::UserStatusChanged => "user_status_changed",
+            Self::OrgCreated => "org_created",
+            Self::OrgUpdated => "org_updated",
+            Self::OrgDeleted => "org_deleted",
+            Self::MemberAdded => "member_added",
+            Self::MemberRoleChanged => "member_role_changed",
+            Self::MemberRemoved => "member_removed",
+            Self::InviteCreated => "invite_created",
+            Self::InviteAccepted => "invite_accepted",
+            Self::InviteRevoked => "invite_revoked",
+            Self::MfaEnabled => "mfa_enabled",
+            Self::MfaDisabled => "mfa_disabled",
+            Self::CreditsSpent => "credits_spent",
+            Self::CreditsAdjusted => "credits_adjusted",
+            Self::CreditsGranted => "credits_granted",
+            Self::CreditsTransferred => "credits_transferred",
+            Self::SubscriptionCreated => "subscription_created",
+            Self::SubscriptionRenewed => "subscription_renewed",
+            Self::SubscriptionPastDue => "subscription_past_due",
+            Self::SubscriptionCanceled => "subscription_canceled",
+            Self::DepositCompleted => "deposit_completed",
+            Self::DepositReversed => "deposit_reversed",
+            Self::WalletCreated => "wallet_created",
+            Self::WalletRecovered => "wallet_recovered",
+            Self::SessionRevoked => "session_revoked",
+        }
+    }
+
+    /// Parse an event name (returns None for unknown events)
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|e| e.as_str() == s)
+    }
 }
 
 /// Webhook payload sent to the target URL
@@ -29,6 +130,8 @@ pub struct WebhookPayload {
 #[serde(untagged)]
 pub enum WebhookData {
     Auth(AuthWebhookData),
+    // Before `Logout`, which would otherwise match on `user_id` alone
+    Status(UserStatusWebhookData),
     Logout(LogoutWebhookData),
 }
 
@@ -52,6 +155,18 @@ pub struct LogoutWebhookData {
     pub user_id: String,
 }
 
+/// Data for account status change events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserStatusWebhookData {
+    pub user_id: String,
+    pub status: String,
+    pub previous_status: String,
+    pub reason: Option<String>,
+    /// RFC 3339 time the status lapses, if it is temporary
+    pub expires_at: Option<String>,
+    pub changed_by: String,
+}
+
 /// Configuration for webhook callbacks
 #[derive(Debug, Clone)]
 pub struct WebhookConfig {
@@ -243,6 +358,16 @@ mod tests {
         assert_eq!(logout, "\"user_logout\"");
     }
 
+    #[test]
+    fn test_webhook_event_catalog_names_match_serde() {
+        for event in WebhookEvent::ALL {
+            let json = serde_json::to_string(event).unwrap();
+            assert_eq!(json, format!("\"{}\"", event.as_str()));
+            assert_eq!(WebhookEvent::from_str(event.as_str()), Some(*event));
+        }
+        assert_eq!(WebhookEvent::from_str("nope"), None);
+    }
+
     #[test]
     fn test_auth_webhook_data_with_wallet() {
         let data = AuthWebhookData {
@@ -286,6 +411,28 @@ mod tests {
         assert_eq!(payload.timestamp, 1702400000);
     }
 
+    #[test]
+    fn test_status_payload_round_trips_as_status_data() {
+        let payload = WebhookPayload {
+            event: WebhookEvent::UserStatusChanged,
+            timestamp: 1702400000,
+            data: WebhookData::Status(UserStatusWebhookData {
+                user_id: "user-123".to_string(),
+                status: "suspended".to_string(),
+                previous_status: "active".to_string(),
+                reason: Some("chargeback".to_string()),
+                expires_at: None,
+                changed_by: "admin-1".to_string(),
+            }),
+        };
+
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(json.contains("\"user_status_changed\""));
+
+        let parsed: WebhookPayload = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed.data, WebhookData::Status(ref d) if d.status == "suspended"));
+    }
+
     #[test]
     fn test_webhook_config_creation() {
         let config = WebhookConfig {
@@ -177,6 +177,50 @@ pub fn load_rate_limit_config() -> RateLimitConfig {
     }
 }
 
+/// Load audit sink configuration from environment
+pub fn load_audit_sink_config() -> AuditSinkConfig {
+    AuditSinkConfig {
+        syslog_addr: std::env::var("AUDIT_SYSLOG_ADDR").ok(),
+        syslog_protocol: std::env::var("AUDIT_SYSLOG_PROTOCOL")
+            .map(|v| v.to_lowercase())
+            .unwrap_or_else(|_| default_syslog_protocol()),
+        app_name: std::env::var("AUDIT_SINK_APP_NAME")
+            .unwrap_or_else(|_| default_audit_sink_app_name()),
+        ndjson_path: std::env::var("AUDIT_NDJSON_PATH").ok(),
+        ndjson_max_bytes: parse_u64("AUDIT_NDJSON_MAX_BYTES", default_ndjson_max_bytes),
+        ndjson_max_files: parse_u32("AUDIT_NDJSON_MAX_FILES", default_ndjson_max_files),
+        otlp_endpoint: std::env::var("AUDIT_OTLP_ENDPOINT").ok(),
+        otlp_headers: std::env::var("AUDIT_OTLP_HEADERS").ok(),
+        poll_interval_secs: parse_u64("AUDIT_SINK_POLL_INTERVAL", default_audit_sink_poll_interval),
+        batch_size: parse_u32("AUDIT_SINK_BATCH_SIZE", default_audit_sink_batch_size),
+    }
+}
+
+/// Load Stripe top-up configuration from environment
+pub fn load_stripe_config() -> StripeConfig {
+    StripeConfig {
+        enabled: parse_bool("STRIPE_ENABLED", false),
+        secret_key: std::env::var("STRIPE_SECRET_KEY").ok(),
+        webhook_secret: std::env::var("STRIPE_WEBHOOK_SECRET").ok(),
+        api_base: std::env::var("STRIPE_API_BASE").unwrap_or_else(|_| default_stripe_api_base()),
+        currency: std::env::var("STRIPE_CURRENCY")
+            .map(|v| v.to_lowercase())
+            .unwrap_or_else(|_| default_stripe_currency()),
+        credit_currency: std::env::var("STRIPE_CREDIT_CURRENCY")
+            .map(|v| v.to_uppercase())
+            .unwrap_or_else(|_| default_stripe_credit_currency()),
+        credits_per_cent: parse_u64("STRIPE_CREDITS_PER_CENT", default_stripe_credits_per_cent),
+        min_amount_cents: parse_u64("STRIPE_MIN_AMOUNT_CENTS", default_stripe_min_amount_cents),
+        max_amount_cents: parse_u64("STRIPE_MAX_AMOUNT_CENTS", default_stripe_max_amount_cents),
+        success_url: std::env::var("STRIPE_SUCCESS_URL").ok(),
+        cancel_url: std::env::var("STRIPE_CANCEL_URL").ok(),
+        webhook_tolerance_secs: parse_u64(
+            "STRIPE_WEBHOOK_TOLERANCE_SECS",
+            default_stripe_webhook_tolerance,
+        ),
+    }
+}
+
 /// Load database configuration from environment
 pub fn load_database_config() -> DatabaseConfig {
     DatabaseConfig {
@@ -28,10 +28,15 @@ pub use privacy::{
 };
 pub use server::{default_auth_base_path, default_host, default_port, ServerConfig};
 pub use services::{
+    default_audit_sink_app_name, default_audit_sink_batch_size, default_audit_sink_poll_interval,
     default_auth_limit, default_credit_limit, default_environment, default_general_limit,
-    default_rate_limit_store, default_wallet_unlock_ttl, default_webhook_retries,
-    default_webhook_timeout, default_window_secs, NotificationConfig, RateLimitConfig, SsoConfig,
-    WalletConfig, WalletRecoveryMode, WebhookConfig,
+    default_ndjson_max_bytes, default_ndjson_max_files, default_rate_limit_store,
+    default_stripe_api_base, default_stripe_credit_currency, default_stripe_credits_per_cent,
+    default_stripe_currency, default_stripe_max_amount_cents, default_stripe_min_amount_cents,
+    default_stripe_webhook_tolerance, default_syslog_protocol, default_wallet_unlock_ttl,
+    default_webhook_retries, default_webhook_timeout, default_window_secs, AuditSinkConfig,
+    NotificationConfig, RateLimitConfig, SsoConfig, StripeConfig, WalletConfig, WalletRecoveryMode,
+    WebhookConfig,
 };
 pub use webauthn::{default_challenge_ttl, WebAuthnConfig};
 
@@ -73,6 +78,10 @@ pub struct Config {
     pub wallet: WalletConfig,
     #[serde(default)]
     pub privacy: PrivacyConfig,
+    #[serde(default)]
+    pub audit_sinks: AuditSinkConfig,
+    #[serde(default)]
+    pub stripe: StripeConfig,
 }
 
 /// Minimum recommended length for JWT secret
@@ -204,6 +213,46 @@ impl Config {
             }
         }
 
+        // Audit sinks need a known syslog transport and non-empty batches
+        if !matches!(self.audit_sinks.syslog_protocol.as_str(), "tcp" | "udp") {
+            return Err(AppError::Config(
+                "AUDIT_SYSLOG_PROTOCOL must be 'tcp' or 'udp'".into(),
+            ));
+        }
+        if self.audit_sinks.any_enabled()
+            && (self.audit_sinks.batch_size == 0 || self.audit_sinks.poll_interval_secs == 0)
+        {
+            return Err(AppError::Config(
+                "AUDIT_SINK_BATCH_SIZE and AUDIT_SINK_POLL_INTERVAL must be greater than 0".into(),
+            ));
+        }
+
+        // Stripe top-ups need API and webhook secrets and a sane amount range
+        if self.stripe.enabled {
+            if self.stripe.secret_key.is_none() {
+                return Err(AppError::Config(
+                    "STRIPE_SECRET_KEY is required when Stripe is enabled".into(),
+                ));
+            }
+            if self.stripe.webhook_secret.is_none() {
+                return Err(AppError::Config(
+                    "STRIPE_WEBHOOK_SECRET is required when Stripe is enabled".into(),
+                ));
+            }
+            if self.stripe.credits_per_cent == 0
+                || self.stripe.min_amount_cents == 0
+                || self.stripe.min_amount_cents > self.stripe.max_amount_cents
+            {
+                return Err(AppError::Config(
+                    "STRIPE_CREDITS_PER_CENT must be positive and STRIPE_MIN_AMOUNT_CENTS \
+                     must be between 1 and STRIPE_MAX_AMOUNT_CENTS"
+                        .into(),
+                ));
+            }
+            url::Url::parse(&self.stripe.api_base)
+                .map_err(|e| AppError::Config(format!("Invalid STRIPE_API_BASE: {}", e)))?;
+        }
+
         // Google requires client_id if enabled
         if self.google.enabled && self.google.client_id.is_none() {
             return Err(AppError::Config(
@@ -546,6 +595,8 @@ impl Config {
             sso: load_sso_config(),
             wallet: load_wallet_config(),
             privacy: load_privacy_config(),
+            audit_sinks: load_audit_sink_config(),
+            stripe: load_stripe_config(),
         };
 
         config.validate()?;
@@ -799,6 +850,8 @@ mod tests {
             sso: SsoConfig::default(),
             wallet: WalletConfig::default(),
             privacy: PrivacyConfig::default(),
+            audit_sinks: AuditSinkConfig::default(),
+            stripe: StripeConfig::default(),
         }
     }
 
@@ -1,4 +1,5 @@
-//! Service configurations (webhook, rate limiting, notifications)
+//! Service configurations (webhook, rate limiting, notifications, audit sinks,
+//! Stripe)
 
 use serde::Deserialize;
 
@@ -265,6 +266,180 @@ impl Default for WalletConfig {
     }
 }
 
+/// Audit log streaming to external collectors (SIEM)
+///
+/// Each configured sink is fed from the audit hash chain and keeps its own
+/// cursor, so entries written while a collector is down are sent once it
+/// recovers.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditSinkConfig {
+    /// RFC 5424 syslog collector as `host:port`
+    pub syslog_addr: Option<String>,
+    /// Syslog transport: "tcp" (octet-counted framing) or "udp"
+    #[serde(default = "default_syslog_protocol")]
+    pub syslog_protocol: String,
+    /// Application name reported to collectors (syslog APP-NAME, OTLP
+    /// `service.name`)
+    #[serde(default = "default_audit_sink_app_name")]
+    pub app_name: String,
+    /// Path of the NDJSON file; rotated files get a `.1`, `.2`, ... suffix
+    pub ndjson_path: Option<String>,
+    /// Size at which the NDJSON file is rotated (bytes)
+    #[serde(default = "default_ndjson_max_bytes")]
+    pub ndjson_max_bytes: u64,
+    /// Rotated NDJSON files to keep
+    #[serde(default = "default_ndjson_max_files")]
+    pub ndjson_max_files: u32,
+    /// OTLP/HTTP collector base URL; logs are POSTed to `{endpoint}/v1/logs`
+    pub otlp_endpoint: Option<String>,
+    /// Extra OTLP request headers as `key=value,key2=value2`
+    pub otlp_headers: Option<String>,
+    /// How often sinks poll for new entries when not woken by a write (seconds)
+    #[serde(default = "default_audit_sink_poll_interval")]
+    pub poll_interval_secs: u64,
+    /// Maximum entries sent per batch
+    #[serde(default = "default_audit_sink_batch_size")]
+    pub batch_size: u32,
+}
+
+pub fn default_syslog_protocol() -> String {
+    "tcp".to_string()
+}
+
+pub fn default_audit_sink_app_name() -> String {
+    "cedros-login".to_string()
+}
+
+pub fn default_ndjson_max_bytes() -> u64 {
+    100 * 1024 * 1024 // 100 MiB
+}
+
+pub fn default_ndjson_max_files() -> u32 {
+    5
+}
+
+pub fn default_audit_sink_poll_interval() -> u64 {
+    5
+}
+
+pub fn default_audit_sink_batch_size() -> u32 {
+    500
+}
+
+impl Default for AuditSinkConfig {
+    fn default() -> Self {
+        Self {
+            syslog_addr: None,
+            syslog_protocol: default_syslog_protocol(),
+            app_name: default_audit_sink_app_name(),
+            ndjson_path: None,
+            ndjson_max_bytes: default_ndjson_max_bytes(),
+            ndjson_max_files: default_ndjson_max_files(),
+            otlp_endpoint: None,
+            otlp_headers: None,
+            poll_interval_secs: default_audit_sink_poll_interval(),
+            batch_size: default_audit_sink_batch_size(),
+        }
+    }
+}
+
+impl AuditSinkConfig {
+    /// Check if any sink is configured
+    pub fn any_enabled(&self) -> bool {
+        self.syslog_addr.is_some() || self.ndjson_path.is_some() || self.otlp_endpoint.is_some()
+    }
+}
+
+/// Stripe card top-ups of credit balances
+///
+/// Payments are taken in `currency` and credited at a fixed rate of
+/// `credits_per_cent` lamports of `credit_currency` per minor unit (cent).
+/// Point `api_base` at stripe-mock (e.g. `http://localhost:12111`) to test
+/// locally.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeConfig {
+    /// Enable Stripe top-ups and the Stripe webhook
+    #[serde(default)]
+    pub enabled: bool,
+    /// Secret API key (`sk_live_...` / `sk_test_...`)
+    pub secret_key: Option<String>,
+    /// Signing secret of the webhook endpoint (`whsec_...`)
+    pub webhook_secret: Option<String>,
+    /// Stripe API base URL
+    #[serde(default = "default_stripe_api_base")]
+    pub api_base: String,
+    /// Payment currency (ISO code, lowercase)
+    #[serde(default = "default_stripe_currency")]
+    pub currency: String,
+    /// Credit currency top-ups are credited in
+    #[serde(default = "default_stripe_credit_currency")]
+    pub credit_currency: String,
+    /// Lamports of `credit_currency` credited per paid cent
+    #[serde(default = "default_stripe_credits_per_cent")]
+    pub credits_per_cent: u64,
+    /// Smallest top-up in cents
+    #[serde(default = "default_stripe_min_amount_cents")]
+    pub min_amount_cents: u64,
+    /// Largest top-up in cents
+    #[serde(default = "default_stripe_max_amount_cents")]
+    pub max_amount_cents: u64,
+    /// Where Checkout redirects after payment; `{CHECKOUT_SESSION_ID}` is
+    /// replaced by Stripe
+    pub success_url: Option<String>,
+    /// Where Checkout redirects when the payment is abandoned
+    pub cancel_url: Option<String>,
+    /// Maximum age of a signed webhook event (seconds)
+    #[serde(default = "default_stripe_webhook_tolerance")]
+    pub webhook_tolerance_secs: u64,
+}
+
+pub fn default_stripe_api_base() -> String {
+    "https://api.stripe.com".to_string()
+}
+
+pub fn default_stripe_currency() -> String {
+    "usd".to_string()
+}
+
+pub fn default_stripe_credit_currency() -> String {
+    "USD".to_string()
+}
+
+pub fn default_stripe_credits_per_cent() -> u64 {
+    10_000 // USD credits have 6 decimals
+}
+
+pub fn default_stripe_min_amount_cents() -> u64 {
+    100
+}
+
+pub fn default_stripe_max_amount_cents() -> u64 {
+    1_000_000
+}
+
+pub fn default_stripe_webhook_tolerance() -> u64 {
+    300
+}
+
+impl Default for StripeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            secret_key: None,
+            webhook_secret: None,
+            api_base: default_stripe_api_base(),
+            currency: default_stripe_currency(),
+            credit_currency: default_stripe_credit_currency(),
+            credits_per_cent: default_stripe_credits_per_cent(),
+            min_amount_cents: default_stripe_min_amount_cents(),
+            max_amount_cents: default_stripe_max_amount_cents(),
+            success_url: None,
+            cancel_url: None,
+            webhook_tolerance_secs: default_stripe_webhook_tolerance(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,4 +545,13 @@ mod tests {
         assert!(WalletRecoveryMode::FullSeed.has_recovery());
         assert!(!WalletRecoveryMode::None.has_recovery());
     }
+
+    #[test]
+    fn test_audit_sink_config_defaults() {
+        let config = AuditSinkConfig::default();
+        assert!(!config.any_enabled());
+        assert_eq!(config.syslog_protocol, "tcp");
+        assert_eq!(config.ndjson_max_files, 5);
+        assert_eq!(config.batch_size, 500);
+    }
 }
@@ -34,6 +34,15 @@ pub enum AppError {
     #[error("Account locked: {0}")]
     AccountLocked(String),
 
+    #[error("Account suspended: {0}")]
+    AccountSuspended(String),
+
+    #[error("Account banned: {0}")]
+    AccountBanned(String),
+
+    #[error("Account pending deletion: {0}")]
+    AccountPendingDeletion(String),
+
     #[error("Email already exists")]
     EmailExists,
 
@@ -88,6 +97,9 @@ pub enum AppError {
 
     #[error("Disposable email addresses are not allowed")]
     DisposableEmailBlocked,
+
+    #[error("Budget exceeded: {0}")]
+    BudgetExceeded(String),
 }
 
 /// Error code for API responses
@@ -96,6 +108,9 @@ pub enum AppError {
 pub enum ErrorCode {
     InvalidCredentials,
     AccountLocked,
+    AccountSuspended,
+    AccountBanned,
+    AccountPendingDeletion,
     EmailExists,
     WalletExists,
     InvalidToken,
@@ -109,6 +124,7 @@ pub enum ErrorCode {
     Unauthorized,
     StepUpRequired,
     DisposableEmailBlocked,
+    BudgetExceeded,
     ServiceUnavailable,
     ServerError,
 }
@@ -135,6 +151,19 @@ impl IntoResponse for AppError {
                 ErrorCode::AccountLocked,
                 msg.clone(),
             ),
+            AppError::AccountSuspended(msg) => (
+                StatusCode::FORBIDDEN,
+                ErrorCode::AccountSuspended,
+                msg.clone(),
+            ),
+            AppError::AccountBanned(msg) => {
+                (StatusCode::FORBIDDEN, ErrorCode::AccountBanned, msg.clone())
+            }
+            AppError::AccountPendingDeletion(msg) => (
+                StatusCode::FORBIDDEN,
+                ErrorCode::AccountPendingDeletion,
+                msg.clone(),
+            ),
             AppError::EmailExists => (
                 StatusCode::CONFLICT,
                 ErrorCode::EmailExists,
@@ -202,6 +231,11 @@ impl IntoResponse for AppError {
                 ErrorCode::DisposableEmailBlocked,
                 self.to_string(),
             ),
+            AppError::BudgetExceeded(msg) => (
+                StatusCode::FORBIDDEN,
+                ErrorCode::BudgetExceeded,
+                msg.clone(),
+            ),
             AppError::Internal(err) => {
                 // Debug-only detail: avoid exposing sensitive data at higher log levels.
                 tracing::debug!(error = %err, "Internal error detail");
@@ -338,6 +372,30 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_account_status_errors_are_forbidden_with_distinct_codes() {
+        for (error, code) in [
+            (
+                AppError::AccountSuspended("Account is suspended".into()),
+                "ACCOUNT_SUSPENDED",
+            ),
+            (
+                AppError::AccountBanned("Account is banned".into()),
+                "ACCOUNT_BANNED",
+            ),
+            (
+                AppError::AccountPendingDeletion("Account is scheduled for deletion".into()),
+                "ACCOUNT_PENDING_DELETION",
+            ),
+        ] {
+            let response = error.into_response();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let body_str = std::str::from_utf8(&body).unwrap();
+            assert!(body_str.contains(&format!("\"code\":\"{}\"", code)));
+        }
+    }
+
     #[test]
     fn test_app_error_from_io_error() {
         let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -359,6 +417,7 @@ mod tests {
             ErrorCode::ValidationError,
             ErrorCode::RateLimited,
             ErrorCode::DisposableEmailBlocked,
+            ErrorCode::BudgetExceeded,
             ErrorCode::ServerError,
         ];
 
@@ -0,0 +1,466 @@
+//! Self-service account deletion and data export
+//!
+//! `DELETE /me` schedules the caller's account for deletion. The account is
+//! locked immediately (sessions revoked, sign-in refused with
+//! `ACCOUNT_PENDING_DELETION`) and the user purge worker removes its personal
+//! data once `account_deletion_grace_days` have passed. Until then a system
+//! admin can restore it by setting the status back to `active`.
+//!
+//! `GET /me/export` returns a ZIP of everything stored about the caller.
+
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{Cursor, Write};
+use std::sync::Arc;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::callback::{AuthCallback, UserStatusChangePayload};
+use crate::errors::AppError;
+use crate::handlers::auth::call_status_changed_callback_with_timeout;
+use crate::handlers::credentials::CredentialResponse;
+use crate::models::{
+    AuditLogResponse, CreditTransactionResponse, DeleteAccountRequest, DeleteAccountResponse,
+    DepositItemResponse, OrgResponse, SessionResponse,
+};
+use crate::repositories::{AuditEventType, AuditLogQuery, OrgRole, UserStatus};
+use crate::services::{CreditHistoryItem, EmailService, DEFAULT_ACCOUNT_DELETION_GRACE_DAYS};
+use crate::utils::{authenticate, user_entity_to_auth_user};
+use crate::AppState;
+
+/// Page size used when collecting export data
+const EXPORT_PAGE_SIZE: u32 = 100;
+
+/// Upper bound on rows exported per file
+const MAX_EXPORT_ROWS: usize = 10_000;
+
+/// Status reason recorded on self-service deletions
+const DELETION_REASON: &str = "self_service";
+
+/// Session revocation reason recorded when deletion is requested
+const REVOKE_REASON_DELETED: &str = "account_deletion_requested";
+
+/// DELETE /me - Schedule the caller's account for deletion
+///
+/// Requires recent strong authentication (step-up). Refused unless the request
+/// acknowledges each consequence that applies: sole-owned organizations are
+/// deleted with the account, remaining credits are forfeited and embedded
+/// wallet keys are destroyed.
+pub async fn delete_account<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    body: Option<Json<DeleteAccountRequest>>,
+) -> Result<Json<DeleteAccountResponse>, AppError> {
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+
+    let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("delete the account")?;
+    let session_id = match (auth.is_api_key_auth, auth.session_id) {
+        (false, Some(session_id)) => session_id,
+        _ => {
+            return Err(AppError::Forbidden(
+                "Account deletion requires an interactive session".into(),
+            ))
+        }
+    };
+    state.step_up_service.require_step_up(session_id).await?;
+
+    let user = state
+        .user_repo
+        .find_by_id(auth.user_id)
+        .await?
+        .ok_or(AppError::InvalidToken)?;
+    if user.is_system_admin {
+        return Err(AppError::Validation(
+            "System admins cannot delete their own account. Remove admin status first.".into(),
+        ));
+    }
+
+    // Credits: a debt must be settled, a positive balance explicitly forfeited,
+    // and holds must settle first so no capture lands after the purge.
+    let balances = state.credit_repo.get_all_balances(user.id).await?;
+    if balances.iter().any(|b| b.balance < 0) {
+        return Err(AppError::Validation(
+            "Settle your outstanding credit balance before deleting your account".into(),
+        ));
+    }
+    if balances.iter().any(|b| b.held_balance > 0) {
+        return Err(AppError::Validation(
+            "Wait for pending credit holds to settle before deleting your account".into(),
+        ));
+    }
+    if balances.iter().any(|b| b.balance > 0) && !req.forfeit_credits {
+        return Err(AppError::Validation(
+            "Your remaining credits will be forfeited. Set forfeitCredits to confirm.".into(),
+        ));
+    }
+
+    if state.wallet_material_repo.exists_for_user(user.id).await? && !req.delete_wallet {
+        return Err(AppError::Validation(
+            "Your embedded wallet's keys will be destroyed and any funds in it lost. \
+             Move funds out, then set deleteWallet to confirm."
+                .into(),
+        ));
+    }
+
+    // Organizations: the personal org goes with the account; any other org the
+    // caller is the only owner of must be explicitly deleted too.
+    let mut orgs_to_delete = Vec::new();
+    let mut sole_owned = Vec::new();
+    for membership in state.membership_repo.find_by_user(user.id).await? {
+        if membership.role != OrgRole::Owner {
+            continue;
+        }
+        let Some(org) = state.org_repo.find_by_id(membership.org_id).await? else {
+            continue;
+        };
+        if org.deleted_at.is_some() {
+            continue;
+        }
+        if org.is_personal {
+            orgs_to_delete.push(org.id);
+        } else if state.membership_repo.count_owners(org.id).await? <= 1 {
+            sole_owned.push(org);
+        }
+    }
+    if !sole_owned.is_empty() && !req.delete_owned_orgs {
+        let names: Vec<&str> = sole_owned.iter().map(|o| o.name.as_str()).collect();
+        return Err(AppError::Validation(format!(
+            "You are the only owner of: {}. Transfer ownership, or set deleteOwnedOrgs to \
+             delete them with your account.",
+            names.join(", ")
+        )));
+    }
+    orgs_to_delete.extend(sole_owned.iter().map(|o| o.id));
+
+    let grace_days = state
+        .settings_service
+        .get_u32("account_deletion_grace_days")
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(DEFAULT_ACCOUNT_DELETION_GRACE_DAYS);
+    let purge_after = Utc::now() + Duration::days(grace_days as i64);
+
+    let previous_status = user.effective_status();
+    state
+        .user_repo
+        .set_status(
+            user.id,
+            UserStatus::PendingDeletion,
+            Some(DELETION_REASON.to_string()),
+            Some(purge_after),
+        )
+        .await?;
+    state
+        .session_repo
+        .revoke_all_for_user_with_reason(user.id, REVOKE_REASON_DELETED)
+        .await?;
+
+    for org_id in &orgs_to_delete {
+        state
+            .org_repo
+            .schedule_deletion(*org_id, purge_after)
+            .await?;
+        let _ = state
+            .audit_service
+            .log_org_event(AuditEventType::OrgDeleted, user.id, *org_id, Some(&headers))
+            .await;
+    }
+
+    state
+        .audit_service
+        .log_user_event_or_warn(
+            AuditEventType::UserDeletionRequested,
+            user.id,
+            Some(&headers),
+        )
+        .await;
+
+    call_status_changed_callback_with_timeout(
+        &state.callback,
+        &UserStatusChangePayload {
+            user_id: user.id,
+            status: UserStatus::PendingDeletion,
+            previous_status,
+            reason: Some(DELETION_REASON.to_string()),
+            expires_at: Some(purge_after),
+            changed_by: user.id,
+        },
+    )
+    .await;
+
+    Ok(Json(DeleteAccountResponse {
+        message: format!(
+            "Account scheduled for deletion; it will be permanently removed after {}",
+            purge_after.to_rfc3339()
+        ),
+        purge_after,
+        deleted_org_ids: orgs_to_delete,
+    }))
+}
+
+/// Identities linked to the account
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportIdentities {
+    auth_methods: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    google_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    apple_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wallet_address: Option<String>,
+    credentials: Vec<CredentialResponse>,
+    passkeys: Vec<ExportPasskey>,
+}
+
+/// A registered passkey, without its public key material
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportPasskey {
+    id: Uuid,
+    label: Option<String>,
+    created_at: chrono::DateTime<Utc>,
+    last_used_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// GET /me/export - Download the caller's data as a ZIP archive
+///
+/// The archive holds one JSON file each for the profile, linked identities,
+/// sessions, audit entries, org memberships, credit transactions and deposits.
+pub async fn export_account_data<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("export account data")?;
+    let user_id = auth.user_id;
+
+    let user = state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or(AppError::InvalidToken)?;
+
+    let credentials = state
+        .credential_repo
+        .find_by_user(user_id)
+        .await?
+        .into_iter()
+        .map(CredentialResponse::from)
+        .collect();
+    let passkeys = state
+        .webauthn_repo
+        .find_by_user(user_id)
+        .await?
+        .into_iter()
+        .map(|p| ExportPasskey {
+            id: p.id,
+            label: p.label,
+            created_at: p.created_at,
+            last_used_at: p.last_used_at,
+        })
+        .collect();
+    let identities = ExportIdentities {
+        auth_methods: user
+            .auth_methods
+            .iter()
+            .map(|m| m.as_str().to_string())
+            .collect(),
+        google_id: user.google_id.clone(),
+        apple_id: user.apple_id.clone(),
+        wallet_address: user.wallet_address.clone(),
+        credentials,
+        passkeys,
+    };
+
+    let current_session = auth.session_id.unwrap_or_default();
+    let sessions: Vec<SessionResponse> = state
+        .session_repo
+        .find_by_user_id(user_id)
+        .await?
+        .iter()
+        .take(MAX_EXPORT_ROWS)
+        .map(|s| SessionResponse::from_entity(s, current_session))
+        .collect();
+
+    let mut memberships = Vec::new();
+    for membership in state.membership_repo.find_by_user(user_id).await? {
+        if let Some(org) = state.org_repo.find_by_id(membership.org_id).await? {
+            memberships.push(OrgResponse::from_entity(&org, membership.role));
+        }
+    }
+
+    let audit = collect_audit_entries(&state, user_id).await?;
+
+    let mut transactions: Vec<CreditTransactionResponse> = Vec::new();
+    loop {
+        let page = state
+            .credit_repo
+            .get_transactions(
+                user_id,
+                None,
+                None,
+                EXPORT_PAGE_SIZE,
+                transactions.len() as u32,
+            )
+            .await?;
+        let done = page.len() < EXPORT_PAGE_SIZE as usize;
+        transactions.extend(
+            page.into_iter()
+                .map(|tx| CreditTransactionResponse::from(CreditHistoryItem::from(tx))),
+        );
+        if done || transactions.len() >= MAX_EXPORT_ROWS {
+            break;
+        }
+    }
+
+    let mut deposits: Vec<DepositItemResponse> = Vec::new();
+    loop {
+        let page = state
+            .deposit_repo
+            .list_by_user(user_id, None, EXPORT_PAGE_SIZE, deposits.len() as u32)
+            .await?;
+        let done = page.len() < EXPORT_PAGE_SIZE as usize;
+        deposits.extend(page.iter().map(DepositItemResponse::from));
+        if done || deposits.len() >= MAX_EXPORT_ROWS {
+            break;
+        }
+    }
+
+    let files = [
+        ("profile.json", to_json(&user_entity_to_auth_user(&user))?),
+        ("identities.json", to_json(&identities)?),
+        ("sessions.json", to_json(&sessions)?),
+        ("audit.json", to_json(&audit)?),
+        ("memberships.json", to_json(&memberships)?),
+        ("credit_transactions.json", to_json(&transactions)?),
+        ("deposits.json", to_json(&deposits)?),
+    ];
+    let archive = build_zip(&files)?;
+
+    state
+        .audit_service
+        .log_user_event_or_warn(AuditEventType::UserDataExported, user_id, Some(&headers))
+        .await;
+
+    let disposition = format!(
+        "attachment; filename=\"account-export-{}.zip\"",
+        Utc::now().format("%Y%m%d")
+    );
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        archive,
+    )
+        .into_response())
+}
+
+/// Audit entries the user performed or that targeted them, newest first
+async fn collect_audit_entries<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    user_id: Uuid,
+) -> Result<Vec<AuditLogResponse>, AppError> {
+    let filters = [
+        AuditLogQuery {
+            actor_user_id: Some(user_id),
+            ..Default::default()
+        },
+        AuditLogQuery {
+            target_type: Some("user".to_string()),
+            target_id: Some(user_id),
+            ..Default::default()
+        },
+    ];
+
+    let mut entries = HashMap::new();
+    for filter in filters {
+        let mut fetched = 0;
+        loop {
+            let page = state
+                .audit_repo
+                .query(AuditLogQuery {
+                    limit: Some(EXPORT_PAGE_SIZE),
+                    offset: Some(fetched),
+                    ..filter.clone()
+                })
+                .await?;
+            fetched += page.len() as u32;
+            let done = page.len() < EXPORT_PAGE_SIZE as usize;
+            for entry in page {
+                entries.insert(entry.id, entry);
+            }
+            if done || fetched as usize >= MAX_EXPORT_ROWS {
+                break;
+            }
+        }
+    }
+
+    let mut entries: Vec<_> = entries.into_values().collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.created_at));
+    Ok(entries.iter().map(AuditLogResponse::from).collect())
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>, AppError> {
+    serde_json::to_vec_pretty(value).map_err(|e| AppError::Internal(e.into()))
+}
+
+/// Write the export files into an in-memory ZIP archive
+fn build_zip(files: &[(&str, Vec<u8>)]) -> Result<Vec<u8>, AppError> {
+    let zip_error = |e: zip::result::ZipError| {
+        AppError::Internal(anyhow::anyhow!("Failed to build export archive: {}", e))
+    };
+
+    let mut buffer = Cursor::new(Vec::new());
+    {
+        let mut zip = ZipWriter::new(&mut buffer);
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(0o600);
+        for (name, content) in files {
+            zip.start_file(*name, options).map_err(zip_error)?;
+            zip.write_all(content)
+                .map_err(|e| AppError::Internal(e.into()))?;
+        }
+        zip.finish().map_err(zip_error)?;
+    }
+    Ok(buffer.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_build_zip_contains_each_file() {
+        let files = [
+            ("profile.json", b"{\"id\":1}".to_vec()),
+            ("sessions.json", b"[]".to_vec()),
+        ];
+        let bytes = build_zip(&files).unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut profile = String::new();
+        archive
+            .by_name("profile.json")
+            .unwrap()
+            .read_to_string(&mut profile)
+            .unwrap();
+        assert_eq!(profile, "{\"id\":1}");
+    }
+}
@@ -17,12 +17,16 @@ use uuid::Uuid;
 
 use crate::callback::AuthCallback;
 use crate::errors::AppError;
-use crate::models::{AuditLogQueryParams, AuditLogResponse, ListAuditLogsResponse};
+use crate::models::{
+    AuditLogQueryParams, AuditLogResponse, ListAuditLogsResponse, VerifyAuditChainParams,
+};
 use crate::repositories::OrgRole;
-use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::services::{AuditChainReport, AuditChainService, EmailService};
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
+use super::users::validate_system_admin;
+
 const MAX_LIMIT: u32 = 200;
 
 /// GET /orgs/:org_id/audit - Get audit logs for an organization
@@ -35,7 +39,7 @@ pub async fn get_org_audit_logs<C: AuthCallback, E: EmailService>(
     Query(params): Query<AuditLogQueryParams>,
 ) -> Result<Json<ListAuditLogsResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Check membership - must be admin+ to view audit logs
     let membership = state
@@ -73,21 +77,7 @@ pub async fn get_system_audit_logs<C: AuthCallback, E: EmailService>(
     headers: HeaderMap,
     Query(params): Query<AuditLogQueryParams>,
 ) -> Result<Json<ListAuditLogsResponse>, AppError> {
-    // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
-
-    // Check if user is system admin
-    let user = state
-        .user_repo
-        .find_by_id(auth.user_id)
-        .await?
-        .ok_or(AppError::InvalidToken)?;
-
-    if !user.is_system_admin {
-        return Err(AppError::Forbidden(
-            "Only system administrators can view system audit logs".into(),
-        ));
-    }
+    validate_system_admin(&state, &headers).await?;
 
     // P-02: Query audit logs and count in single database operation
     let mut query = params.to_query(None);
@@ -102,6 +92,31 @@ pub async fn get_system_audit_logs<C: AuthCallback, E: EmailService>(
     }))
 }
 
+/// GET /admin/audit/verify - Verify the audit hash chain
+///
+/// Walks the global chain (or one org's chain with `orgId`), checks every
+/// signed checkpoint, and reports the first missing or modified entry.
+/// Requires system admin privileges.
+pub async fn verify_audit_chain<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Query(params): Query<VerifyAuditChainParams>,
+) -> Result<Json<AuditChainReport>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    let service = AuditChainService::new(state.audit_repo.clone(), &state.config.jwt.secret);
+    let report = service.verify(params.org_id).await?;
+    if let Some(broken) = &report.broken_link {
+        tracing::warn!(
+            seq = broken.seq,
+            reason = %broken.reason,
+            "Audit chain verification failed"
+        );
+    }
+
+    Ok(Json(report))
+}
+
 fn clamp_limit(limit: Option<u32>) -> Option<u32> {
     limit.map(|value| value.min(MAX_LIMIT))
 }
@@ -0,0 +1,372 @@
+//! Admin spending budget handlers
+//!
+//! GET    /admin/credits/budgets        - List budgets with current usage
+//! PUT    /admin/credits/budgets        - Create or update a budget
+//! DELETE /admin/credits/budgets/{id}   - Delete a budget
+//!
+//! A budget is identified by its subject (user, org member or API key),
+//! currency and period; setting it again replaces the limits.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::repositories::{
+    AuditEventType, AuditLogBuilder, BudgetUsageKey, CreditBudgetEntity, CreditBudgetPeriod,
+    CreditBudgetScope,
+};
+use crate::services::EmailService;
+use crate::utils::{extract_client_ip, validate_currency};
+use crate::AppState;
+
+use super::users::validate_system_admin;
+
+/// Spending budget response
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditBudgetResponse {
+    pub id: Uuid,
+    pub scope: CreditBudgetScope,
+    pub user_id: Option<Uuid>,
+    pub org_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+    pub currency: String,
+    pub period: CreditBudgetPeriod,
+    pub soft_limit_lamports: Option<i64>,
+    pub hard_limit_lamports: Option<i64>,
+    /// Start of the current period
+    pub period_start: DateTime<Utc>,
+    /// Spent or held in the current period
+    pub used_lamports: i64,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CreditBudgetResponse {
+    fn new(budget: CreditBudgetEntity, period_start: DateTime<Utc>, used: i64) -> Self {
+        Self {
+            id: budget.id,
+            scope: budget.scope,
+            user_id: budget.user_id,
+            org_id: budget.org_id,
+            api_key_id: budget.api_key_id,
+            currency: budget.currency,
+            period: budget.period,
+            soft_limit_lamports: budget.soft_limit,
+            hard_limit_lamports: budget.hard_limit,
+            period_start,
+            used_lamports: used,
+            created_by: budget.created_by,
+            created_at: budget.created_at,
+            updated_at: budget.updated_at,
+        }
+    }
+}
+
+/// Response for listing budgets
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCreditBudgetsResponse {
+    pub budgets: Vec<CreditBudgetResponse>,
+}
+
+/// Query parameters for listing budgets (filters combine)
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditBudgetsQueryParams {
+    pub user_id: Option<Uuid>,
+    pub org_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+}
+
+/// Request to create or update a budget
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCreditBudgetRequest {
+    pub scope: CreditBudgetScope,
+    /// Required for `user` and `org_member`
+    pub user_id: Option<Uuid>,
+    /// Required for `org_member`
+    pub org_id: Option<Uuid>,
+    /// Required for `api_key`
+    pub api_key_id: Option<Uuid>,
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    pub period: CreditBudgetPeriod,
+    /// Usage at which a `credit.budget_threshold` notification is queued
+    pub soft_limit_lamports: Option<i64>,
+    /// Usage that spends and holds may not exceed
+    pub hard_limit_lamports: Option<i64>,
+}
+
+fn default_currency() -> String {
+    "SOL".to_string()
+}
+
+/// Response for delete operations
+#[derive(Debug, Serialize)]
+pub struct DeleteResponse {
+    pub success: bool,
+}
+
+/// Check that the request names exactly the subject its scope needs
+fn validate_subject(request: &SetCreditBudgetRequest) -> Result<(), AppError> {
+    let valid = match request.scope {
+        CreditBudgetScope::User => {
+            request.user_id.is_some() && request.org_id.is_none() && request.api_key_id.is_none()
+        }
+        CreditBudgetScope::OrgMember => {
+            request.user_id.is_some() && request.org_id.is_some() && request.api_key_id.is_none()
+        }
+        CreditBudgetScope::ApiKey => {
+            request.api_key_id.is_some() && request.user_id.is_none() && request.org_id.is_none()
+        }
+    };
+    if !valid {
+        return Err(AppError::Validation(
+            "user budgets need userId, org_member budgets userId and orgId, api_key budgets apiKeyId"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_limits(soft: Option<i64>, hard: Option<i64>) -> Result<(), AppError> {
+    if soft.is_none() && hard.is_none() {
+        return Err(AppError::Validation(
+            "softLimitLamports or hardLimitLamports is required".into(),
+        ));
+    }
+    if soft.is_some_and(|s| s <= 0) || hard.is_some_and(|h| h <= 0) {
+        return Err(AppError::Validation(
+            "Budget limits must be positive".into(),
+        ));
+    }
+    if let (Some(soft), Some(hard)) = (soft, hard) {
+        if soft > hard {
+            return Err(AppError::Validation(
+                "softLimitLamports cannot exceed hardLimitLamports".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn with_usage<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    budget: CreditBudgetEntity,
+) -> Result<CreditBudgetResponse, AppError> {
+    let period_start = budget.period.start(Utc::now());
+    let used = state
+        .storage
+        .credit_budget_repo
+        .get_usage(BudgetUsageKey {
+            budget_id: budget.id,
+            period_start,
+        })
+        .await?;
+    Ok(CreditBudgetResponse::new(budget, period_start, used))
+}
+
+async fn audit<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    admin_id: Uuid,
+    budget_id: Uuid,
+    metadata: serde_json::Value,
+) {
+    let mut builder = AuditLogBuilder::new(AuditEventType::CreditBudgetUpdated)
+        .actor(admin_id)
+        .target("credit_budget", budget_id)
+        .metadata(metadata);
+    if let Some(ip) = extract_client_ip(headers, state.config.server.trust_proxy) {
+        builder = builder.ip(&ip);
+    }
+    if let Some(ua) = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+    {
+        builder = builder.user_agent(ua);
+    }
+    state.audit_service.log_or_warn(builder.build()).await;
+}
+
+/// GET /admin/credits/budgets - List budgets with their current usage
+pub async fn list_credit_budgets<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Query(params): Query<CreditBudgetsQueryParams>,
+) -> Result<Json<ListCreditBudgetsResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    let budgets = state
+        .storage
+        .credit_budget_repo
+        .list(params.user_id, params.org_id, params.api_key_id)
+        .await?;
+
+    let mut responses = Vec::with_capacity(budgets.len());
+    for budget in budgets {
+        responses.push(with_usage(&state, budget).await?);
+    }
+
+    Ok(Json(ListCreditBudgetsResponse { budgets: responses }))
+}
+
+/// PUT /admin/credits/budgets - Create or update a budget
+///
+/// New limits apply to the current period's usage right away.
+pub async fn set_credit_budget<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(request): Json<SetCreditBudgetRequest>,
+) -> Result<Json<CreditBudgetResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    validate_subject(&request)?;
+    validate_limits(request.soft_limit_lamports, request.hard_limit_lamports)?;
+    // SRV-14: Validate currency against whitelist
+    validate_currency(&request.currency)?;
+
+    if let Some(user_id) = request.user_id {
+        state
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+    }
+    if let Some(org_id) = request.org_id {
+        state
+            .org_repo
+            .find_by_id(org_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Organization not found".into()))?;
+    }
+
+    let now = Utc::now();
+    let budget = state
+        .storage
+        .credit_budget_repo
+        .upsert(CreditBudgetEntity {
+            id: Uuid::new_v4(),
+            scope: request.scope,
+            user_id: request.user_id,
+            org_id: request.org_id,
+            api_key_id: request.api_key_id,
+            currency: request.currency.to_uppercase(),
+            period: request.period,
+            soft_limit: request.soft_limit_lamports,
+            hard_limit: request.hard_limit_lamports,
+            created_by: Some(admin_id),
+            created_at: now,
+            updated_at: now,
+        })
+        .await?;
+
+    audit(
+        &state,
+        &headers,
+        admin_id,
+        budget.id,
+        serde_json::json!({
+            "scope": budget.scope.as_str(),
+            "userId": budget.user_id,
+            "orgId": budget.org_id,
+            "apiKeyId": budget.api_key_id,
+            "currency": budget.currency,
+            "period": budget.period.as_str(),
+            "softLimitLamports": budget.soft_limit,
+            "hardLimitLamports": budget.hard_limit,
+        }),
+    )
+    .await;
+
+    tracing::info!(
+        admin_id = %admin_id,
+        budget_id = %budget.id,
+        scope = budget.scope.as_str(),
+        period = budget.period.as_str(),
+        soft_limit = ?budget.soft_limit,
+        hard_limit = ?budget.hard_limit,
+        "Admin set credit budget"
+    );
+
+    Ok(Json(with_usage(&state, budget).await?))
+}
+
+/// DELETE /admin/credits/budgets/{id} - Delete a budget
+pub async fn delete_credit_budget<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<DeleteResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    if !state.storage.credit_budget_repo.delete(id).await? {
+        return Err(AppError::NotFound("Budget not found".into()));
+    }
+
+    audit(
+        &state,
+        &headers,
+        admin_id,
+        id,
+        serde_json::json!({ "deleted": true }),
+    )
+    .await;
+
+    Ok(Json(DeleteResponse { success: true }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(scope: &str, fields: serde_json::Value) -> SetCreditBudgetRequest {
+        let mut body = serde_json::json!({ "scope": scope, "period": "daily" });
+        body.as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn test_validate_subject() {
+        let id = Uuid::new_v4();
+        assert!(validate_subject(&request("user", serde_json::json!({ "userId": id }))).is_ok());
+        assert!(validate_subject(&request("user", serde_json::json!({ "apiKeyId": id }))).is_err());
+        assert!(validate_subject(&request(
+            "org_member",
+            serde_json::json!({ "userId": id, "orgId": id })
+        ))
+        .is_ok());
+        assert!(
+            validate_subject(&request("org_member", serde_json::json!({ "userId": id }))).is_err()
+        );
+        assert!(
+            validate_subject(&request("api_key", serde_json::json!({ "apiKeyId": id }))).is_ok()
+        );
+        assert_eq!(
+            request("api_key", serde_json::json!({ "apiKeyId": id })).currency,
+            "SOL"
+        );
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        assert!(validate_limits(None, None).is_err());
+        assert!(validate_limits(Some(0), None).is_err());
+        assert!(validate_limits(Some(200), Some(100)).is_err());
+        assert!(validate_limits(Some(100), Some(100)).is_ok());
+        assert!(validate_limits(None, Some(1)).is_ok());
+    }
+}
@@ -0,0 +1,146 @@
+//! Admin handlers for promotional credit grants
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::{AuthCallback, WebhookEvent};
+use crate::errors::AppError;
+use crate::handlers::admin::users::validate_system_admin;
+use crate::models::{
+    CreateCreditGrantRequest, CreateCreditGrantResponse, CreditGrantResponse,
+    CreditGrantsQueryParams, CreditGrantsResponse,
+};
+use crate::repositories::CreditGrantSource;
+use crate::services::{CreditService, EmailService};
+use crate::utils::validate_currency;
+use crate::AppState;
+
+/// POST /admin/users/:user_id/credits/grants - Grant promotional credit
+///
+/// Grant credit is spent before paid credit and forfeited if still unspent
+/// at `expiresAt`. Use this instead of an adjustment for sign-up bonuses,
+/// referral rewards and promotions.
+pub async fn create_credit_grant<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<CreateCreditGrantRequest>,
+) -> Result<Json<CreateCreditGrantResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    // SRV-14: Validate currency against whitelist
+    validate_currency(&request.currency)?;
+    let source = CreditGrantSource::from_str(&request.source)
+        .ok_or_else(|| AppError::Validation(format!("Unknown grant source: {}", request.source)))?;
+
+    state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or(AppError::NotFound("User not found".into()))?;
+
+    let credit_service =
+        CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+
+    let result = credit_service
+        .grant(
+            Some(admin_id),
+            user_id,
+            request.amount_lamports,
+            &request.currency,
+            source,
+            request.priority,
+            request.expires_at,
+            request.reason.as_deref(),
+        )
+        .await?;
+
+    tracing::info!(
+        admin_id = %admin_id,
+        user_id = %user_id,
+        grant_id = %result.grant.id,
+        amount_lamports = result.grant.amount,
+        currency = %result.grant.currency,
+        source = source.as_str(),
+        expires_at = ?result.grant.expires_at,
+        "Admin promotional credit grant"
+    );
+
+    state
+        .webhook_service
+        .dispatch_or_warn(
+            WebhookEvent::CreditsGranted,
+            serde_json::json!({
+                "user_id": user_id,
+                "admin_id": admin_id,
+                "grant_id": result.grant.id,
+                "transaction_id": result.grant.transaction_id,
+                "source": source.as_str(),
+                "amount_lamports": result.grant.amount,
+                "new_balance_lamports": result.new_balance_lamports,
+                "currency": result.grant.currency,
+                "expires_at": result.grant.expires_at,
+            }),
+        )
+        .await;
+
+    Ok(Json(result.into()))
+}
+
+/// GET /admin/users/:user_id/credits/grants - List a user's grants
+///
+/// Grants are listed in the order they are spent.
+pub async fn list_credit_grants<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+    Query(params): Query<CreditGrantsQueryParams>,
+) -> Result<Json<CreditGrantsResponse>, AppError> {
+    let _admin_id = validate_system_admin(&state, &headers).await?;
+
+    let currency = params.currency.map(|c| c.to_uppercase());
+    if let Some(currency) = &currency {
+        validate_currency(currency)?;
+    }
+
+    let credit_service =
+        CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+    let grants = credit_service
+        .get_grants(user_id, currency.as_deref(), params.active_only)
+        .await?;
+
+    Ok(Json(CreditGrantsResponse {
+        grants: grants.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// DELETE /admin/users/:user_id/credits/grants/:grant_id - Revoke a grant
+///
+/// Forfeits the grant's unspent credit now. Credit reserved by pending
+/// holds is forfeited once the holds settle.
+pub async fn revoke_credit_grant<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((user_id, grant_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<CreditGrantResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let credit_service =
+        CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+    let grant = credit_service.revoke_grant(user_id, grant_id).await?;
+
+    tracing::info!(
+        admin_id = %admin_id,
+        user_id = %user_id,
+        grant_id = %grant_id,
+        remaining_lamports = grant.remaining,
+        "Admin revoked promotional credit grant"
+    );
+
+    Ok(Json(grant.into()))
+}
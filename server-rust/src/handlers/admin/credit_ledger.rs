@@ -0,0 +1,94 @@
+//! Admin handlers for the double-entry credit ledger
+
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::handlers::admin::users::validate_system_admin;
+use crate::models::{
+    CreditReconciliationResponse, LedgerStatementQueryParams, LedgerStatementResponse,
+    TrialBalanceQueryParams, TrialBalanceResponse,
+};
+use crate::services::{CreditService, EmailService};
+use crate::utils::validate_currency;
+use crate::AppState;
+
+/// Normalize and validate an optional currency filter
+fn currency_filter(currency: Option<&str>) -> Result<Option<String>, AppError> {
+    currency
+        .map(|c| {
+            let c = c.to_uppercase();
+            validate_currency(&c)?;
+            Ok(c)
+        })
+        .transpose()
+}
+
+/// GET /admin/credits/ledger/trial-balance - Balance of every ledger account
+pub async fn get_trial_balance<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Query(params): Query<TrialBalanceQueryParams>,
+) -> Result<Json<TrialBalanceResponse>, AppError> {
+    let _admin_id = validate_system_admin(&state, &headers).await?;
+
+    let currency = currency_filter(params.currency.as_deref())?;
+    let as_of = params.as_of.unwrap_or_else(Utc::now);
+
+    let service = CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+    let reports = service
+        .trial_balance(currency.as_deref(), Some(as_of))
+        .await?;
+
+    Ok(Json(TrialBalanceResponse {
+        as_of,
+        currencies: reports.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// GET /admin/credits/ledger/statement - Ledger activity for a period
+///
+/// Reports each account's opening balance, debits, credits and closing
+/// balance for `[from, to)`.
+pub async fn get_ledger_statement<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Query(params): Query<LedgerStatementQueryParams>,
+) -> Result<Json<LedgerStatementResponse>, AppError> {
+    let _admin_id = validate_system_admin(&state, &headers).await?;
+
+    let currency = currency_filter(params.currency.as_deref())?;
+
+    let service = CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+    let reports = service
+        .ledger_statement(currency.as_deref(), params.from, params.to)
+        .await?;
+
+    Ok(Json(LedgerStatementResponse {
+        from: params.from,
+        to: params.to,
+        currencies: reports.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// GET /admin/credits/ledger/reconciliation - Reconcile balances now
+///
+/// Recomputes every stored balance from the journal and reports drift,
+/// unposted transactions and unbalanced currencies.
+pub async fn get_credit_reconciliation<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+) -> Result<Json<CreditReconciliationResponse>, AppError> {
+    let _admin_id = validate_system_admin(&state, &headers).await?;
+
+    let service = CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+    let report = service.reconcile().await?;
+
+    Ok(Json(report.into()))
+}
@@ -9,7 +9,7 @@ use serde::Serialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::callback::AuthCallback;
+use crate::callback::{AuthCallback, WebhookEvent};
 use crate::errors::AppError;
 use crate::handlers::admin::users::validate_system_admin;
 use crate::models::{AdjustCreditsRequest, AdjustCreditsResponse};
@@ -169,6 +169,97 @@ pub async fn adjust_credits<C: AuthCallback, E: EmailService>(
         "Admin credit adjustment"
     );
 
+    state
+        .webhook_service
+        .dispatch_or_warn(
+            WebhookEvent::CreditsAdjusted,
+            serde_json::json!({
+                "user_id": user_id,
+                "admin_id": admin_id,
+                "transaction_id": result.transaction_id,
+                "amount_lamports": result.amount_lamports,
+                "new_balance_lamports": result.new_balance_lamports,
+                "currency": request.currency,
+                "reason": request.reason,
+            }),
+        )
+        .await;
+
+    Ok(Json(AdjustCreditsResponse {
+        transaction_id: result.transaction_id,
+        new_balance_lamports: result.new_balance_lamports,
+        amount_lamports: result.amount_lamports,
+        currency: request.currency,
+        display,
+    }))
+}
+
+/// POST /admin/orgs/:org_id/credits - Adjust an organization's credit balance
+///
+/// Requires system admin privileges. Used to load credits bought by a
+/// company into its shared balance, or to correct it. Positive amounts add
+/// credits, negative amounts remove credits.
+pub async fn adjust_org_credits<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<AdjustCreditsRequest>,
+) -> Result<Json<AdjustCreditsResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    // SRV-14: Validate currency against whitelist
+    validate_currency(&request.currency)?;
+
+    state
+        .org_repo
+        .find_by_id(org_id)
+        .await?
+        .ok_or(AppError::NotFound("Organization not found".into()))?;
+
+    let credit_service =
+        CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+
+    let result = credit_service
+        .adjust_org(
+            admin_id,
+            org_id,
+            request.amount_lamports,
+            &request.currency,
+            &request.reason,
+            request.reference_type.as_deref(),
+            request.reference_id,
+        )
+        .await?;
+
+    let sol_amount = result.new_balance_lamports as f64 / 1_000_000_000.0;
+    let display = format!("{:.4} SOL", sol_amount);
+
+    tracing::info!(
+        admin_id = %admin_id,
+        org_id = %org_id,
+        amount_lamports = result.amount_lamports,
+        currency = %request.currency,
+        reason = %request.reason,
+        transaction_id = %result.transaction_id,
+        "Admin organization credit adjustment"
+    );
+
+    state
+        .webhook_service
+        .dispatch_or_warn(
+            WebhookEvent::CreditsAdjusted,
+            serde_json::json!({
+                "org_id": org_id,
+                "admin_id": admin_id,
+                "transaction_id": result.transaction_id,
+                "amount_lamports": result.amount_lamports,
+                "new_balance_lamports": result.new_balance_lamports,
+                "currency": request.currency,
+                "reason": request.reason,
+            }),
+        )
+        .await;
+
     Ok(Json(AdjustCreditsResponse {
         transaction_id: result.transaction_id,
         new_balance_lamports: result.new_balance_lamports,
@@ -188,11 +279,13 @@ mod tests {
 
     use crate::config::{
         default_access_expiry, default_audience, default_issuer, default_refresh_expiry,
-        AppleConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig, GoogleConfig,
-        JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig, ServerConfig, SolanaConfig,
-        SsoConfig, WalletConfig, WebAuthnConfig, WebhookConfig,
+        AppleConfig, AuditSinkConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig,
+        GoogleConfig, JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig, ServerConfig,
+        SolanaConfig, SsoConfig, StripeConfig, WalletConfig, WebAuthnConfig, WebhookConfig,
+    };
+    use crate::repositories::{
+        generate_api_key, ApiKeyEntity, LoginAttemptConfig, UserEntity, UserStatus,
     };
-    use crate::repositories::{generate_api_key, ApiKeyEntity, LoginAttemptConfig, UserEntity};
     use crate::services::{
         create_wallet_unlock_cache, AppleService, AuditService, CommsService, GoogleService,
         JwtService, LogEmailService, MfaAttemptService, PasswordService, SolanaService,
@@ -241,6 +334,8 @@ mod tests {
             sso: SsoConfig::default(),
             wallet: WalletConfig::default(),
             privacy: PrivacyConfig::default(),
+            audit_sinks: AuditSinkConfig::default(),
+            stripe: StripeConfig::default(),
         }
     }
 
@@ -266,6 +361,11 @@ mod tests {
             "http://localhost:3000".to_string(),
             token_cipher,
         );
+        let webhook_service = Arc::new(crate::services::WebhookService::new(
+            storage.webhook_repo.clone(),
+            storage.outbox_repo.clone(),
+            TokenCipher::new(&config.jwt.secret),
+        ));
 
         Arc::new(AppState {
             config,
@@ -282,6 +382,7 @@ mod tests {
             phantom_email: std::marker::PhantomData::<LogEmailService>,
             audit_service,
             comms_service,
+            webhook_service,
             user_repo: storage.user_repo.clone(),
             session_repo: storage.session_repo.clone(),
             nonce_repo: storage.nonce_repo.clone(),
@@ -297,6 +398,7 @@ mod tests {
             policy_repo: storage.policy_repo.clone(),
             outbox_repo: storage.outbox_repo.clone(),
             api_key_repo: storage.api_key_repo.clone(),
+            api_key_rate_limiter: crate::middleware::rate_limit::RateLimitStore::new(),
             wallet_material_repo: storage.wallet_material_repo.clone(),
             derived_wallet_repo: storage.derived_wallet_repo.clone(),
             wallet_rotation_history_repo: storage.wallet_rotation_history_repo.clone(),
@@ -358,9 +460,16 @@ mod tests {
             stripe_customer_id: None,
             auth_methods: vec![],
             is_system_admin: true,
+            is_service_account: false,
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
         let user = state.user_repo.create(user).await.unwrap();
 
@@ -386,9 +495,16 @@ mod tests {
             stripe_customer_id: None,
             auth_methods: vec![],
             is_system_admin: false,
+            is_service_account: false,
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
         let user = state.user_repo.create(user).await.unwrap();
         user.id
@@ -444,9 +560,16 @@ mod tests {
             stripe_customer_id: None,
             auth_methods: vec![],
             is_system_admin: false,
+            is_service_account: false,
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
         let user = state.user_repo.create(user).await.unwrap();
         let api_key = generate_api_key();
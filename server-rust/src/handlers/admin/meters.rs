@@ -0,0 +1,405 @@
+//! Admin usage meter catalog handlers
+//!
+//! GET    /admin/meters                              - List meters
+//! POST   /admin/meters                              - Create a meter
+//! GET    /admin/meters/{meter_id}                   - Get a meter
+//! PATCH  /admin/meters/{meter_id}                   - Update a meter
+//! GET    /admin/meters/{meter_id}/overrides         - List org price overrides
+//! PUT    /admin/meters/{meter_id}/overrides/{org_id} - Set an org's price schedule
+//! DELETE /admin/meters/{meter_id}/overrides/{org_id} - Remove an org's price schedule
+//!
+//! Meter names and currencies are fixed once created: usage events refer to
+//! meters by name and billed usage keeps its currency.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::repositories::{
+    validate_meter_name, AuditEventType, AuditLogBuilder, MeterEntity, MeterPriceOverrideEntity,
+    PriceSchedule,
+};
+use crate::services::EmailService;
+use crate::utils::{extract_client_ip, validate_currency};
+use crate::AppState;
+
+use super::users::validate_system_admin;
+
+/// Maximum length of a meter display name
+const MAX_DISPLAY_NAME_LEN: usize = 255;
+
+/// Maximum length of a unit label
+const MAX_UNIT_LEN: usize = 32;
+
+/// Meter response
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeterResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub display_name: String,
+    pub unit: String,
+    pub currency: String,
+    pub pricing: PriceSchedule,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<MeterEntity> for MeterResponse {
+    fn from(m: MeterEntity) -> Self {
+        Self {
+            id: m.id,
+            name: m.name,
+            display_name: m.display_name,
+            unit: m.unit,
+            currency: m.currency,
+            pricing: m.pricing,
+            active: m.active,
+            created_at: m.created_at,
+            updated_at: m.updated_at,
+        }
+    }
+}
+
+/// Response for listing meters
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListMetersResponse {
+    pub meters: Vec<MeterResponse>,
+}
+
+/// Organization price override response
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeterPriceOverrideResponse {
+    pub meter_id: Uuid,
+    pub org_id: Uuid,
+    pub pricing: PriceSchedule,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<MeterPriceOverrideEntity> for MeterPriceOverrideResponse {
+    fn from(o: MeterPriceOverrideEntity) -> Self {
+        Self {
+            meter_id: o.meter_id,
+            org_id: o.org_id,
+            pricing: o.pricing,
+            created_by: o.created_by,
+            created_at: o.created_at,
+            updated_at: o.updated_at,
+        }
+    }
+}
+
+/// Response for listing price overrides
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListMeterPriceOverridesResponse {
+    pub overrides: Vec<MeterPriceOverrideResponse>,
+}
+
+/// Request to create a meter
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMeterRequest {
+    /// Key used when reporting usage (e.g. `api_call`)
+    pub name: String,
+    pub display_name: String,
+    /// Unit of measure (e.g. "call", "second")
+    pub unit: String,
+    pub currency: String,
+    pub pricing: PriceSchedule,
+}
+
+/// Request to update a meter
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateMeterRequest {
+    pub display_name: Option<String>,
+    pub unit: Option<String>,
+    /// Applies to usage billed from now on
+    pub pricing: Option<PriceSchedule>,
+    /// Inactive meters reject new usage; pending usage is still billed
+    pub active: Option<bool>,
+}
+
+/// Request to set an organization's price schedule
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMeterPriceOverrideRequest {
+    pub pricing: PriceSchedule,
+}
+
+/// Response for delete operations
+#[derive(Debug, Serialize)]
+pub struct DeleteResponse {
+    pub success: bool,
+}
+
+fn validate_label(value: &str, field: &str, max_len: usize) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() || value.chars().count() > max_len {
+        return Err(AppError::Validation(format!(
+            "{} must be 1-{} characters",
+            field, max_len
+        )));
+    }
+    Ok(value.to_string())
+}
+
+async fn find_meter<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    id: Uuid,
+) -> Result<MeterEntity, AppError> {
+    state
+        .storage
+        .meter_repo
+        .find_meter(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Meter not found".into()))
+}
+
+async fn audit<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    event_type: AuditEventType,
+    admin_id: Uuid,
+    target: (&str, Uuid),
+    metadata: serde_json::Value,
+) {
+    let mut builder = AuditLogBuilder::new(event_type)
+        .actor(admin_id)
+        .target(target.0, target.1)
+        .metadata(metadata);
+    if let Some(ip) = extract_client_ip(headers, state.config.server.trust_proxy) {
+        builder = builder.ip(&ip);
+    }
+    if let Some(ua) = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+    {
+        builder = builder.user_agent(ua);
+    }
+    state.audit_service.log_or_warn(builder.build()).await;
+}
+
+/// GET /admin/meters - List meters
+pub async fn list_meters<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+) -> Result<Json<ListMetersResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    let meters = state.storage.meter_repo.list_meters().await?;
+    Ok(Json(ListMetersResponse {
+        meters: meters.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// POST /admin/meters - Create a meter
+pub async fn create_meter<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateMeterRequest>,
+) -> Result<Json<MeterResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    validate_meter_name(&request.name)?;
+    let display_name = validate_label(&request.display_name, "displayName", MAX_DISPLAY_NAME_LEN)?;
+    let unit = validate_label(&request.unit, "unit", MAX_UNIT_LEN)?;
+    validate_currency(&request.currency)?;
+    request.pricing.validate()?;
+
+    let meter = MeterEntity::new(
+        request.name,
+        display_name,
+        unit,
+        request.currency,
+        request.pricing,
+    );
+    let created = state.storage.meter_repo.create_meter(meter).await?;
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::MeterCreated,
+        admin_id,
+        ("meter", created.id),
+        serde_json::json!({
+            "name": created.name,
+            "currency": created.currency,
+            "pricing": created.pricing,
+        }),
+    )
+    .await;
+
+    Ok(Json(created.into()))
+}
+
+/// GET /admin/meters/{meter_id} - Get a meter
+pub async fn get_meter<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(meter_id): Path<Uuid>,
+) -> Result<Json<MeterResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    let meter = find_meter(&state, meter_id).await?;
+    Ok(Json(meter.into()))
+}
+
+/// PATCH /admin/meters/{meter_id} - Update a meter
+pub async fn update_meter<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(meter_id): Path<Uuid>,
+    Json(request): Json<UpdateMeterRequest>,
+) -> Result<Json<MeterResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let mut meter = find_meter(&state, meter_id).await?;
+
+    if let Some(display_name) = request.display_name {
+        meter.display_name = validate_label(&display_name, "displayName", MAX_DISPLAY_NAME_LEN)?;
+    }
+    if let Some(unit) = request.unit {
+        meter.unit = validate_label(&unit, "unit", MAX_UNIT_LEN)?;
+    }
+    if let Some(pricing) = request.pricing {
+        pricing.validate()?;
+        meter.pricing = pricing;
+    }
+    if let Some(active) = request.active {
+        meter.active = active;
+    }
+
+    let updated = state.storage.meter_repo.update_meter(meter).await?;
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::MeterUpdated,
+        admin_id,
+        ("meter", updated.id),
+        serde_json::json!({
+            "name": updated.name,
+            "pricing": updated.pricing,
+            "active": updated.active,
+        }),
+    )
+    .await;
+
+    Ok(Json(updated.into()))
+}
+
+/// GET /admin/meters/{meter_id}/overrides - List org price overrides
+pub async fn list_meter_price_overrides<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(meter_id): Path<Uuid>,
+) -> Result<Json<ListMeterPriceOverridesResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    let meter = find_meter(&state, meter_id).await?;
+    let overrides = state
+        .storage
+        .meter_repo
+        .list_price_overrides(meter.id)
+        .await?;
+
+    Ok(Json(ListMeterPriceOverridesResponse {
+        overrides: overrides.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// PUT /admin/meters/{meter_id}/overrides/{org_id} - Set an org's price schedule
+pub async fn set_meter_price_override<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((meter_id, org_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<SetMeterPriceOverrideRequest>,
+) -> Result<Json<MeterPriceOverrideResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let meter = find_meter(&state, meter_id).await?;
+    state
+        .org_repo
+        .find_by_id(org_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Organization not found".into()))?;
+    request.pricing.validate()?;
+
+    let now = Utc::now();
+    let stored = state
+        .storage
+        .meter_repo
+        .upsert_price_override(MeterPriceOverrideEntity {
+            meter_id: meter.id,
+            org_id,
+            pricing: request.pricing,
+            created_by: Some(admin_id),
+            created_at: now,
+            updated_at: now,
+        })
+        .await?;
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::MeterPriceOverrideUpdated,
+        admin_id,
+        ("meter", meter.id),
+        serde_json::json!({
+            "name": meter.name,
+            "orgId": org_id,
+            "pricing": stored.pricing,
+        }),
+    )
+    .await;
+
+    Ok(Json(stored.into()))
+}
+
+/// DELETE /admin/meters/{meter_id}/overrides/{org_id} - Remove an org's price schedule
+///
+/// The organization's usage is billed at the meter's default schedule again.
+pub async fn delete_meter_price_override<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((meter_id, org_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<DeleteResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let meter = find_meter(&state, meter_id).await?;
+    let deleted = state
+        .storage
+        .meter_repo
+        .delete_price_override(meter.id, org_id)
+        .await?;
+    if !deleted {
+        return Err(AppError::NotFound("Price override not found".into()));
+    }
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::MeterPriceOverrideUpdated,
+        admin_id,
+        ("meter", meter.id),
+        serde_json::json!({ "name": meter.name, "orgId": org_id, "pricing": null }),
+    )
+    .await;
+
+    Ok(Json(DeleteResponse { success: true }))
+}
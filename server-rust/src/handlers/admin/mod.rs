@@ -1,21 +1,30 @@
 //! Admin handlers
 
 mod audit;
+mod credit_budgets;
+mod credit_grants;
+mod credit_ledger;
 mod credit_refunds;
 mod credits;
 mod dashboard_permissions;
 mod disposable_domains;
+mod meters;
 pub(crate) mod deposits;
 mod orgs;
 mod settings;
 mod sso_providers;
+mod subscription_plans;
 mod treasury;
 mod users;
+mod webhooks;
 
-pub use audit::{get_org_audit_logs, get_system_audit_logs};
+pub use audit::{get_org_audit_logs, get_system_audit_logs, verify_audit_chain};
+pub use credit_budgets::{delete_credit_budget, list_credit_budgets, set_credit_budget};
+pub use credit_grants::{create_credit_grant, list_credit_grants, revoke_credit_grant};
+pub use credit_ledger::{get_credit_reconciliation, get_ledger_statement, get_trial_balance};
 pub use credit_refunds::reject_credit_refund_request;
 pub use credit_refunds::{list_credit_refund_requests, process_credit_refund_request};
-pub use credits::{adjust_credits, get_stats as get_credit_stats};
+pub use credits::{adjust_credits, adjust_org_credits, get_stats as get_credit_stats};
 pub use dashboard_permissions::{get_dashboard_permissions, update_dashboard_permissions};
 pub use disposable_domains::{get_disposable_domains, update_disposable_domains};
 pub use deposits::{
@@ -23,15 +32,29 @@ pub use deposits::{
     list_deposits as list_admin_deposits, list_in_privacy_period, list_pending_withdrawals,
     process_all_withdrawals, process_withdrawal,
 };
+pub use meters::{
+    create_meter, delete_meter_price_override, get_meter, list_meter_price_overrides, list_meters,
+    set_meter_price_override, update_meter,
+};
 pub use orgs::{get_org, list_orgs};
 pub use settings::{list_settings, update_settings};
 pub use sso_providers::{
     create_sso_provider, delete_sso_provider, get_sso_provider, list_sso_providers,
     update_sso_provider,
 };
+pub use subscription_plans::{
+    create_subscription_plan, get_subscription_plan, list_subscription_plans_admin,
+    update_subscription_plan,
+};
 pub use treasury::{authorize_treasury, get_treasury, revoke_treasury};
 pub use users::{
     delete_user, force_password_reset, get_user, get_user_credits, get_user_deposits,
-    get_user_stats, get_user_withdrawal_history, list_users, set_system_admin, update_user,
-    validate_system_admin,
+    get_user_stats, get_user_withdrawal_history, list_users, set_system_admin, set_user_status,
+    update_user, validate_system_admin, validate_system_admin_auth,
+    validate_system_admin_with_scope,
+};
+pub use webhooks::{
+    create_webhook_endpoint, delete_webhook_endpoint, get_webhook_endpoint,
+    list_webhook_deliveries, list_webhook_endpoints, list_webhook_events, replay_webhook_delivery,
+    update_webhook_endpoint,
 };
@@ -9,32 +9,12 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::callback::AuthCallback;
-use crate::errors::{AppError, ERR_SYSTEM_ADMIN_REQUIRED};
+use crate::errors::AppError;
 use crate::models::{AdminOrgResponse, ListAdminOrgsResponse, ListOrgsQueryParams};
 use crate::services::EmailService;
-use crate::utils::authenticate;
 use crate::AppState;
 
-/// Validate system admin access
-async fn validate_system_admin<C: AuthCallback, E: EmailService>(
-    state: &Arc<AppState<C, E>>,
-    headers: &HeaderMap,
-) -> Result<Uuid, AppError> {
-    // Authenticate via JWT or API key
-    let auth_user = authenticate(state, headers).await?;
-
-    let user = state
-        .user_repo
-        .find_by_id(auth_user.user_id)
-        .await?
-        .ok_or(AppError::InvalidToken)?;
-
-    if !user.is_system_admin {
-        return Err(AppError::Forbidden(ERR_SYSTEM_ADMIN_REQUIRED.into()));
-    }
-
-    Ok(auth_user.user_id)
-}
+use super::users::validate_system_admin;
 
 /// GET /admin/orgs - List all organizations
 ///
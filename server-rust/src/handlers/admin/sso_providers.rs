@@ -44,19 +44,22 @@ async fn validate_sso_access<C: AuthCallback, E: EmailService>(
         .await?
         .ok_or(AppError::InvalidToken)?;
 
-    // Get orgs where user is owner
+    // Impersonation sessions and org-bound API keys never act as system admin
+    let is_system_admin = user.is_system_admin && auth_user.ensure_system_admin_principal().is_ok();
+
+    // Get orgs where user is owner (only the bound org for org-bound credentials)
     let memberships = state
         .membership_repo
         .find_by_user(auth_user.user_id)
         .await?;
     let owned_org_ids: Vec<Uuid> = memberships
         .into_iter()
-        .filter(|m| m.role == OrgRole::Owner)
+        .filter(|m| m.role == OrgRole::Owner && auth_user.ensure_org(m.org_id).is_ok())
         .map(|m| m.org_id)
         .collect();
 
     // Must be system admin OR owner of at least one org
-    if !user.is_system_admin && owned_org_ids.is_empty() {
+    if !is_system_admin && owned_org_ids.is_empty() {
         return Err(AppError::Forbidden(
             "SSO management requires org owner or system admin privileges".into(),
         ));
@@ -64,7 +67,7 @@ async fn validate_sso_access<C: AuthCallback, E: EmailService>(
 
     Ok(SsoAccessContext {
         user_id: auth_user.user_id,
-        is_system_admin: user.is_system_admin,
+        is_system_admin,
         owned_org_ids,
     })
 }
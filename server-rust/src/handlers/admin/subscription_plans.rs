@@ -0,0 +1,259 @@
+//! Admin subscription plan catalog handlers
+//!
+//! GET    /admin/subscription-plans            - List plans
+//! POST   /admin/subscription-plans            - Create a plan
+//! GET    /admin/subscription-plans/{plan_id}  - Get a plan
+//! PATCH  /admin/subscription-plans/{plan_id}  - Update a plan
+//!
+//! Plan names, prices, currencies and billing intervals are fixed once
+//! created so existing subscribers are never repriced; publish a new plan
+//! and deactivate the old one instead.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::models::{SubscriptionPlanResponse, SubscriptionPlansResponse};
+use crate::repositories::{
+    validate_entitlements, validate_plan_name, AuditEventType, AuditLogBuilder, PlanInterval,
+    SubscriptionPlanEntity,
+};
+use crate::services::EmailService;
+use crate::utils::{extract_client_ip, validate_currency};
+use crate::AppState;
+
+use super::users::validate_system_admin;
+
+/// Maximum length of a plan display name
+const MAX_DISPLAY_NAME_LEN: usize = 255;
+
+/// Maximum number of intervals per billing period
+const MAX_INTERVAL_COUNT: i32 = 36;
+
+/// Maximum trial or grace period in days
+const MAX_PERIOD_DAYS: i32 = 365;
+
+/// Request to create a plan
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubscriptionPlanRequest {
+    /// Stable key (e.g. `pro_monthly`)
+    pub name: String,
+    pub display_name: String,
+    /// Price per billing period in lamports
+    pub price_lamports: i64,
+    pub currency: String,
+    pub interval: PlanInterval,
+    #[serde(default)]
+    pub interval_count: Option<i32>,
+    #[serde(default)]
+    pub trial_days: Option<i32>,
+    #[serde(default)]
+    pub grace_days: Option<i32>,
+    #[serde(default)]
+    pub entitlements: Vec<String>,
+}
+
+/// Request to update a plan
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSubscriptionPlanRequest {
+    pub display_name: Option<String>,
+    /// Applies to new subscribers
+    pub trial_days: Option<i32>,
+    /// Applies to payments failing from now on
+    pub grace_days: Option<i32>,
+    /// Replaces the plan's entitlements for all subscribers
+    pub entitlements: Option<Vec<String>>,
+    /// Inactive plans accept no new subscribers; existing ones keep renewing
+    pub active: Option<bool>,
+}
+
+fn validate_label(value: &str, field: &str, max_len: usize) -> Result<String, AppError> {
+    let value = value.trim();
+    if value.is_empty() || value.chars().count() > max_len {
+        return Err(AppError::Validation(format!(
+            "{} must be 1-{} characters",
+            field, max_len
+        )));
+    }
+    Ok(value.to_string())
+}
+
+fn validate_days(value: i32, field: &str) -> Result<i32, AppError> {
+    if !(0..=MAX_PERIOD_DAYS).contains(&value) {
+        return Err(AppError::Validation(format!(
+            "{} must be 0-{}",
+            field, MAX_PERIOD_DAYS
+        )));
+    }
+    Ok(value)
+}
+
+async fn find_plan<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    id: Uuid,
+) -> Result<SubscriptionPlanEntity, AppError> {
+    state
+        .storage
+        .subscription_repo
+        .find_plan(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Subscription plan not found".into()))
+}
+
+async fn audit<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    event_type: AuditEventType,
+    admin_id: Uuid,
+    plan: &SubscriptionPlanEntity,
+) {
+    let mut builder = AuditLogBuilder::new(event_type)
+        .actor(admin_id)
+        .target("subscription_plan", plan.id)
+        .metadata(serde_json::json!({
+            "name": plan.name,
+            "priceLamports": plan.price_lamports,
+            "currency": plan.currency,
+            "interval": plan.interval,
+            "intervalCount": plan.interval_count,
+            "entitlements": plan.entitlements,
+            "active": plan.active,
+        }));
+    if let Some(ip) = extract_client_ip(headers, state.config.server.trust_proxy) {
+        builder = builder.ip(&ip);
+    }
+    if let Some(ua) = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+    {
+        builder = builder.user_agent(ua);
+    }
+    state.audit_service.log_or_warn(builder.build()).await;
+}
+
+/// GET /admin/subscription-plans - List plans, including inactive ones
+pub async fn list_subscription_plans_admin<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+) -> Result<Json<SubscriptionPlansResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    let plans = state.storage.subscription_repo.list_plans().await?;
+    Ok(Json(SubscriptionPlansResponse {
+        plans: plans.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// POST /admin/subscription-plans - Create a plan
+pub async fn create_subscription_plan<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateSubscriptionPlanRequest>,
+) -> Result<Json<SubscriptionPlanResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    validate_plan_name(&request.name)?;
+    let display_name = validate_label(&request.display_name, "displayName", MAX_DISPLAY_NAME_LEN)?;
+    if request.price_lamports < 0 {
+        return Err(AppError::Validation(
+            "priceLamports must not be negative".into(),
+        ));
+    }
+    validate_currency(&request.currency)?;
+    let interval_count = request.interval_count.unwrap_or(1);
+    if !(1..=MAX_INTERVAL_COUNT).contains(&interval_count) {
+        return Err(AppError::Validation(format!(
+            "intervalCount must be 1-{}",
+            MAX_INTERVAL_COUNT
+        )));
+    }
+    validate_entitlements(&request.entitlements)?;
+
+    let mut plan = SubscriptionPlanEntity::new(
+        request.name,
+        display_name,
+        request.price_lamports,
+        request.currency,
+        request.interval,
+    );
+    plan.interval_count = interval_count;
+    plan.trial_days = validate_days(request.trial_days.unwrap_or(0), "trialDays")?;
+    plan.grace_days = validate_days(request.grace_days.unwrap_or(0), "graceDays")?;
+    plan.entitlements = request.entitlements;
+
+    let created = state.storage.subscription_repo.create_plan(plan).await?;
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::SubscriptionPlanCreated,
+        admin_id,
+        &created,
+    )
+    .await;
+
+    Ok(Json(created.into()))
+}
+
+/// GET /admin/subscription-plans/{plan_id} - Get a plan
+pub async fn get_subscription_plan<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(plan_id): Path<Uuid>,
+) -> Result<Json<SubscriptionPlanResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    let plan = find_plan(&state, plan_id).await?;
+    Ok(Json(plan.into()))
+}
+
+/// PATCH /admin/subscription-plans/{plan_id} - Update a plan
+pub async fn update_subscription_plan<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(plan_id): Path<Uuid>,
+    Json(request): Json<UpdateSubscriptionPlanRequest>,
+) -> Result<Json<SubscriptionPlanResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let mut plan = find_plan(&state, plan_id).await?;
+
+    if let Some(display_name) = request.display_name {
+        plan.display_name = validate_label(&display_name, "displayName", MAX_DISPLAY_NAME_LEN)?;
+    }
+    if let Some(trial_days) = request.trial_days {
+        plan.trial_days = validate_days(trial_days, "trialDays")?;
+    }
+    if let Some(grace_days) = request.grace_days {
+        plan.grace_days = validate_days(grace_days, "graceDays")?;
+    }
+    if let Some(entitlements) = request.entitlements {
+        validate_entitlements(&entitlements)?;
+        plan.entitlements = entitlements;
+    }
+    if let Some(active) = request.active {
+        plan.active = active;
+    }
+
+    let updated = state.storage.subscription_repo.update_plan(plan).await?;
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::SubscriptionPlanUpdated,
+        admin_id,
+        &updated,
+    )
+    .await;
+
+    Ok(Json(updated.into()))
+}
@@ -11,13 +11,12 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
-use super::users::validate_system_admin;
+use super::users::{validate_system_admin, validate_system_admin_auth};
 use crate::callback::AuthCallback;
 use crate::errors::AppError;
 use crate::models::MessageResponse;
-use crate::repositories::TreasuryConfigEntity;
+use crate::repositories::{TreasuryConfigEntity, SCOPE_ADMIN};
 use crate::services::EmailService;
-use crate::utils::authenticate;
 use crate::AppState;
 
 /// Request to authorize admin's wallet as treasury
@@ -72,11 +71,9 @@ pub async fn authorize_treasury<C: AuthCallback, E: EmailService>(
     headers: HeaderMap,
     Json(request): Json<AuthorizeTreasuryRequest>,
 ) -> Result<Json<TreasuryConfigResponse>, AppError> {
-    // Validate system admin
-    let admin_id = validate_system_admin(&state, &headers).await?;
-
-    // Get authenticated user (needed for session ID for wallet cache)
-    let auth_user = authenticate(&state, &headers).await?;
+    // Validate system admin (keeping the session ID for the wallet cache)
+    let auth_user = validate_system_admin_auth(&state, &headers, SCOPE_ADMIN).await?;
+    let admin_id = auth_user.user_id;
 
     // Get wallet material
     let wallet_material = state
@@ -2,17 +2,18 @@
 
 use axum::{
     extract::{Path, Query, State},
-    http::HeaderMap,
+    http::{header, HeaderMap},
     Json,
 };
 use std::sync::{atomic::AtomicBool, atomic::Ordering, Arc};
 use uuid::Uuid;
 
-use crate::callback::AuthCallback;
+use crate::callback::{AuthCallback, UserStatusChangePayload};
 use crate::errors::AppError;
+use crate::handlers::auth::call_status_changed_callback_with_timeout;
 use crate::models::{
     AdminUpdateUserRequest, AdminUserResponse, ListUsersQueryParams, ListUsersResponse,
-    MessageResponse, SetSystemAdminRequest,
+    MessageResponse, SetSystemAdminRequest, SetUserStatusRequest,
 };
 
 /// S-13: Cache whether any system admins exist to avoid querying on every admin request.
@@ -21,10 +22,10 @@ static ADMINS_EXIST: AtomicBool = AtomicBool::new(false);
 use crate::repositories::pagination::{cap_limit, cap_offset};
 use crate::repositories::{
     default_expiry, generate_verification_token, hash_verification_token, normalize_email,
-    TokenType,
+    AuditEventType, AuditLogBuilder, OrgRole, TokenType, UserEntity, UserStatus, SCOPE_ADMIN,
 };
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::{authenticate_with_scope, extract_client_ip, AuthenticatedUser};
 use crate::AppState;
 
 use super::deposits::AdminDepositItem;
@@ -37,12 +38,38 @@ pub async fn validate_system_admin<C: AuthCallback, E: EmailService>(
     state: &Arc<AppState<C, E>>,
     headers: &HeaderMap,
 ) -> Result<Uuid, AppError> {
+    validate_system_admin_with_scope(state, headers, SCOPE_ADMIN).await
+}
+
+/// Validate system admin access for an endpoint that scoped API keys may call
+///
+/// Restricted API keys must carry `scope` (or `admin`); org-bound keys are
+/// never accepted on system admin endpoints.
+pub async fn validate_system_admin_with_scope<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    scope: &str,
+) -> Result<Uuid, AppError> {
+    Ok(validate_system_admin_auth(state, headers, scope)
+        .await?
+        .user_id)
+}
+
+/// Like [`validate_system_admin_with_scope`], returning the full
+/// authentication (e.g. to know which API key made the request)
+pub async fn validate_system_admin_auth<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    scope: &str,
+) -> Result<AuthenticatedUser, AppError> {
     // Authenticate via JWT or API key
-    let auth_user = authenticate(state, headers).await?;
+    let auth_user = authenticate_with_scope(state, headers, scope).await?;
+    // Covers credit spends and holds, which are admin-authorized
+    auth_user.ensure_system_admin_principal()?;
 
     // P-01: Check JWT claim first — skip DB lookup for known admins
     if auth_user.is_system_admin == Some(true) {
-        return Ok(auth_user.user_id);
+        return Ok(auth_user);
     }
 
     // Fall back to DB lookup for API key auth or tokens without the claim
@@ -53,7 +80,7 @@ pub async fn validate_system_admin<C: AuthCallback, E: EmailService>(
         .ok_or(AppError::InvalidToken)?;
 
     if user.is_system_admin {
-        return Ok(auth_user.user_id);
+        return Ok(auth_user);
     }
 
     // Check for bootstrap scenario: no admins exist + user matches bootstrap email
@@ -90,7 +117,7 @@ pub async fn validate_system_admin<C: AuthCallback, E: EmailService>(
                         "Bootstrapped first system admin via BOOTSTRAP_ADMIN_EMAIL"
                     );
 
-                    return Ok(auth_user.user_id);
+                    return Ok(auth_user);
                 }
             }
         }
@@ -113,13 +140,27 @@ pub async fn list_users<C: AuthCallback, E: EmailService>(
 
     let limit = cap_limit(params.limit);
     let offset = cap_offset(params.offset);
+    let status = params
+        .status
+        .as_deref()
+        .map(|s| {
+            UserStatus::from_str(s)
+                .ok_or_else(|| AppError::Validation(format!("Invalid status: {}", s)))
+        })
+        .transpose()?;
 
     // PERF-02: Parallelize user list and count queries for better latency.
     // These queries are independent and can run concurrently.
-    let (users_result, total_result) = tokio::join!(
-        state.user_repo.list_all(limit, offset),
-        state.user_repo.count()
-    );
+    let (users_result, total_result) = match status {
+        Some(status) => tokio::join!(
+            state.user_repo.list_by_status(status, limit, offset),
+            state.user_repo.count_by_status(status)
+        ),
+        None => tokio::join!(
+            state.user_repo.list_all(limit, offset),
+            state.user_repo.count()
+        ),
+    };
     let users = users_result?;
     let total = total_result?;
 
@@ -391,6 +432,184 @@ pub async fn force_password_reset<C: AuthCallback, E: EmailService>(
     }))
 }
 
+/// Maximum length of a status reason
+const MAX_STATUS_REASON_LENGTH: usize = 500;
+
+/// Restore the organizations deleted along with a pending-deletion account
+///
+/// `DELETE /me` schedules the orgs it takes with the account for the same
+/// purge time as the account; orgs the user owns that were deleted on their
+/// own keep their schedule.
+async fn restore_cascaded_orgs<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    user: &UserEntity,
+    admin_id: Uuid,
+    headers: &HeaderMap,
+) -> Result<(), AppError> {
+    let Some(purge_after) = user.status_expires_at else {
+        return Ok(());
+    };
+    for membership in state
+        .membership_repo
+        .find_by_user_including_pending(user.id)
+        .await?
+    {
+        if membership.role != OrgRole::Owner {
+            continue;
+        }
+        let Some(org) = state.org_repo.find_by_id(membership.org_id).await? else {
+            continue;
+        };
+        if org.purge_after != Some(purge_after) {
+            continue;
+        }
+        if state.org_repo.restore(org.id).await?.is_some() {
+            let _ = state
+                .audit_service
+                .log_org_event(AuditEventType::OrgRestored, admin_id, org.id, Some(headers))
+                .await;
+        }
+    }
+    Ok(())
+}
+
+/// POST /admin/users/:user_id/status - Suspend, ban or reactivate a user
+///
+/// Requires system admin privileges.
+/// Suspending or banning revokes all of the user's sessions; sign-in on every
+/// path is refused until the status is lifted or `expiresAt` passes.
+/// Reactivating a pending deletion also restores the organizations that were
+/// scheduled for deletion with the account.
+pub async fn set_user_status<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+    Json(request): Json<SetUserStatusRequest>,
+) -> Result<Json<AdminUserResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let status = UserStatus::from_str(&request.status)
+        .ok_or_else(|| AppError::Validation(format!("Invalid status: {}", request.status)))?;
+    if matches!(status, UserStatus::PendingDeletion | UserStatus::Deleted) {
+        return Err(AppError::Validation(
+            "Use account deletion to schedule a user for deletion".into(),
+        ));
+    }
+    if user_id == admin_id {
+        return Err(AppError::Validation(
+            "Cannot change your own account status".into(),
+        ));
+    }
+
+    let reason = request
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(str::to_string);
+    if reason
+        .as_ref()
+        .is_some_and(|r| r.len() > MAX_STATUS_REASON_LENGTH)
+    {
+        return Err(AppError::Validation(format!(
+            "Reason must be at most {} characters",
+            MAX_STATUS_REASON_LENGTH
+        )));
+    }
+    let expires_at = if status.lifts_on_expiry() {
+        request.expires_at
+    } else {
+        None
+    };
+    if expires_at.is_some_and(|at| at <= chrono::Utc::now()) {
+        return Err(AppError::Validation("Expiry must be in the future".into()));
+    }
+
+    let user = state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or(AppError::NotFound("User not found".into()))?;
+    if user.is_system_admin {
+        return Err(AppError::Validation(
+            "Cannot change the status of a system admin. Remove admin status first.".into(),
+        ));
+    }
+    if user.status == UserStatus::Deleted {
+        return Err(AppError::Validation("User has been deleted".into()));
+    }
+    let previous_status = user.effective_status();
+
+    state
+        .user_repo
+        .set_status(user_id, status, reason.clone(), expires_at)
+        .await?;
+    // Suspending or banning keeps the deletion's org cascade scheduled
+    if user.status == UserStatus::PendingDeletion && status == UserStatus::Active {
+        restore_cascaded_orgs(&state, &user, admin_id, &headers).await?;
+    }
+
+    let event_type = match status {
+        UserStatus::Suspended => AuditEventType::UserSuspended,
+        UserStatus::Banned => AuditEventType::UserBanned,
+        _ => AuditEventType::UserReactivated,
+    };
+    if status != UserStatus::Active {
+        let revoke_reason = format!("account_{}", status.as_str());
+        state
+            .session_repo
+            .revoke_all_for_user_with_reason(user_id, &revoke_reason)
+            .await?;
+    }
+
+    let mut builder = AuditLogBuilder::new(event_type)
+        .actor(admin_id)
+        .target("user", user_id)
+        .metadata(serde_json::json!({
+            "previousStatus": previous_status.as_str(),
+            "reason": reason,
+            "expiresAt": expires_at,
+        }));
+    if let Some(ip) = extract_client_ip(&headers, state.config.server.trust_proxy) {
+        builder = builder.ip(&ip);
+    }
+    if let Some(ua) = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+    {
+        builder = builder.user_agent(ua);
+    }
+    state.audit_service.log_or_warn(builder.build()).await;
+
+    call_status_changed_callback_with_timeout(
+        &state.callback,
+        &UserStatusChangePayload {
+            user_id,
+            status,
+            previous_status,
+            reason,
+            expires_at,
+            changed_by: admin_id,
+        },
+    )
+    .await;
+
+    tracing::info!(
+        admin_id = %admin_id,
+        target_user_id = %user_id,
+        status = status.as_str(),
+        previous_status = previous_status.as_str(),
+        "Admin changed user account status"
+    );
+
+    let updated = state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or(AppError::NotFound("User not found".into()))?;
+    Ok(Json(AdminUserResponse::from(&updated)))
+}
+
 /// Response for user deposits list
 #[derive(Debug, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -699,9 +918,9 @@ mod tests {
 
     use crate::config::{
         default_access_expiry, default_audience, default_issuer, default_refresh_expiry,
-        AppleConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig, GoogleConfig,
-        JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig, ServerConfig, SolanaConfig,
-        SsoConfig, WalletConfig, WebAuthnConfig, WebhookConfig,
+        AppleConfig, AuditSinkConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig,
+        GoogleConfig, JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig, ServerConfig,
+        SolanaConfig, SsoConfig, StripeConfig, WalletConfig, WebAuthnConfig, WebhookConfig,
     };
     use crate::errors::AppError;
     use crate::repositories::{generate_api_key, ApiKeyEntity, LoginAttemptConfig, UserEntity};
@@ -753,6 +972,8 @@ mod tests {
             sso: SsoConfig::default(),
             wallet: WalletConfig::default(),
             privacy: PrivacyConfig::default(),
+            audit_sinks: AuditSinkConfig::default(),
+            stripe: StripeConfig::default(),
         }
     }
 
@@ -778,6 +999,11 @@ mod tests {
             "http://localhost:3000".to_string(),
             token_cipher,
         );
+        let webhook_service = Arc::new(crate::services::WebhookService::new(
+            storage.webhook_repo.clone(),
+            storage.outbox_repo.clone(),
+            TokenCipher::new(&config.jwt.secret),
+        ));
 
         Arc::new(AppState {
             config,
@@ -794,6 +1020,7 @@ mod tests {
             phantom_email: std::marker::PhantomData::<LogEmailService>,
             audit_service,
             comms_service,
+            webhook_service,
             user_repo: storage.user_repo.clone(),
             session_repo: storage.session_repo.clone(),
             nonce_repo: storage.nonce_repo.clone(),
@@ -809,6 +1036,7 @@ mod tests {
             policy_repo: storage.policy_repo.clone(),
             outbox_repo: storage.outbox_repo.clone(),
             api_key_repo: storage.api_key_repo.clone(),
+            api_key_rate_limiter: crate::middleware::rate_limit::RateLimitStore::new(),
             wallet_material_repo: storage.wallet_material_repo.clone(),
             derived_wallet_repo: storage.derived_wallet_repo.clone(),
             wallet_rotation_history_repo: storage.wallet_rotation_history_repo.clone(),
@@ -872,9 +1100,16 @@ mod tests {
             stripe_customer_id: None,
             auth_methods: vec![],
             is_system_admin: false,
+            is_service_account: false,
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
         let user = state.user_repo.create(user).await.unwrap();
 
@@ -885,6 +1120,137 @@ mod tests {
         (user.id, api_key)
     }
 
+    #[tokio::test]
+    async fn test_restore_cascaded_orgs_only_restores_account_deletions() {
+        use crate::repositories::{MembershipEntity, OrgEntity};
+
+        let state = build_state(base_config(None));
+        let (user_id, _) = setup_user_with_api_key(&state, "owner@example.com", true).await;
+        let purge_after = Utc::now() + chrono::Duration::days(30);
+        let mut org_ids = Vec::new();
+        for slug in ["cascaded", "separate"] {
+            let org = state
+                .org_repo
+                .create(OrgEntity::new(slug.into(), slug.into(), user_id, false))
+                .await
+                .unwrap();
+            state
+                .membership_repo
+                .create(MembershipEntity::new(user_id, org.id, OrgRole::Owner))
+                .await
+                .unwrap();
+            org_ids.push(org.id);
+        }
+        state
+            .org_repo
+            .schedule_deletion(org_ids[0], purge_after)
+            .await
+            .unwrap();
+        // Deleted on its own before the account
+        state
+            .org_repo
+            .schedule_deletion(org_ids[1], purge_after - chrono::Duration::days(1))
+            .await
+            .unwrap();
+        state
+            .user_repo
+            .set_status(
+                user_id,
+                UserStatus::PendingDeletion,
+                None,
+                Some(purge_after),
+            )
+            .await
+            .unwrap();
+
+        let user = state.user_repo.find_by_id(user_id).await.unwrap().unwrap();
+        restore_cascaded_orgs(&state, &user, Uuid::new_v4(), &HeaderMap::new())
+            .await
+            .unwrap();
+
+        let cascaded = state
+            .org_repo
+            .find_by_id(org_ids[0])
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!cascaded.is_pending_deletion());
+        let separate = state
+            .org_repo
+            .find_by_id(org_ids[1])
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(separate.is_pending_deletion());
+    }
+
+    #[tokio::test]
+    async fn test_banning_pending_deletion_keeps_cascaded_orgs_deleted() {
+        use crate::repositories::{MembershipEntity, OrgEntity};
+
+        let state = build_state(base_config(None));
+        let (admin_id, api_key) = setup_user_with_api_key(&state, "admin@example.com", true).await;
+        state
+            .user_repo
+            .set_system_admin(admin_id, true)
+            .await
+            .unwrap();
+        let (user_id, _) = setup_user_with_api_key(&state, "owner@example.com", true).await;
+        let purge_after = Utc::now() + chrono::Duration::days(30);
+        let org = state
+            .org_repo
+            .create(OrgEntity::new(
+                "cascaded".into(),
+                "cascaded".into(),
+                user_id,
+                false,
+            ))
+            .await
+            .unwrap();
+        state
+            .membership_repo
+            .create(MembershipEntity::new(user_id, org.id, OrgRole::Owner))
+            .await
+            .unwrap();
+        state
+            .org_repo
+            .schedule_deletion(org.id, purge_after)
+            .await
+            .unwrap();
+        state
+            .user_repo
+            .set_status(
+                user_id,
+                UserStatus::PendingDeletion,
+                None,
+                Some(purge_after),
+            )
+            .await
+            .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", api_key)).unwrap(),
+        );
+        let Json(response) = set_user_status(
+            State(state.clone()),
+            headers,
+            Path(user_id),
+            Json(SetUserStatusRequest {
+                status: "banned".into(),
+                reason: None,
+                expires_at: None,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status, UserStatus::Banned.as_str());
+
+        let org = state.org_repo.find_by_id(org.id).await.unwrap().unwrap();
+        assert!(org.is_pending_deletion());
+    }
+
     #[tokio::test]
     async fn test_bootstrap_admin_requires_verified_email() {
         let config = base_config(Some("admin@example.com".to_string()));
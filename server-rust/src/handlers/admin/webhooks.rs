@@ -0,0 +1,545 @@
+//! Admin webhook endpoint management handlers
+//!
+//! GET    /admin/webhooks/events                           - List subscribable events
+//! GET    /admin/webhooks                                  - List endpoints
+//! POST   /admin/webhooks                                  - Create an endpoint
+//! GET    /admin/webhooks/{id}                             - Get an endpoint
+//! PATCH  /admin/webhooks/{id}                             - Update an endpoint
+//! DELETE /admin/webhooks/{id}                             - Delete an endpoint
+//! GET    /admin/webhooks/{id}/deliveries                  - List an endpoint's deliveries
+//! POST   /admin/webhooks/deliveries/{delivery_id}/replay  - Replay a delivery
+//!
+//! Signing secrets are generated by the server and only returned when an
+//! endpoint is created or its secret is rotated.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::{validate_webhook_destination, AuthCallback, WebhookEvent};
+use crate::errors::AppError;
+use crate::repositories::pagination::{cap_limit, cap_offset};
+use crate::repositories::{
+    generate_webhook_secret, AuditEventType, AuditLogBuilder, WebhookDeliveryEntity,
+    WebhookDeliveryStatus, WebhookEndpointEntity,
+};
+use crate::services::EmailService;
+use crate::utils::extract_client_ip;
+use crate::AppState;
+
+use super::users::validate_system_admin;
+
+/// Maximum length of an endpoint description
+const MAX_DESCRIPTION_LEN: usize = 500;
+
+/// Webhook endpoint response (excludes the secret)
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEndpointResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub description: Option<String>,
+    /// Subscribed events; empty means every event
+    pub events: Vec<String>,
+    pub enabled: bool,
+    pub consecutive_failures: u32,
+    pub disabled_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&WebhookEndpointEntity> for WebhookEndpointResponse {
+    fn from(e: &WebhookEndpointEntity) -> Self {
+        Self {
+            id: e.id,
+            url: e.url.clone(),
+            description: e.description.clone(),
+            events: e.events.clone(),
+            enabled: e.enabled,
+            consecutive_failures: e.consecutive_failures,
+            disabled_reason: e.disabled_reason.clone(),
+            created_at: e.created_at,
+            updated_at: e.updated_at,
+        }
+    }
+}
+
+/// Endpoint response that includes a newly generated secret
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEndpointWithSecretResponse {
+    #[serde(flatten)]
+    pub endpoint: WebhookEndpointResponse,
+    /// Signing secret; only returned when created or rotated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// Response for listing endpoints
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhookEndpointsResponse {
+    pub endpoints: Vec<WebhookEndpointResponse>,
+    pub total: u64,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+/// Webhook delivery log entry response
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryResponse {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub replay_of: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<&WebhookDeliveryEntity> for WebhookDeliveryResponse {
+    fn from(d: &WebhookDeliveryEntity) -> Self {
+        Self {
+            id: d.id,
+            endpoint_id: d.endpoint_id,
+            event_type: d.event_type.clone(),
+            payload: d.payload.clone(),
+            status: d.status,
+            attempts: d.attempts,
+            response_status: d.response_status,
+            last_error: d.last_error.clone(),
+            replay_of: d.replay_of,
+            created_at: d.created_at,
+            last_attempt_at: d.last_attempt_at,
+            delivered_at: d.delivered_at,
+        }
+    }
+}
+
+/// Response for listing deliveries
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhookDeliveriesResponse {
+    pub deliveries: Vec<WebhookDeliveryResponse>,
+    pub total: u64,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+/// Response for the event catalog
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEventsResponse {
+    pub events: Vec<&'static str>,
+}
+
+/// Pagination query params
+#[derive(Debug, Deserialize)]
+pub struct ListQueryParams {
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub offset: u32,
+}
+
+fn default_limit() -> u32 {
+    50
+}
+
+/// Request to create an endpoint
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookEndpointRequest {
+    pub url: String,
+    pub description: Option<String>,
+    /// Events to subscribe to; omit or leave empty for every event
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// Request to update an endpoint
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookEndpointRequest {
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub events: Option<Vec<String>>,
+    /// Re-enabling an endpoint also clears its failure count
+    pub enabled: Option<bool>,
+    /// Generate a new signing secret
+    #[serde(default)]
+    pub rotate_secret: bool,
+}
+
+/// Response for delete operations
+#[derive(Debug, Serialize)]
+pub struct DeleteResponse {
+    pub success: bool,
+}
+
+/// Reject event names that are not in the catalog and drop duplicates
+fn validate_events(events: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut validated: Vec<String> = Vec::with_capacity(events.len());
+    for event in events {
+        if WebhookEvent::from_str(&event).is_none() {
+            return Err(AppError::Validation(format!(
+                "Unknown webhook event: {}",
+                event
+            )));
+        }
+        if !validated.contains(&event) {
+            validated.push(event);
+        }
+    }
+    Ok(validated)
+}
+
+fn validate_description(description: &Option<String>) -> Result<(), AppError> {
+    if description
+        .as_ref()
+        .is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_LEN)
+    {
+        return Err(AppError::Validation(format!(
+            "Description must be at most {} characters",
+            MAX_DESCRIPTION_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Require HTTPS in production and reject private or internal destinations
+async fn validate_endpoint_url(url: &str, environment: &str) -> Result<(), AppError> {
+    let parsed =
+        url::Url::parse(url).map_err(|_| AppError::Validation("Invalid webhook URL".into()))?;
+    let production =
+        environment.eq_ignore_ascii_case("production") || environment.eq_ignore_ascii_case("prod");
+    if production && parsed.scheme() != "https" {
+        return Err(AppError::Validation(
+            "Webhook URL must use https in production".into(),
+        ));
+    }
+    validate_webhook_destination(url)
+        .await
+        .map_err(|e| match e {
+            AppError::Internal(e) => AppError::Validation(e.to_string()),
+            other => other,
+        })
+}
+
+async fn find_endpoint<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    id: Uuid,
+) -> Result<WebhookEndpointEntity, AppError> {
+    state
+        .storage
+        .webhook_repo
+        .find_endpoint(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Webhook endpoint not found".into()))
+}
+
+async fn audit<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    event_type: AuditEventType,
+    admin_id: Uuid,
+    target: (&str, Uuid),
+    metadata: serde_json::Value,
+) {
+    let mut builder = AuditLogBuilder::new(event_type)
+        .actor(admin_id)
+        .target(target.0, target.1)
+        .metadata(metadata);
+    if let Some(ip) = extract_client_ip(headers, state.config.server.trust_proxy) {
+        builder = builder.ip(&ip);
+    }
+    if let Some(ua) = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+    {
+        builder = builder.user_agent(ua);
+    }
+    state.audit_service.log_or_warn(builder.build()).await;
+}
+
+/// GET /admin/webhooks/events - List events endpoints can subscribe to
+pub async fn list_webhook_events<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+) -> Result<Json<WebhookEventsResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    Ok(Json(WebhookEventsResponse {
+        events: WebhookEvent::ALL.iter().map(|e| e.as_str()).collect(),
+    }))
+}
+
+/// GET /admin/webhooks - List webhook endpoints
+pub async fn list_webhook_endpoints<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Query(params): Query<ListQueryParams>,
+) -> Result<Json<ListWebhookEndpointsResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    let limit = cap_limit(params.limit);
+    let offset = cap_offset(params.offset);
+    let repo = &state.storage.webhook_repo;
+    let (endpoints, total) =
+        tokio::join!(repo.list_endpoints(limit, offset), repo.count_endpoints());
+
+    Ok(Json(ListWebhookEndpointsResponse {
+        endpoints: endpoints?
+            .iter()
+            .map(WebhookEndpointResponse::from)
+            .collect(),
+        total: total?,
+        limit,
+        offset,
+    }))
+}
+
+/// POST /admin/webhooks - Create a webhook endpoint
+///
+/// The signing secret is generated here and returned once.
+pub async fn create_webhook_endpoint<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateWebhookEndpointRequest>,
+) -> Result<Json<WebhookEndpointWithSecretResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let url = request.url.trim().to_string();
+    validate_endpoint_url(&url, &state.config.notification.environment).await?;
+    validate_description(&request.description)?;
+    let events = validate_events(request.events)?;
+
+    let secret = generate_webhook_secret();
+    let endpoint = WebhookEndpointEntity::new(
+        url,
+        request.description,
+        state.webhook_service.encrypt_secret(&secret)?,
+        events,
+        Some(admin_id),
+    );
+    let created = state.storage.webhook_repo.create_endpoint(endpoint).await?;
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::WebhookEndpointCreated,
+        admin_id,
+        ("webhook_endpoint", created.id),
+        serde_json::json!({ "url": created.url, "events": created.events }),
+    )
+    .await;
+
+    Ok(Json(WebhookEndpointWithSecretResponse {
+        endpoint: WebhookEndpointResponse::from(&created),
+        secret: Some(secret),
+    }))
+}
+
+/// GET /admin/webhooks/{id} - Get a webhook endpoint
+pub async fn get_webhook_endpoint<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WebhookEndpointResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    let endpoint = find_endpoint(&state, id).await?;
+    Ok(Json(WebhookEndpointResponse::from(&endpoint)))
+}
+
+/// PATCH /admin/webhooks/{id} - Update a webhook endpoint
+pub async fn update_webhook_endpoint<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateWebhookEndpointRequest>,
+) -> Result<Json<WebhookEndpointWithSecretResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let mut endpoint = find_endpoint(&state, id).await?;
+
+    if let Some(url) = request.url {
+        let url = url.trim().to_string();
+        validate_endpoint_url(&url, &state.config.notification.environment).await?;
+        endpoint.url = url;
+    }
+    if request.description.is_some() {
+        validate_description(&request.description)?;
+        endpoint.description = request.description;
+    }
+    if let Some(events) = request.events {
+        endpoint.events = validate_events(events)?;
+    }
+    if let Some(enabled) = request.enabled {
+        endpoint.enabled = enabled;
+    }
+    let secret = if request.rotate_secret {
+        let secret = generate_webhook_secret();
+        endpoint.secret_encrypted = state.webhook_service.encrypt_secret(&secret)?;
+        Some(secret)
+    } else {
+        None
+    };
+
+    let updated = state.storage.webhook_repo.update_endpoint(endpoint).await?;
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::WebhookEndpointUpdated,
+        admin_id,
+        ("webhook_endpoint", updated.id),
+        serde_json::json!({
+            "url": updated.url,
+            "events": updated.events,
+            "enabled": updated.enabled,
+            "secretRotated": secret.is_some(),
+        }),
+    )
+    .await;
+
+    Ok(Json(WebhookEndpointWithSecretResponse {
+        endpoint: WebhookEndpointResponse::from(&updated),
+        secret,
+    }))
+}
+
+/// DELETE /admin/webhooks/{id} - Delete a webhook endpoint and its delivery log
+pub async fn delete_webhook_endpoint<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+) -> Result<Json<DeleteResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let endpoint = find_endpoint(&state, id).await?;
+    state.storage.webhook_repo.delete_endpoint(id).await?;
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::WebhookEndpointDeleted,
+        admin_id,
+        ("webhook_endpoint", id),
+        serde_json::json!({ "url": endpoint.url }),
+    )
+    .await;
+
+    Ok(Json(DeleteResponse { success: true }))
+}
+
+/// GET /admin/webhooks/{id}/deliveries - List an endpoint's deliveries, newest first
+pub async fn list_webhook_deliveries<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ListQueryParams>,
+) -> Result<Json<ListWebhookDeliveriesResponse>, AppError> {
+    validate_system_admin(&state, &headers).await?;
+
+    find_endpoint(&state, id).await?;
+
+    let limit = cap_limit(params.limit);
+    let offset = cap_offset(params.offset);
+    let repo = &state.storage.webhook_repo;
+    let (deliveries, total) = tokio::join!(
+        repo.list_deliveries(id, limit, offset),
+        repo.count_deliveries(id)
+    );
+
+    Ok(Json(ListWebhookDeliveriesResponse {
+        deliveries: deliveries?
+            .iter()
+            .map(WebhookDeliveryResponse::from)
+            .collect(),
+        total: total?,
+        limit,
+        offset,
+    }))
+}
+
+/// POST /admin/webhooks/deliveries/{delivery_id}/replay - Send a delivery again
+///
+/// Queues a new delivery with the original payload; the original entry is kept.
+pub async fn replay_webhook_delivery<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(delivery_id): Path<Uuid>,
+) -> Result<Json<WebhookDeliveryResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+
+    let replay = state.webhook_service.replay(delivery_id).await?;
+
+    audit(
+        &state,
+        &headers,
+        AuditEventType::WebhookDeliveryReplayed,
+        admin_id,
+        ("webhook_delivery", replay.id),
+        serde_json::json!({
+            "endpointId": replay.endpoint_id,
+            "replayOf": delivery_id,
+        }),
+    )
+    .await;
+
+    Ok(Json(WebhookDeliveryResponse::from(&replay)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_events_rejects_unknown_and_dedupes() {
+        let events = validate_events(vec![
+            "org_created".into(),
+            "mfa_enabled".into(),
+            "org_created".into(),
+        ])
+        .unwrap();
+        assert_eq!(events, vec!["org_created", "mfa_enabled"]);
+
+        assert!(validate_events(vec!["org.created".into()]).is_err());
+        assert!(validate_events(vec![]).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validate_endpoint_url() {
+        assert!(validate_endpoint_url("https://8.8.8.8/hook", "production")
+            .await
+            .is_ok());
+        assert!(matches!(
+            validate_endpoint_url("http://8.8.8.8/hook", "production").await,
+            Err(AppError::Validation(_))
+        ));
+        assert!(validate_endpoint_url("http://8.8.8.8/hook", "development")
+            .await
+            .is_ok());
+        assert!(matches!(
+            validate_endpoint_url("https://10.0.0.5/hook", "development").await,
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            validate_endpoint_url("not a url", "development").await,
+            Err(AppError::Validation(_))
+        ));
+    }
+}
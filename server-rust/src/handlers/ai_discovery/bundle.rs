@@ -583,6 +583,7 @@ System administration operations. Requires admin role.
 |--------|------|-------------|
 | GET | {base}/admin/audit-logs | Get system audit logs |
 | GET | {base}/admin/orgs/{{id}}/audit-logs | Get org audit logs |
+| GET | {base}/admin/audit/verify | Verify the audit hash chain (optional `orgId`) |
 
 ## Example: List Users
 ```
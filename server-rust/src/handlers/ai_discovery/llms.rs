@@ -69,6 +69,9 @@ These require `is_system_admin: true` on the user account.
 | DELETE | {base}/admin/users/{{id}} | Delete user |
 | POST | {base}/admin/users/{{id}}/system-admin | Set system admin status |
 | POST | {base}/admin/users/{{id}}/force-password-reset | Force password reset |
+| POST | {base}/admin/users/{{id}}/status | Suspend, ban or reactivate user |
+| GET | {base}/users/{{id}}/metadata | Get user metadata (`users:read`) |
+| PATCH | {base}/users/{{id}}/metadata | Merge-patch user metadata (`users:write`) |
 
 ## User Credits & Deposits
 | Method | Path | Description |
@@ -107,11 +110,32 @@ These require `is_system_admin: true` on the user account.
 | PATCH | {base}/admin/sso-providers/{{id}} | Update provider |
 | DELETE | {base}/admin/sso-providers/{{id}} | Delete provider |
 
+## Webhook Endpoints
+| Method | Path | Description |
+|--------|------|-------------|
+| GET | {base}/admin/webhooks/events | List subscribable events |
+| GET | {base}/admin/webhooks | List webhook endpoints |
+| POST | {base}/admin/webhooks | Create endpoint (secret returned once) |
+| GET | {base}/admin/webhooks/{{id}} | Get endpoint details |
+| PATCH | {base}/admin/webhooks/{{id}} | Update endpoint, re-enable or rotate secret |
+| DELETE | {base}/admin/webhooks/{{id}} | Delete endpoint |
+| GET | {base}/admin/webhooks/{{id}}/deliveries | List delivery log |
+| POST | {base}/admin/webhooks/deliveries/{{id}}/replay | Replay a delivery |
+
+Deliveries are signed like the legacy webhook (`X-Cedros-Signature` is the
+HMAC-SHA256 of `{{timestamp}}.{{body}}`) and retried with backoff. Endpoints
+are disabled after 25 consecutive failures.
+
 ## Audit Logs
 | Method | Path | Description |
 |--------|------|-------------|
 | GET | {base}/admin/audit-logs | Get system audit logs |
 | GET | {base}/admin/orgs/{{id}}/audit-logs | Get org audit logs |
+| GET | {base}/admin/audit/verify | Verify the audit hash chain (optional `orgId`) |
+
+Audit entries are hash-chained, globally and per org, and the chain head is
+periodically signed. Verification reports the first missing or modified entry
+in `brokenLink`. The same check runs offline with `cedros-login-server verify-audit`.
 
 ## Dashboard Permissions
 | Method | Path | Description |
@@ -277,7 +301,12 @@ Authorization: Bearer ck_live_abc123...
 | Method | Path | Description |
 |--------|------|-------------|
 | GET | {base}/user | Get current user |
-| PATCH | {base}/me | Update profile |
+| PATCH | {base}/me | Update profile and unsafe metadata |
+| DELETE | {base}/me | Schedule account deletion (step-up required) |
+| GET | {base}/me/export | Download account data (ZIP) |
+| POST | {base}/me/email | Request email change (step-up required) |
+| POST | {base}/me/email/confirm | Confirm email change with token |
+| POST | {base}/me/email/revert | Revert email change with token |
 | POST | {base}/change-password | Change password |
 | GET | {base}/user/api-key | Get API key |
 | POST | {base}/user/api-key/regenerate | Regenerate API key |
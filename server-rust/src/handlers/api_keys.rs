@@ -14,11 +14,56 @@ use crate::models::{
     ApiKeyListResponse, ApiKeyResponse, CreateApiKeyRequest, CreateApiKeyResponse, MessageResponse,
     RegenerateApiKeyResponse, ValidateApiKeyRequest, ValidateApiKeyResponse,
 };
-use crate::repositories::{generate_api_key, ApiKeyEntity};
+use crate::repositories::{generate_api_key, validate_api_key_restrictions, ApiKeyEntity};
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::{authenticate, AuthenticatedUser};
 use crate::AppState;
 
+/// Maximum per-key rate limit (requests per minute)
+const MAX_RATE_LIMIT_PER_MINUTE: u32 = 100_000;
+
+/// Maximum API keys per user (service accounts included)
+pub(crate) const MAX_KEYS_PER_USER: usize = 10;
+
+/// Reject key management through a restricted API key, by a service account
+/// or during impersonation.
+///
+/// Otherwise a scoped, org-bound or expiring key could mint an unrestricted one,
+/// and an impersonator a key that outlives the session. Service account keys
+/// are managed by org admins instead.
+async fn ensure_unrestricted_caller<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    auth: &AuthenticatedUser,
+) -> Result<(), AppError> {
+    auth.ensure_not_impersonated("create API keys")?;
+    if state
+        .storage
+        .service_account_repo
+        .find_by_id(auth.user_id)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::Forbidden(
+            "Service account API keys are managed by organization admins".into(),
+        ));
+    }
+    let Some(raw_key) = auth.raw_api_key.as_deref() else {
+        return Ok(());
+    };
+    let restricted = state
+        .api_key_repo
+        .find_by_key(raw_key)
+        .await?
+        .map(|key| key.is_restricted())
+        .unwrap_or(true);
+    if restricted {
+        return Err(AppError::Forbidden(
+            "Restricted API keys cannot create or regenerate API keys".into(),
+        ));
+    }
+    Ok(())
+}
+
 /// GET /user/api-key - Get current user's API key metadata
 ///
 /// Returns the API key metadata (prefix, created_at, last_used_at) but NOT the key itself.
@@ -50,6 +95,7 @@ pub async fn regenerate_api_key<C: AuthCallback, E: EmailService>(
 ) -> Result<Json<RegenerateApiKeyResponse>, AppError> {
     // Authenticate via JWT or API key
     let auth = authenticate(&state, &headers).await?;
+    ensure_unrestricted_caller(&state, &auth).await?;
 
     // S-18: Create new key before deleting old ones.
     // If creation fails, user retains the old key (no keyless window).
@@ -98,6 +144,15 @@ pub async fn validate_api_key<C: AuthCallback, E: EmailService>(
         }
     };
 
+    if api_key.is_expired() {
+        return Ok(Json(ValidateApiKeyResponse {
+            valid: false,
+            user_id: None,
+            user_email: None,
+            message: Some("API key has expired".to_string()),
+        }));
+    }
+
     // Update last_used_at
     state.api_key_repo.update_last_used(api_key.id).await?;
 
@@ -124,6 +179,8 @@ pub async fn validate_api_key<C: AuthCallback, E: EmailService>(
 }
 
 /// GET /user/api-keys - List all API keys for current user
+///
+/// Includes each key's scopes, org binding, expiry and last use.
 pub async fn list_api_keys<C: AuthCallback, E: EmailService>(
     State(state): State<Arc<AppState<C, E>>>,
     headers: HeaderMap,
@@ -135,27 +192,24 @@ pub async fn list_api_keys<C: AuthCallback, E: EmailService>(
 }
 
 /// POST /user/api-keys - Create a new API key with a label
+///
+/// The key may optionally be restricted to scopes, bound to one org the user
+/// belongs to, given an expiry, an IP/CIDR allowlist and a per-minute rate limit.
 pub async fn create_api_key<C: AuthCallback, E: EmailService>(
     State(state): State<Arc<AppState<C, E>>>,
     headers: HeaderMap,
     Json(req): Json<CreateApiKeyRequest>,
 ) -> Result<Json<CreateApiKeyResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    ensure_unrestricted_caller(&state, &auth).await?;
 
-    let label = req.label.trim().to_string();
-    // S-32: Use chars().count() for Unicode-aware length check
-    if label.is_empty() || label.chars().count() > 100 {
-        return Err(AppError::Validation(
-            "Label must be 1-100 characters".into(),
-        ));
-    }
+    let label = validate_label(&req.label)?;
 
     // S-02: Enforce per-user API key limit to prevent resource exhaustion.
     // S-19: This check-then-insert has a small TOCTOU window where concurrent
     // requests could both pass the check. The consequence is at most +1 extra key,
     // not a security issue. A DB unique constraint or serializable transaction
     // would eliminate the race but is not worth the complexity.
-    const MAX_KEYS_PER_USER: usize = 10;
     let existing = state.api_key_repo.find_by_user_id(auth.user_id).await?;
     if existing.len() >= MAX_KEYS_PER_USER {
         return Err(AppError::Validation(format!(
@@ -164,8 +218,17 @@ pub async fn create_api_key<C: AuthCallback, E: EmailService>(
         )));
     }
 
-    let raw_key = generate_api_key();
-    let entity = ApiKeyEntity::new(auth.user_id, &raw_key, &label);
+    if let Some(org_id) = req.org_id {
+        state
+            .membership_repo
+            .find_by_user_and_org(auth.user_id, org_id)
+            .await?
+            .ok_or(AppError::Forbidden(
+                "You are not a member of this organization".into(),
+            ))?;
+    }
+
+    let (raw_key, entity) = build_restricted_api_key(auth.user_id, &label, req)?;
     let created = state.api_key_repo.create(entity).await?;
 
     Ok(Json(CreateApiKeyResponse {
@@ -174,10 +237,69 @@ pub async fn create_api_key<C: AuthCallback, E: EmailService>(
         key_prefix: created.key_prefix,
         label: created.label,
         created_at: created.created_at,
+        scopes: created.scopes,
+        org_id: created.org_id,
+        expires_at: created.expires_at,
         message: "Store this key securely. It cannot be retrieved again.".to_string(),
     }))
 }
 
+/// S-32: Trim and check a key label (1-100 characters, Unicode-aware)
+pub(crate) fn validate_label(label: &str) -> Result<String, AppError> {
+    let label = label.trim().to_string();
+    if label.is_empty() || label.chars().count() > 100 {
+        return Err(AppError::Validation(
+            "Label must be 1-100 characters".into(),
+        ));
+    }
+    Ok(label)
+}
+
+/// Validate the restrictions in a create request and build the key entity.
+///
+/// Org membership is checked by the caller; the org binding is copied as-is.
+/// Returns the raw key (shown once) and the entity to store.
+pub(crate) fn build_restricted_api_key(
+    user_id: Uuid,
+    label: &str,
+    req: CreateApiKeyRequest,
+) -> Result<(String, ApiKeyEntity), AppError> {
+    let mut scopes = req.scopes;
+    scopes.sort();
+    scopes.dedup();
+    let allowed_ips: Vec<String> = req
+        .allowed_ips
+        .iter()
+        .map(|ip| ip.trim().to_string())
+        .collect();
+    validate_api_key_restrictions(&scopes, &allowed_ips)?;
+
+    if let Some(expires_at) = req.expires_at {
+        if expires_at <= chrono::Utc::now() {
+            return Err(AppError::Validation(
+                "expiresAt must be in the future".into(),
+            ));
+        }
+    }
+    if let Some(limit) = req.rate_limit_per_minute {
+        if limit == 0 || limit > MAX_RATE_LIMIT_PER_MINUTE {
+            return Err(AppError::Validation(format!(
+                "rateLimitPerMinute must be between 1 and {}",
+                MAX_RATE_LIMIT_PER_MINUTE
+            )));
+        }
+    }
+
+    let raw_key = generate_api_key();
+    let mut entity = ApiKeyEntity::new(user_id, &raw_key, label);
+    entity.scopes = scopes;
+    entity.org_id = req.org_id;
+    entity.expires_at = req.expires_at;
+    entity.allowed_ips = allowed_ips;
+    entity.rate_limit_per_minute = req.rate_limit_per_minute;
+    Ok((raw_key, entity))
+}
+
 /// DELETE /user/api-keys/{id} - Delete a specific API key
 ///
 /// When deleting a key that has a linked wallet, the wallet becomes orphaned
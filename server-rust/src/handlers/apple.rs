@@ -6,20 +6,21 @@ use chrono::{Duration, Utc};
 use sqlx::PgPool;
 use std::sync::Arc;
 
-use crate::callback::{AuthCallback, AuthCallbackPayload};
+use crate::callback::{AuthCallback, AuthCallbackPayload, ClaimsTrigger, TokenClaimsPayload};
 use crate::errors::AppError;
 use crate::handlers::auth::{
-    call_authenticated_callback_with_timeout, call_registered_callback_with_timeout,
+    call_authenticated_callback_with_timeout, call_claims_callback_with_timeout,
+    call_registered_callback_with_timeout,
 };
 use crate::models::{AppleAuthRequest, AuthMethod, AuthResponse};
 use crate::repositories::{
     generate_api_key, normalize_email, ApiKeyEntity, AuditEventType, MembershipEntity,
-    SessionEntity, UserEntity,
+    SessionEntity, UserEntity, UserStatus,
 };
 use crate::services::EmailService;
 use crate::utils::{
     build_json_response_with_cookies, extract_client_ip_with_fallback, get_default_org_context,
-    hash_refresh_token, resolve_org_assignment, user_entity_to_auth_user, PeerIp,
+    hash_refresh_token, metadata_claims, resolve_org_assignment, user_entity_to_auth_user, PeerIp,
 };
 use crate::AppState;
 
@@ -219,9 +220,16 @@ pub async fn apple_auth<C: AuthCallback, E: EmailService>(
             stripe_customer_id: None,
             auth_methods: vec![AuthMethod::Apple],
             is_system_admin: false,
+            is_service_account: false,
             created_at: now,
             updated_at: now,
             last_login_at: Some(now),
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
         let org_assignment = resolve_org_assignment(&state, user.id).await?;
         let membership = MembershipEntity::new(user.id, org_assignment.org_id, org_assignment.role);
@@ -251,9 +259,15 @@ pub async fn apple_auth<C: AuthCallback, E: EmailService>(
         (user, true, Some(raw_api_key))
     };
 
+    user.ensure_active()?;
+
     // Get user's memberships to find default org context
     let memberships = state.membership_repo.find_by_user(user.id).await?;
-    let token_context = get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    let mut token_context =
+        get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    token_context.metadata = metadata_claims(&state, &user).await;
+    let claims_payload = TokenClaimsPayload::new(user.id, &token_context, ClaimsTrigger::Login);
+    token_context.ext = call_claims_callback_with_timeout(&state.callback, &claims_payload).await;
 
     // Create session with org context
     let session_id = uuid::Uuid::new_v4();
@@ -39,7 +39,8 @@ use chrono::{Duration, Utc};
 use serde_json::json;
 use std::sync::Arc;
 
-use crate::callback::{AuthCallback, AuthCallbackPayload};
+use super::call_claims_callback_with_timeout;
+use crate::callback::{AuthCallback, AuthCallbackPayload, ClaimsTrigger, TokenClaimsPayload};
 use crate::errors::AppError;
 use crate::models::{AuthMethod, AuthResponse, LoginRequest, MfaLoginRequest};
 use crate::repositories::{
@@ -49,7 +50,8 @@ use crate::repositories::{
 use crate::services::EmailService;
 use crate::utils::{
     build_json_response_with_cookies, extract_client_ip_with_fallback, get_default_org_context,
-    hash_refresh_token, is_new_device, user_entity_to_auth_user, DeviceInfo, PeerIp,
+    hash_refresh_token, is_new_device, metadata_claims, user_entity_to_auth_user, DeviceInfo,
+    PeerIp,
 };
 use crate::AppState;
 
@@ -207,6 +209,9 @@ pub async fn login<C: AuthCallback, E: EmailService>(
         return Err(AppError::InvalidCredentials);
     }
 
+    // Checked after the password so the account state is only revealed to its owner
+    user.ensure_active()?;
+
     // Successful login - clear failed attempts
     let _ = state
         .login_attempt_repo
@@ -494,8 +499,15 @@ async fn complete_login_flow<C: AuthCallback, E: EmailService>(
     ),
     AppError,
 > {
+    // Also covers the MFA step, in case the status changed after the challenge
+    user.ensure_active()?;
+
     let memberships = state.membership_repo.find_by_user(user.id).await?;
-    let token_context = get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    let mut token_context =
+        get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    token_context.metadata = metadata_claims(state, user).await;
+    let claims_payload = TokenClaimsPayload::new(user.id, &token_context, ClaimsTrigger::Login);
+    token_context.ext = call_claims_callback_with_timeout(&state.callback, &claims_payload).await;
 
     let session_id = uuid::Uuid::new_v4();
     let token_pair =
@@ -14,7 +14,10 @@ use std::sync::Arc;
 use std::time::Duration as StdDuration;
 use tokio::time::timeout;
 
-use crate::callback::{AuthCallback, AuthCallbackPayload};
+use crate::callback::{
+    AuthCallback, AuthCallbackPayload, TokenClaimsPayload, UserStatusChangePayload,
+};
+use crate::services::sanitize_custom_claims;
 
 const CALLBACK_TIMEOUT_SECS: u64 = 2;
 
@@ -106,6 +109,68 @@ pub(crate) async fn call_logout_callback_with_timeout<C: AuthCallback>(
     };
 }
 
+pub(crate) async fn call_status_changed_callback_with_timeout<C: AuthCallback>(
+    callback: &Arc<C>,
+    payload: &UserStatusChangePayload,
+) {
+    match timeout(
+        StdDuration::from_secs(CALLBACK_TIMEOUT_SECS),
+        callback.on_user_status_changed(payload),
+    )
+    .await
+    {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => {
+            tracing::warn!(
+                user_id = %payload.user_id,
+                status = payload.status.as_str(),
+                error = %error,
+                "Status change callback failed"
+            );
+        }
+        Err(_elapsed) => {
+            tracing::warn!(
+                user_id = %payload.user_id,
+                timeout_secs = CALLBACK_TIMEOUT_SECS,
+                "Status change callback timed out"
+            );
+        }
+    };
+}
+
+/// Run the claims-enrichment hook and sanitize its result for `TokenContext::ext`
+pub(crate) async fn call_claims_callback_with_timeout<C: AuthCallback>(
+    callback: &Arc<C>,
+    payload: &TokenClaimsPayload,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    match timeout(
+        StdDuration::from_secs(CALLBACK_TIMEOUT_SECS),
+        callback.enrich_claims(payload),
+    )
+    .await
+    {
+        Ok(Ok(claims)) => claims.and_then(sanitize_custom_claims),
+        Ok(Err(error)) => {
+            tracing::warn!(
+                user_id = %payload.user_id,
+                trigger = ?payload.trigger,
+                error = %error,
+                "Claims callback failed"
+            );
+            None
+        }
+        Err(_elapsed) => {
+            tracing::warn!(
+                user_id = %payload.user_id,
+                trigger = ?payload.trigger,
+                timeout_secs = CALLBACK_TIMEOUT_SECS,
+                "Claims callback timed out"
+            );
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +219,52 @@ mod tests {
         async fn on_logout(&self, _user_id: &str) -> Result<(), AppError> {
             Err(AppError::Internal(anyhow::anyhow!("callback failed")))
         }
+
+        async fn enrich_claims(
+            &self,
+            _payload: &TokenClaimsPayload,
+        ) -> Result<Option<serde_json::Map<String, Value>>, AppError> {
+            Err(AppError::Internal(anyhow::anyhow!("callback failed")))
+        }
+    }
+
+    struct ClaimsCallback;
+
+    #[async_trait]
+    impl AuthCallback for ClaimsCallback {
+        async fn on_authenticated(
+            &self,
+            _payload: &AuthCallbackPayload,
+        ) -> Result<Value, AppError> {
+            Ok(Value::Null)
+        }
+
+        async fn on_registered(&self, _payload: &AuthCallbackPayload) -> Result<Value, AppError> {
+            Ok(Value::Null)
+        }
+
+        async fn on_logout(&self, _user_id: &str) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        async fn enrich_claims(
+            &self,
+            payload: &TokenClaimsPayload,
+        ) -> Result<Option<serde_json::Map<String, Value>>, AppError> {
+            let mut claims = serde_json::Map::new();
+            claims.insert("sub".into(), Value::from("forged"));
+            claims.insert("trigger".into(), serde_json::json!(payload.trigger));
+            Ok(Some(claims))
+        }
+    }
+
+    fn claims_payload() -> TokenClaimsPayload {
+        TokenClaimsPayload {
+            user_id: Uuid::new_v4(),
+            org_id: None,
+            role: None,
+            trigger: crate::callback::ClaimsTrigger::OrgSwitch,
+        }
     }
 
     fn test_payload() -> AuthCallbackPayload {
@@ -168,6 +279,8 @@ mod tests {
                 email_verified: true,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                public_metadata: Default::default(),
+                unsafe_metadata: Default::default(),
             },
             method: crate::models::AuthMethod::Email,
             is_new_user: false,
@@ -208,4 +321,21 @@ mod tests {
         .await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_claims_callback_strips_reserved_claims() {
+        let callback = Arc::new(ClaimsCallback);
+        let claims = call_claims_callback_with_timeout(&callback, &claims_payload())
+            .await
+            .unwrap();
+        assert!(!claims.contains_key("sub"));
+        assert_eq!(claims["trigger"], "org_switch");
+    }
+
+    #[tokio::test]
+    async fn test_claims_callback_error_returns_none() {
+        let callback = Arc::new(FailingCallback);
+        let result = call_claims_callback_with_timeout(&callback, &claims_payload()).await;
+        assert!(result.is_none());
+    }
 }
@@ -38,14 +38,15 @@ use axum::{
 use chrono::{Duration, Utc};
 use std::sync::Arc;
 
-use crate::callback::AuthCallback;
+use super::call_claims_callback_with_timeout;
+use crate::callback::{AuthCallback, ClaimsTrigger, TokenClaimsPayload};
 use crate::errors::AppError;
 use crate::models::{RefreshRequest, RefreshResponse};
 use crate::repositories::SessionEntity;
 use crate::services::EmailService;
 use crate::utils::{
     build_json_response_with_cookies, extract_client_ip_with_fallback, extract_cookie,
-    get_default_org_context, hash_refresh_token, PeerIp,
+    get_default_org_context, hash_refresh_token, metadata_claims, PeerIp,
 };
 use crate::AppState;
 
@@ -174,12 +175,17 @@ pub async fn refresh<C: AuthCallback, E: EmailService>(
         .find_by_id(session.user_id)
         .await?
         .ok_or(AppError::InvalidToken)?;
+    user.ensure_active()?;
 
     // Preserve org context: look up user's memberships and select default org
     let memberships = state.membership_repo.find_by_user(session.user_id).await?;
 
     // Select default org using shared helper
-    let token_context = get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    let mut token_context =
+        get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    token_context.metadata = metadata_claims(&state, &user).await;
+    let claims_payload = TokenClaimsPayload::new(user.id, &token_context, ClaimsTrigger::Refresh);
+    token_context.ext = call_claims_callback_with_timeout(&state.callback, &claims_payload).await;
 
     // Generate new tokens with preserved org context
     let new_session_id = uuid::Uuid::new_v4();
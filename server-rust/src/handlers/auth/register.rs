@@ -27,13 +27,12 @@ use crate::errors::AppError;
 use crate::models::{AuthMethod, AuthResponse, RegisterRequest};
 use crate::repositories::{
     default_expiry, generate_api_key, generate_verification_token, hash_verification_token,
-    normalize_email, validate_email_ascii_local, ApiKeyEntity, AuditEventType, MembershipEntity,
-    SessionEntity, TokenType, UserEntity,
+    ApiKeyEntity, AuditEventType, MembershipEntity, SessionEntity, TokenType, UserEntity,
 };
 use crate::services::{EmailService, TokenContext};
 use crate::utils::{
-    attach_auth_cookies, extract_client_ip_with_fallback, hash_refresh_token, is_disposable_email,
-    is_valid_email, resolve_org_assignment, user_entity_to_auth_user, PeerIp,
+    attach_auth_cookies, extract_client_ip_with_fallback, hash_refresh_token,
+    resolve_org_assignment, user_entity_to_auth_user, validate_account_email, PeerIp,
 };
 use crate::AppState;
 
@@ -167,48 +166,7 @@ pub async fn register<C: AuthCallback, E: EmailService>(
         return Err(AppError::NotFound("Email auth disabled".into()));
     }
 
-    // Validate email format
-    if !is_valid_email(&req.email) {
-        return Err(AppError::Validation("Invalid email format".to_string()));
-    }
-
-    // SEC-29: Block disposable email addresses if configured (runtime toggle with config fallback)
-    let block_disposable = state
-        .settings_service
-        .get_bool("auth_email_block_disposable")
-        .await
-        .ok()
-        .flatten()
-        .unwrap_or(state.config.email.block_disposable_emails);
-
-    if block_disposable {
-        // Build custom blocked domains set from config + DB setting
-        let mut custom_domains: std::collections::HashSet<String> = state
-            .config
-            .email
-            .custom_blocked_domains
-            .iter()
-            .cloned()
-            .collect();
-        if let Ok(Some(db_domains)) = state.settings_service.get("custom_blocked_domains").await {
-            if let Ok(domains) = serde_json::from_str::<Vec<String>>(&db_domains) {
-                custom_domains.extend(domains.into_iter().map(|d| d.to_lowercase()));
-            }
-        }
-        let custom_ref = if custom_domains.is_empty() {
-            None
-        } else {
-            Some(&custom_domains)
-        };
-        if is_disposable_email(&req.email, custom_ref) {
-            return Err(AppError::DisposableEmailBlocked);
-        }
-    }
-
-    // SRV-10: Reject non-ASCII local parts to prevent homograph attacks
-    validate_email_ascii_local(&req.email)?;
-
-    let normalized_email = normalize_email(&req.email);
+    let normalized_email = validate_account_email(&state, &req.email).await?;
 
     // Validate password strength BEFORE checking email to prevent timing attacks
     // that could enumerate valid emails based on response time differences
@@ -258,6 +216,10 @@ pub async fn register<C: AuthCallback, E: EmailService>(
         role: Some(org_assignment.role.as_str().to_string()),
         is_system_admin: None,
         email_verified: Some(user.email_verified),
+        service_account: None,
+        act: None,
+        metadata: None,
+        ext: None,
     };
     let token_pair =
         state
@@ -25,8 +25,8 @@ use crate::models::{MessageResponse, UserResponse};
 use crate::repositories::AuditEventType;
 use crate::services::EmailService;
 use crate::utils::{
-    authenticate, build_logout_cookies, extract_access_token, extract_cookie, hash_refresh_token,
-    user_entity_to_auth_user,
+    apply_metadata_patch, authenticate, build_logout_cookies, extract_access_token, extract_cookie,
+    hash_refresh_token, user_entity_to_auth_user,
 };
 use crate::AppState;
 
@@ -230,11 +230,13 @@ pub struct UpdateProfileRequest {
     pub name: Option<String>,
     /// User's profile picture URL
     pub picture: Option<String>,
+    /// Merge patch for user-writable metadata (`null` removes a key)
+    pub unsafe_metadata: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 /// PATCH /auth/me - Update current user's profile
 ///
-/// Allows users to update their name and profile picture.
+/// Allows users to update their name, profile picture and unsafe metadata.
 pub async fn update_profile<C: AuthCallback, E: EmailService>(
     State(state): State<Arc<AppState<C, E>>>,
     headers: HeaderMap,
@@ -284,8 +286,21 @@ pub async fn update_profile<C: AuthCallback, E: EmailService>(
         }
     }
 
+    // Validate the metadata patch before saving anything; the repository
+    // applies it again to the stored metadata under a row lock
+    let unsafe_metadata = req.unsafe_metadata;
+    if let Some(patch) = &unsafe_metadata {
+        apply_metadata_patch(&user.unsafe_metadata, patch.clone(), "unsafeMetadata")?;
+    }
+
     // Save updated user
-    let updated_user = state.user_repo.update(user).await?;
+    let mut updated_user = state.user_repo.update(user).await?;
+    if unsafe_metadata.is_some() {
+        updated_user = state
+            .user_repo
+            .update_metadata(auth.user_id, None, None, unsafe_metadata)
+            .await?;
+    }
 
     // Log audit event
     let _ = state
@@ -1,15 +1,20 @@
 //! Authorization endpoint handler
 
 use axum::{extract::State, http::HeaderMap, Json};
+use chrono::Utc;
 use std::sync::Arc;
 
 use crate::callback::AuthCallback;
 use crate::errors::AppError;
+use crate::handlers::{service_account_custom_role, subscription_service};
 use crate::models::{
     AuthorizeRequest, AuthorizeResponse, GetPermissionsRequest, GetPermissionsResponse,
 };
-use crate::services::{AuthorizationService, EmailService, PolicyContext, PolicyService};
-use crate::utils::authenticate;
+use crate::services::{
+    AuthorizationService, EmailService, PolicyContext, PolicyEvaluationResult, PolicyService,
+    ENTITLEMENT_PERMISSION_PREFIX,
+};
+use crate::utils::{authenticate_for_org, extract_client_ip_with_fallback, PeerIp};
 use crate::AppState;
 
 /// POST /authorize - Check if user has permission
@@ -19,13 +24,48 @@ use crate::AppState;
 ///
 /// If resource or environment attributes are provided, ABAC policies are evaluated first.
 /// If no ABAC policy matches, falls back to RBAC role-based checks.
+///
+/// When credits are enabled the response lists the entitlements granted by
+/// the user's and the organization's subscriptions. Permissions of the form
+/// `entitlement:<name>` are answered from those entitlements alone.
 pub async fn authorize<C: AuthCallback, E: EmailService>(
     State(state): State<Arc<AppState<C, E>>>,
     headers: HeaderMap,
+    PeerIp(peer_ip): PeerIp,
     Json(req): Json<AuthorizeRequest>,
 ) -> Result<Json<AuthorizeResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, req.org_id).await?;
+
+    let entitlements = if state.config.privacy.enabled {
+        Some(
+            subscription_service(&state)
+                .entitlements_for(auth.user_id, Some(req.org_id), Utc::now())
+                .await?,
+        )
+    } else {
+        None
+    };
+
+    if let Some(entitlement) = req.permission.strip_prefix(ENTITLEMENT_PERMISSION_PREFIX) {
+        let allowed = entitlements
+            .as_ref()
+            .is_some_and(|e| e.iter().any(|granted| granted == entitlement));
+        let reason = if allowed {
+            format!("Subscription grants '{}'", entitlement)
+        } else {
+            format!("No active subscription grants '{}'", entitlement)
+        };
+        return Ok(Json(AuthorizeResponse {
+            allowed,
+            reason: Some(reason),
+            permissions: None,
+            matched_policy_id: None,
+            matched_policy_name: None,
+            used_rbac_fallback: None,
+            entitlements,
+        }));
+    }
 
     // P-03: Build policy context, taking ownership to avoid clones
     let context = build_policy_context(req.resource, req.environment);
@@ -38,10 +78,31 @@ pub async fn authorize<C: AuthCallback, E: EmailService>(
         state.membership_repo.clone(),
     );
 
-    let result = policy_service
-        .evaluate(auth.user_id, req.org_id, &req.permission, context)
+    let client_ip =
+        extract_client_ip_with_fallback(&headers, state.config.server.trust_proxy, peer_ip);
+    let mut result = policy_service
+        .evaluate(
+            auth.user_id,
+            req.org_id,
+            &req.permission,
+            context,
+            client_ip,
+        )
         .await?;
 
+    // Service accounts also hold their custom role's permissions. Only an
+    // RBAC denial is overridden; explicit ABAC denies still win.
+    if !result.allowed && result.used_rbac_fallback {
+        if let Some(role) = service_account_custom_role(&state, auth.user_id, req.org_id).await? {
+            if role.permissions.contains(&req.permission) {
+                result = PolicyEvaluationResult::allowed_by_rbac(&format!(
+                    "Custom role '{}' has '{}' permission",
+                    role.name, req.permission
+                ));
+            }
+        }
+    }
+
     Ok(Json(AuthorizeResponse {
         allowed: result.allowed,
         reason: result.reason,
@@ -49,6 +110,7 @@ pub async fn authorize<C: AuthCallback, E: EmailService>(
         matched_policy_id: result.matched_policy_id,
         matched_policy_name: result.matched_policy_name,
         used_rbac_fallback: Some(result.used_rbac_fallback),
+        entitlements,
     }))
 }
 
@@ -89,7 +151,7 @@ pub async fn get_permissions<C: AuthCallback, E: EmailService>(
     Json(req): Json<GetPermissionsRequest>,
 ) -> Result<Json<GetPermissionsResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, req.org_id).await?;
 
     // Create authorization service and get permissions
     let auth_service = AuthorizationService::new(
@@ -110,8 +172,17 @@ pub async fn get_permissions<C: AuthCallback, E: EmailService>(
         .await?
         .ok_or_else(|| AppError::Forbidden("Not a member of this organization".into()))?;
 
+    let mut permissions: Vec<String> = permissions.iter().map(|p| p.as_str().to_string()).collect();
+    if let Some(role) = service_account_custom_role(&state, auth.user_id, req.org_id).await? {
+        for permission in role.permissions {
+            if !permissions.contains(&permission) {
+                permissions.push(permission);
+            }
+        }
+    }
+
     Ok(Json(GetPermissionsResponse {
-        permissions: permissions.iter().map(|p| p.as_str().to_string()).collect(),
+        permissions,
         role: Some(membership.role.as_str().to_string()),
     }))
 }
@@ -86,6 +86,7 @@ pub async fn update_credential<C: AuthCallback, E: EmailService>(
     Json(request): Json<UpdateCredentialRequest>,
 ) -> Result<StatusCode, AppError> {
     let user = authenticate(&state, &headers).await?;
+    user.ensure_not_impersonated("change sign-in methods")?;
 
     let credential = state
         .storage
@@ -124,6 +125,7 @@ pub async fn unlink_credential<C: AuthCallback, E: EmailService>(
     Path(credential_id): Path<Uuid>,
 ) -> Result<StatusCode, AppError> {
     let user = authenticate(&state, &headers).await?;
+    user.ensure_not_impersonated("change sign-in methods")?;
 
     let credential = state
         .storage
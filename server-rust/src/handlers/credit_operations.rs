@@ -5,28 +5,98 @@
 //!
 //! POST /credits/spend/{user_id}      - Direct spend (debit credits immediately)
 //! POST /credits/hold/{user_id}       - Create a hold (reserve credits)
-//! POST /credits/capture/{hold_id}    - Capture a hold (finalize spend)
+//! POST /credits/capture/{hold_id}    - Capture a hold, in full or in part
+//! POST /credits/extend/{hold_id}     - Increase a hold or push back its expiry
 //! POST /credits/release/{hold_id}    - Release a hold (return credits)
+//! POST /credits/usage-events         - Report metered usage (billed in batches)
+//!
+//! Spend and hold take an optional `orgId` to charge an organization's shared
+//! balance on behalf of the member in the path. The member needs spend access
+//! (role `credits:spend` or an explicit override) and must be within their
+//! monthly cap.
+//!
+//! Spends and holds count against the spending budgets of the user, the org
+//! member and the calling API key. A hard limit rejects them with
+//! `BUDGET_EXCEEDED`; crossing a soft limit queues a `credit.budget_threshold`
+//! notification.
+//!
+//! Usage events are debited later by the usage billing worker, priced by the
+//! meter catalog (or the organization's override).
 
 use axum::{
     extract::{Path, State},
     http::HeaderMap,
     Json,
 };
+use chrono::{Duration, Utc};
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::callback::AuthCallback;
+use crate::callback::{AuthCallback, WebhookEvent};
 use crate::errors::AppError;
-use crate::handlers::admin::validate_system_admin;
+use crate::handlers::admin::{validate_system_admin_auth, validate_system_admin_with_scope};
+use crate::handlers::require_org_credit_permission;
 use crate::models::{
-    CaptureHoldResponse, CreateHoldRequest, CreateHoldResponse, ReleaseHoldResponse,
-    SpendCreditsRequest, SpendCreditsResponse,
+    CaptureHoldRequest, CaptureHoldResponse, CreateHoldRequest, CreateHoldResponse,
+    ExtendHoldRequest, ExtendHoldResponse, RecordUsageRequest, RecordUsageResponse,
+    ReleaseHoldResponse, SpendCreditsRequest, SpendCreditsResponse, UsageEventOutcome,
+    UsageEventResult,
+};
+use crate::repositories::SCOPE_CREDITS_SPEND;
+use crate::services::{
+    BudgetAlert, CreditService, EmailService, MeteringService, OrgSpender, Permission, UsageReport,
 };
-use crate::services::{CreditService, EmailService};
 use crate::utils::{validate_currency, validate_metadata_no_secrets, validate_reference_type};
 use crate::AppState;
 
+/// Resolve the member spending from `org_id`'s balance
+///
+/// The role only sets the default; member settings are applied by the
+/// credit service.
+async fn org_spender<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    user_id: Uuid,
+    org_id: Uuid,
+) -> Result<OrgSpender, AppError> {
+    let role =
+        require_org_credit_permission(state, user_id, org_id, Permission::CreditsRead).await?;
+    Ok(OrgSpender {
+        org_id,
+        user_id,
+        role_can_spend: Permission::CreditsSpend.is_allowed_for(role),
+    })
+}
+
+/// Credit service enforcing spending budgets, counting against `api_key_id`'s
+fn budgeted_credit_service<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    api_key_id: Option<Uuid>,
+) -> CreditService {
+    CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone())
+        .with_budgets(state.storage.credit_budget_repo.clone())
+        .for_api_key(api_key_id)
+}
+
+/// Queue a notification for each budget whose soft limit was crossed
+async fn notify_budget_alerts<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    alerts: &[BudgetAlert],
+) {
+    for alert in alerts {
+        if let Err(e) = state
+            .comms_service
+            .notify_credit_budget_threshold(alert)
+            .await
+        {
+            tracing::warn!(
+                error = %e,
+                budget_id = %alert.budget.id,
+                "Failed to queue budget threshold notification"
+            );
+        }
+    }
+}
+
 /// POST /credits/spend/{user_id} - Spend credits directly
 ///
 /// Debit credits from a user's account immediately. This is a one-step operation.
@@ -40,7 +110,8 @@ pub async fn spend_credits<C: AuthCallback, E: EmailService>(
     Json(request): Json<SpendCreditsRequest>,
 ) -> Result<Json<SpendCreditsResponse>, AppError> {
     // Validate admin API key
-    let admin_id = validate_system_admin(&state, &headers).await?;
+    let auth = validate_system_admin_auth(&state, &headers, SCOPE_CREDITS_SPEND).await?;
+    let admin_id = auth.user_id;
 
     // Validate metadata doesn't contain secrets
     validate_metadata_no_secrets(request.metadata.as_ref())?;
@@ -58,26 +129,44 @@ pub async fn spend_credits<C: AuthCallback, E: EmailService>(
         .await?
         .ok_or(AppError::NotFound("User not found".into()))?;
 
-    // Create credit service
-    let credit_service =
-        CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+    // Create credit service (budgets count the calling API key too)
+    let credit_service = budgeted_credit_service(&state, auth.api_key_id);
 
     // Execute spend
-    let result = credit_service
-        .spend(
-            user_id,
-            request.amount_lamports,
-            &request.currency,
-            request.idempotency_key,
-            &request.reference_type,
-            request.reference_id,
-            request.metadata,
-        )
-        .await?;
+    let result = match request.org_id {
+        Some(org_id) => {
+            let spender = org_spender(&state, user_id, org_id).await?;
+            credit_service
+                .spend_from_org(
+                    spender,
+                    request.amount_lamports,
+                    &request.currency,
+                    request.idempotency_key,
+                    &request.reference_type,
+                    request.reference_id,
+                    request.metadata,
+                )
+                .await?
+        }
+        None => {
+            credit_service
+                .spend(
+                    user_id,
+                    request.amount_lamports,
+                    &request.currency,
+                    request.idempotency_key,
+                    &request.reference_type,
+                    request.reference_id,
+                    request.metadata,
+                )
+                .await?
+        }
+    };
 
     tracing::info!(
         admin_id = %admin_id,
         user_id = %user_id,
+        org_id = ?result.org_id,
         amount_lamports = result.amount_lamports,
         currency = %request.currency,
         reference_type = %request.reference_type,
@@ -86,6 +175,25 @@ pub async fn spend_credits<C: AuthCallback, E: EmailService>(
         "Credit spend operation"
     );
 
+    notify_budget_alerts(&state, &result.budget_alerts).await;
+
+    state
+        .webhook_service
+        .dispatch_or_warn(
+            WebhookEvent::CreditsSpent,
+            serde_json::json!({
+                "user_id": user_id,
+                "org_id": result.org_id,
+                "transaction_id": result.transaction_id,
+                "amount_lamports": result.amount_lamports,
+                "new_balance_lamports": result.new_balance_lamports,
+                "currency": result.currency,
+                "reference_type": request.reference_type,
+                "reference_id": request.reference_id,
+            }),
+        )
+        .await;
+
     Ok(Json(SpendCreditsResponse::from_result(
         result,
         &request.currency,
@@ -106,7 +214,8 @@ pub async fn create_hold<C: AuthCallback, E: EmailService>(
     Json(request): Json<CreateHoldRequest>,
 ) -> Result<Json<CreateHoldResponse>, AppError> {
     // Validate admin API key
-    let admin_id = validate_system_admin(&state, &headers).await?;
+    let auth = validate_system_admin_auth(&state, &headers, SCOPE_CREDITS_SPEND).await?;
+    let admin_id = auth.user_id;
 
     // Validate metadata doesn't contain secrets
     validate_metadata_no_secrets(request.metadata.as_ref())?;
@@ -134,27 +243,46 @@ pub async fn create_hold<C: AuthCallback, E: EmailService>(
     }
     let ttl_minutes = request.ttl_minutes;
 
-    // Create credit service
-    let credit_service =
-        CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+    // Create credit service (budgets count the calling API key too)
+    let credit_service = budgeted_credit_service(&state, auth.api_key_id);
 
     // Create hold
-    let result = credit_service
-        .hold(
-            user_id,
-            request.amount_lamports,
-            &request.currency,
-            request.idempotency_key.clone(),
-            Some(ttl_minutes),
-            request.reference_type.as_deref(),
-            request.reference_id,
-            request.metadata,
-        )
-        .await?;
+    let result = match request.org_id {
+        Some(org_id) => {
+            let spender = org_spender(&state, user_id, org_id).await?;
+            credit_service
+                .hold_from_org(
+                    spender,
+                    request.amount_lamports,
+                    &request.currency,
+                    request.idempotency_key.clone(),
+                    Some(ttl_minutes),
+                    request.reference_type.as_deref(),
+                    request.reference_id,
+                    request.metadata,
+                )
+                .await?
+        }
+        None => {
+            credit_service
+                .hold(
+                    user_id,
+                    request.amount_lamports,
+                    &request.currency,
+                    request.idempotency_key.clone(),
+                    Some(ttl_minutes),
+                    request.reference_type.as_deref(),
+                    request.reference_id,
+                    request.metadata,
+                )
+                .await?
+        }
+    };
 
     tracing::info!(
         admin_id = %admin_id,
         user_id = %user_id,
+        org_id = ?request.org_id,
         hold_id = %result.hold_id,
         amount_lamports = result.amount_lamports,
         currency = %request.currency,
@@ -163,6 +291,8 @@ pub async fn create_hold<C: AuthCallback, E: EmailService>(
         "Credit hold created"
     );
 
+    notify_budget_alerts(&state, &result.budget_alerts).await;
+
     Ok(Json(CreateHoldResponse::from_result(
         result,
         &request.currency,
@@ -172,34 +302,109 @@ pub async fn create_hold<C: AuthCallback, E: EmailService>(
 /// POST /credits/capture/{hold_id} - Capture a hold
 ///
 /// Finalize a previously created hold, converting it to a spend transaction.
-/// The held amount is permanently deducted from the user's balance.
+/// Without a body the whole held amount is deducted from the user's balance.
+///
+/// The optional body captures a smaller amount: by default the hold is then
+/// settled and the remainder released; with `releaseRemainder: false` it
+/// stays open for further captures, each keyed by its `idempotencyKey`.
 pub async fn capture_hold<C: AuthCallback, E: EmailService>(
     State(state): State<Arc<AppState<C, E>>>,
     headers: HeaderMap,
     Path(hold_id): Path<Uuid>,
+    request: Option<Json<CaptureHoldRequest>>,
 ) -> Result<Json<CaptureHoldResponse>, AppError> {
     // Validate admin API key
-    let admin_id = validate_system_admin(&state, &headers).await?;
+    let admin_id = validate_system_admin_with_scope(&state, &headers, SCOPE_CREDITS_SPEND).await?;
+
+    let request = request.map(|Json(r)| r).unwrap_or_default();
 
     // Create credit service
-    let credit_service =
-        CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+    let credit_service = budgeted_credit_service(&state, None);
 
     // S-14: Capture returns currency from the hold it fetches internally,
     // avoiding a separate get_hold() call just to read the currency.
-    let result = credit_service.capture(hold_id).await?;
+    let result = credit_service
+        .capture_partial(
+            hold_id,
+            request.amount_lamports,
+            request.idempotency_key.as_deref(),
+            request.release_remainder,
+        )
+        .await?;
 
     tracing::info!(
         admin_id = %admin_id,
         hold_id = %hold_id,
-        transaction_id = %result.transaction_id,
-        amount_lamports = result.amount_lamports,
+        transaction_id = %result.spend.transaction_id,
+        amount_lamports = result.spend.amount_lamports,
+        hold_status = result.hold.status.as_str(),
+        replayed = result.replayed,
         "Credit hold captured"
     );
 
+    if !result.replayed {
+        state
+            .webhook_service
+            .dispatch_or_warn(
+                WebhookEvent::CreditsSpent,
+                serde_json::json!({
+                    "user_id": result.spend.user_id,
+                    "org_id": result.spend.org_id,
+                    "transaction_id": result.spend.transaction_id,
+                    "amount_lamports": result.spend.amount_lamports,
+                    "new_balance_lamports": result.spend.new_balance_lamports,
+                    "currency": result.spend.currency,
+                    "hold_id": hold_id,
+                    "hold_status": result.hold.status.as_str(),
+                }),
+            )
+            .await;
+    }
+
     Ok(Json(CaptureHoldResponse::from_result(result)))
 }
 
+/// POST /credits/extend/{hold_id} - Increase or extend a hold
+///
+/// Raise a pending hold's total amount and/or push back its expiry, for
+/// workloads that need more than they reserved. Lower values are ignored,
+/// so the call can be retried safely.
+pub async fn extend_hold<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(hold_id): Path<Uuid>,
+    Json(request): Json<ExtendHoldRequest>,
+) -> Result<Json<ExtendHoldResponse>, AppError> {
+    // Validate admin API key
+    let admin_id = validate_system_admin_with_scope(&state, &headers, SCOPE_CREDITS_SPEND).await?;
+
+    // SRV-12: Same bound as the hold TTL (at most 60 minutes out)
+    if let Some(expires_at) = request.expires_at {
+        if expires_at > Utc::now() + Duration::minutes(60) {
+            return Err(AppError::Validation(
+                "expiresAt must be at most 60 minutes from now".into(),
+            ));
+        }
+    }
+
+    // Create credit service
+    let credit_service = budgeted_credit_service(&state, None);
+
+    let hold = credit_service
+        .extend_hold(hold_id, request.amount_lamports, request.expires_at)
+        .await?;
+
+    tracing::info!(
+        admin_id = %admin_id,
+        hold_id = %hold_id,
+        amount_lamports = hold.amount,
+        expires_at = %hold.expires_at,
+        "Credit hold extended"
+    );
+
+    Ok(Json(hold.into()))
+}
+
 /// POST /credits/release/{hold_id} - Release a hold
 ///
 /// Cancel a previously created hold, returning the reserved credits
@@ -210,11 +415,10 @@ pub async fn release_hold<C: AuthCallback, E: EmailService>(
     Path(hold_id): Path<Uuid>,
 ) -> Result<Json<ReleaseHoldResponse>, AppError> {
     // Validate admin API key
-    let admin_id = validate_system_admin(&state, &headers).await?;
+    let admin_id = validate_system_admin_with_scope(&state, &headers, SCOPE_CREDITS_SPEND).await?;
 
     // Create credit service
-    let credit_service =
-        CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
+    let credit_service = budgeted_credit_service(&state, None);
 
     // Release the hold
     credit_service.release(hold_id).await?;
@@ -231,6 +435,77 @@ pub async fn release_hold<C: AuthCallback, E: EmailService>(
     }))
 }
 
+/// POST /credits/usage-events - Report metered usage
+///
+/// Accepts up to 1000 events per request. The batch is rejected as a whole
+/// if any event is invalid (unknown or inactive meter, bad quantity or time,
+/// org the user is not a member of). Events whose idempotency key the user
+/// already used are reported as duplicates and not billed again.
+pub async fn record_usage<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(request): Json<RecordUsageRequest>,
+) -> Result<Json<RecordUsageResponse>, AppError> {
+    // Validate admin API key
+    let admin_id = validate_system_admin_with_scope(&state, &headers, SCOPE_CREDITS_SPEND).await?;
+
+    let keys: Vec<String> = request
+        .events
+        .iter()
+        .map(|e| e.idempotency_key.clone())
+        .collect();
+    let reports = request
+        .events
+        .into_iter()
+        .map(|e| UsageReport {
+            meter: e.meter,
+            user_id: e.user_id,
+            org_id: e.org_id,
+            quantity: e.quantity,
+            idempotency_key: e.idempotency_key,
+            occurred_at: e.occurred_at,
+        })
+        .collect();
+
+    let metering_service = MeteringService::new(
+        state.storage.meter_repo.clone(),
+        state.credit_repo.clone(),
+        state.credit_hold_repo.clone(),
+        state.membership_repo.clone(),
+    );
+    let stored = metering_service.ingest(reports).await?;
+
+    let results: Vec<UsageEventResult> = keys
+        .into_iter()
+        .zip(stored)
+        .map(|(idempotency_key, stored)| UsageEventResult {
+            idempotency_key,
+            status: if stored {
+                UsageEventOutcome::Accepted
+            } else {
+                UsageEventOutcome::Duplicate
+            },
+        })
+        .collect();
+    let accepted = results
+        .iter()
+        .filter(|r| r.status == UsageEventOutcome::Accepted)
+        .count();
+
+    tracing::info!(
+        admin_id = %admin_id,
+        accepted = accepted,
+        duplicates = results.len() - accepted,
+        "Usage events recorded"
+    );
+
+    Ok(Json(RecordUsageResponse {
+        accepted,
+        duplicates: results.len() - accepted,
+        results,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,11 +516,14 @@ mod tests {
 
     use crate::config::{
         default_access_expiry, default_audience, default_issuer, default_refresh_expiry,
-        AppleConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig, GoogleConfig,
-        JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig, ServerConfig, SolanaConfig,
-        SsoConfig, WalletConfig, WebAuthnConfig, WebhookConfig,
+        AppleConfig, AuditSinkConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig,
+        GoogleConfig, JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig, ServerConfig,
+        SolanaConfig, SsoConfig, StripeConfig, WalletConfig, WebAuthnConfig, WebhookConfig,
+    };
+    use crate::repositories::{
+        generate_api_key, ApiKeyEntity, LoginAttemptConfig, UserEntity, UserStatus,
+        SCOPE_USERS_READ,
     };
-    use crate::repositories::{generate_api_key, ApiKeyEntity, LoginAttemptConfig, UserEntity};
     use crate::services::{
         create_wallet_unlock_cache, AppleService, AuditService, CommsService, GoogleService,
         JwtService, LogEmailService, MfaAttemptService, PasswordService, SolanaService,
@@ -294,6 +572,8 @@ mod tests {
             sso: SsoConfig::default(),
             wallet: WalletConfig::default(),
             privacy: PrivacyConfig::default(),
+            audit_sinks: AuditSinkConfig::default(),
+            stripe: StripeConfig::default(),
         }
     }
 
@@ -319,6 +599,11 @@ mod tests {
             "http://localhost:3000".to_string(),
             token_cipher,
         );
+        let webhook_service = Arc::new(crate::services::WebhookService::new(
+            storage.webhook_repo.clone(),
+            storage.outbox_repo.clone(),
+            TokenCipher::new(&config.jwt.secret),
+        ));
 
         Arc::new(AppState {
             config,
@@ -335,6 +620,7 @@ mod tests {
             phantom_email: std::marker::PhantomData::<LogEmailService>,
             audit_service,
             comms_service,
+            webhook_service,
             user_repo: storage.user_repo.clone(),
             session_repo: storage.session_repo.clone(),
             nonce_repo: storage.nonce_repo.clone(),
@@ -350,6 +636,7 @@ mod tests {
             policy_repo: storage.policy_repo.clone(),
             outbox_repo: storage.outbox_repo.clone(),
             api_key_repo: storage.api_key_repo.clone(),
+            api_key_rate_limiter: crate::middleware::rate_limit::RateLimitStore::new(),
             wallet_material_repo: storage.wallet_material_repo.clone(),
             derived_wallet_repo: storage.derived_wallet_repo.clone(),
             wallet_rotation_history_repo: storage.wallet_rotation_history_repo.clone(),
@@ -411,9 +698,16 @@ mod tests {
             stripe_customer_id: None,
             auth_methods: vec![],
             is_system_admin: true,
+            is_service_account: false,
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
         let user = state.user_repo.create(user).await.unwrap();
 
@@ -439,9 +733,16 @@ mod tests {
             stripe_customer_id: None,
             auth_methods: vec![],
             is_system_admin: false,
+            is_service_account: false,
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
         let user = state.user_repo.create(user).await.unwrap();
         user.id
@@ -484,6 +785,7 @@ mod tests {
             reference_type: "order".to_string(),
             reference_id: Uuid::new_v4(),
             metadata: None,
+            org_id: None,
         };
 
         let result = spend_credits(State(state.clone()), headers, Path(user_id), Json(request))
@@ -516,6 +818,7 @@ mod tests {
             reference_type: "order".to_string(),
             reference_id: Uuid::new_v4(),
             metadata: None,
+            org_id: None,
         };
 
         let result =
@@ -549,6 +852,7 @@ mod tests {
             reference_type: Some("order".to_string()),
             reference_id: Some(Uuid::new_v4()),
             metadata: None,
+            org_id: None,
         };
 
         let hold_result = create_hold(
@@ -564,10 +868,14 @@ mod tests {
         assert_eq!(hold_result.0.amount_lamports, 500_000_000);
 
         // Capture hold
-        let capture_result =
-            capture_hold(State(state.clone()), headers, Path(hold_result.0.hold_id))
-                .await
-                .unwrap();
+        let capture_result = capture_hold(
+            State(state.clone()),
+            headers,
+            Path(hold_result.0.hold_id),
+            None,
+        )
+        .await
+        .unwrap();
 
         assert_eq!(capture_result.0.amount_lamports, 500_000_000);
         assert_eq!(capture_result.0.new_balance_lamports, 500_000_000);
@@ -598,6 +906,7 @@ mod tests {
             reference_type: Some("order".to_string()),
             reference_id: Some(Uuid::new_v4()),
             metadata: None,
+            org_id: None,
         };
 
         let hold_result = create_hold(
@@ -617,4 +926,49 @@ mod tests {
 
         assert!(release_result.0.released);
     }
+
+    #[tokio::test]
+    async fn test_scoped_key_only_reaches_its_scope() {
+        let config = base_config();
+        let state = build_state(config);
+        let (admin_id, _) = setup_admin_with_api_key(&state).await;
+        let user_id = setup_regular_user(&state).await;
+        add_credits_to_user(&state, admin_id, user_id, 1_000_000_000).await;
+
+        let api_key = generate_api_key();
+        let mut api_key_entity = ApiKeyEntity::new(admin_id, &api_key, "spend-only");
+        api_key_entity.scopes = vec![SCOPE_CREDITS_SPEND.to_string()];
+        state.api_key_repo.create(api_key_entity).await.unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", api_key)).unwrap(),
+        );
+
+        // Endpoints that don't opt in to a scope reject restricted keys
+        assert!(matches!(
+            crate::utils::authenticate(&state, &headers).await,
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(
+            validate_system_admin_auth(&state, &headers, SCOPE_USERS_READ)
+                .await
+                .is_err()
+        );
+
+        let request = SpendCreditsRequest {
+            amount_lamports: 100_000_000,
+            currency: "SOL".to_string(),
+            idempotency_key: "order:scoped".to_string(),
+            reference_type: "order".to_string(),
+            reference_id: Uuid::new_v4(),
+            metadata: None,
+            org_id: None,
+        };
+        let result = spend_credits(State(state.clone()), headers, Path(user_id), Json(request))
+            .await
+            .unwrap();
+        assert_eq!(result.0.new_balance_lamports, 900_000_000);
+    }
 }
@@ -0,0 +1,228 @@
+//! Credit statement handlers
+//!
+//! GET  /credits/statements       - Download a statement (JSON, CSV or HTML)
+//! POST /credits/statements/email - Email a statement to the caller
+//!
+//! Statements cover a calendar month (`month=YYYY-MM`) or any `from`/`to`
+//! range up to a year, for the caller's own balance or, with `orgId`, an
+//! organization's (requires `credits:read`). Deposits on a statement get
+//! sequential invoice numbers that stay the same on every later statement.
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::credits::authenticate_credit_reader;
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::models::{
+    CreditStatementQueryParams, CreditStatementRequest, CreditStatementResponse,
+    EmailCreditStatementResponse, StatementFormat,
+};
+use crate::services::{CreditAccount, CreditStatement, CreditStatementService, EmailService};
+use crate::utils::validate_currency;
+use crate::AppState;
+
+/// Resolve the statement period from `month` or `from`/`to`
+fn resolve_period(
+    request: &CreditStatementRequest,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), AppError> {
+    match (&request.month, request.from, request.to) {
+        (Some(month), None, None) => {
+            let start = NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+                .map_err(|_| AppError::Validation("month must be formatted as YYYY-MM".into()))?;
+            let end = if start.month() == 12 {
+                NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+            }
+            .ok_or_else(|| AppError::Validation("month is out of range".into()))?;
+            let midnight = |date: NaiveDate| date.and_hms_opt(0, 0, 0).map(|d| d.and_utc());
+            match (midnight(start), midnight(end)) {
+                (Some(from), Some(to)) => Ok((from, to)),
+                _ => Err(AppError::Validation("month is out of range".into())),
+            }
+        }
+        (None, Some(from), Some(to)) => Ok((from, to)),
+        _ => Err(AppError::Validation(
+            "Provide either month or both from and to".into(),
+        )),
+    }
+}
+
+/// Authorize the caller and generate the requested statement
+async fn generate_statement<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    request: &CreditStatementRequest,
+) -> Result<(Uuid, CreditStatement), AppError> {
+    if !state.config.privacy.enabled {
+        return Err(AppError::NotFound("Credits not enabled".into()));
+    }
+
+    let user_id = authenticate_credit_reader(state, headers, request.org_id).await?;
+
+    // SRV-14: Validate currency against whitelist
+    validate_currency(&request.currency)?;
+    let (from, to) = resolve_period(request)?;
+
+    let (account, holder_name) = match request.org_id {
+        Some(org_id) => {
+            let org = state
+                .org_repo
+                .find_by_id(org_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Organization not found".into()))?;
+            (CreditAccount::Org(org_id), Some(org.name))
+        }
+        None => {
+            let user = state.user_repo.find_by_id(user_id).await?;
+            let holder_name = user.and_then(|u| u.name.or(u.email));
+            (CreditAccount::User(user_id), holder_name)
+        }
+    };
+
+    let service = CreditStatementService::new(
+        state.credit_repo.clone(),
+        state.storage.credit_invoice_repo.clone(),
+    );
+    let mut statement = service
+        .generate(account, &request.currency, from, to)
+        .await?;
+    statement.holder_name = holder_name;
+
+    Ok((user_id, statement))
+}
+
+/// File download response
+fn download(content_type: &str, file_name: String, body: String) -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// GET /credits/statements - Download a statement
+///
+/// `format=csv` and `format=html` return a file; the HTML page is laid out
+/// for printing to PDF.
+pub async fn get_credit_statement<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Query(params): Query<CreditStatementQueryParams>,
+) -> Result<Response, AppError> {
+    let format = params.format;
+    let (_, statement) = generate_statement(&state, &headers, &params.into()).await?;
+
+    Ok(match format {
+        StatementFormat::Json => Json(CreditStatementResponse::from(statement)).into_response(),
+        StatementFormat::Csv => download(
+            "text/csv; charset=utf-8",
+            format!("{}.csv", statement.file_stem()),
+            statement.to_csv(),
+        ),
+        StatementFormat::Html => download(
+            "text/html; charset=utf-8",
+            format!("{}.html", statement.file_stem()),
+            statement.to_html(),
+        ),
+    })
+}
+
+/// POST /credits/statements/email - Email a statement to the caller
+///
+/// The statement is sent through the outbox to the caller's verified email
+/// address.
+pub async fn email_credit_statement<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(request): Json<CreditStatementRequest>,
+) -> Result<Json<EmailCreditStatementResponse>, AppError> {
+    let (user_id, statement) = generate_statement(&state, &headers, &request).await?;
+
+    let user = state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+    let email = match user.email {
+        Some(email) if user.email_verified => email,
+        _ => {
+            return Err(AppError::Validation(
+                "A verified email address is required to email statements".into(),
+            ))
+        }
+    };
+
+    state
+        .comms_service
+        .queue_credit_statement_email(&email, user.name.as_deref(), user_id, &statement)
+        .await?;
+
+    tracing::info!(
+        user_id = %user_id,
+        owner_type = statement.account.owner_type(),
+        owner_id = %statement.account.id(),
+        from = %statement.from,
+        to = %statement.to,
+        "Queued credit statement email"
+    );
+
+    Ok(Json(EmailCreditStatementResponse {
+        queued: true,
+        email,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(month: Option<&str>, range: Option<(&str, &str)>) -> CreditStatementRequest {
+        CreditStatementRequest {
+            currency: "SOL".into(),
+            org_id: None,
+            month: month.map(String::from),
+            from: range.map(|(from, _)| from.parse().unwrap()),
+            to: range.map(|(_, to)| to.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_resolve_period() {
+        let (from, to) = resolve_period(&request(Some("2026-12"), None)).unwrap();
+        assert_eq!(from.to_rfc3339(), "2026-12-01T00:00:00+00:00");
+        assert_eq!(to.to_rfc3339(), "2027-01-01T00:00:00+00:00");
+
+        let (from, to) = resolve_period(&request(
+            None,
+            Some(("2026-09-15T00:00:00Z", "2026-09-16T00:00:00Z")),
+        ))
+        .unwrap();
+        assert_eq!((to - from).num_days(), 1);
+
+        assert!(resolve_period(&request(Some("2026-13"), None)).is_err());
+        assert!(resolve_period(&request(Some("Sept"), None)).is_err());
+        assert!(resolve_period(&request(None, None)).is_err());
+        assert!(resolve_period(&request(
+            Some("2026-09"),
+            Some(("2026-09-01T00:00:00Z", "2026-10-01T00:00:00Z"))
+        ))
+        .is_err());
+    }
+}
@@ -0,0 +1,269 @@
+//! Credit transfer handlers
+//!
+//! POST /credits/transfers - Send credits to a user or organization
+//!
+//! Transfers move paid credit from the caller's balance, or from an
+//! organization's with `fromOrgId` (requires `credits:manage`), to another
+//! user or to an organization the caller belongs to. Both sides are written
+//! atomically, retries with the same `idempotencyKey` return the original
+//! transfer, and amounts above `credit_transfer_step_up_lamports` require
+//! recent strong authentication. Each user may send at most
+//! `credit_transfer_daily_limit_lamports` per UTC day.
+
+use axum::{
+    extract::State,
+    http::{header, HeaderMap},
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::{AuthCallback, WebhookEvent};
+use crate::errors::AppError;
+use crate::handlers::require_org_credit_permission;
+use crate::models::{CreateCreditTransferRequest, CreditTransferResponse};
+use crate::repositories::{AuditEventType, AuditLogBuilder, UserStatus};
+use crate::services::{
+    CreditAccount, CreditTransfer, CreditTransferService, EmailService, Permission, TransferRequest,
+};
+use crate::utils::{authenticate, authenticate_for_org, extract_client_ip, validate_currency};
+use crate::AppState;
+
+/// Maximum length of a transfer idempotency key
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Check that `to` can receive credits from `sender_id`
+async fn verify_recipient<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    sender_id: Uuid,
+    to: CreditAccount,
+) -> Result<(), AppError> {
+    match to {
+        CreditAccount::User(user_id) => {
+            let user = state.user_repo.find_by_id(user_id).await?;
+            if !user.is_some_and(|u| u.status == UserStatus::Active) {
+                return Err(AppError::NotFound("Recipient not found".into()));
+            }
+        }
+        CreditAccount::Org(org_id) => {
+            if state.org_repo.find_by_id(org_id).await?.is_none() {
+                return Err(AppError::NotFound("Organization not found".into()));
+            }
+            if state
+                .membership_repo
+                .find_by_user_and_org(sender_id, org_id)
+                .await?
+                .is_none()
+            {
+                return Err(AppError::Forbidden(
+                    "Credits can only be transferred to organizations you belong to".into(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Record the transfer in the audit log of both sides
+async fn audit_transfer<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    transfer: &CreditTransfer,
+) {
+    let metadata = serde_json::json!({
+        "transferId": transfer.id,
+        "transactionId": transfer.transaction_id,
+        "fromType": transfer.from.owner_type(),
+        "fromId": transfer.from.id(),
+        "toType": transfer.to.owner_type(),
+        "toId": transfer.to.id(),
+        "amountLamports": transfer.amount,
+        "currency": transfer.currency,
+    });
+    let ip = extract_client_ip(headers, state.config.server.trust_proxy);
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok());
+
+    let sides = [
+        (
+            AuditEventType::CreditTransferSent,
+            transfer.from,
+            transfer.to,
+        ),
+        (
+            AuditEventType::CreditTransferReceived,
+            transfer.to,
+            transfer.from,
+        ),
+    ];
+    for (event_type, owner, counterparty) in sides {
+        let mut builder = AuditLogBuilder::new(event_type)
+            .actor(transfer.sender_id)
+            .target(counterparty.owner_type(), counterparty.id())
+            .metadata(metadata.clone());
+        builder = match owner {
+            CreditAccount::Org(org_id) => builder.org(org_id),
+            CreditAccount::User(_) => builder,
+        };
+        if let Some(ip) = &ip {
+            builder = builder.ip(ip);
+        }
+        if let Some(ua) = user_agent {
+            builder = builder.user_agent(ua);
+        }
+        state.audit_service.log_or_warn(builder.build()).await;
+    }
+}
+
+/// POST /credits/transfers - Transfer credits to a user or organization
+pub async fn create_credit_transfer<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateCreditTransferRequest>,
+) -> Result<Json<CreditTransferResponse>, AppError> {
+    if !state.config.privacy.enabled {
+        return Err(AppError::NotFound("Credits not enabled".into()));
+    }
+
+    let auth = match request.from_org_id {
+        Some(org_id) => {
+            let auth = authenticate_for_org(&state, &headers, org_id).await?;
+            require_org_credit_permission(&state, auth.user_id, org_id, Permission::CreditsManage)
+                .await?;
+            auth
+        }
+        None => authenticate(&state, &headers).await?,
+    };
+    auth.ensure_not_impersonated("transfer credits")?;
+    let sender_id = auth.user_id;
+
+    validate_currency(&request.currency)?;
+    let idempotency_key = request.idempotency_key.trim();
+    if idempotency_key.is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(AppError::Validation(format!(
+            "idempotencyKey must be 1-{} characters",
+            MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+
+    let to = match (request.to_user_id, request.to_org_id) {
+        (Some(user_id), None) => CreditAccount::User(user_id),
+        (None, Some(org_id)) => CreditAccount::Org(org_id),
+        _ => {
+            return Err(AppError::Validation(
+                "Exactly one of toUserId and toOrgId is required".into(),
+            ))
+        }
+    };
+    let from = match request.from_org_id {
+        Some(org_id) => CreditAccount::Org(org_id),
+        None => CreditAccount::User(sender_id),
+    };
+
+    let service =
+        CreditTransferService::new(state.credit_repo.clone(), state.settings_service.clone());
+
+    // A retried request returns the original transfer
+    if let Some(existing) = service.find_existing(sender_id, idempotency_key).await? {
+        return Ok(Json(CreditTransferResponse::from_transfer(existing, None)));
+    }
+
+    verify_recipient(&state, sender_id, to).await?;
+
+    let prepared = service
+        .prepare(TransferRequest {
+            sender_id,
+            from,
+            to,
+            amount: request.amount_lamports,
+            currency: request.currency.to_uppercase(),
+            idempotency_key: idempotency_key.to_string(),
+            note: request.note,
+        })
+        .await?;
+
+    if prepared.step_up_required {
+        let session_id = match (auth.is_api_key_auth, auth.session_id) {
+            (false, Some(session_id)) => session_id,
+            _ => {
+                return Err(AppError::Forbidden(
+                    "Transfers of this size require an interactive session".into(),
+                ))
+            }
+        };
+        state.step_up_service.require_step_up(session_id).await?;
+    }
+
+    let transfer = prepared.transfer.clone();
+    #[cfg(feature = "postgres")]
+    let written = if let Some(pool) = state.postgres_pool.as_ref() {
+        crate::repositories::TransactionalOps::transfer_credits_atomic(
+            pool,
+            prepared.debit,
+            prepared.credit,
+            prepared.daily_limit,
+            prepared.day_start,
+        )
+        .await
+        .map(|(sender_balance, _)| sender_balance)
+    } else {
+        service.apply(prepared).await
+    };
+    #[cfg(not(feature = "postgres"))]
+    let written = service.apply(prepared).await;
+    let new_balance = match written {
+        Ok(balance) => balance,
+        Err(e) => {
+            // A concurrent retry with the same key may have written first;
+            // the debit leg's unique idempotency key rejects this one
+            if let Some(existing) = service.find_existing(sender_id, idempotency_key).await? {
+                return Ok(Json(CreditTransferResponse::from_transfer(existing, None)));
+            }
+            return Err(e);
+        }
+    };
+
+    tracing::info!(
+        transfer_id = %transfer.id,
+        sender_id = %sender_id,
+        from = %transfer.from.id(),
+        to = %transfer.to.id(),
+        amount_lamports = transfer.amount,
+        currency = %transfer.currency,
+        "Credit transfer"
+    );
+
+    audit_transfer(&state, &headers, &transfer).await;
+
+    if let Err(e) = state.comms_service.notify_credit_transfer(&transfer).await {
+        tracing::warn!(
+            error = %e,
+            transfer_id = %transfer.id,
+            "Failed to queue credit transfer notifications"
+        );
+    }
+
+    state
+        .webhook_service
+        .dispatch_or_warn(
+            WebhookEvent::CreditsTransferred,
+            serde_json::json!({
+                "transfer_id": transfer.id,
+                "transaction_id": transfer.transaction_id,
+                "sender_id": transfer.sender_id,
+                "from_type": transfer.from.owner_type(),
+                "from_id": transfer.from.id(),
+                "to_type": transfer.to.owner_type(),
+                "to_id": transfer.to.id(),
+                "amount_lamports": transfer.amount,
+                "currency": transfer.currency,
+            }),
+        )
+        .await;
+
+    Ok(Json(CreditTransferResponse::from_transfer(
+        transfer,
+        Some(new_balance),
+    )))
+}
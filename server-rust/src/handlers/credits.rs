@@ -4,6 +4,9 @@
 //! GET /credits/balance/sol - Get SOL credit balance
 //! GET /credits/history - Get transaction history
 //! GET /credits/holds - Get pending credit holds
+//!
+//! Balance, history and holds take an optional `orgId` to read an
+//! organization's shared balance instead (requires `credits:read`).
 //! GET /credits/usage - Get usage analytics
 //! POST /credits/refund-request - Submit a refund request
 
@@ -14,16 +17,18 @@ use axum::{
 };
 use serde::Deserialize;
 use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::callback::AuthCallback;
 use crate::errors::AppError;
+use crate::handlers::require_org_credit_permission;
 use crate::models::{
     BalancesResponse, CreditBalanceResponse, CreditHistoryResponse, CreditUsageResponse,
     PendingHoldsResponse, RefundRequestInput, RefundRequestResponse,
 };
 use crate::repositories::CreditRefundRequestEntity;
-use crate::services::{CreditService, EmailService};
-use crate::utils::authenticate;
+use crate::services::{CreditService, EmailService, MeteringService, Permission};
+use crate::utils::{authenticate, authenticate_for_org};
 use crate::AppState;
 
 /// Query parameters for credit history
@@ -40,29 +45,61 @@ pub struct CreditHistoryParams {
     /// Offset for pagination (default 0)
     #[serde(default)]
     pub offset: u32,
+    /// Organization ledger to read instead of the personal one
+    pub org_id: Option<Uuid>,
 }
 
 fn default_limit() -> u32 {
     20
 }
 
+/// Query parameters selecting an organization's balance
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrgBalanceParams {
+    /// Organization balance to read instead of the personal one
+    pub org_id: Option<Uuid>,
+}
+
+/// Authenticate the caller and, for an org-scoped read, require
+/// `credits:read` in that organization
+pub(crate) async fn authenticate_credit_reader<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Option<Uuid>,
+) -> Result<Uuid, AppError> {
+    match org_id {
+        Some(org_id) => {
+            let auth = authenticate_for_org(state, headers, org_id).await?;
+            require_org_credit_permission(state, auth.user_id, org_id, Permission::CreditsRead)
+                .await?;
+            Ok(auth.user_id)
+        }
+        None => Ok(authenticate(state, headers).await?.user_id),
+    }
+}
+
 /// GET /credits/balance - Get user's credit balances (all currencies)
 pub async fn get_balance<C: AuthCallback, E: EmailService>(
     State(state): State<Arc<AppState<C, E>>>,
     headers: HeaderMap,
+    Query(params): Query<OrgBalanceParams>,
 ) -> Result<Json<BalancesResponse>, AppError> {
     if !state.config.privacy.enabled {
         return Err(AppError::NotFound("Credits not enabled".into()));
     }
 
-    let auth_user = authenticate(&state, &headers).await?;
+    let user_id = authenticate_credit_reader(&state, &headers, params.org_id).await?;
 
     // Create credit service
     let credit_service =
         CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
 
-    // Get all balances
-    let balances = credit_service.get_all_balances(auth_user.user_id).await?;
+    // Get all balances (currently just SOL)
+    let balances = match params.org_id {
+        Some(org_id) => vec![credit_service.get_org_balance(org_id, "SOL").await?],
+        None => credit_service.get_all_balances(user_id).await?,
+    };
 
     Ok(Json(BalancesResponse {
         balances: balances.into_iter().map(Into::into).collect(),
@@ -100,7 +137,7 @@ pub async fn get_history<C: AuthCallback, E: EmailService>(
         return Err(AppError::NotFound("Credits not enabled".into()));
     }
 
-    let auth_user = authenticate(&state, &headers).await?;
+    let user_id = authenticate_credit_reader(&state, &headers, params.org_id).await?;
 
     // Cap limit at 100
     let limit = params.limit.min(100);
@@ -109,16 +146,21 @@ pub async fn get_history<C: AuthCallback, E: EmailService>(
     let credit_service =
         CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
 
-    // Get transaction history
-    let history = credit_service
-        .get_history(
-            auth_user.user_id,
-            params.currency.as_deref(),
-            params.tx_type.as_deref(),
-            limit,
-            params.offset,
-        )
-        .await?;
+    // Get transaction history of the personal or organization ledger
+    let currency = params.currency.as_deref();
+    let tx_type = params.tx_type.as_deref();
+    let history = match params.org_id {
+        Some(org_id) => {
+            credit_service
+                .get_org_history(org_id, currency, tx_type, limit, params.offset)
+                .await?
+        }
+        None => {
+            credit_service
+                .get_history(user_id, currency, tx_type, limit, params.offset)
+                .await?
+        }
+    };
 
     Ok(Json(history.into()))
 }
@@ -129,6 +171,8 @@ pub async fn get_history<C: AuthCallback, E: EmailService>(
 pub struct PendingHoldsParams {
     /// Currency filter (optional, defaults to all)
     pub currency: Option<String>,
+    /// Organization balance to read instead of the personal one
+    pub org_id: Option<Uuid>,
 }
 
 /// GET /credits/holds - Get user's pending credit holds
@@ -141,18 +185,24 @@ pub async fn get_pending_holds<C: AuthCallback, E: EmailService>(
         return Err(AppError::NotFound("Credits not enabled".into()));
     }
 
-    let auth_user = authenticate(&state, &headers).await?;
+    let user_id = authenticate_credit_reader(&state, &headers, params.org_id).await?;
 
     // Create credit service
     let credit_service =
         CreditService::new(state.credit_repo.clone(), state.credit_hold_repo.clone());
 
     // Get pending holds
-    let holds = credit_service
-        .get_pending_holds(auth_user.user_id, params.currency.as_deref())
-        .await?;
-
-    let total_held: i64 = holds.iter().map(|h| h.amount).sum();
+    let currency = params.currency.as_deref();
+    let holds = match params.org_id {
+        Some(org_id) => {
+            credit_service
+                .get_pending_org_holds(org_id, currency)
+                .await?
+        }
+        None => credit_service.get_pending_holds(user_id, currency).await?,
+    };
+
+    let total_held: i64 = holds.iter().map(|h| h.remaining()).sum();
 
     Ok(Json(PendingHoldsResponse {
         holds: holds.into_iter().map(Into::into).collect(),
@@ -176,7 +226,7 @@ fn default_sol() -> String {
 /// GET /credits/usage - Get credit usage analytics
 ///
 /// Returns user's credit usage statistics including total deposited,
-/// total spent, and transaction counts.
+/// total spent, transaction counts, and metered usage per meter.
 pub async fn get_usage<C: AuthCallback, E: EmailService>(
     State(state): State<Arc<AppState<C, E>>>,
     headers: HeaderMap,
@@ -197,7 +247,20 @@ pub async fn get_usage<C: AuthCallback, E: EmailService>(
         .get_user_stats(auth_user.user_id, &params.currency)
         .await?;
 
-    Ok(Json(stats.into()))
+    // Break metered usage down per meter
+    let metering_service = MeteringService::new(
+        state.storage.meter_repo.clone(),
+        state.credit_repo.clone(),
+        state.credit_hold_repo.clone(),
+        state.membership_repo.clone(),
+    );
+    let meters = metering_service
+        .usage_by_meter(auth_user.user_id, &params.currency)
+        .await?;
+
+    let mut response: CreditUsageResponse = stats.into();
+    response.meters = meters.into_iter().map(Into::into).collect();
+    Ok(Json(response))
 }
 
 /// POST /credits/refund-request - Submit a refund request
@@ -215,6 +278,7 @@ pub async fn request_refund<C: AuthCallback, E: EmailService>(
     }
 
     let auth_user = authenticate(&state, &headers).await?;
+    auth_user.ensure_not_impersonated("request refunds")?;
 
     // Validate input
     if input.amount_lamports <= 0 {
@@ -247,6 +311,12 @@ pub async fn request_refund<C: AuthCallback, E: EmailService>(
         ));
     }
 
+    if original_tx.org_id.is_some() {
+        return Err(AppError::Validation(
+            "Organization credit transactions cannot be refunded by members".into(),
+        ));
+    }
+
     if original_tx.amount <= 0 {
         return Err(AppError::Validation(
             "Only positive credit transactions can be refunded".into(),
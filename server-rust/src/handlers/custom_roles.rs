@@ -16,7 +16,7 @@ use crate::models::MessageResponse;
 use crate::repositories::pagination::{cap_limit, cap_offset};
 use crate::repositories::{AuditEventType, CustomRole, OrgRole};
 use crate::services::{EmailService, Permission};
-use crate::utils::authenticate;
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
 /// Request to create a custom role
@@ -107,7 +107,7 @@ async fn verify_org_admin<C: AuthCallback, E: EmailService>(
     org_id: Uuid,
 ) -> Result<Uuid, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(state, headers).await?;
+    let auth = authenticate_for_org(state, headers, org_id).await?;
 
     // Verify membership and role
     let membership = state
@@ -132,7 +132,7 @@ pub async fn list_custom_roles<C: AuthCallback, E: EmailService>(
     Query(params): Query<ListCustomRolesQueryParams>,
 ) -> Result<Json<ListCustomRolesResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Verify membership
     state
@@ -196,7 +196,7 @@ pub async fn get_custom_role<C: AuthCallback, E: EmailService>(
     Path((org_id, role_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<CustomRoleResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Verify membership
     state
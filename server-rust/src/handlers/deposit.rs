@@ -19,7 +19,7 @@ use uuid::Uuid;
 #[cfg(feature = "postgres")]
 use sqlx::FromRow;
 
-use crate::callback::AuthCallback;
+use crate::callback::{AuthCallback, WebhookEvent};
 use crate::errors::AppError;
 use crate::models::{
     ConfirmSplDepositRequest, ConfirmSplDepositResponse, DepositConfigResponse,
@@ -100,6 +100,7 @@ pub async fn execute_deposit<C: AuthCallback, E: EmailService>(
 
     // Authenticate user
     let auth_user = authenticate(&state, &headers).await?;
+    auth_user.ensure_not_impersonated("deposit funds")?;
 
     // Get wallet material - user must have enrolled SSS wallet
     let wallet_material = state
@@ -171,6 +172,20 @@ pub async fn execute_deposit<C: AuthCallback, E: EmailService>(
         )
         .await?;
 
+    state
+        .webhook_service
+        .dispatch_or_warn(
+            WebhookEvent::DepositCompleted,
+            serde_json::json!({
+                "user_id": auth_user.user_id,
+                "session_id": result.session_id,
+                "deposit_type": "privacy",
+                "tx_signature": result.tx_signature,
+                "amount_lamports": result.amount_lamports,
+            }),
+        )
+        .await;
+
     let sol_amount = result.amount_lamports as f64 / 1_000_000_000.0;
 
     Ok(Json(PrivacyDepositResponse {
@@ -540,6 +555,7 @@ pub async fn confirm_spl_deposit<C: AuthCallback, E: EmailService>(
     }
 
     let auth_user = authenticate(&state, &headers).await?;
+    auth_user.ensure_not_impersonated("deposit funds")?;
 
     #[cfg(feature = "postgres")]
     let pool = state.postgres_pool.as_ref().ok_or_else(|| {
@@ -691,6 +707,21 @@ pub async fn confirm_spl_deposit<C: AuthCallback, E: EmailService>(
                 );
             }
 
+            state
+                .webhook_service
+                .dispatch_or_warn(
+                    WebhookEvent::DepositCompleted,
+                    serde_json::json!({
+                        "user_id": auth_user.user_id,
+                        "session_id": ok.session_id,
+                        "deposit_type": "privacy_spl",
+                        "tx_signature": ok.deposit_tx_signature,
+                        "amount_lamports": ok.sol_amount_lamports,
+                        "input_mint": ok.input_mint,
+                    }),
+                )
+                .await;
+
             Ok(Json(ConfirmSplDepositResponse {
                 success: true,
                 pending_id: pending.id,
@@ -12,7 +12,7 @@ use axum::{
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::callback::AuthCallback;
+use crate::callback::{AuthCallback, WebhookEvent};
 use crate::errors::AppError;
 use crate::models::DepositQuoteResponse;
 use crate::services::{EmailService, JupiterOrderParams, TieredDepositService};
@@ -141,6 +141,7 @@ pub async fn execute_public_deposit<C: AuthCallback, E: EmailService>(
     }
 
     let auth_user = authenticate(&state, &headers).await?;
+    auth_user.ensure_not_impersonated("deposit funds")?;
 
     // Require Jupiter swap service
     let jupiter = state.jupiter_swap_service.as_ref().ok_or_else(|| {
@@ -189,6 +190,21 @@ pub async fn execute_public_deposit<C: AuthCallback, E: EmailService>(
         )
         .await?;
 
+    state
+        .webhook_service
+        .dispatch_or_warn(
+            WebhookEvent::DepositCompleted,
+            serde_json::json!({
+                "user_id": auth_user.user_id,
+                "session_id": result.session_id,
+                "deposit_type": "public",
+                "tx_signature": result.tx_signature,
+                "credit_amount": result.output_amount,
+                "credit_currency": result.credit_currency,
+            }),
+        )
+        .await;
+
     Ok(Json(TieredDepositResponse {
         session_id: result.session_id,
         tx_signature: result.tx_signature,
@@ -224,6 +240,7 @@ pub async fn execute_micro_deposit<C: AuthCallback, E: EmailService>(
     }
 
     let auth_user = authenticate(&state, &headers).await?;
+    auth_user.ensure_not_impersonated("deposit funds")?;
 
     // Resolve the configured treasury wallet (global)
     let treasury_config = state
@@ -324,6 +341,20 @@ pub async fn execute_micro_deposit<C: AuthCallback, E: EmailService>(
         )
         .await?;
 
+    state
+        .webhook_service
+        .dispatch_or_warn(
+            WebhookEvent::DepositCompleted,
+            serde_json::json!({
+                "user_id": auth_user.user_id,
+                "session_id": result.session_id,
+                "deposit_type": "sol_micro",
+                "tx_signature": result.tx_signature,
+                "amount_lamports": result.amount_lamports,
+            }),
+        )
+        .await;
+
     let sol_amount = result.amount_lamports as f64 / LAMPORTS_PER_SOL;
 
     Ok(Json(TieredDepositResponse {
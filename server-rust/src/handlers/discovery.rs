@@ -362,6 +362,8 @@ mod tests {
             sso: crate::config::SsoConfig::default(),
             wallet: crate::config::WalletConfig::default(),
             privacy: crate::config::PrivacyConfig::default(),
+            audit_sinks: crate::config::AuditSinkConfig::default(),
+            stripe: crate::config::StripeConfig::default(),
         };
 
         config.solana.enabled = false;
@@ -397,6 +399,8 @@ mod tests {
             sso: crate::config::SsoConfig::default(),
             wallet: crate::config::WalletConfig::default(),
             privacy: crate::config::PrivacyConfig::default(),
+            audit_sinks: crate::config::AuditSinkConfig::default(),
+            stripe: crate::config::StripeConfig::default(),
         };
 
         let methods = build_auth_methods(&config, "/auth/v2");
@@ -0,0 +1,360 @@
+//! Verified email address change
+//!
+//! `POST /me/email` sends a confirmation link to the new address; nothing
+//! changes until that link is followed. Confirming swaps the address and sends
+//! the previous address a notice with a revert link, so a hijacked session
+//! cannot silently take over the account's recovery channel. Reverting
+//! restores the old address and signs out every session.
+//!
+//! Memberships in organizations whose SSO provider is restricted to the old
+//! email domain are removed when the new address leaves that domain. They are
+//! not restored by a revert.
+
+use axum::{extract::State, http::HeaderMap, Json};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::models::MessageResponse;
+use crate::repositories::{
+    default_expiry, generate_verification_token, hash_verification_token, normalize_email,
+    AuditEventType, TokenType, UserEntity,
+};
+use crate::services::EmailService;
+use crate::utils::{authenticate, validate_account_email};
+use crate::AppState;
+
+/// Session revocation reason recorded when an email change is reverted
+const REVOKE_REASON_REVERTED: &str = "email_change_reverted";
+
+/// Request to change the caller's email address
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEmailRequest {
+    pub new_email: String,
+}
+
+/// Request carrying an email change confirmation or revert token
+#[derive(Debug, Deserialize)]
+pub struct EmailChangeTokenRequest {
+    pub token: String,
+}
+
+/// POST /auth/me/email - Request an email address change
+///
+/// Requires recent strong authentication (step-up). The new address applies
+/// only once the link sent to it is confirmed.
+pub async fn request_email_change<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(req): Json<ChangeEmailRequest>,
+) -> Result<Json<MessageResponse>, AppError> {
+    let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("change the email address")?;
+    let session_id = match (auth.is_api_key_auth, auth.session_id) {
+        (false, Some(session_id)) => session_id,
+        _ => {
+            return Err(AppError::Forbidden(
+                "Changing email requires an interactive session".into(),
+            ))
+        }
+    };
+    state.step_up_service.require_step_up(session_id).await?;
+
+    let user = state
+        .user_repo
+        .find_by_id(auth.user_id)
+        .await?
+        .ok_or(AppError::InvalidToken)?;
+
+    let new_email = validate_account_email(&state, &req.new_email).await?;
+    if user.email.as_deref().map(normalize_email).as_deref() == Some(new_email.as_str()) {
+        return Err(AppError::Validation(
+            "New email is the same as the current one".into(),
+        ));
+    }
+    if state.user_repo.email_exists(&new_email).await? {
+        return Err(AppError::EmailExists);
+    }
+
+    let token = generate_verification_token();
+    state
+        .verification_repo
+        .create_with_payload(
+            user.id,
+            &hash_verification_token(&token),
+            TokenType::EmailChange,
+            default_expiry(TokenType::EmailChange),
+            &new_email,
+        )
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create token: {}", e)))?;
+
+    state
+        .comms_service
+        .queue_email_change_email(&new_email, user.name.as_deref(), &token, user.id)
+        .await?;
+
+    state
+        .audit_service
+        .log_user_event_or_warn(
+            AuditEventType::UserEmailChangeRequested,
+            user.id,
+            Some(&headers),
+        )
+        .await;
+
+    Ok(Json(MessageResponse {
+        message: "A confirmation link has been sent to the new email address".to_string(),
+    }))
+}
+
+/// POST /auth/me/email/confirm - Apply an email change from the confirmation link
+pub async fn confirm_email_change<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(req): Json<EmailChangeTokenRequest>,
+) -> Result<Json<MessageResponse>, AppError> {
+    let (mut user, new_email) =
+        consume_email_change_token(&state, &req.token, TokenType::EmailChange).await?;
+
+    // The address may have been claimed since the link was sent
+    if state.user_repo.email_exists(&new_email).await? {
+        return Err(AppError::EmailExists);
+    }
+
+    let old_email = user.email.replace(new_email.clone());
+    user.email_verified = true;
+    let user = state.user_repo.update(user).await?;
+
+    // Links already sent to the old address must not keep working
+    for token_type in [
+        TokenType::EmailVerify,
+        TokenType::PasswordReset,
+        TokenType::InstantLink,
+    ] {
+        state
+            .verification_repo
+            .delete_for_user(user.id, token_type)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to delete tokens: {}", e)))?;
+    }
+
+    if let Some(old_email) = old_email {
+        let token = generate_verification_token();
+        state
+            .verification_repo
+            .create_with_payload(
+                user.id,
+                &hash_verification_token(&token),
+                TokenType::EmailChangeRevert,
+                default_expiry(TokenType::EmailChangeRevert),
+                &old_email,
+            )
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to create token: {}", e)))?;
+        state
+            .comms_service
+            .queue_email_change_notice_email(
+                &old_email,
+                user.name.as_deref(),
+                &new_email,
+                &token,
+                user.id,
+            )
+            .await?;
+
+        remove_sso_domain_memberships(&state, user.id, &old_email, &new_email, &headers).await?;
+    }
+
+    state
+        .audit_service
+        .log_user_event_or_warn(AuditEventType::UserEmailChanged, user.id, Some(&headers))
+        .await;
+
+    Ok(Json(MessageResponse {
+        message: "Email address changed successfully".to_string(),
+    }))
+}
+
+/// POST /auth/me/email/revert - Undo an email change from the notice link
+///
+/// Restores the previous address, cancels pending email changes and password
+/// resets, and signs out every session.
+pub async fn revert_email_change<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(req): Json<EmailChangeTokenRequest>,
+) -> Result<Json<MessageResponse>, AppError> {
+    let (mut user, old_email) =
+        consume_email_change_token(&state, &req.token, TokenType::EmailChangeRevert).await?;
+
+    if let Some(owner) = state.user_repo.find_by_email(&old_email).await? {
+        if owner.id != user.id {
+            return Err(AppError::EmailExists);
+        }
+    }
+
+    user.email = Some(old_email);
+    user.email_verified = true;
+    let user = state.user_repo.update(user).await?;
+
+    for token_type in [
+        TokenType::EmailChange,
+        TokenType::EmailChangeRevert,
+        TokenType::PasswordReset,
+        TokenType::InstantLink,
+    ] {
+        state
+            .verification_repo
+            .delete_for_user(user.id, token_type)
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to delete tokens: {}", e)))?;
+    }
+    state
+        .session_repo
+        .revoke_all_for_user_with_reason(user.id, REVOKE_REASON_REVERTED)
+        .await?;
+
+    state
+        .audit_service
+        .log_user_event_or_warn(
+            AuditEventType::UserEmailChangeReverted,
+            user.id,
+            Some(&headers),
+        )
+        .await;
+
+    Ok(Json(MessageResponse {
+        message: "Email address restored. All sessions have been signed out; reset your \
+                  password if you no longer know it."
+            .to_string(),
+    }))
+}
+
+/// Consume a token of the expected type and return its user and payload
+async fn consume_email_change_token<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    token: &str,
+    expected: TokenType,
+) -> Result<(UserEntity, String), AppError> {
+    // Atomically consume the token (prevents TOCTOU race conditions)
+    let token = state
+        .verification_repo
+        .consume_if_valid(&hash_verification_token(token))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to consume token: {}", e)))?
+        .ok_or_else(|| AppError::Validation("Invalid or expired token".to_string()))?;
+
+    if token.token_type != expected {
+        return Err(AppError::Validation("Invalid token type".to_string()));
+    }
+    let email = token
+        .payload
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Email change token has no payload")))?;
+
+    let user = state
+        .user_repo
+        .find_by_id(token.user_id)
+        .await?
+        .ok_or_else(|| AppError::Validation("Invalid or expired token".to_string()))?;
+    user.ensure_active()?;
+
+    Ok((user, email))
+}
+
+/// Remove the user from organizations whose enabled SSO provider is bound to
+/// the old email domain but not the new one. Personal orgs and last owners
+/// are left in place.
+async fn remove_sso_domain_memberships<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    user_id: Uuid,
+    old_email: &str,
+    new_email: &str,
+    headers: &HeaderMap,
+) -> Result<(), AppError> {
+    let (Some(old_domain), Some(new_domain)) = (email_domain(old_email), email_domain(new_email))
+    else {
+        return Ok(());
+    };
+    if old_domain == new_domain {
+        return Ok(());
+    }
+
+    for membership in state.membership_repo.find_by_user(user_id).await? {
+        let providers = state
+            .storage
+            .sso_repo
+            .find_providers_by_org(membership.org_id)
+            .await?;
+        let domains: Vec<&str> = providers
+            .iter()
+            .filter(|p| p.enabled)
+            .filter_map(|p| p.email_domain.as_deref())
+            .collect();
+        if !domain_bound(&domains, &old_domain) || domain_bound(&domains, &new_domain) {
+            continue;
+        }
+        let Some(org) = state.org_repo.find_by_id(membership.org_id).await? else {
+            continue;
+        };
+        if org.is_personal {
+            continue;
+        }
+        if state
+            .membership_repo
+            .delete_if_not_last_owner(membership.id, org.id)
+            .await?
+        {
+            let _ = state
+                .audit_service
+                .log_member_event(
+                    AuditEventType::MemberRemoved,
+                    user_id,
+                    org.id,
+                    user_id,
+                    Some(serde_json::json!({ "reason": "email_domain_changed" })),
+                    Some(headers),
+                )
+                .await;
+        }
+    }
+    Ok(())
+}
+
+/// Lowercased domain part of an email address
+fn email_domain(email: &str) -> Option<String> {
+    email
+        .rsplit_once('@')
+        .map(|(_, domain)| domain.trim().to_lowercase())
+}
+
+/// Whether any of the SSO-restricted domains matches `domain`
+fn domain_bound(sso_domains: &[&str], domain: &str) -> bool {
+    sso_domains
+        .iter()
+        .any(|d| d.trim().eq_ignore_ascii_case(domain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_email_request_deserialize() {
+        let req: ChangeEmailRequest =
+            serde_json::from_str(r#"{"newEmail": "new@example.com"}"#).unwrap();
+        assert_eq!(req.new_email, "new@example.com");
+    }
+
+    #[test]
+    fn test_domain_bound() {
+        let domain = email_domain("Jane@Corp.Example.com").unwrap();
+        assert_eq!(domain, "corp.example.com");
+        assert!(domain_bound(&["CORP.example.com"], &domain));
+        assert!(!domain_bound(&["example.com"], &domain));
+        assert!(!domain_bound(&[], &domain));
+    }
+}
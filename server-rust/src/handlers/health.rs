@@ -59,9 +59,9 @@ mod tests {
     use super::*;
     use crate::config::{
         default_access_expiry, default_audience, default_issuer, default_refresh_expiry,
-        AppleConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig, GoogleConfig,
-        JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig, ServerConfig, SolanaConfig,
-        SsoConfig, WalletConfig, WebAuthnConfig, WebhookConfig,
+        AppleConfig, AuditSinkConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig,
+        GoogleConfig, JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig, ServerConfig,
+        SolanaConfig, SsoConfig, StripeConfig, WalletConfig, WebAuthnConfig, WebhookConfig,
     };
     use crate::repositories::LoginAttemptConfig;
     use crate::services::{
@@ -112,6 +112,8 @@ mod tests {
             sso: SsoConfig::default(),
             wallet: WalletConfig::default(),
             privacy: PrivacyConfig::default(),
+            audit_sinks: AuditSinkConfig::default(),
+            stripe: StripeConfig::default(),
         }
     }
 
@@ -137,6 +139,11 @@ mod tests {
             "http://localhost:3000".to_string(),
             token_cipher,
         );
+        let webhook_service = Arc::new(crate::services::WebhookService::new(
+            storage.webhook_repo.clone(),
+            storage.outbox_repo.clone(),
+            TokenCipher::new(&config.jwt.secret),
+        ));
 
         Arc::new(AppState {
             config,
@@ -153,6 +160,7 @@ mod tests {
             phantom_email: std::marker::PhantomData::<LogEmailService>,
             audit_service,
             comms_service,
+            webhook_service,
             user_repo: storage.user_repo.clone(),
             session_repo: storage.session_repo.clone(),
             nonce_repo: storage.nonce_repo.clone(),
@@ -168,6 +176,7 @@ mod tests {
             policy_repo: storage.policy_repo.clone(),
             outbox_repo: storage.outbox_repo.clone(),
             api_key_repo: storage.api_key_repo.clone(),
+            api_key_rate_limiter: crate::middleware::rate_limit::RateLimitStore::new(),
             wallet_material_repo: storage.wallet_material_repo.clone(),
             derived_wallet_repo: storage.derived_wallet_repo.clone(),
             wallet_rotation_history_repo: storage.wallet_rotation_history_repo.clone(),
@@ -0,0 +1,320 @@
+//! Impersonation handlers
+//!
+//! Lets support staff see what a user sees. System admins can impersonate any
+//! non-admin user; org owners can impersonate the members of their org when
+//! `impersonation_org_owners_enabled` is set. Impersonation tokens carry an
+//! RFC 8693 `act` claim naming the impersonator, are read-only unless
+//! `allowWrites` is requested, expire after `impersonation_duration_mins` and
+//! cannot be refreshed. Wallet signing, moving money (withdrawals, deposits,
+//! transfers, spends, top-ups) and changing credentials (passwords, MFA,
+//! passkeys, API keys) are always refused (see
+//! `AuthenticatedUser::ensure_not_impersonated`).
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap},
+    Json,
+};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::{AppError, ERR_NOT_A_MEMBER};
+use crate::handlers::admin::validate_system_admin;
+use crate::models::{ImpersonationResponse, MessageResponse, StartImpersonationRequest};
+use crate::repositories::{AuditEventType, AuditLogBuilder, OrgRole, SessionEntity, UserEntity};
+use crate::services::{ActorClaim, EmailService, TokenContext};
+use crate::utils::{
+    authenticate, authenticate_for_org, extract_client_ip, get_default_org_context,
+    hash_refresh_token, metadata_claims,
+};
+use crate::AppState;
+
+/// Default impersonation session lifetime in minutes
+const DEFAULT_IMPERSONATION_DURATION_MINS: u32 = 15;
+
+/// Upper bound on the configured lifetime, whatever the setting says
+const MAX_IMPERSONATION_DURATION_MINS: u32 = 60;
+
+/// Maximum length of the audit reason
+const MAX_REASON_LENGTH: usize = 500;
+
+/// Session revocation reason recorded when an impersonation is ended
+const REVOKE_REASON_ENDED: &str = "impersonation_ended";
+
+/// POST /admin/users/:user_id/impersonate - Start impersonating a user (system admin)
+pub async fn impersonate_user<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+    Json(req): Json<StartImpersonationRequest>,
+) -> Result<Json<ImpersonationResponse>, AppError> {
+    let admin_id = validate_system_admin(&state, &headers).await?;
+    let target = load_target(&state, admin_id, user_id).await?;
+
+    let memberships = state.membership_repo.find_by_user(target.id).await?;
+    let context = get_default_org_context(&memberships, false, target.email_verified);
+
+    let response =
+        start_impersonation(&state, &headers, admin_id, &target, context, None, &req).await?;
+    Ok(Json(response))
+}
+
+/// POST /orgs/:org_id/members/:user_id/impersonate - Start impersonating a member (org owner)
+///
+/// Disabled unless `impersonation_org_owners_enabled` is set. The session is
+/// confined to the org: org-scoped endpoints for other orgs are refused.
+pub async fn impersonate_member<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<StartImpersonationRequest>,
+) -> Result<Json<ImpersonationResponse>, AppError> {
+    let enabled = state
+        .settings_service
+        .get_bool("impersonation_org_owners_enabled")
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(false);
+    if !enabled {
+        return Err(AppError::Forbidden(
+            "Organization owners cannot impersonate members".into(),
+        ));
+    }
+
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
+    auth.ensure_not_impersonated("start impersonation")?;
+    if auth.is_api_key_auth {
+        return Err(AppError::Forbidden(
+            "Impersonation requires an interactive session".into(),
+        ));
+    }
+
+    let caller = state
+        .membership_repo
+        .find_by_user_and_org(auth.user_id, org_id)
+        .await?
+        .ok_or(AppError::Forbidden(ERR_NOT_A_MEMBER.into()))?;
+    if caller.role != OrgRole::Owner {
+        return Err(AppError::Forbidden(
+            "Only organization owners can impersonate members".into(),
+        ));
+    }
+
+    let target = load_target(&state, auth.user_id, user_id).await?;
+    let membership = state
+        .membership_repo
+        .find_by_user_and_org(target.id, org_id)
+        .await?
+        .ok_or(AppError::NotFound("Member not found".into()))?;
+    if membership.role == OrgRole::Owner {
+        return Err(AppError::Forbidden("Cannot impersonate an owner".into()));
+    }
+
+    let context = TokenContext {
+        org_id: Some(org_id),
+        role: Some(membership.role.as_str().to_string()),
+        is_system_admin: None,
+        email_verified: Some(target.email_verified),
+        service_account: None,
+        act: None,
+        metadata: None,
+        ext: None,
+    };
+
+    let response = start_impersonation(
+        &state,
+        &headers,
+        auth.user_id,
+        &target,
+        context,
+        Some(org_id),
+        &req,
+    )
+    .await?;
+    Ok(Json(response))
+}
+
+/// POST /auth/impersonation/end - End the current impersonation session
+pub async fn end_impersonation<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+) -> Result<Json<MessageResponse>, AppError> {
+    let auth = authenticate(&state, &headers).await?;
+    let (Some(act), Some(session_id)) = (auth.impersonation.as_ref(), auth.session_id) else {
+        return Err(AppError::Validation("Not an impersonation session".into()));
+    };
+
+    state
+        .session_repo
+        .revoke_with_reason(session_id, REVOKE_REASON_ENDED)
+        .await?;
+
+    log_impersonation_event(
+        &state,
+        AuditEventType::ImpersonationEnded,
+        act,
+        auth.user_id,
+        session_id,
+        None,
+        &headers,
+    )
+    .await;
+
+    Ok(Json(MessageResponse {
+        message: "Impersonation ended".into(),
+    }))
+}
+
+/// Load the user to impersonate, refusing self, system admins and service accounts
+async fn load_target<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    impersonator_id: Uuid,
+    user_id: Uuid,
+) -> Result<UserEntity, AppError> {
+    if user_id == impersonator_id {
+        return Err(AppError::Validation("Cannot impersonate yourself".into()));
+    }
+
+    let target = state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or(AppError::NotFound("User not found".into()))?;
+    if target.is_system_admin {
+        return Err(AppError::Forbidden(
+            "Cannot impersonate a system administrator".into(),
+        ));
+    }
+    if target.is_service_account {
+        return Err(AppError::Forbidden(
+            "Cannot impersonate a service account".into(),
+        ));
+    }
+    Ok(target)
+}
+
+/// Create the impersonation session and token, and audit the start
+async fn start_impersonation<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    impersonator_id: Uuid,
+    target: &UserEntity,
+    mut context: TokenContext,
+    scoped_org_id: Option<Uuid>,
+    req: &StartImpersonationRequest,
+) -> Result<ImpersonationResponse, AppError> {
+    let reason = req
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+    if reason.is_some_and(|r| r.len() > MAX_REASON_LENGTH) {
+        return Err(AppError::Validation(format!(
+            "Reason must be at most {} characters",
+            MAX_REASON_LENGTH
+        )));
+    }
+
+    let duration_mins = state
+        .settings_service
+        .get_u32("impersonation_duration_mins")
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(DEFAULT_IMPERSONATION_DURATION_MINS)
+        .clamp(1, MAX_IMPERSONATION_DURATION_MINS);
+    let expires_in = u64::from(duration_mins) * 60;
+    let expires_at = Utc::now() + Duration::seconds(expires_in as i64);
+
+    let act = ActorClaim {
+        sub: impersonator_id,
+        write: req.allow_writes,
+        org_id: scoped_org_id,
+    };
+    context.act = Some(act.clone());
+    context.metadata = metadata_claims(state, target).await;
+
+    let session_id = Uuid::new_v4();
+    let access_token = state
+        .jwt_service
+        .generate_access_token_with_expiry(target.id, session_id, &context, expires_in)?;
+
+    // As with service accounts, the refresh hash is of a token that is never
+    // handed out, so the session cannot outlive its access token.
+    let unused_refresh = state.jwt_service.generate_refresh_token();
+    let session = SessionEntity::new_with_id(
+        session_id,
+        target.id,
+        hash_refresh_token(&unused_refresh, &state.config.jwt.secret),
+        expires_at,
+        extract_client_ip(headers, state.config.server.trust_proxy),
+        headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string()),
+    );
+    state.session_repo.create(session).await?;
+
+    log_impersonation_event(
+        state,
+        AuditEventType::ImpersonationStarted,
+        &act,
+        target.id,
+        session_id,
+        Some(serde_json::json!({
+            "readOnly": !req.allow_writes,
+            "expiresAt": expires_at,
+            "reason": reason,
+        })),
+        headers,
+    )
+    .await;
+
+    Ok(ImpersonationResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in,
+        expires_at,
+        session_id,
+        user_id: target.id,
+        impersonator_id,
+        org_id: context.org_id,
+        read_only: !req.allow_writes,
+    })
+}
+
+/// Write an impersonation audit event: the impersonator is the actor and the
+/// impersonated user the target
+async fn log_impersonation_event<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    event_type: AuditEventType,
+    act: &ActorClaim,
+    user_id: Uuid,
+    session_id: Uuid,
+    metadata: Option<serde_json::Value>,
+    headers: &HeaderMap,
+) {
+    let mut builder = AuditLogBuilder::new(event_type)
+        .actor(act.sub)
+        .session(session_id)
+        .target("user", user_id);
+    if let Some(org_id) = act.org_id {
+        builder = builder.org(org_id);
+    }
+    if let Some(ip) = extract_client_ip(headers, state.config.server.trust_proxy) {
+        builder = builder.ip(&ip);
+    }
+    if let Some(ua) = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+    {
+        builder = builder.user_agent(ua);
+    }
+    if let Some(meta) = metadata {
+        builder = builder.metadata(meta);
+    }
+    state.audit_service.log_or_warn(builder.build()).await;
+}
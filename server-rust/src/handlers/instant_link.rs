@@ -10,9 +10,11 @@ use chrono::{Duration, Utc};
 use serde::Deserialize;
 use std::sync::Arc;
 
-use crate::callback::{AuthCallback, AuthCallbackPayload};
+use crate::callback::{AuthCallback, AuthCallbackPayload, ClaimsTrigger, TokenClaimsPayload};
 use crate::errors::AppError;
-use crate::handlers::auth::call_authenticated_callback_with_timeout;
+use crate::handlers::auth::{
+    call_authenticated_callback_with_timeout, call_claims_callback_with_timeout,
+};
 use crate::models::{AuthMethod, AuthResponse, MessageResponse};
 use crate::repositories::{
     default_expiry, generate_verification_token, hash_verification_token, normalize_email,
@@ -21,7 +23,8 @@ use crate::repositories::{
 use crate::services::EmailService;
 use crate::utils::{
     build_json_response_with_cookies, extract_client_ip_with_fallback, get_default_org_context,
-    hash_refresh_token, is_new_device, user_entity_to_auth_user, DeviceInfo, PeerIp,
+    hash_refresh_token, is_new_device, metadata_claims, user_entity_to_auth_user, DeviceInfo,
+    PeerIp,
 };
 use crate::AppState;
 use serde_json::json;
@@ -247,9 +250,15 @@ pub async fn verify_instant_link<C: AuthCallback, E: EmailService>(
         .into_response());
     }
 
+    user.ensure_active()?;
+
     // Get user's memberships to find default org
     let memberships = state.membership_repo.find_by_user(user.id).await?;
-    let token_context = get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    let mut token_context =
+        get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    token_context.metadata = metadata_claims(&state, &user).await;
+    let claims_payload = TokenClaimsPayload::new(user.id, &token_context, ClaimsTrigger::Login);
+    token_context.ext = call_claims_callback_with_timeout(&state.callback, &claims_payload).await;
 
     // Create session
     let session_id = uuid::Uuid::new_v4();
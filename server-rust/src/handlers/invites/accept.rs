@@ -37,6 +37,7 @@ pub async fn accept_invite<C: AuthCallback, E: EmailService>(
         .find_by_token_hash(&token_hash)
         .await?
         .ok_or(AppError::NotFound("Invalid or expired invite".into()))?;
+    auth.ensure_org(invite.org_id)?;
 
     // Verify the invite recipient matches the authenticated user
     if let Some(ref invite_email) = invite.email {
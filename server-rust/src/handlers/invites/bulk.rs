@@ -0,0 +1,358 @@
+//! Bulk invite handler
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap},
+    Json,
+};
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::create::{check_email_recipient, check_wallet_recipient, issue_invite, InviteSender};
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::models::{
+    BulkInviteEntry, BulkInviteRequest, BulkInviteResponse, BulkInviteRowResult, BulkInviteStatus,
+};
+use crate::repositories::{normalize_email, OrgRole};
+use crate::services::EmailService;
+use crate::utils::{authenticate_for_org, is_valid_email, is_valid_wallet_address};
+use crate::AppState;
+
+/// Maximum rows accepted in one bulk invite request
+const MAX_BULK_INVITES: usize = 500;
+
+/// POST /orgs/:org_id/invites/bulk - Invite many recipients at once
+///
+/// Accepts `text/csv` (columns `email`, `wallet_address`, `role`; the header
+/// row is optional, in which case the columns are recipient then role) or JSON
+/// `{"invites": [...], "defaultRole": "member"}`. Each row is processed
+/// independently; existing members, pending invites and duplicate rows are
+/// skipped rather than failing the whole request.
+pub async fn bulk_create_invites<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    body: String,
+) -> Result<Json<BulkInviteResponse>, AppError> {
+    // Authenticate via JWT or API key
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
+
+    // Check caller's membership - must be admin+ to invite
+    let caller_membership = state
+        .membership_repo
+        .find_by_user_and_org(auth.user_id, org_id)
+        .await?
+        .ok_or(AppError::Forbidden(
+            "Not a member of this organization".into(),
+        ))?;
+
+    if !caller_membership.role.has_at_least(OrgRole::Admin) {
+        return Err(AppError::Forbidden(
+            "Only owners and admins can invite members".into(),
+        ));
+    }
+
+    let is_csv = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|ct| ct.starts_with("text/csv"))
+        .unwrap_or(false);
+    let request = if is_csv {
+        BulkInviteRequest {
+            invites: parse_csv(&body)?,
+            default_role: "member".to_string(),
+        }
+    } else {
+        serde_json::from_str::<BulkInviteRequest>(&body)
+            .map_err(|e| AppError::Validation(format!("Invalid request body: {}", e)))?
+    };
+
+    if request.invites.is_empty() {
+        return Err(AppError::Validation("No invites provided".into()));
+    }
+    if request.invites.len() > MAX_BULK_INVITES {
+        return Err(AppError::Validation(format!(
+            "At most {} invites per request",
+            MAX_BULK_INVITES
+        )));
+    }
+
+    let sender = InviteSender::load(&state, org_id, auth.user_id).await?;
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut results = Vec::with_capacity(request.invites.len());
+
+    for (index, entry) in request.invites.into_iter().enumerate() {
+        let mut result = BulkInviteRowResult {
+            row: index + 1,
+            email: None,
+            wallet_address: None,
+            status: BulkInviteStatus::Error,
+            reason: None,
+            invite_id: None,
+            token: None,
+        };
+
+        match prepare_row(
+            &state,
+            org_id,
+            caller_membership.role,
+            &request.default_role,
+            &entry,
+            &mut seen,
+            &mut result,
+        )
+        .await?
+        {
+            RowOutcome::Ready(role) => {
+                match issue_invite(
+                    &state,
+                    &sender,
+                    org_id,
+                    result.email.clone(),
+                    result.wallet_address.clone(),
+                    role,
+                )
+                .await
+                {
+                    Ok((invite, token)) => {
+                        result.status = BulkInviteStatus::Invited;
+                        result.invite_id = Some(invite.id);
+                        // Email invites are delivered by email; only wallet
+                        // invites need the token handed back
+                        if invite.wallet_address.is_some() {
+                            result.token = Some(token);
+                        }
+                    }
+                    Err(AppError::Validation(msg)) => result.reason = Some(msg),
+                    Err(e) => {
+                        tracing::warn!(error = %e, org_id = %org_id, "Bulk invite row failed");
+                        result.reason = Some("Failed to create invite".into());
+                    }
+                }
+            }
+            RowOutcome::Skipped(reason) => {
+                result.status = BulkInviteStatus::Skipped;
+                result.reason = Some(reason.into());
+            }
+            RowOutcome::Invalid(reason) => result.reason = Some(reason),
+        }
+
+        results.push(result);
+    }
+
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    Ok(Json(BulkInviteResponse {
+        invited: count(BulkInviteStatus::Invited),
+        skipped: count(BulkInviteStatus::Skipped),
+        failed: count(BulkInviteStatus::Error),
+        results,
+    }))
+}
+
+enum RowOutcome {
+    Ready(OrgRole),
+    Skipped(&'static str),
+    Invalid(String),
+}
+
+/// Validate one row and check it against existing members, pending invites
+/// and earlier rows. Fills in the normalized recipient on `result`.
+async fn prepare_row<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    org_id: Uuid,
+    caller_role: OrgRole,
+    default_role: &str,
+    entry: &BulkInviteEntry,
+    seen: &mut HashSet<String>,
+    result: &mut BulkInviteRowResult,
+) -> Result<RowOutcome, AppError> {
+    let role_str = entry.role.as_deref().unwrap_or(default_role);
+    let Some(role) = OrgRole::from_str(role_str) else {
+        return Ok(RowOutcome::Invalid(format!("Invalid role: {}", role_str)));
+    };
+    if role == OrgRole::Owner && caller_role != OrgRole::Owner {
+        return Ok(RowOutcome::Invalid(
+            "Only owners can invite new owners".into(),
+        ));
+    }
+
+    match (entry.email.as_deref(), entry.wallet_address.as_deref()) {
+        (Some(email_input), None) => {
+            // F-34: normalize email (NFKC + lowercase)
+            let email = normalize_email(email_input);
+            result.email = Some(email.clone());
+            if !is_valid_email(&email) {
+                return Ok(RowOutcome::Invalid("Invalid email format".into()));
+            }
+            if !seen.insert(format!("email:{}", email)) {
+                return Ok(RowOutcome::Skipped("Duplicate entry in request"));
+            }
+            if let Some(reason) = check_email_recipient(state, org_id, &email).await? {
+                return Ok(RowOutcome::Skipped(reason));
+            }
+        }
+        (None, Some(wallet_input)) => {
+            let wallet = wallet_input.trim().to_string();
+            result.wallet_address = Some(wallet.clone());
+            if !is_valid_wallet_address(&wallet) {
+                return Ok(RowOutcome::Invalid("Invalid wallet address format".into()));
+            }
+            if !seen.insert(format!("wallet:{}", wallet)) {
+                return Ok(RowOutcome::Skipped("Duplicate entry in request"));
+            }
+            if let Some(reason) = check_wallet_recipient(state, org_id, &wallet).await? {
+                return Ok(RowOutcome::Skipped(reason));
+            }
+        }
+        (None, None) => {
+            return Ok(RowOutcome::Invalid(
+                "Either email or walletAddress must be provided".into(),
+            ))
+        }
+        (Some(_), Some(_)) => {
+            return Ok(RowOutcome::Invalid(
+                "Cannot specify both email and walletAddress".into(),
+            ))
+        }
+    }
+
+    Ok(RowOutcome::Ready(role))
+}
+
+/// Parse a CSV body into bulk invite entries.
+///
+/// With a header row, the `email`, `wallet_address` (or `wallet`/`walletAddress`)
+/// and `role` columns are used. Without one, column 1 is the recipient (an email
+/// if it contains '@', otherwise a wallet address) and column 2 the role.
+fn parse_csv(body: &str) -> Result<Vec<BulkInviteEntry>, AppError> {
+    let mut rows = body
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(split_csv_line);
+
+    let Some(first) = rows.next() else {
+        return Ok(Vec::new());
+    };
+
+    let column = |header: &[String], names: &[&str]| {
+        header.iter().position(|h| {
+            let h = h.to_lowercase().replace(['_', ' '], "");
+            names.contains(&h.as_str())
+        })
+    };
+    let email_col = column(&first, &["email", "emailaddress"]);
+    let wallet_col = column(&first, &["wallet", "walletaddress"]);
+    let role_col = column(&first, &["role"]);
+
+    let field = |row: &[String], col: Option<usize>| {
+        col.and_then(|c| row.get(c))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    if email_col.is_some() || wallet_col.is_some() {
+        return Ok(rows
+            .map(|row| BulkInviteEntry {
+                email: field(&row, email_col),
+                wallet_address: field(&row, wallet_col),
+                role: field(&row, role_col),
+            })
+            .collect());
+    }
+    if role_col.is_some() {
+        return Err(AppError::Validation(
+            "CSV header must include an email or wallet_address column".into(),
+        ));
+    }
+
+    // Headerless: recipient, role
+    Ok(std::iter::once(first)
+        .chain(rows)
+        .map(|row| {
+            let recipient = field(&row, Some(0));
+            let is_email = recipient.as_deref().is_some_and(|r| r.contains('@'));
+            BulkInviteEntry {
+                email: recipient.clone().filter(|_| is_email),
+                wallet_address: recipient.filter(|_| !is_email),
+                role: field(&row, Some(1)),
+            }
+        })
+        .collect())
+}
+
+/// Split one CSV line into fields, honouring double-quoted fields and `""` escapes
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_csv_line_quotes() {
+        assert_eq!(
+            split_csv_line(r#"a@b.com,"admin, ""lead""",x"#),
+            vec!["a@b.com", r#"admin, "lead""#, "x"]
+        );
+        assert_eq!(split_csv_line("a,,b"), vec!["a", "", "b"]);
+    }
+
+    #[test]
+    fn test_parse_csv_with_header() {
+        let csv = "Email,Role,Wallet Address\r\nalice@acme.com,admin,\r\n\r\n,,Wa11et111\nbob@acme.com,,\n";
+        let rows = parse_csv(csv).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].email.as_deref(), Some("alice@acme.com"));
+        assert_eq!(rows[0].role.as_deref(), Some("admin"));
+        assert!(rows[0].wallet_address.is_none());
+        assert_eq!(rows[1].wallet_address.as_deref(), Some("Wa11et111"));
+        assert!(rows[1].email.is_none());
+        assert!(rows[2].role.is_none());
+    }
+
+    #[test]
+    fn test_parse_csv_headerless() {
+        let rows = parse_csv("alice@acme.com,viewer\nWa11et111\n").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].email.as_deref(), Some("alice@acme.com"));
+        assert_eq!(rows[0].role.as_deref(), Some("viewer"));
+        assert_eq!(rows[1].wallet_address.as_deref(), Some("Wa11et111"));
+        assert!(rows[1].role.is_none());
+    }
+
+    #[test]
+    fn test_parse_csv_role_only_header_rejected() {
+        assert!(parse_csv("role\nadmin\n").is_err());
+    }
+
+    #[test]
+    fn test_bulk_request_json_defaults() {
+        let json =
+            r#"{"invites": [{"email": "a@b.com"}, {"walletAddress": "W", "role": "admin"}]}"#;
+        let req: BulkInviteRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.default_role, "member");
+        assert_eq!(req.invites.len(), 2);
+        assert_eq!(req.invites[1].role.as_deref(), Some("admin"));
+    }
+}
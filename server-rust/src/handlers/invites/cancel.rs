@@ -13,7 +13,7 @@ use crate::errors::AppError;
 use crate::models::MessageResponse;
 use crate::repositories::OrgRole;
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
 /// DELETE /orgs/:org_id/invites/:invite_id - Cancel an invite
@@ -23,7 +23,7 @@ pub async fn cancel_invite<C: AuthCallback, E: EmailService>(
     Path((org_id, invite_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<MessageResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Check caller's membership - must be admin+ to cancel invites
     let caller_membership = state
@@ -16,7 +16,7 @@ use crate::repositories::{
     OrgRole,
 };
 use crate::services::EmailService;
-use crate::utils::{authenticate, is_valid_email, is_valid_wallet_address};
+use crate::utils::{authenticate_for_org, is_valid_email, is_valid_wallet_address};
 use crate::AppState;
 
 /// POST /orgs/:org_id/invites - Create an invite
@@ -30,7 +30,7 @@ pub async fn create_invite<C: AuthCallback, E: EmailService>(
     Json(req): Json<CreateInviteRequest>,
 ) -> Result<Json<InviteWithTokenResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Validate request: must have either email or wallet_address
     req.validate().map_err(|e| AppError::Validation(e.into()))?;
@@ -68,34 +68,9 @@ pub async fn create_invite<C: AuthCallback, E: EmailService>(
         if !is_valid_email(&email) {
             return Err(AppError::Validation("Invalid email format".into()));
         }
-
-        // Check if user is already a member by email
-        if let Some(existing_user) = state.user_repo.find_by_email(&email).await? {
-            if state
-                .membership_repo
-                .find_by_user_and_org(existing_user.id, org_id)
-                .await?
-                .is_some()
-            {
-                return Err(AppError::Validation(
-                    "User is already a member of this organization".into(),
-                ));
-            }
-        }
-
-        // Check for existing pending invite by email
-        if let Some(existing) = state
-            .invite_repo
-            .find_by_org_and_email(org_id, &email)
-            .await?
-        {
-            if existing.accepted_at.is_none() && existing.expires_at > chrono::Utc::now() {
-                return Err(AppError::Validation(
-                    "An invite already exists for this email".into(),
-                ));
-            }
+        if let Some(reason) = check_email_recipient(&state, org_id, &email).await? {
+            return Err(AppError::Validation(reason.into()));
         }
-
         (Some(email), None)
     } else if let Some(ref wallet_input) = req.wallet_address {
         // Wallet-based invite
@@ -103,34 +78,9 @@ pub async fn create_invite<C: AuthCallback, E: EmailService>(
         if !is_valid_wallet_address(&wallet) {
             return Err(AppError::Validation("Invalid wallet address format".into()));
         }
-
-        // Check if user is already a member by wallet
-        if let Some(existing_user) = state.user_repo.find_by_wallet(&wallet).await? {
-            if state
-                .membership_repo
-                .find_by_user_and_org(existing_user.id, org_id)
-                .await?
-                .is_some()
-            {
-                return Err(AppError::Validation(
-                    "User is already a member of this organization".into(),
-                ));
-            }
-        }
-
-        // Check for existing pending invite by wallet
-        if let Some(existing) = state
-            .invite_repo
-            .find_by_org_and_wallet(org_id, &wallet)
-            .await?
-        {
-            if existing.accepted_at.is_none() && existing.expires_at > chrono::Utc::now() {
-                return Err(AppError::Validation(
-                    "An invite already exists for this wallet address".into(),
-                ));
-            }
+        if let Some(reason) = check_wallet_recipient(&state, org_id, &wallet).await? {
+            return Err(AppError::Validation(reason.into()));
         }
-
         (None, Some(wallet))
     } else {
         // Should be unreachable due to validate() check
@@ -139,6 +89,120 @@ pub async fn create_invite<C: AuthCallback, E: EmailService>(
         ));
     };
 
+    let sender = InviteSender::load(&state, org_id, auth.user_id).await?;
+    let (created, invite_token) =
+        issue_invite(&state, &sender, org_id, email, wallet_address, role).await?;
+
+    Ok(Json(InviteWithTokenResponse {
+        invite: InviteResponse::from_entity(&created),
+        token: invite_token,
+    }))
+}
+
+/// Org and inviter details used in invite emails (loaded once per request)
+pub(super) struct InviteSender {
+    pub inviter_id: Uuid,
+    pub org_name: String,
+    pub inviter_name: Option<String>,
+}
+
+impl InviteSender {
+    pub(super) async fn load<C: AuthCallback, E: EmailService>(
+        state: &Arc<AppState<C, E>>,
+        org_id: Uuid,
+        inviter_id: Uuid,
+    ) -> Result<Self, AppError> {
+        // PERF-01: Parallelize org and inviter lookups for better latency.
+        // These queries are independent and can run concurrently.
+        let (org_result, inviter) = tokio::join!(
+            state.org_repo.find_by_id(org_id),
+            state.user_repo.find_by_id(inviter_id)
+        );
+        let org = org_result?.ok_or(AppError::NotFound("Organization not found".into()))?;
+        Ok(Self {
+            inviter_id,
+            org_name: org.name,
+            inviter_name: inviter?.and_then(|u| u.name),
+        })
+    }
+}
+
+/// Returns a reason if the (normalized) email cannot be invited to the org:
+/// the user is already a member or a pending invite exists.
+pub(super) async fn check_email_recipient<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    org_id: Uuid,
+    email: &str,
+) -> Result<Option<&'static str>, AppError> {
+    // Check if user is already a member by email
+    if let Some(existing_user) = state.user_repo.find_by_email(email).await? {
+        if state
+            .membership_repo
+            .find_by_user_and_org(existing_user.id, org_id)
+            .await?
+            .is_some()
+        {
+            return Ok(Some("User is already a member of this organization"));
+        }
+    }
+
+    // Check for existing pending invite by email
+    if let Some(existing) = state
+        .invite_repo
+        .find_by_org_and_email(org_id, email)
+        .await?
+    {
+        if existing.accepted_at.is_none() && existing.expires_at > chrono::Utc::now() {
+            return Ok(Some("An invite already exists for this email"));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns a reason if the wallet cannot be invited to the org
+pub(super) async fn check_wallet_recipient<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    org_id: Uuid,
+    wallet: &str,
+) -> Result<Option<&'static str>, AppError> {
+    // Check if user is already a member by wallet
+    if let Some(existing_user) = state.user_repo.find_by_wallet(wallet).await? {
+        if state
+            .membership_repo
+            .find_by_user_and_org(existing_user.id, org_id)
+            .await?
+            .is_some()
+        {
+            return Ok(Some("User is already a member of this organization"));
+        }
+    }
+
+    // Check for existing pending invite by wallet
+    if let Some(existing) = state
+        .invite_repo
+        .find_by_org_and_wallet(org_id, wallet)
+        .await?
+    {
+        if existing.accepted_at.is_none() && existing.expires_at > chrono::Utc::now() {
+            return Ok(Some("An invite already exists for this wallet address"));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Create an invite for a validated recipient and queue its email.
+///
+/// Returns the stored invite and the plaintext token (shown once).
+pub(super) async fn issue_invite<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    sender: &InviteSender,
+    org_id: Uuid,
+    email: Option<String>,
+    wallet_address: Option<String>,
+    role: OrgRole,
+) -> Result<(InviteEntity, String), AppError> {
     // Generate invite token
     let invite_token = generate_invite_token();
     let token_hash = hash_invite_token(&invite_token);
@@ -148,10 +212,10 @@ pub async fn create_invite<C: AuthCallback, E: EmailService>(
         id: Uuid::new_v4(),
         org_id,
         email: email.clone(),
-        wallet_address: wallet_address.clone(),
+        wallet_address,
         role,
         token_hash,
-        invited_by: auth.user_id,
+        invited_by: sender.inviter_id,
         created_at: chrono::Utc::now(),
         expires_at: default_invite_expiry(),
         accepted_at: None,
@@ -164,27 +228,18 @@ pub async fn create_invite<C: AuthCallback, E: EmailService>(
 
     // Only send email for email-based invites
     if let Some(ref email_addr) = email {
-        // PERF-01: Parallelize org and inviter lookups for better latency.
-        // These queries are independent and can run concurrently.
-        let (org_result, inviter) = tokio::join!(
-            state.org_repo.find_by_id(org_id),
-            state.user_repo.find_by_id(auth.user_id)
-        );
-        let org = org_result?.ok_or(AppError::NotFound("Organization not found".into()))?;
-        let inviter_name = inviter?.and_then(|u| u.name);
-
         // H-04: Queue email AFTER invite is created. If email queue fails,
         // roll back by deleting the invite to maintain consistency.
         if let Err(err) = state
             .comms_service
             .queue_invite_email(
                 email_addr,
-                &org.name,
-                inviter_name.as_deref(),
+                &sender.org_name,
+                sender.inviter_name.as_deref(),
                 role.as_str(),
                 &invite_token,
                 org_id,
-                auth.user_id,
+                sender.inviter_id,
             )
             .await
         {
@@ -195,8 +250,5 @@ pub async fn create_invite<C: AuthCallback, E: EmailService>(
     }
     // Wallet-based invites: no email to send, token returned in response
 
-    Ok(Json(InviteWithTokenResponse {
-        invite: InviteResponse::from_entity(&created),
-        token: invite_token,
-    }))
+    Ok((created, invite_token))
 }
@@ -0,0 +1,351 @@
+//! Multi-use invite link handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::models::{
+    AcceptInviteRequest, AcceptInviteResponse, CreateInviteLinkRequest, InviteLinkResponse,
+    InviteLinkWithTokenResponse, ListInviteLinksResponse, MessageResponse,
+};
+use crate::repositories::{
+    generate_invite_token, hash_invite_token, AuditEventType, InviteLinkEntity, MembershipEntity,
+    OrgRole, INVITE_EXPIRY_DAYS,
+};
+use crate::services::EmailService;
+use crate::utils::{authenticate, authenticate_for_org};
+use crate::AppState;
+
+/// Longest lifetime an invite link may be given (90 days)
+const MAX_LINK_EXPIRY_HOURS: u32 = 90 * 24;
+
+/// Maximum number of domains in a link's allowlist
+const MAX_ALLOWED_DOMAINS: usize = 20;
+
+/// Helper to verify the caller is an owner/admin of the org, returning their role
+async fn verify_org_admin<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Uuid,
+) -> Result<(Uuid, OrgRole), AppError> {
+    let auth = authenticate_for_org(state, headers, org_id).await?;
+
+    let membership = state
+        .membership_repo
+        .find_by_user_and_org(auth.user_id, org_id)
+        .await?
+        .ok_or(AppError::Forbidden(
+            "Not a member of this organization".into(),
+        ))?;
+
+    if !membership.role.has_at_least(OrgRole::Admin) {
+        return Err(AppError::Forbidden(
+            "Only owners and admins can manage invite links".into(),
+        ));
+    }
+
+    Ok((auth.user_id, membership.role))
+}
+
+/// Normalize and validate a domain allowlist (lowercase, no '@', deduplicated)
+fn normalize_domains(domains: Vec<String>) -> Result<Vec<String>, AppError> {
+    if domains.len() > MAX_ALLOWED_DOMAINS {
+        return Err(AppError::Validation(format!(
+            "At most {} allowed domains",
+            MAX_ALLOWED_DOMAINS
+        )));
+    }
+    let mut normalized: Vec<String> = Vec::with_capacity(domains.len());
+    for domain in domains {
+        let domain = domain.trim().trim_start_matches('@').to_lowercase();
+        let valid = domain.contains('.')
+            && domain.len() <= 253
+            && domain.split('.').all(|label| {
+                !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            });
+        if !valid {
+            return Err(AppError::Validation(format!("Invalid domain: {}", domain)));
+        }
+        if !normalized.contains(&domain) {
+            normalized.push(domain);
+        }
+    }
+    Ok(normalized)
+}
+
+/// POST /orgs/:org_id/invite-links - Create a shareable invite link
+pub async fn create_invite_link<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<CreateInviteLinkRequest>,
+) -> Result<Json<InviteLinkWithTokenResponse>, AppError> {
+    let (user_id, _) = verify_org_admin(&state, &headers, org_id).await?;
+
+    let role =
+        OrgRole::from_str(&req.role).ok_or_else(|| AppError::Validation("Invalid role".into()))?;
+    // Links are bearer tokens that can be forwarded; never let them mint owners
+    if role == OrgRole::Owner {
+        return Err(AppError::Validation(
+            "Invite links cannot grant the owner role".into(),
+        ));
+    }
+
+    if req.max_uses == Some(0) {
+        return Err(AppError::Validation("maxUses must be at least 1".into()));
+    }
+
+    let expiry_hours = req.expires_in_hours.unwrap_or(INVITE_EXPIRY_DAYS * 24);
+    if expiry_hours == 0 || expiry_hours > MAX_LINK_EXPIRY_HOURS {
+        return Err(AppError::Validation(format!(
+            "expiresInHours must be between 1 and {}",
+            MAX_LINK_EXPIRY_HOURS
+        )));
+    }
+
+    let allowed_domains = normalize_domains(req.allowed_domains)?;
+
+    let token = generate_invite_token();
+    let now = Utc::now();
+    let link = InviteLinkEntity {
+        id: Uuid::new_v4(),
+        org_id,
+        role,
+        token_hash: hash_invite_token(&token),
+        max_uses: req.max_uses,
+        use_count: 0,
+        allowed_domains,
+        created_by: user_id,
+        created_at: now,
+        expires_at: now + Duration::hours(expiry_hours as i64),
+        revoked_at: None,
+    };
+
+    let created = state.storage.invite_link_repo.create(link).await?;
+
+    let _ = state
+        .audit_service
+        .log_invite_event(
+            AuditEventType::InviteLinkCreated,
+            user_id,
+            org_id,
+            created.id,
+            Some(serde_json::json!({
+                "role": created.role.as_str(),
+                "maxUses": created.max_uses,
+                "allowedDomains": created.allowed_domains,
+            })),
+            Some(&headers),
+        )
+        .await;
+
+    Ok(Json(InviteLinkWithTokenResponse {
+        link: InviteLinkResponse::from_entity(&created),
+        token,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListInviteLinksQueryParams {
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub offset: u32,
+}
+
+fn default_limit() -> u32 {
+    50
+}
+
+/// GET /orgs/:org_id/invite-links - List invite links (including revoked/expired)
+pub async fn list_invite_links<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Query(params): Query<ListInviteLinksQueryParams>,
+) -> Result<Json<ListInviteLinksResponse>, AppError> {
+    verify_org_admin(&state, &headers, org_id).await?;
+
+    let repo = &state.storage.invite_link_repo;
+    let (links, total) = tokio::join!(
+        repo.find_by_org_paged(org_id, params.limit, params.offset),
+        repo.count_by_org(org_id)
+    );
+
+    Ok(Json(ListInviteLinksResponse {
+        links: links?.iter().map(InviteLinkResponse::from_entity).collect(),
+        total: total?,
+    }))
+}
+
+/// DELETE /orgs/:org_id/invite-links/:link_id - Revoke an invite link
+pub async fn revoke_invite_link<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, link_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<MessageResponse>, AppError> {
+    let (user_id, _) = verify_org_admin(&state, &headers, org_id).await?;
+
+    let link = state
+        .storage
+        .invite_link_repo
+        .find_by_id(link_id)
+        .await?
+        .filter(|l| l.org_id == org_id)
+        .ok_or(AppError::NotFound("Invite link not found".into()))?;
+
+    if state.storage.invite_link_repo.revoke(link.id).await? {
+        let _ = state
+            .audit_service
+            .log_invite_event(
+                AuditEventType::InviteLinkRevoked,
+                user_id,
+                org_id,
+                link.id,
+                None,
+                Some(&headers),
+            )
+            .await;
+    }
+
+    Ok(Json(MessageResponse {
+        message: "Invite link revoked".into(),
+    }))
+}
+
+/// POST /invite-links/accept - Join an organization via an invite link
+///
+/// Requires authentication. If the link has a domain allowlist, the user's
+/// email must be verified and belong to one of those domains.
+pub async fn accept_invite_link<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(req): Json<AcceptInviteRequest>,
+) -> Result<Json<AcceptInviteResponse>, AppError> {
+    let auth = authenticate(&state, &headers).await?;
+
+    let link = state
+        .storage
+        .invite_link_repo
+        .find_by_token_hash(&hash_invite_token(&req.token))
+        .await?
+        .filter(InviteLinkEntity::is_usable)
+        .ok_or(AppError::NotFound("Invalid or expired invite link".into()))?;
+    auth.ensure_org(link.org_id)?;
+
+    if !link.allowed_domains.is_empty() {
+        let user = state
+            .user_repo
+            .find_by_id(auth.user_id)
+            .await?
+            .ok_or(AppError::InvalidToken)?;
+        let allowed = user.email_verified
+            && user
+                .email
+                .as_deref()
+                .map(|email| link.allows_email(email))
+                .unwrap_or(false);
+        if !allowed {
+            return Err(AppError::Forbidden(
+                "This invite link is restricted to verified emails at specific domains".into(),
+            ));
+        }
+    }
+
+    if state
+        .membership_repo
+        .find_by_user_and_org(auth.user_id, link.org_id)
+        .await?
+        .is_some()
+    {
+        return Err(AppError::Validation(
+            "You are already a member of this organization".into(),
+        ));
+    }
+
+    // Reserve a use atomically, then create the membership; give the use back
+    // if the membership insert fails (e.g. a concurrent join by the same user).
+    state
+        .storage
+        .invite_link_repo
+        .consume_use(link.id)
+        .await?
+        .ok_or(AppError::NotFound("Invalid or expired invite link".into()))?;
+
+    let membership = match state
+        .membership_repo
+        .create(MembershipEntity::new(auth.user_id, link.org_id, link.role))
+        .await
+    {
+        Ok(m) => m,
+        Err(e) => {
+            let _ = state.storage.invite_link_repo.release_use(link.id).await;
+            return Err(e);
+        }
+    };
+
+    let _ = state
+        .audit_service
+        .log_member_event(
+            AuditEventType::MemberJoined,
+            auth.user_id,
+            link.org_id,
+            auth.user_id,
+            Some(serde_json::json!({ "inviteLinkId": link.id })),
+            Some(&headers),
+        )
+        .await;
+
+    let org = state
+        .org_repo
+        .find_by_id(link.org_id)
+        .await?
+        .ok_or(AppError::NotFound("Organization not found".into()))?;
+
+    Ok(Json(AcceptInviteResponse {
+        org_id: org.id,
+        org_name: org.name,
+        role: membership.role.as_str().to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_domains() {
+        let domains = normalize_domains(vec![
+            " @Acme.com".into(),
+            "acme.com".into(),
+            "eu.acme.io".into(),
+        ])
+        .unwrap();
+        assert_eq!(
+            domains,
+            vec!["acme.com".to_string(), "eu.acme.io".to_string()]
+        );
+
+        assert!(normalize_domains(vec!["localhost".into()]).is_err());
+        assert!(normalize_domains(vec!["acme..com".into()]).is_err());
+        assert!(normalize_domains(vec!["a@b.com".into()]).is_err());
+        assert!(normalize_domains(vec!["x.com".into(); MAX_ALLOWED_DOMAINS + 1]).is_err());
+    }
+
+    #[test]
+    fn test_create_invite_link_request_defaults() {
+        let req: CreateInviteLinkRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(req.role, "member");
+        assert!(req.max_uses.is_none());
+        assert!(req.expires_in_hours.is_none());
+        assert!(req.allowed_domains.is_empty());
+    }
+}
@@ -13,7 +13,7 @@ use crate::callback::AuthCallback;
 use crate::errors::AppError;
 use crate::models::{InviteResponse, ListInvitesResponse};
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
 const MAX_LIMIT: u32 = 200;
@@ -26,7 +26,7 @@ pub async fn list_invites<C: AuthCallback, E: EmailService>(
     Query(params): Query<ListInvitesQueryParams>,
 ) -> Result<Json<ListInvitesResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Check caller's membership - any member can view invites
     let _caller_membership = state
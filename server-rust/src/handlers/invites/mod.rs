@@ -1,13 +1,17 @@
 //! Invite handlers
 
 mod accept;
+mod bulk;
 mod cancel;
 mod create;
+mod links;
 mod list;
 mod resend;
 
 pub use accept::accept_invite;
+pub use bulk::bulk_create_invites;
 pub use cancel::cancel_invite;
 pub use create::create_invite;
+pub use links::{accept_invite_link, create_invite_link, list_invite_links, revoke_invite_link};
 pub use list::list_invites;
 pub use resend::resend_invite;
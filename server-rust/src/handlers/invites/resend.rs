@@ -15,7 +15,7 @@ use crate::repositories::{
     default_invite_expiry, generate_invite_token, hash_invite_token, InviteEntity, OrgRole,
 };
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
 /// POST /orgs/:org_id/invites/:invite_id/resend - Resend an invite
@@ -25,7 +25,7 @@ pub async fn resend_invite<C: AuthCallback, E: EmailService>(
     Path((org_id, invite_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<InviteWithTokenResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Check caller's membership - must be admin+ to resend invites
     let caller_membership = state
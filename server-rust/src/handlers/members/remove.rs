@@ -13,7 +13,7 @@ use crate::errors::AppError;
 use crate::models::MessageResponse;
 use crate::repositories::OrgRole;
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
 /// DELETE /orgs/:org_id/members/:user_id - Remove a member from organization
@@ -23,7 +23,7 @@ pub async fn remove_member<C: AuthCallback, E: EmailService>(
     Path((org_id, user_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<MessageResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Check caller's membership
     let caller_membership = state
@@ -13,7 +13,7 @@ use crate::errors::AppError;
 use crate::models::{MemberResponse, UpdateMemberRoleRequest};
 use crate::repositories::OrgRole;
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
 /// PATCH /orgs/:org_id/members/:user_id - Update member role
@@ -24,7 +24,7 @@ pub async fn update_member_role<C: AuthCallback, E: EmailService>(
     Json(req): Json<UpdateMemberRoleRequest>,
 ) -> Result<Json<MemberResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // TOCTOU-FIX: Prevent self-role-change to avoid privilege escalation edge cases
     // (check early, before any DB lookups)
@@ -79,6 +79,18 @@ async fn get_authenticated_session<C: AuthCallback, E: EmailService>(
     Ok((auth.user_id, session_id))
 }
 
+/// Like [`get_authenticated_session`], but refuses impersonation sessions,
+/// which must never change the user's second factor
+async fn get_mfa_settings_session<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+) -> Result<(uuid::Uuid, uuid::Uuid), AppError> {
+    let auth = authenticate(state, headers).await?;
+    auth.ensure_not_impersonated("change MFA settings")?;
+    let session_id = auth.session_id.ok_or(AppError::StepUpRequired)?;
+    Ok((auth.user_id, session_id))
+}
+
 /// POST /auth/mfa/setup - Start MFA setup
 /// Returns the secret and QR code URI for the user to set up their authenticator app
 ///
@@ -98,7 +110,7 @@ pub async fn setup_mfa<C: AuthCallback, E: EmailService>(
     State(state): State<Arc<AppState<C, E>>>,
     headers: HeaderMap,
 ) -> Result<Json<MfaSetupResponse>, AppError> {
-    let (user_id, session_id) = get_authenticated_session(&state, &headers).await?;
+    let (user_id, session_id) = get_mfa_settings_session(&state, &headers).await?;
 
     // Require recent strong authentication to prevent MFA enrollment via stolen sessions.
     state.step_up_service.require_step_up(session_id).await?;
@@ -161,7 +173,7 @@ pub async fn enable_mfa<C: AuthCallback, E: EmailService>(
     headers: HeaderMap,
     Json(req): Json<EnableMfaRequest>,
 ) -> Result<Json<MessageResponse>, AppError> {
-    let (user_id, session_id) = get_authenticated_session(&state, &headers).await?;
+    let (user_id, session_id) = get_mfa_settings_session(&state, &headers).await?;
 
     // Require recent strong authentication for MFA enablement.
     state.step_up_service.require_step_up(session_id).await?;
@@ -231,7 +243,7 @@ pub async fn disable_mfa<C: AuthCallback, E: EmailService>(
     headers: HeaderMap,
     Json(req): Json<DisableMfaRequest>,
 ) -> Result<Json<MessageResponse>, AppError> {
-    let (user_id, _) = get_authenticated_session(&state, &headers).await?;
+    let (user_id, _) = get_mfa_settings_session(&state, &headers).await?;
 
     // Get user and verify password
     let db_user = state
@@ -469,7 +481,7 @@ pub async fn regenerate_recovery_codes<C: AuthCallback, E: EmailService>(
     headers: HeaderMap,
     Json(req): Json<RegenerateRecoveryCodesRequest>,
 ) -> Result<Json<RegenerateRecoveryCodesResponse>, AppError> {
-    let (user_id, session_id) = get_authenticated_session(&state, &headers).await?;
+    let (user_id, session_id) = get_mfa_settings_session(&state, &headers).await?;
 
     // Check MFA is enabled
     let totp_secret = state
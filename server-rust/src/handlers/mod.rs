@@ -1,5 +1,6 @@
 //! HTTP request handlers
 
+mod account;
 pub mod admin;
 pub mod ai_discovery;
 mod api_keys;
@@ -8,47 +9,74 @@ mod auth;
 mod authorize;
 mod credentials;
 mod credit_operations;
+mod credit_statements;
+mod credit_transfers;
 mod credits;
 mod custom_roles;
 mod deposit;
 mod deposit_tiered;
 mod discovery;
+mod email_change;
 mod email_verification;
 mod features;
 mod google;
 mod health;
+mod impersonation;
 mod instant_link;
 pub mod invites;
 pub mod members;
 mod metrics;
 mod mfa;
+mod org_credits;
 pub mod orgs;
 mod password_change;
 mod password_reset;
 mod policies;
 mod prices;
+mod relationships;
+mod service_accounts;
 mod sessions;
 pub mod setup;
 mod solana;
 mod sso;
+mod stripe;
+mod subscriptions;
 mod user_lookup;
 mod user_withdrawal;
 mod wallet;
 mod webauthn;
 mod webhook;
 
+pub use account::{delete_account, export_account_data};
 pub use admin::{
-    adjust_credits, authorize_treasury, create_sso_provider, delete_sso_provider, delete_user,
-    force_password_reset, get_credit_stats, get_dashboard_permissions, get_deposit_stats,
-    get_disposable_domains, get_org as get_admin_org, get_org_audit_logs, get_privacy_status,
-    get_sso_provider, get_system_audit_logs, get_treasury, get_user as get_admin_user,
-    get_user_credits, get_user_deposits, get_user_stats, get_user_withdrawal_history,
-    list_admin_deposits, list_credit_refund_requests, list_in_privacy_period,
-    list_orgs as list_admin_orgs, list_pending_withdrawals, list_settings, list_sso_providers,
-    list_users, process_all_withdrawals, process_credit_refund_request, process_withdrawal,
-    reject_credit_refund_request, revoke_treasury, set_system_admin, update_dashboard_permissions,
-    update_disposable_domains, update_settings, update_sso_provider, update_user,
+    adjust_credits, adjust_org_credits, authorize_treasury, create_credit_grant,
+    create_sso_provider, delete_sso_provider, delete_user, force_password_reset,
+    get_credit_reconciliation, get_credit_stats, get_dashboard_permissions, get_deposit_stats,
+    get_disposable_domains, get_ledger_statement, get_org as get_admin_org, get_org_audit_logs,
+    get_privacy_status, get_sso_provider, get_system_audit_logs, get_treasury, get_trial_balance,
+    get_user as get_admin_user, get_user_credits, get_user_deposits, get_user_stats,
+    get_user_withdrawal_history, list_admin_deposits, list_credit_grants,
+    list_credit_refund_requests, list_in_privacy_period, list_orgs as list_admin_orgs,
+    list_pending_withdrawals, list_settings, list_sso_providers, list_users,
+    process_all_withdrawals, process_credit_refund_request, process_withdrawal,
+    reject_credit_refund_request, revoke_credit_grant, revoke_treasury, set_system_admin,
+    set_user_status, update_dashboard_permissions, update_disposable_domains, update_settings,
+    update_sso_provider, update_user, verify_audit_chain,
 };
+pub use admin::{
+    create_meter, delete_meter_price_override, get_meter, list_meter_price_overrides, list_meters,
+    set_meter_price_override, update_meter,
+};
+pub use admin::{
+    create_subscription_plan, get_subscription_plan, list_subscription_plans_admin,
+    update_subscription_plan,
+};
+pub use admin::{
+    create_webhook_endpoint, delete_webhook_endpoint, get_webhook_endpoint,
+    list_webhook_deliveries, list_webhook_endpoints, list_webhook_events, replay_webhook_delivery,
+    update_webhook_endpoint,
+};
+pub use admin::{delete_credit_budget, list_credit_budgets, set_credit_budget};
 pub use ai_discovery::{
     agent_json, agent_md, ai_discovery_index, ai_plugin_json, ai_txt, heartbeat_json, heartbeat_md,
     llms_admin_txt, llms_full_txt, llms_txt, mcp_discovery, skill_admin_md, skill_auth_md,
@@ -65,7 +93,11 @@ pub use auth::{
 };
 pub use authorize::{authorize, get_permissions};
 pub use credentials::{list_credentials, unlink_credential, update_credential};
-pub use credit_operations::{capture_hold, create_hold, release_hold, spend_credits};
+pub use credit_operations::{
+    capture_hold, create_hold, extend_hold, record_usage, release_hold, spend_credits,
+};
+pub use credit_statements::{email_credit_statement, get_credit_statement};
+pub use credit_transfers::create_credit_transfer;
 pub use credits::{
     get_balance, get_history, get_pending_holds, get_sol_balance, get_usage, request_refund,
 };
@@ -79,28 +111,56 @@ pub use deposit::{
 };
 pub use deposit_tiered::{deposit_quote, execute_micro_deposit, execute_public_deposit};
 pub use discovery::{auth_config, jwks, openapi_spec};
+pub use email_change::{confirm_email_change, request_email_change, revert_email_change};
 pub use email_verification::{send_verification, verify_email};
 pub use features::auth_features;
 pub use google::google_auth;
 pub use health::health_check;
+pub use impersonation::{end_impersonation, impersonate_member, impersonate_user};
 pub use instant_link::{send_instant_link, verify_instant_link};
-pub use invites::{accept_invite, cancel_invite, create_invite, list_invites, resend_invite};
+pub use invites::{
+    accept_invite, accept_invite_link, bulk_create_invites, cancel_invite, create_invite,
+    create_invite_link, list_invite_links, list_invites, resend_invite, revoke_invite_link,
+};
 pub use members::{list_members, remove_member, update_member_role};
 pub use metrics::prometheus_metrics;
 pub use mfa::{
     disable_mfa, enable_mfa, mfa_status, regenerate_recovery_codes, setup_mfa, use_recovery_code,
     verify_mfa,
 };
-pub use orgs::{create_org, delete_org, get_org, list_orgs, switch_org, update_org};
+pub(crate) use org_credits::require_org_credit_permission;
+pub use org_credits::{
+    delete_org_credit_member, list_org_credit_members, update_org_credit_member,
+};
+pub use orgs::{create_org, delete_org, get_org, list_orgs, restore_org, switch_org, update_org};
 pub use password_change::change_password;
 pub use password_reset::{forgot_password, reset_password};
 pub use policies::{create_policy, delete_policy, get_policy, list_policies, update_policy};
 pub use prices::token_prices;
+pub use relationships::{
+    check_relationship, delete_relationships, expand_relationship, get_relationship_schema,
+    list_related_objects, list_relationships, put_relationship_schema, write_relationships,
+};
+pub(crate) use service_accounts::service_account_custom_role;
+pub use service_accounts::{
+    create_service_account, create_service_account_api_key, delete_service_account,
+    delete_service_account_api_key, get_service_account, list_service_account_api_keys,
+    list_service_accounts, rotate_service_account_secret, service_account_token,
+    update_service_account,
+};
 pub use sessions::{list_sessions, revoke_all_sessions};
 pub use setup::{create_first_admin, setup_status};
 pub use solana::{solana_auth, solana_challenge};
 pub use sso::{sso_callback, start_sso};
-pub use user_lookup::{link_stripe_customer, lookup_by_stripe_customer, lookup_by_wallet};
+pub use stripe::{create_stripe_topup, handle_stripe_webhook, list_stripe_topups};
+pub(crate) use subscriptions::subscription_service;
+pub use subscriptions::{
+    cancel_subscription, create_subscription, list_subscription_plans, list_subscriptions,
+};
+pub use user_lookup::{
+    get_user_metadata, link_stripe_customer, lookup_by_stripe_customer, lookup_by_wallet,
+    update_user_metadata,
+};
 pub use user_withdrawal::{withdraw_balances, withdraw_history, withdraw_sol, withdraw_spl};
 pub use wallet::{
     acknowledge_recovery, create_derived_wallet, delete_derived_wallet, get_pending_recovery,
@@ -0,0 +1,172 @@
+//! Organization credit balance settings
+//!
+//! GET    /orgs/{org_id}/credits/members            - List member spending rules
+//! PUT    /orgs/{org_id}/credits/members/{user_id}  - Set a member's spending rules
+//! DELETE /orgs/{org_id}/credits/members/{user_id}  - Reset a member to role defaults
+//!
+//! The org balance itself is read through `/credits/balance`, `/credits/history`
+//! and `/credits/holds` with `?orgId=`, and spent through `/credits/spend` and
+//! `/credits/hold` with `orgId` in the body.
+
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::{AppError, ERR_NOT_A_MEMBER};
+use crate::models::{
+    MessageResponse, OrgCreditMemberResponse, OrgCreditMembersResponse,
+    UpdateOrgCreditMemberRequest,
+};
+use crate::repositories::{AuditEventType, OrgCreditMemberSettings, OrgRole};
+use crate::services::{EmailService, Permission};
+use crate::utils::authenticate_for_org;
+use crate::AppState;
+
+/// Look up `user_id`'s role in `org_id` and require `permission`
+pub(crate) async fn require_org_credit_permission<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    user_id: Uuid,
+    org_id: Uuid,
+    permission: Permission,
+) -> Result<OrgRole, AppError> {
+    let membership = state
+        .membership_repo
+        .find_by_user_and_org(user_id, org_id)
+        .await?
+        .ok_or(AppError::Forbidden(ERR_NOT_A_MEMBER.into()))?;
+
+    if !permission.is_allowed_for(membership.role) {
+        return Err(AppError::Forbidden(format!(
+            "Role '{}' does not have '{}' permission",
+            membership.role.as_str(),
+            permission.as_str()
+        )));
+    }
+
+    Ok(membership.role)
+}
+
+/// Authenticate the caller and require `credits:manage` in the org
+async fn verify_credit_manager<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Uuid,
+) -> Result<Uuid, AppError> {
+    if !state.config.privacy.enabled {
+        return Err(AppError::NotFound("Credits not enabled".into()));
+    }
+
+    let auth = authenticate_for_org(state, headers, org_id).await?;
+    require_org_credit_permission(state, auth.user_id, org_id, Permission::CreditsManage).await?;
+    Ok(auth.user_id)
+}
+
+/// GET /orgs/{org_id}/credits/members - List member spending rules
+///
+/// Members without an entry use their role default: owners and admins may
+/// spend, members may not, and nobody is capped.
+pub async fn list_org_credit_members<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<OrgCreditMembersResponse>, AppError> {
+    verify_credit_manager(&state, &headers, org_id).await?;
+
+    let settings = state.credit_repo.list_org_member_settings(org_id).await?;
+
+    Ok(Json(OrgCreditMembersResponse {
+        members: settings.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// PUT /orgs/{org_id}/credits/members/{user_id} - Set a member's spending rules
+pub async fn update_org_credit_member<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateOrgCreditMemberRequest>,
+) -> Result<Json<OrgCreditMemberResponse>, AppError> {
+    let actor_id = verify_credit_manager(&state, &headers, org_id).await?;
+
+    if request.monthly_cap_lamports.is_some_and(|cap| cap < 0) {
+        return Err(AppError::Validation(
+            "monthlyCapLamports cannot be negative".into(),
+        ));
+    }
+
+    state
+        .membership_repo
+        .find_by_user_and_org(user_id, org_id)
+        .await?
+        .ok_or(AppError::NotFound("Member not found".into()))?;
+
+    let settings = state
+        .credit_repo
+        .upsert_org_member_settings(OrgCreditMemberSettings {
+            org_id,
+            user_id,
+            spend_allowed: request.spend_allowed,
+            monthly_cap: request.monthly_cap_lamports,
+            updated_by: Some(actor_id),
+            updated_at: Utc::now(),
+        })
+        .await?;
+
+    let _ = state
+        .audit_service
+        .log_member_event(
+            AuditEventType::OrgCreditMemberUpdated,
+            actor_id,
+            org_id,
+            user_id,
+            Some(serde_json::json!({
+                "spendAllowed": settings.spend_allowed,
+                "monthlyCapLamports": settings.monthly_cap,
+            })),
+            Some(&headers),
+        )
+        .await;
+
+    Ok(Json(settings.into()))
+}
+
+/// DELETE /orgs/{org_id}/credits/members/{user_id} - Reset a member to role defaults
+pub async fn delete_org_credit_member<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<MessageResponse>, AppError> {
+    let actor_id = verify_credit_manager(&state, &headers, org_id).await?;
+
+    if !state
+        .credit_repo
+        .delete_org_member_settings(org_id, user_id)
+        .await?
+    {
+        return Err(AppError::NotFound(
+            "Member credit settings not found".into(),
+        ));
+    }
+
+    let _ = state
+        .audit_service
+        .log_member_event(
+            AuditEventType::OrgCreditMemberUpdated,
+            actor_id,
+            org_id,
+            user_id,
+            Some(serde_json::json!({ "reset": true })),
+            Some(&headers),
+        )
+        .await;
+
+    Ok(Json(MessageResponse {
+        message: "Member credit settings reset".to_string(),
+    }))
+}
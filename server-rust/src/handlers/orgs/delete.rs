@@ -5,25 +5,39 @@ use axum::{
     http::HeaderMap,
     Json,
 };
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::callback::AuthCallback;
 use crate::errors::AppError;
-use crate::models::MessageResponse;
 use crate::repositories::{AuditEventType, OrgRole};
-use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::services::{EmailService, DEFAULT_ORG_DELETION_GRACE_DAYS};
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
+/// Response for a scheduled organization deletion
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteOrgResponse {
+    pub message: String,
+    /// When the organization will be permanently purged
+    pub purge_after: DateTime<Utc>,
+}
+
 /// DELETE /orgs/:org_id - Delete organization
+///
+/// The organization enters a grace period (`org_deletion_grace_days`, default
+/// 30) during which members lose access and the owner or a system admin can
+/// restore it. The org purge worker removes it afterwards.
 pub async fn delete_org<C: AuthCallback, E: EmailService>(
     State(state): State<Arc<AppState<C, E>>>,
     headers: HeaderMap,
     Path(org_id): Path<Uuid>,
-) -> Result<Json<MessageResponse>, AppError> {
+) -> Result<Json<DeleteOrgResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Check membership - only owner can delete
     let membership = state
@@ -61,7 +75,23 @@ pub async fn delete_org<C: AuthCallback, E: EmailService>(
         ));
     }
 
-    // H-11: Log audit event before deletion (so org_id is still valid for logging)
+    let grace_days = state
+        .settings_service
+        .get_u32("org_deletion_grace_days")
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(DEFAULT_ORG_DELETION_GRACE_DAYS);
+    let purge_after = Utc::now() + Duration::days(grace_days as i64);
+
+    // Soft delete: members lose access immediately, data is kept until the
+    // purge worker removes the org after the grace period.
+    state
+        .org_repo
+        .schedule_deletion(org_id, purge_after)
+        .await?
+        .ok_or(AppError::NotFound("Organization not found".into()))?;
+
     let _ = state
         .audit_service
         .log_org_event(
@@ -72,20 +102,11 @@ pub async fn delete_org<C: AuthCallback, E: EmailService>(
         )
         .await;
 
-    if state.config.database.url.is_none() {
-        // In-memory storage doesn't enforce FK cascades.
-        state.invite_repo.delete_by_org(org_id).await?;
-        state.membership_repo.delete_by_org(org_id).await?;
-        state.custom_role_repo.delete_by_org(org_id).await?;
-        state.policy_repo.delete_by_org(org_id).await?;
-        state.audit_repo.delete_by_org(org_id).await?;
-        state.outbox_repo.delete_by_org(org_id).await?;
-    }
-
-    // Delete the organization
-    state.org_repo.delete(org_id).await?;
-
-    Ok(Json(MessageResponse {
-        message: "Organization deleted successfully".to_string(),
+    Ok(Json(DeleteOrgResponse {
+        message: format!(
+            "Organization scheduled for deletion; it can be restored until {}",
+            purge_after.to_rfc3339()
+        ),
+        purge_after,
     }))
 }
@@ -12,7 +12,7 @@ use crate::callback::AuthCallback;
 use crate::errors::AppError;
 use crate::models::OrgResponse;
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
 /// GET /orgs/:org_id - Get organization details
@@ -22,7 +22,7 @@ pub async fn get_org<C: AuthCallback, E: EmailService>(
     Path(org_id): Path<Uuid>,
 ) -> Result<Json<OrgResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Check membership - user must be a member of the org to view it
     let membership = state
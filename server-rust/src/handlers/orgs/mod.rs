@@ -4,6 +4,7 @@ mod create;
 mod delete;
 mod get;
 mod list;
+mod restore;
 mod switch;
 mod update;
 
@@ -11,5 +12,6 @@ pub use create::create_org;
 pub use delete::delete_org;
 pub use get::get_org;
 pub use list::list_orgs;
+pub use restore::restore_org;
 pub use switch::switch_org;
 pub use update::update_org;
@@ -0,0 +1,83 @@
+//! Restore organization handler
+
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::models::MessageResponse;
+use crate::repositories::{AuditEventType, OrgRole};
+use crate::services::EmailService;
+use crate::utils::authenticate_for_org;
+use crate::AppState;
+
+/// POST /orgs/:org_id/restore - Restore an organization pending deletion
+///
+/// Allowed for members with the owner role and system admins. Memberships of
+/// an org pending deletion are hidden from the regular lookups, so the role is
+/// checked with `find_by_user_and_org_including_pending`.
+pub async fn restore_org<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<MessageResponse>, AppError> {
+    // Authenticate via JWT or API key
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
+
+    let org = state
+        .org_repo
+        .find_by_id(org_id)
+        .await?
+        .ok_or(AppError::NotFound("Organization not found".into()))?;
+
+    let is_owner = state
+        .membership_repo
+        .find_by_user_and_org_including_pending(auth.user_id, org_id)
+        .await?
+        .is_some_and(|m| m.role == OrgRole::Owner);
+    if !is_owner {
+        let is_system_admin = state
+            .user_repo
+            .find_by_id(auth.user_id)
+            .await?
+            .map(|u| u.is_system_admin)
+            .unwrap_or(false);
+        if !is_system_admin {
+            // Don't reveal pending-deletion orgs to non-owners
+            return Err(AppError::NotFound("Organization not found".into()));
+        }
+    }
+
+    if !org.is_pending_deletion() {
+        return Err(AppError::Validation(
+            "Organization is not pending deletion".into(),
+        ));
+    }
+
+    state
+        .org_repo
+        .restore(org_id)
+        .await?
+        .ok_or(AppError::Validation(
+            "Organization is not pending deletion".into(),
+        ))?;
+
+    let _ = state
+        .audit_service
+        .log_org_event(
+            AuditEventType::OrgRestored,
+            auth.user_id,
+            org_id,
+            Some(&headers),
+        )
+        .await;
+
+    Ok(Json(MessageResponse {
+        message: "Organization restored".to_string(),
+    }))
+}
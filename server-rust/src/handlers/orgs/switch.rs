@@ -10,14 +10,15 @@ use serde::Serialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::callback::AuthCallback;
+use crate::callback::{AuthCallback, ClaimsTrigger, TokenClaimsPayload};
 use crate::errors::AppError;
+use crate::handlers::auth::call_claims_callback_with_timeout;
 use crate::models::TokenPair;
 use crate::repositories::SessionEntity;
 use crate::services::{EmailService, TokenContext};
 use crate::utils::{
     build_json_response_with_cookies, extract_access_token, extract_client_ip_with_fallback,
-    hash_refresh_token, PeerIp,
+    hash_refresh_token, metadata_claims, PeerIp,
 };
 use crate::AppState;
 
@@ -56,6 +57,19 @@ pub async fn switch_org<C: AuthCallback, E: EmailService>(
         .ok_or(AppError::InvalidToken)?;
     let claims = state.jwt_service.validate_access_token(&token)?;
 
+    // Impersonation sessions stay in the context they were started with
+    if claims.act.is_some() {
+        return Err(AppError::Forbidden(
+            "Cannot switch organization during impersonation".into(),
+        ));
+    }
+    // Service account tokens are pinned to the account's own org
+    if claims.service_account == Some(true) {
+        return Err(AppError::Forbidden(
+            "Service accounts cannot switch organization".into(),
+        ));
+    }
+
     // Verify current session
     let session = state
         .session_repo
@@ -85,7 +99,7 @@ pub async fn switch_org<C: AuthCallback, E: EmailService>(
     // Create new session with updated org context BEFORE revoking old session
     // This prevents the user from being logged out if session creation fails
     let new_session_id = Uuid::new_v4();
-    let token_context = TokenContext {
+    let mut token_context = TokenContext {
         org_id: Some(org_id),
         role: Some(membership.role.as_str().to_string()),
         is_system_admin: if user.is_system_admin {
@@ -94,7 +108,13 @@ pub async fn switch_org<C: AuthCallback, E: EmailService>(
             None
         },
         email_verified: Some(user.email_verified),
+        service_account: None,
+        act: None,
+        metadata: metadata_claims(&state, &user).await,
+        ext: None,
     };
+    let claims_payload = TokenClaimsPayload::new(user.id, &token_context, ClaimsTrigger::OrgSwitch);
+    token_context.ext = call_claims_callback_with_timeout(&state.callback, &claims_payload).await;
     let token_pair = state.jwt_service.generate_token_pair_with_context(
         claims.sub,
         new_session_id,
@@ -13,7 +13,7 @@ use crate::errors::AppError;
 use crate::models::{OrgResponse, UpdateOrgRequest};
 use crate::repositories::OrgRole;
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
 /// PATCH /orgs/:org_id - Update organization
@@ -24,7 +24,7 @@ pub async fn update_org<C: AuthCallback, E: EmailService>(
     Json(req): Json<UpdateOrgRequest>,
 ) -> Result<Json<OrgResponse>, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(&state, &headers).await?;
+    let auth = authenticate_for_org(&state, &headers, org_id).await?;
 
     // Check membership - must be owner or admin to update
     let membership = state
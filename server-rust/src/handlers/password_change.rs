@@ -38,6 +38,7 @@ pub async fn change_password<C: AuthCallback, E: EmailService>(
     Json(req): Json<ChangePasswordRequest>,
 ) -> Result<Json<MessageResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("change the password")?;
     let user_id = auth.user_id;
 
     // Get user
@@ -15,7 +15,7 @@ use crate::models::MessageResponse;
 use crate::repositories::pagination::{cap_limit, cap_offset};
 use crate::repositories::{AbacPolicy, OrgRole, PolicyConditions, PolicyEffect};
 use crate::services::EmailService;
-use crate::utils::authenticate;
+use crate::utils::authenticate_for_org;
 use crate::AppState;
 
 /// Request to create an ABAC policy
@@ -110,7 +110,7 @@ async fn verify_org_admin<C: AuthCallback, E: EmailService>(
     org_id: Uuid,
 ) -> Result<Uuid, AppError> {
     // Authenticate via JWT or API key
-    let auth = authenticate(state, headers).await?;
+    let auth = authenticate_for_org(state, headers, org_id).await?;
 
     // M-02: Check membership first (common case) to avoid user fetch
     if let Some(membership) = state
@@ -165,6 +165,7 @@ pub async fn create_policy<C: AuthCallback, E: EmailService>(
     Json(req): Json<CreatePolicyRequest>,
 ) -> Result<Json<PolicyResponse>, AppError> {
     verify_org_admin(&state, &headers, org_id).await?;
+    req.conditions.validate().map_err(AppError::Validation)?;
 
     let policy = AbacPolicy::new(org_id, &req.name, &req.permission, req.effect)
         .with_conditions(req.conditions)
@@ -229,6 +230,7 @@ pub async fn update_policy<C: AuthCallback, E: EmailService>(
         policy.permission = permission;
     }
     if let Some(conditions) = req.conditions {
+        conditions.validate().map_err(AppError::Validation)?;
         policy.conditions = conditions;
     }
     if let Some(effect) = req.effect {
@@ -295,6 +297,27 @@ mod tests {
         assert_eq!(req.priority, 10);
     }
 
+    #[test]
+    fn test_create_policy_request_rich_conditions() {
+        let json = r#"{
+            "name": "Office hours from VPN",
+            "permission": "billing:read",
+            "conditions": {
+                "environment": {
+                    "ip": {"type": "in_cidr", "value": ["10.0.0.0/8"]},
+                    "time": {"type": "time_window", "value": {
+                        "days": ["Mon", "Fri"], "start": "09:00", "end": "18:00",
+                        "timezone": "Europe/London"
+                    }}
+                },
+                "not": {"subject": {"email": {"type": "regex", "value": "@contractor\\."}}}
+            },
+            "effect": "allow"
+        }"#;
+        let req: CreatePolicyRequest = serde_json::from_str(json).unwrap();
+        assert!(req.conditions.validate().is_ok());
+    }
+
     #[test]
     fn test_update_policy_request_partial() {
         let json = r#"{"name": "Updated Name", "enabled": false}"#;
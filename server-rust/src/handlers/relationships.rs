@@ -0,0 +1,500 @@
+//! Relationship-based authorization handlers
+//!
+//! Tuples and the schema are managed by org admins. Any member may run
+//! `check` and `list-objects` for themselves; querying on behalf of another
+//! subject, or expanding a relation, requires admin.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::{AppError, ERR_ADMIN_REQUIRED, ERR_NOT_A_MEMBER};
+use crate::repositories::{
+    validate_relation_name, AuditEventType, MembershipEntity, NamespaceDefinition, ObjectRef,
+    OrgRole, RelationTuple, RelationshipSchema, SubjectRef, TupleFilter, TupleKey,
+};
+use crate::services::{EmailService, ExpandNode, RelationshipService};
+use crate::utils::{authenticate_for_org, AuthenticatedUser};
+use crate::AppState;
+
+/// Maximum tuples accepted in a single write or delete request
+const MAX_TUPLES_PER_REQUEST: usize = 100;
+
+/// A tuple as sent by clients: `object#relation@subject`, split into parts
+#[derive(Debug, Clone, Deserialize)]
+pub struct TupleRequest {
+    /// Object as `type:id`
+    pub object: String,
+    pub relation: String,
+    /// Subject as `type:id` or `type:id#relation`
+    pub subject: String,
+}
+
+impl TupleRequest {
+    fn into_key(self) -> Result<TupleKey, AppError> {
+        validate_relation_name("relation", &self.relation)?;
+        Ok(TupleKey::new(
+            ObjectRef::parse(&self.object)?,
+            self.relation,
+            SubjectRef::parse(&self.subject)?,
+        ))
+    }
+}
+
+/// Request to write or delete tuples
+#[derive(Debug, Deserialize)]
+pub struct TuplesRequest {
+    pub tuples: Vec<TupleRequest>,
+}
+
+/// Response for a stored tuple
+#[derive(Debug, Serialize)]
+pub struct RelationTupleResponse {
+    pub id: Uuid,
+    pub object: String,
+    pub relation: String,
+    pub subject: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: String,
+}
+
+impl From<RelationTuple> for RelationTupleResponse {
+    fn from(tuple: RelationTuple) -> Self {
+        Self {
+            id: tuple.id,
+            object: tuple.key.object.to_string(),
+            relation: tuple.key.relation,
+            subject: tuple.key.subject.to_string(),
+            created_by: tuple.created_by,
+            created_at: tuple.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Response for write/delete requests (only tuples that changed are listed)
+#[derive(Debug, Serialize)]
+pub struct TuplesResponse {
+    pub tuples: Vec<RelationTupleResponse>,
+}
+
+/// Response for listing tuples
+#[derive(Debug, Serialize)]
+pub struct ListRelationshipsResponse {
+    pub tuples: Vec<RelationTupleResponse>,
+    pub total: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListRelationshipsQueryParams {
+    #[serde(default)]
+    pub object_type: Option<String>,
+    #[serde(default)]
+    pub object_id: Option<String>,
+    #[serde(default)]
+    pub relation: Option<String>,
+    /// Subject as `type:id` or `type:id#relation`
+    #[serde(default)]
+    pub subject: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub offset: u32,
+}
+
+fn default_limit() -> u32 {
+    50
+}
+
+/// Request and response body for the relationship schema
+#[derive(Debug, Deserialize)]
+pub struct PutSchemaRequest {
+    pub namespaces: BTreeMap<String, NamespaceDefinition>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SchemaResponse {
+    pub namespaces: BTreeMap<String, NamespaceDefinition>,
+    pub updated_at: Option<String>,
+}
+
+/// Request to check a relation
+#[derive(Debug, Deserialize)]
+pub struct CheckRelationRequest {
+    pub object: String,
+    pub relation: String,
+    /// Defaults to the calling user
+    #[serde(default)]
+    pub subject: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckRelationResponse {
+    pub allowed: bool,
+}
+
+/// Request to expand a relation
+#[derive(Debug, Deserialize)]
+pub struct ExpandRelationRequest {
+    pub object: String,
+    pub relation: String,
+}
+
+/// Request to list objects a subject has a relation on
+#[derive(Debug, Deserialize)]
+pub struct ListObjectsRequest {
+    pub object_type: String,
+    pub relation: String,
+    /// Defaults to the calling user
+    #[serde(default)]
+    pub subject: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListObjectsResponse {
+    pub object_type: String,
+    pub object_ids: Vec<String>,
+}
+
+/// Helper to authenticate and load the caller's membership in the org
+async fn verify_org_member<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Uuid,
+) -> Result<(AuthenticatedUser, MembershipEntity), AppError> {
+    let auth = authenticate_for_org(state, headers, org_id).await?;
+
+    let membership = state
+        .membership_repo
+        .find_by_user_and_org(auth.user_id, org_id)
+        .await?
+        .ok_or(AppError::Forbidden(ERR_NOT_A_MEMBER.into()))?;
+
+    Ok((auth, membership))
+}
+
+/// Helper to verify user has admin access to org
+async fn verify_org_admin<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Uuid,
+) -> Result<Uuid, AppError> {
+    let (auth, membership) = verify_org_member(state, headers, org_id).await?;
+
+    if !membership.role.has_at_least(OrgRole::Admin) {
+        return Err(AppError::Forbidden(ERR_ADMIN_REQUIRED.into()));
+    }
+
+    Ok(auth.user_id)
+}
+
+/// Resolve the subject of a query; members may only query for themselves
+fn resolve_subject(
+    requested: Option<&str>,
+    auth: &AuthenticatedUser,
+    membership: &MembershipEntity,
+) -> Result<SubjectRef, AppError> {
+    let own = SubjectRef::user(auth.user_id);
+    let subject = match requested {
+        Some(s) => SubjectRef::parse(s)?,
+        None => return Ok(own),
+    };
+    if subject != own && !membership.role.has_at_least(OrgRole::Admin) {
+        return Err(AppError::Forbidden(ERR_ADMIN_REQUIRED.into()));
+    }
+    Ok(subject)
+}
+
+fn parse_tuples(tuples: Vec<TupleRequest>) -> Result<Vec<TupleKey>, AppError> {
+    if tuples.is_empty() {
+        return Err(AppError::Validation(
+            "At least one tuple is required".into(),
+        ));
+    }
+    if tuples.len() > MAX_TUPLES_PER_REQUEST {
+        return Err(AppError::Validation(format!(
+            "At most {} tuples per request",
+            MAX_TUPLES_PER_REQUEST
+        )));
+    }
+    tuples.into_iter().map(TupleRequest::into_key).collect()
+}
+
+/// GET /orgs/:org_id/relationships/schema - Get the relationship schema
+pub async fn get_relationship_schema<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<SchemaResponse>, AppError> {
+    verify_org_member(&state, &headers, org_id).await?;
+
+    let schema = state.storage.relationship_repo.get_schema(org_id).await?;
+
+    Ok(Json(match schema {
+        Some(schema) => SchemaResponse {
+            namespaces: schema.namespaces,
+            updated_at: Some(schema.updated_at.to_rfc3339()),
+        },
+        None => SchemaResponse {
+            namespaces: BTreeMap::new(),
+            updated_at: None,
+        },
+    }))
+}
+
+/// PUT /orgs/:org_id/relationships/schema - Replace the relationship schema
+pub async fn put_relationship_schema<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<PutSchemaRequest>,
+) -> Result<Json<SchemaResponse>, AppError> {
+    let user_id = verify_org_admin(&state, &headers, org_id).await?;
+
+    let schema = RelationshipSchema::new(org_id, req.namespaces);
+    schema.validate()?;
+    let stored = state.storage.relationship_repo.put_schema(schema).await?;
+
+    let _ = state
+        .audit_service
+        .log_org_event(
+            AuditEventType::RelationshipSchemaUpdated,
+            user_id,
+            org_id,
+            Some(&headers),
+        )
+        .await;
+
+    Ok(Json(SchemaResponse {
+        namespaces: stored.namespaces,
+        updated_at: Some(stored.updated_at.to_rfc3339()),
+    }))
+}
+
+/// GET /orgs/:org_id/relationships - List relationship tuples
+pub async fn list_relationships<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Query(params): Query<ListRelationshipsQueryParams>,
+) -> Result<Json<ListRelationshipsResponse>, AppError> {
+    verify_org_admin(&state, &headers, org_id).await?;
+
+    let filter = TupleFilter {
+        object_type: params.object_type,
+        object_id: params.object_id,
+        relation: params.relation,
+        subject: params
+            .subject
+            .as_deref()
+            .map(SubjectRef::parse)
+            .transpose()?,
+    };
+
+    let repo = &state.storage.relationship_repo;
+    let (tuples_result, total_result) = tokio::join!(
+        repo.find_paged(org_id, &filter, params.limit, params.offset),
+        repo.count(org_id, &filter)
+    );
+
+    Ok(Json(ListRelationshipsResponse {
+        tuples: tuples_result?
+            .into_iter()
+            .map(RelationTupleResponse::from)
+            .collect(),
+        total: total_result?,
+    }))
+}
+
+/// POST /orgs/:org_id/relationships - Write relationship tuples
+pub async fn write_relationships<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<TuplesRequest>,
+) -> Result<Json<TuplesResponse>, AppError> {
+    let user_id = verify_org_admin(&state, &headers, org_id).await?;
+    let keys = parse_tuples(req.tuples)?;
+
+    let service = RelationshipService::new(state.storage.relationship_repo.clone());
+    let created = service.write_tuples(org_id, keys, Some(user_id)).await?;
+
+    for tuple in &created {
+        let _ = state
+            .audit_service
+            .log_relationship_event(
+                AuditEventType::RelationshipTupleWritten,
+                user_id,
+                org_id,
+                tuple.id,
+                &tuple.key.to_string(),
+                Some(&headers),
+            )
+            .await;
+    }
+
+    Ok(Json(TuplesResponse {
+        tuples: created
+            .into_iter()
+            .map(RelationTupleResponse::from)
+            .collect(),
+    }))
+}
+
+/// POST /orgs/:org_id/relationships/delete - Delete relationship tuples
+pub async fn delete_relationships<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<TuplesRequest>,
+) -> Result<Json<TuplesResponse>, AppError> {
+    let user_id = verify_org_admin(&state, &headers, org_id).await?;
+    let keys = parse_tuples(req.tuples)?;
+
+    let service = RelationshipService::new(state.storage.relationship_repo.clone());
+    let deleted = service.delete_tuples(org_id, keys).await?;
+
+    for tuple in &deleted {
+        let _ = state
+            .audit_service
+            .log_relationship_event(
+                AuditEventType::RelationshipTupleDeleted,
+                user_id,
+                org_id,
+                tuple.id,
+                &tuple.key.to_string(),
+                Some(&headers),
+            )
+            .await;
+    }
+
+    Ok(Json(TuplesResponse {
+        tuples: deleted
+            .into_iter()
+            .map(RelationTupleResponse::from)
+            .collect(),
+    }))
+}
+
+/// POST /orgs/:org_id/relationships/check - Check whether a subject has a relation
+pub async fn check_relationship<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<CheckRelationRequest>,
+) -> Result<Json<CheckRelationResponse>, AppError> {
+    let (auth, membership) = verify_org_member(&state, &headers, org_id).await?;
+    let subject = resolve_subject(req.subject.as_deref(), &auth, &membership)?;
+    let object = ObjectRef::parse(&req.object)?;
+    validate_relation_name("relation", &req.relation)?;
+
+    let service = RelationshipService::new(state.storage.relationship_repo.clone());
+    let allowed = service
+        .check(org_id, &object, &req.relation, &subject)
+        .await?;
+
+    Ok(Json(CheckRelationResponse { allowed }))
+}
+
+/// POST /orgs/:org_id/relationships/expand - Expand the subjects of a relation
+pub async fn expand_relationship<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<ExpandRelationRequest>,
+) -> Result<Json<ExpandNode>, AppError> {
+    verify_org_admin(&state, &headers, org_id).await?;
+    let object = ObjectRef::parse(&req.object)?;
+    validate_relation_name("relation", &req.relation)?;
+
+    let service = RelationshipService::new(state.storage.relationship_repo.clone());
+    let tree = service.expand(org_id, &object, &req.relation).await?;
+
+    Ok(Json(tree))
+}
+
+/// POST /orgs/:org_id/relationships/list-objects - List objects a subject has a relation on
+pub async fn list_related_objects<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<ListObjectsRequest>,
+) -> Result<Json<ListObjectsResponse>, AppError> {
+    let (auth, membership) = verify_org_member(&state, &headers, org_id).await?;
+    let subject = resolve_subject(req.subject.as_deref(), &auth, &membership)?;
+    validate_relation_name("object type", &req.object_type)?;
+    validate_relation_name("relation", &req.relation)?;
+
+    let service = RelationshipService::new(state.storage.relationship_repo.clone());
+    let object_ids = service
+        .list_objects(org_id, &req.object_type, &req.relation, &subject)
+        .await?;
+
+    Ok(Json(ListObjectsResponse {
+        object_type: req.object_type,
+        object_ids,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tuples_request_deserialize() {
+        let json = r#"{"tuples": [
+            {"object": "document:readme", "relation": "editor", "subject": "user:42"},
+            {"object": "folder:docs", "relation": "viewer", "subject": "group:eng#member"}
+        ]}"#;
+        let req: TuplesRequest = serde_json::from_str(json).unwrap();
+        let keys = parse_tuples(req.tuples).unwrap();
+        assert_eq!(keys[1].to_string(), "folder:docs#viewer@group:eng#member");
+    }
+
+    #[test]
+    fn test_parse_tuples_limits() {
+        assert!(parse_tuples(vec![]).is_err());
+
+        let tuple = TupleRequest {
+            object: "document:1".into(),
+            relation: "viewer".into(),
+            subject: "user:1".into(),
+        };
+        assert!(parse_tuples(vec![tuple.clone(); MAX_TUPLES_PER_REQUEST]).is_ok());
+        assert!(parse_tuples(vec![tuple; MAX_TUPLES_PER_REQUEST + 1]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_subject_restricts_members() {
+        let user_id = Uuid::new_v4();
+        let auth = AuthenticatedUser {
+            user_id,
+            session_id: None,
+            org_id: None,
+            role: None,
+            is_api_key_auth: false,
+            api_key_id: None,
+            raw_api_key: None,
+            is_system_admin: Some(false),
+            email_verified: Some(true),
+            api_key_scopes: None,
+            impersonation: None,
+            is_service_account: false,
+        };
+        let mut membership = MembershipEntity::new(user_id, Uuid::new_v4(), OrgRole::Member);
+
+        let own = resolve_subject(None, &auth, &membership).unwrap();
+        assert_eq!(own, SubjectRef::user(user_id));
+        assert!(resolve_subject(Some(&format!("user:{}", user_id)), &auth, &membership).is_ok());
+        assert!(resolve_subject(Some("user:someone-else"), &auth, &membership).is_err());
+
+        membership.role = OrgRole::Admin;
+        assert!(resolve_subject(Some("user:someone-else"), &auth, &membership).is_ok());
+    }
+}
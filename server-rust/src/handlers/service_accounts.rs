@@ -0,0 +1,778 @@
+//! Service account management and client-credentials token handlers
+//!
+//! Service accounts are org-owned, non-human principals. Org admins create
+//! them, set their role and optional custom role, issue API keys for them and
+//! rotate their client secret. The service account itself exchanges its
+//! client credentials for a short-lived access token.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg(feature = "postgres")]
+use sqlx::PgPool;
+
+use crate::callback::AuthCallback;
+use crate::errors::{AppError, ERR_ADMIN_REQUIRED, ERR_NOT_A_MEMBER};
+use crate::models::{
+    ApiKeyListResponse, ApiKeyResponse, CreateApiKeyRequest, CreateApiKeyResponse,
+    CreateServiceAccountRequest, ListServiceAccountsResponse, MessageResponse,
+    ServiceAccountResponse, ServiceAccountTokenRequest, ServiceAccountTokenResponse,
+    ServiceAccountWithSecretResponse, UpdateServiceAccountRequest,
+};
+use crate::repositories::pagination::{cap_limit, cap_offset};
+use crate::repositories::{
+    generate_client_secret, hash_client_secret, AuditEventType, AuditLogBuilder, CustomRole,
+    MembershipEntity, OrgRole, ServiceAccountEntity, SessionEntity, UserEntity,
+};
+use crate::services::{EmailService, TokenContext};
+use crate::utils::{
+    authenticate_for_org, extract_client_ip, hash_refresh_token, AuthenticatedUser,
+};
+use crate::AppState;
+
+use super::api_keys::{build_restricted_api_key, validate_label, MAX_KEYS_PER_USER};
+
+/// Maximum service accounts per organization
+const MAX_SERVICE_ACCOUNTS_PER_ORG: u64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct ListServiceAccountsQueryParams {
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub offset: u32,
+}
+
+fn default_limit() -> u32 {
+    50
+}
+
+/// Helper to verify the caller is a member of the org, returning their auth and role
+async fn verify_org_member<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Uuid,
+) -> Result<(AuthenticatedUser, OrgRole), AppError> {
+    let auth = authenticate_for_org(state, headers, org_id).await?;
+
+    let membership = state
+        .membership_repo
+        .find_by_user_and_org(auth.user_id, org_id)
+        .await?
+        .ok_or(AppError::Forbidden(ERR_NOT_A_MEMBER.into()))?;
+
+    Ok((auth, membership.role))
+}
+
+/// Helper to verify the caller is an owner/admin of the org
+async fn verify_org_admin<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Uuid,
+) -> Result<AuthenticatedUser, AppError> {
+    let (auth, role) = verify_org_member(state, headers, org_id).await?;
+    if !role.has_at_least(OrgRole::Admin) {
+        return Err(AppError::Forbidden(ERR_ADMIN_REQUIRED.into()));
+    }
+    Ok(auth)
+}
+
+/// Load a service account, hiding accounts that belong to another org
+async fn load_service_account<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    org_id: Uuid,
+    id: Uuid,
+) -> Result<ServiceAccountEntity, AppError> {
+    state
+        .storage
+        .service_account_repo
+        .find_by_id(id)
+        .await?
+        .filter(|account| account.org_id == org_id)
+        .ok_or(AppError::NotFound("Service account not found".into()))
+}
+
+/// Load the membership that carries a service account's org role
+async fn load_membership<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    account: &ServiceAccountEntity,
+) -> Result<MembershipEntity, AppError> {
+    state
+        .membership_repo
+        .find_by_user_and_org(account.id, account.org_id)
+        .await?
+        .ok_or(AppError::NotFound("Service account not found".into()))
+}
+
+/// Trim and check a service account name (1-100 characters)
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.chars().count() > 100 {
+        return Err(AppError::Validation("Name must be 1-100 characters".into()));
+    }
+    Ok(name)
+}
+
+/// Parse the org role for a service account (owner is never allowed)
+fn parse_role(role: &str) -> Result<OrgRole, AppError> {
+    match OrgRole::from_str(role) {
+        Some(OrgRole::Owner) => Err(AppError::Validation(
+            "Service accounts cannot be owners".into(),
+        )),
+        Some(role) => Ok(role),
+        None => Err(AppError::Validation("Invalid role".into())),
+    }
+}
+
+/// Check that a custom role exists in the org
+async fn validate_custom_role<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    org_id: Uuid,
+    role_id: Uuid,
+) -> Result<(), AppError> {
+    state
+        .custom_role_repo
+        .find_by_id(role_id)
+        .await?
+        .filter(|role| role.org_id == org_id)
+        .ok_or(AppError::Validation("Custom role not found".into()))?;
+    Ok(())
+}
+
+/// Custom role assigned to `user_id` if it is a service account in `org_id`
+///
+/// Used by the authorization endpoints to grant the role's permissions on top
+/// of the membership role.
+pub(crate) async fn service_account_custom_role<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    user_id: Uuid,
+    org_id: Uuid,
+) -> Result<Option<CustomRole>, AppError> {
+    let Some(account) = state
+        .storage
+        .service_account_repo
+        .find_by_id(user_id)
+        .await?
+    else {
+        return Ok(None);
+    };
+    match account.custom_role_id {
+        Some(role_id) if account.org_id == org_id => Ok(state
+            .custom_role_repo
+            .find_by_id(role_id)
+            .await?
+            .filter(|role| role.org_id == org_id)),
+        _ => Ok(None),
+    }
+}
+
+/// Log a service account event with the account as the target
+async fn log_service_account_event<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    event_type: AuditEventType,
+    actor_id: Uuid,
+    account: &ServiceAccountEntity,
+    metadata: Option<serde_json::Value>,
+    headers: &HeaderMap,
+) {
+    let mut builder = AuditLogBuilder::new(event_type)
+        .actor(actor_id)
+        .org(account.org_id)
+        .target("service_account", account.id);
+    if let Some(ip) = extract_client_ip(headers, state.config.server.trust_proxy) {
+        builder = builder.ip(&ip);
+    }
+    if let Some(ua) = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+    {
+        builder = builder.user_agent(ua);
+    }
+    if let Some(meta) = metadata {
+        builder = builder.metadata(meta);
+    }
+    state.audit_service.log_or_warn(builder.build()).await;
+}
+
+/// Insert the backing user, membership and service account atomically.
+///
+/// The user row is flagged `is_service_account` so it passes the
+/// login-identifier check without any credentials.
+#[cfg(feature = "postgres")]
+async fn create_with_transaction(
+    pool: &PgPool,
+    user: &UserEntity,
+    membership: &MembershipEntity,
+    account: &ServiceAccountEntity,
+) -> Result<(), AppError> {
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email_verified, name, auth_methods, is_system_admin,
+                           is_service_account, created_at, updated_at)
+        VALUES ($1, $2, $3, '{}', FALSE, TRUE, $4, $5)
+        "#,
+    )
+    .bind(user.id)
+    .bind(user.email_verified)
+    .bind(&user.name)
+    .bind(user.created_at)
+    .bind(user.updated_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO memberships (id, user_id, org_id, role)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(membership.id)
+    .bind(membership.user_id)
+    .bind(membership.org_id)
+    .bind(membership.role.as_str())
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO service_accounts (id, org_id, name, description, custom_role_id, client_id,
+                                      client_secret_hash, created_by, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#,
+    )
+    .bind(account.id)
+    .bind(account.org_id)
+    .bind(&account.name)
+    .bind(&account.description)
+    .bind(account.custom_role_id)
+    .bind(&account.client_id)
+    .bind(&account.client_secret_hash)
+    .bind(account.created_by)
+    .bind(account.created_at)
+    .bind(account.updated_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+    Ok(())
+}
+
+/// Insert the backing user, membership and service account through the repositories
+async fn create_with_repositories<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    user: UserEntity,
+    membership: MembershipEntity,
+    account: ServiceAccountEntity,
+) -> Result<(), AppError> {
+    state.user_repo.create(user).await?;
+    state.membership_repo.create(membership).await?;
+    state.storage.service_account_repo.create(account).await?;
+    Ok(())
+}
+
+/// GET /orgs/:org_id/service-accounts - List service accounts in an organization
+pub async fn list_service_accounts<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Query(params): Query<ListServiceAccountsQueryParams>,
+) -> Result<Json<ListServiceAccountsResponse>, AppError> {
+    verify_org_member(&state, &headers, org_id).await?;
+
+    let repo = &state.storage.service_account_repo;
+    let (accounts_result, total_result) = tokio::join!(
+        repo.find_by_org_paged(org_id, cap_limit(params.limit), cap_offset(params.offset)),
+        repo.count_by_org(org_id)
+    );
+    let accounts = accounts_result?;
+    let total = total_result?;
+
+    let mut service_accounts = Vec::with_capacity(accounts.len());
+    for account in &accounts {
+        let membership = load_membership(&state, account).await?;
+        service_accounts.push(ServiceAccountResponse::from_entity(
+            account,
+            membership.role,
+        ));
+    }
+
+    Ok(Json(ListServiceAccountsResponse {
+        service_accounts,
+        total,
+    }))
+}
+
+/// POST /orgs/:org_id/service-accounts - Create a service account
+///
+/// Returns the client secret - this is the only time it is shown.
+pub async fn create_service_account<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(org_id): Path<Uuid>,
+    Json(req): Json<CreateServiceAccountRequest>,
+) -> Result<Json<ServiceAccountWithSecretResponse>, AppError> {
+    let auth = verify_org_admin(&state, &headers, org_id).await?;
+    // Credentials minted here would outlive an impersonation session
+    auth.ensure_not_impersonated("create service account credentials")?;
+    let actor_id = auth.user_id;
+
+    let name = validate_name(&req.name)?;
+    let role = parse_role(&req.role)?;
+    if let Some(role_id) = req.custom_role_id {
+        validate_custom_role(&state, org_id, role_id).await?;
+    }
+
+    // Check limits and name uniqueness before creating the backing user so a
+    // rejected request leaves nothing behind on backends without transactions.
+    let existing = state
+        .storage
+        .service_account_repo
+        .find_by_org(org_id)
+        .await?;
+    if existing.len() as u64 >= MAX_SERVICE_ACCOUNTS_PER_ORG {
+        return Err(AppError::Validation(format!(
+            "Maximum of {} service accounts per organization reached",
+            MAX_SERVICE_ACCOUNTS_PER_ORG
+        )));
+    }
+    if existing.iter().any(|account| account.name == name) {
+        return Err(AppError::Validation(
+            "A service account with this name already exists".into(),
+        ));
+    }
+
+    let id = Uuid::new_v4();
+    let (mut account, client_secret) =
+        ServiceAccountEntity::new(id, org_id, name.clone(), Some(actor_id));
+    account.description = req.description;
+    account.custom_role_id = req.custom_role_id;
+    let user = UserEntity::new_service_account(id, name);
+    let membership = MembershipEntity::new(id, org_id, role);
+
+    #[cfg(feature = "postgres")]
+    if let Some(pool) = state.postgres_pool.as_ref() {
+        create_with_transaction(pool, &user, &membership, &account).await?;
+    } else {
+        create_with_repositories(&state, user, membership, account.clone()).await?;
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    create_with_repositories(&state, user, membership, account.clone()).await?;
+
+    log_service_account_event(
+        &state,
+        AuditEventType::ServiceAccountCreated,
+        actor_id,
+        &account,
+        Some(serde_json::json!({ "role": role.as_str() })),
+        &headers,
+    )
+    .await;
+
+    Ok(Json(ServiceAccountWithSecretResponse {
+        service_account: ServiceAccountResponse::from_entity(&account, role),
+        client_secret,
+        message: "Store this secret securely. It cannot be retrieved again.".to_string(),
+    }))
+}
+
+/// GET /orgs/:org_id/service-accounts/:id - Get a service account
+pub async fn get_service_account<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ServiceAccountResponse>, AppError> {
+    verify_org_member(&state, &headers, org_id).await?;
+
+    let account = load_service_account(&state, org_id, id).await?;
+    let membership = load_membership(&state, &account).await?;
+
+    Ok(Json(ServiceAccountResponse::from_entity(
+        &account,
+        membership.role,
+    )))
+}
+
+/// PATCH /orgs/:org_id/service-accounts/:id - Update name, description or roles
+pub async fn update_service_account<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UpdateServiceAccountRequest>,
+) -> Result<Json<ServiceAccountResponse>, AppError> {
+    let actor_id = verify_org_admin(&state, &headers, org_id).await?.user_id;
+
+    let mut account = load_service_account(&state, org_id, id).await?;
+    let mut membership = load_membership(&state, &account).await?;
+
+    if let Some(name) = req.name {
+        account.name = validate_name(&name)?;
+    }
+    if let Some(description) = req.description {
+        account.description = Some(description);
+    }
+    if req.clear_custom_role {
+        account.custom_role_id = None;
+    } else if let Some(role_id) = req.custom_role_id {
+        validate_custom_role(&state, org_id, role_id).await?;
+        account.custom_role_id = Some(role_id);
+    }
+    let new_role = req.role.as_deref().map(parse_role).transpose()?;
+
+    let updated = state.storage.service_account_repo.update(account).await?;
+    if let Some(role) = new_role {
+        if role != membership.role {
+            membership = state
+                .membership_repo
+                .update_role(membership.id, role)
+                .await?;
+        }
+    }
+
+    log_service_account_event(
+        &state,
+        AuditEventType::ServiceAccountUpdated,
+        actor_id,
+        &updated,
+        Some(serde_json::json!({ "role": membership.role.as_str() })),
+        &headers,
+    )
+    .await;
+
+    Ok(Json(ServiceAccountResponse::from_entity(
+        &updated,
+        membership.role,
+    )))
+}
+
+/// DELETE /orgs/:org_id/service-accounts/:id - Delete a service account
+///
+/// Revokes its sessions and API keys and removes the backing user.
+pub async fn delete_service_account<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<MessageResponse>, AppError> {
+    let actor_id = verify_org_admin(&state, &headers, org_id).await?.user_id;
+
+    let account = load_service_account(&state, org_id, id).await?;
+
+    state
+        .session_repo
+        .revoke_all_for_user_with_reason(account.id, "service_account_deleted")
+        .await?;
+    state.api_key_repo.delete_for_user(account.id).await?;
+    if let Some(membership) = state
+        .membership_repo
+        .find_by_user_and_org(account.id, org_id)
+        .await?
+    {
+        state.membership_repo.delete(membership.id).await?;
+    }
+    state
+        .storage
+        .service_account_repo
+        .delete(account.id)
+        .await?;
+    state.user_repo.delete(account.id).await?;
+
+    log_service_account_event(
+        &state,
+        AuditEventType::ServiceAccountDeleted,
+        actor_id,
+        &account,
+        Some(serde_json::json!({ "name": account.name })),
+        &headers,
+    )
+    .await;
+
+    Ok(Json(MessageResponse {
+        message: "Service account deleted".into(),
+    }))
+}
+
+/// POST /orgs/:org_id/service-accounts/:id/rotate-secret - Issue a new client secret
+///
+/// The old secret stops working immediately; existing access tokens remain
+/// valid until they expire.
+pub async fn rotate_service_account_secret<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ServiceAccountWithSecretResponse>, AppError> {
+    let auth = verify_org_admin(&state, &headers, org_id).await?;
+    // Credentials minted here would outlive an impersonation session
+    auth.ensure_not_impersonated("create service account credentials")?;
+    let actor_id = auth.user_id;
+
+    let mut account = load_service_account(&state, org_id, id).await?;
+    let membership = load_membership(&state, &account).await?;
+
+    let client_secret = generate_client_secret();
+    account.client_secret_hash = hash_client_secret(&client_secret);
+    state
+        .storage
+        .service_account_repo
+        .update_secret_hash(account.id, &account.client_secret_hash)
+        .await?;
+
+    log_service_account_event(
+        &state,
+        AuditEventType::ServiceAccountSecretRotated,
+        actor_id,
+        &account,
+        None,
+        &headers,
+    )
+    .await;
+
+    Ok(Json(ServiceAccountWithSecretResponse {
+        service_account: ServiceAccountResponse::from_entity(&account, membership.role),
+        client_secret,
+        message: "Store this secret securely. It cannot be retrieved again.".to_string(),
+    }))
+}
+
+/// GET /orgs/:org_id/service-accounts/:id/api-keys - List a service account's API keys
+pub async fn list_service_account_api_keys<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiKeyListResponse>, AppError> {
+    verify_org_admin(&state, &headers, org_id).await?;
+
+    let account = load_service_account(&state, org_id, id).await?;
+    let keys = state.api_key_repo.find_by_user_id(account.id).await?;
+
+    Ok(Json(ApiKeyListResponse {
+        keys: keys.iter().map(ApiKeyResponse::from).collect(),
+    }))
+}
+
+/// POST /orgs/:org_id/service-accounts/:id/api-keys - Create an API key for a service account
+///
+/// Accepts the same restrictions as user API keys. The key is always bound to
+/// the service account's organization.
+pub async fn create_service_account_api_key<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, id)): Path<(Uuid, Uuid)>,
+    Json(mut req): Json<CreateApiKeyRequest>,
+) -> Result<Json<CreateApiKeyResponse>, AppError> {
+    let auth = verify_org_admin(&state, &headers, org_id).await?;
+    // Credentials minted here would outlive an impersonation session
+    auth.ensure_not_impersonated("create service account credentials")?;
+    let actor_id = auth.user_id;
+
+    let account = load_service_account(&state, org_id, id).await?;
+    let label = validate_label(&req.label)?;
+
+    if req.org_id.is_some_and(|bound| bound != org_id) {
+        return Err(AppError::Validation(
+            "Service account API keys are bound to their organization".into(),
+        ));
+    }
+    req.org_id = Some(org_id);
+
+    let existing = state.api_key_repo.find_by_user_id(account.id).await?;
+    if existing.len() >= MAX_KEYS_PER_USER {
+        return Err(AppError::Validation(format!(
+            "Maximum of {} API keys per service account reached",
+            MAX_KEYS_PER_USER
+        )));
+    }
+
+    let (raw_key, entity) = build_restricted_api_key(account.id, &label, req)?;
+    let created = state.api_key_repo.create(entity).await?;
+
+    log_service_account_event(
+        &state,
+        AuditEventType::ServiceAccountKeyCreated,
+        actor_id,
+        &account,
+        Some(serde_json::json!({
+            "keyId": created.id,
+            "keyPrefix": created.key_prefix,
+            "scopes": created.scopes,
+        })),
+        &headers,
+    )
+    .await;
+
+    Ok(Json(CreateApiKeyResponse {
+        api_key: raw_key,
+        id: created.id,
+        key_prefix: created.key_prefix,
+        label: created.label,
+        created_at: created.created_at,
+        scopes: created.scopes,
+        org_id: created.org_id,
+        expires_at: created.expires_at,
+        message: "Store this key securely. It cannot be retrieved again.".to_string(),
+    }))
+}
+
+/// DELETE /orgs/:org_id/service-accounts/:id/api-keys/:key_id - Revoke a service account API key
+pub async fn delete_service_account_api_key<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path((org_id, id, key_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<MessageResponse>, AppError> {
+    let actor_id = verify_org_admin(&state, &headers, org_id).await?.user_id;
+
+    let account = load_service_account(&state, org_id, id).await?;
+    let deleted = state.api_key_repo.delete_by_id(key_id, account.id).await?;
+    if !deleted {
+        return Err(AppError::NotFound("API key not found".into()));
+    }
+
+    log_service_account_event(
+        &state,
+        AuditEventType::ServiceAccountKeyRevoked,
+        actor_id,
+        &account,
+        Some(serde_json::json!({ "keyId": key_id })),
+        &headers,
+    )
+    .await;
+
+    Ok(Json(MessageResponse {
+        message: "API key deleted".into(),
+    }))
+}
+
+/// POST /auth/service-accounts/token - Client-credentials token exchange
+///
+/// Public endpoint: the client ID and secret are the authentication. Issues a
+/// short-lived access token scoped to the service account's organization.
+/// No refresh token is returned.
+pub async fn service_account_token<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(req): Json<ServiceAccountTokenRequest>,
+) -> Result<Json<ServiceAccountTokenResponse>, AppError> {
+    if req
+        .grant_type
+        .as_deref()
+        .is_some_and(|grant| grant != "client_credentials")
+    {
+        return Err(AppError::Validation("Unsupported grant type".into()));
+    }
+
+    // Uniform error for unknown client IDs and wrong secrets
+    let account = state
+        .storage
+        .service_account_repo
+        .find_by_client_id(&req.client_id)
+        .await?
+        .filter(|account| account.verify_secret(&req.client_secret))
+        .ok_or(AppError::InvalidCredentials)?;
+
+    // Fails once the account is removed from its org or the org is deleted
+    let membership = state
+        .membership_repo
+        .find_by_user_and_org(account.id, account.org_id)
+        .await?
+        .ok_or(AppError::InvalidCredentials)?;
+
+    let session_id = Uuid::new_v4();
+    let token_context = TokenContext {
+        org_id: Some(account.org_id),
+        role: Some(membership.role.as_str().to_string()),
+        is_system_admin: None,
+        email_verified: None,
+        service_account: Some(true),
+        act: None,
+        metadata: None,
+        ext: None,
+    };
+    let access_token = state.jwt_service.generate_access_token_with_context(
+        account.id,
+        session_id,
+        &token_context,
+    )?;
+    let expires_in = state.jwt_service.access_expiry_secs();
+
+    // The session only backs the access token, so its refresh hash is of a
+    // token that is never handed out.
+    let unused_refresh = state.jwt_service.generate_refresh_token();
+    let session = SessionEntity::new_with_id(
+        session_id,
+        account.id,
+        hash_refresh_token(&unused_refresh, &state.config.jwt.secret),
+        Utc::now() + Duration::seconds(expires_in as i64),
+        extract_client_ip(&headers, state.config.server.trust_proxy),
+        headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string()),
+    );
+    state.session_repo.create(session).await?;
+
+    let _ = state
+        .storage
+        .service_account_repo
+        .update_last_used(account.id)
+        .await;
+
+    log_service_account_event(
+        &state,
+        AuditEventType::ServiceAccountTokenIssued,
+        account.id,
+        &account,
+        None,
+        &headers,
+    )
+    .await;
+
+    Ok(Json(ServiceAccountTokenResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_role_rejects_owner() {
+        assert!(parse_role("owner").is_err());
+        assert!(parse_role("nope").is_err());
+        assert_eq!(parse_role("admin").unwrap(), OrgRole::Admin);
+        assert_eq!(parse_role("member").unwrap(), OrgRole::Member);
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert_eq!(validate_name("  ci bot ").unwrap(), "ci bot");
+        assert!(validate_name("   ").is_err());
+        assert!(validate_name(&"x".repeat(101)).is_err());
+    }
+
+    #[test]
+    fn test_list_query_defaults() {
+        let params: ListServiceAccountsQueryParams = serde_json::from_str("{}").unwrap();
+        assert_eq!(params.limit, 50);
+        assert_eq!(params.offset, 0);
+    }
+}
@@ -14,7 +14,7 @@ use crate::callback::AuthCallback;
 use crate::errors::AppError;
 use crate::models::AuthMethod;
 use crate::repositories::{
-    normalize_email, MembershipEntity, OrgEntity, OrgRole, SystemSetting, UserEntity,
+    normalize_email, MembershipEntity, OrgEntity, OrgRole, SystemSetting, UserEntity, UserStatus,
 };
 use crate::services::EmailService;
 use crate::AppState;
@@ -143,9 +143,16 @@ pub async fn create_first_admin<C: AuthCallback, E: EmailService>(
         stripe_customer_id: None,
         auth_methods: vec![AuthMethod::Email],
         is_system_admin: true, // This is the key - make them admin
+        is_service_account: false,
         created_at: now,
         updated_at: now,
         last_login_at: Some(now),
+        status: UserStatus::Active,
+        status_reason: None,
+        status_expires_at: None,
+        public_metadata: Default::default(),
+        private_metadata: Default::default(),
+        unsafe_metadata: Default::default(),
     };
 
     // Create site organization
@@ -16,9 +16,11 @@ use std::future::Future;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::callback::{AuthCallback, AuthCallbackPayload};
+use crate::callback::{AuthCallback, AuthCallbackPayload, ClaimsTrigger, TokenClaimsPayload};
 use crate::errors::AppError;
-use crate::handlers::auth::call_authenticated_callback_with_timeout;
+use crate::handlers::auth::{
+    call_authenticated_callback_with_timeout, call_claims_callback_with_timeout,
+};
 use crate::models::{AuthMethod, AuthResponse};
 use crate::repositories::{
     normalize_email, AuditEventType, CredentialEntity, CredentialRepository, CredentialType,
@@ -27,7 +29,7 @@ use crate::repositories::{
 use crate::services::EmailService;
 use crate::utils::{
     attach_auth_cookies, build_json_response_with_cookies, extract_client_ip,
-    get_default_org_context, hash_refresh_token, user_entity_to_auth_user,
+    get_default_org_context, hash_refresh_token, metadata_claims, user_entity_to_auth_user,
 };
 use crate::AppState;
 
@@ -236,9 +238,16 @@ pub async fn sso_callback<C: AuthCallback, E: EmailService>(
                 stripe_customer_id: None,
                 auth_methods: vec![AuthMethod::Sso],
                 is_system_admin: false,
+                is_service_account: false,
                 created_at: now,
                 updated_at: now,
                 last_login_at: Some(now),
+                status: crate::repositories::UserStatus::Active,
+                status_reason: None,
+                status_expires_at: None,
+                public_metadata: Default::default(),
+                private_metadata: Default::default(),
+                unsafe_metadata: Default::default(),
             };
 
             let created = state.user_repo.create(new_user).await?;
@@ -265,9 +274,15 @@ pub async fn sso_callback<C: AuthCallback, E: EmailService>(
     })
     .await?;
 
+    user.ensure_active()?;
+
     // Get memberships for token context
     let memberships = state.membership_repo.find_by_user(user.id).await?;
-    let token_context = get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    let mut token_context =
+        get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    token_context.metadata = metadata_claims(&state, &user).await;
+    let claims_payload = TokenClaimsPayload::new(user.id, &token_context, ClaimsTrigger::Login);
+    token_context.ext = call_claims_callback_with_timeout(&state.callback, &claims_payload).await;
 
     // Create session
     let session_id = Uuid::new_v4();
@@ -501,6 +516,8 @@ mod tests {
                 email_verified: true,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                public_metadata: Default::default(),
+                unsafe_metadata: Default::default(),
             },
             method: AuthMethod::Sso,
             is_new_user: false,
@@ -0,0 +1,187 @@
+//! Stripe top-up handlers
+//!
+//! POST /credits/top-ups/stripe   - Start a card top-up
+//! GET  /credits/top-ups          - List top-ups
+//! POST /webhook/stripe           - Stripe webhook (signed with `Stripe-Signature`)
+//!
+//! Top-ups take an optional `orgId` to fund an organization's balance
+//! (requires `credits:manage`; listing requires `credits:read`). Credits are
+//! only added by the webhook, never when the top-up is started.
+
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::handlers::require_org_credit_permission;
+use crate::handlers::webhook::WebhookResponse;
+use crate::models::{CreateStripeTopupRequest, StripeTopupResponse, StripeTopupsResponse};
+use crate::services::{
+    verify_stripe_signature, EmailService, Permission, StripeEvent, StripeTopupService,
+};
+use crate::utils::{authenticate, authenticate_for_org, AuthenticatedUser};
+use crate::AppState;
+
+/// Default number of top-ups listed
+const DEFAULT_LIST_LIMIT: u32 = 50;
+
+/// Maximum number of top-ups listed
+const MAX_LIST_LIMIT: u32 = 200;
+
+/// Query parameters for listing top-ups
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StripeTopupsQueryParams {
+    /// Organization whose top-ups to list instead of the personal ones
+    pub org_id: Option<Uuid>,
+    pub limit: Option<u32>,
+}
+
+/// Top-up service bound to the app's storage, dispatching webhooks
+fn stripe_topup_service<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+) -> Result<StripeTopupService, AppError> {
+    if !state.config.stripe.enabled {
+        return Err(AppError::NotFound("Stripe top-ups not enabled".into()));
+    }
+    Ok(StripeTopupService::new(
+        state.storage.stripe_topup_repo.clone(),
+        state.credit_repo.clone(),
+        state.user_repo.clone(),
+        state.config.stripe.clone(),
+    )
+    .with_webhooks(state.webhook_service.clone()))
+}
+
+/// Authenticate the caller and, for an organization, require `permission`
+async fn authenticate_payer<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Option<Uuid>,
+    permission: Permission,
+) -> Result<AuthenticatedUser, AppError> {
+    match org_id {
+        Some(org_id) => {
+            let auth = authenticate_for_org(state, headers, org_id).await?;
+            require_org_credit_permission(state, auth.user_id, org_id, permission).await?;
+            Ok(auth)
+        }
+        None => authenticate(state, headers).await,
+    }
+}
+
+/// POST /credits/top-ups/stripe - Start a card top-up
+///
+/// Returns a Checkout URL to redirect to, or a PaymentIntent client secret
+/// to confirm with Stripe Elements.
+pub async fn create_stripe_topup<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateStripeTopupRequest>,
+) -> Result<Json<StripeTopupResponse>, AppError> {
+    let service = stripe_topup_service(&state)?;
+    let auth =
+        authenticate_payer(&state, &headers, request.org_id, Permission::CreditsManage).await?;
+    auth.ensure_not_impersonated("top up credits")?;
+    let user_id = auth.user_id;
+
+    let checkout = service
+        .create_topup(user_id, request.org_id, request.flow, request.amount_cents)
+        .await?;
+
+    tracing::info!(
+        topup_id = %checkout.topup.id,
+        user_id = %user_id,
+        org_id = ?request.org_id,
+        amount_cents = request.amount_cents,
+        flow = request.flow.as_str(),
+        "Stripe top-up started"
+    );
+
+    let mut response = StripeTopupResponse::from(checkout.topup);
+    response.checkout_url = checkout.checkout_url;
+    response.client_secret = checkout.client_secret;
+    Ok(Json(response))
+}
+
+/// GET /credits/top-ups - List the caller's (or an org's) top-ups
+pub async fn list_stripe_topups<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Query(params): Query<StripeTopupsQueryParams>,
+) -> Result<Json<StripeTopupsResponse>, AppError> {
+    stripe_topup_service(&state)?;
+    let user_id = authenticate_payer(&state, &headers, params.org_id, Permission::CreditsRead)
+        .await?
+        .user_id;
+
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .clamp(1, MAX_LIST_LIMIT);
+    let repo = &state.storage.stripe_topup_repo;
+    let topups = match params.org_id {
+        Some(org_id) => repo.list_for_org(org_id, limit).await?,
+        None => repo.list_for_user(user_id, limit).await?,
+    };
+
+    Ok(Json(StripeTopupsResponse {
+        topups: topups.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// POST /webhook/stripe - Handle Stripe events
+///
+/// Security:
+/// - Verifies the `Stripe-Signature` HMAC and rejects stale timestamps
+/// - Credits and reversals are idempotent, so Stripe's retries are safe
+pub async fn handle_stripe_webhook<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> Result<Json<WebhookResponse>, AppError> {
+    let service = stripe_topup_service(&state)?;
+    let config = &state.config.stripe;
+    let secret = config.webhook_secret.as_deref().ok_or_else(|| {
+        tracing::warn!("Stripe webhook received but STRIPE_WEBHOOK_SECRET not configured");
+        AppError::Config("Webhook secret not configured".into())
+    })?;
+
+    let signature = headers
+        .get("stripe-signature")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| AppError::Unauthorized("Missing webhook signature".into()))?;
+    if !verify_stripe_signature(
+        secret,
+        signature,
+        &body,
+        Utc::now().timestamp(),
+        config.webhook_tolerance_secs,
+    ) {
+        tracing::warn!("Stripe webhook signature verification failed");
+        return Err(AppError::Unauthorized("Invalid webhook signature".into()));
+    }
+
+    let event: StripeEvent = serde_json::from_slice(&body)
+        .map_err(|e| AppError::Validation(format!("Invalid webhook payload: {}", e)))?;
+    let outcome = service.handle_event(&event).await?;
+
+    tracing::info!(
+        event_id = %event.id,
+        event_type = %event.event_type,
+        outcome = outcome.as_str(),
+        "Processed Stripe webhook"
+    );
+
+    Ok(Json(WebhookResponse {
+        received: true,
+        message: Some(outcome.as_str().to_string()),
+    }))
+}
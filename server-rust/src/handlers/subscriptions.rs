@@ -0,0 +1,238 @@
+//! Subscription handlers
+//!
+//! GET  /credits/plans                         - List available subscription plans
+//! GET  /credits/subscriptions                 - List subscriptions
+//! POST /credits/subscriptions                 - Subscribe to a plan
+//! POST /credits/subscriptions/{id}/cancel     - Cancel a subscription
+//!
+//! Listing takes an optional `orgId` to read an organization's
+//! subscriptions instead (requires `credits:read`). Subscribing or
+//! canceling on behalf of an organization requires `credits:manage`.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::handlers::credits::OrgBalanceParams;
+use crate::handlers::require_org_credit_permission;
+use crate::models::{
+    CancelSubscriptionRequest, CreateSubscriptionRequest, SubscriptionPlansResponse,
+    SubscriptionResponse, SubscriptionsResponse,
+};
+use crate::repositories::SubscriptionEntity;
+use crate::services::{EmailService, Permission, SubscriptionService};
+use crate::utils::{authenticate, authenticate_for_org, AuthenticatedUser};
+use crate::AppState;
+
+/// Subscription service bound to the app's storage, enforcing spending
+/// budgets and dispatching webhooks
+pub(crate) fn subscription_service<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+) -> SubscriptionService {
+    SubscriptionService::new(
+        state.storage.subscription_repo.clone(),
+        state.credit_repo.clone(),
+        state.credit_hold_repo.clone(),
+        state.membership_repo.clone(),
+    )
+    .with_budgets(state.storage.credit_budget_repo.clone())
+    .with_webhooks(state.webhook_service.clone())
+}
+
+fn ensure_credits_enabled<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+) -> Result<(), AppError> {
+    if !state.config.privacy.enabled {
+        return Err(AppError::NotFound("Credits not enabled".into()));
+    }
+    Ok(())
+}
+
+/// Authenticate the caller and, for an organization, require `permission`
+async fn authenticate_subscriber<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Option<Uuid>,
+    permission: Permission,
+) -> Result<AuthenticatedUser, AppError> {
+    match org_id {
+        Some(org_id) => {
+            let auth = authenticate_for_org(state, headers, org_id).await?;
+            require_org_credit_permission(state, auth.user_id, org_id, permission).await?;
+            Ok(auth)
+        }
+        None => authenticate(state, headers).await,
+    }
+}
+
+/// Attach plans to subscriptions for the response
+async fn subscription_responses<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    subscriptions: Vec<SubscriptionEntity>,
+) -> Result<Vec<SubscriptionResponse>, AppError> {
+    let plans: HashMap<_, _> = state
+        .storage
+        .subscription_repo
+        .list_plans()
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+
+    Ok(subscriptions
+        .into_iter()
+        .filter_map(|s| {
+            let plan = plans.get(&s.plan_id)?.clone();
+            Some(SubscriptionResponse::new(s, plan))
+        })
+        .collect())
+}
+
+/// GET /credits/plans - List plans open to new subscribers
+pub async fn list_subscription_plans<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+) -> Result<Json<SubscriptionPlansResponse>, AppError> {
+    ensure_credits_enabled(&state)?;
+    authenticate(&state, &headers).await?;
+
+    let plans = state.storage.subscription_repo.list_plans().await?;
+    Ok(Json(SubscriptionPlansResponse {
+        plans: plans
+            .into_iter()
+            .filter(|p| p.active)
+            .map(Into::into)
+            .collect(),
+    }))
+}
+
+/// GET /credits/subscriptions - List the caller's (or an org's) subscriptions
+pub async fn list_subscriptions<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Query(params): Query<OrgBalanceParams>,
+) -> Result<Json<SubscriptionsResponse>, AppError> {
+    ensure_credits_enabled(&state)?;
+    let user_id = authenticate_subscriber(&state, &headers, params.org_id, Permission::CreditsRead)
+        .await?
+        .user_id;
+
+    let subscriptions = match params.org_id {
+        Some(org_id) => state.storage.subscription_repo.list_for_org(org_id).await?,
+        None => {
+            state
+                .storage
+                .subscription_repo
+                .list_for_user(user_id)
+                .await?
+        }
+    };
+    let subscriptions = subscription_responses(&state, subscriptions).await?;
+
+    let mut entitlements: Vec<String> = subscriptions
+        .iter()
+        .filter(|s| s.entitled)
+        .flat_map(|s| s.plan.entitlements.iter().cloned())
+        .collect();
+    entitlements.sort();
+    entitlements.dedup();
+
+    Ok(Json(SubscriptionsResponse {
+        subscriptions,
+        entitlements,
+    }))
+}
+
+/// POST /credits/subscriptions - Subscribe to a plan
+///
+/// Charges the first period immediately unless the plan has a trial the
+/// subscriber has not used yet. Fails without subscribing if the charge is
+/// rejected (e.g. insufficient credits).
+pub async fn create_subscription<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateSubscriptionRequest>,
+) -> Result<Json<SubscriptionResponse>, AppError> {
+    ensure_credits_enabled(&state)?;
+    let auth = authenticate_subscriber(&state, &headers, request.org_id, Permission::CreditsManage)
+        .await?;
+    auth.ensure_not_impersonated("start subscriptions")?;
+    let user_id = auth.user_id;
+
+    let subscription = subscription_service(&state)
+        .subscribe(request.plan_id, user_id, request.org_id, Utc::now())
+        .await?;
+
+    tracing::info!(
+        subscription_id = %subscription.id,
+        plan_id = %subscription.plan_id,
+        user_id = %user_id,
+        org_id = ?subscription.org_id,
+        status = subscription.status.as_str(),
+        "Subscription created"
+    );
+
+    let mut responses = subscription_responses(&state, vec![subscription]).await?;
+    responses
+        .pop()
+        .map(Json)
+        .ok_or_else(|| AppError::NotFound("Subscription plan not found".into()))
+}
+
+/// POST /credits/subscriptions/{id}/cancel - Cancel a subscription
+///
+/// By default the subscription stays active until the paid period ends.
+pub async fn cancel_subscription<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(subscription_id): Path<Uuid>,
+    Json(request): Json<CancelSubscriptionRequest>,
+) -> Result<Json<SubscriptionResponse>, AppError> {
+    ensure_credits_enabled(&state)?;
+    let not_found = || AppError::NotFound("Subscription not found".into());
+
+    let subscription = state
+        .storage
+        .subscription_repo
+        .find_subscription(subscription_id)
+        .await?
+        .ok_or_else(not_found)?;
+    let user_id = match subscription.org_id {
+        Some(org_id) => {
+            authenticate_subscriber(&state, &headers, Some(org_id), Permission::CreditsManage)
+                .await?
+                .user_id
+        }
+        None => {
+            let user_id = authenticate(&state, &headers).await?.user_id;
+            // Other users' subscriptions are indistinguishable from missing ones
+            if subscription.user_id != user_id {
+                return Err(not_found());
+            }
+            user_id
+        }
+    };
+
+    let canceled = subscription_service(&state)
+        .cancel(subscription, request.at_period_end, Utc::now())
+        .await?;
+
+    tracing::info!(
+        subscription_id = %canceled.id,
+        user_id = %user_id,
+        org_id = ?canceled.org_id,
+        at_period_end = request.at_period_end,
+        "Subscription canceled"
+    );
+
+    let mut responses = subscription_responses(&state, vec![canceled]).await?;
+    responses.pop().map(Json).ok_or_else(not_found)
+}
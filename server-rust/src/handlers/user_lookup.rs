@@ -6,20 +6,27 @@
 //! GET /users/by-wallet/{wallet_address} - Look up user by wallet address
 //! GET /users/by-stripe-customer/{stripe_customer_id} - Look up user by Stripe customer ID
 //! POST /users/by-stripe-customer/{stripe_customer_id}/link - Link Stripe customer ID to a user
+//! GET /users/{user_id}/metadata - Read a user's metadata blobs
+//! PATCH /users/{user_id}/metadata - Merge-patch a user's metadata blobs
 
 use axum::{
     extract::{Path, State},
-    http::HeaderMap,
+    http::{header, HeaderMap},
     Json,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::callback::AuthCallback;
 use crate::errors::AppError;
-use crate::handlers::admin::validate_system_admin;
+use crate::handlers::admin::validate_system_admin_with_scope;
+use crate::repositories::{
+    AuditEventType, AuditLogBuilder, UserEntity, SCOPE_USERS_READ, SCOPE_USERS_WRITE,
+};
 use crate::services::EmailService;
+use crate::utils::extract_client_ip;
 use crate::AppState;
 
 /// Response from wallet lookup endpoint
@@ -46,6 +53,39 @@ pub struct LinkStripeCustomerRequest {
     pub user_id: Uuid,
 }
 
+/// All three metadata blobs of a user
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserMetadataResponse {
+    pub user_id: Uuid,
+    /// Visible to the user, writable only here
+    pub public_metadata: Map<String, Value>,
+    /// Never exposed to the user
+    pub private_metadata: Map<String, Value>,
+    /// Writable by the user via `PATCH /auth/me`
+    pub unsafe_metadata: Map<String, Value>,
+}
+
+impl From<UserEntity> for UserMetadataResponse {
+    fn from(user: UserEntity) -> Self {
+        Self {
+            user_id: user.id,
+            public_metadata: user.public_metadata,
+            private_metadata: user.private_metadata,
+            unsafe_metadata: user.unsafe_metadata,
+        }
+    }
+}
+
+/// Merge patches for a user's metadata; omitted blobs are left unchanged
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateUserMetadataRequest {
+    pub public_metadata: Option<Map<String, Value>>,
+    pub private_metadata: Option<Map<String, Value>>,
+    pub unsafe_metadata: Option<Map<String, Value>>,
+}
+
 /// GET /users/by-wallet/{wallet_address} - Look up user by wallet address
 ///
 /// Returns the user_id associated with a wallet address, or null if the wallet
@@ -64,7 +104,7 @@ pub async fn lookup_by_wallet<C: AuthCallback, E: EmailService>(
     Path(wallet_address): Path<String>,
 ) -> Result<Json<WalletLookupResponse>, AppError> {
     // Validate admin API key
-    let admin_id = validate_system_admin(&state, &headers).await?;
+    let admin_id = validate_system_admin_with_scope(&state, &headers, SCOPE_USERS_READ).await?;
 
     // Basic validation - Solana addresses are base58, 32-44 chars
     if wallet_address.len() < 32 || wallet_address.len() > 44 {
@@ -100,7 +140,7 @@ pub async fn lookup_by_stripe_customer<C: AuthCallback, E: EmailService>(
     headers: HeaderMap,
     Path(stripe_customer_id): Path<String>,
 ) -> Result<Json<StripeCustomerLookupResponse>, AppError> {
-    let admin_id = validate_system_admin(&state, &headers).await?;
+    let admin_id = validate_system_admin_with_scope(&state, &headers, SCOPE_USERS_READ).await?;
 
     // Basic validation - Stripe customers are typically like `cus_...`
     if !stripe_customer_id.starts_with("cus_")
@@ -144,7 +184,7 @@ pub async fn link_stripe_customer<C: AuthCallback, E: EmailService>(
     Path(stripe_customer_id): Path<String>,
     Json(req): Json<LinkStripeCustomerRequest>,
 ) -> Result<Json<StripeCustomerLookupResponse>, AppError> {
-    let admin_id = validate_system_admin(&state, &headers).await?;
+    let admin_id = validate_system_admin_with_scope(&state, &headers, SCOPE_USERS_WRITE).await?;
 
     if !stripe_customer_id.starts_with("cus_")
         || stripe_customer_id.len() < 8
@@ -207,6 +247,92 @@ pub async fn link_stripe_customer<C: AuthCallback, E: EmailService>(
     }))
 }
 
+/// GET /users/{user_id}/metadata - Read a user's metadata
+///
+/// - 200: Public, private and unsafe metadata
+/// - 401/403: Invalid auth or missing `users:read` scope
+/// - 404: User not found
+pub async fn get_user_metadata<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<UserMetadataResponse>, AppError> {
+    validate_system_admin_with_scope(&state, &headers, SCOPE_USERS_READ).await?;
+
+    let user = state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+
+    Ok(Json(user.into()))
+}
+
+/// PATCH /users/{user_id}/metadata - Update a user's metadata
+///
+/// Each provided blob is applied as a JSON merge patch (`null` removes a
+/// key). Every resulting blob must stay within the size and depth limits.
+///
+/// - 200: Updated metadata
+/// - 400: Blob too large or too deeply nested
+/// - 401/403: Invalid auth or missing `users:write` scope
+/// - 404: User not found
+pub async fn update_user_metadata<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    headers: HeaderMap,
+    Path(user_id): Path<Uuid>,
+    Json(req): Json<UpdateUserMetadataRequest>,
+) -> Result<Json<UserMetadataResponse>, AppError> {
+    let admin_id = validate_system_admin_with_scope(&state, &headers, SCOPE_USERS_WRITE).await?;
+
+    let user = state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+
+    // Patches are applied to the stored blobs by the repository, under a lock
+    let fields: Vec<&str> = [
+        ("publicMetadata", req.public_metadata.is_some()),
+        ("privateMetadata", req.private_metadata.is_some()),
+        ("unsafeMetadata", req.unsafe_metadata.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(field, present)| present.then_some(field))
+    .collect();
+
+    if fields.is_empty() {
+        return Ok(Json(user.into()));
+    }
+
+    let updated = state
+        .user_repo
+        .update_metadata(
+            user_id,
+            req.public_metadata,
+            req.private_metadata,
+            req.unsafe_metadata,
+        )
+        .await?;
+
+    let mut builder = AuditLogBuilder::new(AuditEventType::UserMetadataUpdated)
+        .actor(admin_id)
+        .target("user", user_id)
+        .metadata(serde_json::json!({ "fields": fields }));
+    if let Some(ip) = extract_client_ip(&headers, state.config.server.trust_proxy) {
+        builder = builder.ip(&ip);
+    }
+    if let Some(ua) = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+    {
+        builder = builder.user_agent(ua);
+    }
+    state.audit_service.log_or_warn(builder.build()).await;
+
+    Ok(Json(updated.into()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
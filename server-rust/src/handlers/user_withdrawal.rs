@@ -136,6 +136,7 @@ async fn reconstruct_key<C: AuthCallback, E: EmailService>(
     headers: &HeaderMap,
 ) -> Result<(zeroize::Zeroizing<String>, uuid::Uuid), AppError> {
     let auth_user = authenticate(state, headers).await?;
+    auth_user.ensure_not_impersonated("withdraw funds")?;
 
     let wallet_material = state
         .wallet_material_repo
@@ -30,6 +30,7 @@ pub async fn create_derived_wallet<C: AuthCallback, E: EmailService>(
     Json(req): Json<CreateDerivedWalletRequest>,
 ) -> Result<Json<DerivedWalletResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("manage derived wallets")?;
     let user_id = auth.user_id;
 
     // Validate label
@@ -135,6 +136,7 @@ pub async fn delete_derived_wallet<C: AuthCallback, E: EmailService>(
     Path(wallet_id): Path<uuid::Uuid>,
 ) -> Result<Json<MessageResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("manage derived wallets")?;
     let user_id = auth.user_id;
 
     // Look up the wallet before deleting so we can record history
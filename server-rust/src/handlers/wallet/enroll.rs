@@ -31,6 +31,7 @@ pub async fn wallet_enroll<C: AuthCallback, E: EmailService>(
     Json(req): Json<WalletEnrollRequest>,
 ) -> Result<Json<MessageResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("enroll a wallet")?;
     let user_id = auth.user_id;
 
     // Check if user logged in with their own Solana wallet
@@ -56,6 +56,7 @@ pub async fn wallet_unlock<C: AuthCallback, E: EmailService>(
     Json(req): Json<WalletUnlockRequest>,
 ) -> Result<Json<WalletUnlockResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("unlock the wallet")?;
     let user_id = auth.user_id;
 
     let session_id = auth
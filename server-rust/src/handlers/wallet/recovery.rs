@@ -108,6 +108,7 @@ pub async fn get_share_b_for_recovery<C: AuthCallback, E: EmailService>(
     Json(req): Json<ShareCRecoveryRequest>,
 ) -> Result<Json<ShareCRecoveryResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("access wallet shares")?;
     let user_id = auth.user_id;
 
     if state.config.wallet.recovery_mode != crate::config::WalletRecoveryMode::ShareCOnly {
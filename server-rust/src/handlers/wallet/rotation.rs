@@ -30,6 +30,7 @@ pub async fn rotate_user_secret<C: AuthCallback, E: EmailService>(
     Json(req): Json<RotateUserSecretRequest>,
 ) -> Result<Json<MessageResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("rotate wallet secrets")?;
     let user_id = auth.user_id;
 
     let current = state
@@ -92,6 +93,7 @@ pub async fn wallet_rotate<C: AuthCallback, E: EmailService>(
     Json(req): Json<WalletRotateRequest>,
 ) -> Result<Json<MessageResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("rotate wallet secrets")?;
     let user_id = auth.user_id;
 
     // SRV-08: Require recent strong auth for session-based users (not API key).
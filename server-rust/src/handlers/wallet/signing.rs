@@ -32,6 +32,7 @@ pub async fn sign_transaction<C: AuthCallback, E: EmailService>(
     Json(req): Json<SignTransactionRequest>,
 ) -> Result<Json<SignTransactionResponse>, AppError> {
     let auth = authenticate(&state, &headers).await?;
+    auth.ensure_not_impersonated("sign transactions")?;
     let user_id = auth.user_id;
 
     // SEC-008: Use inner function to unify all error responses
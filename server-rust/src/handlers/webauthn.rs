@@ -12,9 +12,11 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::callback::{AuthCallback, AuthCallbackPayload};
+use crate::callback::{AuthCallback, AuthCallbackPayload, ClaimsTrigger, TokenClaimsPayload};
 use crate::errors::AppError;
-use crate::handlers::auth::call_authenticated_callback_with_timeout;
+use crate::handlers::auth::{
+    call_authenticated_callback_with_timeout, call_claims_callback_with_timeout,
+};
 use crate::models::{AuthMethod, AuthResponse};
 use crate::repositories::{
     normalize_email, AuditEventType, CredentialEntity, CredentialType, SessionEntity,
@@ -25,7 +27,7 @@ use crate::services::{
 };
 use crate::utils::{
     auth::authenticate, build_json_response_with_cookies, extract_client_ip,
-    get_default_org_context, hash_refresh_token, user_entity_to_auth_user,
+    get_default_org_context, hash_refresh_token, metadata_claims, user_entity_to_auth_user,
 };
 use crate::AppState;
 
@@ -80,6 +82,7 @@ pub async fn register_options<C: AuthCallback, E: EmailService>(
 ) -> Result<Json<RegisterOptionsResponse>, AppError> {
     // Verify user is authenticated
     let auth_user = authenticate(&state, &headers).await?;
+    auth_user.ensure_not_impersonated("register passkeys")?;
 
     // Get user details
     let user = state
@@ -128,6 +131,7 @@ pub async fn register_verify<C: AuthCallback, E: EmailService>(
 ) -> Result<Json<serde_json::Value>, AppError> {
     // Verify user is authenticated
     let auth_user = authenticate(&state, &headers).await?;
+    auth_user.ensure_not_impersonated("register passkeys")?;
 
     // Parse the credential from JSON
     let credential: webauthn_rs::prelude::RegisterPublicKeyCredential =
@@ -327,9 +331,15 @@ pub async fn auth_verify<C: AuthCallback, E: EmailService>(
             "User not found after WebAuthn auth"
         )))?;
 
+    user.ensure_active()?;
+
     // Get memberships for token context
     let memberships = state.membership_repo.find_by_user(verified_user_id).await?;
-    let token_context = get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    let mut token_context =
+        get_default_org_context(&memberships, user.is_system_admin, user.email_verified);
+    token_context.metadata = metadata_claims(&state, &user).await;
+    let claims_payload = TokenClaimsPayload::new(user.id, &token_context, ClaimsTrigger::Login);
+    token_context.ext = call_claims_callback_with_timeout(&state.callback, &claims_payload).await;
 
     // Create session
     let session_id = Uuid::new_v4();
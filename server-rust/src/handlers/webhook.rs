@@ -463,7 +463,7 @@ mod tests {
     async fn test_insert_pending_spl_deposit_dedup_by_tx_signature() {
         use crate::config::privacy::USDC_MINT;
         use crate::config::DatabaseConfig;
-        use crate::repositories::UserEntity;
+        use crate::repositories::{UserEntity, UserStatus};
         use crate::Storage;
         use chrono::Utc;
         use std::env;
@@ -502,9 +502,16 @@ mod tests {
             stripe_customer_id: None,
             auth_methods: vec![],
             is_system_admin: false,
+            is_service_account: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
         let user = storage
             .user_repo
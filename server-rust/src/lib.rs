@@ -48,7 +48,9 @@ pub mod utils;
 
 mod router;
 
-pub use callback::{AuthCallback, AuthCallbackPayload, NoopCallback};
+pub use callback::{
+    AuthCallback, AuthCallbackPayload, ClaimsTrigger, NoopCallback, TokenClaimsPayload,
+};
 pub use config::{Config, DatabaseConfig, NotificationConfig};
 pub use errors::AppError;
 pub use router::create_router;
@@ -63,6 +65,7 @@ pub use sqlx::PgPool;
 pub use storage::Storage;
 
 use axum::Router;
+use middleware::rate_limit::RateLimitStore;
 use repositories::{
     ApiKeyRepository, AuditLogRepository, CredentialRepository, CreditHoldRepository,
     CreditRefundRequestRepository, CreditRepository, CustomRoleRepository, DepositRepository,
@@ -78,7 +81,7 @@ use services::{
     DepositFeeService, EncryptionService, GoogleService, JupiterSwapService, JwtService,
     MfaAttemptService, NoteEncryptionService, OidcService, PasswordService, PrivacySidecarClient,
     SettingsService, SidecarClientConfig, SolPriceService, SolanaService, StepUpService,
-    TotpService, WalletSigningService, WalletUnlockCache, WebAuthnService,
+    TotpService, WalletSigningService, WalletUnlockCache, WebAuthnService, WebhookService,
 };
 use std::sync::Arc;
 use utils::TokenCipher;
@@ -137,6 +140,7 @@ pub struct AppState<C: AuthCallback, E: EmailService = LogEmailService> {
     pub phantom_email: std::marker::PhantomData<E>,
     pub audit_service: AuditService,
     pub comms_service: CommsService,
+    pub webhook_service: Arc<WebhookService>,
     pub user_repo: Arc<dyn UserRepository>,
     pub session_repo: Arc<dyn SessionRepository>,
     pub nonce_repo: Arc<dyn NonceRepository>,
@@ -152,6 +156,8 @@ pub struct AppState<C: AuthCallback, E: EmailService = LogEmailService> {
     pub policy_repo: Arc<dyn PolicyRepository>,
     pub outbox_repo: Arc<dyn OutboxRepository>,
     pub api_key_repo: Arc<dyn ApiKeyRepository>,
+    /// Per-key rate limiter for API keys with `rate_limit_per_minute` set
+    pub api_key_rate_limiter: RateLimitStore,
     pub wallet_material_repo: Arc<dyn WalletMaterialRepository>,
     pub derived_wallet_repo: Arc<dyn DerivedWalletRepository>,
     pub wallet_rotation_history_repo: Arc<dyn WalletRotationHistoryRepository>,
@@ -228,7 +234,15 @@ pub fn router_with_storage<C: AuthCallback + 'static>(
     let solana_service = SolanaService::new(&config.solana, "Cedros Login".to_string());
     let totp_service = TotpService::new("Cedros");
     let webauthn_service = WebAuthnService::new(&config.webauthn);
-    let audit_service = AuditService::new(storage.audit_repo.clone(), config.server.trust_proxy);
+    let webhook_service = Arc::new(WebhookService::new(
+        storage.webhook_repo.clone(),
+        storage.outbox_repo.clone(),
+        TokenCipher::new(&config.jwt.secret),
+    ));
+    let audit_service = AuditService::new(storage.audit_repo.clone(), config.server.trust_proxy)
+        .with_service_accounts(storage.service_account_repo.clone())
+        .with_webhooks(webhook_service.clone())
+        .with_sink_notify(storage.audit_notify.clone());
     let step_up_service = StepUpService::new(storage.session_repo.clone());
 
     // Create SSO services
@@ -366,6 +380,7 @@ pub fn router_with_storage<C: AuthCallback + 'static>(
         phantom_email: std::marker::PhantomData::<LogEmailService>,
         audit_service,
         comms_service,
+        webhook_service,
         user_repo: storage.user_repo.clone(),
         session_repo: storage.session_repo.clone(),
         nonce_repo: storage.nonce_repo.clone(),
@@ -381,6 +396,7 @@ pub fn router_with_storage<C: AuthCallback + 'static>(
         policy_repo: storage.policy_repo.clone(),
         outbox_repo: storage.outbox_repo.clone(),
         api_key_repo: storage.api_key_repo.clone(),
+        api_key_rate_limiter: RateLimitStore::new(),
         wallet_material_repo: storage.wallet_material_repo.clone(),
         derived_wallet_repo: storage.derived_wallet_repo.clone(),
         wallet_rotation_history_repo: storage.wallet_rotation_history_repo.clone(),
@@ -570,11 +586,205 @@ pub fn create_hold_expiration_worker(
         storage.credit_repo.clone(),
         storage.credit_hold_repo.clone(),
         HoldExpirationConfig::default(),
+    )
+    .with_budgets(storage.credit_budget_repo.clone());
+
+    worker.start(cancel_token)
+}
+
+/// Create an expiration worker for promotional credit grants.
+///
+/// This worker periodically forfeits the unspent credit of grants that have
+/// passed their expiry.
+///
+/// Returns the JoinHandle for the background task.
+pub fn create_grant_expiration_worker(
+    storage: &Storage,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    use services::{GrantExpirationConfig, GrantExpirationWorker};
+
+    let worker = GrantExpirationWorker::new(
+        storage.credit_repo.clone(),
+        storage.credit_hold_repo.clone(),
+        GrantExpirationConfig::default(),
+    );
+
+    worker.start(cancel_token)
+}
+
+/// Create a billing worker for metered usage.
+///
+/// This worker periodically prices pending usage events against the meter
+/// catalog and debits the payer's credits.
+///
+/// Returns the JoinHandle for the background task.
+pub fn create_usage_billing_worker(
+    storage: &Storage,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    use services::{UsageBillingConfig, UsageBillingWorker};
+
+    let worker = UsageBillingWorker::new(
+        storage.meter_repo.clone(),
+        storage.credit_repo.clone(),
+        storage.credit_hold_repo.clone(),
+        storage.membership_repo.clone(),
+        UsageBillingConfig::default(),
+    );
+
+    worker.start(cancel_token)
+}
+
+/// Create a renewal worker for recurring subscriptions.
+///
+/// This worker periodically charges subscriptions whose period has ended,
+/// and queues dunning notifications and webhooks when a charge is rejected.
+///
+/// Returns the JoinHandle for the background task.
+pub fn create_subscription_renewal_worker(
+    config: &Config,
+    storage: &Storage,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    use services::{SubscriptionRenewalConfig, SubscriptionRenewalWorker, SubscriptionService};
+
+    let base_url = config
+        .server
+        .frontend_url
+        .clone()
+        .unwrap_or_else(|| "http://localhost:3000".to_string());
+    let comms_service = CommsService::new(
+        storage.outbox_repo.clone(),
+        base_url,
+        TokenCipher::new(&config.jwt.secret),
+    );
+    let webhook_service = Arc::new(WebhookService::new(
+        storage.webhook_repo.clone(),
+        storage.outbox_repo.clone(),
+        TokenCipher::new(&config.jwt.secret),
+    ));
+    let service = SubscriptionService::new(
+        storage.subscription_repo.clone(),
+        storage.credit_repo.clone(),
+        storage.credit_hold_repo.clone(),
+        storage.membership_repo.clone(),
+    )
+    .with_budgets(storage.credit_budget_repo.clone())
+    .with_comms(comms_service)
+    .with_webhooks(webhook_service);
+
+    let worker = SubscriptionRenewalWorker::new(service, SubscriptionRenewalConfig::default());
+
+    worker.start(cancel_token)
+}
+
+/// Create a reconciliation worker for the credit ledger.
+///
+/// This worker periodically recomputes every credit balance from the
+/// double-entry journal and logs any drift it finds.
+///
+/// Returns the JoinHandle for the background task.
+pub fn create_credit_reconciliation_worker(
+    storage: &Storage,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    use services::{CreditReconciliationConfig, CreditReconciliationWorker};
+
+    let worker = CreditReconciliationWorker::new(
+        storage.credit_repo.clone(),
+        storage.credit_hold_repo.clone(),
+        CreditReconciliationConfig::default(),
     );
 
     worker.start(cancel_token)
 }
 
+/// Create a purge worker for soft-deleted organizations.
+///
+/// This worker periodically removes organizations whose deletion grace period
+/// has ended, along with their org-scoped data.
+///
+/// Returns the JoinHandle for the background task.
+pub fn create_org_purge_worker(
+    config: &Config,
+    storage: &Storage,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    use services::{OrgPurgeConfig, OrgPurgeWorker};
+
+    let worker = OrgPurgeWorker::new(
+        storage.clone(),
+        OrgPurgeConfig {
+            cascade_in_memory: config.database.url.is_none(),
+            ..Default::default()
+        },
+    );
+
+    worker.start(cancel_token)
+}
+
+/// Create a worker that signs checkpoints of the audit hash chain.
+///
+/// The signing key is derived from the JWT secret, so checkpoints verify
+/// with the same key in the admin endpoint and the `verify-audit` command.
+pub fn create_audit_checkpoint_worker(
+    config: &Config,
+    storage: &Storage,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    use services::{AuditChainService, AuditCheckpointConfig, AuditCheckpointWorker};
+
+    let chain_service = AuditChainService::new(storage.audit_repo.clone(), &config.jwt.secret);
+    let worker = AuditCheckpointWorker::new(chain_service, AuditCheckpointConfig::default());
+
+    worker.start(cancel_token)
+}
+
+/// Create one streaming worker per audit sink enabled in the config.
+///
+/// Returns an empty list when no sink is configured, and an error when a
+/// sink cannot be built (e.g. malformed OTLP headers).
+pub fn create_audit_sink_workers(
+    config: &Config,
+    storage: &Storage,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> Result<Vec<tokio::task::JoinHandle<()>>, AppError> {
+    use services::{audit_sinks_from_config, AuditSinkWorker};
+
+    let handles = audit_sinks_from_config(&config.audit_sinks)?
+        .into_iter()
+        .map(|sink| {
+            AuditSinkWorker::new(
+                storage.audit_repo.clone(),
+                sink,
+                storage.audit_notify.clone(),
+                &config.audit_sinks,
+            )
+            .start(cancel_token.clone())
+        })
+        .collect();
+
+    Ok(handles)
+}
+
+/// Create a purge worker for accounts scheduled for deletion.
+///
+/// This worker periodically removes the personal data of accounts whose
+/// deletion cooling-off period has ended.
+///
+/// Returns the JoinHandle for the background task.
+pub fn create_user_purge_worker(
+    storage: &Storage,
+    cancel_token: tokio_util::sync::CancellationToken,
+) -> tokio::task::JoinHandle<()> {
+    use services::{UserPurgeConfig, UserPurgeWorker};
+
+    let worker = UserPurgeWorker::new(storage.clone(), UserPurgeConfig::default());
+
+    worker.start(cancel_token)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -583,9 +793,10 @@ mod tests {
     fn base_config() -> Config {
         use crate::config::{
             default_access_expiry, default_audience, default_issuer, default_refresh_expiry,
-            AppleConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig, GoogleConfig,
-            JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig, ServerConfig,
-            SolanaConfig, SsoConfig, WalletConfig, WebAuthnConfig, WebhookConfig,
+            AppleConfig, AuditSinkConfig, CookieConfig, CorsConfig, DatabaseConfig, EmailConfig,
+            GoogleConfig, JwtConfig, NotificationConfig, PrivacyConfig, RateLimitConfig,
+            ServerConfig, SolanaConfig, SsoConfig, StripeConfig, WalletConfig, WebAuthnConfig,
+            WebhookConfig,
         };
 
         Config {
@@ -619,6 +830,8 @@ mod tests {
             sso: SsoConfig::default(),
             wallet: WalletConfig::default(),
             privacy: PrivacyConfig::default(),
+            audit_sinks: AuditSinkConfig::default(),
+            stripe: StripeConfig::default(),
         }
     }
 
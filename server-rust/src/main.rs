@@ -5,17 +5,22 @@
 //! cargo run
 //! # or
 //! cedros-login-server
+//!
+//! # check the tamper-evident audit log and exit
+//! cedros-login-server verify-audit [--org <org-id>]
 //! ```
 
 use cedros_login::services::{
-    init_logging, init_metrics, DiscordNotificationService, LogEmailService,
+    init_logging, init_metrics, AuditChainService, DiscordNotificationService, LogEmailService,
     LogNotificationService, OutboxWorker, OutboxWorkerConfig, PostmarkEmailService,
     TelegramNotificationService,
 };
 use cedros_login::utils::TokenCipher;
 use cedros_login::{
-    create_micro_batch_worker, create_withdrawal_worker, router_with_storage, Config, NoopCallback,
-    Storage,
+    create_audit_checkpoint_worker, create_audit_sink_workers, create_credit_reconciliation_worker,
+    create_grant_expiration_worker, create_micro_batch_worker, create_org_purge_worker,
+    create_subscription_renewal_worker, create_usage_billing_worker, create_user_purge_worker,
+    create_withdrawal_worker, router_with_storage, Config, NoopCallback, Storage,
 };
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -23,6 +28,9 @@ use tokio::net::TcpListener;
 use tokio::signal;
 use tokio_util::sync::CancellationToken;
 use tracing::info;
+use uuid::Uuid;
+
+const VERIFY_AUDIT_USAGE: &str = "Usage: cedros-login-server verify-audit [--org <org-id>]";
 
 fn is_production_like_environment(environment: &str) -> bool {
     let env_lc = environment.trim().to_ascii_lowercase();
@@ -34,6 +42,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Load environment variables from .env file
     dotenvy::dotenv().ok();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("verify-audit") {
+        return verify_audit(&args[1..]).await;
+    }
+
     // Initialize tracing with reloadable filter (allows runtime log level changes)
     let logging_service = init_logging("cedros_login=info,tower_http=info,axum=info");
 
@@ -144,6 +157,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         base_url,
         token_cipher,
     )
+    .with_webhooks(storage.webhook_repo.clone())
     .start(cancel_token.clone());
 
     // Create settings service for runtime-configurable values (from database)
@@ -182,6 +196,41 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         info!("Micro batch worker started for SOL micro deposits");
     }
 
+    // Start purge worker for soft-deleted organizations
+    let org_purge_worker_handle = create_org_purge_worker(&config, &storage, cancel_token.clone());
+
+    // Start purge worker for accounts scheduled for deletion
+    let user_purge_worker_handle = create_user_purge_worker(&storage, cancel_token.clone());
+
+    // Start worker that forfeits expired promotional credit grants
+    let grant_expiration_worker_handle =
+        create_grant_expiration_worker(&storage, cancel_token.clone());
+
+    // Start worker that bills metered usage
+    let usage_billing_worker_handle = create_usage_billing_worker(&storage, cancel_token.clone());
+
+    // Start worker that charges recurring subscriptions
+    let subscription_renewal_worker_handle =
+        create_subscription_renewal_worker(&config, &storage, cancel_token.clone());
+
+    // Start worker that reconciles credit balances against the ledger
+    let credit_reconciliation_worker_handle =
+        create_credit_reconciliation_worker(&storage, cancel_token.clone());
+
+    // Start worker that signs checkpoints of the audit hash chain
+    let audit_checkpoint_worker_handle =
+        create_audit_checkpoint_worker(&config, &storage, cancel_token.clone());
+
+    // Start workers that stream the audit log to external collectors
+    let audit_sink_worker_handles =
+        create_audit_sink_workers(&config, &storage, cancel_token.clone())?;
+    if !audit_sink_worker_handles.is_empty() {
+        info!(
+            sinks = audit_sink_worker_handles.len(),
+            "Audit sink workers started"
+        );
+    }
+
     // Create router with storage backend
     let callback = Arc::new(NoopCallback);
     let app = router_with_storage(config, callback, storage);
@@ -229,6 +278,72 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Wait for org purge worker to finish current batch (with timeout)
+    if tokio::time::timeout(shutdown_timeout, org_purge_worker_handle)
+        .await
+        .is_err()
+    {
+        info!("Org purge worker shutdown timed out");
+    }
+
+    // Wait for user purge worker to finish current batch (with timeout)
+    if tokio::time::timeout(shutdown_timeout, user_purge_worker_handle)
+        .await
+        .is_err()
+    {
+        info!("User purge worker shutdown timed out");
+    }
+
+    // Wait for grant expiration worker to finish its run (with timeout)
+    if tokio::time::timeout(shutdown_timeout, grant_expiration_worker_handle)
+        .await
+        .is_err()
+    {
+        info!("Grant expiration worker shutdown timed out");
+    }
+
+    // Wait for usage billing worker to finish its run (with timeout)
+    if tokio::time::timeout(shutdown_timeout, usage_billing_worker_handle)
+        .await
+        .is_err()
+    {
+        info!("Usage billing worker shutdown timed out");
+    }
+
+    // Wait for subscription renewal worker to finish its run (with timeout)
+    if tokio::time::timeout(shutdown_timeout, subscription_renewal_worker_handle)
+        .await
+        .is_err()
+    {
+        info!("Subscription renewal worker shutdown timed out");
+    }
+
+    // Wait for credit reconciliation worker to finish its run (with timeout)
+    if tokio::time::timeout(shutdown_timeout, credit_reconciliation_worker_handle)
+        .await
+        .is_err()
+    {
+        info!("Credit reconciliation worker shutdown timed out");
+    }
+
+    // Wait for audit checkpoint worker to sign the final head (with timeout)
+    if tokio::time::timeout(shutdown_timeout, audit_checkpoint_worker_handle)
+        .await
+        .is_err()
+    {
+        info!("Audit checkpoint worker shutdown timed out");
+    }
+
+    // Wait for audit sink workers to finish their current batch (with timeout)
+    for handle in audit_sink_worker_handles {
+        if tokio::time::timeout(shutdown_timeout, handle)
+            .await
+            .is_err()
+        {
+            info!("Audit sink worker shutdown timed out");
+        }
+    }
+
     // Wait for cleanup task to finish gracefully (with timeout)
     if tokio::time::timeout(shutdown_timeout, cleanup_handle)
         .await
@@ -241,6 +356,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Verify the audit hash chain and print the report as JSON
+///
+/// Exits with status 1 when a broken link is found, so the command can run
+/// from cron or CI.
+async fn verify_audit(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let org_id = parse_verify_audit_args(args)?;
+
+    let config = Config::from_env()?;
+    if config.database.url.is_none() {
+        return Err("verify-audit requires DATABASE_URL".into());
+    }
+    let storage = Storage::from_config(&config.database).await?;
+
+    let service = AuditChainService::new(storage.audit_repo.clone(), &config.jwt.secret);
+    let report = service.verify(org_id).await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    if !report.valid {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn parse_verify_audit_args(args: &[String]) -> Result<Option<Uuid>, String> {
+    match args {
+        [] => Ok(None),
+        [flag, org_id] if flag == "--org" => Uuid::parse_str(org_id)
+            .map(Some)
+            .map_err(|_| format!("Invalid org ID: {}", org_id)),
+        _ => Err(VERIFY_AUDIT_USAGE.to_string()),
+    }
+}
+
 /// Create a future that resolves when a shutdown signal is received
 async fn shutdown_signal() {
     let ctrl_c = async {
@@ -283,7 +431,7 @@ async fn shutdown_signal() {
 
 #[cfg(test)]
 mod tests {
-    use super::is_production_like_environment;
+    use super::{is_production_like_environment, parse_verify_audit_args};
 
     #[test]
     fn production_like_environment_detection() {
@@ -292,4 +440,18 @@ mod tests {
         assert!(!is_production_like_environment("development"));
         assert!(!is_production_like_environment("test"));
     }
+
+    #[test]
+    fn verify_audit_args() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let org_id = uuid::Uuid::new_v4();
+
+        assert_eq!(parse_verify_audit_args(&[]), Ok(None));
+        assert_eq!(
+            parse_verify_audit_args(&args(&["--org", &org_id.to_string()])),
+            Ok(Some(org_id))
+        );
+        assert!(parse_verify_audit_args(&args(&["--org", "nope"])).is_err());
+        assert!(parse_verify_audit_args(&args(&["--bogus"])).is_err());
+    }
 }
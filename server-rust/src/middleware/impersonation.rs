@@ -0,0 +1,139 @@
+//! Impersonation guard middleware
+//!
+//! Requests carrying an impersonation token (an access token with an `act`
+//! claim) are:
+//!
+//! 1. Refused when they would write and the session is read-only. Only safe
+//!    methods and ending the impersonation are allowed.
+//! 2. Recorded as an `impersonation.request` audit event naming the
+//!    impersonator, the impersonated user, the method, path and status.
+//! 3. Run inside an impersonation scope, so every audit event the handler
+//!    writes is tagged with `impersonatedBy`.
+//!
+//! Tokens that fail validation pass through untouched; the handler rejects
+//! them as usual.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::callback::AuthCallback;
+use crate::errors::AppError;
+use crate::repositories::{AuditEventType, AuditLogBuilder, API_KEY_PREFIX};
+use crate::services::{
+    with_impersonation, AccessTokenClaims, ActorClaim, EmailService, ImpersonationTag,
+};
+use crate::utils::{extract_access_token, extract_client_ip};
+use crate::AppState;
+
+/// Path (relative to the auth base path) that ends an impersonation
+const IMPERSONATION_END_PATH: &str = "/impersonation/end";
+
+/// Enforce read-only impersonation and audit every impersonated request
+pub async fn impersonation_guard<C: AuthCallback, E: EmailService>(
+    State(state): State<Arc<AppState<C, E>>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let impersonation =
+        extract_access_token(req.headers(), &state.config.cookie.access_cookie_name)
+            .filter(|token| !token.starts_with(API_KEY_PREFIX))
+            .and_then(|token| state.jwt_service.validate_access_token(&token).ok())
+            .and_then(|claims| claims.act.clone().map(|act| (claims, act)));
+    let Some((claims, act)) = impersonation else {
+        return next.run(req).await;
+    };
+
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let headers = req.headers().clone();
+
+    let response = if !is_allowed(&act, &method, &path) {
+        AppError::Forbidden("Impersonation session is read-only".into()).into_response()
+    } else {
+        let tag = ImpersonationTag {
+            impersonator_id: act.sub,
+            session_id: claims.sid,
+        };
+        with_impersonation(tag, next.run(req)).await
+    };
+
+    let status = response.status().as_u16();
+    log_request(&state, &claims, &act, &method, &path, status, &headers).await;
+    response
+}
+
+/// Whether an impersonation session may make this request
+fn is_allowed(act: &ActorClaim, method: &Method, path: &str) -> bool {
+    act.write
+        || matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || path == IMPERSONATION_END_PATH
+}
+
+async fn log_request<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    claims: &AccessTokenClaims,
+    act: &ActorClaim,
+    method: &Method,
+    path: &str,
+    status: u16,
+    headers: &HeaderMap,
+) {
+    let mut builder = AuditLogBuilder::new(AuditEventType::ImpersonationRequest)
+        .actor(act.sub)
+        .session(claims.sid)
+        .target("user", claims.sub)
+        .metadata(serde_json::json!({
+            "method": method.as_str(),
+            "path": path,
+            "status": status,
+        }));
+    if let Some(org_id) = act.org_id.or(claims.org_id) {
+        builder = builder.org(org_id);
+    }
+    if let Some(ip) = extract_client_ip(headers, state.config.server.trust_proxy) {
+        builder = builder.ip(&ip);
+    }
+    if let Some(ua) = headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+    {
+        builder = builder.user_agent(ua);
+    }
+    state.audit_service.log_or_warn(builder.build()).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn act(write: bool) -> ActorClaim {
+        ActorClaim {
+            sub: Uuid::new_v4(),
+            write,
+            org_id: None,
+        }
+    }
+
+    #[test]
+    fn test_read_only_allows_safe_methods_and_end() {
+        let act = act(false);
+        assert!(is_allowed(&act, &Method::GET, "/user"));
+        assert!(is_allowed(&act, &Method::HEAD, "/user"));
+        assert!(is_allowed(&act, &Method::POST, IMPERSONATION_END_PATH));
+        assert!(!is_allowed(&act, &Method::POST, "/orgs"));
+        assert!(!is_allowed(&act, &Method::DELETE, "/sessions"));
+    }
+
+    #[test]
+    fn test_write_impersonation_allows_writes() {
+        let act = act(true);
+        assert!(is_allowed(&act, &Method::POST, "/orgs"));
+        assert!(is_allowed(&act, &Method::PATCH, "/orgs/1"));
+    }
+}
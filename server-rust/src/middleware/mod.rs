@@ -1,9 +1,13 @@
 //! Middleware for request processing
 
 pub mod csrf;
+pub mod impersonation;
+pub mod peer_ip;
 pub mod rate_limit;
 pub mod request_id;
 
 pub use csrf::CsrfLayer;
+pub use impersonation::impersonation_guard;
+pub use peer_ip::peer_ip_scope;
 pub use rate_limit::{KeyExtractor, RateLimitConfig, RateLimitLayer, RateLimitStore};
 pub use request_id::{RequestId, RequestIdLayer, REQUEST_ID_HEADER};
@@ -0,0 +1,15 @@
+//! Peer IP middleware
+//!
+//! Makes the socket peer address available to the rest of the request via
+//! [`current_peer_ip`](crate::utils::current_peer_ip), for code that only
+//! receives headers and would otherwise lose the client IP when
+//! `TRUST_PROXY` is off.
+
+use axum::{extract::Request, middleware::Next, response::Response};
+
+use crate::utils::{with_peer_ip, PeerIp};
+
+/// Run the request inside a scope carrying its socket peer address
+pub async fn peer_ip_scope(PeerIp(peer_ip): PeerIp, req: Request, next: Next) -> Response {
+    with_peer_ip(peer_ip, next.run(req)).await
+}
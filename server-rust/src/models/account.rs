@@ -0,0 +1,46 @@
+//! Self-service account deletion models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Request to delete the caller's own account
+///
+/// Each flag acknowledges something that would otherwise block deletion; the
+/// request is refused with an explanation when a needed flag is missing.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAccountRequest {
+    /// Delete organizations the caller is the only owner of
+    #[serde(default)]
+    pub delete_owned_orgs: bool,
+    /// Give up any remaining credit balance
+    #[serde(default)]
+    pub forfeit_credits: bool,
+    /// Destroy the embedded wallet's key material
+    #[serde(default)]
+    pub delete_wallet: bool,
+}
+
+/// Response for a scheduled account deletion
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAccountResponse {
+    pub message: String,
+    /// When the account's personal data will be permanently removed
+    pub purge_after: DateTime<Utc>,
+    /// Organizations scheduled for deletion along with the account
+    pub deleted_org_ids: Vec<uuid::Uuid>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_request_defaults_to_no_acknowledgements() {
+        let req: DeleteAccountRequest = serde_json::from_str("{}").unwrap();
+        assert!(!req.delete_owned_orgs);
+        assert!(!req.forfeit_credits);
+        assert!(!req.delete_wallet);
+    }
+}
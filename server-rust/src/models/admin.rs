@@ -14,6 +14,10 @@ pub struct ListUsersQueryParams {
     pub limit: u32,
     #[serde(default)]
     pub offset: u32,
+    /// Only list users in this account state (active, suspended, banned,
+    /// pending_deletion)
+    #[serde(default)]
+    pub status: Option<String>,
 }
 
 /// Query parameters for listing orgs
@@ -51,6 +55,12 @@ pub struct AdminUserResponse {
     pub updated_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_login_at: Option<DateTime<Utc>>,
+    /// Account state; a lapsed suspension or ban reads as `active`
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_expires_at: Option<DateTime<Utc>>,
     /// Credit balance in lamports (only present if credit system is enabled)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub balance_lamports: Option<i64>,
@@ -58,6 +68,13 @@ pub struct AdminUserResponse {
 
 impl From<&UserEntity> for AdminUserResponse {
     fn from(entity: &UserEntity) -> Self {
+        let status = entity.effective_status();
+        // Reason and expiry of a lapsed status are no longer meaningful
+        let (reason, expires_at) = if status == entity.status {
+            (entity.status_reason.clone(), entity.status_expires_at)
+        } else {
+            (None, None)
+        };
         Self {
             id: entity.id,
             email: entity.email.clone(),
@@ -71,6 +88,9 @@ impl From<&UserEntity> for AdminUserResponse {
             created_at: entity.created_at,
             updated_at: entity.updated_at,
             last_login_at: entity.last_login_at,
+            status: status.as_str().to_string(),
+            status_reason: reason,
+            status_expires_at: expires_at,
             balance_lamports: None, // Set separately via with_balance()
         }
     }
@@ -107,6 +127,12 @@ pub struct AdminOrgResponse {
     pub owner_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the organization is pending deletion
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// When a pending deletion becomes permanent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purge_after: Option<DateTime<Utc>>,
 }
 
 impl From<&OrgEntity> for AdminOrgResponse {
@@ -120,6 +146,8 @@ impl From<&OrgEntity> for AdminOrgResponse {
             owner_id: entity.owner_id,
             created_at: entity.created_at,
             updated_at: entity.updated_at,
+            deleted_at: entity.deleted_at,
+            purge_after: entity.purge_after,
         }
     }
 }
@@ -141,6 +169,19 @@ pub struct SetSystemAdminRequest {
     pub is_admin: bool,
 }
 
+/// Request to suspend, ban or reactivate a user
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetUserStatusRequest {
+    /// `active`, `suspended` or `banned`
+    pub status: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// When a suspension or ban lifts by itself; omit for indefinite
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
 /// Request to adjust a user's credit balance
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -16,6 +16,16 @@ pub struct ApiKeyResponse {
     pub created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_used_at: Option<DateTime<Utc>>,
+    /// Scopes the key is restricted to (empty = unrestricted)
+    pub scopes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_ips: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_minute: Option<u32>,
 }
 
 impl From<&ApiKeyEntity> for ApiKeyResponse {
@@ -26,6 +36,11 @@ impl From<&ApiKeyEntity> for ApiKeyResponse {
             label: entity.label.clone(),
             created_at: entity.created_at,
             last_used_at: entity.last_used_at,
+            scopes: entity.scopes.clone(),
+            org_id: entity.org_id,
+            expires_at: entity.expires_at,
+            allowed_ips: entity.allowed_ips.clone(),
+            rate_limit_per_minute: entity.rate_limit_per_minute,
         }
     }
 }
@@ -48,6 +63,21 @@ pub struct RegenerateApiKeyResponse {
 pub struct CreateApiKeyRequest {
     /// Human-readable label for the key (e.g., "default", "bot-alpha")
     pub label: String,
+    /// Restrict the key to these scopes (omit for an unrestricted key)
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Bind the key to one organization the user belongs to
+    #[serde(default)]
+    pub org_id: Option<Uuid>,
+    /// When the key stops working (omit for a non-expiring key)
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// IP addresses or CIDR blocks allowed to use the key
+    #[serde(default)]
+    pub allowed_ips: Vec<String>,
+    /// Maximum requests per minute for this key
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
 }
 
 /// Response when creating a new API key
@@ -60,6 +90,11 @@ pub struct CreateApiKeyResponse {
     pub key_prefix: String,
     pub label: String,
     pub created_at: DateTime<Utc>,
+    pub scopes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
     pub message: String,
 }
 
@@ -102,12 +137,27 @@ mod tests {
             label: "default".to_string(),
             created_at: Utc::now(),
             last_used_at: None,
+            scopes: vec!["credits:spend".to_string()],
+            org_id: None,
+            expires_at: None,
+            allowed_ips: Vec::new(),
+            rate_limit_per_minute: None,
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"keyPrefix\":\"ck_abc123\""));
         assert!(json.contains("\"label\":\"default\""));
+        assert!(json.contains("\"scopes\":[\"credits:spend\"]"));
         // Optional None fields should not be serialized
         assert!(!json.contains("lastUsedAt"));
+        assert!(!json.contains("allowedIps"));
+    }
+
+    #[test]
+    fn test_create_request_defaults() {
+        let request: CreateApiKeyRequest = serde_json::from_str(r#"{"label":"bot"}"#).unwrap();
+        assert!(request.scopes.is_empty());
+        assert!(request.org_id.is_none());
+        assert!(request.rate_limit_per_minute.is_none());
     }
 
     #[test]
@@ -14,6 +14,8 @@ pub struct AuditLogResponse {
     pub event_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub actor_user_id: Option<Uuid>,
+    /// `user` or `service_account`
+    pub actor_type: String,
     /// S-23: Session ID for forensic correlation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<Uuid>,
@@ -37,6 +39,7 @@ impl From<&AuditLogEntry> for AuditLogResponse {
             id: entry.id,
             event_type: entry.event_type.as_str().to_string(),
             actor_user_id: entry.actor_user_id,
+            actor_type: entry.actor_type.as_str().to_string(),
             session_id: entry.session_id,
             org_id: entry.org_id,
             target_type: entry.target_type.clone(),
@@ -97,3 +100,12 @@ impl AuditLogQueryParams {
         }
     }
 }
+
+/// Query parameters for audit chain verification
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyAuditChainParams {
+    /// Verify one org's chain instead of the global chain
+    #[serde(default)]
+    pub org_id: Option<Uuid>,
+}
@@ -4,7 +4,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::services::{CreditBalance, CreditHistory, CreditHistoryItem, HoldResult, SpendResult};
+use crate::services::{
+    CaptureResult, CreditBalance, CreditBucket, CreditHistory, CreditHistoryItem, HoldResult,
+    MeterUsage, SpendResult,
+};
 
 fn default_currency() -> String {
     "SOL".to_string()
@@ -32,6 +35,10 @@ pub struct SpendCreditsRequest {
     /// Optional metadata (items, SKUs, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+    /// Spend from this organization's balance instead of the user's own
+    /// (the user must be a member allowed to spend it)
+    #[serde(default)]
+    pub org_id: Option<Uuid>,
 }
 
 /// Response from spend operation
@@ -48,6 +55,9 @@ pub struct SpendCreditsResponse {
     pub currency: String,
     /// Human-readable display
     pub display: String,
+    /// Organization whose balance was spent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
 }
 
 impl SpendCreditsResponse {
@@ -59,6 +69,7 @@ impl SpendCreditsResponse {
             amount_lamports: result.amount_lamports,
             currency: currency.to_string(),
             display,
+            org_id: result.org_id,
         }
     }
 }
@@ -90,6 +101,9 @@ pub struct CreateHoldRequest {
     /// Optional metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+    /// Hold this organization's balance instead of the user's own
+    #[serde(default)]
+    pub org_id: Option<Uuid>,
 }
 
 fn default_ttl() -> i64 {
@@ -124,6 +138,38 @@ impl CreateHoldResponse {
     }
 }
 
+/// Request to capture a hold (body is optional)
+///
+/// Without a body the whole remaining amount is captured and the hold settled.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureHoldRequest {
+    /// Amount to capture (default: everything left on the hold)
+    #[serde(default)]
+    pub amount_lamports: Option<i64>,
+    /// Key for this capture; repeating it returns the original capture.
+    /// Required when `releaseRemainder` is false.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Settle the hold, releasing whatever is not captured (default: true)
+    #[serde(default = "default_release_remainder")]
+    pub release_remainder: bool,
+}
+
+fn default_release_remainder() -> bool {
+    true
+}
+
+impl Default for CaptureHoldRequest {
+    fn default() -> Self {
+        Self {
+            amount_lamports: None,
+            idempotency_key: None,
+            release_remainder: default_release_remainder(),
+        }
+    }
+}
+
 /// Response from capturing or releasing a hold
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -138,18 +184,87 @@ pub struct CaptureHoldResponse {
     pub currency: String,
     /// Human-readable display
     pub display: String,
+    /// Organization whose balance was spent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    /// Hold status after the capture ("pending" while more can be captured)
+    pub hold_status: String,
+    /// Total captured from the hold so far
+    pub captured_lamports: i64,
+    /// Amount still reserved on the hold (0 once settled)
+    pub remaining_lamports: i64,
+    /// Whether this repeats an earlier capture with the same key
+    pub replayed: bool,
 }
 
 impl CaptureHoldResponse {
     /// S-14: Currency is now included in SpendResult, avoiding a double-fetch.
-    pub fn from_result(result: SpendResult) -> Self {
-        let display = format_balance(result.new_balance_lamports, &result.currency);
+    pub fn from_result(result: CaptureResult) -> Self {
+        let CaptureResult {
+            spend,
+            hold,
+            replayed,
+        } = result;
+        let display = format_balance(spend.new_balance_lamports, &spend.currency);
+        let remaining = if hold.status == HoldStatus::Pending {
+            hold.remaining()
+        } else {
+            0
+        };
         Self {
-            transaction_id: result.transaction_id,
-            new_balance_lamports: result.new_balance_lamports,
-            amount_lamports: result.amount_lamports,
-            currency: result.currency,
+            transaction_id: spend.transaction_id,
+            new_balance_lamports: spend.new_balance_lamports,
+            amount_lamports: spend.amount_lamports,
+            currency: spend.currency,
             display,
+            org_id: spend.org_id,
+            hold_status: hold.status.as_str().to_string(),
+            captured_lamports: hold.captured_amount,
+            remaining_lamports: remaining,
+            replayed,
+        }
+    }
+}
+
+/// Request to increase and/or extend a pending hold
+///
+/// Values below the hold's current ones are ignored, so retries are safe.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtendHoldRequest {
+    /// New total amount authorized, including anything already captured
+    #[serde(default)]
+    pub amount_lamports: Option<i64>,
+    /// New expiry (at most 60 minutes from now)
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Response from extending a hold
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtendHoldResponse {
+    pub hold_id: Uuid,
+    /// Total amount authorized
+    pub amount_lamports: i64,
+    /// Total captured so far
+    pub captured_lamports: i64,
+    /// Amount still reserved
+    pub remaining_lamports: i64,
+    pub currency: String,
+    /// When the hold expires
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<CreditHoldEntity> for ExtendHoldResponse {
+    fn from(hold: CreditHoldEntity) -> Self {
+        Self {
+            hold_id: hold.id,
+            amount_lamports: hold.amount,
+            captured_lamports: hold.captured_amount,
+            remaining_lamports: hold.remaining(),
+            currency: hold.currency,
+            expires_at: hold.expires_at,
         }
     }
 }
@@ -164,6 +279,92 @@ pub struct ReleaseHoldResponse {
     pub message: String,
 }
 
+// ============================================================================
+// Usage Metering
+// ============================================================================
+
+/// One reported usage event
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageEventInput {
+    /// Meter name (e.g. "api_call")
+    pub meter: String,
+    /// User the usage is attributed to
+    pub user_id: Uuid,
+    /// Bill the organization's balance instead (the user must be a member)
+    pub org_id: Option<Uuid>,
+    /// Units used; must be positive
+    pub quantity: i64,
+    /// Unique per user; events with a key already used are ignored
+    pub idempotency_key: String,
+    /// When the usage happened (default: now); at most 31 days ago
+    pub occurred_at: Option<DateTime<Utc>>,
+}
+
+/// Request to report a batch of usage events (requires API key with system admin)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordUsageRequest {
+    /// Up to 1000 events
+    pub events: Vec<UsageEventInput>,
+}
+
+/// What happened to a reported usage event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageEventOutcome {
+    /// Stored; billed by the next usage billing run
+    Accepted,
+    /// Idempotency key already used; ignored
+    Duplicate,
+}
+
+/// Outcome of one reported event, in request order
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageEventResult {
+    pub idempotency_key: String,
+    pub status: UsageEventOutcome,
+}
+
+/// Response from reporting usage
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordUsageResponse {
+    pub accepted: usize,
+    pub duplicates: usize,
+    pub results: Vec<UsageEventResult>,
+}
+
+/// Usage of one meter
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeterUsageResponse {
+    /// Meter name (e.g. "api_call")
+    pub meter: String,
+    pub display_name: String,
+    pub unit: String,
+    /// Billed quantity
+    pub quantity: i64,
+    /// Total charged for the billed quantity
+    pub cost_lamports: i64,
+    /// Reported but not yet billed
+    pub pending_quantity: i64,
+}
+
+impl From<MeterUsage> for MeterUsageResponse {
+    fn from(usage: MeterUsage) -> Self {
+        Self {
+            meter: usage.meter.name,
+            display_name: usage.meter.display_name,
+            unit: usage.meter.unit,
+            quantity: usage.totals.billed_quantity,
+            cost_lamports: usage.totals.billed_cost,
+            pending_quantity: usage.totals.pending_quantity,
+        }
+    }
+}
+
 // ============================================================================
 // Refund Request Operations
 // ============================================================================
@@ -220,6 +421,8 @@ pub struct CreditUsageResponse {
     pub total_deposited_display: String,
     pub total_spent_display: String,
     pub current_balance_display: String,
+    /// Usage per meter in this currency (personal balance only)
+    pub meters: Vec<MeterUsageResponse>,
 }
 
 impl From<UserCreditStats> for CreditUsageResponse {
@@ -245,6 +448,7 @@ impl From<UserCreditStats> for CreditUsageResponse {
             total_spent_display: display(stats.total_spent, &stats.currency),
             current_balance_display: display(stats.current_balance, &stats.currency),
             currency: stats.currency,
+            meters: Vec::new(),
         }
     }
 }
@@ -277,6 +481,12 @@ fn format_balance(lamports: i64, currency: &str) -> String {
 pub struct CreditBalanceResponse {
     /// Balance in lamports
     pub balance_lamports: i64,
+    /// Part of the balance that is paid credit
+    pub paid_lamports: i64,
+    /// Part of the balance that is unspent promotional grants
+    pub promotional_lamports: i64,
+    /// Balance by bucket, in the order credits are spent
+    pub buckets: Vec<CreditBucketResponse>,
     /// Currency (e.g., "SOL")
     pub currency: String,
     /// Human-readable balance (e.g., "0.5000 SOL")
@@ -287,12 +497,40 @@ impl From<CreditBalance> for CreditBalanceResponse {
     fn from(balance: CreditBalance) -> Self {
         Self {
             balance_lamports: balance.balance_lamports,
+            paid_lamports: balance.paid_lamports,
+            promotional_lamports: balance.promotional_lamports,
+            buckets: balance.buckets.into_iter().map(Into::into).collect(),
             currency: balance.currency,
             display: balance.display,
         }
     }
 }
 
+/// Part of a balance by where the credit came from
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditBucketResponse {
+    /// "paid", or the grant source (e.g., "signup_bonus", "referral")
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grant_id: Option<Uuid>,
+    pub amount_lamports: i64,
+    /// When unspent grant credit expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<CreditBucket> for CreditBucketResponse {
+    fn from(bucket: CreditBucket) -> Self {
+        Self {
+            source: bucket.source,
+            grant_id: bucket.grant_id,
+            amount_lamports: bucket.amount_lamports,
+            expires_at: bucket.expires_at,
+        }
+    }
+}
+
 /// Multiple balances response
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -308,13 +546,22 @@ pub struct CreditTransactionResponse {
     /// Amount in lamports (positive = credit, negative = debit)
     pub amount_lamports: i64,
     pub currency: String,
-    /// Transaction type: "deposit", "spend", "adjustment"
+    /// Transaction type: "deposit", "spend", "adjustment", "grant",
+    /// "expiration", "transfer"
     pub tx_type: String,
     /// Human-readable description
     pub description: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deposit_session_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
+    /// Ledger the entry belongs to: "user" or "org"
+    pub owner_type: String,
+    /// Organization whose balance moved (org entries only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    /// Member or admin who made the transaction (org entries only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
 }
 
 impl From<CreditHistoryItem> for CreditTransactionResponse {
@@ -323,6 +570,10 @@ impl From<CreditHistoryItem> for CreditTransactionResponse {
             "deposit" => "Privacy Cash deposit".to_string(),
             "spend" => "Service usage".to_string(),
             "adjustment" => "Manual adjustment".to_string(),
+            "grant" => "Promotional credit".to_string(),
+            "expiration" => "Expired promotional credit".to_string(),
+            "transfer" if item.amount_lamports < 0 => "Credit transfer sent".to_string(),
+            "transfer" => "Credit transfer received".to_string(),
             _ => format!("Unknown ({})", item.tx_type),
         };
 
@@ -334,6 +585,9 @@ impl From<CreditHistoryItem> for CreditTransactionResponse {
             description,
             deposit_session_id: item.deposit_session_id,
             created_at: item.created_at,
+            user_id: item.org_id.map(|_| item.user_id),
+            org_id: item.org_id,
+            owner_type: item.owner_type,
         }
     }
 }
@@ -363,15 +617,19 @@ impl From<CreditHistory> for CreditHistoryResponse {
 // Pending Holds Models
 // ============================================================================
 
-use crate::repositories::CreditHoldEntity;
+use crate::repositories::{CreditHoldEntity, HoldStatus};
 
 /// A pending credit hold visible to the user
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PendingHoldResponse {
     pub hold_id: Uuid,
-    /// Amount held in lamports
+    /// Total amount authorized in lamports
     pub amount_lamports: i64,
+    /// Amount already captured by partial captures
+    pub captured_lamports: i64,
+    /// Amount still reserved in lamports
+    pub remaining_lamports: i64,
     pub currency: String,
     /// When the hold expires
     pub expires_at: DateTime<Utc>,
@@ -389,6 +647,8 @@ impl From<CreditHoldEntity> for PendingHoldResponse {
         Self {
             hold_id: hold.id,
             amount_lamports: hold.amount,
+            captured_lamports: hold.captured_amount,
+            remaining_lamports: hold.remaining(),
             currency: hold.currency,
             expires_at: hold.expires_at,
             reference_type: hold.reference_type,
@@ -403,10 +663,851 @@ impl From<CreditHoldEntity> for PendingHoldResponse {
 #[serde(rename_all = "camelCase")]
 pub struct PendingHoldsResponse {
     pub holds: Vec<PendingHoldResponse>,
-    /// Total amount held in lamports
+    /// Total amount still reserved in lamports
     pub total_held_lamports: i64,
 }
 
+// ============================================================================
+// Organization Credit Settings
+// ============================================================================
+
+use crate::repositories::OrgCreditMemberSettings;
+
+/// Request to set a member's spending rules for the org balance
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateOrgCreditMemberRequest {
+    /// Allow or forbid spending regardless of role (null = role default)
+    #[serde(default)]
+    pub spend_allowed: Option<bool>,
+    /// Maximum spend per calendar month (UTC) in lamports (null = no cap)
+    #[serde(default)]
+    pub monthly_cap_lamports: Option<i64>,
+}
+
+/// A member's spending rules for the org balance
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrgCreditMemberResponse {
+    pub user_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spend_allowed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monthly_cap_lamports: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<OrgCreditMemberSettings> for OrgCreditMemberResponse {
+    fn from(settings: OrgCreditMemberSettings) -> Self {
+        Self {
+            user_id: settings.user_id,
+            spend_allowed: settings.spend_allowed,
+            monthly_cap_lamports: settings.monthly_cap,
+            updated_by: settings.updated_by,
+            updated_at: settings.updated_at,
+        }
+    }
+}
+
+/// Response listing member spending rules for an organization
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrgCreditMembersResponse {
+    pub members: Vec<OrgCreditMemberResponse>,
+}
+
+// ============================================================================
+// Promotional Grants (Admin)
+// ============================================================================
+
+use crate::repositories::CreditGrantEntity;
+use crate::services::GrantResult;
+
+/// Request to grant promotional credit to a user
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCreditGrantRequest {
+    /// Amount in lamports (must be positive)
+    pub amount_lamports: i64,
+    /// Currency (default: "SOL")
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// "signup_bonus", "referral", "promotion" or "manual"
+    pub source: String,
+    /// Higher priority grants are spent first (default: 0)
+    #[serde(default)]
+    pub priority: i32,
+    /// When unspent credit is forfeited (default: never)
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Reason shown in the ledger
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Query parameters for listing a user's grants
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditGrantsQueryParams {
+    pub currency: Option<String>,
+    /// Only grants with unspent credit (default: false)
+    #[serde(default)]
+    pub active_only: bool,
+}
+
+/// Promotional credit grant
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditGrantResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub currency: String,
+    pub source: String,
+    pub amount_lamports: i64,
+    pub remaining_lamports: i64,
+    pub priority: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// "active", "depleted" or "expired"
+    pub status: String,
+    /// Transaction that added the grant to the balance
+    pub transaction_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<CreditGrantEntity> for CreditGrantResponse {
+    fn from(grant: CreditGrantEntity) -> Self {
+        Self {
+            id: grant.id,
+            user_id: grant.user_id,
+            currency: grant.currency,
+            source: grant.source.as_str().to_string(),
+            amount_lamports: grant.amount,
+            remaining_lamports: grant.remaining,
+            priority: grant.priority,
+            expires_at: grant.expires_at,
+            status: grant.status.as_str().to_string(),
+            transaction_id: grant.transaction_id,
+            reason: grant.reason,
+            created_by: grant.created_by,
+            created_at: grant.created_at,
+            updated_at: grant.updated_at,
+        }
+    }
+}
+
+/// Response from granting promotional credit
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCreditGrantResponse {
+    pub grant: CreditGrantResponse,
+    /// New balance after the grant
+    pub new_balance_lamports: i64,
+    /// Human-readable display
+    pub display: String,
+}
+
+impl From<GrantResult> for CreateCreditGrantResponse {
+    fn from(result: GrantResult) -> Self {
+        let display = format_balance(result.new_balance_lamports, &result.grant.currency);
+        Self {
+            grant: result.grant.into(),
+            new_balance_lamports: result.new_balance_lamports,
+            display,
+        }
+    }
+}
+
+/// A user's promotional credit grants
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditGrantsResponse {
+    pub grants: Vec<CreditGrantResponse>,
+}
+
+// ============================================================================
+// Credit Ledger (Admin)
+// ============================================================================
+
+use crate::repositories::CreditBalanceDrift;
+use crate::services::{LedgerAccountLine, LedgerReport, ReconciliationReport};
+
+/// Query parameters for the trial balance
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrialBalanceQueryParams {
+    /// Only report this currency
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// Report balances as of this instant (default: now)
+    #[serde(default)]
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for a ledger statement
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerStatementQueryParams {
+    /// Only report this currency
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// Start of the period (inclusive)
+    pub from: DateTime<Utc>,
+    /// End of the period (exclusive)
+    pub to: DateTime<Utc>,
+}
+
+/// One ledger account in a trial balance or statement
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerAccountLineResponse {
+    pub account: String,
+    /// "debit" or "credit": the side on which the account's balance grows
+    pub normal_side: String,
+    pub opening_balance: i64,
+    pub debits: i64,
+    pub credits: i64,
+    pub closing_balance: i64,
+}
+
+impl From<LedgerAccountLine> for LedgerAccountLineResponse {
+    fn from(line: LedgerAccountLine) -> Self {
+        Self {
+            account: line.account.as_str().to_string(),
+            normal_side: if line.account.is_debit_normal() {
+                "debit"
+            } else {
+                "credit"
+            }
+            .to_string(),
+            opening_balance: line.opening_balance,
+            debits: line.debits,
+            credits: line.credits,
+            closing_balance: line.closing_balance,
+        }
+    }
+}
+
+/// Ledger accounts for one currency
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerReportResponse {
+    pub currency: String,
+    pub accounts: Vec<LedgerAccountLineResponse>,
+    pub total_debits: i64,
+    pub total_credits: i64,
+    /// Whether total debits equal total credits
+    pub balanced: bool,
+}
+
+impl From<LedgerReport> for LedgerReportResponse {
+    fn from(report: LedgerReport) -> Self {
+        let balanced = report.is_balanced();
+        Self {
+            currency: report.currency,
+            accounts: report.lines.into_iter().map(Into::into).collect(),
+            total_debits: report.total_debits,
+            total_credits: report.total_credits,
+            balanced,
+        }
+    }
+}
+
+/// Trial balance response
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrialBalanceResponse {
+    pub as_of: DateTime<Utc>,
+    pub currencies: Vec<LedgerReportResponse>,
+}
+
+/// Ledger statement response
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerStatementResponse {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub currencies: Vec<LedgerReportResponse>,
+}
+
+/// A stored balance that disagrees with the ledger
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditBalanceDriftResponse {
+    /// "user" or "org"
+    pub owner_type: String,
+    pub owner_id: Uuid,
+    pub currency: String,
+    pub stored_balance: i64,
+    pub ledger_balance: i64,
+    /// Stored minus ledger balance
+    pub drift: i64,
+}
+
+impl From<CreditBalanceDrift> for CreditBalanceDriftResponse {
+    fn from(drift: CreditBalanceDrift) -> Self {
+        Self {
+            owner_type: drift.owner_type.as_str().to_string(),
+            owner_id: drift.owner_id,
+            drift: drift.drift(),
+            currency: drift.currency,
+            stored_balance: drift.stored_balance,
+            ledger_balance: drift.ledger_balance,
+        }
+    }
+}
+
+/// Result of reconciling balances against the ledger
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditReconciliationResponse {
+    pub checked_at: DateTime<Utc>,
+    /// True when no drift, unposted transaction or unbalanced currency was found
+    pub clean: bool,
+    pub balances_checked: u64,
+    pub drift: Vec<CreditBalanceDriftResponse>,
+    pub unposted_transactions: u64,
+    pub unbalanced_currencies: Vec<String>,
+}
+
+impl From<ReconciliationReport> for CreditReconciliationResponse {
+    fn from(report: ReconciliationReport) -> Self {
+        Self {
+            checked_at: report.checked_at,
+            clean: report.is_clean(),
+            balances_checked: report.balances_checked,
+            drift: report.drift.into_iter().map(Into::into).collect(),
+            unposted_transactions: report.unposted_transactions,
+            unbalanced_currencies: report.unbalanced_currencies,
+        }
+    }
+}
+
+// ============================================================================
+// Subscriptions
+// ============================================================================
+
+use crate::repositories::{PlanInterval, SubscriptionEntity, SubscriptionPlanEntity};
+
+fn default_true() -> bool {
+    true
+}
+
+/// Subscription plan
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionPlanResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub display_name: String,
+    pub price_lamports: i64,
+    pub currency: String,
+    pub interval: PlanInterval,
+    pub interval_count: i32,
+    pub trial_days: i32,
+    pub grace_days: i32,
+    pub entitlements: Vec<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<SubscriptionPlanEntity> for SubscriptionPlanResponse {
+    fn from(plan: SubscriptionPlanEntity) -> Self {
+        Self {
+            id: plan.id,
+            name: plan.name,
+            display_name: plan.display_name,
+            price_lamports: plan.price_lamports,
+            currency: plan.currency,
+            interval: plan.interval,
+            interval_count: plan.interval_count,
+            trial_days: plan.trial_days,
+            grace_days: plan.grace_days,
+            entitlements: plan.entitlements,
+            active: plan.active,
+            created_at: plan.created_at,
+            updated_at: plan.updated_at,
+        }
+    }
+}
+
+/// Subscription plan catalog
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionPlansResponse {
+    pub plans: Vec<SubscriptionPlanResponse>,
+}
+
+/// Request to subscribe to a plan
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubscriptionRequest {
+    pub plan_id: Uuid,
+    /// Subscribe an organization, paid from its balance (requires `credits:manage`)
+    #[serde(default)]
+    pub org_id: Option<Uuid>,
+}
+
+/// Request to cancel a subscription
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelSubscriptionRequest {
+    /// Keep the subscription until the paid period ends (default: true)
+    #[serde(default = "default_true")]
+    pub at_period_end: bool,
+}
+
+/// A user's or organization's subscription
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionResponse {
+    pub id: Uuid,
+    pub plan: SubscriptionPlanResponse,
+    pub user_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    /// "trialing", "active", "past_due" or "canceled"
+    pub status: String,
+    pub current_period_start: DateTime<Utc>,
+    pub current_period_end: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial_end: Option<DateTime<Utc>>,
+    pub cancel_at_period_end: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub past_due_since: Option<DateTime<Utc>>,
+    pub dunning_attempts: i32,
+    /// When the next charge (or retry) is attempted; absent once canceled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canceled_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cancel_reason: Option<String>,
+    /// Whether the plan's entitlements are currently granted
+    pub entitled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SubscriptionResponse {
+    pub fn new(subscription: SubscriptionEntity, plan: SubscriptionPlanEntity) -> Self {
+        let entitled = subscription.is_entitled(plan.grace_days, Utc::now());
+        let canceled = subscription.canceled_at.is_some();
+        Self {
+            id: subscription.id,
+            plan: plan.into(),
+            user_id: subscription.user_id,
+            org_id: subscription.org_id,
+            status: subscription.status.as_str().to_string(),
+            current_period_start: subscription.current_period_start,
+            current_period_end: subscription.current_period_end,
+            trial_end: subscription.trial_end,
+            cancel_at_period_end: subscription.cancel_at_period_end,
+            past_due_since: subscription.past_due_since,
+            dunning_attempts: subscription.dunning_attempts,
+            next_attempt_at: (!canceled).then_some(subscription.next_attempt_at),
+            canceled_at: subscription.canceled_at,
+            cancel_reason: subscription.cancel_reason,
+            entitled,
+            created_at: subscription.created_at,
+        }
+    }
+}
+
+/// Subscriptions of a user or organization, newest first
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionsResponse {
+    pub subscriptions: Vec<SubscriptionResponse>,
+    /// Entitlements currently granted by these subscriptions
+    pub entitlements: Vec<String>,
+}
+
+// ============================================================================
+// Stripe top-ups
+// ============================================================================
+
+use crate::repositories::{StripeTopupEntity, StripeTopupFlow};
+
+/// Request to top up a balance by card through Stripe
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateStripeTopupRequest {
+    /// Amount to pay in the payment currency's minor unit (cents)
+    pub amount_cents: i64,
+    /// Credit an organization's balance (requires `credits:manage`)
+    #[serde(default)]
+    pub org_id: Option<Uuid>,
+    /// "checkout" (hosted page, default) or "payment_intent" (Stripe Elements)
+    #[serde(default)]
+    pub flow: StripeTopupFlow,
+}
+
+/// A Stripe top-up
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StripeTopupResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    pub flow: StripeTopupFlow,
+    pub amount_cents: i64,
+    pub currency: String,
+    /// Credits added once paid (lamports)
+    pub credit_amount: i64,
+    pub credit_currency: String,
+    /// "pending", "succeeded" or "failed"
+    pub status: String,
+    pub refunded_cents: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dispute_status: Option<String>,
+    /// Credits taken back by refunds and disputes
+    pub reversed_amount: i64,
+    /// Stripe-hosted payment page (checkout flow, on creation only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkout_url: Option<String>,
+    /// Secret to confirm the PaymentIntent with (on creation only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<StripeTopupEntity> for StripeTopupResponse {
+    fn from(topup: StripeTopupEntity) -> Self {
+        Self {
+            id: topup.id,
+            user_id: topup.user_id,
+            org_id: topup.org_id,
+            flow: topup.flow,
+            amount_cents: topup.amount_cents,
+            currency: topup.currency,
+            credit_amount: topup.credit_amount,
+            credit_currency: topup.credit_currency,
+            status: topup.status.as_str().to_string(),
+            refunded_cents: topup.refunded_cents,
+            dispute_status: topup.dispute_status,
+            reversed_amount: topup.reversed_amount,
+            checkout_url: None,
+            client_secret: None,
+            created_at: topup.created_at,
+            completed_at: topup.completed_at,
+        }
+    }
+}
+
+/// Stripe top-ups of a user or organization, newest first
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StripeTopupsResponse {
+    pub topups: Vec<StripeTopupResponse>,
+}
+
+// =============================================================================
+// Credit Transfers
+// =============================================================================
+
+use crate::services::{CreditAccount, CreditTransfer};
+
+/// Request to transfer credits to a user or organization
+///
+/// Exactly one of `toUserId` and `toOrgId` must be set.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCreditTransferRequest {
+    /// Amount in lamports (must be positive)
+    pub amount_lamports: i64,
+    /// Currency (default: "SOL")
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// Idempotency key; repeating it returns the original transfer
+    pub idempotency_key: String,
+    #[serde(default)]
+    pub to_user_id: Option<Uuid>,
+    /// Organization to credit (the sender must be a member)
+    #[serde(default)]
+    pub to_org_id: Option<Uuid>,
+    /// Send from this organization's balance (requires `credits:manage`)
+    #[serde(default)]
+    pub from_org_id: Option<Uuid>,
+    /// Message shown to the recipient
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// A credit transfer
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditTransferResponse {
+    pub id: Uuid,
+    /// The sender's transaction
+    pub transaction_id: Uuid,
+    pub sender_id: Uuid,
+    /// Balance debited: "user" or "org"
+    pub from_type: String,
+    pub from_id: Uuid,
+    /// Balance credited: "user" or "org"
+    pub to_type: String,
+    pub to_id: Uuid,
+    pub amount_lamports: i64,
+    pub currency: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// The debited balance after the transfer (new transfers only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_balance_lamports: Option<i64>,
+    /// False when the idempotency key returned an earlier transfer
+    pub is_new: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CreditTransferResponse {
+    pub fn from_transfer(transfer: CreditTransfer, new_balance: Option<i64>) -> Self {
+        let account = |a: CreditAccount| (a.owner_type().to_string(), a.id());
+        let (from_type, from_id) = account(transfer.from);
+        let (to_type, to_id) = account(transfer.to);
+        Self {
+            id: transfer.id,
+            transaction_id: transfer.transaction_id,
+            sender_id: transfer.sender_id,
+            from_type,
+            from_id,
+            to_type,
+            to_id,
+            amount_lamports: transfer.amount,
+            currency: transfer.currency,
+            note: transfer.note,
+            new_balance_lamports: new_balance,
+            is_new: new_balance.is_some(),
+            created_at: transfer.created_at,
+        }
+    }
+}
+
+// =============================================================================
+// Credit Statements
+// =============================================================================
+
+use crate::repositories::CreditInvoiceEntity;
+use crate::services::{CreditStatement, StatementLine, StatementSpendGroup};
+
+/// Output format of a statement download
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementFormat {
+    #[default]
+    Json,
+    Csv,
+    Html,
+}
+
+/// Statement period and balance
+///
+/// Give either `month` (`YYYY-MM`, UTC) or both `from` and `to`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditStatementRequest {
+    /// Currency (default: "SOL")
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// Organization balance to report instead of the personal one
+    #[serde(default)]
+    pub org_id: Option<Uuid>,
+    /// Calendar month, e.g. "2026-09"
+    #[serde(default)]
+    pub month: Option<String>,
+    /// Start of the period (inclusive)
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// End of the period (exclusive)
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for downloading a statement
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditStatementQueryParams {
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    #[serde(default)]
+    pub org_id: Option<Uuid>,
+    #[serde(default)]
+    pub month: Option<String>,
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+    /// "json" (default), "csv" or "html"
+    #[serde(default)]
+    pub format: StatementFormat,
+}
+
+impl From<CreditStatementQueryParams> for CreditStatementRequest {
+    fn from(params: CreditStatementQueryParams) -> Self {
+        Self {
+            currency: params.currency,
+            org_id: params.org_id,
+            month: params.month,
+            from: params.from,
+            to: params.to,
+        }
+    }
+}
+
+/// Spends sharing a reference type
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementSpendGroupResponse {
+    pub reference_type: String,
+    pub count: u64,
+    pub amount_lamports: i64,
+}
+
+impl From<StatementSpendGroup> for StatementSpendGroupResponse {
+    fn from(group: StatementSpendGroup) -> Self {
+        Self {
+            reference_type: group.reference_type,
+            count: group.count,
+            amount_lamports: group.amount,
+        }
+    }
+}
+
+/// Invoice for a deposit
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditInvoiceResponse {
+    /// Sequential invoice number, e.g. "INV-000042"
+    pub invoice_number: String,
+    pub transaction_id: Uuid,
+    pub amount_lamports: i64,
+    pub currency: String,
+    pub deposited_at: DateTime<Utc>,
+    pub issued_at: DateTime<Utc>,
+}
+
+impl From<CreditInvoiceEntity> for CreditInvoiceResponse {
+    fn from(invoice: CreditInvoiceEntity) -> Self {
+        Self {
+            invoice_number: invoice.invoice_number(),
+            transaction_id: invoice.transaction_id,
+            amount_lamports: invoice.amount,
+            currency: invoice.currency,
+            deposited_at: invoice.deposited_at,
+            issued_at: invoice.issued_at,
+        }
+    }
+}
+
+/// One transaction on a statement
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementLineResponse {
+    pub transaction_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    /// "deposit", "deposit_reversal", "spend", "refund", "grant",
+    /// "expiration", "adjustment", "transfer_in" or "transfer_out"
+    pub category: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invoice_number: Option<String>,
+    pub amount_lamports: i64,
+    pub balance_after_lamports: i64,
+}
+
+impl From<StatementLine> for StatementLineResponse {
+    fn from(line: StatementLine) -> Self {
+        Self {
+            transaction_id: line.transaction.id,
+            created_at: line.transaction.created_at,
+            category: line.category.as_str().to_string(),
+            reference_type: line.transaction.reference_type,
+            reference_id: line.transaction.reference_id,
+            invoice_number: line.invoice_number,
+            amount_lamports: line.transaction.amount,
+            balance_after_lamports: line.balance_after,
+        }
+    }
+}
+
+/// A balance's statement for a period
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditStatementResponse {
+    /// "user" or "org"
+    pub owner_type: String,
+    pub owner_id: Uuid,
+    pub currency: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub opening_balance_lamports: i64,
+    pub deposits_lamports: i64,
+    pub deposit_reversals_lamports: i64,
+    /// Spends grouped by reference type
+    pub spends: Vec<StatementSpendGroupResponse>,
+    pub total_spent_lamports: i64,
+    pub refunds_lamports: i64,
+    pub grants_lamports: i64,
+    pub expirations_lamports: i64,
+    /// Net of manual adjustments (may be negative)
+    pub adjustments_lamports: i64,
+    pub transfers_in_lamports: i64,
+    pub transfers_out_lamports: i64,
+    pub closing_balance_lamports: i64,
+    pub invoices: Vec<CreditInvoiceResponse>,
+    pub transactions: Vec<StatementLineResponse>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl From<CreditStatement> for CreditStatementResponse {
+    fn from(statement: CreditStatement) -> Self {
+        Self {
+            owner_type: statement.account.owner_type().to_string(),
+            owner_id: statement.account.id(),
+            currency: statement.currency,
+            from: statement.from,
+            to: statement.to,
+            opening_balance_lamports: statement.opening_balance,
+            deposits_lamports: statement.deposits,
+            deposit_reversals_lamports: statement.deposit_reversals,
+            spends: statement.spends.into_iter().map(Into::into).collect(),
+            total_spent_lamports: statement.total_spent,
+            refunds_lamports: statement.refunds,
+            grants_lamports: statement.grants,
+            expirations_lamports: statement.expirations,
+            adjustments_lamports: statement.adjustments,
+            transfers_in_lamports: statement.transfers_in,
+            transfers_out_lamports: statement.transfers_out,
+            closing_balance_lamports: statement.closing_balance,
+            invoices: statement.invoices.into_iter().map(Into::into).collect(),
+            transactions: statement.lines.into_iter().map(Into::into).collect(),
+            generated_at: statement.generated_at,
+        }
+    }
+}
+
+/// Response after queueing a statement email
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailCreditStatementResponse {
+    pub queued: bool,
+    /// Address the statement is sent to
+    pub email: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -415,6 +1516,9 @@ mod tests {
     fn test_balance_response_serialization() {
         let response = CreditBalanceResponse {
             balance_lamports: 1_000_000_000,
+            paid_lamports: 1_000_000_000,
+            promotional_lamports: 0,
+            buckets: vec![],
             currency: "SOL".to_string(),
             display: "1.0000 SOL".to_string(),
         };
@@ -434,9 +1538,35 @@ mod tests {
             description: "Privacy Cash deposit".to_string(),
             deposit_session_id: Some(Uuid::nil()),
             created_at: Utc::now(),
+            owner_type: "user".to_string(),
+            org_id: None,
+            user_id: None,
         };
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"txType\":\"deposit\""));
         assert!(json.contains("\"description\":\"Privacy Cash deposit\""));
+        assert!(json.contains("\"ownerType\":\"user\""));
+        assert!(!json.contains("orgId"));
+    }
+
+    #[test]
+    fn test_spend_request_accepts_org_id() {
+        let request: SpendCreditsRequest = serde_json::from_value(serde_json::json!({
+            "amountLamports": 100,
+            "idempotencyKey": "k",
+            "referenceType": "order",
+            "referenceId": Uuid::nil(),
+            "orgId": Uuid::nil(),
+        }))
+        .unwrap();
+        assert_eq!(request.org_id, Some(Uuid::nil()));
+        assert_eq!(request.currency, "SOL");
+    }
+
+    #[test]
+    fn test_cancel_subscription_defaults_to_period_end() {
+        let request: CancelSubscriptionRequest =
+            serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(request.at_period_end);
     }
 }
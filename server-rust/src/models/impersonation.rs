@@ -0,0 +1,53 @@
+//! Impersonation models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Request to start an impersonation session
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartImpersonationRequest {
+    /// Allow non-read requests (read-only by default)
+    #[serde(default)]
+    pub allow_writes: bool,
+    /// Support ticket or other justification, recorded in the audit log
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Impersonation session response
+///
+/// The access token is only returned in the body so it never replaces the
+/// impersonator's own session cookie. No refresh token is issued.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpersonationResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    pub expires_at: DateTime<Utc>,
+    pub session_id: Uuid,
+    pub user_id: Uuid,
+    pub impersonator_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    pub read_only: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_request_defaults_to_read_only() {
+        let req: StartImpersonationRequest = serde_json::from_str("{}").unwrap();
+        assert!(!req.allow_writes);
+        assert!(req.reason.is_none());
+
+        let req: StartImpersonationRequest =
+            serde_json::from_str(r#"{"allowWrites":true,"reason":"TICKET-42"}"#).unwrap();
+        assert!(req.allow_writes);
+        assert_eq!(req.reason.as_deref(), Some("TICKET-42"));
+    }
+}
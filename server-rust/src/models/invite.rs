@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::repositories::InviteEntity;
+use crate::repositories::{InviteEntity, InviteLinkEntity};
 
 /// Invite response (excludes token_hash for security)
 #[derive(Debug, Clone, Serialize)]
@@ -110,3 +110,140 @@ pub struct InviteWithTokenResponse {
     /// The invite token (only shown once)
     pub token: String,
 }
+
+/// Create invite link request
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInviteLinkRequest {
+    #[serde(default = "default_role")]
+    pub role: String,
+    /// Maximum number of redemptions (omit for unlimited until expiry)
+    #[serde(default)]
+    pub max_uses: Option<u32>,
+    /// Lifetime in hours (defaults to the standard invite expiry)
+    #[serde(default)]
+    pub expires_in_hours: Option<u32>,
+    /// Restrict redemption to verified emails at these domains
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+}
+
+/// Invite link response (excludes token_hash for security)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteLinkResponse {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u32>,
+    pub use_count: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_domains: Vec<String>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Whether the link can still be redeemed
+    pub active: bool,
+}
+
+impl InviteLinkResponse {
+    /// Create from entity
+    pub fn from_entity(link: &InviteLinkEntity) -> Self {
+        Self {
+            id: link.id,
+            org_id: link.org_id,
+            role: link.role.as_str().to_string(),
+            max_uses: link.max_uses,
+            use_count: link.use_count,
+            allowed_domains: link.allowed_domains.clone(),
+            created_by: link.created_by,
+            created_at: link.created_at,
+            expires_at: link.expires_at,
+            revoked_at: link.revoked_at,
+            active: link.is_usable(),
+        }
+    }
+}
+
+/// Invite link with token response (only returned on create)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteLinkWithTokenResponse {
+    #[serde(flatten)]
+    pub link: InviteLinkResponse,
+    /// The link token (only shown once)
+    pub token: String,
+}
+
+/// List invite links response
+#[derive(Debug, Clone, Serialize)]
+pub struct ListInviteLinksResponse {
+    pub links: Vec<InviteLinkResponse>,
+    pub total: u64,
+}
+
+/// One recipient in a JSON bulk invite request
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkInviteEntry {
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub wallet_address: Option<String>,
+    /// Overrides the request-level default role
+    #[serde(default)]
+    pub role: Option<String>,
+}
+
+/// JSON bulk invite request (CSV bodies are converted to the same entries)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkInviteRequest {
+    pub invites: Vec<BulkInviteEntry>,
+    #[serde(default = "default_role")]
+    pub default_role: String,
+}
+
+/// Outcome of a single bulk invite row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkInviteStatus {
+    /// Invite created
+    Invited,
+    /// Skipped: already a member, pending invite, or duplicate row
+    Skipped,
+    /// Row could not be processed
+    Error,
+}
+
+/// Per-row result of a bulk invite
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkInviteRowResult {
+    /// 1-based row number in the submitted list (excluding any CSV header)
+    pub row: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_address: Option<String>,
+    pub status: BulkInviteStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invite_id: Option<Uuid>,
+    /// Invite token (only for created wallet invites; email invites are delivered by email)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// Bulk invite response
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkInviteResponse {
+    pub invited: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub results: Vec<BulkInviteRowResult>,
+}
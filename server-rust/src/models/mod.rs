@@ -3,49 +3,74 @@
 // SEC-06: Import zeroize for sensitive data structs
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+mod account;
 mod admin;
 mod api_key;
 mod audit;
 mod credit;
 mod deposit;
+mod impersonation;
 mod invite;
 mod org;
+mod service_account;
 mod session;
 pub mod sso;
 mod wallet;
 
+pub use account::{DeleteAccountRequest, DeleteAccountResponse};
 pub use admin::{
     AdjustCreditsRequest, AdjustCreditsResponse, AdminCreditRefundRequestResponse,
     AdminOrgResponse, AdminUpdateUserRequest, AdminUserResponse, ListAdminOrgsResponse,
     ListCreditRefundRequestsQueryParams, ListCreditRefundRequestsResponse, ListOrgsQueryParams,
     ListUsersQueryParams, ListUsersResponse, ProcessCreditRefundRequestInput,
     ProcessCreditRefundRequestResponse, RejectCreditRefundRequestInput,
-    RejectCreditRefundRequestResponse, SetSystemAdminRequest,
+    RejectCreditRefundRequestResponse, SetSystemAdminRequest, SetUserStatusRequest,
 };
 pub use api_key::{
     ApiKeyListResponse, ApiKeyResponse, CreateApiKeyRequest, CreateApiKeyResponse,
     RegenerateApiKeyResponse, ValidateApiKeyRequest, ValidateApiKeyResponse,
 };
-pub use audit::{AuditLogQueryParams, AuditLogResponse, ListAuditLogsResponse};
+pub use audit::{
+    AuditLogQueryParams, AuditLogResponse, ListAuditLogsResponse, VerifyAuditChainParams,
+};
 pub use credit::{
-    BalancesResponse, CaptureHoldResponse, CreateHoldRequest, CreateHoldResponse,
-    CreditBalanceResponse, CreditHistoryResponse, CreditTransactionResponse, CreditUsageResponse,
-    PendingHoldResponse, PendingHoldsResponse, RefundRequestInput, RefundRequestResponse,
-    ReleaseHoldResponse, SpendCreditsRequest, SpendCreditsResponse,
+    BalancesResponse, CancelSubscriptionRequest, CaptureHoldRequest, CaptureHoldResponse,
+    CreateCreditGrantRequest, CreateCreditGrantResponse, CreateCreditTransferRequest,
+    CreateHoldRequest, CreateHoldResponse, CreateStripeTopupRequest, CreateSubscriptionRequest,
+    CreditBalanceResponse, CreditBucketResponse, CreditGrantResponse, CreditGrantsQueryParams,
+    CreditGrantsResponse, CreditHistoryResponse, CreditInvoiceResponse,
+    CreditReconciliationResponse, CreditStatementQueryParams, CreditStatementRequest,
+    CreditStatementResponse, CreditTransactionResponse, CreditTransferResponse,
+    CreditUsageResponse, EmailCreditStatementResponse, ExtendHoldRequest, ExtendHoldResponse,
+    LedgerStatementQueryParams, LedgerStatementResponse, MeterUsageResponse,
+    OrgCreditMemberResponse, OrgCreditMembersResponse, PendingHoldResponse, PendingHoldsResponse,
+    RecordUsageRequest, RecordUsageResponse, RefundRequestInput, RefundRequestResponse,
+    ReleaseHoldResponse, SpendCreditsRequest, SpendCreditsResponse, StatementFormat,
+    StripeTopupResponse, StripeTopupsResponse, SubscriptionPlanResponse, SubscriptionPlansResponse,
+    SubscriptionResponse, SubscriptionsResponse, TrialBalanceQueryParams, TrialBalanceResponse,
+    UpdateOrgCreditMemberRequest, UsageEventInput, UsageEventOutcome, UsageEventResult,
 };
 pub use deposit::{
     ConfirmSplDepositRequest, ConfirmSplDepositResponse, CustomTokenDefinition,
     DepositConfigResponse, DepositItemResponse, DepositListResponse, DepositQuoteResponse,
     DepositStatusResponse, PendingSplDepositItemResponse, PendingSplDepositListResponse,
 };
+pub use impersonation::{ImpersonationResponse, StartImpersonationRequest};
 pub use invite::{
-    AcceptInviteRequest, AcceptInviteResponse, CreateInviteRequest, InviteResponse,
-    InviteWithTokenResponse, ListInvitesResponse,
+    AcceptInviteRequest, AcceptInviteResponse, BulkInviteEntry, BulkInviteRequest,
+    BulkInviteResponse, BulkInviteRowResult, BulkInviteStatus, CreateInviteLinkRequest,
+    CreateInviteRequest, InviteLinkResponse, InviteLinkWithTokenResponse, InviteResponse,
+    InviteWithTokenResponse, ListInviteLinksResponse, ListInvitesResponse,
 };
 pub use org::{
     CreateOrgRequest, ListMembersResponse, ListOrgsResponse, MemberResponse, OrgResponse,
     UpdateMemberRoleRequest, UpdateOrgRequest,
 };
+pub use service_account::{
+    CreateServiceAccountRequest, ListServiceAccountsResponse, ServiceAccountResponse,
+    ServiceAccountTokenRequest, ServiceAccountTokenResponse, ServiceAccountWithSecretResponse,
+    UpdateServiceAccountRequest,
+};
 pub use session::{ListSessionsResponse, RevokeAllSessionsResponse, SessionResponse};
 pub use wallet::{
     AcknowledgeRecoveryRequest, AllWalletsListResponse, CreateDerivedWalletRequest,
@@ -109,6 +134,12 @@ pub struct AuthUser {
     pub email_verified: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Host-app data set by admins or API keys (read-only for the user)
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub public_metadata: serde_json::Map<String, serde_json::Value>,
+    /// Host-app data the user can write via `PATCH /me`
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub unsafe_metadata: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Token pair
@@ -310,6 +341,10 @@ pub struct AuthorizeResponse {
     /// Whether RBAC fallback was used
     #[serde(skip_serializing_if = "Option::is_none")]
     pub used_rbac_fallback: Option<bool>,
+    /// Entitlements granted by the user's and the org's subscriptions
+    /// (only when credits are enabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entitlements: Option<Vec<String>>,
 }
 
 /// Get permissions request
@@ -388,6 +423,8 @@ mod tests {
             email_verified: true,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            public_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
 
         let json = serde_json::to_string(&user).unwrap();
@@ -532,6 +569,8 @@ mod tests {
             email_verified: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            public_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
 
         let json = serde_json::to_string(&user).unwrap();
@@ -19,6 +19,12 @@ pub struct OrgResponse {
     pub role: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the organization is pending deletion
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// When a pending deletion becomes permanent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purge_after: Option<DateTime<Utc>>,
 }
 
 impl OrgResponse {
@@ -33,6 +39,8 @@ impl OrgResponse {
             role: role.as_str().to_string(),
             created_at: org.created_at,
             updated_at: org.updated_at,
+            deleted_at: org.deleted_at,
+            purge_after: org.purge_after,
         }
     }
 }
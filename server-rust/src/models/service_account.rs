@@ -0,0 +1,159 @@
+//! Service account models
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::repositories::{OrgRole, ServiceAccountEntity};
+
+/// Service account response (excludes the client secret hash)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountResponse {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Org role held through the service account's membership
+    pub role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_role_id: Option<Uuid>,
+    pub client_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ServiceAccountResponse {
+    /// Create from entity and the role of its membership
+    pub fn from_entity(account: &ServiceAccountEntity, role: OrgRole) -> Self {
+        Self {
+            id: account.id,
+            org_id: account.org_id,
+            name: account.name.clone(),
+            description: account.description.clone(),
+            role: role.as_str().to_string(),
+            custom_role_id: account.custom_role_id,
+            client_id: account.client_id.clone(),
+            created_by: account.created_by,
+            created_at: account.created_at,
+            updated_at: account.updated_at,
+            last_used_at: account.last_used_at,
+        }
+    }
+}
+
+/// Service account response including the client secret (only on create/rotate)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountWithSecretResponse {
+    pub service_account: ServiceAccountResponse,
+    /// The client secret - only returned once
+    pub client_secret: String,
+    pub message: String,
+}
+
+/// Request to create a service account
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateServiceAccountRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Org role for the service account (admin or member)
+    #[serde(default = "default_role")]
+    pub role: String,
+    /// Custom role granting additional permissions
+    #[serde(default)]
+    pub custom_role_id: Option<Uuid>,
+}
+
+fn default_role() -> String {
+    "member".to_string()
+}
+
+/// Request to update a service account
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateServiceAccountRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Custom role to assign; `null` leaves it unchanged (see `clear_custom_role`)
+    #[serde(default)]
+    pub custom_role_id: Option<Uuid>,
+    /// Remove the custom role assignment
+    #[serde(default)]
+    pub clear_custom_role: bool,
+}
+
+/// Response listing service accounts
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListServiceAccountsResponse {
+    pub service_accounts: Vec<ServiceAccountResponse>,
+    pub total: u64,
+}
+
+/// Client-credentials token request
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountTokenRequest {
+    /// Must be `client_credentials` when provided
+    #[serde(default)]
+    pub grant_type: Option<String>,
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+/// Client-credentials token response
+///
+/// No refresh token is issued; clients exchange their credentials again.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountTokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_defaults_to_member() {
+        let req: CreateServiceAccountRequest = serde_json::from_str(r#"{"name":"ci"}"#).unwrap();
+        assert_eq!(req.role, "member");
+        assert!(req.custom_role_id.is_none());
+    }
+
+    #[test]
+    fn test_response_omits_secret_hash() {
+        let (account, _) =
+            ServiceAccountEntity::new(Uuid::new_v4(), Uuid::new_v4(), "ci".into(), None);
+        let json = serde_json::to_string(&ServiceAccountResponse::from_entity(
+            &account,
+            OrgRole::Member,
+        ))
+        .unwrap();
+        assert!(json.contains("\"clientId\":\"sa_"));
+        assert!(json.contains("\"role\":\"member\""));
+        assert!(!json.contains("Hash"));
+    }
+
+    #[test]
+    fn test_token_request_deserialization() {
+        let json = r#"{"grantType":"client_credentials","clientId":"sa_x","clientSecret":"sas_y"}"#;
+        let req: ServiceAccountTokenRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.grant_type.as_deref(), Some("client_credentials"));
+        assert_eq!(req.client_id, "sa_x");
+    }
+}
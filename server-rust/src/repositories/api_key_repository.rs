@@ -5,15 +5,34 @@ use chrono::{DateTime, Utc};
 use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::net::IpAddr;
 use subtle::ConstantTimeEq;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::errors::AppError;
+use crate::repositories::policy_repository::{cidr_contains, parse_cidr};
 
 /// API key prefix for identification
 pub const API_KEY_PREFIX: &str = "ck_";
 
+/// Scope granting everything the owning user can do, including system-admin endpoints
+pub const SCOPE_ADMIN: &str = "admin";
+/// Scope for `/credits/spend`, `/credits/hold`, `/credits/capture` and `/credits/release`
+pub const SCOPE_CREDITS_SPEND: &str = "credits:spend";
+/// Scope for `/users/by-wallet` and `/users/by-stripe-customer` lookups
+pub const SCOPE_USERS_READ: &str = "users:read";
+/// Scope for linking Stripe customers to users
+pub const SCOPE_USERS_WRITE: &str = "users:write";
+
+/// All scopes an API key may be restricted to
+pub const API_KEY_SCOPES: &[&str] = &[
+    SCOPE_ADMIN,
+    SCOPE_CREDITS_SPEND,
+    SCOPE_USERS_READ,
+    SCOPE_USERS_WRITE,
+];
+
 /// API key entity for storage
 #[derive(Debug, Clone)]
 pub struct ApiKeyEntity {
@@ -24,6 +43,16 @@ pub struct ApiKeyEntity {
     pub label: String,
     pub created_at: DateTime<Utc>,
     pub last_used_at: Option<DateTime<Utc>>,
+    /// Scopes the key is restricted to (empty = unrestricted, legacy behaviour)
+    pub scopes: Vec<String>,
+    /// Organization the key is bound to (None = any org the user belongs to)
+    pub org_id: Option<Uuid>,
+    /// When the key stops working (None = never)
+    pub expires_at: Option<DateTime<Utc>>,
+    /// IP addresses or CIDR blocks allowed to use the key (empty = any)
+    pub allowed_ips: Vec<String>,
+    /// Maximum requests per minute for this key (None = no per-key limit)
+    pub rate_limit_per_minute: Option<u32>,
 }
 
 impl ApiKeyEntity {
@@ -38,8 +67,72 @@ impl ApiKeyEntity {
             label: label.to_string(),
             created_at: now,
             last_used_at: None,
+            scopes: Vec::new(),
+            org_id: None,
+            expires_at: None,
+            allowed_ips: Vec::new(),
+            rate_limit_per_minute: None,
         }
     }
+
+    /// Check if any restriction (scope, org, expiry, IP or rate limit) is set
+    pub fn is_restricted(&self) -> bool {
+        !self.scopes.is_empty()
+            || self.org_id.is_some()
+            || self.expires_at.is_some()
+            || !self.allowed_ips.is_empty()
+            || self.rate_limit_per_minute.is_some()
+    }
+
+    /// Check if the key has passed its expiry date
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|exp| exp <= Utc::now())
+    }
+
+    /// Check if the key grants a scope (unscoped keys and `admin` grant all)
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.is_empty() || self.scopes.iter().any(|s| s == scope || s == SCOPE_ADMIN)
+    }
+
+    /// Check a client IP against the allowlist.
+    ///
+    /// An unknown client IP never satisfies a non-empty allowlist.
+    pub fn allows_ip(&self, client_ip: Option<&str>) -> bool {
+        if self.allowed_ips.is_empty() {
+            return true;
+        }
+        let Some(ip) = client_ip.and_then(|ip| ip.parse::<IpAddr>().ok()) else {
+            return false;
+        };
+        self.allowed_ips
+            .iter()
+            .filter_map(|block| parse_cidr(block))
+            .any(|cidr| cidr_contains(cidr, ip))
+    }
+}
+
+/// Validate scope names and IP/CIDR entries for a new key
+pub fn validate_api_key_restrictions(
+    scopes: &[String],
+    allowed_ips: &[String],
+) -> Result<(), AppError> {
+    if let Some(unknown) = scopes
+        .iter()
+        .find(|s| !API_KEY_SCOPES.contains(&s.as_str()))
+    {
+        return Err(AppError::Validation(format!(
+            "Unknown scope '{}'. Valid scopes: {}",
+            unknown,
+            API_KEY_SCOPES.join(", ")
+        )));
+    }
+    if let Some(invalid) = allowed_ips.iter().find(|b| parse_cidr(b).is_none()) {
+        return Err(AppError::Validation(format!(
+            "Invalid IP address or CIDR block '{}'",
+            invalid
+        )));
+    }
+    Ok(())
 }
 
 /// Generate a new API key (prefix + 43 alphanumeric chars)
@@ -86,6 +179,9 @@ pub trait ApiKeyRepository: Send + Sync {
 
     /// Update last_used_at timestamp
     async fn update_last_used(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Delete all API keys bound to an organization
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
 }
 
 /// In-memory API key repository for development/testing
@@ -171,6 +267,13 @@ impl ApiKeyRepository for InMemoryApiKeyRepository {
         }
         Ok(())
     }
+
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let mut keys = self.keys.write().await;
+        let before = keys.len();
+        keys.retain(|_, k| k.org_id != Some(org_id));
+        Ok((before - keys.len()) as u64)
+    }
 }
 
 #[cfg(test)]
@@ -303,4 +406,36 @@ mod tests {
             .await;
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_scopes_expiry_and_ip_allowlist() {
+        let mut key = ApiKeyEntity::new(Uuid::new_v4(), &generate_api_key(), "bot");
+        assert!(!key.is_restricted());
+        assert!(key.has_scope(SCOPE_CREDITS_SPEND));
+        assert!(key.allows_ip(None));
+        assert!(!key.is_expired());
+
+        key.scopes = vec![SCOPE_USERS_READ.to_string()];
+        assert!(key.is_restricted());
+        assert!(key.has_scope(SCOPE_USERS_READ));
+        assert!(!key.has_scope(SCOPE_CREDITS_SPEND));
+        key.scopes.push(SCOPE_ADMIN.to_string());
+        assert!(key.has_scope(SCOPE_CREDITS_SPEND));
+
+        key.allowed_ips = vec!["10.0.0.0/8".into(), "2001:db8::1".into()];
+        assert!(key.allows_ip(Some("10.1.2.3")));
+        assert!(key.allows_ip(Some("2001:db8::1")));
+        assert!(!key.allows_ip(Some("192.168.0.1")));
+        assert!(!key.allows_ip(None));
+
+        key.expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        assert!(key.is_expired());
+    }
+
+    #[test]
+    fn test_validate_api_key_restrictions() {
+        assert!(validate_api_key_restrictions(&["users:read".into()], &["::1".into()]).is_ok());
+        assert!(validate_api_key_restrictions(&["root".into()], &[]).is_err());
+        assert!(validate_api_key_restrictions(&[], &["10.0.0.0/33".into()]).is_err());
+    }
 }
@@ -1,9 +1,11 @@
 //! Audit log repository trait and implementations
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use tokio::sync::RwLock;
 use uuid::Uuid;
@@ -32,6 +34,7 @@ pub enum AuditEventType {
     OrgCreated,
     OrgUpdated,
     OrgDeleted,
+    OrgRestored,
 
     // Membership events
     MemberJoined,
@@ -45,6 +48,8 @@ pub enum AuditEventType {
     InviteResent,
     /// H-03: Data inconsistency - invite marked accepted but membership creation failed
     InviteRollbackFailed,
+    InviteLinkCreated,
+    InviteLinkRevoked,
 
     // Password reset events
     PasswordResetRequested,
@@ -69,6 +74,44 @@ pub enum AuditEventType {
     CustomRoleUpdated,
     CustomRoleDeleted,
 
+    // Service account events
+    ServiceAccountCreated,
+    ServiceAccountUpdated,
+    ServiceAccountDeleted,
+    ServiceAccountSecretRotated,
+    ServiceAccountKeyCreated,
+    ServiceAccountKeyRevoked,
+    ServiceAccountTokenIssued,
+
+    // Impersonation events
+    ImpersonationStarted,
+    ImpersonationEnded,
+    /// A request made under an impersonation session
+    ImpersonationRequest,
+
+    // Account lifecycle events
+    UserSuspended,
+    UserBanned,
+    /// A suspension or ban was lifted by an admin
+    UserReactivated,
+    /// A user asked for their account to be deleted
+    UserDeletionRequested,
+    /// A user's personal data was purged after the cooling-off period
+    UserDeleted,
+    UserDataExported,
+    /// A confirmation link was sent to a new email address
+    UserEmailChangeRequested,
+    UserEmailChanged,
+    /// An email change was undone from the previous address
+    UserEmailChangeReverted,
+    /// An admin or API key changed a user's metadata
+    UserMetadataUpdated,
+
+    // Relationship (resource-level authorization) events
+    RelationshipTupleWritten,
+    RelationshipTupleDeleted,
+    RelationshipSchemaUpdated,
+
     // Wallet events
     WalletEnrolled,
     WalletRecovered,
@@ -78,6 +121,34 @@ pub enum AuditEventType {
     WalletTransactionSigned,
     WalletUnlocked,
     WalletLocked,
+
+    // Webhook endpoint events
+    WebhookEndpointCreated,
+    WebhookEndpointUpdated,
+    WebhookEndpointDeleted,
+    WebhookDeliveryReplayed,
+
+    // Organization credit events
+    /// A member's spending rules for the org balance were changed
+    OrgCreditMemberUpdated,
+
+    // Usage metering events
+    MeterCreated,
+    MeterUpdated,
+    /// An organization's price schedule for a meter was set or removed
+    MeterPriceOverrideUpdated,
+
+    // Subscription plan events
+    SubscriptionPlanCreated,
+    SubscriptionPlanUpdated,
+
+    // Credit transfer events
+    CreditTransferSent,
+    CreditTransferReceived,
+
+    // Spending budget events
+    /// A spending budget was created, changed or deleted
+    CreditBudgetUpdated,
 }
 
 impl AuditEventType {
@@ -96,6 +167,7 @@ impl AuditEventType {
             Self::OrgCreated => "org.created",
             Self::OrgUpdated => "org.updated",
             Self::OrgDeleted => "org.deleted",
+            Self::OrgRestored => "org.restored",
             Self::MemberJoined => "member.joined",
             Self::MemberRoleChanged => "member.role_changed",
             Self::MemberRemoved => "member.removed",
@@ -104,6 +176,8 @@ impl AuditEventType {
             Self::InviteCanceled => "invite.canceled",
             Self::InviteResent => "invite.resent",
             Self::InviteRollbackFailed => "invite.rollback_failed",
+            Self::InviteLinkCreated => "invite_link.created",
+            Self::InviteLinkRevoked => "invite_link.revoked",
             Self::PasswordResetRequested => "password.reset_requested",
             Self::PasswordResetCompleted => "password.reset_completed",
             Self::InstantLinkRequested => "instant_link.requested",
@@ -118,6 +192,29 @@ impl AuditEventType {
             Self::CustomRoleCreated => "custom_role.created",
             Self::CustomRoleUpdated => "custom_role.updated",
             Self::CustomRoleDeleted => "custom_role.deleted",
+            Self::ServiceAccountCreated => "service_account.created",
+            Self::ServiceAccountUpdated => "service_account.updated",
+            Self::ServiceAccountDeleted => "service_account.deleted",
+            Self::ServiceAccountSecretRotated => "service_account.secret_rotated",
+            Self::ServiceAccountKeyCreated => "service_account.key_created",
+            Self::ServiceAccountKeyRevoked => "service_account.key_revoked",
+            Self::ServiceAccountTokenIssued => "service_account.token_issued",
+            Self::ImpersonationStarted => "impersonation.started",
+            Self::ImpersonationEnded => "impersonation.ended",
+            Self::ImpersonationRequest => "impersonation.request",
+            Self::UserSuspended => "user.suspended",
+            Self::UserBanned => "user.banned",
+            Self::UserReactivated => "user.reactivated",
+            Self::UserDeletionRequested => "user.deletion_requested",
+            Self::UserDeleted => "user.deleted",
+            Self::UserDataExported => "user.data_exported",
+            Self::UserEmailChangeRequested => "user.email_change_requested",
+            Self::UserEmailChanged => "user.email_changed",
+            Self::UserEmailChangeReverted => "user.email_change_reverted",
+            Self::UserMetadataUpdated => "user.metadata_updated",
+            Self::RelationshipTupleWritten => "relationship.tuple_written",
+            Self::RelationshipTupleDeleted => "relationship.tuple_deleted",
+            Self::RelationshipSchemaUpdated => "relationship.schema_updated",
             Self::WalletEnrolled => "wallet.enrolled",
             Self::WalletRecovered => "wallet.recovered",
             Self::WalletRecoveryAcknowledged => "wallet.recovery_acknowledged",
@@ -126,6 +223,19 @@ impl AuditEventType {
             Self::WalletTransactionSigned => "wallet.transaction_signed",
             Self::WalletUnlocked => "wallet.unlocked",
             Self::WalletLocked => "wallet.locked",
+            Self::WebhookEndpointCreated => "webhook.endpoint_created",
+            Self::WebhookEndpointUpdated => "webhook.endpoint_updated",
+            Self::WebhookEndpointDeleted => "webhook.endpoint_deleted",
+            Self::WebhookDeliveryReplayed => "webhook.delivery_replayed",
+            Self::OrgCreditMemberUpdated => "org.credit_member_updated",
+            Self::MeterCreated => "meter.created",
+            Self::MeterUpdated => "meter.updated",
+            Self::MeterPriceOverrideUpdated => "meter.price_override_updated",
+            Self::SubscriptionPlanCreated => "subscription_plan.created",
+            Self::SubscriptionPlanUpdated => "subscription_plan.updated",
+            Self::CreditTransferSent => "credit.transfer_sent",
+            Self::CreditTransferReceived => "credit.transfer_received",
+            Self::CreditBudgetUpdated => "credit.budget_updated",
         }
     }
 
@@ -146,6 +256,7 @@ impl AuditEventType {
             "org.created" => Some(Self::OrgCreated),
             "org.updated" => Some(Self::OrgUpdated),
             "org.deleted" => Some(Self::OrgDeleted),
+            "org.restored" => Some(Self::OrgRestored),
             "member.joined" => Some(Self::MemberJoined),
             "member.role_changed" => Some(Self::MemberRoleChanged),
             "member.removed" => Some(Self::MemberRemoved),
@@ -154,6 +265,8 @@ impl AuditEventType {
             "invite.canceled" => Some(Self::InviteCanceled),
             "invite.resent" => Some(Self::InviteResent),
             "invite.rollback_failed" => Some(Self::InviteRollbackFailed),
+            "invite_link.created" => Some(Self::InviteLinkCreated),
+            "invite_link.revoked" => Some(Self::InviteLinkRevoked),
             "password.reset_requested" => Some(Self::PasswordResetRequested),
             "password.reset_completed" => Some(Self::PasswordResetCompleted),
             "instant_link.requested" => Some(Self::InstantLinkRequested),
@@ -168,6 +281,29 @@ impl AuditEventType {
             "custom_role.created" => Some(Self::CustomRoleCreated),
             "custom_role.updated" => Some(Self::CustomRoleUpdated),
             "custom_role.deleted" => Some(Self::CustomRoleDeleted),
+            "service_account.created" => Some(Self::ServiceAccountCreated),
+            "service_account.updated" => Some(Self::ServiceAccountUpdated),
+            "service_account.deleted" => Some(Self::ServiceAccountDeleted),
+            "service_account.secret_rotated" => Some(Self::ServiceAccountSecretRotated),
+            "service_account.key_created" => Some(Self::ServiceAccountKeyCreated),
+            "service_account.key_revoked" => Some(Self::ServiceAccountKeyRevoked),
+            "service_account.token_issued" => Some(Self::ServiceAccountTokenIssued),
+            "impersonation.started" => Some(Self::ImpersonationStarted),
+            "impersonation.ended" => Some(Self::ImpersonationEnded),
+            "impersonation.request" => Some(Self::ImpersonationRequest),
+            "user.suspended" => Some(Self::UserSuspended),
+            "user.banned" => Some(Self::UserBanned),
+            "user.reactivated" => Some(Self::UserReactivated),
+            "user.deletion_requested" => Some(Self::UserDeletionRequested),
+            "user.deleted" => Some(Self::UserDeleted),
+            "user.data_exported" => Some(Self::UserDataExported),
+            "user.email_change_requested" => Some(Self::UserEmailChangeRequested),
+            "user.email_changed" => Some(Self::UserEmailChanged),
+            "user.email_change_reverted" => Some(Self::UserEmailChangeReverted),
+            "user.metadata_updated" => Some(Self::UserMetadataUpdated),
+            "relationship.tuple_written" => Some(Self::RelationshipTupleWritten),
+            "relationship.tuple_deleted" => Some(Self::RelationshipTupleDeleted),
+            "relationship.schema_updated" => Some(Self::RelationshipSchemaUpdated),
             "wallet.enrolled" => Some(Self::WalletEnrolled),
             "wallet.recovered" => Some(Self::WalletRecovered),
             "wallet.recovery_acknowledged" => Some(Self::WalletRecoveryAcknowledged),
@@ -176,6 +312,19 @@ impl AuditEventType {
             "wallet.transaction_signed" => Some(Self::WalletTransactionSigned),
             "wallet.unlocked" => Some(Self::WalletUnlocked),
             "wallet.locked" => Some(Self::WalletLocked),
+            "webhook.endpoint_created" => Some(Self::WebhookEndpointCreated),
+            "webhook.endpoint_updated" => Some(Self::WebhookEndpointUpdated),
+            "webhook.endpoint_deleted" => Some(Self::WebhookEndpointDeleted),
+            "webhook.delivery_replayed" => Some(Self::WebhookDeliveryReplayed),
+            "org.credit_member_updated" => Some(Self::OrgCreditMemberUpdated),
+            "meter.created" => Some(Self::MeterCreated),
+            "meter.updated" => Some(Self::MeterUpdated),
+            "meter.price_override_updated" => Some(Self::MeterPriceOverrideUpdated),
+            "subscription_plan.created" => Some(Self::SubscriptionPlanCreated),
+            "subscription_plan.updated" => Some(Self::SubscriptionPlanUpdated),
+            "credit.transfer_sent" => Some(Self::CreditTransferSent),
+            "credit.transfer_received" => Some(Self::CreditTransferReceived),
+            "credit.budget_updated" => Some(Self::CreditBudgetUpdated),
             _ => None,
         }
     }
@@ -187,12 +336,42 @@ impl std::fmt::Display for AuditEventType {
     }
 }
 
+/// Kind of principal that performed an audited action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditActorType {
+    #[default]
+    User,
+    ServiceAccount,
+}
+
+impl AuditActorType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::ServiceAccount => "service_account",
+        }
+    }
+
+    /// Parse actor type from string (returns None for invalid values)
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "user" => Some(Self::User),
+            "service_account" => Some(Self::ServiceAccount),
+            _ => None,
+        }
+    }
+}
+
 /// Audit log entry entity
 #[derive(Debug, Clone)]
 pub struct AuditLogEntry {
     pub id: Uuid,
     pub event_type: AuditEventType,
     pub actor_user_id: Option<Uuid>,
+    /// Whether `actor_user_id` is a human user or a service account
+    pub actor_type: AuditActorType,
     /// S-23: Session ID in which the action was performed (for forensic correlation)
     pub session_id: Option<Uuid>,
     pub org_id: Option<Uuid>,
@@ -202,12 +381,201 @@ pub struct AuditLogEntry {
     pub user_agent: Option<String>,
     pub metadata: Value,
     pub created_at: DateTime<Utc>,
+    /// Position in the hash chain; `None` until the entry is stored, and for
+    /// entries written before chaining was introduced
+    pub chain: Option<AuditChainLink>,
+}
+
+/// `prev_hash` of the first entry in the chain (and `org_prev_hash` of the
+/// first entry of each org)
+pub const AUDIT_GENESIS_HASH: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Link of an audit entry in the tamper-evident hash chain
+///
+/// Every entry commits to the hash of the entry before it, both globally and
+/// within its org, so editing or deleting a stored row breaks the chain at
+/// that point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditChainLink {
+    /// Global position, starting at 1 with no gaps
+    pub seq: i64,
+    /// `entry_hash` of the entry at `seq - 1`
+    pub prev_hash: String,
+    /// `entry_hash` of the previous entry for the same org (None when the
+    /// entry has no org)
+    pub org_prev_hash: Option<String>,
+    /// SHA-256 over the fields kept on redaction and `personal_hash`
+    pub content_hash: String,
+    /// SHA-256 over the personal fields (actor, session, target, client and
+    /// metadata), as they were when the entry was written
+    pub personal_hash: String,
+    /// SHA-256 over the predecessor hashes and `content_hash`
+    pub entry_hash: String,
+    /// Personal fields were cleared after the entry was hashed; the stored
+    /// `personal_hash` is kept so the chain still verifies
+    pub redacted: bool,
+}
+
+impl AuditLogEntry {
+    /// SHA-256 (hex) over the fields that [`redact`](Self::redact) keeps,
+    /// committing to the personal fields through `personal_hash`
+    ///
+    /// Split this way, redacted entries still have everything but their
+    /// personal fields verified.
+    pub fn compute_content_hash(&self, seq: i64, personal_hash: &str) -> String {
+        hash_canonical(&serde_json::json!([
+            self.id,
+            seq,
+            self.event_type.as_str(),
+            self.actor_type.as_str(),
+            self.org_id,
+            self.created_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            personal_hash,
+        ]))
+    }
+
+    /// SHA-256 (hex) over the fields that [`redact`](Self::redact) clears
+    ///
+    /// Object keys in `metadata` are sorted so the hash survives a round
+    /// trip through JSONB.
+    pub fn compute_personal_hash(&self) -> String {
+        hash_canonical(&serde_json::json!([
+            self.actor_user_id,
+            self.session_id,
+            self.target_type,
+            self.target_id,
+            self.ip_address,
+            self.user_agent,
+            self.metadata,
+        ]))
+    }
+
+    /// Whether every personal field is in the state [`redact`](Self::redact)
+    /// leaves it in
+    pub fn has_redacted_shape(&self) -> bool {
+        self.actor_user_id.is_none()
+            && self.session_id.is_none()
+            && self.target_type.is_none()
+            && self.target_id.is_none()
+            && self.ip_address.is_none()
+            && self.user_agent.is_none()
+            && self.metadata.as_object().is_some_and(|m| m.is_empty())
+    }
+
+    /// Append this entry to the chain after the given predecessors
+    ///
+    /// `created_at` is truncated to microseconds first, which is the
+    /// precision Postgres stores.
+    pub fn link(&mut self, seq: i64, prev_hash: &str, org_prev_hash: Option<&str>) {
+        self.created_at = self.created_at.trunc_subsecs(6);
+        let personal_hash = self.compute_personal_hash();
+        let content_hash = self.compute_content_hash(seq, &personal_hash);
+        let entry_hash = compute_entry_hash(prev_hash, org_prev_hash, &content_hash);
+        self.chain = Some(AuditChainLink {
+            seq,
+            prev_hash: prev_hash.to_string(),
+            org_prev_hash: org_prev_hash.map(str::to_string),
+            content_hash,
+            personal_hash,
+            entry_hash,
+            redacted: false,
+        });
+    }
+
+    /// Clear personal fields, keeping the event type, org, time and chain
+    /// hashes (used when an org is purged)
+    pub fn redact(&mut self) {
+        self.actor_user_id = None;
+        self.session_id = None;
+        self.target_type = None;
+        self.target_id = None;
+        self.ip_address = None;
+        self.user_agent = None;
+        self.metadata = Value::Object(serde_json::Map::new());
+        if let Some(chain) = self.chain.as_mut() {
+            chain.redacted = true;
+        }
+    }
+}
+
+/// SHA-256 (hex) linking an entry's content to its predecessors
+pub fn compute_entry_hash(
+    prev_hash: &str,
+    org_prev_hash: Option<&str>,
+    content_hash: &str,
+) -> String {
+    let input = format!(
+        "{}:{}:{}",
+        prev_hash,
+        org_prev_hash.unwrap_or(""),
+        content_hash
+    );
+    hex::encode(Sha256::digest(input.as_bytes()))
+}
+
+/// SHA-256 (hex) over the canonical JSON form of `value`
+fn hash_canonical(value: &Value) -> String {
+    let mut canonical = String::new();
+    write_canonical_json(value, &mut canonical);
+    hex::encode(Sha256::digest(canonical.as_bytes()))
+}
+
+/// Serialize JSON with object keys sorted, independent of map ordering
+fn write_canonical_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(&map[key], out);
+            }
+            out.push('}');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+/// Signed statement of the chain head at a point in time
+///
+/// Checkpoints let verification detect a rewritten tail: an attacker can
+/// recompute every hash after an edit, but cannot produce a signature over
+/// the new head without the server key.
+#[derive(Debug, Clone)]
+pub struct AuditCheckpoint {
+    pub id: Uuid,
+    /// Sequence number of the entry the checkpoint covers
+    pub seq: i64,
+    /// `entry_hash` of that entry
+    pub entry_hash: String,
+    /// Hex Ed25519 public key of the signer
+    pub public_key: String,
+    /// Hex Ed25519 signature over the checkpoint message
+    pub signature: String,
+    pub created_at: DateTime<Utc>,
 }
 
 /// Builder for creating audit log entries
 pub struct AuditLogBuilder {
     event_type: AuditEventType,
     actor_user_id: Option<Uuid>,
+    actor_type: AuditActorType,
     session_id: Option<Uuid>,
     org_id: Option<Uuid>,
     target_type: Option<String>,
@@ -222,6 +590,7 @@ impl AuditLogBuilder {
         Self {
             event_type,
             actor_user_id: None,
+            actor_type: AuditActorType::User,
             session_id: None,
             org_id: None,
             target_type: None,
@@ -237,6 +606,11 @@ impl AuditLogBuilder {
         self
     }
 
+    pub fn actor_type(mut self, actor_type: AuditActorType) -> Self {
+        self.actor_type = actor_type;
+        self
+    }
+
     /// S-23: Set the session ID for forensic correlation
     pub fn session(mut self, session_id: Uuid) -> Self {
         self.session_id = Some(session_id);
@@ -274,6 +648,7 @@ impl AuditLogBuilder {
             id: Uuid::new_v4(),
             event_type: self.event_type,
             actor_user_id: self.actor_user_id,
+            actor_type: self.actor_type,
             session_id: self.session_id,
             org_id: self.org_id,
             target_type: self.target_type,
@@ -282,6 +657,7 @@ impl AuditLogBuilder {
             user_agent: self.user_agent,
             metadata: self.metadata,
             created_at: Utc::now(),
+            chain: None,
         }
     }
 }
@@ -305,7 +681,10 @@ pub struct AuditLogQuery {
 /// Audit log repository trait
 #[async_trait]
 pub trait AuditLogRepository: Send + Sync {
-    /// Create a new audit log entry
+    /// Create a new audit log entry, appending it to the hash chain
+    ///
+    /// Implementations serialize appends so sequence numbers have no gaps;
+    /// `created_at` is set to the time of the append.
     async fn create(&self, entry: AuditLogEntry) -> Result<AuditLogEntry, AppError>;
 
     /// Find audit log entry by ID
@@ -325,23 +704,87 @@ pub trait AuditLogRepository: Send + Sync {
     ) -> Result<(Vec<AuditLogEntry>, u64), AppError>;
 
     /// Delete audit logs older than a given date
+    ///
+    /// Removes a prefix of the chain; verification then starts at the oldest
+    /// remaining entry.
     async fn delete_older_than(&self, date: DateTime<Utc>) -> Result<u64, AppError>;
 
-    /// Delete audit logs for an organization
-    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
+    /// Redact audit logs for an organization
+    ///
+    /// Rows are kept (with personal fields cleared) rather than deleted so
+    /// the hash chain stays intact.
+    async fn redact_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
+
+    /// Chained entries with `seq > after_seq`, oldest first, optionally
+    /// limited to one org
+    async fn list_chain(
+        &self,
+        org_id: Option<Uuid>,
+        after_seq: i64,
+        limit: u32,
+    ) -> Result<Vec<AuditLogEntry>, AppError>;
+
+    /// Link of the most recently chained entry
+    async fn chain_head(&self) -> Result<Option<AuditChainLink>, AppError>;
+
+    /// Store a signed checkpoint
+    async fn create_checkpoint(
+        &self,
+        checkpoint: AuditCheckpoint,
+    ) -> Result<AuditCheckpoint, AppError>;
+
+    /// All checkpoints, oldest first
+    async fn list_checkpoints(&self) -> Result<Vec<AuditCheckpoint>, AppError>;
+
+    /// Sequence number of the last entry delivered to an audit sink
+    async fn get_sink_cursor(&self, sink: &str) -> Result<Option<i64>, AppError>;
+
+    /// Record that `sink` has received every entry up to `seq`
+    ///
+    /// The cursor only moves forward, so a slower concurrent worker can't
+    /// rewind it and trigger re-delivery.
+    async fn set_sink_cursor(&self, sink: &str, seq: i64) -> Result<(), AppError>;
 }
 
 /// In-memory audit log repository for development/testing
 pub struct InMemoryAuditLogRepository {
     entries: RwLock<HashMap<Uuid, AuditLogEntry>>,
+    checkpoints: RwLock<Vec<AuditCheckpoint>>,
+    sink_cursors: RwLock<HashMap<String, i64>>,
 }
 
 impl InMemoryAuditLogRepository {
     pub fn new() -> Self {
         Self {
             entries: RwLock::new(HashMap::new()),
+            checkpoints: RwLock::new(Vec::new()),
+            sink_cursors: RwLock::new(HashMap::new()),
         }
     }
+
+    /// Replace a stored entry without re-chaining it (for tamper tests)
+    #[cfg(test)]
+    pub async fn overwrite(&self, entry: AuditLogEntry) {
+        self.entries.write().await.insert(entry.id, entry);
+    }
+
+    /// Remove a stored entry without touching the chain (for tamper tests)
+    #[cfg(test)]
+    pub async fn remove(&self, id: Uuid) {
+        self.entries.write().await.remove(&id);
+    }
+}
+
+/// Most recently chained entry, optionally within one org
+fn chain_tail(
+    entries: &HashMap<Uuid, AuditLogEntry>,
+    org_id: Option<Uuid>,
+) -> Option<&AuditChainLink> {
+    entries
+        .values()
+        .filter(|e| org_id.is_none() || e.org_id == org_id)
+        .filter_map(|e| e.chain.as_ref())
+        .max_by_key(|link| link.seq)
 }
 
 impl Default for InMemoryAuditLogRepository {
@@ -352,8 +795,21 @@ impl Default for InMemoryAuditLogRepository {
 
 #[async_trait]
 impl AuditLogRepository for InMemoryAuditLogRepository {
-    async fn create(&self, entry: AuditLogEntry) -> Result<AuditLogEntry, AppError> {
+    async fn create(&self, mut entry: AuditLogEntry) -> Result<AuditLogEntry, AppError> {
         let mut entries = self.entries.write().await;
+
+        let (seq, prev_hash) = match chain_tail(&entries, None) {
+            Some(tail) => (tail.seq + 1, tail.entry_hash.clone()),
+            None => (1, AUDIT_GENESIS_HASH.to_string()),
+        };
+        let org_prev_hash = entry.org_id.map(|org_id| {
+            chain_tail(&entries, Some(org_id))
+                .map(|tail| tail.entry_hash.clone())
+                .unwrap_or_else(|| AUDIT_GENESIS_HASH.to_string())
+        });
+        entry.created_at = Utc::now();
+        entry.link(seq, &prev_hash, org_prev_hash.as_deref());
+
         entries.insert(entry.id, entry.clone());
         Ok(entry)
     }
@@ -406,7 +862,7 @@ impl AuditLogRepository for InMemoryAuditLogRepository {
             .collect();
 
         // Sort by created_at descending
-        results.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        results.sort_by_key(|e| Reverse(e.created_at));
 
         // Apply pagination
         let offset = query.offset.unwrap_or(0) as usize;
@@ -508,7 +964,7 @@ impl AuditLogRepository for InMemoryAuditLogRepository {
         let total = filtered.len() as u64;
 
         // Sort by created_at descending
-        filtered.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        filtered.sort_by_key(|e| Reverse(e.created_at));
 
         // Apply pagination
         let offset = query.offset.unwrap_or(0) as usize;
@@ -535,12 +991,66 @@ impl AuditLogRepository for InMemoryAuditLogRepository {
         Ok(count)
     }
 
-    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+    async fn redact_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
         let mut entries = self.entries.write().await;
 
-        let before = entries.len();
-        entries.retain(|_, entry| entry.org_id != Some(org_id));
-        Ok((before - entries.len()) as u64)
+        let mut count = 0;
+        for entry in entries.values_mut() {
+            if entry.org_id == Some(org_id) {
+                entry.redact();
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    async fn list_chain(
+        &self,
+        org_id: Option<Uuid>,
+        after_seq: i64,
+        limit: u32,
+    ) -> Result<Vec<AuditLogEntry>, AppError> {
+        let entries = self.entries.read().await;
+
+        let mut results: Vec<AuditLogEntry> = entries
+            .values()
+            .filter(|e| org_id.is_none() || e.org_id == org_id)
+            .filter(|e| e.chain.as_ref().is_some_and(|link| link.seq > after_seq))
+            .cloned()
+            .collect();
+        results.sort_by_key(|e| e.chain.as_ref().map(|link| link.seq));
+        results.truncate(limit as usize);
+        Ok(results)
+    }
+
+    async fn chain_head(&self) -> Result<Option<AuditChainLink>, AppError> {
+        let entries = self.entries.read().await;
+        Ok(chain_tail(&entries, None).cloned())
+    }
+
+    async fn create_checkpoint(
+        &self,
+        checkpoint: AuditCheckpoint,
+    ) -> Result<AuditCheckpoint, AppError> {
+        self.checkpoints.write().await.push(checkpoint.clone());
+        Ok(checkpoint)
+    }
+
+    async fn list_checkpoints(&self) -> Result<Vec<AuditCheckpoint>, AppError> {
+        let mut checkpoints = self.checkpoints.read().await.clone();
+        checkpoints.sort_by_key(|c| c.seq);
+        Ok(checkpoints)
+    }
+
+    async fn get_sink_cursor(&self, sink: &str) -> Result<Option<i64>, AppError> {
+        Ok(self.sink_cursors.read().await.get(sink).copied())
+    }
+
+    async fn set_sink_cursor(&self, sink: &str, seq: i64) -> Result<(), AppError> {
+        let mut cursors = self.sink_cursors.write().await;
+        let cursor = cursors.entry(sink.to_string()).or_insert(seq);
+        *cursor = (*cursor).max(seq);
+        Ok(())
     }
 }
 
@@ -819,4 +1329,91 @@ mod tests {
             .unwrap();
         assert_eq!(count, 2);
     }
+
+    #[tokio::test]
+    async fn test_create_links_entries_globally_and_per_org() {
+        let repo = InMemoryAuditLogRepository::new();
+        let org_id = Uuid::new_v4();
+
+        let first = repo
+            .create(
+                AuditLogBuilder::new(AuditEventType::OrgCreated)
+                    .org(org_id)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let second = repo
+            .create(AuditLogBuilder::new(AuditEventType::UserLogin).build())
+            .await
+            .unwrap();
+        let third = repo
+            .create(
+                AuditLogBuilder::new(AuditEventType::OrgUpdated)
+                    .org(org_id)
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let first = first.chain.unwrap();
+        let second = second.chain.unwrap();
+        let third_entry = third.clone();
+        let third = third.chain.unwrap();
+        assert_eq!((first.seq, second.seq, third.seq), (1, 2, 3));
+        assert_eq!(first.prev_hash, AUDIT_GENESIS_HASH);
+        assert_eq!(first.org_prev_hash.as_deref(), Some(AUDIT_GENESIS_HASH));
+        assert_eq!(second.prev_hash, first.entry_hash);
+        assert_eq!(second.org_prev_hash, None);
+        assert_eq!(third.prev_hash, second.entry_hash);
+        assert_eq!(third.org_prev_hash, Some(first.entry_hash));
+        assert_eq!(third_entry.compute_personal_hash(), third.personal_hash);
+        assert_eq!(
+            third_entry.compute_content_hash(3, &third.personal_hash),
+            third.content_hash
+        );
+
+        let org_chain = repo.list_chain(Some(org_id), 0, 10).await.unwrap();
+        assert_eq!(org_chain.len(), 2);
+        assert_eq!(repo.chain_head().await.unwrap().unwrap().seq, 3);
+    }
+
+    #[tokio::test]
+    async fn test_redact_by_org_keeps_chain() {
+        let repo = InMemoryAuditLogRepository::new();
+        let org_id = Uuid::new_v4();
+        let entry = AuditLogBuilder::new(AuditEventType::MemberJoined)
+            .actor(Uuid::new_v4())
+            .org(org_id)
+            .ip("10.0.0.1")
+            .metadata(serde_json::json!({"email": "a@example.com"}))
+            .build();
+        let created = repo.create(entry).await.unwrap();
+
+        assert_eq!(repo.redact_by_org(org_id).await.unwrap(), 1);
+
+        let stored = repo.find_by_id(created.id).await.unwrap().unwrap();
+        assert_eq!(stored.actor_user_id, None);
+        assert_eq!(stored.ip_address, None);
+        assert_eq!(stored.metadata, serde_json::json!({}));
+        assert!(stored.has_redacted_shape());
+        let link = stored.chain.unwrap();
+        assert!(link.redacted);
+        assert_eq!(link.entry_hash, created.chain.unwrap().entry_hash);
+    }
+
+    #[test]
+    fn test_content_hash_ignores_metadata_key_order() {
+        let mut a = AuditLogBuilder::new(AuditEventType::UserLogin).build();
+        a.metadata = serde_json::json!({"b": 1, "a": {"y": true, "x": [1, 2]}});
+        let mut b = a.clone();
+        let mut map = serde_json::Map::new();
+        map.insert("a".into(), serde_json::json!({"x": [1, 2], "y": true}));
+        map.insert("b".into(), serde_json::json!(1));
+        b.metadata = Value::Object(map);
+        assert_eq!(a.compute_personal_hash(), b.compute_personal_hash());
+
+        b.metadata = serde_json::json!({"b": 2, "a": {"y": true, "x": [1, 2]}});
+        assert_ne!(a.compute_personal_hash(), b.compute_personal_hash());
+    }
 }
@@ -0,0 +1,545 @@
+//! Credit budget repository
+//!
+//! Budgets cap what can be spent per day or per calendar month (UTC) by a
+//! user from their own balance, by a member from an organization's balance,
+//! or through an API key. A budget has an optional soft limit, which raises
+//! an alert when usage crosses it, and an optional hard limit, which rejects
+//! spends and holds that would exceed it.
+//!
+//! Usage is kept as a counter per budget and period. Spends and holds
+//! consume from it atomically, so concurrent requests cannot overshoot a
+//! hard limit; holds record which counters they consumed so the uncaptured
+//! part can be given back once they settle.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Whose spending a budget limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CreditBudgetScope {
+    /// A user spending from their own balance
+    User,
+    /// A member spending from an organization's balance
+    OrgMember,
+    /// Any spend made with an API key
+    ApiKey,
+}
+
+impl CreditBudgetScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::OrgMember => "org_member",
+            Self::ApiKey => "api_key",
+        }
+    }
+}
+
+impl std::str::FromStr for CreditBudgetScope {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(Self::User),
+            "org_member" => Ok(Self::OrgMember),
+            "api_key" => Ok(Self::ApiKey),
+            _ => Err(AppError::Internal(anyhow::anyhow!(
+                "Unknown credit budget scope: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Period a budget's usage is counted over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CreditBudgetPeriod {
+    /// UTC calendar day
+    Daily,
+    /// UTC calendar month
+    Monthly,
+}
+
+impl CreditBudgetPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Monthly => "monthly",
+        }
+    }
+
+    /// Start of the period containing `at`
+    pub fn start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let day = match self {
+            Self::Daily => at.day(),
+            Self::Monthly => 1,
+        };
+        Utc.with_ymd_and_hms(at.year(), at.month(), day, 0, 0, 0)
+            .single()
+            .unwrap_or(at)
+    }
+}
+
+impl std::str::FromStr for CreditBudgetPeriod {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daily" => Ok(Self::Daily),
+            "monthly" => Ok(Self::Monthly),
+            _ => Err(AppError::Internal(anyhow::anyhow!(
+                "Unknown credit budget period: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Credit budget entity for storage
+#[derive(Debug, Clone)]
+pub struct CreditBudgetEntity {
+    pub id: Uuid,
+    pub scope: CreditBudgetScope,
+    /// Spending user (`user` and `org_member` scopes)
+    pub user_id: Option<Uuid>,
+    /// Organization whose balance is spent (`org_member` scope)
+    pub org_id: Option<Uuid>,
+    /// API key making the spends (`api_key` scope)
+    pub api_key_id: Option<Uuid>,
+    pub currency: String,
+    pub period: CreditBudgetPeriod,
+    /// Usage at which an alert is raised
+    pub soft_limit: Option<i64>,
+    /// Usage that spends and holds may not exceed
+    pub hard_limit: Option<i64>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CreditBudgetEntity {
+    /// Whether this budget covers a spend by `user_id` from `org_id`'s
+    /// balance (or their own when None) made with `api_key_id`
+    pub fn applies_to(
+        &self,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+        currency: &str,
+    ) -> bool {
+        if !self.currency.eq_ignore_ascii_case(currency) {
+            return false;
+        }
+        match self.scope {
+            CreditBudgetScope::User => org_id.is_none() && self.user_id == Some(user_id),
+            CreditBudgetScope::OrgMember => {
+                org_id.is_some() && self.org_id == org_id && self.user_id == Some(user_id)
+            }
+            CreditBudgetScope::ApiKey => api_key_id.is_some() && self.api_key_id == api_key_id,
+        }
+    }
+
+    /// Whether `other` limits the same subject, currency and period
+    fn same_subject(&self, other: &CreditBudgetEntity) -> bool {
+        self.scope == other.scope
+            && self.user_id == other.user_id
+            && self.org_id == other.org_id
+            && self.api_key_id == other.api_key_id
+            && self.currency == other.currency
+            && self.period == other.period
+    }
+}
+
+/// Usage counter of a budget for one period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BudgetUsageKey {
+    pub budget_id: Uuid,
+    pub period_start: DateTime<Utc>,
+}
+
+/// A budget to consume from for the period starting at `period_start`
+#[derive(Debug, Clone)]
+pub struct CreditBudgetCharge {
+    pub budget: CreditBudgetEntity,
+    pub period_start: DateTime<Utc>,
+}
+
+impl CreditBudgetCharge {
+    pub fn key(&self) -> BudgetUsageKey {
+        BudgetUsageKey {
+            budget_id: self.budget.id,
+            period_start: self.period_start,
+        }
+    }
+
+    /// Error for a charge of `amount` on top of `used` exceeding the hard limit
+    pub fn exceeded(&self, used: i64, amount: i64) -> AppError {
+        AppError::BudgetExceeded(format!(
+            "{} {} spending budget exceeded: used {}, limit {}, need {}",
+            match self.budget.period {
+                CreditBudgetPeriod::Daily => "Daily",
+                CreditBudgetPeriod::Monthly => "Monthly",
+            },
+            self.budget.scope.as_str(),
+            used,
+            self.budget.hard_limit.unwrap_or_default(),
+            amount
+        ))
+    }
+}
+
+/// Credit budget repository trait
+#[async_trait]
+pub trait CreditBudgetRepository: Send + Sync {
+    /// Create a budget, or update the limits of the existing budget for the
+    /// same subject, currency and period
+    async fn upsert(&self, budget: CreditBudgetEntity) -> Result<CreditBudgetEntity, AppError>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<CreditBudgetEntity>, AppError>;
+
+    /// Budgets matching every given filter, oldest first
+    async fn list(
+        &self,
+        user_id: Option<Uuid>,
+        org_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+    ) -> Result<Vec<CreditBudgetEntity>, AppError>;
+
+    /// Delete a budget; returns false if it did not exist
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError>;
+
+    /// Budgets covering a spend (see [`CreditBudgetEntity::applies_to`])
+    async fn find_applicable(
+        &self,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+        currency: &str,
+    ) -> Result<Vec<CreditBudgetEntity>, AppError>;
+
+    /// Usage of a budget in a period
+    async fn get_usage(&self, key: BudgetUsageKey) -> Result<i64, AppError>;
+
+    /// Add `amount` to the usage of every charge, all or nothing
+    ///
+    /// Fails with `BudgetExceeded` if any charge would exceed its hard limit.
+    /// Returns the usage of each charge afterwards, in order.
+    async fn consume(
+        &self,
+        charges: &[CreditBudgetCharge],
+        amount: i64,
+    ) -> Result<Vec<i64>, AppError>;
+
+    /// Give back `amount` of usage (never below zero)
+    async fn release(&self, keys: &[BudgetUsageKey], amount: i64) -> Result<(), AppError>;
+
+    /// Record the counters a hold consumed from
+    async fn reserve_hold(&self, hold_id: Uuid, keys: &[BudgetUsageKey]) -> Result<(), AppError>;
+
+    /// Counters a hold consumed from
+    async fn hold_reservations(&self, hold_id: Uuid) -> Result<Vec<BudgetUsageKey>, AppError>;
+
+    /// Remove and return a hold's reservations (each is returned only once)
+    async fn take_hold_reservations(&self, hold_id: Uuid) -> Result<Vec<BudgetUsageKey>, AppError>;
+
+    /// Holds with reservations, up to `limit`
+    async fn list_reserved_holds(&self, limit: u32) -> Result<Vec<Uuid>, AppError>;
+}
+
+/// In-memory credit budget repository for development/testing
+pub struct InMemoryCreditBudgetRepository {
+    budgets: RwLock<HashMap<Uuid, CreditBudgetEntity>>,
+    usage: RwLock<HashMap<BudgetUsageKey, i64>>,
+    holds: RwLock<HashMap<Uuid, Vec<BudgetUsageKey>>>,
+}
+
+impl InMemoryCreditBudgetRepository {
+    pub fn new() -> Self {
+        Self {
+            budgets: RwLock::new(HashMap::new()),
+            usage: RwLock::new(HashMap::new()),
+            holds: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryCreditBudgetRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CreditBudgetRepository for InMemoryCreditBudgetRepository {
+    async fn upsert(&self, budget: CreditBudgetEntity) -> Result<CreditBudgetEntity, AppError> {
+        let mut budgets = self.budgets.write().await;
+        if let Some(existing) = budgets.values_mut().find(|b| b.same_subject(&budget)) {
+            existing.soft_limit = budget.soft_limit;
+            existing.hard_limit = budget.hard_limit;
+            existing.updated_at = Utc::now();
+            return Ok(existing.clone());
+        }
+        budgets.insert(budget.id, budget.clone());
+        Ok(budget)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<CreditBudgetEntity>, AppError> {
+        Ok(self.budgets.read().await.get(&id).cloned())
+    }
+
+    async fn list(
+        &self,
+        user_id: Option<Uuid>,
+        org_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+    ) -> Result<Vec<CreditBudgetEntity>, AppError> {
+        let mut budgets: Vec<_> = self
+            .budgets
+            .read()
+            .await
+            .values()
+            .filter(|b| {
+                user_id.map_or(true, |id| b.user_id == Some(id))
+                    && org_id.map_or(true, |id| b.org_id == Some(id))
+                    && api_key_id.map_or(true, |id| b.api_key_id == Some(id))
+            })
+            .cloned()
+            .collect();
+        budgets.sort_by_key(|b| b.created_at);
+        Ok(budgets)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError> {
+        let deleted = self.budgets.write().await.remove(&id).is_some();
+        if deleted {
+            self.usage.write().await.retain(|k, _| k.budget_id != id);
+            for keys in self.holds.write().await.values_mut() {
+                keys.retain(|k| k.budget_id != id);
+            }
+        }
+        Ok(deleted)
+    }
+
+    async fn find_applicable(
+        &self,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+        currency: &str,
+    ) -> Result<Vec<CreditBudgetEntity>, AppError> {
+        Ok(self
+            .budgets
+            .read()
+            .await
+            .values()
+            .filter(|b| b.applies_to(user_id, org_id, api_key_id, currency))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_usage(&self, key: BudgetUsageKey) -> Result<i64, AppError> {
+        Ok(self.usage.read().await.get(&key).copied().unwrap_or(0))
+    }
+
+    async fn consume(
+        &self,
+        charges: &[CreditBudgetCharge],
+        amount: i64,
+    ) -> Result<Vec<i64>, AppError> {
+        let mut usage = self.usage.write().await;
+        for charge in charges {
+            let used = usage.get(&charge.key()).copied().unwrap_or(0);
+            if charge
+                .budget
+                .hard_limit
+                .is_some_and(|hard| used + amount > hard)
+            {
+                return Err(charge.exceeded(used, amount));
+            }
+        }
+        Ok(charges
+            .iter()
+            .map(|charge| {
+                let used = usage.entry(charge.key()).or_insert(0);
+                *used += amount;
+                *used
+            })
+            .collect())
+    }
+
+    async fn release(&self, keys: &[BudgetUsageKey], amount: i64) -> Result<(), AppError> {
+        let mut usage = self.usage.write().await;
+        for key in keys {
+            if let Some(used) = usage.get_mut(key) {
+                *used = (*used - amount).max(0);
+            }
+        }
+        Ok(())
+    }
+
+    async fn reserve_hold(&self, hold_id: Uuid, keys: &[BudgetUsageKey]) -> Result<(), AppError> {
+        if !keys.is_empty() {
+            self.holds.write().await.insert(hold_id, keys.to_vec());
+        }
+        Ok(())
+    }
+
+    async fn hold_reservations(&self, hold_id: Uuid) -> Result<Vec<BudgetUsageKey>, AppError> {
+        Ok(self
+            .holds
+            .read()
+            .await
+            .get(&hold_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn take_hold_reservations(&self, hold_id: Uuid) -> Result<Vec<BudgetUsageKey>, AppError> {
+        Ok(self
+            .holds
+            .write()
+            .await
+            .remove(&hold_id)
+            .unwrap_or_default())
+    }
+
+    async fn list_reserved_holds(&self, limit: u32) -> Result<Vec<Uuid>, AppError> {
+        Ok(self
+            .holds
+            .read()
+            .await
+            .keys()
+            .take(limit as usize)
+            .copied()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(scope: CreditBudgetScope, hard_limit: Option<i64>) -> CreditBudgetEntity {
+        let now = Utc::now();
+        CreditBudgetEntity {
+            id: Uuid::new_v4(),
+            scope,
+            user_id: None,
+            org_id: None,
+            api_key_id: None,
+            currency: "SOL".into(),
+            period: CreditBudgetPeriod::Daily,
+            soft_limit: None,
+            hard_limit,
+            created_by: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_period_start() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 17, 15, 4, 5).unwrap();
+        assert_eq!(
+            CreditBudgetPeriod::Daily.start(at),
+            Utc.with_ymd_and_hms(2026, 3, 17, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            CreditBudgetPeriod::Monthly.start(at),
+            Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_applies_to_scopes() {
+        let user_id = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+        let key_id = Uuid::new_v4();
+
+        let mut personal = budget(CreditBudgetScope::User, None);
+        personal.user_id = Some(user_id);
+        assert!(personal.applies_to(user_id, None, None, "sol"));
+        assert!(!personal.applies_to(user_id, Some(org_id), None, "SOL"));
+        assert!(!personal.applies_to(user_id, None, None, "USD"));
+
+        let mut member = budget(CreditBudgetScope::OrgMember, None);
+        member.user_id = Some(user_id);
+        member.org_id = Some(org_id);
+        assert!(member.applies_to(user_id, Some(org_id), Some(key_id), "SOL"));
+        assert!(!member.applies_to(user_id, None, None, "SOL"));
+        assert!(!member.applies_to(Uuid::new_v4(), Some(org_id), None, "SOL"));
+
+        let mut key = budget(CreditBudgetScope::ApiKey, None);
+        key.api_key_id = Some(key_id);
+        assert!(key.applies_to(Uuid::new_v4(), None, Some(key_id), "SOL"));
+        assert!(!key.applies_to(user_id, None, None, "SOL"));
+    }
+
+    #[tokio::test]
+    async fn test_consume_is_all_or_nothing() {
+        let repo = InMemoryCreditBudgetRepository::new();
+        let period_start = CreditBudgetPeriod::Daily.start(Utc::now());
+        let loose = repo
+            .upsert(budget(CreditBudgetScope::ApiKey, Some(1_000)))
+            .await
+            .unwrap();
+        let mut tight = budget(CreditBudgetScope::ApiKey, Some(300));
+        tight.period = CreditBudgetPeriod::Monthly;
+        let tight = repo.upsert(tight).await.unwrap();
+        let charges: Vec<_> = [loose, tight]
+            .into_iter()
+            .map(|budget| CreditBudgetCharge {
+                budget,
+                period_start,
+            })
+            .collect();
+
+        assert_eq!(repo.consume(&charges, 200).await.unwrap(), vec![200, 200]);
+        let err = repo.consume(&charges, 200).await.unwrap_err();
+        assert!(matches!(err, AppError::BudgetExceeded(_)));
+        assert_eq!(repo.get_usage(charges[0].key()).await.unwrap(), 200);
+
+        let keys: Vec<_> = charges.iter().map(CreditBudgetCharge::key).collect();
+        repo.reserve_hold(Uuid::nil(), &keys).await.unwrap();
+        assert_eq!(
+            repo.list_reserved_holds(10).await.unwrap(),
+            vec![Uuid::nil()]
+        );
+        let taken = repo.take_hold_reservations(Uuid::nil()).await.unwrap();
+        assert!(repo
+            .take_hold_reservations(Uuid::nil())
+            .await
+            .unwrap()
+            .is_empty());
+        repo.release(&taken, 500).await.unwrap();
+        assert_eq!(repo.get_usage(charges[1].key()).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_updates_same_subject() {
+        let repo = InMemoryCreditBudgetRepository::new();
+        let mut first = budget(CreditBudgetScope::User, Some(100));
+        first.user_id = Some(Uuid::new_v4());
+        let first = repo.upsert(first).await.unwrap();
+
+        let mut second = first.clone();
+        second.id = Uuid::new_v4();
+        second.soft_limit = Some(50);
+        second.hard_limit = Some(200);
+        let stored = repo.upsert(second).await.unwrap();
+        assert_eq!(stored.id, first.id);
+        assert_eq!(stored.hard_limit, Some(200));
+        assert_eq!(repo.list(first.user_id, None, None).await.unwrap().len(), 1);
+
+        assert!(repo.delete(first.id).await.unwrap());
+        assert!(!repo.delete(first.id).await.unwrap());
+    }
+}
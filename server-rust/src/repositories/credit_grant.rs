@@ -0,0 +1,269 @@
+//! Promotional credit grants
+//!
+//! A grant is a bucket of free credit (sign-up bonus, referral reward,
+//! promotion) added to a user's balance. Grant credit is part of the stored
+//! balance but is tracked separately so it can expire unused: spends draw
+//! from active grants before paid credit, highest priority first, then
+//! soonest expiry. Other debits (corrections, forfeitures) draw from paid
+//! credit first.
+
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+use super::{CreditOwnerType, CreditTransactionEntity, CreditTxType};
+
+/// Reference type of transactions that grant or expire a grant
+pub const GRANT_REFERENCE_TYPE: &str = "credit_grant";
+
+/// Why a grant was issued
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreditGrantSource {
+    SignupBonus,
+    Referral,
+    Promotion,
+    Manual,
+}
+
+impl CreditGrantSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SignupBonus => "signup_bonus",
+            Self::Referral => "referral",
+            Self::Promotion => "promotion",
+            Self::Manual => "manual",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "signup_bonus" => Some(Self::SignupBonus),
+            "referral" => Some(Self::Referral),
+            "promotion" => Some(Self::Promotion),
+            "manual" => Some(Self::Manual),
+            _ => None,
+        }
+    }
+}
+
+/// Grant lifecycle status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreditGrantStatus {
+    /// Has remaining credit that can be spent
+    Active,
+    /// Fully spent
+    Depleted,
+    /// Expired or revoked; any unspent credit was forfeited
+    Expired,
+}
+
+impl CreditGrantStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Depleted => "depleted",
+            Self::Expired => "expired",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "active" => Some(Self::Active),
+            "depleted" => Some(Self::Depleted),
+            "expired" => Some(Self::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// Promotional credit granted to a user
+#[derive(Debug, Clone)]
+pub struct CreditGrantEntity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub currency: String,
+    pub source: CreditGrantSource,
+    /// Amount originally granted
+    pub amount: i64,
+    /// Amount not yet spent or forfeited
+    pub remaining: i64,
+    /// Higher priority grants are spent first
+    pub priority: i32,
+    /// When unspent credit is forfeited (None = never)
+    pub expires_at: Option<DateTime<Utc>>,
+    pub status: CreditGrantStatus,
+    /// Transaction that added the grant to the balance
+    pub transaction_id: Uuid,
+    pub reason: Option<String>,
+    /// Admin who issued the grant
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CreditGrantEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_id: Uuid,
+        amount: i64,
+        currency: &str,
+        source: CreditGrantSource,
+        priority: i32,
+        expires_at: Option<DateTime<Utc>>,
+        reason: Option<String>,
+        created_by: Option<Uuid>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            currency: currency.to_uppercase(),
+            source,
+            amount,
+            remaining: amount,
+            priority,
+            expires_at,
+            status: CreditGrantStatus::Active,
+            transaction_id: Uuid::new_v4(),
+            reason,
+            created_by,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether the grant still holds spendable credit
+    pub fn is_active(&self) -> bool {
+        self.status == CreditGrantStatus::Active && self.remaining > 0
+    }
+
+    /// Whether the grant is past its expiry
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|e| e <= now)
+    }
+
+    /// Order in which grants are spent: priority (highest first), then
+    /// expiry (soonest first, never-expiring last), then age
+    pub fn spend_order(a: &Self, b: &Self) -> Ordering {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| match (a.expires_at, b.expires_at) {
+                (Some(x), Some(y)) => x.cmp(&y),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            })
+            .then_with(|| a.created_at.cmp(&b.created_at))
+    }
+
+    /// Record `take` as spent or forfeited
+    pub(crate) fn draw(&mut self, take: i64, exhausted: CreditGrantStatus) {
+        self.remaining -= take;
+        if self.remaining <= 0 {
+            self.remaining = 0;
+            self.status = exhausted;
+        }
+        self.updated_at = Utc::now();
+    }
+}
+
+/// How much of a debit comes from promotional credit.
+///
+/// Spends use promotional credit first; other debits use paid credit first
+/// and only dip into promotional credit for the shortfall. Organization
+/// balances have no grants.
+pub fn promotional_draw(
+    tx: &CreditTransactionEntity,
+    amount: i64,
+    balance_before: i64,
+    promotional: i64,
+) -> i64 {
+    if tx.owner_type == CreditOwnerType::Org || amount <= 0 || promotional <= 0 {
+        return 0;
+    }
+    match tx.tx_type {
+        CreditTxType::Spend => amount.min(promotional),
+        _ => {
+            let paid = (balance_before - promotional).max(0);
+            (amount - paid).clamp(0, promotional)
+        }
+    }
+}
+
+/// Split `draw` across grants given as `(id, remaining)` in spend order
+pub fn allocate_draw(
+    grants: impl IntoIterator<Item = (Uuid, i64)>,
+    mut draw: i64,
+) -> Vec<(Uuid, i64)> {
+    let mut takes = Vec::new();
+    for (id, remaining) in grants {
+        if draw <= 0 {
+            break;
+        }
+        let take = remaining.min(draw);
+        if take > 0 {
+            takes.push((id, take));
+            draw -= take;
+        }
+    }
+    takes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_spend_order() {
+        let user_id = Uuid::new_v4();
+        let grant = |priority, expires_in_days: Option<i64>| {
+            CreditGrantEntity::new(
+                user_id,
+                100,
+                "SOL",
+                CreditGrantSource::Promotion,
+                priority,
+                expires_in_days.map(|d| Utc::now() + Duration::days(d)),
+                None,
+                None,
+            )
+        };
+        let never = grant(0, None);
+        let late = grant(0, Some(30));
+        let soon = grant(0, Some(7));
+        let boosted = grant(10, None);
+
+        let mut grants = [never.clone(), late.clone(), soon.clone(), boosted.clone()];
+        grants.sort_by(CreditGrantEntity::spend_order);
+        let ids: Vec<Uuid> = grants.iter().map(|g| g.id).collect();
+        assert_eq!(ids, vec![boosted.id, soon.id, late.id, never.id]);
+    }
+
+    #[test]
+    fn test_promotional_draw_and_allocation() {
+        let user_id = Uuid::new_v4();
+        let spend = CreditTransactionEntity::new_spend(user_id, 0, "SOL", None);
+        let correction =
+            CreditTransactionEntity::new_adjustment(user_id, 0, "SOL", user_id, "fix", None, None);
+
+        // Balance 1000, of which 300 is promotional
+        assert_eq!(promotional_draw(&spend, 200, 1000, 300), 200);
+        assert_eq!(promotional_draw(&spend, 500, 1000, 300), 300);
+        assert_eq!(promotional_draw(&correction, 500, 1000, 300), 0);
+        assert_eq!(promotional_draw(&correction, 800, 1000, 300), 100);
+        assert_eq!(
+            promotional_draw(&spend.clone().for_org(Uuid::new_v4()), 200, 1000, 300),
+            0
+        );
+
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(
+            allocate_draw([(a, 50), (b, 100)], 120),
+            vec![(a, 50), (b, 70)]
+        );
+        assert_eq!(allocate_draw([(a, 50), (b, 100)], 30), vec![(a, 30)]);
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! Holds allow reserving credits before finalizing a purchase.
 //! Flow: create_hold -> capture (converts to spend) OR release (cancels)
+//!
+//! A hold may be captured in several parts; a final capture releases
+//! whatever is left. Pending holds can be increased and extended.
 
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
@@ -11,14 +14,18 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::errors::AppError;
-use crate::repositories::{CreditRepository, CreditTransactionEntity};
+use crate::repositories::credit_repository::consume_budgets;
+use crate::repositories::{
+    CreditBudgetRepository, CreditOwnerType, CreditRepository, CreditTransactionEntity,
+    MemberSpendCap, SpendLimits,
+};
 
 /// Hold status
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HoldStatus {
     /// Hold is active, credits reserved
     Pending,
-    /// Hold was converted to a spend
+    /// Hold was converted to a spend (any uncaptured remainder released)
     Captured,
     /// Hold was cancelled, credits released
     Released,
@@ -53,7 +60,10 @@ impl HoldStatus {
 pub struct CreditHoldEntity {
     pub id: Uuid,
     pub user_id: Uuid,
+    /// Total amount authorized by the hold
     pub amount: i64,
+    /// Amount captured so far (partial captures add up)
+    pub captured_amount: i64,
     pub currency: String,
     pub idempotency_key: String,
     pub reference_type: Option<String>,
@@ -65,6 +75,10 @@ pub struct CreditHoldEntity {
     pub updated_at: DateTime<Utc>,
     /// Transaction ID if captured
     pub captured_transaction_id: Option<Uuid>,
+    /// Whose balance the hold reserves
+    pub owner_type: CreditOwnerType,
+    /// Organization whose balance is held (`owner_type == Org`)
+    pub org_id: Option<Uuid>,
 }
 
 impl CreditHoldEntity {
@@ -85,6 +99,7 @@ impl CreditHoldEntity {
             id: Uuid::new_v4(),
             user_id,
             amount,
+            captured_amount: 0,
             currency: currency.to_string(),
             idempotency_key,
             reference_type: reference_type.map(String::from),
@@ -95,9 +110,28 @@ impl CreditHoldEntity {
             created_at: now,
             updated_at: now,
             captured_transaction_id: None,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
         }
     }
 
+    /// Reserve the organization's balance instead of `user_id`'s
+    pub fn for_org(mut self, org_id: Uuid) -> Self {
+        self.owner_type = CreditOwnerType::Org;
+        self.org_id = Some(org_id);
+        self
+    }
+
+    /// Key of the balance this hold reserves (organization or user)
+    fn balance_owner(&self) -> Uuid {
+        self.org_id.unwrap_or(self.user_id)
+    }
+
+    /// Amount still reserved for future captures
+    pub fn remaining(&self) -> i64 {
+        self.amount - self.captured_amount
+    }
+
     /// Check if hold is expired
     pub fn is_expired(&self) -> bool {
         self.status == HoldStatus::Pending && Utc::now() > self.expires_at
@@ -141,8 +175,14 @@ pub trait CreditHoldRepository: Send + Sync {
     /// Create a new hold, reserving credits
     ///
     /// Returns existing hold if idempotency key matches.
-    /// Updates held_balance atomically.
-    async fn create_hold(&self, hold: CreditHoldEntity) -> Result<CreateHoldResult, AppError>;
+    /// Updates held_balance atomically, checking `limits` in the same step;
+    /// a new hold consumes from and records its budgets. Returns the hold
+    /// and the usage of each budget afterwards (empty for an existing hold).
+    async fn create_hold(
+        &self,
+        hold: CreditHoldEntity,
+        limits: &SpendLimits,
+    ) -> Result<(CreateHoldResult, Vec<i64>), AppError>;
 
     /// Get a hold by ID
     async fn get_hold(&self, hold_id: Uuid) -> Result<Option<CreditHoldEntity>, AppError>;
@@ -154,32 +194,58 @@ pub trait CreditHoldRepository: Send + Sync {
         idempotency_key: &str,
     ) -> Result<Option<CreditHoldEntity>, AppError>;
 
-    /// Capture a hold, converting it to a spend transaction
+    /// Capture part or all of a hold, converting it to a spend transaction
+    ///
+    /// Captures `-credit_tx.amount`, which must not exceed the hold's
+    /// remaining amount. With `release_remainder` the hold is settled and
+    /// the uncaptured remainder returns to the available balance; otherwise
+    /// it stays pending until fully captured, released or expired.
     ///
     /// SRV-02: Also deducts the actual balance and inserts the credit
     /// transaction record atomically in the same DB transaction, preventing
     /// inconsistency if the process crashes between capture and deduction.
     ///
-    /// Returns `(captured_hold, new_balance)`.
+    /// Returns `(updated_hold, new_balance)`.
     async fn capture_hold(
         &self,
         hold_id: Uuid,
-        transaction_id: Uuid,
         credit_tx: CreditTransactionEntity,
+        release_remainder: bool,
     ) -> Result<(CreditHoldEntity, i64), AppError>;
 
+    /// Raise a pending hold's amount and/or expiry
+    ///
+    /// Never lowers either value, so repeating a call is a no-op. An
+    /// increase is reserved atomically against the available balance and
+    /// checked against `limits`. Returns the hold and the usage of each
+    /// budget afterwards.
+    async fn extend_hold(
+        &self,
+        hold_id: Uuid,
+        amount: i64,
+        expires_at: DateTime<Utc>,
+        limits: &SpendLimits,
+    ) -> Result<(CreditHoldEntity, Vec<i64>), AppError>;
+
     /// Release a hold, returning credits to available balance
     ///
     /// Updates held_balance atomically.
     async fn release_hold(&self, hold_id: Uuid) -> Result<CreditHoldEntity, AppError>;
 
-    /// Get all pending holds for a user
+    /// Get all pending holds on a user's personal balance
     async fn get_pending_holds(
         &self,
         user_id: Uuid,
         currency: Option<&str>,
     ) -> Result<Vec<CreditHoldEntity>, AppError>;
 
+    /// Get all pending holds on an organization's balance
+    async fn get_pending_org_holds(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+    ) -> Result<Vec<CreditHoldEntity>, AppError>;
+
     /// Expire holds past their expiration time
     ///
     /// Returns number of holds expired.
@@ -196,6 +262,9 @@ pub struct InMemoryCreditHoldRepository {
     /// When set, `capture_hold` deducts the hold amount and returns the real new balance,
     /// mirroring what the Postgres implementation does in a single DB transaction.
     credit_repo: Option<Arc<dyn CreditRepository>>,
+    /// Budget repository consumed from by new and increased holds
+    /// In real impl, this is done in the same DB transaction as the hold
+    budget_repo: Option<Arc<dyn CreditBudgetRepository>>,
 }
 
 impl InMemoryCreditHoldRepository {
@@ -204,6 +273,7 @@ impl InMemoryCreditHoldRepository {
             holds: RwLock::new(HashMap::new()),
             balances_held: RwLock::new(HashMap::new()),
             credit_repo: None,
+            budget_repo: None,
         }
     }
 
@@ -216,14 +286,51 @@ impl InMemoryCreditHoldRepository {
             holds: RwLock::new(HashMap::new()),
             balances_held: RwLock::new(HashMap::new()),
             credit_repo: Some(credit_repo),
+            budget_repo: None,
         }
     }
 
-    /// Get total held balance for a user (for testing)
+    /// Consume the budgets of new and increased holds from `budget_repo`
+    pub fn with_budgets(mut self, budget_repo: Arc<dyn CreditBudgetRepository>) -> Self {
+        self.budget_repo = Some(budget_repo);
+        self
+    }
+
+    /// What the holder of `hold` has used of their cap: spends recorded in the
+    /// shared credit repository plus their other pending holds on the org
+    async fn member_cap_usage(
+        &self,
+        holds: &HashMap<Uuid, CreditHoldEntity>,
+        hold: &CreditHoldEntity,
+        org_id: Uuid,
+        cap: &MemberSpendCap,
+    ) -> Result<i64, AppError> {
+        let held: i64 = holds
+            .values()
+            .filter(|h| {
+                h.id != hold.id
+                    && h.org_id == Some(org_id)
+                    && h.user_id == hold.user_id
+                    && h.status == HoldStatus::Pending
+                    && h.currency.eq_ignore_ascii_case(&hold.currency)
+            })
+            .map(|h| h.remaining())
+            .sum();
+        let spent = match &self.credit_repo {
+            Some(repo) => {
+                repo.sum_org_member_spend(org_id, hold.user_id, &hold.currency, cap.since)
+                    .await?
+            }
+            None => 0,
+        };
+        Ok(spent + held)
+    }
+
+    /// Get total held balance for a user or organization (for testing)
     #[allow(dead_code)]
-    pub async fn get_held_balance(&self, user_id: Uuid, currency: &str) -> i64 {
+    pub async fn get_held_balance(&self, owner_id: Uuid, currency: &str) -> i64 {
         let held = self.balances_held.read().await;
-        *held.get(&(user_id, currency.to_string())).unwrap_or(&0)
+        *held.get(&(owner_id, currency.to_string())).unwrap_or(&0)
     }
 }
 
@@ -235,7 +342,11 @@ impl Default for InMemoryCreditHoldRepository {
 
 #[async_trait]
 impl CreditHoldRepository for InMemoryCreditHoldRepository {
-    async fn create_hold(&self, hold: CreditHoldEntity) -> Result<CreateHoldResult, AppError> {
+    async fn create_hold(
+        &self,
+        hold: CreditHoldEntity,
+        limits: &SpendLimits,
+    ) -> Result<(CreateHoldResult, Vec<i64>), AppError> {
         let mut holds = self.holds.write().await;
         let mut balances_held = self.balances_held.write().await;
 
@@ -243,16 +354,27 @@ impl CreditHoldRepository for InMemoryCreditHoldRepository {
         for existing in holds.values() {
             if existing.user_id == hold.user_id && existing.idempotency_key == hold.idempotency_key
             {
-                return Ok(CreateHoldResult::Existing(existing.clone()));
+                return Ok((CreateHoldResult::Existing(existing.clone()), Vec::new()));
             }
         }
 
+        if let (Some(org_id), Some(cap)) = (hold.org_id, &limits.member_cap) {
+            let used = self.member_cap_usage(&holds, &hold, org_id, cap).await?;
+            cap.check(used, hold.amount)?;
+        }
+        let budget_usage =
+            consume_budgets(self.budget_repo.as_deref(), &limits.budgets, hold.amount).await?;
+        if let Some(budget_repo) = &self.budget_repo {
+            let keys: Vec<_> = limits.budgets.iter().map(|c| c.key()).collect();
+            budget_repo.reserve_hold(hold.id, &keys).await?;
+        }
+
         // Update held balance
-        let key = (hold.user_id, hold.currency.clone());
+        let key = (hold.balance_owner(), hold.currency.clone());
         *balances_held.entry(key).or_insert(0) += hold.amount;
 
         holds.insert(hold.id, hold.clone());
-        Ok(CreateHoldResult::Created(hold))
+        Ok((CreateHoldResult::Created(hold), budget_usage))
     }
 
     async fn get_hold(&self, hold_id: Uuid) -> Result<Option<CreditHoldEntity>, AppError> {
@@ -275,8 +397,8 @@ impl CreditHoldRepository for InMemoryCreditHoldRepository {
     async fn capture_hold(
         &self,
         hold_id: Uuid,
-        transaction_id: Uuid,
         credit_tx: CreditTransactionEntity,
+        release_remainder: bool,
     ) -> Result<(CreditHoldEntity, i64), AppError> {
         let mut holds = self.holds.write().await;
         let mut balances_held = self.balances_held.write().await;
@@ -295,20 +417,38 @@ impl CreditHoldRepository for InMemoryCreditHoldRepository {
             )));
         }
 
+        let amount = credit_tx.amount.abs();
+        if amount > hold.remaining() {
+            return Err(AppError::Validation(format!(
+                "Capture of {} exceeds the remaining hold amount {}",
+                amount,
+                hold.remaining()
+            )));
+        }
+
+        // Settled holds give back the uncaptured remainder as well
+        let released = if release_remainder {
+            hold.remaining()
+        } else {
+            amount
+        };
+
         // Update hold status
-        hold.status = HoldStatus::Captured;
-        hold.captured_transaction_id = Some(transaction_id);
+        hold.captured_amount += amount;
+        if release_remainder || hold.remaining() == 0 {
+            hold.status = HoldStatus::Captured;
+        }
+        hold.captured_transaction_id = Some(credit_tx.id);
         hold.updated_at = Utc::now();
 
         // Release held balance
-        let key = (hold.user_id, hold.currency.clone());
+        let key = (hold.balance_owner(), hold.currency.clone());
         if let Some(held) = balances_held.get_mut(&key) {
-            *held = (*held - hold.amount).max(0);
+            *held = (*held - released).max(0);
         }
 
         let captured = hold.clone();
         let user_id = captured.user_id;
-        let amount = captured.amount;
         let currency = captured.currency.clone();
 
         // Drop write-locks before calling the credit repo to avoid deadlock.
@@ -327,6 +467,57 @@ impl CreditHoldRepository for InMemoryCreditHoldRepository {
         Ok((captured, new_balance))
     }
 
+    async fn extend_hold(
+        &self,
+        hold_id: Uuid,
+        amount: i64,
+        expires_at: DateTime<Utc>,
+        limits: &SpendLimits,
+    ) -> Result<(CreditHoldEntity, Vec<i64>), AppError> {
+        let mut holds = self.holds.write().await;
+        let mut balances_held = self.balances_held.write().await;
+
+        let current = holds
+            .get(&hold_id)
+            .ok_or_else(|| AppError::NotFound(format!("Hold {} not found", hold_id)))?;
+
+        if !current.can_capture() {
+            if current.is_expired() {
+                return Err(AppError::Validation("Hold has expired".into()));
+            }
+            return Err(AppError::Validation(format!(
+                "Hold cannot be extended, status: {}",
+                current.status.as_str()
+            )));
+        }
+
+        let increase = (amount - current.amount).max(0);
+        if let (Some(org_id), Some(cap)) = (current.org_id, &limits.member_cap) {
+            if increase > 0 {
+                let used = self.member_cap_usage(&holds, current, org_id, cap).await?;
+                cap.check(used + current.remaining(), increase)?;
+            }
+        }
+        let budget_usage = if increase > 0 {
+            consume_budgets(self.budget_repo.as_deref(), &limits.budgets, increase).await?
+        } else {
+            Vec::new()
+        };
+
+        let hold = holds
+            .get_mut(&hold_id)
+            .ok_or_else(|| AppError::NotFound(format!("Hold {} not found", hold_id)))?;
+        hold.amount += increase;
+        hold.expires_at = hold.expires_at.max(expires_at);
+        hold.updated_at = Utc::now();
+
+        // Reserve the increase
+        let key = (hold.balance_owner(), hold.currency.clone());
+        *balances_held.entry(key).or_insert(0) += increase;
+
+        Ok((hold.clone(), budget_usage))
+    }
+
     async fn release_hold(&self, hold_id: Uuid) -> Result<CreditHoldEntity, AppError> {
         let mut holds = self.holds.write().await;
         let mut balances_held = self.balances_held.write().await;
@@ -347,9 +538,9 @@ impl CreditHoldRepository for InMemoryCreditHoldRepository {
         hold.updated_at = Utc::now();
 
         // Release held balance
-        let key = (hold.user_id, hold.currency.clone());
+        let key = (hold.balance_owner(), hold.currency.clone());
         if let Some(held) = balances_held.get_mut(&key) {
-            *held = (*held - hold.amount).max(0);
+            *held = (*held - hold.remaining()).max(0);
         }
 
         Ok(hold.clone())
@@ -365,6 +556,24 @@ impl CreditHoldRepository for InMemoryCreditHoldRepository {
             .values()
             .filter(|h| {
                 h.user_id == user_id
+                    && h.org_id.is_none()
+                    && h.status == HoldStatus::Pending
+                    && currency.map_or(true, |c| h.currency == c)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn get_pending_org_holds(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+    ) -> Result<Vec<CreditHoldEntity>, AppError> {
+        let holds = self.holds.read().await;
+        Ok(holds
+            .values()
+            .filter(|h| {
+                h.org_id == Some(org_id)
                     && h.status == HoldStatus::Pending
                     && currency.map_or(true, |c| h.currency == c)
             })
@@ -384,7 +593,7 @@ impl CreditHoldRepository for InMemoryCreditHoldRepository {
                 tracing::info!(
                     hold_id = %hold.id,
                     user_id = %hold.user_id,
-                    amount_lamports = hold.amount,
+                    amount_lamports = hold.remaining(),
                     currency = %hold.currency,
                     reference_type = ?hold.reference_type,
                     reference_id = ?hold.reference_id,
@@ -397,9 +606,9 @@ impl CreditHoldRepository for InMemoryCreditHoldRepository {
                 hold.updated_at = now;
 
                 // Release held balance
-                let key = (hold.user_id, hold.currency.clone());
+                let key = (hold.balance_owner(), hold.currency.clone());
                 if let Some(held) = balances_held.get_mut(&key) {
-                    *held = (*held - hold.amount).max(0);
+                    *held = (*held - hold.remaining()).max(0);
                 }
 
                 count += 1;
@@ -430,7 +639,10 @@ mod tests {
             None,
         );
 
-        let result = repo.create_hold(hold).await.unwrap();
+        let (result, _) = repo
+            .create_hold(hold, &SpendLimits::default())
+            .await
+            .unwrap();
         assert!(result.is_new());
         assert_eq!(result.hold().amount, 100_000);
         assert_eq!(result.hold().status, HoldStatus::Pending);
@@ -463,10 +675,16 @@ mod tests {
             None,
         );
 
-        let result1 = repo.create_hold(hold1).await.unwrap();
+        let (result1, _) = repo
+            .create_hold(hold1, &SpendLimits::default())
+            .await
+            .unwrap();
         assert!(result1.is_new());
 
-        let result2 = repo.create_hold(hold2).await.unwrap();
+        let (result2, _) = repo
+            .create_hold(hold2, &SpendLimits::default())
+            .await
+            .unwrap();
         assert!(!result2.is_new()); // Should return existing
         assert_eq!(result1.hold().id, result2.hold().id);
     }
@@ -487,14 +705,17 @@ mod tests {
             None,
         );
 
-        let result = repo.create_hold(hold).await.unwrap();
+        let (result, _) = repo
+            .create_hold(hold, &SpendLimits::default())
+            .await
+            .unwrap();
         let hold_id = result.hold().id;
-        let tx_id = Uuid::new_v4();
 
         let credit_tx = CreditTransactionEntity::from_captured_hold(
             user_id, 100_000, "SOL", hold_id, "order-123", None, None, None,
         );
-        let (captured, _balance) = repo.capture_hold(hold_id, tx_id, credit_tx).await.unwrap();
+        let tx_id = credit_tx.id;
+        let (captured, _balance) = repo.capture_hold(hold_id, credit_tx, true).await.unwrap();
         assert_eq!(captured.status, HoldStatus::Captured);
         assert_eq!(captured.captured_transaction_id, Some(tx_id));
     }
@@ -515,7 +736,10 @@ mod tests {
             None,
         );
 
-        let result = repo.create_hold(hold).await.unwrap();
+        let (result, _) = repo
+            .create_hold(hold, &SpendLimits::default())
+            .await
+            .unwrap();
         let hold_id = result.hold().id;
 
         let released = repo.release_hold(hold_id).await.unwrap();
@@ -538,7 +762,10 @@ mod tests {
             None,
         );
 
-        let result = repo.create_hold(hold).await.unwrap();
+        let (result, _) = repo
+            .create_hold(hold, &SpendLimits::default())
+            .await
+            .unwrap();
         let hold_id = result.hold().id;
 
         // Release the hold
@@ -548,7 +775,96 @@ mod tests {
         let credit_tx = CreditTransactionEntity::from_captured_hold(
             user_id, 100_000, "SOL", hold_id, "order-123", None, None, None,
         );
-        let capture_result = repo.capture_hold(hold_id, Uuid::new_v4(), credit_tx).await;
+        let capture_result = repo.capture_hold(hold_id, credit_tx, true).await;
         assert!(capture_result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_partial_and_incremental_capture() {
+        let repo = InMemoryCreditHoldRepository::new();
+        let user_id = Uuid::new_v4();
+
+        let hold = CreditHoldEntity::new(
+            user_id,
+            100_000,
+            "SOL",
+            "render-1".to_string(),
+            Duration::minutes(15),
+            None,
+            None,
+            None,
+        );
+        let hold_id = repo
+            .create_hold(hold, &SpendLimits::default())
+            .await
+            .unwrap()
+            .0
+            .hold()
+            .id;
+
+        // Incremental capture keeps the rest reserved
+        let credit_tx = CreditTransactionEntity::from_captured_hold(
+            user_id,
+            30_000,
+            "SOL",
+            hold_id,
+            "render-1:a",
+            None,
+            None,
+            None,
+        );
+        let (hold, _) = repo.capture_hold(hold_id, credit_tx, false).await.unwrap();
+        assert_eq!(hold.status, HoldStatus::Pending);
+        assert_eq!(hold.remaining(), 70_000);
+        assert_eq!(repo.get_held_balance(user_id, "SOL").await, 70_000);
+
+        // Increase and extend, then a smaller value is a no-op
+        let expires_at = hold.expires_at + Duration::minutes(10);
+        let (hold, _) = repo
+            .extend_hold(hold_id, 150_000, expires_at, &SpendLimits::default())
+            .await
+            .unwrap();
+        assert_eq!(hold.amount, 150_000);
+        assert_eq!(hold.expires_at, expires_at);
+        let (hold, _) = repo
+            .extend_hold(hold_id, 120_000, hold.created_at, &SpendLimits::default())
+            .await
+            .unwrap();
+        assert_eq!(hold.amount, 150_000);
+        assert_eq!(hold.expires_at, expires_at);
+        assert_eq!(repo.get_held_balance(user_id, "SOL").await, 120_000);
+
+        // Cannot capture more than what is left
+        let credit_tx = CreditTransactionEntity::from_captured_hold(
+            user_id,
+            130_000,
+            "SOL",
+            hold_id,
+            "render-1:b",
+            None,
+            None,
+            None,
+        );
+        assert!(repo.capture_hold(hold_id, credit_tx, false).await.is_err());
+
+        // A final capture releases the remainder
+        let credit_tx = CreditTransactionEntity::from_captured_hold(
+            user_id,
+            20_000,
+            "SOL",
+            hold_id,
+            "render-1:b",
+            None,
+            None,
+            None,
+        );
+        let (hold, _) = repo.capture_hold(hold_id, credit_tx, true).await.unwrap();
+        assert_eq!(hold.status, HoldStatus::Captured);
+        assert_eq!(hold.captured_amount, 50_000);
+        assert_eq!(repo.get_held_balance(user_id, "SOL").await, 0);
+        assert!(repo
+            .extend_hold(hold_id, 200_000, expires_at, &SpendLimits::default())
+            .await
+            .is_err());
+    }
 }
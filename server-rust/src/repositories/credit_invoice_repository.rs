@@ -0,0 +1,164 @@
+//! Credit invoice repository
+//!
+//! Every deposit shown on a statement gets an invoice with a sequential
+//! number. Numbers are issued the first time a deposit is invoiced, in
+//! deposit order, and are never reused or skipped; an invoiced deposit keeps
+//! its number on every later statement.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{CreditOwnerType, CreditTransactionEntity};
+
+/// An invoice for one deposit
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditInvoiceEntity {
+    /// Sequential invoice number (starting at 1)
+    pub number: i64,
+    pub transaction_id: Uuid,
+    /// Whose balance the deposit credited
+    pub owner_type: CreditOwnerType,
+    /// Depositing user (the acting member for organization deposits)
+    pub user_id: Uuid,
+    pub org_id: Option<Uuid>,
+    pub amount: i64,
+    pub currency: String,
+    pub deposited_at: DateTime<Utc>,
+    pub issued_at: DateTime<Utc>,
+}
+
+impl CreditInvoiceEntity {
+    fn issue(number: i64, deposit: &CreditTransactionEntity, issued_at: DateTime<Utc>) -> Self {
+        Self {
+            number,
+            transaction_id: deposit.id,
+            owner_type: deposit.owner_type,
+            user_id: deposit.user_id,
+            org_id: deposit.org_id,
+            amount: deposit.amount,
+            currency: deposit.currency.clone(),
+            deposited_at: deposit.created_at,
+            issued_at,
+        }
+    }
+
+    /// Display form of the invoice number, e.g. `INV-000042`
+    pub fn invoice_number(&self) -> String {
+        format!("INV-{:06}", self.number)
+    }
+}
+
+/// Order in which uninvoiced deposits receive numbers
+pub(crate) fn sort_for_numbering(deposits: &mut [&CreditTransactionEntity]) {
+    deposits.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+}
+
+/// Credit invoice repository trait
+#[async_trait]
+pub trait CreditInvoiceRepository: Send + Sync {
+    /// Invoices for the given deposits, numbering the ones not invoiced yet
+    ///
+    /// Returns one invoice per deposit, ordered by number.
+    async fn issue(
+        &self,
+        deposits: &[CreditTransactionEntity],
+    ) -> Result<Vec<CreditInvoiceEntity>, AppError>;
+}
+
+/// In-memory credit invoice repository for development/testing
+pub struct InMemoryCreditInvoiceRepository {
+    invoices: Mutex<HashMap<Uuid, CreditInvoiceEntity>>,
+}
+
+impl InMemoryCreditInvoiceRepository {
+    pub fn new() -> Self {
+        Self {
+            invoices: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryCreditInvoiceRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CreditInvoiceRepository for InMemoryCreditInvoiceRepository {
+    async fn issue(
+        &self,
+        deposits: &[CreditTransactionEntity],
+    ) -> Result<Vec<CreditInvoiceEntity>, AppError> {
+        let mut invoices = self.invoices.lock().await;
+        let mut last_number = invoices.values().map(|i| i.number).max().unwrap_or(0);
+
+        let mut missing: Vec<_> = deposits
+            .iter()
+            .filter(|d| !invoices.contains_key(&d.id))
+            .collect();
+        sort_for_numbering(&mut missing);
+        missing.dedup_by_key(|d| d.id);
+
+        let now = Utc::now();
+        for deposit in missing {
+            last_number += 1;
+            invoices.insert(
+                deposit.id,
+                CreditInvoiceEntity::issue(last_number, deposit, now),
+            );
+        }
+
+        let mut issued: Vec<_> = deposits
+            .iter()
+            .filter_map(|d| invoices.get(&d.id).cloned())
+            .collect();
+        issued.sort_by_key(|i| i.number);
+        issued.dedup_by_key(|i| i.number);
+        Ok(issued)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposit(amount: i64, minutes_ago: i64) -> CreditTransactionEntity {
+        let mut tx = CreditTransactionEntity::new_privacy_deposit(
+            Uuid::new_v4(),
+            amount,
+            "SOL",
+            Uuid::new_v4(),
+        );
+        tx.created_at = Utc::now() - chrono::Duration::minutes(minutes_ago);
+        tx
+    }
+
+    #[tokio::test]
+    async fn test_issue_numbers_sequentially_and_keeps_numbers() {
+        let repo = InMemoryCreditInvoiceRepository::new();
+        let later = deposit(200, 1);
+        let earlier = deposit(100, 10);
+
+        let first = repo.issue(&[later.clone(), earlier.clone()]).await.unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].transaction_id, earlier.id);
+        assert_eq!(first[0].invoice_number(), "INV-000001");
+        assert_eq!(first[1].transaction_id, later.id);
+        assert_eq!(first[1].number, 2);
+
+        // Already invoiced deposits keep their numbers; new ones continue
+        let newest = deposit(300, 0);
+        let second = repo.issue(&[newest.clone(), later.clone()]).await.unwrap();
+        assert_eq!(
+            second.iter().map(|i| i.number).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(second[1].transaction_id, newest.id);
+        assert!(repo.issue(&[]).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,422 @@
+//! Double-entry journal behind the credit ledger
+//!
+//! Every credit transaction posts journal entries that each debit one
+//! account and credit another by the same amount, so the journal always
+//! balances. Balance accounts (one per user or organization and currency)
+//! are liabilities: a credit increases what is owed to the owner. Promotional
+//! grant credit posts to the owner's promotional liability instead, which
+//! counts towards the user's stored balance. The stored balances in
+//! `credit_balances` / `org_credit_balances` must equal the journal's view
+//! of those accounts; reconciliation checks exactly that.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{CreditOwnerType, CreditTransactionEntity, CreditTxType};
+
+/// Reference type that marks an adjustment as a refund
+pub const REFUND_REFERENCE_TYPE: &str = "refund";
+
+/// Ledger accounts credits move between
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LedgerAccount {
+    /// Credits owed to a user (one account per user and currency)
+    UserBalance,
+    /// Credits owed to an organization (one account per org and currency)
+    OrgBalance,
+    /// Funds received from deposits, pending settlement
+    DepositClearing,
+    /// Credits earned by spending
+    Revenue,
+    /// Credits returned to owners by refunds
+    Refunds,
+    /// Promotional credits granted but not yet spent or expired
+    PromotionalLiability,
+    /// Manual corrections, bonuses and forfeitures
+    Adjustments,
+    /// Credits in flight between two balances; each transfer's legs net
+    /// this account back to zero
+    Transfers,
+}
+
+impl LedgerAccount {
+    pub const ALL: [LedgerAccount; 8] = [
+        Self::UserBalance,
+        Self::OrgBalance,
+        Self::DepositClearing,
+        Self::Revenue,
+        Self::Refunds,
+        Self::PromotionalLiability,
+        Self::Adjustments,
+        Self::Transfers,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UserBalance => "user_balance",
+            Self::OrgBalance => "org_balance",
+            Self::DepositClearing => "deposit_clearing",
+            Self::Revenue => "revenue",
+            Self::Refunds => "refunds",
+            Self::PromotionalLiability => "promotional_liability",
+            Self::Adjustments => "adjustments",
+            Self::Transfers => "transfers",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.as_str() == s)
+    }
+
+    /// Whether the account's balance grows with debits (assets and
+    /// expenses) rather than credits (liabilities and revenue)
+    pub fn is_debit_normal(&self) -> bool {
+        matches!(
+            self,
+            Self::DepositClearing | Self::Refunds | Self::Adjustments
+        )
+    }
+
+    /// Balance on the account's normal side
+    pub fn normal_balance(&self, debits: i64, credits: i64) -> i64 {
+        if self.is_debit_normal() {
+            debits - credits
+        } else {
+            credits - debits
+        }
+    }
+}
+
+/// One side of a journal entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedgerPosting {
+    pub account: LedgerAccount,
+    /// User or organization for balance accounts; None for system accounts
+    pub owner_id: Option<Uuid>,
+}
+
+impl LedgerPosting {
+    pub fn system(account: LedgerAccount) -> Self {
+        Self {
+            account,
+            owner_id: None,
+        }
+    }
+
+    /// The balance account a transaction moves
+    pub fn owner_of(tx: &CreditTransactionEntity) -> Self {
+        match (tx.owner_type, tx.org_id) {
+            (CreditOwnerType::Org, Some(org_id)) => Self {
+                account: LedgerAccount::OrgBalance,
+                owner_id: Some(org_id),
+            },
+            _ => Self {
+                account: LedgerAccount::UserBalance,
+                owner_id: Some(tx.user_id),
+            },
+        }
+    }
+}
+
+/// Journal entry: `amount` moves from the debit side to the credit side
+#[derive(Debug, Clone)]
+pub struct CreditJournalEntry {
+    pub id: Uuid,
+    pub transaction_id: Option<Uuid>,
+    pub currency: String,
+    /// Always non-negative
+    pub amount: i64,
+    pub debit: LedgerPosting,
+    pub credit: LedgerPosting,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CreditJournalEntry {
+    /// Post a credit transaction.
+    ///
+    /// The owner's balance account is credited when the transaction adds
+    /// credits and debited when it removes them; the other side is the
+    /// counter account for the transaction type. The part of the amount
+    /// that moves promotional grant credit posts to the owner's
+    /// promotional liability instead, so a transaction that mixes both
+    /// posts two entries.
+    pub fn for_transaction(tx: &CreditTransactionEntity) -> Vec<Self> {
+        let counter = LedgerPosting::system(match tx.tx_type {
+            CreditTxType::Deposit => LedgerAccount::DepositClearing,
+            CreditTxType::Spend => LedgerAccount::Revenue,
+            CreditTxType::Adjustment
+                if tx.reference_type.as_deref() == Some(REFUND_REFERENCE_TYPE) =>
+            {
+                LedgerAccount::Refunds
+            }
+            CreditTxType::Adjustment | CreditTxType::Grant | CreditTxType::Expiration => {
+                LedgerAccount::Adjustments
+            }
+            CreditTxType::Transfer => LedgerAccount::Transfers,
+        });
+        let owner = LedgerPosting::owner_of(tx);
+        let promotional = LedgerPosting {
+            account: LedgerAccount::PromotionalLiability,
+            owner_id: owner.owner_id,
+        };
+
+        let paid_amount = tx.amount - tx.grant_amount;
+        let mut parts = Vec::with_capacity(2);
+        if paid_amount != 0 || tx.grant_amount == 0 {
+            parts.push((owner, paid_amount));
+        }
+        if tx.grant_amount != 0 {
+            parts.push((promotional, tx.grant_amount));
+        }
+
+        parts
+            .into_iter()
+            .map(|(posting, amount)| {
+                let (debit, credit) = if amount >= 0 {
+                    (counter, posting)
+                } else {
+                    (posting, counter)
+                };
+                Self {
+                    id: Uuid::new_v4(),
+                    transaction_id: Some(tx.id),
+                    currency: tx.currency.to_uppercase(),
+                    amount: amount.abs(),
+                    debit,
+                    credit,
+                    created_at: tx.created_at,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Debit and credit totals of one account type in one currency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerAccountTotals {
+    pub account: LedgerAccount,
+    pub currency: String,
+    pub debits: i64,
+    pub credits: i64,
+}
+
+impl LedgerAccountTotals {
+    /// Sum journal entries per account type and currency
+    pub fn from_entries<'a>(
+        entries: impl IntoIterator<Item = &'a CreditJournalEntry>,
+    ) -> Vec<Self> {
+        let mut totals: std::collections::BTreeMap<(String, LedgerAccount), (i64, i64)> =
+            std::collections::BTreeMap::new();
+        for entry in entries {
+            totals
+                .entry((entry.currency.clone(), entry.debit.account))
+                .or_default()
+                .0 += entry.amount;
+            totals
+                .entry((entry.currency.clone(), entry.credit.account))
+                .or_default()
+                .1 += entry.amount;
+        }
+        totals
+            .into_iter()
+            .map(|((currency, account), (debits, credits))| Self {
+                account,
+                currency,
+                debits,
+                credits,
+            })
+            .collect()
+    }
+}
+
+/// A stored balance that disagrees with the journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreditBalanceDrift {
+    pub owner_type: CreditOwnerType,
+    pub owner_id: Uuid,
+    pub currency: String,
+    /// Balance in `credit_balances` / `org_credit_balances`
+    pub stored_balance: i64,
+    /// Balance recomputed from the journal
+    pub ledger_balance: i64,
+}
+
+impl CreditBalanceDrift {
+    pub fn drift(&self) -> i64 {
+        self.stored_balance - self.ledger_balance
+    }
+}
+
+/// Result of recomputing every balance from the journal
+#[derive(Debug, Clone, Default)]
+pub struct BalanceReconciliation {
+    /// Number of stored balances compared
+    pub balances_checked: u64,
+    /// Balances that disagree with the journal (capped by the query limit)
+    pub drift: Vec<CreditBalanceDrift>,
+    /// Transactions that have no journal entry
+    pub unposted_transactions: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_posting_rules() {
+        let user_id = Uuid::new_v4();
+
+        let deposit =
+            CreditTransactionEntity::new_privacy_deposit(user_id, 500, "sol", Uuid::new_v4());
+        let entry = CreditJournalEntry::for_transaction(&deposit).remove(0);
+        assert_eq!(entry.debit.account, LedgerAccount::DepositClearing);
+        assert_eq!(entry.credit.account, LedgerAccount::UserBalance);
+        assert_eq!(entry.credit.owner_id, Some(user_id));
+        assert_eq!(entry.amount, 500);
+        assert_eq!(entry.currency, "SOL");
+
+        let spend = CreditTransactionEntity::new_spend(user_id, 200, "SOL", None);
+        let entry = CreditJournalEntry::for_transaction(&spend).remove(0);
+        assert_eq!(entry.debit.account, LedgerAccount::UserBalance);
+        assert_eq!(entry.credit.account, LedgerAccount::Revenue);
+        assert_eq!(entry.amount, 200);
+
+        let refund = CreditTransactionEntity::new_refund_adjustment(
+            user_id,
+            50,
+            "SOL",
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            spend.id,
+            "duplicate charge",
+        );
+        let entry = CreditJournalEntry::for_transaction(&refund).remove(0);
+        assert_eq!(entry.debit.account, LedgerAccount::Refunds);
+
+        let org_id = Uuid::new_v4();
+        let correction = CreditTransactionEntity::new_adjustment(
+            user_id,
+            -30,
+            "SOL",
+            user_id,
+            "correction",
+            None,
+            None,
+        )
+        .for_org(org_id);
+        let entry = CreditJournalEntry::for_transaction(&correction).remove(0);
+        assert_eq!(entry.debit.account, LedgerAccount::OrgBalance);
+        assert_eq!(entry.debit.owner_id, Some(org_id));
+        assert_eq!(entry.credit.account, LedgerAccount::Adjustments);
+        assert_eq!(entry.amount, 30);
+    }
+
+    #[test]
+    fn test_totals_balance_and_normal_side() {
+        let user_id = Uuid::new_v4();
+        let entries = [
+            CreditJournalEntry::for_transaction(&CreditTransactionEntity::new_privacy_deposit(
+                user_id,
+                1000,
+                "SOL",
+                Uuid::new_v4(),
+            )),
+            CreditJournalEntry::for_transaction(&CreditTransactionEntity::new_spend(
+                user_id, 400, "SOL", None,
+            )),
+        ]
+        .concat();
+        let totals = LedgerAccountTotals::from_entries(&entries);
+
+        let debits: i64 = totals.iter().map(|t| t.debits).sum();
+        let credits: i64 = totals.iter().map(|t| t.credits).sum();
+        assert_eq!(debits, credits);
+
+        let balance = |account: LedgerAccount| {
+            let t = totals.iter().find(|t| t.account == account).unwrap();
+            account.normal_balance(t.debits, t.credits)
+        };
+        assert_eq!(balance(LedgerAccount::UserBalance), 600);
+        assert_eq!(balance(LedgerAccount::DepositClearing), 1000);
+        assert_eq!(balance(LedgerAccount::Revenue), 400);
+    }
+
+    #[test]
+    fn test_transfer_legs_net_to_zero() {
+        let sender = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+        let transfer_id = Uuid::new_v4();
+        let out = CreditTransactionEntity::new_transfer_out(
+            sender,
+            250,
+            "SOL",
+            "gift-1".into(),
+            transfer_id,
+            None,
+        );
+        let into = CreditTransactionEntity::new_transfer_in(sender, 250, "SOL", transfer_id, None)
+            .for_org(org_id);
+        let entries = [
+            CreditJournalEntry::for_transaction(&out),
+            CreditJournalEntry::for_transaction(&into),
+        ]
+        .concat();
+
+        assert_eq!(entries[0].debit, LedgerPosting::owner_of(&out));
+        assert_eq!(entries[0].credit.account, LedgerAccount::Transfers);
+        assert_eq!(entries[1].debit.account, LedgerAccount::Transfers);
+        assert_eq!(entries[1].credit.account, LedgerAccount::OrgBalance);
+        assert_eq!(entries[1].credit.owner_id, Some(org_id));
+
+        let totals = LedgerAccountTotals::from_entries(&entries);
+        let transfers = totals
+            .iter()
+            .find(|t| t.account == LedgerAccount::Transfers)
+            .unwrap();
+        assert_eq!(
+            LedgerAccount::Transfers.normal_balance(transfers.debits, transfers.credits),
+            0
+        );
+    }
+
+    #[test]
+    fn test_promotional_split() {
+        let user_id = Uuid::new_v4();
+        let grant = crate::repositories::CreditGrantEntity::new(
+            user_id,
+            300,
+            "SOL",
+            crate::repositories::CreditGrantSource::SignupBonus,
+            0,
+            None,
+            None,
+            None,
+        );
+        let entries =
+            CreditJournalEntry::for_transaction(&CreditTransactionEntity::new_grant(&grant));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].debit.account, LedgerAccount::Adjustments);
+        assert_eq!(
+            entries[0].credit.account,
+            LedgerAccount::PromotionalLiability
+        );
+        assert_eq!(entries[0].credit.owner_id, Some(user_id));
+
+        // 400 spend of which 100 came from the grant
+        let mut spend = CreditTransactionEntity::new_spend(user_id, 400, "SOL", None);
+        spend.grant_amount = -100;
+        let entries = CreditJournalEntry::for_transaction(&spend);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].debit.account, LedgerAccount::UserBalance);
+        assert_eq!(entries[0].amount, 300);
+        assert_eq!(
+            entries[1].debit.account,
+            LedgerAccount::PromotionalLiability
+        );
+        assert_eq!(entries[1].amount, 100);
+        assert!(entries
+            .iter()
+            .all(|e| e.credit.account == LedgerAccount::Revenue));
+    }
+}
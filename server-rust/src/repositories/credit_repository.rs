@@ -2,12 +2,23 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::repositories::pagination::{cap_limit, cap_offset};
+use crate::repositories::{
+    allocate_draw, promotional_draw, BalanceReconciliation, CreditBalanceDrift, CreditBudgetCharge,
+    CreditBudgetRepository, CreditGrantEntity, CreditGrantStatus, CreditJournalEntry,
+    LedgerAccount, LedgerAccountTotals, GRANT_REFERENCE_TYPE, REFUND_REFERENCE_TYPE,
+};
+
+/// Reference type of both legs of a credit transfer (`reference_id` is the
+/// transfer ID)
+pub const TRANSFER_REFERENCE_TYPE: &str = "credit_transfer";
 
 /// Credit transaction type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,6 +29,12 @@ pub enum CreditTxType {
     Spend,
     /// Manual adjustment
     Adjustment,
+    /// Promotional credit granted
+    Grant,
+    /// Unspent promotional credit forfeited on expiry
+    Expiration,
+    /// Credit moved between two balances (one debit and one credit leg)
+    Transfer,
 }
 
 impl CreditTxType {
@@ -26,6 +43,9 @@ impl CreditTxType {
             Self::Deposit => "deposit",
             Self::Spend => "spend",
             Self::Adjustment => "adjustment",
+            Self::Grant => "grant",
+            Self::Expiration => "expiration",
+            Self::Transfer => "transfer",
         }
     }
 
@@ -35,6 +55,36 @@ impl CreditTxType {
             "deposit" => Some(Self::Deposit),
             "spend" => Some(Self::Spend),
             "adjustment" => Some(Self::Adjustment),
+            "grant" => Some(Self::Grant),
+            "expiration" => Some(Self::Expiration),
+            "transfer" => Some(Self::Transfer),
+            _ => None,
+        }
+    }
+}
+
+/// Owner of the balance a transaction or hold moves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreditOwnerType {
+    /// The personal balance of `user_id`
+    User,
+    /// The shared balance of `org_id`; `user_id` is the acting member
+    Org,
+}
+
+impl CreditOwnerType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Org => "org",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "user" => Some(Self::User),
+            "org" => Some(Self::Org),
             _ => None,
         }
     }
@@ -59,12 +109,83 @@ impl CreditBalanceEntity {
     }
 }
 
+/// Credit balance owned by an organization (team wallet)
+#[derive(Debug, Clone)]
+pub struct OrgCreditBalanceEntity {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub balance: i64,
+    /// Credits reserved by pending holds (not available for spending)
+    pub held_balance: i64,
+    pub currency: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OrgCreditBalanceEntity {
+    /// Returns the available balance (total minus held)
+    pub fn available(&self) -> i64 {
+        self.balance - self.held_balance
+    }
+}
+
+/// Per-member rules for spending from an organization's balance
+#[derive(Debug, Clone)]
+pub struct OrgCreditMemberSettings {
+    pub org_id: Uuid,
+    pub user_id: Uuid,
+    /// Overrides the role default for spending (None = role decides)
+    pub spend_allowed: Option<bool>,
+    /// Maximum the member may spend per calendar month (UTC)
+    pub monthly_cap: Option<i64>,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A member's cap on spending from an organization's balance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemberSpendCap {
+    pub cap: i64,
+    /// Start of the period the cap covers
+    pub since: DateTime<Utc>,
+}
+
+impl MemberSpendCap {
+    /// Check that `amount` more fits under the cap, given what the member
+    /// has `used` (captured spends plus pending holds) since the period start
+    pub fn check(&self, used: i64, amount: i64) -> Result<(), AppError> {
+        if used + amount > self.cap {
+            return Err(AppError::Forbidden(format!(
+                "Monthly organization spending cap exceeded: used {}, cap {}, need {}",
+                used, self.cap, amount
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Limits a spend or hold must stay within, checked under the balance lock
+/// in the same transaction as the debit or reservation
+#[derive(Debug, Clone, Default)]
+pub struct SpendLimits {
+    /// Cap of the member spending from an organization's balance
+    pub member_cap: Option<MemberSpendCap>,
+    /// Budgets to consume the amount from, all or nothing
+    pub budgets: Vec<CreditBudgetCharge>,
+}
+
 /// Credit transaction entity (immutable audit log)
 #[derive(Debug, Clone)]
 pub struct CreditTransactionEntity {
     pub id: Uuid,
+    /// Balance owner for user-owned transactions; acting member for org-owned
     pub user_id: Uuid,
+    /// Whose balance this transaction moves
+    pub owner_type: CreditOwnerType,
+    /// Organization whose balance this transaction moves (org-owned only)
+    pub org_id: Option<Uuid>,
     pub amount: i64,
+    /// Part of `amount` drawn from or added to promotional grants (same sign)
+    pub grant_amount: i64,
     pub currency: String,
     pub tx_type: CreditTxType,
     pub deposit_session_id: Option<Uuid>,
@@ -95,7 +216,10 @@ impl CreditTransactionEntity {
         Self {
             id: Uuid::new_v4(),
             user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
             amount,
+            grant_amount: 0,
             currency: currency.to_string(),
             tx_type: CreditTxType::Deposit,
             deposit_session_id: Some(deposit_session_id),
@@ -121,7 +245,10 @@ impl CreditTransactionEntity {
         Self {
             id: Uuid::new_v4(),
             user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
             amount,
+            grant_amount: 0,
             currency: currency.to_string(),
             tx_type: CreditTxType::Deposit,
             deposit_session_id: Some(deposit_session_id),
@@ -135,6 +262,39 @@ impl CreditTransactionEntity {
         }
     }
 
+    /// Create a deposit transaction for a payment outside the privacy flow
+    ///
+    /// A negative `amount` reverses (part of) an earlier deposit, e.g. when
+    /// the payment is refunded or disputed.
+    pub fn new_deposit_with_reference(
+        user_id: Uuid,
+        amount: i64,
+        currency: &str,
+        idempotency_key: String,
+        reference_type: &str,
+        reference_id: Uuid,
+        metadata: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
+            amount,
+            grant_amount: 0,
+            currency: currency.to_string(),
+            tx_type: CreditTxType::Deposit,
+            deposit_session_id: None,
+            privacy_note_id: None,
+            idempotency_key: Some(idempotency_key),
+            reference_type: Some(reference_type.to_string()),
+            reference_id: Some(reference_id),
+            hold_id: None,
+            metadata,
+            created_at: Utc::now(),
+        }
+    }
+
     /// Create a new spend debit transaction (simple)
     pub fn new_spend(
         user_id: Uuid,
@@ -145,7 +305,10 @@ impl CreditTransactionEntity {
         Self {
             id: Uuid::new_v4(),
             user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
             amount: -amount.abs(), // Always negative for spend
+            grant_amount: 0,
             currency: currency.to_string(),
             tx_type: CreditTxType::Spend,
             deposit_session_id: None,
@@ -174,7 +337,10 @@ impl CreditTransactionEntity {
         Self {
             id: Uuid::new_v4(),
             user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
             amount: -amount.abs(),
+            grant_amount: 0,
             currency: currency.to_string(),
             tx_type: CreditTxType::Spend,
             deposit_session_id: None,
@@ -203,7 +369,10 @@ impl CreditTransactionEntity {
         Self {
             id: Uuid::new_v4(),
             user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
             amount: -amount.abs(),
+            grant_amount: 0,
             currency: currency.to_string(),
             tx_type: CreditTxType::Spend,
             deposit_session_id: None,
@@ -233,7 +402,10 @@ impl CreditTransactionEntity {
         Self {
             id: Uuid::new_v4(),
             user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
             amount,
+            grant_amount: 0,
             currency: currency.to_string(),
             tx_type: CreditTxType::Adjustment,
             deposit_session_id: None,
@@ -265,13 +437,16 @@ impl CreditTransactionEntity {
         Self {
             id: Uuid::new_v4(),
             user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
             amount,
+            grant_amount: 0,
             currency: currency.to_string(),
             tx_type: CreditTxType::Adjustment,
             deposit_session_id: None,
             privacy_note_id: None,
             idempotency_key: Some(format!("refund_request:{}", refund_request_id)),
-            reference_type: Some("refund".to_string()),
+            reference_type: Some(REFUND_REFERENCE_TYPE.to_string()),
             reference_id: Some(original_transaction_id),
             hold_id: None,
             metadata: Some(serde_json::json!({
@@ -282,6 +457,123 @@ impl CreditTransactionEntity {
             created_at: Utc::now(),
         }
     }
+
+    /// Create the transaction that adds a promotional grant to the balance
+    pub fn new_grant(grant: &CreditGrantEntity) -> Self {
+        Self {
+            id: grant.transaction_id,
+            user_id: grant.user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
+            amount: grant.amount,
+            grant_amount: grant.amount,
+            currency: grant.currency.clone(),
+            tx_type: CreditTxType::Grant,
+            deposit_session_id: None,
+            privacy_note_id: None,
+            idempotency_key: None,
+            reference_type: Some(GRANT_REFERENCE_TYPE.to_string()),
+            reference_id: Some(grant.id),
+            hold_id: None,
+            metadata: Some(serde_json::json!({
+                "source": grant.source.as_str(),
+                "admin_id": grant.created_by.map(|id| id.to_string()),
+                "reason": grant.reason
+            })),
+            created_at: grant.created_at,
+        }
+    }
+
+    /// Create the transaction that forfeits `amount` of an expired grant
+    pub fn new_grant_expiration(grant: &CreditGrantEntity, amount: i64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id: grant.user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
+            amount: -amount.abs(),
+            grant_amount: -amount.abs(),
+            currency: grant.currency.clone(),
+            tx_type: CreditTxType::Expiration,
+            deposit_session_id: None,
+            privacy_note_id: None,
+            idempotency_key: None,
+            reference_type: Some(GRANT_REFERENCE_TYPE.to_string()),
+            reference_id: Some(grant.id),
+            hold_id: None,
+            metadata: Some(serde_json::json!({ "source": grant.source.as_str() })),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Create the debit leg of a transfer out of `user_id`'s balance
+    ///
+    /// Carries the sender's idempotency key, so retrying a transfer finds
+    /// this leg.
+    pub fn new_transfer_out(
+        user_id: Uuid,
+        amount: i64,
+        currency: &str,
+        idempotency_key: String,
+        transfer_id: Uuid,
+        metadata: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
+            amount: -amount.abs(),
+            grant_amount: 0,
+            currency: currency.to_string(),
+            tx_type: CreditTxType::Transfer,
+            deposit_session_id: None,
+            privacy_note_id: None,
+            idempotency_key: Some(idempotency_key),
+            reference_type: Some(TRANSFER_REFERENCE_TYPE.to_string()),
+            reference_id: Some(transfer_id),
+            hold_id: None,
+            metadata,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Create the credit leg of a transfer into `user_id`'s balance
+    pub fn new_transfer_in(
+        user_id: Uuid,
+        amount: i64,
+        currency: &str,
+        transfer_id: Uuid,
+        metadata: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            owner_type: CreditOwnerType::User,
+            org_id: None,
+            amount: amount.abs(),
+            grant_amount: 0,
+            currency: currency.to_string(),
+            tx_type: CreditTxType::Transfer,
+            deposit_session_id: None,
+            privacy_note_id: None,
+            idempotency_key: Some(format!("transfer_in:{}", transfer_id)),
+            reference_type: Some(TRANSFER_REFERENCE_TYPE.to_string()),
+            reference_id: Some(transfer_id),
+            hold_id: None,
+            metadata,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Move the organization's balance instead of `user_id`'s
+    ///
+    /// `user_id` is kept as the member (or admin) who made the transaction.
+    pub fn for_org(mut self, org_id: Uuid) -> Self {
+        self.owner_type = CreditOwnerType::Org;
+        self.org_id = Some(org_id);
+        self
+    }
 }
 
 /// Aggregate credit statistics (admin view)
@@ -307,9 +599,9 @@ pub struct CurrencyCreditStats {
     pub total_credited: i64,
     /// Total spent (absolute value of spend transactions)
     pub total_spent: i64,
-    /// Total positive adjustments (refunds, bonuses)
+    /// Total positive adjustments (refunds, bonuses, promotional grants)
     pub total_positive_adjustments: i64,
-    /// Total negative adjustments (corrections, chargebacks)
+    /// Total negative adjustments (corrections, chargebacks, expired grants)
     pub total_negative_adjustments: i64,
     /// Current outstanding balance (total_credited - total_spent + net_adjustments)
     pub current_outstanding: i64,
@@ -317,7 +609,7 @@ pub struct CurrencyCreditStats {
     pub deposit_count: u64,
     /// Number of spend transactions
     pub spend_count: u64,
-    /// Number of adjustment transactions
+    /// Number of adjustment, grant and expiration transactions
     pub adjustment_count: u64,
 }
 
@@ -362,6 +654,8 @@ pub trait CreditRepository: Send + Sync {
 
     /// Add credit to a user's balance (atomic operation)
     /// Returns the new balance
+    ///
+    /// Org-owned transactions (`tx.org_id`) credit the organization's balance.
     async fn add_credit(
         &self,
         user_id: Uuid,
@@ -372,6 +666,8 @@ pub trait CreditRepository: Send + Sync {
 
     /// Deduct credit from a user's balance (atomic operation)
     /// Returns the new balance or error if insufficient funds
+    ///
+    /// Org-owned transactions (`tx.org_id`) debit the organization's balance.
     async fn deduct_credit(
         &self,
         user_id: Uuid,
@@ -380,7 +676,22 @@ pub trait CreditRepository: Send + Sync {
         tx: CreditTransactionEntity,
     ) -> Result<i64, AppError>;
 
-    /// Get transaction history for a user
+    /// Deduct a spend, checking `limits` atomically with the debit
+    ///
+    /// Like `deduct_credit`, but fails (and debits nothing) if the spend
+    /// would take the acting member past their cap or exceed a budget's
+    /// hard limit. Returns the new balance and the usage of each budget
+    /// afterwards, in order.
+    async fn deduct_credit_limited(
+        &self,
+        user_id: Uuid,
+        amount: i64,
+        currency: &str,
+        tx: CreditTransactionEntity,
+        limits: &SpendLimits,
+    ) -> Result<(i64, Vec<i64>), AppError>;
+
+    /// Get personal transaction history for a user (excludes org-owned entries)
     async fn get_transactions(
         &self,
         user_id: Uuid,
@@ -432,20 +743,359 @@ pub trait CreditRepository: Send + Sync {
         reference_type: &str,
         reference_id: Uuid,
     ) -> Result<i64, AppError>;
+
+    // =========================================================================
+    // Organization balances
+    // =========================================================================
+
+    /// Get or create an organization's balance entity for a currency
+    async fn get_or_create_org_balance(
+        &self,
+        org_id: Uuid,
+        currency: &str,
+    ) -> Result<OrgCreditBalanceEntity, AppError>;
+
+    /// Get transaction history of an organization's balance
+    async fn get_org_transactions(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+        tx_type: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<CreditTransactionEntity>, AppError>;
+
+    /// Get total transaction count of an organization's balance
+    async fn count_org_transactions(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+        tx_type: Option<&str>,
+    ) -> Result<u64, AppError>;
+
+    /// Sum what a member spent from an organization's balance since `since`
+    /// (positive amount)
+    async fn sum_org_member_spend(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        currency: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, AppError>;
+
+    /// Sum what a user transferred out since `since`, from their own or an
+    /// organization's balance (positive amount)
+    async fn sum_transfers_out(
+        &self,
+        user_id: Uuid,
+        currency: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, AppError>;
+
+    /// Net amount of a balance's transactions created before `before`,
+    /// i.e. the balance at that time
+    async fn sum_transactions_before(
+        &self,
+        owner_type: CreditOwnerType,
+        owner_id: Uuid,
+        currency: &str,
+        before: DateTime<Utc>,
+    ) -> Result<i64, AppError>;
+
+    /// Transactions of a balance created in `[from, to)`, oldest first, at
+    /// most `limit` (not capped to a page size)
+    async fn get_transactions_between(
+        &self,
+        owner_type: CreditOwnerType,
+        owner_id: Uuid,
+        currency: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<CreditTransactionEntity>, AppError>;
+
+    /// Get a member's spending rules for an organization's balance
+    async fn get_org_member_settings(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrgCreditMemberSettings>, AppError>;
+
+    /// List all member spending rules for an organization
+    async fn list_org_member_settings(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<OrgCreditMemberSettings>, AppError>;
+
+    /// Create or replace a member's spending rules
+    async fn upsert_org_member_settings(
+        &self,
+        settings: OrgCreditMemberSettings,
+    ) -> Result<OrgCreditMemberSettings, AppError>;
+
+    /// Remove a member's spending rules. Returns true if they existed.
+    async fn delete_org_member_settings(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, AppError>;
+
+    /// Delete an organization's balances and member rules (org purge).
+    /// Transactions are kept as the ledger.
+    async fn delete_org_credit_data(&self, org_id: Uuid) -> Result<(), AppError>;
+
+    // =========================================================================
+    // Promotional grants
+    // =========================================================================
+
+    /// Store a grant and add it to the user's balance (atomic operation)
+    /// Returns the new balance
+    async fn create_grant(&self, grant: CreditGrantEntity) -> Result<i64, AppError>;
+
+    /// Get a grant by ID
+    async fn get_grant(&self, grant_id: Uuid) -> Result<Option<CreditGrantEntity>, AppError>;
+
+    /// Get a user's grants in spend order
+    async fn get_grants(
+        &self,
+        user_id: Uuid,
+        currency: Option<&str>,
+        active_only: bool,
+    ) -> Result<Vec<CreditGrantEntity>, AppError>;
+
+    /// Expire a grant now, forfeiting its unspent credit.
+    ///
+    /// Credit reserved by pending holds cannot be forfeited; such a grant
+    /// stays active past its expiry until the holds settle.
+    async fn expire_grant(&self, grant_id: Uuid) -> Result<CreditGrantEntity, AppError>;
+
+    /// Forfeit up to `limit` active grants past their expiry at `now`.
+    /// Returns the number of grants that expired.
+    async fn expire_grants(&self, now: DateTime<Utc>, limit: u32) -> Result<u64, AppError>;
+
+    // =========================================================================
+    // Double-entry journal
+    // =========================================================================
+
+    /// Debit and credit totals per ledger account and currency, over journal
+    /// entries created in `[from, to)` (either bound may be open)
+    async fn get_ledger_totals(
+        &self,
+        currency: Option<&str>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LedgerAccountTotals>, AppError>;
+
+    /// Recompute every stored balance from the journal.
+    ///
+    /// Returns at most `limit` drifted balances.
+    async fn reconcile_balances(&self, limit: u32) -> Result<BalanceReconciliation, AppError>;
+}
+
+/// Consume `amount` from `charges` through the budget repository shared by
+/// an in-memory credit or hold repository
+pub(crate) async fn consume_budgets(
+    budget_repo: Option<&dyn CreditBudgetRepository>,
+    charges: &[CreditBudgetCharge],
+    amount: i64,
+) -> Result<Vec<i64>, AppError> {
+    if charges.is_empty() {
+        return Ok(Vec::new());
+    }
+    match budget_repo {
+        Some(repo) => repo.consume(charges, amount).await,
+        None => Err(AppError::Internal(anyhow::anyhow!(
+            "Spend has budgets but no budget repository is configured"
+        ))),
+    }
 }
 
 /// In-memory credit repository for development/testing
 pub struct InMemoryCreditRepository {
     balances: RwLock<HashMap<(Uuid, String), CreditBalanceEntity>>,
+    org_balances: RwLock<HashMap<(Uuid, String), OrgCreditBalanceEntity>>,
     transactions: RwLock<Vec<CreditTransactionEntity>>,
+    journal: RwLock<Vec<CreditJournalEntry>>,
+    member_settings: RwLock<HashMap<(Uuid, Uuid), OrgCreditMemberSettings>>,
+    grants: RwLock<HashMap<Uuid, CreditGrantEntity>>,
+    /// Budget repository consumed from by limited spends
+    /// In real impl, this is done in the same DB transaction as the debit
+    budget_repo: Option<Arc<dyn CreditBudgetRepository>>,
 }
 
 impl InMemoryCreditRepository {
     pub fn new() -> Self {
         Self {
             balances: RwLock::new(HashMap::new()),
+            org_balances: RwLock::new(HashMap::new()),
             transactions: RwLock::new(Vec::new()),
+            journal: RwLock::new(Vec::new()),
+            member_settings: RwLock::new(HashMap::new()),
+            grants: RwLock::new(HashMap::new()),
+            budget_repo: None,
+        }
+    }
+
+    /// Consume the budgets of limited spends from `budget_repo`
+    ///
+    /// Use this in `Storage::in_memory()` alongside a service with the same
+    /// budgets, so spends are checked against them under the balance lock.
+    pub fn with_budgets(mut self, budget_repo: Arc<dyn CreditBudgetRepository>) -> Self {
+        self.budget_repo = Some(budget_repo);
+        self
+    }
+
+    /// Consume `amount` from the budgets in `limits`
+    async fn consume_budgets(
+        &self,
+        limits: &SpendLimits,
+        amount: i64,
+    ) -> Result<Vec<i64>, AppError> {
+        consume_budgets(self.budget_repo.as_deref(), &limits.budgets, amount).await
+    }
+
+    /// Apply `amount` to an organization's balance, failing if a debit
+    /// exceeds the available balance
+    async fn apply_org_amount(
+        &self,
+        org_id: Uuid,
+        amount: i64,
+        currency: &str,
+        tx: CreditTransactionEntity,
+        limits: &SpendLimits,
+    ) -> Result<(i64, Vec<i64>), AppError> {
+        let mut balances = self.org_balances.write().await;
+        let mut transactions = self.transactions.write().await;
+
+        // Pending holds live in the hold repository, so only spends count here
+        if let Some(cap) = &limits.member_cap {
+            let used = transactions
+                .iter()
+                .filter(|t| {
+                    t.org_id == Some(org_id)
+                        && t.user_id == tx.user_id
+                        && t.tx_type == CreditTxType::Spend
+                        && t.currency.eq_ignore_ascii_case(currency)
+                        && t.created_at >= cap.since
+                })
+                .map(|t| t.amount.abs())
+                .sum();
+            cap.check(used, -amount)?;
+        }
+
+        let balance = balances
+            .entry((org_id, currency.to_string()))
+            .or_insert_with(|| OrgCreditBalanceEntity {
+                id: Uuid::new_v4(),
+                org_id,
+                balance: 0,
+                held_balance: 0,
+                currency: currency.to_string(),
+                updated_at: Utc::now(),
+            });
+
+        if amount < 0 && balance.available() < -amount {
+            return Err(AppError::Validation(format!(
+                "Insufficient organization credit balance: available {}, need {} (total: {}, held: {})",
+                balance.available(),
+                -amount,
+                balance.balance,
+                balance.held_balance
+            )));
+        }
+        let budget_usage = self.consume_budgets(limits, -amount).await?;
+
+        balance.balance += amount;
+        balance.updated_at = Utc::now();
+        self.journal
+            .write()
+            .await
+            .extend(CreditJournalEntry::for_transaction(&tx));
+        transactions.push(tx);
+
+        Ok((balance.balance, budget_usage))
+    }
+
+    /// Draw the promotional part of a personal debit from the user's grants
+    /// and record it on the transaction
+    fn draw_grants(
+        grants: &mut HashMap<Uuid, CreditGrantEntity>,
+        tx: &mut CreditTransactionEntity,
+        amount: i64,
+        balance_before: i64,
+    ) {
+        let mut active: Vec<&CreditGrantEntity> = grants
+            .values()
+            .filter(|g| {
+                g.user_id == tx.user_id
+                    && g.currency.eq_ignore_ascii_case(&tx.currency)
+                    && g.is_active()
+            })
+            .collect();
+        active.sort_by(|a, b| CreditGrantEntity::spend_order(a, b));
+        let promotional = active.iter().map(|g| g.remaining).sum();
+        let draw = promotional_draw(tx, amount, balance_before, promotional);
+
+        let takes = allocate_draw(active.iter().map(|g| (g.id, g.remaining)), draw);
+        for (grant_id, take) in takes {
+            if let Some(grant) = grants.get_mut(&grant_id) {
+                grant.draw(take, CreditGrantStatus::Depleted);
+            }
+        }
+        tx.grant_amount = -draw;
+    }
+
+    /// Expire a grant at `now`, forfeiting as much of its unspent credit as
+    /// is not reserved by holds
+    async fn forfeit_grant(
+        &self,
+        grant_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<CreditGrantEntity, AppError> {
+        let mut balances = self.balances.write().await;
+        let mut transactions = self.transactions.write().await;
+        let mut grants = self.grants.write().await;
+
+        let grant = grants
+            .get_mut(&grant_id)
+            .ok_or_else(|| AppError::NotFound(format!("Grant {} not found", grant_id)))?;
+        if !grant.is_active() {
+            return Err(AppError::Validation(format!(
+                "Grant is not active, status: {}",
+                grant.status.as_str()
+            )));
         }
+        grant.expires_at = Some(grant.expires_at.map_or(now, |e| e.min(now)));
+
+        let balance = balances.get_mut(&(grant.user_id, grant.currency.clone()));
+        let available = balance.as_ref().map_or(0, |b| b.available().max(0));
+        let forfeit = grant.remaining.min(available);
+        if let (Some(balance), true) = (balance, forfeit > 0) {
+            balance.balance -= forfeit;
+            balance.updated_at = Utc::now();
+
+            let tx = CreditTransactionEntity::new_grant_expiration(grant, forfeit);
+            self.journal
+                .write()
+                .await
+                .extend(CreditJournalEntry::for_transaction(&tx));
+            transactions.push(tx);
+        }
+        grant.draw(forfeit, CreditGrantStatus::Expired);
+
+        Ok(grant.clone())
+    }
+}
+
+/// Whether `tx` moves the balance of the given owner
+fn moves_balance_of(
+    tx: &CreditTransactionEntity,
+    owner_type: CreditOwnerType,
+    owner_id: Uuid,
+) -> bool {
+    match owner_type {
+        CreditOwnerType::User => tx.org_id.is_none() && tx.user_id == owner_id,
+        CreditOwnerType::Org => tx.org_id == Some(owner_id),
     }
 }
 
@@ -515,6 +1165,13 @@ impl CreditRepository for InMemoryCreditRepository {
         currency: &str,
         tx: CreditTransactionEntity,
     ) -> Result<i64, AppError> {
+        if let Some(org_id) = tx.org_id {
+            let (balance, _) = self
+                .apply_org_amount(org_id, amount, currency, tx, &SpendLimits::default())
+                .await?;
+            return Ok(balance);
+        }
+
         let mut balances = self.balances.write().await;
         let mut transactions = self.transactions.write().await;
 
@@ -532,6 +1189,10 @@ impl CreditRepository for InMemoryCreditRepository {
         balance.balance += amount;
         balance.updated_at = Utc::now();
 
+        self.journal
+            .write()
+            .await
+            .extend(CreditJournalEntry::for_transaction(&tx));
         transactions.push(tx);
 
         Ok(balance.balance)
@@ -544,6 +1205,26 @@ impl CreditRepository for InMemoryCreditRepository {
         currency: &str,
         tx: CreditTransactionEntity,
     ) -> Result<i64, AppError> {
+        let (balance, _) = self
+            .deduct_credit_limited(user_id, amount, currency, tx, &SpendLimits::default())
+            .await?;
+        Ok(balance)
+    }
+
+    async fn deduct_credit_limited(
+        &self,
+        user_id: Uuid,
+        amount: i64,
+        currency: &str,
+        mut tx: CreditTransactionEntity,
+        limits: &SpendLimits,
+    ) -> Result<(i64, Vec<i64>), AppError> {
+        if let Some(org_id) = tx.org_id {
+            return self
+                .apply_org_amount(org_id, -amount, currency, tx, limits)
+                .await;
+        }
+
         let mut balances = self.balances.write().await;
         let mut transactions = self.transactions.write().await;
 
@@ -561,13 +1242,24 @@ impl CreditRepository for InMemoryCreditRepository {
                 available, amount, balance.balance, balance.held_balance
             )));
         }
+        let budget_usage = self.consume_budgets(limits, amount).await?;
 
+        Self::draw_grants(
+            &mut *self.grants.write().await,
+            &mut tx,
+            amount,
+            balance.balance,
+        );
         balance.balance -= amount;
         balance.updated_at = Utc::now();
 
+        self.journal
+            .write()
+            .await
+            .extend(CreditJournalEntry::for_transaction(&tx));
         transactions.push(tx);
 
-        Ok(balance.balance)
+        Ok((balance.balance, budget_usage))
     }
 
     async fn get_transactions(
@@ -586,6 +1278,7 @@ impl CreditRepository for InMemoryCreditRepository {
             .iter()
             .filter(|t| {
                 t.user_id == user_id
+                    && t.org_id.is_none()
                     && currency.map_or(true, |c| t.currency == c)
                     && tx_type.map_or(true, |tt| t.tx_type.as_str() == tt)
             })
@@ -593,7 +1286,7 @@ impl CreditRepository for InMemoryCreditRepository {
             .collect();
 
         // Sort by created_at descending (newest first)
-        filtered.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        filtered.sort_by_key(|t| Reverse(t.created_at));
 
         Ok(filtered
             .into_iter()
@@ -613,6 +1306,7 @@ impl CreditRepository for InMemoryCreditRepository {
             .iter()
             .filter(|t| {
                 t.user_id == user_id
+                    && t.org_id.is_none()
                     && currency.map_or(true, |c| t.currency == c)
                     && tx_type.map_or(true, |tt| t.tx_type.as_str() == tt)
             })
@@ -621,6 +1315,7 @@ impl CreditRepository for InMemoryCreditRepository {
 
     async fn get_stats(&self) -> Result<CreditStats, AppError> {
         let balances = self.balances.read().await;
+        let org_balances = self.org_balances.read().await;
         let transactions = self.transactions.read().await;
 
         let mut stats = CreditStats::default();
@@ -629,8 +1324,9 @@ impl CreditRepository for InMemoryCreditRepository {
         let users: std::collections::HashSet<_> = balances.keys().map(|(uid, _)| *uid).collect();
         stats.total_users_with_balance = users.len() as u64;
 
-        // Sum all balances
-        stats.total_outstanding_lamports = balances.values().map(|b| b.balance).sum();
+        // Sum all balances, including organization balances
+        stats.total_outstanding_lamports = balances.values().map(|b| b.balance).sum::<i64>()
+            + org_balances.values().map(|b| b.balance).sum::<i64>();
 
         // Process transactions by currency
         for tx in transactions.iter() {
@@ -649,7 +1345,7 @@ impl CreditRepository for InMemoryCreditRepository {
                     currency_stats.spend_count += 1;
                     currency_stats.total_spent += tx.amount.abs();
                 }
-                CreditTxType::Adjustment => {
+                CreditTxType::Adjustment | CreditTxType::Grant | CreditTxType::Expiration => {
                     currency_stats.adjustment_count += 1;
                     if tx.amount >= 0 {
                         currency_stats.total_positive_adjustments += tx.amount;
@@ -657,17 +1353,24 @@ impl CreditRepository for InMemoryCreditRepository {
                         currency_stats.total_negative_adjustments += tx.amount.abs();
                     }
                 }
+                // Transfers move credit between balances without changing
+                // the total outstanding
+                CreditTxType::Transfer => {}
             }
         }
 
         // Calculate current outstanding for each currency
-        for ((_user_id, currency), balance) in balances.iter() {
+        let outstanding = balances
+            .values()
+            .map(|b| (&b.currency, b.balance))
+            .chain(org_balances.values().map(|b| (&b.currency, b.balance)));
+        for (currency, balance) in outstanding {
             let currency_stats = match currency.to_uppercase().as_str() {
                 "SOL" => &mut stats.sol,
                 "USD" => &mut stats.usd,
                 _ => continue,
             };
-            currency_stats.current_outstanding += balance.balance;
+            currency_stats.current_outstanding += balance;
         }
 
         Ok(stats)
@@ -693,7 +1396,10 @@ impl CreditRepository for InMemoryCreditRepository {
 
         // Process user's transactions for this currency
         for tx in transactions.iter() {
-            if tx.user_id != user_id || tx.currency.to_uppercase() != currency.to_uppercase() {
+            if tx.user_id != user_id
+                || tx.org_id.is_some()
+                || tx.currency.to_uppercase() != currency.to_uppercase()
+            {
                 continue;
             }
 
@@ -711,6 +1417,7 @@ impl CreditRepository for InMemoryCreditRepository {
                         stats.total_refunds += tx.amount;
                     }
                 }
+                CreditTxType::Grant | CreditTxType::Expiration | CreditTxType::Transfer => {}
             }
         }
 
@@ -764,6 +1471,7 @@ impl CreditRepository for InMemoryCreditRepository {
             .iter()
             .filter(|t| {
                 t.user_id == user_id
+                    && t.org_id.is_none()
                     && t.tx_type == CreditTxType::Adjustment
                     && t.amount > 0
                     && t.currency.eq_ignore_ascii_case(currency)
@@ -777,6 +1485,395 @@ impl CreditRepository for InMemoryCreditRepository {
             .sum();
         Ok(sum)
     }
+
+    async fn get_or_create_org_balance(
+        &self,
+        org_id: Uuid,
+        currency: &str,
+    ) -> Result<OrgCreditBalanceEntity, AppError> {
+        let mut balances = self.org_balances.write().await;
+        let balance = balances
+            .entry((org_id, currency.to_string()))
+            .or_insert_with(|| OrgCreditBalanceEntity {
+                id: Uuid::new_v4(),
+                org_id,
+                balance: 0,
+                held_balance: 0,
+                currency: currency.to_string(),
+                updated_at: Utc::now(),
+            });
+        Ok(balance.clone())
+    }
+
+    async fn get_org_transactions(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+        tx_type: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<CreditTransactionEntity>, AppError> {
+        let limit = cap_limit(limit);
+        let offset = cap_offset(offset);
+
+        let transactions = self.transactions.read().await;
+        let mut filtered: Vec<_> = transactions
+            .iter()
+            .filter(|t| {
+                t.org_id == Some(org_id)
+                    && currency.map_or(true, |c| t.currency == c)
+                    && tx_type.map_or(true, |tt| t.tx_type.as_str() == tt)
+            })
+            .cloned()
+            .collect();
+
+        filtered.sort_by_key(|t| std::cmp::Reverse(t.created_at));
+
+        Ok(filtered
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn count_org_transactions(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+        tx_type: Option<&str>,
+    ) -> Result<u64, AppError> {
+        let transactions = self.transactions.read().await;
+        Ok(transactions
+            .iter()
+            .filter(|t| {
+                t.org_id == Some(org_id)
+                    && currency.map_or(true, |c| t.currency == c)
+                    && tx_type.map_or(true, |tt| t.tx_type.as_str() == tt)
+            })
+            .count() as u64)
+    }
+
+    async fn sum_org_member_spend(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        currency: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let transactions = self.transactions.read().await;
+        Ok(transactions
+            .iter()
+            .filter(|t| {
+                t.org_id == Some(org_id)
+                    && t.user_id == user_id
+                    && t.tx_type == CreditTxType::Spend
+                    && t.currency.eq_ignore_ascii_case(currency)
+                    && t.created_at >= since
+            })
+            .map(|t| t.amount.abs())
+            .sum())
+    }
+
+    async fn sum_transfers_out(
+        &self,
+        user_id: Uuid,
+        currency: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let transactions = self.transactions.read().await;
+        Ok(transactions
+            .iter()
+            .filter(|t| {
+                t.user_id == user_id
+                    && t.tx_type == CreditTxType::Transfer
+                    && t.amount < 0
+                    && t.currency.eq_ignore_ascii_case(currency)
+                    && t.created_at >= since
+            })
+            .map(|t| t.amount.abs())
+            .sum())
+    }
+
+    async fn sum_transactions_before(
+        &self,
+        owner_type: CreditOwnerType,
+        owner_id: Uuid,
+        currency: &str,
+        before: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let transactions = self.transactions.read().await;
+        Ok(transactions
+            .iter()
+            .filter(|t| {
+                moves_balance_of(t, owner_type, owner_id)
+                    && t.currency.eq_ignore_ascii_case(currency)
+                    && t.created_at < before
+            })
+            .map(|t| t.amount)
+            .sum())
+    }
+
+    async fn get_transactions_between(
+        &self,
+        owner_type: CreditOwnerType,
+        owner_id: Uuid,
+        currency: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<CreditTransactionEntity>, AppError> {
+        let transactions = self.transactions.read().await;
+        let mut filtered: Vec<_> = transactions
+            .iter()
+            .filter(|t| {
+                moves_balance_of(t, owner_type, owner_id)
+                    && t.currency.eq_ignore_ascii_case(currency)
+                    && t.created_at >= from
+                    && t.created_at < to
+            })
+            .cloned()
+            .collect();
+        filtered.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        filtered.truncate(limit as usize);
+        Ok(filtered)
+    }
+
+    async fn get_org_member_settings(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrgCreditMemberSettings>, AppError> {
+        let settings = self.member_settings.read().await;
+        Ok(settings.get(&(org_id, user_id)).cloned())
+    }
+
+    async fn list_org_member_settings(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<OrgCreditMemberSettings>, AppError> {
+        let settings = self.member_settings.read().await;
+        let mut list: Vec<_> = settings
+            .values()
+            .filter(|s| s.org_id == org_id)
+            .cloned()
+            .collect();
+        list.sort_by_key(|s| s.user_id);
+        Ok(list)
+    }
+
+    async fn upsert_org_member_settings(
+        &self,
+        settings: OrgCreditMemberSettings,
+    ) -> Result<OrgCreditMemberSettings, AppError> {
+        let mut all = self.member_settings.write().await;
+        all.insert((settings.org_id, settings.user_id), settings.clone());
+        Ok(settings)
+    }
+
+    async fn delete_org_member_settings(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, AppError> {
+        let mut settings = self.member_settings.write().await;
+        Ok(settings.remove(&(org_id, user_id)).is_some())
+    }
+
+    async fn delete_org_credit_data(&self, org_id: Uuid) -> Result<(), AppError> {
+        self.org_balances
+            .write()
+            .await
+            .retain(|(id, _), _| *id != org_id);
+        self.member_settings
+            .write()
+            .await
+            .retain(|(id, _), _| *id != org_id);
+        Ok(())
+    }
+
+    async fn create_grant(&self, grant: CreditGrantEntity) -> Result<i64, AppError> {
+        let mut balances = self.balances.write().await;
+        let mut transactions = self.transactions.write().await;
+
+        let balance = balances
+            .entry((grant.user_id, grant.currency.clone()))
+            .or_insert_with(|| CreditBalanceEntity {
+                id: Uuid::new_v4(),
+                user_id: grant.user_id,
+                balance: 0,
+                held_balance: 0,
+                currency: grant.currency.clone(),
+                updated_at: Utc::now(),
+            });
+        balance.balance += grant.amount;
+        balance.updated_at = Utc::now();
+
+        let tx = CreditTransactionEntity::new_grant(&grant);
+        self.journal
+            .write()
+            .await
+            .extend(CreditJournalEntry::for_transaction(&tx));
+        transactions.push(tx);
+        self.grants.write().await.insert(grant.id, grant);
+
+        Ok(balance.balance)
+    }
+
+    async fn get_grant(&self, grant_id: Uuid) -> Result<Option<CreditGrantEntity>, AppError> {
+        Ok(self.grants.read().await.get(&grant_id).cloned())
+    }
+
+    async fn get_grants(
+        &self,
+        user_id: Uuid,
+        currency: Option<&str>,
+        active_only: bool,
+    ) -> Result<Vec<CreditGrantEntity>, AppError> {
+        let grants = self.grants.read().await;
+        let mut result: Vec<CreditGrantEntity> = grants
+            .values()
+            .filter(|g| {
+                g.user_id == user_id
+                    && currency.map_or(true, |c| g.currency.eq_ignore_ascii_case(c))
+                    && (!active_only || g.is_active())
+            })
+            .cloned()
+            .collect();
+        result.sort_by(CreditGrantEntity::spend_order);
+        Ok(result)
+    }
+
+    async fn expire_grant(&self, grant_id: Uuid) -> Result<CreditGrantEntity, AppError> {
+        self.forfeit_grant(grant_id, Utc::now()).await
+    }
+
+    async fn expire_grants(&self, now: DateTime<Utc>, limit: u32) -> Result<u64, AppError> {
+        let due: Vec<Uuid> = self
+            .grants
+            .read()
+            .await
+            .values()
+            .filter(|g| g.is_active() && g.is_expired_at(now))
+            .take(limit as usize)
+            .map(|g| g.id)
+            .collect();
+
+        let mut expired = 0;
+        for grant_id in due {
+            if self.forfeit_grant(grant_id, now).await?.status == CreditGrantStatus::Expired {
+                expired += 1;
+            }
+        }
+        Ok(expired)
+    }
+
+    async fn get_ledger_totals(
+        &self,
+        currency: Option<&str>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LedgerAccountTotals>, AppError> {
+        let journal = self.journal.read().await;
+        Ok(LedgerAccountTotals::from_entries(journal.iter().filter(
+            |e| {
+                currency.map_or(true, |c| e.currency == c)
+                    && from.map_or(true, |f| e.created_at >= f)
+                    && to.map_or(true, |t| e.created_at < t)
+            },
+        )))
+    }
+
+    async fn reconcile_balances(&self, limit: u32) -> Result<BalanceReconciliation, AppError> {
+        let balances = self.balances.read().await;
+        let org_balances = self.org_balances.read().await;
+        let transactions = self.transactions.read().await;
+        let journal = self.journal.read().await;
+
+        let mut ledger: HashMap<(LedgerAccount, Uuid, String), i64> = HashMap::new();
+        for entry in journal.iter() {
+            for (posting, delta) in [(entry.credit, entry.amount), (entry.debit, -entry.amount)] {
+                if let Some(owner_id) = posting.owner_id {
+                    // Promotional credit is part of the user's stored balance
+                    let account = match posting.account {
+                        LedgerAccount::PromotionalLiability => LedgerAccount::UserBalance,
+                        account => account,
+                    };
+                    *ledger
+                        .entry((account, owner_id, entry.currency.clone()))
+                        .or_default() += delta;
+                }
+            }
+        }
+
+        let stored = balances
+            .values()
+            .map(|b| {
+                (
+                    (
+                        LedgerAccount::UserBalance,
+                        b.user_id,
+                        b.currency.to_uppercase(),
+                    ),
+                    b.balance,
+                )
+            })
+            .chain(org_balances.values().map(|b| {
+                (
+                    (
+                        LedgerAccount::OrgBalance,
+                        b.org_id,
+                        b.currency.to_uppercase(),
+                    ),
+                    b.balance,
+                )
+            }));
+
+        let mut result = BalanceReconciliation::default();
+        let mut drift = Vec::new();
+        for (key, stored_balance) in stored {
+            result.balances_checked += 1;
+            let ledger_balance = ledger.remove(&key).unwrap_or(0);
+            if stored_balance != ledger_balance {
+                drift.push((key, stored_balance, ledger_balance));
+            }
+        }
+        // Journal activity for owners without a stored balance
+        drift.extend(
+            ledger
+                .into_iter()
+                .filter(|(_, balance)| *balance != 0)
+                .map(|(key, balance)| (key, 0, balance)),
+        );
+
+        result.drift = drift
+            .into_iter()
+            .take(limit as usize)
+            .map(
+                |((account, owner_id, currency), stored_balance, ledger_balance)| {
+                    CreditBalanceDrift {
+                        owner_type: if account == LedgerAccount::OrgBalance {
+                            CreditOwnerType::Org
+                        } else {
+                            CreditOwnerType::User
+                        },
+                        owner_id,
+                        currency,
+                        stored_balance,
+                        ledger_balance,
+                    }
+                },
+            )
+            .collect();
+
+        let posted: std::collections::HashSet<Uuid> =
+            journal.iter().filter_map(|e| e.transaction_id).collect();
+        result.unposted_transactions = transactions
+            .iter()
+            .filter(|t| !posted.contains(&t.id))
+            .count() as u64;
+
+        Ok(result)
+    }
 }
 
 #[cfg(test)]
@@ -926,4 +2023,184 @@ mod tests {
             .unwrap();
         assert_eq!(transactions.len() as u32, DEFAULT_MAX_PAGE_SIZE);
     }
+
+    #[tokio::test]
+    async fn test_org_transactions_use_org_balance() {
+        let repo = InMemoryCreditRepository::new();
+        let org_id = Uuid::new_v4();
+        let admin_id = Uuid::new_v4();
+        let member_id = Uuid::new_v4();
+
+        let tx = CreditTransactionEntity::new_adjustment(
+            admin_id, 1000, "SOL", admin_id, "top-up", None, None,
+        )
+        .for_org(org_id);
+        assert_eq!(
+            repo.add_credit(admin_id, 1000, "SOL", tx).await.unwrap(),
+            1000
+        );
+
+        let tx = CreditTransactionEntity::new_spend(member_id, 300, "SOL", None).for_org(org_id);
+        assert_eq!(
+            repo.deduct_credit(member_id, 300, "SOL", tx).await.unwrap(),
+            700
+        );
+
+        // Personal balances and history are untouched
+        assert_eq!(repo.get_balance(admin_id, "SOL").await.unwrap(), 0);
+        assert_eq!(repo.get_balance(member_id, "SOL").await.unwrap(), 0);
+        assert_eq!(
+            repo.count_transactions(member_id, None, None)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let balance = repo.get_or_create_org_balance(org_id, "SOL").await.unwrap();
+        assert_eq!(balance.balance, 700);
+        assert_eq!(
+            repo.count_org_transactions(org_id, None, None)
+                .await
+                .unwrap(),
+            2
+        );
+        let since = Utc::now() - chrono::Duration::days(1);
+        assert_eq!(
+            repo.sum_org_member_spend(org_id, member_id, "SOL", since)
+                .await
+                .unwrap(),
+            300
+        );
+
+        let tx = CreditTransactionEntity::new_spend(member_id, 800, "SOL", None).for_org(org_id);
+        assert!(repo.deduct_credit(member_id, 800, "SOL", tx).await.is_err());
+
+        // The member's cap is checked together with the debit
+        let limits = SpendLimits {
+            member_cap: Some(MemberSpendCap { cap: 500, since }),
+            ..Default::default()
+        };
+        let tx = CreditTransactionEntity::new_spend(member_id, 300, "SOL", None).for_org(org_id);
+        assert!(matches!(
+            repo.deduct_credit_limited(member_id, 300, "SOL", tx, &limits)
+                .await,
+            Err(AppError::Forbidden(_))
+        ));
+        let tx = CreditTransactionEntity::new_spend(member_id, 200, "SOL", None).for_org(org_id);
+        assert_eq!(
+            repo.deduct_credit_limited(member_id, 200, "SOL", tx, &limits)
+                .await
+                .unwrap(),
+            (500, Vec::new())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_balances_detects_drift() {
+        let repo = InMemoryCreditRepository::new();
+        let user_id = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+
+        let tx = CreditTransactionEntity::new_privacy_deposit(user_id, 1000, "SOL", Uuid::new_v4());
+        repo.add_credit(user_id, 1000, "SOL", tx).await.unwrap();
+        let tx = CreditTransactionEntity::new_spend(user_id, 250, "SOL", None);
+        repo.deduct_credit(user_id, 250, "SOL", tx).await.unwrap();
+        let tx = CreditTransactionEntity::new_adjustment(
+            user_id, 500, "SOL", user_id, "top-up", None, None,
+        )
+        .for_org(org_id);
+        repo.add_credit(user_id, 500, "SOL", tx).await.unwrap();
+
+        let reconciliation = repo.reconcile_balances(100).await.unwrap();
+        assert_eq!(reconciliation.balances_checked, 2);
+        assert!(reconciliation.drift.is_empty());
+        assert_eq!(reconciliation.unposted_transactions, 0);
+
+        // A balance changed outside the ledger shows up as drift
+        repo.balances
+            .write()
+            .await
+            .get_mut(&(user_id, "SOL".to_string()))
+            .unwrap()
+            .balance += 5;
+
+        let reconciliation = repo.reconcile_balances(100).await.unwrap();
+        assert_eq!(reconciliation.drift.len(), 1);
+        let drift = &reconciliation.drift[0];
+        assert_eq!(drift.owner_type, CreditOwnerType::User);
+        assert_eq!(drift.owner_id, user_id);
+        assert_eq!(drift.stored_balance, 755);
+        assert_eq!(drift.ledger_balance, 750);
+        assert_eq!(drift.drift(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_grants_spent_first_and_expire() {
+        use crate::repositories::CreditGrantSource;
+        use chrono::Duration;
+
+        let repo = InMemoryCreditRepository::new();
+        let user_id = Uuid::new_v4();
+        let grant = |amount, priority, expires_at| {
+            CreditGrantEntity::new(
+                user_id,
+                amount,
+                "SOL",
+                CreditGrantSource::Promotion,
+                priority,
+                expires_at,
+                None,
+                None,
+            )
+        };
+
+        let tx = CreditTransactionEntity::new_privacy_deposit(user_id, 1000, "SOL", Uuid::new_v4());
+        repo.add_credit(user_id, 1000, "SOL", tx).await.unwrap();
+        let late = grant(200, 0, Some(Utc::now() + Duration::days(30)));
+        let soon = grant(100, 0, Some(Utc::now() + Duration::days(1)));
+        repo.create_grant(late.clone()).await.unwrap();
+        assert_eq!(repo.create_grant(soon.clone()).await.unwrap(), 1300);
+
+        // Spends drain the soonest-expiring grant first, then the next one
+        let tx = CreditTransactionEntity::new_spend(user_id, 150, "SOL", None);
+        repo.deduct_credit(user_id, 150, "SOL", tx).await.unwrap();
+        let grants = repo.get_grants(user_id, Some("SOL"), false).await.unwrap();
+        assert_eq!(grants[0].id, soon.id);
+        assert_eq!(grants[0].status, CreditGrantStatus::Depleted);
+        assert_eq!(grants[1].remaining, 150);
+
+        let spend = repo
+            .get_transactions(user_id, None, Some("spend"), 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(spend[0].grant_amount, -150);
+
+        // Corrections come out of paid credit
+        let tx = CreditTransactionEntity::new_adjustment(
+            user_id, -100, "SOL", user_id, "fix", None, None,
+        );
+        repo.deduct_credit(user_id, 100, "SOL", tx).await.unwrap();
+        assert_eq!(
+            repo.get_grant(late.id).await.unwrap().unwrap().remaining,
+            150
+        );
+
+        // Nothing is due yet; at the later expiry the rest is forfeited
+        assert_eq!(repo.expire_grants(Utc::now(), 100).await.unwrap(), 0);
+        let expired = repo
+            .expire_grants(Utc::now() + Duration::days(31), 100)
+            .await
+            .unwrap();
+        assert_eq!(expired, 1);
+        assert_eq!(repo.get_balance(user_id, "SOL").await.unwrap(), 900);
+        assert!(repo
+            .get_grants(user_id, None, true)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Grant credit reconciles against the user's stored balance
+        let reconciliation = repo.reconcile_balances(100).await.unwrap();
+        assert!(reconciliation.drift.is_empty());
+    }
 }
@@ -0,0 +1,275 @@
+//! Invite link repository trait and implementations
+//!
+//! Unlike `InviteEntity` (one recipient, single use), an invite link is a
+//! shareable token that any authenticated user can redeem until it expires,
+//! is revoked, or reaches `max_uses`. An optional domain allowlist restricts
+//! redemption to users with a verified email at one of those domains.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::membership_repository::OrgRole;
+use crate::repositories::pagination::{cap_limit, cap_offset};
+
+/// Invite link entity for storage
+#[derive(Debug, Clone)]
+pub struct InviteLinkEntity {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// Role granted to users who redeem the link
+    pub role: OrgRole,
+    pub token_hash: String,
+    /// Maximum redemptions (None = unlimited until expiry)
+    pub max_uses: Option<u32>,
+    pub use_count: u32,
+    /// Lowercased email domains allowed to redeem (empty = any)
+    pub allowed_domains: Vec<String>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl InviteLinkEntity {
+    /// Check if the link can still be redeemed
+    pub fn is_usable(&self) -> bool {
+        self.revoked_at.is_none()
+            && self.expires_at > Utc::now()
+            && self.max_uses.map_or(true, |max| self.use_count < max)
+    }
+
+    /// Check whether an email address satisfies the domain restriction
+    pub fn allows_email(&self, email: &str) -> bool {
+        if self.allowed_domains.is_empty() {
+            return true;
+        }
+        email
+            .rsplit_once('@')
+            .map(|(_, domain)| {
+                let domain = domain.to_lowercase();
+                self.allowed_domains.contains(&domain)
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Invite link repository trait
+#[async_trait]
+pub trait InviteLinkRepository: Send + Sync {
+    /// Create a new invite link
+    async fn create(&self, link: InviteLinkEntity) -> Result<InviteLinkEntity, AppError>;
+
+    /// Find invite link by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<InviteLinkEntity>, AppError>;
+
+    /// Find invite link by token hash
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<InviteLinkEntity>, AppError>;
+
+    /// Find invite links for an organization (newest first, paged)
+    async fn find_by_org_paged(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<InviteLinkEntity>, AppError>;
+
+    /// Count invite links for an organization
+    async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
+
+    /// Atomically consume one use if the link is still usable.
+    /// Returns the updated link, or None if revoked/expired/exhausted.
+    async fn consume_use(&self, id: Uuid) -> Result<Option<InviteLinkEntity>, AppError>;
+
+    /// Give back a consumed use (rollback on downstream failure)
+    async fn release_use(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Revoke a link. Returns false if it was already revoked or missing.
+    async fn revoke(&self, id: Uuid) -> Result<bool, AppError>;
+
+    /// Delete all invite links for an organization
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
+}
+
+/// In-memory invite link repository for development/testing
+pub struct InMemoryInviteLinkRepository {
+    links: RwLock<HashMap<Uuid, InviteLinkEntity>>,
+}
+
+impl InMemoryInviteLinkRepository {
+    pub fn new() -> Self {
+        Self {
+            links: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryInviteLinkRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InviteLinkRepository for InMemoryInviteLinkRepository {
+    async fn create(&self, link: InviteLinkEntity) -> Result<InviteLinkEntity, AppError> {
+        let mut links = self.links.write().await;
+        if links.values().any(|l| l.token_hash == link.token_hash) {
+            return Err(AppError::Validation(
+                "Invite token collision - please retry".into(),
+            ));
+        }
+        links.insert(link.id, link.clone());
+        Ok(link)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<InviteLinkEntity>, AppError> {
+        Ok(self.links.read().await.get(&id).cloned())
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<InviteLinkEntity>, AppError> {
+        let links = self.links.read().await;
+        Ok(links.values().find(|l| l.token_hash == token_hash).cloned())
+    }
+
+    async fn find_by_org_paged(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<InviteLinkEntity>, AppError> {
+        let links = self.links.read().await;
+        let mut results: Vec<_> = links
+            .values()
+            .filter(|l| l.org_id == org_id)
+            .cloned()
+            .collect();
+        results.sort_by_key(|l| Reverse(l.created_at));
+        Ok(results
+            .into_iter()
+            .skip(cap_offset(offset) as usize)
+            .take(cap_limit(limit) as usize)
+            .collect())
+    }
+
+    async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let links = self.links.read().await;
+        Ok(links.values().filter(|l| l.org_id == org_id).count() as u64)
+    }
+
+    async fn consume_use(&self, id: Uuid) -> Result<Option<InviteLinkEntity>, AppError> {
+        // Write lock held across check-and-increment so concurrent redemptions
+        // cannot exceed max_uses.
+        let mut links = self.links.write().await;
+        match links.get_mut(&id) {
+            Some(link) if link.is_usable() => {
+                link.use_count += 1;
+                Ok(Some(link.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn release_use(&self, id: Uuid) -> Result<(), AppError> {
+        let mut links = self.links.write().await;
+        if let Some(link) = links.get_mut(&id) {
+            link.use_count = link.use_count.saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    async fn revoke(&self, id: Uuid) -> Result<bool, AppError> {
+        let mut links = self.links.write().await;
+        match links.get_mut(&id) {
+            Some(link) if link.revoked_at.is_none() => {
+                link.revoked_at = Some(Utc::now());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let mut links = self.links.write().await;
+        let before = links.len();
+        links.retain(|_, l| l.org_id != org_id);
+        Ok((before - links.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn link(org_id: Uuid, max_uses: Option<u32>) -> InviteLinkEntity {
+        InviteLinkEntity {
+            id: Uuid::new_v4(),
+            org_id,
+            role: OrgRole::Member,
+            token_hash: Uuid::new_v4().to_string(),
+            max_uses,
+            use_count: 0,
+            allowed_domains: vec![],
+            created_by: Uuid::new_v4(),
+            created_at: Utc::now(),
+            expires_at: Utc::now() + Duration::days(1),
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_allows_email_domain_restriction() {
+        let mut l = link(Uuid::new_v4(), None);
+        assert!(l.allows_email("anyone@example.com"));
+
+        l.allowed_domains = vec!["acme.com".into()];
+        assert!(l.allows_email("alice@ACME.com"));
+        assert!(!l.allows_email("alice@acme.com.evil.io"));
+        assert!(!l.allows_email("alice@sub.acme.com"));
+        assert!(!l.allows_email("not-an-email"));
+    }
+
+    #[tokio::test]
+    async fn test_consume_respects_max_uses() {
+        let repo = InMemoryInviteLinkRepository::new();
+        let created = repo.create(link(Uuid::new_v4(), Some(2))).await.unwrap();
+
+        assert!(repo.consume_use(created.id).await.unwrap().is_some());
+        assert!(repo.consume_use(created.id).await.unwrap().is_some());
+        assert!(repo.consume_use(created.id).await.unwrap().is_none());
+
+        repo.release_use(created.id).await.unwrap();
+        let again = repo.consume_use(created.id).await.unwrap().unwrap();
+        assert_eq!(again.use_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_and_expired_links_are_unusable() {
+        let repo = InMemoryInviteLinkRepository::new();
+        let org_id = Uuid::new_v4();
+
+        let revoked = repo.create(link(org_id, None)).await.unwrap();
+        assert!(repo.revoke(revoked.id).await.unwrap());
+        assert!(!repo.revoke(revoked.id).await.unwrap());
+        assert!(repo.consume_use(revoked.id).await.unwrap().is_none());
+
+        let mut expired = link(org_id, None);
+        expired.expires_at = Utc::now() - Duration::seconds(1);
+        let expired = repo.create(expired).await.unwrap();
+        assert!(repo.consume_use(expired.id).await.unwrap().is_none());
+
+        assert_eq!(repo.count_by_org(org_id).await.unwrap(), 2);
+        assert_eq!(repo.delete_by_org(org_id).await.unwrap(), 2);
+    }
+}
@@ -3,11 +3,13 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::errors::AppError;
+use crate::repositories::org_repository::OrgRepository;
 
 /// Role within an organization
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -113,9 +115,22 @@ pub trait MembershipRepository: Send + Sync {
         org_id: Uuid,
     ) -> Result<Option<MembershipEntity>, AppError>;
 
+    /// Find membership by user and org, including organizations pending deletion
+    async fn find_by_user_and_org_including_pending(
+        &self,
+        user_id: Uuid,
+        org_id: Uuid,
+    ) -> Result<Option<MembershipEntity>, AppError>;
+
     /// Find all memberships for a user
     async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<MembershipEntity>, AppError>;
 
+    /// Find all memberships for a user, including organizations pending deletion
+    async fn find_by_user_including_pending(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<MembershipEntity>, AppError>;
+
     /// Find memberships for a user (paged)
     async fn find_by_user_paged(
         &self,
@@ -170,6 +185,9 @@ pub trait MembershipRepository: Send + Sync {
     /// Delete all memberships for an organization
     async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
 
+    /// Delete all memberships for a user
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, AppError>;
+
     /// Count members in an organization
     async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
 
@@ -218,6 +236,14 @@ impl MembershipRepository for InMemoryMembershipRepository {
             .cloned())
     }
 
+    async fn find_by_user_and_org_including_pending(
+        &self,
+        user_id: Uuid,
+        org_id: Uuid,
+    ) -> Result<Option<MembershipEntity>, AppError> {
+        self.find_by_user_and_org(user_id, org_id).await
+    }
+
     async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<MembershipEntity>, AppError> {
         let memberships = self.memberships.read().await;
         Ok(memberships
@@ -227,6 +253,13 @@ impl MembershipRepository for InMemoryMembershipRepository {
             .collect())
     }
 
+    async fn find_by_user_including_pending(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<MembershipEntity>, AppError> {
+        self.find_by_user(user_id).await
+    }
+
     async fn find_by_user_paged(
         &self,
         user_id: Uuid,
@@ -400,6 +433,13 @@ impl MembershipRepository for InMemoryMembershipRepository {
         Ok(count)
     }
 
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, AppError> {
+        let mut memberships = self.memberships.write().await;
+        let before = memberships.len();
+        memberships.retain(|_, m| m.user_id != user_id);
+        Ok((before - memberships.len()) as u64)
+    }
+
     async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
         let memberships = self.memberships.read().await;
         Ok(memberships.values().filter(|m| m.org_id == org_id).count() as u64)
@@ -422,6 +462,196 @@ impl MembershipRepository for InMemoryMembershipRepository {
     }
 }
 
+/// Membership repository wrapper that hides memberships of organizations
+/// pending deletion from the per-user lookups (`find_by_user_and_org`,
+/// `find_by_user`, `find_by_user_paged` and `count_by_user`).
+///
+/// Org-scoped access checks and the org context issued at sign-in go through
+/// those lookups, so members lose access for the whole grace period while
+/// their membership rows are kept for a possible restore. All other methods
+/// delegate unchanged.
+pub struct ActiveOrgMembershipRepository {
+    inner: Arc<dyn MembershipRepository>,
+    org_repo: Arc<dyn OrgRepository>,
+}
+
+impl ActiveOrgMembershipRepository {
+    pub fn new(inner: Arc<dyn MembershipRepository>, org_repo: Arc<dyn OrgRepository>) -> Self {
+        Self { inner, org_repo }
+    }
+
+    /// Page size used when scanning a user's memberships
+    const SCAN_PAGE_SIZE: u32 = 100;
+
+    async fn active_only(
+        &self,
+        memberships: Vec<MembershipEntity>,
+    ) -> Result<Vec<MembershipEntity>, AppError> {
+        if memberships.is_empty() {
+            return Ok(memberships);
+        }
+        let org_ids: Vec<Uuid> = memberships.iter().map(|m| m.org_id).collect();
+        let active: HashSet<Uuid> = self
+            .org_repo
+            .find_by_ids(&org_ids)
+            .await?
+            .into_iter()
+            .filter(|org| !org.is_pending_deletion())
+            .map(|org| org.id)
+            .collect();
+        Ok(memberships
+            .into_iter()
+            .filter(|m| active.contains(&m.org_id))
+            .collect())
+    }
+
+    /// Active memberships of a user, newest first, stopping once `wanted`
+    /// have been collected (all of them when `None`).
+    async fn scan_active_by_user(
+        &self,
+        user_id: Uuid,
+        wanted: Option<usize>,
+    ) -> Result<Vec<MembershipEntity>, AppError> {
+        let mut active = Vec::new();
+        let mut offset = 0u32;
+        loop {
+            let page = self
+                .inner
+                .find_by_user_paged(user_id, Self::SCAN_PAGE_SIZE, offset)
+                .await?;
+            let fetched = page.len() as u32;
+            active.extend(self.active_only(page).await?);
+            offset += fetched;
+            if fetched < Self::SCAN_PAGE_SIZE || wanted.is_some_and(|n| active.len() >= n) {
+                return Ok(active);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MembershipRepository for ActiveOrgMembershipRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<MembershipEntity>, AppError> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_user_and_org(
+        &self,
+        user_id: Uuid,
+        org_id: Uuid,
+    ) -> Result<Option<MembershipEntity>, AppError> {
+        let Some(membership) = self.inner.find_by_user_and_org(user_id, org_id).await? else {
+            return Ok(None);
+        };
+        match self.org_repo.find_by_id(org_id).await? {
+            Some(org) if !org.is_pending_deletion() => Ok(Some(membership)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn find_by_user_and_org_including_pending(
+        &self,
+        user_id: Uuid,
+        org_id: Uuid,
+    ) -> Result<Option<MembershipEntity>, AppError> {
+        self.inner.find_by_user_and_org(user_id, org_id).await
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<MembershipEntity>, AppError> {
+        let memberships = self.inner.find_by_user(user_id).await?;
+        self.active_only(memberships).await
+    }
+
+    async fn find_by_user_including_pending(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<MembershipEntity>, AppError> {
+        self.inner.find_by_user(user_id).await
+    }
+
+    async fn find_by_user_paged(
+        &self,
+        user_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<MembershipEntity>, AppError> {
+        let wanted = offset as usize + limit as usize;
+        Ok(self
+            .scan_active_by_user(user_id, Some(wanted))
+            .await?
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn find_by_org(&self, org_id: Uuid) -> Result<Vec<MembershipEntity>, AppError> {
+        self.inner.find_by_org(org_id).await
+    }
+
+    async fn find_by_org_with_users(&self, org_id: Uuid) -> Result<Vec<MemberWithUser>, AppError> {
+        self.inner.find_by_org_with_users(org_id).await
+    }
+
+    async fn find_by_org_with_users_paged(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<MemberWithUser>, AppError> {
+        self.inner
+            .find_by_org_with_users_paged(org_id, limit, offset)
+            .await
+    }
+
+    async fn create(&self, membership: MembershipEntity) -> Result<MembershipEntity, AppError> {
+        self.inner.create(membership).await
+    }
+
+    async fn update_role(&self, id: Uuid, role: OrgRole) -> Result<MembershipEntity, AppError> {
+        self.inner.update_role(id, role).await
+    }
+
+    async fn update_role_if_not_last_owner(
+        &self,
+        id: Uuid,
+        org_id: Uuid,
+        new_role: OrgRole,
+    ) -> Result<Option<MembershipEntity>, AppError> {
+        self.inner
+            .update_role_if_not_last_owner(id, org_id, new_role)
+            .await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), AppError> {
+        self.inner.delete(id).await
+    }
+
+    async fn delete_if_not_last_owner(&self, id: Uuid, org_id: Uuid) -> Result<bool, AppError> {
+        self.inner.delete_if_not_last_owner(id, org_id).await
+    }
+
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        self.inner.delete_by_org(org_id).await
+    }
+
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, AppError> {
+        self.inner.delete_by_user(user_id).await
+    }
+
+    async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        self.inner.count_by_org(org_id).await
+    }
+
+    async fn count_by_user(&self, user_id: Uuid) -> Result<u64, AppError> {
+        Ok(self.scan_active_by_user(user_id, None).await?.len() as u64)
+    }
+
+    async fn count_owners(&self, org_id: Uuid) -> Result<u64, AppError> {
+        self.inner.count_owners(org_id).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -613,4 +843,61 @@ mod tests {
         let count = repo.count_owners(org_id).await.unwrap();
         assert_eq!(count, 1);
     }
+
+    #[tokio::test]
+    async fn test_active_org_wrapper_hides_pending_deletion() {
+        use crate::repositories::org_repository::{InMemoryOrgRepository, OrgEntity};
+
+        let org_repo = Arc::new(InMemoryOrgRepository::new());
+        let repo = ActiveOrgMembershipRepository::new(
+            Arc::new(InMemoryMembershipRepository::new()),
+            org_repo.clone(),
+        );
+        let user_id = Uuid::new_v4();
+        let org = org_repo
+            .create(OrgEntity::new("Org".into(), "org".into(), user_id, false))
+            .await
+            .unwrap();
+        repo.create(MembershipEntity::new(user_id, org.id, OrgRole::Owner))
+            .await
+            .unwrap();
+
+        assert!(repo
+            .find_by_user_and_org(user_id, org.id)
+            .await
+            .unwrap()
+            .is_some());
+
+        org_repo
+            .schedule_deletion(org.id, Utc::now() + chrono::Duration::days(1))
+            .await
+            .unwrap();
+        assert!(repo
+            .find_by_user_and_org(user_id, org.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repo.find_by_user(user_id).await.unwrap().is_empty());
+        assert!(repo
+            .find_by_user_paged(user_id, 10, 0)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(repo.count_by_user(user_id).await.unwrap(), 0);
+        // Rows are retained for restore
+        assert_eq!(repo.count_by_org(org.id).await.unwrap(), 1);
+        assert!(repo
+            .find_by_user_and_org_including_pending(user_id, org.id)
+            .await
+            .unwrap()
+            .is_some());
+
+        org_repo.restore(org.id).await.unwrap();
+        assert!(repo
+            .find_by_user_and_org(user_id, org.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(repo.count_by_user(user_id).await.unwrap(), 1);
+    }
 }
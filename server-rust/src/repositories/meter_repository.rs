@@ -0,0 +1,838 @@
+//! Usage meter, price catalog and usage event repository
+//!
+//! A meter is a named unit of billable usage (e.g. `api_call`,
+//! `gpu_second`) with a price schedule. Organizations can be given their own
+//! schedule for a meter. Usage events are reported with idempotency keys and
+//! billed in batches by the usage billing worker, which debits credits per
+//! (meter, payer, month) batch.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Maximum number of tiers in a price schedule
+pub const MAX_PRICE_TIERS: usize = 10;
+
+/// Reference type of transactions that pay for metered usage
+pub const USAGE_REFERENCE_TYPE: &str = "usage";
+
+/// How a schedule's tiers apply to usage within a billing period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PricingModel {
+    /// Graduated: each unit is priced by the tier it falls in
+    Tiered,
+    /// Each batch is priced by the tier the period-to-date total reaches
+    Volume,
+}
+
+/// One step of a price schedule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceTier {
+    /// Last unit of the period (inclusive) priced by this tier; `None` is unbounded
+    pub up_to: Option<i64>,
+    /// Price per unit in lamports
+    pub unit_price_lamports: i64,
+    /// Charged once per period when usage first enters this tier
+    #[serde(default)]
+    pub flat_fee_lamports: i64,
+}
+
+/// A pricing model with ascending tiers; the last tier is unbounded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceSchedule {
+    pub model: PricingModel,
+    pub tiers: Vec<PriceTier>,
+}
+
+impl PriceSchedule {
+    /// A single-tier schedule charging `unit_price_lamports` per unit
+    pub fn per_unit(unit_price_lamports: i64) -> Self {
+        Self {
+            model: PricingModel::Tiered,
+            tiers: vec![PriceTier {
+                up_to: None,
+                unit_price_lamports,
+                flat_fee_lamports: 0,
+            }],
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.tiers.is_empty() {
+            return Err(AppError::Validation(
+                "A price schedule needs at least one tier".into(),
+            ));
+        }
+        if self.tiers.len() > MAX_PRICE_TIERS {
+            return Err(AppError::Validation(format!(
+                "A price schedule can have at most {} tiers",
+                MAX_PRICE_TIERS
+            )));
+        }
+        let mut previous = 0i64;
+        for (i, tier) in self.tiers.iter().enumerate() {
+            if tier.unit_price_lamports < 0 || tier.flat_fee_lamports < 0 {
+                return Err(AppError::Validation(
+                    "Tier prices and fees cannot be negative".into(),
+                ));
+            }
+            let last = i == self.tiers.len() - 1;
+            match tier.up_to {
+                None if last => {}
+                None => {
+                    return Err(AppError::Validation(
+                        "Only the last tier can be unbounded".into(),
+                    ))
+                }
+                Some(_) if last => {
+                    return Err(AppError::Validation(
+                        "The last tier must be unbounded (upTo: null)".into(),
+                    ))
+                }
+                Some(up_to) if up_to <= previous => {
+                    return Err(AppError::Validation(
+                        "Tier upTo values must be positive and strictly increasing".into(),
+                    ))
+                }
+                Some(up_to) => previous = up_to,
+            }
+        }
+        Ok(())
+    }
+
+    /// Index of the tier that prices the `unit`-th unit (1-based) of a period
+    fn tier_index(&self, unit: i64) -> usize {
+        self.tiers
+            .iter()
+            .position(|t| t.up_to.map_or(true, |up_to| unit <= up_to))
+            .unwrap_or(self.tiers.len().saturating_sub(1))
+    }
+
+    /// Price of `quantity` more units when `prior` units were already billed
+    /// in the same period
+    ///
+    /// Volume pricing is not retroactive: earlier batches keep the price of
+    /// the tier they were billed at.
+    pub fn cost(&self, prior: i64, quantity: i64) -> i64 {
+        if quantity <= 0 || self.tiers.is_empty() {
+            return 0;
+        }
+        let prior = prior.max(0);
+        let end = prior.saturating_add(quantity);
+        let mut cost: i128 = 0;
+
+        match self.model {
+            PricingModel::Tiered => {
+                // Units already in the period before this tier starts
+                let mut lower = 0i64;
+                for tier in &self.tiers {
+                    let upper = tier.up_to.unwrap_or(i64::MAX);
+                    let from = prior.max(lower);
+                    let to = end.min(upper);
+                    if to > from {
+                        cost += (to - from) as i128 * tier.unit_price_lamports as i128;
+                        if prior <= lower {
+                            cost += tier.flat_fee_lamports as i128;
+                        }
+                    }
+                    if upper >= end {
+                        break;
+                    }
+                    lower = upper;
+                }
+            }
+            PricingModel::Volume => {
+                let index = self.tier_index(end);
+                let tier = &self.tiers[index];
+                cost = quantity as i128 * tier.unit_price_lamports as i128;
+                if prior == 0 || self.tier_index(prior) != index {
+                    cost += tier.flat_fee_lamports as i128;
+                }
+            }
+        }
+
+        cost.min(i64::MAX as i128) as i64
+    }
+}
+
+/// Validate a meter name: 1-64 chars of `a-z`, `0-9`, `_`, `.` or `-`,
+/// starting with a letter
+pub fn validate_meter_name(name: &str) -> Result<(), AppError> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '.' | '-'));
+    if !valid {
+        return Err(AppError::Validation(
+            "Meter name must be 1-64 characters of a-z, 0-9, '_', '.' or '-' and start with a letter"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Start of the calendar month (UTC) containing `at`, and of the next month
+pub fn billing_period(at: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = Utc
+        .with_ymd_and_hms(at.year(), at.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(at);
+    let (year, month) = if at.month() == 12 {
+        (at.year() + 1, 1)
+    } else {
+        (at.year(), at.month() + 1)
+    };
+    let end = Utc
+        .with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(at);
+    (start, end)
+}
+
+/// Meter entity for storage
+#[derive(Debug, Clone)]
+pub struct MeterEntity {
+    pub id: Uuid,
+    /// Key used when reporting usage (e.g. `api_call`); unique
+    pub name: String,
+    pub display_name: String,
+    /// Unit of measure shown to users (e.g. "call", "second")
+    pub unit: String,
+    /// Credit currency usage is billed in
+    pub currency: String,
+    /// Default price schedule
+    pub pricing: PriceSchedule,
+    /// Inactive meters reject new usage events
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MeterEntity {
+    pub fn new(
+        name: String,
+        display_name: String,
+        unit: String,
+        currency: String,
+        pricing: PriceSchedule,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            display_name,
+            unit,
+            currency,
+            pricing,
+            active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Organization-specific price schedule for a meter
+#[derive(Debug, Clone)]
+pub struct MeterPriceOverrideEntity {
+    pub meter_id: Uuid,
+    pub org_id: Uuid,
+    pub pricing: PriceSchedule,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Billing state of a usage event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageEventStatus {
+    /// Not yet billed (possibly claimed into a batch)
+    Pending,
+    Billed,
+    /// Billing was rejected (e.g. insufficient credits); not retried
+    Failed,
+}
+
+impl UsageEventStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Billed => "billed",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for UsageEventStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "billed" => Ok(Self::Billed),
+            "failed" => Ok(Self::Failed),
+            _ => Err(AppError::Internal(anyhow::anyhow!(
+                "Unknown usage event status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Reported usage of a meter
+#[derive(Debug, Clone)]
+pub struct UsageEventEntity {
+    pub id: Uuid,
+    pub meter_id: Uuid,
+    /// User the usage is attributed to
+    pub user_id: Uuid,
+    /// Organization whose balance pays; `None` bills the user's own balance
+    pub org_id: Option<Uuid>,
+    pub quantity: i64,
+    /// Caller-provided key, unique per user
+    pub idempotency_key: String,
+    pub occurred_at: DateTime<Utc>,
+    pub status: UsageEventStatus,
+    /// Billing batch the event was claimed into
+    pub batch_id: Option<Uuid>,
+    /// Price charged for this event once billed
+    pub cost: Option<i64>,
+    /// Credit transaction that paid for the event's batch
+    pub transaction_id: Option<Uuid>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub billed_at: Option<DateTime<Utc>>,
+}
+
+impl UsageEventEntity {
+    pub fn new(
+        meter_id: Uuid,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        quantity: i64,
+        idempotency_key: String,
+        occurred_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            meter_id,
+            user_id,
+            org_id,
+            quantity,
+            idempotency_key,
+            occurred_at,
+            status: UsageEventStatus::Pending,
+            batch_id: None,
+            cost: None,
+            transaction_id: None,
+            error: None,
+            created_at: Utc::now(),
+            billed_at: None,
+        }
+    }
+}
+
+/// A user's personal usage of one meter
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MeterUsageTotals {
+    pub meter_id: Uuid,
+    pub billed_quantity: i64,
+    pub billed_cost: i64,
+    /// Reported but not yet billed
+    pub pending_quantity: i64,
+}
+
+/// Meter catalog and usage event repository trait
+#[async_trait]
+pub trait MeterRepository: Send + Sync {
+    /// Create a meter; fails if the name is taken
+    async fn create_meter(&self, meter: MeterEntity) -> Result<MeterEntity, AppError>;
+
+    async fn find_meter(&self, id: Uuid) -> Result<Option<MeterEntity>, AppError>;
+
+    async fn find_meter_by_name(&self, name: &str) -> Result<Option<MeterEntity>, AppError>;
+
+    /// All meters, by name
+    async fn list_meters(&self) -> Result<Vec<MeterEntity>, AppError>;
+
+    /// Update display name, unit, pricing and active state
+    async fn update_meter(&self, meter: MeterEntity) -> Result<MeterEntity, AppError>;
+
+    /// Create or replace an organization's schedule for a meter
+    async fn upsert_price_override(
+        &self,
+        price_override: MeterPriceOverrideEntity,
+    ) -> Result<MeterPriceOverrideEntity, AppError>;
+
+    async fn find_price_override(
+        &self,
+        meter_id: Uuid,
+        org_id: Uuid,
+    ) -> Result<Option<MeterPriceOverrideEntity>, AppError>;
+
+    async fn list_price_overrides(
+        &self,
+        meter_id: Uuid,
+    ) -> Result<Vec<MeterPriceOverrideEntity>, AppError>;
+
+    async fn delete_price_override(&self, meter_id: Uuid, org_id: Uuid) -> Result<bool, AppError>;
+
+    /// Store events, skipping any whose (user, idempotency key) already exists.
+    ///
+    /// Returns, per input event, whether it was stored.
+    async fn insert_usage_events(
+        &self,
+        events: Vec<UsageEventEntity>,
+    ) -> Result<Vec<bool>, AppError>;
+
+    /// Pending events, oldest first
+    async fn list_pending_events(&self, limit: u32) -> Result<Vec<UsageEventEntity>, AppError>;
+
+    /// Pending events claimed into `batch_id`
+    async fn list_batch_events(&self, batch_id: Uuid) -> Result<Vec<UsageEventEntity>, AppError>;
+
+    /// Claim unclaimed pending events into `batch_id`; returns the claimed events
+    async fn claim_events(
+        &self,
+        ids: &[Uuid],
+        batch_id: Uuid,
+    ) -> Result<Vec<UsageEventEntity>, AppError>;
+
+    /// Quantity billed for a meter and payer in `[from, to)` by `occurred_at`.
+    ///
+    /// With `org_id` the payer is the organization (all members); otherwise
+    /// it is the user's personal usage.
+    async fn billed_quantity(
+        &self,
+        meter_id: Uuid,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<i64, AppError>;
+
+    /// Mark a batch's events billed with their individual costs
+    async fn mark_billed(
+        &self,
+        batch_id: Uuid,
+        costs: &[(Uuid, i64)],
+        transaction_id: Option<Uuid>,
+    ) -> Result<(), AppError>;
+
+    /// Mark a batch's pending events failed
+    async fn mark_failed(&self, batch_id: Uuid, error: &str) -> Result<(), AppError>;
+
+    /// A user's personal usage per meter
+    async fn usage_by_meter(&self, user_id: Uuid) -> Result<Vec<MeterUsageTotals>, AppError>;
+}
+
+/// In-memory meter repository for development/testing
+pub struct InMemoryMeterRepository {
+    meters: RwLock<HashMap<Uuid, MeterEntity>>,
+    overrides: RwLock<HashMap<(Uuid, Uuid), MeterPriceOverrideEntity>>,
+    events: RwLock<Vec<UsageEventEntity>>,
+}
+
+impl InMemoryMeterRepository {
+    pub fn new() -> Self {
+        Self {
+            meters: RwLock::new(HashMap::new()),
+            overrides: RwLock::new(HashMap::new()),
+            events: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for InMemoryMeterRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl MeterRepository for InMemoryMeterRepository {
+    async fn create_meter(&self, meter: MeterEntity) -> Result<MeterEntity, AppError> {
+        let mut meters = self.meters.write().await;
+        if meters.values().any(|m| m.name == meter.name) {
+            return Err(AppError::Validation(
+                "A meter with this name already exists".into(),
+            ));
+        }
+        meters.insert(meter.id, meter.clone());
+        Ok(meter)
+    }
+
+    async fn find_meter(&self, id: Uuid) -> Result<Option<MeterEntity>, AppError> {
+        Ok(self.meters.read().await.get(&id).cloned())
+    }
+
+    async fn find_meter_by_name(&self, name: &str) -> Result<Option<MeterEntity>, AppError> {
+        let meters = self.meters.read().await;
+        Ok(meters.values().find(|m| m.name == name).cloned())
+    }
+
+    async fn list_meters(&self) -> Result<Vec<MeterEntity>, AppError> {
+        let mut meters: Vec<_> = self.meters.read().await.values().cloned().collect();
+        meters.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(meters)
+    }
+
+    async fn update_meter(&self, meter: MeterEntity) -> Result<MeterEntity, AppError> {
+        let mut meters = self.meters.write().await;
+        let existing = meters
+            .get_mut(&meter.id)
+            .ok_or(AppError::NotFound("Meter not found".into()))?;
+        existing.display_name = meter.display_name;
+        existing.unit = meter.unit;
+        existing.pricing = meter.pricing;
+        existing.active = meter.active;
+        existing.updated_at = Utc::now();
+        Ok(existing.clone())
+    }
+
+    async fn upsert_price_override(
+        &self,
+        price_override: MeterPriceOverrideEntity,
+    ) -> Result<MeterPriceOverrideEntity, AppError> {
+        let mut overrides = self.overrides.write().await;
+        let key = (price_override.meter_id, price_override.org_id);
+        let stored = match overrides.get(&key) {
+            Some(existing) => MeterPriceOverrideEntity {
+                created_by: existing.created_by,
+                created_at: existing.created_at,
+                updated_at: Utc::now(),
+                ..price_override
+            },
+            None => price_override,
+        };
+        overrides.insert(key, stored.clone());
+        Ok(stored)
+    }
+
+    async fn find_price_override(
+        &self,
+        meter_id: Uuid,
+        org_id: Uuid,
+    ) -> Result<Option<MeterPriceOverrideEntity>, AppError> {
+        Ok(self
+            .overrides
+            .read()
+            .await
+            .get(&(meter_id, org_id))
+            .cloned())
+    }
+
+    async fn list_price_overrides(
+        &self,
+        meter_id: Uuid,
+    ) -> Result<Vec<MeterPriceOverrideEntity>, AppError> {
+        let mut overrides: Vec<_> = self
+            .overrides
+            .read()
+            .await
+            .values()
+            .filter(|o| o.meter_id == meter_id)
+            .cloned()
+            .collect();
+        overrides.sort_by_key(|o| o.created_at);
+        Ok(overrides)
+    }
+
+    async fn delete_price_override(&self, meter_id: Uuid, org_id: Uuid) -> Result<bool, AppError> {
+        Ok(self
+            .overrides
+            .write()
+            .await
+            .remove(&(meter_id, org_id))
+            .is_some())
+    }
+
+    async fn insert_usage_events(
+        &self,
+        events: Vec<UsageEventEntity>,
+    ) -> Result<Vec<bool>, AppError> {
+        let mut stored = self.events.write().await;
+        let mut inserted = Vec::with_capacity(events.len());
+        for event in events {
+            let duplicate = stored
+                .iter()
+                .any(|e| e.user_id == event.user_id && e.idempotency_key == event.idempotency_key);
+            if !duplicate {
+                stored.push(event);
+            }
+            inserted.push(!duplicate);
+        }
+        Ok(inserted)
+    }
+
+    async fn list_pending_events(&self, limit: u32) -> Result<Vec<UsageEventEntity>, AppError> {
+        let mut events: Vec<_> = self
+            .events
+            .read()
+            .await
+            .iter()
+            .filter(|e| e.status == UsageEventStatus::Pending)
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| (e.occurred_at, e.created_at));
+        events.truncate(limit as usize);
+        Ok(events)
+    }
+
+    async fn list_batch_events(&self, batch_id: Uuid) -> Result<Vec<UsageEventEntity>, AppError> {
+        let events = self.events.read().await;
+        Ok(events
+            .iter()
+            .filter(|e| e.batch_id == Some(batch_id) && e.status == UsageEventStatus::Pending)
+            .cloned()
+            .collect())
+    }
+
+    async fn claim_events(
+        &self,
+        ids: &[Uuid],
+        batch_id: Uuid,
+    ) -> Result<Vec<UsageEventEntity>, AppError> {
+        let mut events = self.events.write().await;
+        let mut claimed = Vec::new();
+        for event in events.iter_mut() {
+            if ids.contains(&event.id)
+                && event.batch_id.is_none()
+                && event.status == UsageEventStatus::Pending
+            {
+                event.batch_id = Some(batch_id);
+                claimed.push(event.clone());
+            }
+        }
+        Ok(claimed)
+    }
+
+    async fn billed_quantity(
+        &self,
+        meter_id: Uuid,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let events = self.events.read().await;
+        Ok(events
+            .iter()
+            .filter(|e| {
+                e.meter_id == meter_id
+                    && e.status == UsageEventStatus::Billed
+                    && e.occurred_at >= from
+                    && e.occurred_at < to
+                    && match org_id {
+                        Some(org_id) => e.org_id == Some(org_id),
+                        None => e.org_id.is_none() && e.user_id == user_id,
+                    }
+            })
+            .map(|e| e.quantity)
+            .sum())
+    }
+
+    async fn mark_billed(
+        &self,
+        batch_id: Uuid,
+        costs: &[(Uuid, i64)],
+        transaction_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        let mut events = self.events.write().await;
+        let now = Utc::now();
+        for event in events.iter_mut() {
+            if event.batch_id != Some(batch_id) || event.status != UsageEventStatus::Pending {
+                continue;
+            }
+            if let Some((_, cost)) = costs.iter().find(|(id, _)| *id == event.id) {
+                event.status = UsageEventStatus::Billed;
+                event.cost = Some(*cost);
+                event.transaction_id = transaction_id;
+                event.billed_at = Some(now);
+            }
+        }
+        Ok(())
+    }
+
+    async fn mark_failed(&self, batch_id: Uuid, error: &str) -> Result<(), AppError> {
+        let mut events = self.events.write().await;
+        for event in events.iter_mut() {
+            if event.batch_id == Some(batch_id) && event.status == UsageEventStatus::Pending {
+                event.status = UsageEventStatus::Failed;
+                event.error = Some(error.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    async fn usage_by_meter(&self, user_id: Uuid) -> Result<Vec<MeterUsageTotals>, AppError> {
+        let events = self.events.read().await;
+        let mut totals: HashMap<Uuid, MeterUsageTotals> = HashMap::new();
+        for event in events
+            .iter()
+            .filter(|e| e.user_id == user_id && e.org_id.is_none())
+        {
+            let entry = totals.entry(event.meter_id).or_insert(MeterUsageTotals {
+                meter_id: event.meter_id,
+                ..Default::default()
+            });
+            match event.status {
+                UsageEventStatus::Billed => {
+                    entry.billed_quantity += event.quantity;
+                    entry.billed_cost += event.cost.unwrap_or(0);
+                }
+                UsageEventStatus::Pending => entry.pending_quantity += event.quantity,
+                UsageEventStatus::Failed => {}
+            }
+        }
+        Ok(totals.into_values().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiers(model: PricingModel) -> PriceSchedule {
+        PriceSchedule {
+            model,
+            tiers: vec![
+                PriceTier {
+                    up_to: Some(100),
+                    unit_price_lamports: 10,
+                    flat_fee_lamports: 0,
+                },
+                PriceTier {
+                    up_to: Some(1000),
+                    unit_price_lamports: 5,
+                    flat_fee_lamports: 200,
+                },
+                PriceTier {
+                    up_to: None,
+                    unit_price_lamports: 1,
+                    flat_fee_lamports: 0,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_tiered_pricing_is_graduated() {
+        let schedule = tiers(PricingModel::Tiered);
+        assert_eq!(schedule.cost(0, 50), 500);
+        // 50 units at 10, then 50 at 5 plus the tier's flat fee
+        assert_eq!(schedule.cost(50, 100), 500 + 250 + 200);
+        // Flat fee is only charged when the tier is entered
+        assert_eq!(schedule.cost(150, 10), 50);
+        assert_eq!(schedule.cost(990, 20), 50 + 10);
+        assert_eq!(schedule.cost(0, 0), 0);
+    }
+
+    #[test]
+    fn test_volume_pricing_uses_period_total() {
+        let schedule = tiers(PricingModel::Volume);
+        assert_eq!(schedule.cost(0, 50), 500);
+        // Period total reaches tier 2: the whole batch at 5, plus its fee
+        assert_eq!(schedule.cost(50, 100), 500 + 200);
+        assert_eq!(schedule.cost(150, 10), 50);
+        assert_eq!(schedule.cost(0, 2000), 2000);
+    }
+
+    #[test]
+    fn test_schedule_validation() {
+        assert!(tiers(PricingModel::Tiered).validate().is_ok());
+        assert!(PriceSchedule::per_unit(3).validate().is_ok());
+
+        let mut unbounded_middle = tiers(PricingModel::Tiered);
+        unbounded_middle.tiers[1].up_to = None;
+        assert!(unbounded_middle.validate().is_err());
+
+        let mut bounded_last = tiers(PricingModel::Tiered);
+        bounded_last.tiers[2].up_to = Some(5000);
+        assert!(bounded_last.validate().is_err());
+
+        let mut descending = tiers(PricingModel::Tiered);
+        descending.tiers[1].up_to = Some(50);
+        assert!(descending.validate().is_err());
+
+        let mut negative = PriceSchedule::per_unit(1);
+        negative.tiers[0].unit_price_lamports = -1;
+        assert!(negative.validate().is_err());
+
+        assert!(validate_meter_name("gpu_second").is_ok());
+        assert!(validate_meter_name("1call").is_err());
+        assert!(validate_meter_name("API_CALL").is_err());
+    }
+
+    #[test]
+    fn test_billing_period_bounds() {
+        let at = Utc.with_ymd_and_hms(2026, 12, 15, 8, 30, 0).unwrap();
+        let (start, end) = billing_period(at);
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_usage_events_dedupe_claim_and_bill() {
+        let repo = InMemoryMeterRepository::new();
+        let meter = repo
+            .create_meter(MeterEntity::new(
+                "api_call".into(),
+                "API calls".into(),
+                "call".into(),
+                "SOL".into(),
+                PriceSchedule::per_unit(2),
+            ))
+            .await
+            .unwrap();
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+        let event =
+            |key: &str| UsageEventEntity::new(meter.id, user_id, None, 10, key.to_string(), now);
+
+        let inserted = repo
+            .insert_usage_events(vec![event("a"), event("b"), event("a")])
+            .await
+            .unwrap();
+        assert_eq!(inserted, [true, true, false]);
+
+        let pending = repo.list_pending_events(100).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        let ids: Vec<Uuid> = pending.iter().map(|e| e.id).collect();
+        let batch_id = Uuid::new_v4();
+        assert_eq!(repo.claim_events(&ids, batch_id).await.unwrap().len(), 2);
+        // Already claimed events cannot be claimed again
+        assert!(repo
+            .claim_events(&ids, Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty());
+
+        let costs: Vec<(Uuid, i64)> = ids.iter().map(|id| (*id, 20)).collect();
+        repo.mark_billed(batch_id, &costs, Some(Uuid::new_v4()))
+            .await
+            .unwrap();
+
+        let (from, to) = billing_period(now);
+        let billed = repo
+            .billed_quantity(meter.id, user_id, None, from, to)
+            .await
+            .unwrap();
+        assert_eq!(billed, 20);
+
+        let usage = repo.usage_by_meter(user_id).await.unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].billed_cost, 40);
+        assert_eq!(usage[0].pending_quantity, 0);
+    }
+}
@@ -123,23 +123,33 @@
 mod api_key_repository;
 mod audit_repository;
 mod credential_repository;
+mod credit_budget_repository;
+mod credit_grant;
 mod credit_hold_repository;
+mod credit_invoice_repository;
+mod credit_journal;
 mod credit_refund_request_repository;
 mod credit_repository;
 mod custom_role_repository;
 mod deposit_repository;
 mod derived_wallet_repository;
+mod invite_link_repository;
 mod invite_repository;
 mod login_attempt_repository;
 mod membership_repository;
+mod meter_repository;
 mod nonce_repository;
 mod org_repository;
 mod outbox_repository;
 mod pending_wallet_recovery_repository;
 mod policy_repository;
 mod privacy_note_repository;
+mod relationship_repository;
+mod service_account_repository;
 mod session_repository;
 mod sso_repository;
+mod stripe_topup_repository;
+mod subscription_repository;
 mod system_settings_repository;
 mod totp_repository;
 mod transactional_ops;
@@ -150,6 +160,7 @@ mod verification_repository;
 mod wallet_material_repository;
 mod wallet_rotation_history_repository;
 mod webauthn_repository;
+mod webhook_repository;
 mod withdrawal_history_repository;
 
 // R-08: Shared pagination constants
@@ -162,27 +173,45 @@ pub mod postgres;
 mod tests;
 
 pub use api_key_repository::{
-    generate_api_key, hash_api_key, ApiKeyEntity, ApiKeyRepository, InMemoryApiKeyRepository,
-    API_KEY_PREFIX,
+    generate_api_key, hash_api_key, validate_api_key_restrictions, ApiKeyEntity, ApiKeyRepository,
+    InMemoryApiKeyRepository, API_KEY_PREFIX, API_KEY_SCOPES, SCOPE_ADMIN, SCOPE_CREDITS_SPEND,
+    SCOPE_USERS_READ, SCOPE_USERS_WRITE,
 };
 pub use audit_repository::{
-    AuditEventType, AuditLogBuilder, AuditLogEntry, AuditLogQuery, AuditLogRepository,
-    InMemoryAuditLogRepository,
+    compute_entry_hash, AuditActorType, AuditChainLink, AuditCheckpoint, AuditEventType,
+    AuditLogBuilder, AuditLogEntry, AuditLogQuery, AuditLogRepository, InMemoryAuditLogRepository,
+    AUDIT_GENESIS_HASH,
 };
 pub use credential_repository::{
     CredentialEntity, CredentialRepository, CredentialType, InMemoryCredentialRepository,
 };
+pub use credit_budget_repository::{
+    BudgetUsageKey, CreditBudgetCharge, CreditBudgetEntity, CreditBudgetPeriod,
+    CreditBudgetRepository, CreditBudgetScope, InMemoryCreditBudgetRepository,
+};
+pub use credit_grant::{
+    allocate_draw, promotional_draw, CreditGrantEntity, CreditGrantSource, CreditGrantStatus,
+    GRANT_REFERENCE_TYPE,
+};
 pub use credit_hold_repository::{
     CreateHoldResult, CreditHoldEntity, CreditHoldRepository, HoldStatus,
     InMemoryCreditHoldRepository,
 };
+pub use credit_invoice_repository::{
+    CreditInvoiceEntity, CreditInvoiceRepository, InMemoryCreditInvoiceRepository,
+};
+pub use credit_journal::{
+    BalanceReconciliation, CreditBalanceDrift, CreditJournalEntry, LedgerAccount,
+    LedgerAccountTotals, LedgerPosting, REFUND_REFERENCE_TYPE,
+};
 pub use credit_refund_request_repository::{
     CreditRefundRequestEntity, CreditRefundRequestRepository, CreditRefundRequestStatus,
     InMemoryCreditRefundRequestRepository,
 };
 pub use credit_repository::{
-    CreditBalanceEntity, CreditRepository, CreditStats, CreditTransactionEntity, CreditTxType,
-    CurrencyCreditStats, InMemoryCreditRepository, UserCreditStats,
+    CreditBalanceEntity, CreditOwnerType, CreditRepository, CreditStats, CreditTransactionEntity,
+    CreditTxType, CurrencyCreditStats, InMemoryCreditRepository, MemberSpendCap,
+    OrgCreditBalanceEntity, OrgCreditMemberSettings, SpendLimits, UserCreditStats,
 };
 pub use custom_role_repository::{CustomRole, CustomRoleRepository, InMemoryCustomRoleRepository};
 pub use deposit_repository::{
@@ -193,6 +222,9 @@ pub use derived_wallet_repository::{
     CreateDerivedWallet, DerivedWalletEntity, DerivedWalletRepository,
     InMemoryDerivedWalletRepository,
 };
+pub use invite_link_repository::{
+    InMemoryInviteLinkRepository, InviteLinkEntity, InviteLinkRepository,
+};
 pub use invite_repository::{
     default_invite_expiry, generate_invite_token, hash_invite_token, InMemoryInviteRepository,
     InviteEntity, InviteRepository, INVITE_EXPIRY_DAYS,
@@ -202,7 +234,13 @@ pub use login_attempt_repository::{
     LoginAttemptRepository,
 };
 pub use membership_repository::{
-    InMemoryMembershipRepository, MemberWithUser, MembershipEntity, MembershipRepository, OrgRole,
+    ActiveOrgMembershipRepository, InMemoryMembershipRepository, MemberWithUser, MembershipEntity,
+    MembershipRepository, OrgRole,
+};
+pub use meter_repository::{
+    billing_period, validate_meter_name, InMemoryMeterRepository, MeterEntity,
+    MeterPriceOverrideEntity, MeterRepository, MeterUsageTotals, PriceSchedule, PriceTier,
+    PricingModel, UsageEventEntity, UsageEventStatus, MAX_PRICE_TIERS, USAGE_REFERENCE_TYPE,
 };
 pub use nonce_repository::{InMemoryNonceRepository, NonceEntity, NonceRepository};
 pub use org_repository::{generate_slug, InMemoryOrgRepository, OrgEntity, OrgRepository};
@@ -220,8 +258,28 @@ pub use policy_repository::{
 pub use privacy_note_repository::{
     InMemoryPrivacyNoteRepository, NoteStatus, PrivacyNoteEntity, PrivacyNoteRepository,
 };
+pub use relationship_repository::{
+    validate_relation_name, InMemoryRelationshipRepository, NamespaceDefinition, ObjectRef,
+    RelationDefinition, RelationTuple, RelationshipRepository, RelationshipSchema, SubjectRef,
+    TupleFilter, TupleKey, UsersetRewrite, USER_SUBJECT_TYPE,
+};
+pub use service_account_repository::{
+    generate_client_id, generate_client_secret, hash_client_secret,
+    InMemoryServiceAccountRepository, ServiceAccountEntity, ServiceAccountRepository,
+    CLIENT_ID_PREFIX, CLIENT_SECRET_PREFIX,
+};
 pub use session_repository::{InMemorySessionRepository, SessionEntity, SessionRepository};
 pub use sso_repository::{InMemorySsoRepository, SsoRepository};
+pub use stripe_topup_repository::{
+    InMemoryStripeTopupRepository, StripeTopupEntity, StripeTopupFlow, StripeTopupLock,
+    StripeTopupRepository, StripeTopupStatus, STRIPE_DISPUTE_REFERENCE_TYPE,
+    STRIPE_REFUND_REFERENCE_TYPE, STRIPE_TOPUP_REFERENCE_TYPE,
+};
+pub use subscription_repository::{
+    validate_entitlements, validate_plan_name, InMemorySubscriptionRepository, PlanInterval,
+    SubscriptionEntity, SubscriptionPlanEntity, SubscriptionRepository, SubscriptionStatus,
+    MAX_PLAN_ENTITLEMENTS, SUBSCRIPTION_REFERENCE_TYPE,
+};
 pub use system_settings_repository::{
     InMemorySystemSettingsRepository, SystemSetting, SystemSettingsRepository,
 };
@@ -231,7 +289,8 @@ pub use treasury_config_repository::{
     InMemoryTreasuryConfigRepository, TreasuryConfigEntity, TreasuryConfigRepository,
 };
 pub use user_repository::{
-    normalize_email, validate_email_ascii_local, InMemoryUserRepository, UserEntity, UserRepository,
+    normalize_email, validate_email_ascii_local, InMemoryUserRepository, UserEntity,
+    UserRepository, UserStatus,
 };
 pub use user_withdrawal_log_repository::{
     InMemoryUserWithdrawalLogRepository, UserWithdrawalLogEntry, UserWithdrawalLogRepository,
@@ -252,6 +311,10 @@ pub use wallet_material_repository::{
 pub use webauthn_repository::{
     InMemoryWebAuthnRepository, WebAuthnChallenge, WebAuthnCredential, WebAuthnRepository,
 };
+pub use webhook_repository::{
+    generate_webhook_secret, InMemoryWebhookRepository, WebhookDeliveryEntity,
+    WebhookDeliveryStatus, WebhookEndpointEntity, WebhookRepository, WEBHOOK_SECRET_PREFIX,
+};
 pub use withdrawal_history_repository::{
     InMemoryWithdrawalHistoryRepository, WithdrawalHistoryEntry, WithdrawalHistoryRepository,
 };
@@ -259,15 +322,20 @@ pub use withdrawal_history_repository::{
 #[cfg(feature = "postgres")]
 pub use postgres::{
     PostgresApiKeyRepository, PostgresAuditLogRepository, PostgresCredentialRepository,
-    PostgresCreditHoldRepository, PostgresCreditRefundRequestRepository, PostgresCreditRepository,
+    PostgresCreditBudgetRepository, PostgresCreditHoldRepository, PostgresCreditInvoiceRepository,
+    PostgresCreditRefundRequestRepository, PostgresCreditRepository,
     PostgresCustomRoleRepository, PostgresDerivedWalletRepository, PostgresDepositRepository,
-    PostgresInviteRepository,
-    PostgresLoginAttemptRepository, PostgresMembershipRepository, PostgresNonceRepository,
-    PostgresOrgRepository, PostgresOutboxRepository, PostgresPendingWalletRecoveryRepository,
-    PostgresPolicyRepository, PostgresPrivacyNoteRepository, PostgresSessionRepository,
-    PostgresSsoRepository, PostgresSystemSettingsRepository, PostgresTotpRepository,
+    PostgresInviteLinkRepository, PostgresInviteRepository,
+    PostgresLoginAttemptRepository, PostgresMembershipRepository, PostgresMeterRepository,
+    PostgresNonceRepository, PostgresOrgRepository, PostgresOutboxRepository, PostgresPendingWalletRecoveryRepository,
+    PostgresPolicyRepository, PostgresPrivacyNoteRepository, PostgresRelationshipRepository,
+    PostgresServiceAccountRepository,
+    PostgresSessionRepository,
+    PostgresSsoRepository, PostgresStripeTopupRepository, PostgresSubscriptionRepository,
+    PostgresSystemSettingsRepository,
+    PostgresTotpRepository,
     PostgresTreasuryConfigRepository, PostgresUserRepository, PostgresUserWithdrawalLogRepository,
     PostgresVerificationRepository, PostgresWalletMaterialRepository,
     PostgresWalletRotationHistoryRepository, PostgresWebAuthnRepository,
-    PostgresWithdrawalHistoryRepository,
+    PostgresWebhookRepository, PostgresWithdrawalHistoryRepository,
 };
@@ -31,6 +31,10 @@ pub struct OrgEntity {
     pub owner_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// When deletion was requested (None = active)
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// When a pending deletion becomes permanent
+    pub purge_after: Option<DateTime<Utc>>,
 }
 
 impl OrgEntity {
@@ -46,9 +50,16 @@ impl OrgEntity {
             owner_id,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
+            purge_after: None,
         }
     }
 
+    /// Check if the organization is in its deletion grace period
+    pub fn is_pending_deletion(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
     /// Create a personal organization for a user
     pub fn new_personal(user_id: Uuid, user_name: Option<&str>) -> Self {
         let name = match user_name {
@@ -93,6 +104,25 @@ pub trait OrgRepository: Send + Sync {
 
     /// Count total organizations
     async fn count(&self) -> Result<u64, AppError>;
+
+    /// Mark an active organization as pending deletion.
+    /// Returns None if the org is missing or already pending deletion.
+    async fn schedule_deletion(
+        &self,
+        id: Uuid,
+        purge_after: DateTime<Utc>,
+    ) -> Result<Option<OrgEntity>, AppError>;
+
+    /// Clear a pending deletion.
+    /// Returns None if the org is missing or not pending deletion.
+    async fn restore(&self, id: Uuid) -> Result<Option<OrgEntity>, AppError>;
+
+    /// Find organizations whose grace period ended before `now` (oldest first)
+    async fn find_due_for_purge(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<OrgEntity>, AppError>;
 }
 
 /// In-memory organization repository for development/testing
@@ -212,6 +242,52 @@ impl OrgRepository for InMemoryOrgRepository {
         let orgs = self.orgs.read().await;
         Ok(orgs.len() as u64)
     }
+
+    async fn schedule_deletion(
+        &self,
+        id: Uuid,
+        purge_after: DateTime<Utc>,
+    ) -> Result<Option<OrgEntity>, AppError> {
+        let mut orgs = self.orgs.write().await;
+        match orgs.get_mut(&id) {
+            Some(org) if org.deleted_at.is_none() => {
+                let now = Utc::now();
+                org.deleted_at = Some(now);
+                org.purge_after = Some(purge_after);
+                org.updated_at = now;
+                Ok(Some(org.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<Option<OrgEntity>, AppError> {
+        let mut orgs = self.orgs.write().await;
+        match orgs.get_mut(&id) {
+            Some(org) if org.deleted_at.is_some() => {
+                org.deleted_at = None;
+                org.purge_after = None;
+                org.updated_at = Utc::now();
+                Ok(Some(org.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn find_due_for_purge(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<OrgEntity>, AppError> {
+        let orgs = self.orgs.read().await;
+        let mut due: Vec<_> = orgs
+            .values()
+            .filter(|o| o.purge_after.is_some_and(|p| p <= now))
+            .cloned()
+            .collect();
+        due.sort_by_key(|o| o.purge_after);
+        Ok(due.into_iter().take(limit as usize).collect())
+    }
 }
 
 #[cfg(test)]
@@ -300,4 +376,37 @@ mod tests {
         let found = repo.find_by_id(created.id).await.unwrap();
         assert!(found.is_none());
     }
+
+    #[tokio::test]
+    async fn test_schedule_deletion_and_restore() {
+        let repo = InMemoryOrgRepository::new();
+        let org = OrgEntity::new("Test Org".into(), "test-org".into(), Uuid::new_v4(), false);
+        let created = repo.create(org).await.unwrap();
+
+        let purge_after = Utc::now() - chrono::Duration::seconds(1);
+        let pending = repo
+            .schedule_deletion(created.id, purge_after)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(pending.is_pending_deletion());
+        assert!(repo
+            .schedule_deletion(created.id, purge_after)
+            .await
+            .unwrap()
+            .is_none());
+
+        let due = repo.find_due_for_purge(Utc::now(), 10).await.unwrap();
+        assert_eq!(due.len(), 1);
+
+        let restored = repo.restore(created.id).await.unwrap().unwrap();
+        assert!(!restored.is_pending_deletion());
+        assert!(restored.purge_after.is_none());
+        assert!(repo.restore(created.id).await.unwrap().is_none());
+        assert!(repo
+            .find_due_for_purge(Utc::now(), 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
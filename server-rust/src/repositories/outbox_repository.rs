@@ -41,6 +41,9 @@ pub enum OutboxEventType {
     EmailInvite,
     EmailInstantLink,
     EmailSecurityAlert,
+    EmailChange,
+    EmailChangeNotice,
+    EmailCreditStatement,
     // Admin notification events
     NotifyLoginThreshold,
     NotifyTokenReuse,
@@ -52,6 +55,13 @@ pub enum OutboxEventType {
     CreditSpend,
     CreditHoldExpired,
     CreditRefundRequested,
+    CreditSubscriptionPastDue,
+    CreditSubscriptionCanceled,
+    CreditTransferSent,
+    CreditTransferReceived,
+    CreditBudgetThreshold,
+    // Webhook events (payload carries the delivery log ID)
+    WebhookDelivery,
 }
 
 impl OutboxEventType {
@@ -62,6 +72,9 @@ impl OutboxEventType {
             Self::EmailInvite => "email.invite",
             Self::EmailInstantLink => "email.instant_link",
             Self::EmailSecurityAlert => "email.security_alert",
+            Self::EmailChange => "email.email_change",
+            Self::EmailChangeNotice => "email.email_change_notice",
+            Self::EmailCreditStatement => "email.credit_statement",
             Self::NotifyLoginThreshold => "notify.login_threshold",
             Self::NotifyTokenReuse => "notify.token_reuse",
             Self::NotifyRoleChange => "notify.role_change",
@@ -71,6 +84,12 @@ impl OutboxEventType {
             Self::CreditSpend => "credit.spend",
             Self::CreditHoldExpired => "credit.hold_expired",
             Self::CreditRefundRequested => "credit.refund_requested",
+            Self::CreditSubscriptionPastDue => "credit.subscription_past_due",
+            Self::CreditSubscriptionCanceled => "credit.subscription_canceled",
+            Self::CreditTransferSent => "credit.transfer_sent",
+            Self::CreditTransferReceived => "credit.transfer_received",
+            Self::CreditBudgetThreshold => "credit.budget_threshold",
+            Self::WebhookDelivery => "webhook.delivery",
         }
     }
 
@@ -82,6 +101,9 @@ impl OutboxEventType {
                 | Self::EmailInvite
                 | Self::EmailInstantLink
                 | Self::EmailSecurityAlert
+                | Self::EmailChange
+                | Self::EmailChangeNotice
+                | Self::EmailCreditStatement
         )
     }
 
@@ -103,8 +125,17 @@ impl OutboxEventType {
                 | Self::CreditSpend
                 | Self::CreditHoldExpired
                 | Self::CreditRefundRequested
+                | Self::CreditSubscriptionPastDue
+                | Self::CreditSubscriptionCanceled
+                | Self::CreditTransferSent
+                | Self::CreditTransferReceived
+                | Self::CreditBudgetThreshold
         )
     }
+
+    pub fn is_webhook(&self) -> bool {
+        matches!(self, Self::WebhookDelivery)
+    }
 }
 
 /// Outbox event entity
@@ -560,6 +591,9 @@ mod tests {
     async fn test_event_type_classification() {
         assert!(OutboxEventType::EmailVerification.is_email());
         assert!(OutboxEventType::EmailInvite.is_email());
+        assert!(OutboxEventType::EmailChange.is_email());
+        assert!(OutboxEventType::EmailChangeNotice.is_email());
+        assert!(OutboxEventType::EmailCreditStatement.is_email());
         assert!(!OutboxEventType::NotifyRoleChange.is_email());
 
         assert!(OutboxEventType::NotifyTokenReuse.is_notification());
@@ -585,12 +619,28 @@ mod tests {
         assert!(OutboxEventType::CreditSpend.is_credit_event());
         assert!(OutboxEventType::CreditHoldExpired.is_credit_event());
         assert!(OutboxEventType::CreditRefundRequested.is_credit_event());
+        assert!(OutboxEventType::CreditSubscriptionPastDue.is_credit_event());
+        assert!(OutboxEventType::CreditSubscriptionCanceled.is_credit_event());
+        assert!(OutboxEventType::CreditTransferSent.is_credit_event());
+        assert!(OutboxEventType::CreditTransferReceived.is_credit_event());
+        assert!(OutboxEventType::CreditBudgetThreshold.is_credit_event());
 
         // Other types are not credit events
         assert!(!OutboxEventType::EmailVerification.is_credit_event());
         assert!(!OutboxEventType::NotifyRoleChange.is_credit_event());
     }
 
+    #[tokio::test]
+    async fn test_webhook_event_type_classification() {
+        let event_type = OutboxEventType::WebhookDelivery;
+        assert!(event_type.is_webhook());
+        assert!(!event_type.is_email());
+        assert!(!event_type.is_notification());
+        assert!(!event_type.is_credit_event());
+        assert!(!OutboxEventType::CreditSpend.is_webhook());
+        assert_eq!(event_type.as_str(), "webhook.delivery");
+    }
+
     #[tokio::test]
     async fn test_credit_event_type_as_str() {
         assert_eq!(
@@ -606,5 +656,25 @@ mod tests {
             OutboxEventType::CreditRefundRequested.as_str(),
             "credit.refund_requested"
         );
+        assert_eq!(
+            OutboxEventType::CreditSubscriptionPastDue.as_str(),
+            "credit.subscription_past_due"
+        );
+        assert_eq!(
+            OutboxEventType::CreditSubscriptionCanceled.as_str(),
+            "credit.subscription_canceled"
+        );
+        assert_eq!(
+            OutboxEventType::CreditTransferSent.as_str(),
+            "credit.transfer_sent"
+        );
+        assert_eq!(
+            OutboxEventType::CreditTransferReceived.as_str(),
+            "credit.transfer_received"
+        );
+        assert_eq!(
+            OutboxEventType::CreditBudgetThreshold.as_str(),
+            "credit.budget_threshold"
+        );
     }
 }
@@ -1,10 +1,15 @@
 //! ABAC Policy repository trait and implementations
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Mutex, OnceLock};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
@@ -28,12 +33,82 @@ impl PolicyEffect {
     }
 }
 
+/// Maximum nesting depth for composed matchers and conditions
+pub const MAX_CONDITION_DEPTH: usize = 8;
+
+/// Maximum compiled size for regex matchers (bytes)
+const REGEX_SIZE_LIMIT: usize = 64 * 1024;
+
+/// Maximum number of compiled regex matchers kept between evaluations
+const MAX_CACHED_REGEXES: usize = 1024;
+
+/// Compiled regex matchers by pattern, so evaluating a policy does not
+/// recompile its patterns on every check
+static REGEX_CACHE: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+
+/// Recurring time window, e.g. weekdays 09:00-18:00 Europe/London
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Days of week the window applies to (empty = every day)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<Weekday>,
+    /// Start of the window as local "HH:MM"
+    pub start: String,
+    /// End of the window as local "HH:MM" (exclusive; may be before `start` for overnight windows)
+    pub end: String,
+    /// IANA time zone name (defaults to UTC)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl TimeWindow {
+    fn parse_bounds(&self) -> Result<(NaiveTime, NaiveTime, Tz), String> {
+        let start = NaiveTime::parse_from_str(&self.start, "%H:%M")
+            .map_err(|_| format!("Invalid time window start '{}', expected HH:MM", self.start))?;
+        let end = NaiveTime::parse_from_str(&self.end, "%H:%M")
+            .map_err(|_| format!("Invalid time window end '{}', expected HH:MM", self.end))?;
+        let tz = match &self.timezone {
+            Some(name) => name
+                .parse::<Tz>()
+                .map_err(|_| format!("Unknown time zone '{}'", name))?,
+            None => Tz::UTC,
+        };
+        Ok((start, end, tz))
+    }
+
+    /// Check whether an instant falls inside the window
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        let Ok((start, end, tz)) = self.parse_bounds() else {
+            return false;
+        };
+        let local = at.with_timezone(&tz);
+        let time = local.time();
+
+        // Overnight windows (22:00-06:00) belong to the day they started on
+        let (in_window, day) = if start <= end {
+            (time >= start && time < end, local.weekday())
+        } else if time >= start {
+            (true, local.weekday())
+        } else {
+            (time < end, local.weekday().pred())
+        };
+
+        in_window && (self.days.is_empty() || self.days.contains(&day))
+    }
+}
+
 /// Attribute matcher for policy conditions
+///
+/// String operands of `equals`, `not_equals`, `in`, `not_in`, `before` and
+/// `after` may reference another attribute as `${section.key}`, e.g.
+/// `${subject.user_id}`; references are resolved by the policy service.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "value", rename_all = "snake_case")]
 pub enum AttributeMatcher {
     /// Exact equality match
     Equals(Value),
+    /// Inequality match (a missing value counts as not equal)
+    NotEquals(Value),
     /// Value is in the given list
     In(Vec<Value>),
     /// Value is not in the given list
@@ -50,6 +125,22 @@ pub enum AttributeMatcher {
     EndsWith(String),
     /// Boolean existence check (true = must exist, false = must not exist)
     Exists(bool),
+    /// String matches a regular expression (unanchored unless the pattern uses ^/$)
+    Regex(String),
+    /// IP address is inside any of the given CIDR blocks (bare addresses allowed)
+    InCidr(Vec<String>),
+    /// RFC 3339 timestamp is strictly before the given timestamp
+    Before(String),
+    /// RFC 3339 timestamp is strictly after the given timestamp
+    After(String),
+    /// RFC 3339 timestamp falls inside a recurring local time window
+    TimeWindow(TimeWindow),
+    /// All nested matchers must match
+    AllOf(Vec<AttributeMatcher>),
+    /// At least one nested matcher must match
+    AnyOf(Vec<AttributeMatcher>),
+    /// Nested matcher must not match
+    Not(Box<AttributeMatcher>),
 }
 
 impl AttributeMatcher {
@@ -57,6 +148,7 @@ impl AttributeMatcher {
     pub fn matches(&self, value: Option<&Value>) -> bool {
         match self {
             Self::Equals(expected) => value.map(|v| v == expected).unwrap_or(false),
+            Self::NotEquals(expected) => value.map(|v| v != expected).unwrap_or(true),
             Self::In(list) => value.map(|v| list.contains(v)).unwrap_or(false),
             Self::NotIn(list) => value.map(|v| !list.contains(v)).unwrap_or(true),
             Self::GreaterThan(threshold) => value
@@ -83,11 +175,199 @@ impl AttributeMatcher {
                 let exists = value.is_some() && !matches!(value, Some(Value::Null));
                 exists == *should_exist
             }
+            Self::Regex(pattern) => match (build_regex(pattern), value.and_then(|v| v.as_str())) {
+                (Ok(re), Some(s)) => re.is_match(s),
+                _ => false,
+            },
+            Self::InCidr(blocks) => value
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.trim().parse::<IpAddr>().ok())
+                .map(|ip| {
+                    blocks.iter().any(|block| {
+                        parse_cidr(block)
+                            .map(|cidr| cidr_contains(cidr, ip))
+                            .unwrap_or(false)
+                    })
+                })
+                .unwrap_or(false),
+            Self::Before(bound) => match (parse_timestamp_value(value), parse_timestamp(bound)) {
+                (Some(at), Some(bound)) => at < bound,
+                _ => false,
+            },
+            Self::After(bound) => match (parse_timestamp_value(value), parse_timestamp(bound)) {
+                (Some(at), Some(bound)) => at > bound,
+                _ => false,
+            },
+            Self::TimeWindow(window) => parse_timestamp_value(value)
+                .map(|at| window.contains(at))
+                .unwrap_or(false),
+            Self::AllOf(matchers) => matchers.iter().all(|m| m.matches(value)),
+            Self::AnyOf(matchers) => matchers.iter().any(|m| m.matches(value)),
+            Self::Not(matcher) => !matcher.matches(value),
+        }
+    }
+
+    /// Return a copy with `${section.key}` references replaced by `lookup`.
+    ///
+    /// Unresolvable references are left as literal strings.
+    pub fn resolve_references(&self, lookup: &dyn Fn(&str) -> Option<Value>) -> Self {
+        let resolve = |value: &Value| -> Value {
+            value
+                .as_str()
+                .and_then(reference_path)
+                .and_then(lookup)
+                .unwrap_or_else(|| value.clone())
+        };
+        let resolve_str = |s: &String| -> String {
+            match reference_path(s).and_then(lookup) {
+                Some(Value::String(resolved)) => resolved,
+                _ => s.clone(),
+            }
+        };
+
+        match self {
+            Self::Equals(v) => Self::Equals(resolve(v)),
+            Self::NotEquals(v) => Self::NotEquals(resolve(v)),
+            Self::In(list) => Self::In(list.iter().map(resolve).collect()),
+            Self::NotIn(list) => Self::NotIn(list.iter().map(resolve).collect()),
+            Self::Before(bound) => Self::Before(resolve_str(bound)),
+            Self::After(bound) => Self::After(resolve_str(bound)),
+            Self::AllOf(matchers) => Self::AllOf(
+                matchers
+                    .iter()
+                    .map(|m| m.resolve_references(lookup))
+                    .collect(),
+            ),
+            Self::AnyOf(matchers) => Self::AnyOf(
+                matchers
+                    .iter()
+                    .map(|m| m.resolve_references(lookup))
+                    .collect(),
+            ),
+            Self::Not(matcher) => Self::Not(Box::new(matcher.resolve_references(lookup))),
+            other => other.clone(),
+        }
+    }
+
+    /// Validate operands that are parsed at evaluation time
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_at_depth(0)
+    }
+
+    fn validate_at_depth(&self, depth: usize) -> Result<(), String> {
+        if depth > MAX_CONDITION_DEPTH {
+            return Err(format!(
+                "Conditions nested deeper than {} levels",
+                MAX_CONDITION_DEPTH
+            ));
+        }
+        match self {
+            Self::Regex(pattern) => build_regex(pattern)
+                .map(|_| ())
+                .map_err(|e| format!("Invalid regex '{}': {}", pattern, e)),
+            Self::InCidr(blocks) => {
+                if blocks.is_empty() {
+                    return Err("in_cidr requires at least one CIDR block".into());
+                }
+                for block in blocks {
+                    parse_cidr(block).ok_or_else(|| format!("Invalid CIDR block '{}'", block))?;
+                }
+                Ok(())
+            }
+            Self::Before(bound) | Self::After(bound) => {
+                if reference_path(bound).is_some() || parse_timestamp(bound).is_some() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Invalid timestamp '{}', expected RFC 3339 or ${{section.key}}",
+                        bound
+                    ))
+                }
+            }
+            Self::TimeWindow(window) => window.parse_bounds().map(|_| ()),
+            Self::AllOf(matchers) | Self::AnyOf(matchers) => {
+                if matchers.is_empty() {
+                    return Err("all_of/any_of require at least one matcher".into());
+                }
+                matchers
+                    .iter()
+                    .try_for_each(|m| m.validate_at_depth(depth + 1))
+            }
+            Self::Not(matcher) => matcher.validate_at_depth(depth + 1),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Extract `section.key` from a `${section.key}` reference
+pub fn reference_path(s: &str) -> Option<&str> {
+    s.strip_prefix("${").and_then(|rest| rest.strip_suffix('}'))
+}
+
+fn build_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    let cache = REGEX_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(regex) = cache.lock().unwrap_or_else(|e| e.into_inner()).get(pattern) {
+        return Ok(regex.clone());
+    }
+
+    let regex = RegexBuilder::new(pattern)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()?;
+    let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+    if cache.len() >= MAX_CACHED_REGEXES {
+        cache.clear();
+    }
+    cache.insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
+
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn parse_timestamp_value(value: Option<&Value>) -> Option<DateTime<Utc>> {
+    value.and_then(|v| v.as_str()).and_then(parse_timestamp)
+}
+
+/// Parse "10.0.0.0/8" or a bare address into (network, prefix length)
+pub(crate) fn parse_cidr(block: &str) -> Option<(IpAddr, u8)> {
+    let (addr, prefix) = match block.trim().split_once('/') {
+        Some((addr, prefix)) => (addr.parse::<IpAddr>().ok()?, prefix.parse::<u8>().ok()?),
+        None => {
+            let addr = block.trim().parse::<IpAddr>().ok()?;
+            let max = if addr.is_ipv4() { 32 } else { 128 };
+            (addr, max)
+        }
+    };
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    (prefix <= max).then_some((addr, prefix))
+}
+
+pub(crate) fn cidr_contains((network, prefix): (IpAddr, u8), ip: IpAddr) -> bool {
+    // Treat IPv4-mapped IPv6 addresses (::ffff:a.b.c.d) as IPv4
+    let ip = match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        v4 => v4,
+    };
+    match (network, ip) {
+        (IpAddr::V4(net), IpAddr::V4(addr)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(net) & mask == u32::from(addr) & mask
+        }
+        (IpAddr::V6(net), IpAddr::V6(addr)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(net) & mask == u128::from(addr) & mask
         }
+        _ => false,
     }
 }
 
 /// Conditions that must be met for a policy to apply
+///
+/// The subject/resource/environment maps are ANDed together with the
+/// `all`, `any` and `not` groups, which nest further conditions.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PolicyConditions {
     /// Conditions on subject (user) attributes
@@ -99,6 +379,15 @@ pub struct PolicyConditions {
     /// Conditions on environment attributes
     #[serde(default)]
     pub environment: HashMap<String, AttributeMatcher>,
+    /// Every nested group must match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub all: Vec<PolicyConditions>,
+    /// At least one nested group must match (ignored when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub any: Vec<PolicyConditions>,
+    /// Nested group must not match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<PolicyConditions>>,
 }
 
 impl PolicyConditions {
@@ -120,6 +409,53 @@ impl PolicyConditions {
         self.environment.insert(key.to_string(), matcher);
         self
     }
+
+    pub fn with_all(mut self, conditions: PolicyConditions) -> Self {
+        self.all.push(conditions);
+        self
+    }
+
+    pub fn with_any(mut self, conditions: PolicyConditions) -> Self {
+        self.any.push(conditions);
+        self
+    }
+
+    pub fn with_not(mut self, conditions: PolicyConditions) -> Self {
+        self.not = Some(Box::new(conditions));
+        self
+    }
+
+    /// Validate all matchers and the nesting depth
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_at_depth(0)
+    }
+
+    fn validate_at_depth(&self, depth: usize) -> Result<(), String> {
+        if depth > MAX_CONDITION_DEPTH {
+            return Err(format!(
+                "Conditions nested deeper than {} levels",
+                MAX_CONDITION_DEPTH
+            ));
+        }
+        for (section, matchers) in [
+            ("subject", &self.subject),
+            ("resource", &self.resource),
+            ("environment", &self.environment),
+        ] {
+            for (key, matcher) in matchers {
+                matcher
+                    .validate()
+                    .map_err(|e| format!("{}.{}: {}", section, key, e))?;
+            }
+        }
+        for nested in self.all.iter().chain(self.any.iter()) {
+            nested.validate_at_depth(depth + 1)?;
+        }
+        if let Some(nested) = &self.not {
+            nested.validate_at_depth(depth + 1)?;
+        }
+        Ok(())
+    }
 }
 
 /// ABAC Policy entity
@@ -256,7 +592,7 @@ impl PolicyRepository for InMemoryPolicyRepository {
             .filter(|p| p.org_id == org_id)
             .cloned()
             .collect();
-        results.sort_by(|a, b| b.priority.cmp(&a.priority));
+        results.sort_by_key(|p| Reverse(p.priority));
         Ok(results)
     }
 
@@ -272,7 +608,7 @@ impl PolicyRepository for InMemoryPolicyRepository {
             .filter(|p| p.org_id == org_id)
             .cloned()
             .collect();
-        results.sort_by(|a, b| b.priority.cmp(&a.priority));
+        results.sort_by_key(|p| Reverse(p.priority));
         let capped_limit = cap_limit(limit);
         let capped_offset = cap_offset(offset);
         let start = capped_offset as usize;
@@ -296,7 +632,7 @@ impl PolicyRepository for InMemoryPolicyRepository {
             .filter(|p| p.org_id == org_id && p.permission == permission && p.enabled)
             .cloned()
             .collect();
-        results.sort_by(|a, b| b.priority.cmp(&a.priority));
+        results.sort_by_key(|p| Reverse(p.priority));
         Ok(results)
     }
 
@@ -375,6 +711,185 @@ mod tests {
         assert!(exists_false.matches(None));
     }
 
+    #[test]
+    fn test_attribute_matcher_regex() {
+        let matcher = AttributeMatcher::Regex(r"^[a-z]+@example\.com$".to_string());
+        assert!(matcher.matches(Some(&Value::String("alice@example.com".to_string()))));
+        assert!(!matcher.matches(Some(&Value::String("alice@evil.com".to_string()))));
+        assert!(!matcher.matches(None));
+
+        assert!(AttributeMatcher::Regex("(".to_string()).validate().is_err());
+    }
+
+    #[test]
+    fn test_attribute_matcher_in_cidr() {
+        let matcher =
+            AttributeMatcher::InCidr(vec!["10.0.0.0/8".to_string(), "2001:db8::/32".to_string()]);
+        assert!(matcher.matches(Some(&Value::String("10.1.2.3".to_string()))));
+        assert!(matcher.matches(Some(&Value::String("::ffff:10.9.9.9".to_string()))));
+        assert!(matcher.matches(Some(&Value::String("2001:db8::1".to_string()))));
+        assert!(!matcher.matches(Some(&Value::String("192.168.1.1".to_string()))));
+        assert!(!matcher.matches(Some(&Value::String("not-an-ip".to_string()))));
+
+        let single = AttributeMatcher::InCidr(vec!["203.0.113.7".to_string()]);
+        assert!(single.matches(Some(&Value::String("203.0.113.7".to_string()))));
+        assert!(!single.matches(Some(&Value::String("203.0.113.8".to_string()))));
+
+        assert!(AttributeMatcher::InCidr(vec!["10.0.0.0/33".to_string()])
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_attribute_matcher_before_after() {
+        let before = AttributeMatcher::Before("2025-01-01T00:00:00Z".to_string());
+        let after = AttributeMatcher::After("2025-01-01T00:00:00Z".to_string());
+        let early = Value::String("2024-06-01T12:00:00+02:00".to_string());
+        let late = Value::String("2025-06-01T12:00:00Z".to_string());
+
+        assert!(before.matches(Some(&early)));
+        assert!(!before.matches(Some(&late)));
+        assert!(after.matches(Some(&late)));
+        assert!(!after.matches(Some(&Value::String("garbage".to_string()))));
+
+        assert!(
+            AttributeMatcher::Before("${resource.expires_at}".to_string())
+                .validate()
+                .is_ok()
+        );
+        assert!(AttributeMatcher::Before("tomorrow".to_string())
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_attribute_matcher_time_window() {
+        let window = TimeWindow {
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            start: "09:00".to_string(),
+            end: "18:00".to_string(),
+            timezone: Some("Europe/London".to_string()),
+        };
+        let matcher = AttributeMatcher::TimeWindow(window);
+
+        // Wednesday 2024-07-10 08:30 UTC = 09:30 BST
+        assert!(matcher.matches(Some(&Value::String("2024-07-10T08:30:00Z".to_string()))));
+        // Wednesday 2024-07-10 17:30 UTC = 18:30 BST
+        assert!(!matcher.matches(Some(&Value::String("2024-07-10T17:30:00Z".to_string()))));
+        // Saturday 2024-07-13 10:00 UTC
+        assert!(!matcher.matches(Some(&Value::String("2024-07-13T10:00:00Z".to_string()))));
+    }
+
+    #[test]
+    fn test_time_window_overnight() {
+        let window = TimeWindow {
+            days: vec![Weekday::Fri],
+            start: "22:00".to_string(),
+            end: "06:00".to_string(),
+            timezone: None,
+        };
+        // Friday 23:00 and the following Saturday 02:00 belong to Friday's window
+        assert!(window.contains("2024-07-12T23:00:00Z".parse().unwrap()));
+        assert!(window.contains("2024-07-13T02:00:00Z".parse().unwrap()));
+        assert!(!window.contains("2024-07-13T23:00:00Z".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_time_window_rejects_unknown_timezone() {
+        let matcher = AttributeMatcher::TimeWindow(TimeWindow {
+            days: vec![],
+            start: "09:00".to_string(),
+            end: "17:00".to_string(),
+            timezone: Some("Mars/Olympus_Mons".to_string()),
+        });
+        assert!(matcher.validate().is_err());
+    }
+
+    #[test]
+    fn test_attribute_matcher_composition() {
+        let matcher = AttributeMatcher::AnyOf(vec![
+            AttributeMatcher::StartsWith("admin-".to_string()),
+            AttributeMatcher::AllOf(vec![
+                AttributeMatcher::Contains("ops".to_string()),
+                AttributeMatcher::Not(Box::new(AttributeMatcher::EndsWith(
+                    "-readonly".to_string(),
+                ))),
+            ]),
+        ]);
+        assert!(matcher.matches(Some(&Value::String("admin-root".to_string()))));
+        assert!(matcher.matches(Some(&Value::String("ops-team".to_string()))));
+        assert!(!matcher.matches(Some(&Value::String("ops-readonly".to_string()))));
+        assert!(!matcher.matches(Some(&Value::String("guest".to_string()))));
+    }
+
+    #[test]
+    fn test_attribute_matcher_resolve_references() {
+        let matcher = AttributeMatcher::In(vec![
+            Value::String("${subject.user_id}".to_string()),
+            Value::String("literal".to_string()),
+        ]);
+        let resolved = matcher.resolve_references(&|path| {
+            (path == "subject.user_id").then(|| Value::String("u-1".to_string()))
+        });
+        assert!(resolved.matches(Some(&Value::String("u-1".to_string()))));
+        assert!(resolved.matches(Some(&Value::String("literal".to_string()))));
+        assert!(!resolved.matches(Some(&Value::String("${subject.user_id}".to_string()))));
+    }
+
+    #[test]
+    fn test_policy_conditions_legacy_json_deserializes() {
+        let json = r#"{
+            "subject": {"role": {"type": "in", "value": ["owner", "admin"]}},
+            "resource": {"owner_id": {"type": "equals", "value": "${subject.user_id}"}}
+        }"#;
+        let conditions: PolicyConditions = serde_json::from_str(json).unwrap();
+        assert!(conditions.all.is_empty());
+        assert!(conditions.any.is_empty());
+        assert!(conditions.not.is_none());
+        assert!(conditions.validate().is_ok());
+
+        // Empty composition groups are omitted so stored JSON keeps its shape
+        let serialized = serde_json::to_value(&conditions).unwrap();
+        assert!(serialized.get("all").is_none());
+        assert!(serialized.get("not").is_none());
+    }
+
+    #[test]
+    fn test_policy_conditions_nested_json() {
+        let json = r#"{
+            "any": [
+                {"environment": {"ip": {"type": "in_cidr", "value": ["10.0.0.0/8"]}}},
+                {"subject": {"role": {"type": "equals", "value": "owner"}}}
+            ],
+            "not": {"resource": {"status": {"type": "regex", "value": "^archived"}}},
+            "environment": {
+                "time": {"type": "time_window", "value": {
+                    "days": ["Mon", "Tue", "Wed", "Thu", "Fri"],
+                    "start": "09:00", "end": "18:00", "timezone": "Europe/London"
+                }}
+            }
+        }"#;
+        let conditions: PolicyConditions = serde_json::from_str(json).unwrap();
+        assert_eq!(conditions.any.len(), 2);
+        assert!(conditions.not.is_some());
+        assert!(conditions.validate().is_ok());
+    }
+
+    #[test]
+    fn test_policy_conditions_depth_limit() {
+        let mut conditions = PolicyConditions::new();
+        for _ in 0..=MAX_CONDITION_DEPTH + 1 {
+            conditions = PolicyConditions::new().with_not(conditions);
+        }
+        assert!(conditions.validate().is_err());
+    }
+
     #[tokio::test]
     async fn test_policy_repository_crud() {
         let repo = InMemoryPolicyRepository::new();
@@ -53,6 +53,11 @@ struct ApiKeyRow {
     label: String,
     created_at: DateTime<Utc>,
     last_used_at: Option<DateTime<Utc>>,
+    scopes: Vec<String>,
+    org_id: Option<Uuid>,
+    expires_at: Option<DateTime<Utc>>,
+    allowed_ips: Vec<String>,
+    rate_limit_per_minute: Option<i32>,
 }
 
 impl From<ApiKeyRow> for ApiKeyEntity {
@@ -65,6 +70,11 @@ impl From<ApiKeyRow> for ApiKeyEntity {
             label: row.label,
             created_at: row.created_at,
             last_used_at: row.last_used_at,
+            scopes: row.scopes,
+            org_id: row.org_id,
+            expires_at: row.expires_at,
+            allowed_ips: row.allowed_ips,
+            rate_limit_per_minute: row.rate_limit_per_minute.map(|n| n.max(0) as u32),
         }
     }
 }
@@ -74,9 +84,11 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
     async fn create(&self, entity: ApiKeyEntity) -> Result<ApiKeyEntity, AppError> {
         let row: ApiKeyRow = sqlx::query_as(
             r#"
-            INSERT INTO api_keys (id, user_id, key_hash, key_prefix, label, created_at, last_used_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING id, user_id, key_hash, key_prefix, label, created_at, last_used_at
+            INSERT INTO api_keys (id, user_id, key_hash, key_prefix, label, created_at, last_used_at,
+                                  scopes, org_id, expires_at, allowed_ips, rate_limit_per_minute)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING id, user_id, key_hash, key_prefix, label, created_at, last_used_at,
+                      scopes, org_id, expires_at, allowed_ips, rate_limit_per_minute
             "#,
         )
         .bind(entity.id)
@@ -86,6 +98,11 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         .bind(&entity.label)
         .bind(entity.created_at)
         .bind(entity.last_used_at)
+        .bind(&entity.scopes)
+        .bind(entity.org_id)
+        .bind(entity.expires_at)
+        .bind(&entity.allowed_ips)
+        .bind(entity.rate_limit_per_minute.map(|n| n as i32))
         .fetch_one(&self.pool)
         .await
         .map_err(|e| {
@@ -108,7 +125,8 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<ApiKeyEntity>, AppError> {
         let rows: Vec<ApiKeyRow> = sqlx::query_as(
             r#"
-            SELECT id, user_id, key_hash, key_prefix, label, created_at, last_used_at
+            SELECT id, user_id, key_hash, key_prefix, label, created_at, last_used_at,
+                   scopes, org_id, expires_at, allowed_ips, rate_limit_per_minute
             FROM api_keys WHERE user_id = $1
             ORDER BY created_at
             "#,
@@ -124,7 +142,8 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
     async fn find_one_by_user_id(&self, user_id: Uuid) -> Result<Option<ApiKeyEntity>, AppError> {
         let row: Option<ApiKeyRow> = sqlx::query_as(
             r#"
-            SELECT id, user_id, key_hash, key_prefix, label, created_at, last_used_at
+            SELECT id, user_id, key_hash, key_prefix, label, created_at, last_used_at,
+                   scopes, org_id, expires_at, allowed_ips, rate_limit_per_minute
             FROM api_keys WHERE user_id = $1
             ORDER BY created_at
             LIMIT 1
@@ -147,7 +166,8 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
 
         let rows: Vec<ApiKeyRow> = sqlx::query_as(
             r#"
-            SELECT id, user_id, key_hash, key_prefix, label, created_at, last_used_at
+            SELECT id, user_id, key_hash, key_prefix, label, created_at, last_used_at,
+                   scopes, org_id, expires_at, allowed_ips, rate_limit_per_minute
             FROM api_keys WHERE key_prefix = $1
             "#,
         )
@@ -202,4 +222,14 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
 
         Ok(())
     }
+
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM api_keys WHERE org_id = $1")
+            .bind(org_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(result.rows_affected())
+    }
 }
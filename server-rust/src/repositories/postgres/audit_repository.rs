@@ -11,7 +11,16 @@ use crate::repositories::pagination::{cap_limit, cap_offset};
 
 #[cfg(test)]
 use crate::repositories::pagination::{DEFAULT_MAX_OFFSET, DEFAULT_MAX_PAGE_SIZE};
-use crate::repositories::{AuditEventType, AuditLogEntry, AuditLogQuery, AuditLogRepository};
+use crate::repositories::{
+    AuditActorType, AuditChainLink, AuditCheckpoint, AuditEventType, AuditLogEntry, AuditLogQuery,
+    AuditLogRepository, AUDIT_GENESIS_HASH,
+};
+
+/// Advisory lock key serializing appends to the audit hash chain
+const AUDIT_CHAIN_ADVISORY_LOCK_KEY: i64 = 0x4155_4449_5443_4841; // "AUDITCHA"
+
+/// Chained entries fetched per batch when walking the chain
+const MAX_CHAIN_BATCH: u32 = 1000;
 
 /// PostgreSQL audit log repository
 pub struct PostgresAuditLogRepository {
@@ -31,6 +40,7 @@ struct AuditLogRow {
     id: Uuid,
     event_type: String,
     actor_user_id: Option<Uuid>,
+    actor_type: String,
     /// S-23: Session ID for forensic correlation
     session_id: Option<Uuid>,
     org_id: Option<Uuid>,
@@ -40,6 +50,13 @@ struct AuditLogRow {
     user_agent: Option<String>,
     metadata: Value,
     created_at: DateTime<Utc>,
+    seq: Option<i64>,
+    prev_hash: Option<String>,
+    org_prev_hash: Option<String>,
+    content_hash: Option<String>,
+    personal_hash: Option<String>,
+    entry_hash: Option<String>,
+    redacted: bool,
 }
 
 impl TryFrom<AuditLogRow> for AuditLogEntry {
@@ -49,10 +66,37 @@ impl TryFrom<AuditLogRow> for AuditLogEntry {
         let event_type = AuditEventType::from_str(&row.event_type)
             .ok_or_else(|| AppError::Database(format!("Invalid event type: {}", row.event_type)))?;
 
+        // Rows written before chaining have no link
+        let chain = match (
+            row.seq,
+            row.prev_hash,
+            row.content_hash,
+            row.personal_hash,
+            row.entry_hash,
+        ) {
+            (
+                Some(seq),
+                Some(prev_hash),
+                Some(content_hash),
+                Some(personal_hash),
+                Some(entry_hash),
+            ) => Some(AuditChainLink {
+                seq,
+                prev_hash,
+                org_prev_hash: row.org_prev_hash,
+                content_hash,
+                personal_hash,
+                entry_hash,
+                redacted: row.redacted,
+            }),
+            _ => None,
+        };
+
         Ok(Self {
             id: row.id,
             event_type,
             actor_user_id: row.actor_user_id,
+            actor_type: AuditActorType::from_str(&row.actor_type).unwrap_or_default(),
             session_id: row.session_id,
             org_id: row.org_id,
             target_type: row.target_type,
@@ -61,10 +105,35 @@ impl TryFrom<AuditLogRow> for AuditLogEntry {
             user_agent: row.user_agent,
             metadata: row.metadata,
             created_at: row.created_at,
+            chain,
         })
     }
 }
 
+/// Row type for audit checkpoint queries
+#[derive(sqlx::FromRow)]
+struct AuditCheckpointRow {
+    id: Uuid,
+    seq: i64,
+    entry_hash: String,
+    public_key: String,
+    signature: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AuditCheckpointRow> for AuditCheckpoint {
+    fn from(row: AuditCheckpointRow) -> Self {
+        Self {
+            id: row.id,
+            seq: row.seq,
+            entry_hash: row.entry_hash,
+            public_key: row.public_key,
+            signature: row.signature,
+            created_at: row.created_at,
+        }
+    }
+}
+
 fn cap_pagination_values(query: &AuditLogQuery) -> (i64, i64) {
     let limit = cap_limit(query.limit.unwrap_or(100)) as i64;
     let offset = cap_offset(query.offset.unwrap_or(0)) as i64;
@@ -124,17 +193,66 @@ fn append_where_conditions<'a>(builder: &mut QueryBuilder<'a, Postgres>, query:
 
 #[async_trait]
 impl AuditLogRepository for PostgresAuditLogRepository {
-    async fn create(&self, entry: AuditLogEntry) -> Result<AuditLogEntry, AppError> {
-        let row: AuditLogRow = sqlx::query_as(
+    async fn create(&self, mut entry: AuditLogEntry) -> Result<AuditLogEntry, AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // Appends are serialized so every entry sees the current chain head
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(AUDIT_CHAIN_ADVISORY_LOCK_KEY)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let tail: Option<(i64, String)> = sqlx::query_as(
+            "SELECT seq, entry_hash FROM audit_logs WHERE seq IS NOT NULL ORDER BY seq DESC LIMIT 1",
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        let (seq, prev_hash) = match tail {
+            Some((seq, hash)) => (seq + 1, hash),
+            None => (1, AUDIT_GENESIS_HASH.to_string()),
+        };
+
+        let org_prev_hash = match entry.org_id {
+            Some(org_id) => {
+                let hash: Option<(String,)> = sqlx::query_as(
+                    r#"
+                    SELECT entry_hash FROM audit_logs
+                    WHERE org_id = $1 AND seq IS NOT NULL
+                    ORDER BY seq DESC LIMIT 1
+                    "#,
+                )
+                .bind(org_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?;
+                Some(hash.map_or_else(|| AUDIT_GENESIS_HASH.to_string(), |(h,)| h))
+            }
+            None => None,
+        };
+
+        entry.created_at = Utc::now();
+        entry.link(seq, &prev_hash, org_prev_hash.as_deref());
+        let link = entry
+            .chain
+            .as_ref()
+            .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Audit entry was not linked")))?;
+
+        sqlx::query(
             r#"
-            INSERT INTO audit_logs (id, event_type, actor_user_id, session_id, org_id, target_type, target_id, ip_address, user_agent, metadata)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            RETURNING id, event_type, actor_user_id, session_id, org_id, target_type, target_id, ip_address, user_agent, metadata, created_at
+            INSERT INTO audit_logs (id, event_type, actor_user_id, actor_type, session_id, org_id, target_type, target_id, ip_address, user_agent, metadata, created_at, seq, prev_hash, org_prev_hash, content_hash, personal_hash, entry_hash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
             "#,
         )
         .bind(entry.id)
         .bind(entry.event_type.as_str())
         .bind(entry.actor_user_id)
+        .bind(entry.actor_type.as_str())
         .bind(entry.session_id)
         .bind(entry.org_id)
         .bind(&entry.target_type)
@@ -142,17 +260,28 @@ impl AuditLogRepository for PostgresAuditLogRepository {
         .bind(&entry.ip_address)
         .bind(&entry.user_agent)
         .bind(&entry.metadata)
-        .fetch_one(&self.pool)
+        .bind(entry.created_at)
+        .bind(link.seq)
+        .bind(&link.prev_hash)
+        .bind(&link.org_prev_hash)
+        .bind(&link.content_hash)
+        .bind(&link.personal_hash)
+        .bind(&link.entry_hash)
+        .execute(&mut *tx)
         .await
         .map_err(|e| AppError::Database(e.to_string()))?;
 
-        row.try_into()
+        tx.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(entry)
     }
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<AuditLogEntry>, AppError> {
         let row: Option<AuditLogRow> = sqlx::query_as(
             r#"
-            SELECT id, event_type, actor_user_id, session_id, org_id, target_type, target_id, ip_address, user_agent, metadata, created_at
+            SELECT id, event_type, actor_user_id, actor_type, session_id, org_id, target_type, target_id, ip_address, user_agent, metadata, created_at, seq, prev_hash, org_prev_hash, content_hash, personal_hash, entry_hash, redacted
             FROM audit_logs WHERE id = $1
             "#,
         )
@@ -171,7 +300,7 @@ impl AuditLogRepository for PostgresAuditLogRepository {
         let (limit, offset) = cap_pagination_values(&query);
 
         let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
-            "SELECT id, event_type, actor_user_id, session_id, org_id, target_type, target_id, ip_address, user_agent, metadata, created_at FROM audit_logs",
+            "SELECT id, event_type, actor_user_id, actor_type, session_id, org_id, target_type, target_id, ip_address, user_agent, metadata, created_at, seq, prev_hash, org_prev_hash, content_hash, personal_hash, entry_hash, redacted FROM audit_logs",
         );
         append_where_conditions(&mut builder, &query);
         builder.push(" ORDER BY created_at DESC LIMIT ");
@@ -212,8 +341,9 @@ impl AuditLogRepository for PostgresAuditLogRepository {
 
         let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
             r#"WITH filtered AS (
-                SELECT id, event_type, actor_user_id, session_id, org_id,
+                SELECT id, event_type, actor_user_id, actor_type, session_id, org_id,
                        target_type, target_id, ip_address, user_agent, metadata, created_at,
+                       seq, prev_hash, org_prev_hash, content_hash, personal_hash, entry_hash, redacted,
                        COUNT(*) OVER() as total_count
                 FROM audit_logs"#,
         );
@@ -221,8 +351,9 @@ impl AuditLogRepository for PostgresAuditLogRepository {
         builder.push(
             r#"
             )
-            SELECT id, event_type, actor_user_id, session_id, org_id,
+            SELECT id, event_type, actor_user_id, actor_type, session_id, org_id,
                    target_type, target_id, ip_address, user_agent, metadata, created_at,
+                   seq, prev_hash, org_prev_hash, content_hash, personal_hash, entry_hash, redacted,
                    total_count
             FROM filtered
             ORDER BY created_at DESC
@@ -234,17 +365,8 @@ impl AuditLogRepository for PostgresAuditLogRepository {
 
         #[derive(sqlx::FromRow)]
         struct AuditLogRowWithCount {
-            id: Uuid,
-            event_type: String,
-            actor_user_id: Option<Uuid>,
-            session_id: Option<Uuid>,
-            org_id: Option<Uuid>,
-            target_type: Option<String>,
-            target_id: Option<Uuid>,
-            ip_address: Option<String>,
-            user_agent: Option<String>,
-            metadata: Value,
-            created_at: DateTime<Utc>,
+            #[sqlx(flatten)]
+            row: AuditLogRow,
             total_count: i64,
         }
 
@@ -261,27 +383,8 @@ impl AuditLogRepository for PostgresAuditLogRepository {
             .unwrap_or(0);
 
         // Convert to entries
-        let entries: Result<Vec<AuditLogEntry>, AppError> = rows
-            .into_iter()
-            .map(|row| {
-                let event_type = AuditEventType::from_str(&row.event_type).ok_or_else(|| {
-                    AppError::Database(format!("Invalid event type: {}", row.event_type))
-                })?;
-                Ok(AuditLogEntry {
-                    id: row.id,
-                    event_type,
-                    actor_user_id: row.actor_user_id,
-                    session_id: row.session_id,
-                    org_id: row.org_id,
-                    target_type: row.target_type,
-                    target_id: row.target_id,
-                    ip_address: row.ip_address,
-                    user_agent: row.user_agent,
-                    metadata: row.metadata,
-                    created_at: row.created_at,
-                })
-            })
-            .collect();
+        let entries: Result<Vec<AuditLogEntry>, AppError> =
+            rows.into_iter().map(|r| r.row.try_into()).collect();
 
         Ok((entries?, total))
     }
@@ -296,15 +399,137 @@ impl AuditLogRepository for PostgresAuditLogRepository {
         Ok(result.rows_affected())
     }
 
-    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
-        let result = sqlx::query("DELETE FROM audit_logs WHERE org_id = $1")
-            .bind(org_id)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| AppError::Database(e.to_string()))?;
+    async fn redact_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE audit_logs
+            SET actor_user_id = NULL, session_id = NULL, target_type = NULL, target_id = NULL,
+                ip_address = NULL, user_agent = NULL, metadata = '{}'::jsonb, redacted = TRUE
+            WHERE org_id = $1 AND redacted = FALSE
+            "#,
+        )
+        .bind(org_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
 
         Ok(result.rows_affected())
     }
+
+    async fn list_chain(
+        &self,
+        org_id: Option<Uuid>,
+        after_seq: i64,
+        limit: u32,
+    ) -> Result<Vec<AuditLogEntry>, AppError> {
+        let limit = limit.min(MAX_CHAIN_BATCH) as i64;
+        let rows: Vec<AuditLogRow> = sqlx::query_as(
+            r#"
+            SELECT id, event_type, actor_user_id, actor_type, session_id, org_id, target_type, target_id, ip_address, user_agent, metadata, created_at, seq, prev_hash, org_prev_hash, content_hash, personal_hash, entry_hash, redacted
+            FROM audit_logs
+            WHERE seq > $1 AND ($2::uuid IS NULL OR org_id = $2)
+            ORDER BY seq ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(after_seq)
+        .bind(org_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn chain_head(&self) -> Result<Option<AuditChainLink>, AppError> {
+        let row: Option<AuditLogRow> = sqlx::query_as(
+            r#"
+            SELECT id, event_type, actor_user_id, actor_type, session_id, org_id, target_type, target_id, ip_address, user_agent, metadata, created_at, seq, prev_hash, org_prev_hash, content_hash, personal_hash, entry_hash, redacted
+            FROM audit_logs
+            WHERE seq IS NOT NULL
+            ORDER BY seq DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        match row {
+            Some(r) => Ok(AuditLogEntry::try_from(r)?.chain),
+            None => Ok(None),
+        }
+    }
+
+    async fn create_checkpoint(
+        &self,
+        checkpoint: AuditCheckpoint,
+    ) -> Result<AuditCheckpoint, AppError> {
+        let row: AuditCheckpointRow = sqlx::query_as(
+            r#"
+            INSERT INTO audit_checkpoints (id, seq, entry_hash, public_key, signature, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, seq, entry_hash, public_key, signature, created_at
+            "#,
+        )
+        .bind(checkpoint.id)
+        .bind(checkpoint.seq)
+        .bind(&checkpoint.entry_hash)
+        .bind(&checkpoint.public_key)
+        .bind(&checkpoint.signature)
+        .bind(checkpoint.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.into())
+    }
+
+    async fn list_checkpoints(&self) -> Result<Vec<AuditCheckpoint>, AppError> {
+        let rows: Vec<AuditCheckpointRow> = sqlx::query_as(
+            r#"
+            SELECT id, seq, entry_hash, public_key, signature, created_at
+            FROM audit_checkpoints
+            ORDER BY seq ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_sink_cursor(&self, sink: &str) -> Result<Option<i64>, AppError> {
+        let row: Option<(i64,)> =
+            sqlx::query_as("SELECT last_seq FROM audit_sink_cursors WHERE sink = $1")
+                .bind(sink)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.map(|(seq,)| seq))
+    }
+
+    async fn set_sink_cursor(&self, sink: &str, seq: i64) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_sink_cursors (sink, last_seq, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (sink) DO UPDATE
+            SET last_seq = GREATEST(audit_sink_cursors.last_seq, EXCLUDED.last_seq),
+                updated_at = NOW()
+            "#,
+        )
+        .bind(sink)
+        .bind(seq)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
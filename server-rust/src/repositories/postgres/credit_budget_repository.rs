@@ -0,0 +1,407 @@
+//! PostgreSQL credit budget repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{
+    BudgetUsageKey, CreditBudgetCharge, CreditBudgetEntity, CreditBudgetRepository,
+};
+
+const COLUMNS: &str = "id, scope, user_id, org_id, api_key_id, currency, period, soft_limit, \
+     hard_limit, created_by, created_at, updated_at";
+
+/// Add `amount` to the usage of every charge inside an open DB transaction,
+/// all or nothing
+///
+/// Fails with `BudgetExceeded` if any charge would exceed its hard limit;
+/// the caller's transaction must then be rolled back. Returns the usage of
+/// each charge afterwards, in order.
+pub(crate) async fn consume_budget_charges(
+    conn: &mut PgConnection,
+    charges: &[CreditBudgetCharge],
+    amount: i64,
+) -> Result<Vec<i64>, AppError> {
+    // The insert below only checks the limit when the counter exists
+    if let Some(charge) = charges
+        .iter()
+        .find(|c| c.budget.hard_limit.is_some_and(|hard| amount > hard))
+    {
+        let used = budget_usage(conn, charge.key()).await?;
+        return Err(charge.exceeded(used, amount));
+    }
+
+    // Lock counters in a fixed order so concurrent spends cannot deadlock
+    let mut order: Vec<usize> = (0..charges.len()).collect();
+    order.sort_by_key(|&i| (charges[i].budget.id, charges[i].period_start));
+
+    let mut used_after = vec![0; charges.len()];
+    for i in order {
+        let charge = &charges[i];
+        let used: Option<i64> = sqlx::query_scalar(
+            r#"
+            INSERT INTO credit_budget_usage (budget_id, period_start, used, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (budget_id, period_start) DO UPDATE
+            SET used = credit_budget_usage.used + EXCLUDED.used, updated_at = NOW()
+            WHERE $4::BIGINT IS NULL OR credit_budget_usage.used + EXCLUDED.used <= $4
+            RETURNING used
+            "#,
+        )
+        .bind(charge.budget.id)
+        .bind(charge.period_start)
+        .bind(amount)
+        .bind(charge.budget.hard_limit)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        match used {
+            Some(used) => used_after[i] = used,
+            None => {
+                let used = budget_usage(conn, charge.key()).await?;
+                return Err(charge.exceeded(used, amount));
+            }
+        }
+    }
+    Ok(used_after)
+}
+
+async fn budget_usage(conn: &mut PgConnection, key: BudgetUsageKey) -> Result<i64, AppError> {
+    let used: Option<i64> = sqlx::query_scalar(
+        "SELECT used FROM credit_budget_usage WHERE budget_id = $1 AND period_start = $2",
+    )
+    .bind(key.budget_id)
+    .bind(key.period_start)
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| AppError::Database(e.to_string()))?;
+
+    Ok(used.unwrap_or(0))
+}
+
+/// Record the counters a hold consumed from inside an open DB transaction
+pub(crate) async fn insert_hold_reservations(
+    conn: &mut PgConnection,
+    hold_id: Uuid,
+    keys: &[BudgetUsageKey],
+) -> Result<(), AppError> {
+    for key in keys {
+        sqlx::query(
+            r#"
+            INSERT INTO credit_budget_hold_reservations (hold_id, budget_id, period_start)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (hold_id, budget_id) DO NOTHING
+            "#,
+        )
+        .bind(hold_id)
+        .bind(key.budget_id)
+        .bind(key.period_start)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// PostgreSQL credit budget repository
+pub struct PostgresCreditBudgetRepository {
+    pool: PgPool,
+}
+
+impl PostgresCreditBudgetRepository {
+    /// Create a new Postgres credit budget repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct BudgetRow {
+    id: Uuid,
+    scope: String,
+    user_id: Option<Uuid>,
+    org_id: Option<Uuid>,
+    api_key_id: Option<Uuid>,
+    currency: String,
+    period: String,
+    soft_limit: Option<i64>,
+    hard_limit: Option<i64>,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<BudgetRow> for CreditBudgetEntity {
+    type Error = AppError;
+
+    fn try_from(row: BudgetRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            scope: row.scope.parse()?,
+            user_id: row.user_id,
+            org_id: row.org_id,
+            api_key_id: row.api_key_id,
+            currency: row.currency,
+            period: row.period.parse()?,
+            soft_limit: row.soft_limit,
+            hard_limit: row.hard_limit,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+fn map_foreign_key_error(e: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e {
+        if db_err.is_foreign_key_violation() {
+            return AppError::Validation(
+                "Budget refers to an unknown user, organization or API key".into(),
+            );
+        }
+    }
+    AppError::Database(e.to_string())
+}
+
+fn key_from_row((budget_id, period_start): (Uuid, DateTime<Utc>)) -> BudgetUsageKey {
+    BudgetUsageKey {
+        budget_id,
+        period_start,
+    }
+}
+
+#[async_trait]
+impl CreditBudgetRepository for PostgresCreditBudgetRepository {
+    async fn upsert(&self, budget: CreditBudgetEntity) -> Result<CreditBudgetEntity, AppError> {
+        let row: BudgetRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO credit_budgets (
+                id, scope, user_id, org_id, api_key_id, currency, period,
+                soft_limit, hard_limit, created_by, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (
+                scope,
+                COALESCE(user_id, '00000000-0000-0000-0000-000000000000'::uuid),
+                COALESCE(org_id, '00000000-0000-0000-0000-000000000000'::uuid),
+                COALESCE(api_key_id, '00000000-0000-0000-0000-000000000000'::uuid),
+                currency,
+                period
+            )
+            DO UPDATE SET soft_limit = EXCLUDED.soft_limit,
+                          hard_limit = EXCLUDED.hard_limit,
+                          updated_at = NOW()
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(budget.id)
+        .bind(budget.scope.as_str())
+        .bind(budget.user_id)
+        .bind(budget.org_id)
+        .bind(budget.api_key_id)
+        .bind(&budget.currency)
+        .bind(budget.period.as_str())
+        .bind(budget.soft_limit)
+        .bind(budget.hard_limit)
+        .bind(budget.created_by)
+        .bind(budget.created_at)
+        .bind(budget.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_foreign_key_error)?;
+
+        row.try_into()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<CreditBudgetEntity>, AppError> {
+        let row: Option<BudgetRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM credit_budgets WHERE id = $1",
+            COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn list(
+        &self,
+        user_id: Option<Uuid>,
+        org_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+    ) -> Result<Vec<CreditBudgetEntity>, AppError> {
+        let rows: Vec<BudgetRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM credit_budgets
+            WHERE ($1::uuid IS NULL OR user_id = $1)
+              AND ($2::uuid IS NULL OR org_id = $2)
+              AND ($3::uuid IS NULL OR api_key_id = $3)
+            ORDER BY created_at
+            "#,
+            COLUMNS
+        ))
+        .bind(user_id)
+        .bind(org_id)
+        .bind(api_key_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM credit_budgets WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_applicable(
+        &self,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        api_key_id: Option<Uuid>,
+        currency: &str,
+    ) -> Result<Vec<CreditBudgetEntity>, AppError> {
+        let rows: Vec<BudgetRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM credit_budgets
+            WHERE currency = $4
+              AND (
+                (scope = 'user' AND $2::uuid IS NULL AND user_id = $1)
+                OR (scope = 'org_member' AND org_id = $2 AND user_id = $1)
+                OR (scope = 'api_key' AND api_key_id = $3)
+              )
+            "#,
+            COLUMNS
+        ))
+        .bind(user_id)
+        .bind(org_id)
+        .bind(api_key_id)
+        .bind(currency.to_uppercase())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn get_usage(&self, key: BudgetUsageKey) -> Result<i64, AppError> {
+        let used: Option<i64> = sqlx::query_scalar(
+            "SELECT used FROM credit_budget_usage WHERE budget_id = $1 AND period_start = $2",
+        )
+        .bind(key.budget_id)
+        .bind(key.period_start)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(used.unwrap_or(0))
+    }
+
+    async fn consume(
+        &self,
+        charges: &[CreditBudgetCharge],
+        amount: i64,
+    ) -> Result<Vec<i64>, AppError> {
+        let mut db_tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        // The transaction rolls back on drop if a limit is exceeded
+        let used_after = consume_budget_charges(&mut db_tx, charges, amount).await?;
+
+        db_tx
+            .commit()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(used_after)
+    }
+
+    async fn release(&self, keys: &[BudgetUsageKey], amount: i64) -> Result<(), AppError> {
+        for key in keys {
+            sqlx::query(
+                r#"
+                UPDATE credit_budget_usage
+                SET used = GREATEST(used - $3, 0), updated_at = NOW()
+                WHERE budget_id = $1 AND period_start = $2
+                "#,
+            )
+            .bind(key.budget_id)
+            .bind(key.period_start)
+            .bind(amount)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    async fn reserve_hold(&self, hold_id: Uuid, keys: &[BudgetUsageKey]) -> Result<(), AppError> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        insert_hold_reservations(&mut conn, hold_id, keys).await
+    }
+
+    async fn hold_reservations(&self, hold_id: Uuid) -> Result<Vec<BudgetUsageKey>, AppError> {
+        let rows: Vec<(Uuid, DateTime<Utc>)> = sqlx::query_as(
+            "SELECT budget_id, period_start FROM credit_budget_hold_reservations WHERE hold_id = $1",
+        )
+        .bind(hold_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(key_from_row).collect())
+    }
+
+    async fn take_hold_reservations(&self, hold_id: Uuid) -> Result<Vec<BudgetUsageKey>, AppError> {
+        let rows: Vec<(Uuid, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            DELETE FROM credit_budget_hold_reservations
+            WHERE hold_id = $1
+            RETURNING budget_id, period_start
+            "#,
+        )
+        .bind(hold_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(key_from_row).collect())
+    }
+
+    async fn list_reserved_holds(&self, limit: u32) -> Result<Vec<Uuid>, AppError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT hold_id
+            FROM credit_budget_hold_reservations
+            GROUP BY hold_id
+            ORDER BY MIN(created_at)
+            LIMIT $1
+            "#,
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
@@ -5,9 +5,14 @@ use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use super::credit_budget_repository::{consume_budget_charges, insert_hold_reservations};
+use super::credit_repository::{
+    balance_target, draw_grants, insert_credit_transaction, member_spend_since,
+};
 use crate::errors::AppError;
 use crate::repositories::{
-    CreateHoldResult, CreditHoldEntity, CreditHoldRepository, CreditTransactionEntity, HoldStatus,
+    CreateHoldResult, CreditHoldEntity, CreditHoldRepository, CreditOwnerType,
+    CreditTransactionEntity, HoldStatus, SpendLimits,
 };
 
 /// PostgreSQL credit hold repository
@@ -27,6 +32,7 @@ struct CreditHoldRow {
     id: Uuid,
     user_id: Uuid,
     amount: i64,
+    captured_amount: i64,
     currency: String,
     idempotency_key: String,
     reference_type: Option<String>,
@@ -37,6 +43,8 @@ struct CreditHoldRow {
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     captured_transaction_id: Option<Uuid>,
+    owner_type: String,
+    org_id: Option<Uuid>,
 }
 
 impl From<CreditHoldRow> for CreditHoldEntity {
@@ -45,6 +53,7 @@ impl From<CreditHoldRow> for CreditHoldEntity {
             id: row.id,
             user_id: row.user_id,
             amount: row.amount,
+            captured_amount: row.captured_amount,
             currency: row.currency,
             idempotency_key: row.idempotency_key,
             reference_type: row.reference_type,
@@ -55,13 +64,19 @@ impl From<CreditHoldRow> for CreditHoldEntity {
             created_at: row.created_at,
             updated_at: row.updated_at,
             captured_transaction_id: row.captured_transaction_id,
+            owner_type: CreditOwnerType::from_str(&row.owner_type).unwrap_or(CreditOwnerType::User),
+            org_id: row.org_id,
         }
     }
 }
 
 #[async_trait]
 impl CreditHoldRepository for PostgresCreditHoldRepository {
-    async fn create_hold(&self, hold: CreditHoldEntity) -> Result<CreateHoldResult, AppError> {
+    async fn create_hold(
+        &self,
+        hold: CreditHoldEntity,
+        limits: &SpendLimits,
+    ) -> Result<(CreateHoldResult, Vec<i64>), AppError> {
         let mut tx = self
             .pool
             .begin()
@@ -75,14 +90,15 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
             INSERT INTO credit_holds (
                 id, user_id, amount, currency, idempotency_key,
                 reference_type, reference_id, status, expires_at,
-                metadata, created_at, updated_at
+                metadata, created_at, updated_at, owner_type, org_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             ON CONFLICT (user_id, idempotency_key) DO UPDATE
             SET updated_at = credit_holds.updated_at
             RETURNING id, user_id, amount, currency, idempotency_key,
                       reference_type, reference_id, status, expires_at,
-                      metadata, created_at, updated_at, captured_transaction_id
+                      metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                      captured_amount
             "#,
         )
         .bind(hold.id)
@@ -97,26 +113,30 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
         .bind(&hold.metadata)
         .bind(hold.created_at)
         .bind(hold.updated_at)
+        .bind(hold.owner_type.as_str())
+        .bind(hold.org_id)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
         let is_new = row.id == hold.id;
 
+        let mut budget_usage = Vec::new();
         if is_new {
             // SRV-01: Atomic balance check + held_balance update.
             // Uses conditional UPDATE to prevent TOCTOU race where two
             // concurrent holds both pass the service-level balance check.
-            let result = sqlx::query(
+            let (table, owner_col, owner_id) = balance_target(hold.org_id, hold.user_id);
+            let result = sqlx::query(&format!(
                 r#"
-                UPDATE credit_balances
+                UPDATE {table}
                 SET held_balance = held_balance + $2,
                     updated_at = NOW()
-                WHERE user_id = $1 AND currency = $3
+                WHERE {owner_col} = $1 AND currency = $3
                   AND (balance - held_balance) >= $2
-                "#,
-            )
-            .bind(hold.user_id)
+                "#
+            ))
+            .bind(owner_id)
             .bind(hold.amount)
             .bind(&hold.currency)
             .execute(&mut *tx)
@@ -129,6 +149,18 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
                     "Insufficient available balance".into(),
                 ));
             }
+
+            // Under the balance row lock; the new hold is already counted
+            if let (Some(org_id), Some(cap)) = (hold.org_id, &limits.member_cap) {
+                let used =
+                    member_spend_since(&mut tx, org_id, hold.user_id, &hold.currency, cap.since)
+                        .await?;
+                cap.check(used - hold.amount, hold.amount)?;
+            }
+
+            budget_usage = consume_budget_charges(&mut tx, &limits.budgets, hold.amount).await?;
+            let keys: Vec<_> = limits.budgets.iter().map(|c| c.key()).collect();
+            insert_hold_reservations(&mut tx, hold.id, &keys).await?;
         }
 
         tx.commit()
@@ -137,9 +169,9 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
 
         let entity: CreditHoldEntity = row.into();
         if is_new {
-            Ok(CreateHoldResult::Created(entity))
+            Ok((CreateHoldResult::Created(entity), budget_usage))
         } else {
-            Ok(CreateHoldResult::Existing(entity))
+            Ok((CreateHoldResult::Existing(entity), budget_usage))
         }
     }
 
@@ -148,7 +180,8 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
             r#"
             SELECT id, user_id, amount, currency, idempotency_key,
                    reference_type, reference_id, status, expires_at,
-                   metadata, created_at, updated_at, captured_transaction_id
+                   metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                   captured_amount
             FROM credit_holds
             WHERE id = $1
             "#,
@@ -170,7 +203,8 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
             r#"
             SELECT id, user_id, amount, currency, idempotency_key,
                    reference_type, reference_id, status, expires_at,
-                   metadata, created_at, updated_at, captured_transaction_id
+                   metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                   captured_amount
             FROM credit_holds
             WHERE user_id = $1 AND idempotency_key = $2
             "#,
@@ -187,30 +221,38 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
     async fn capture_hold(
         &self,
         hold_id: Uuid,
-        transaction_id: Uuid,
-        credit_tx: CreditTransactionEntity,
+        mut credit_tx: CreditTransactionEntity,
+        release_remainder: bool,
     ) -> Result<(CreditHoldEntity, i64), AppError> {
+        let amount = credit_tx.amount.abs();
         let mut tx = self
             .pool
             .begin()
             .await
             .map_err(|e| AppError::Internal(e.into()))?;
 
-        // Update hold status atomically, only if pending and not expired
+        // Update hold status atomically, only if pending, not expired and
+        // the capture fits in what is left of the hold
         let row: Option<CreditHoldRow> = sqlx::query_as(
             r#"
             UPDATE credit_holds
-            SET status = 'captured',
+            SET captured_amount = captured_amount + $3,
+                status = CASE WHEN $4 OR captured_amount + $3 = amount
+                              THEN 'captured' ELSE 'pending' END,
                 captured_transaction_id = $2,
                 updated_at = NOW()
             WHERE id = $1 AND status = 'pending' AND expires_at > NOW()
+              AND captured_amount + $3 <= amount
             RETURNING id, user_id, amount, currency, idempotency_key,
                       reference_type, reference_id, status, expires_at,
-                      metadata, created_at, updated_at, captured_transaction_id
+                      metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                      captured_amount
             "#,
         )
         .bind(hold_id)
-        .bind(transaction_id)
+        .bind(credit_tx.id)
+        .bind(amount)
+        .bind(release_remainder)
         .fetch_optional(&mut *tx)
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
@@ -223,7 +265,8 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
                     r#"
                     SELECT id, user_id, amount, currency, idempotency_key,
                            reference_type, reference_id, status, expires_at,
-                           metadata, created_at, updated_at, captured_transaction_id
+                           metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                           captured_amount
                     FROM credit_holds WHERE id = $1
                     "#,
                 )
@@ -240,65 +283,61 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
                             h.status
                         )))
                     }
+                    Some(h) if h.expires_at > Utc::now() => {
+                        return Err(AppError::Validation(format!(
+                            "Capture of {} exceeds the remaining hold amount {}",
+                            amount,
+                            h.amount - h.captured_amount
+                        )))
+                    }
                     Some(_) => return Err(AppError::Validation("Hold has expired".into())),
                 }
             }
         };
 
+        // A settling capture gives back the uncaptured remainder as well
+        let released = if release_remainder {
+            row.amount - row.captured_amount + amount
+        } else {
+            amount
+        };
+
         // SRV-02: Reduce held_balance AND deduct actual balance in one transaction.
         // Previously these were separate transactions, risking inconsistency on crash.
-        sqlx::query(
+        let (table, owner_col, owner_id) = balance_target(row.org_id, row.user_id);
+        sqlx::query(&format!(
             r#"
-            UPDATE credit_balances
-            SET held_balance = GREATEST(0, held_balance - $1),
+            UPDATE {table}
+            SET held_balance = GREATEST(0, held_balance - $4),
                 balance = balance - $1,
                 updated_at = NOW()
-            WHERE user_id = $2 AND currency = $3
-            "#,
-        )
-        .bind(row.amount)
-        .bind(row.user_id)
+            WHERE {owner_col} = $2 AND currency = $3
+            "#
+        ))
+        .bind(amount)
+        .bind(owner_id)
         .bind(&row.currency)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| AppError::Internal(e.into()))?;
-
-        // Insert credit transaction record (was previously in deduct_credit)
-        sqlx::query(
-            r#"
-            INSERT INTO credit_transactions (id, user_id, amount, currency, tx_type,
-                deposit_session_id, privacy_note_id, idempotency_key, reference_type,
-                reference_id, hold_id, metadata, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
-            "#,
-        )
-        .bind(credit_tx.id)
-        .bind(credit_tx.user_id)
-        .bind(credit_tx.amount)
-        .bind(&credit_tx.currency)
-        .bind(credit_tx.tx_type.as_str())
-        .bind(credit_tx.deposit_session_id)
-        .bind(credit_tx.privacy_note_id)
-        .bind(&credit_tx.idempotency_key)
-        .bind(&credit_tx.reference_type)
-        .bind(credit_tx.reference_id)
-        .bind(credit_tx.hold_id)
-        .bind(&credit_tx.metadata)
-        .bind(credit_tx.created_at)
+        .bind(released)
         .execute(&mut *tx)
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
         // Read back the new balance
-        let new_balance: i64 = sqlx::query_scalar(
-            "SELECT balance FROM credit_balances WHERE user_id = $1 AND currency = $2",
-        )
-        .bind(row.user_id)
+        let new_balance: i64 = sqlx::query_scalar(&format!(
+            "SELECT balance FROM {table} WHERE {owner_col} = $1 AND currency = $2"
+        ))
+        .bind(owner_id)
         .bind(&row.currency)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| AppError::Internal(e.into()))?;
 
+        // Spend promotional grants before paid credit
+        draw_grants(&mut tx, &mut credit_tx, amount, new_balance + amount).await?;
+
+        // Insert credit transaction record (was previously in deduct_credit)
+        insert_credit_transaction(&mut tx, &credit_tx).await?;
+
         tx.commit()
             .await
             .map_err(|e| AppError::Internal(e.into()))?;
@@ -322,7 +361,8 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
             WHERE id = $1 AND status = 'pending'
             RETURNING id, user_id, amount, currency, idempotency_key,
                       reference_type, reference_id, status, expires_at,
-                      metadata, created_at, updated_at, captured_transaction_id
+                      metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                      captured_amount
             "#,
         )
         .bind(hold_id)
@@ -338,7 +378,8 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
                     r#"
                     SELECT id, user_id, amount, currency, idempotency_key,
                            reference_type, reference_id, status, expires_at,
-                           metadata, created_at, updated_at, captured_transaction_id
+                           metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                           captured_amount
                     FROM credit_holds WHERE id = $1
                     "#,
                 )
@@ -359,17 +400,18 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
             }
         };
 
-        // Reduce held_balance
-        sqlx::query(
+        // Reduce held_balance by what was not captured
+        let (table, owner_col, owner_id) = balance_target(row.org_id, row.user_id);
+        sqlx::query(&format!(
             r#"
-            UPDATE credit_balances
+            UPDATE {table}
             SET held_balance = GREATEST(0, held_balance - $1),
                 updated_at = NOW()
-            WHERE user_id = $2 AND currency = $3
-            "#,
-        )
-        .bind(row.amount)
-        .bind(row.user_id)
+            WHERE {owner_col} = $2 AND currency = $3
+            "#
+        ))
+        .bind(row.amount - row.captured_amount)
+        .bind(owner_id)
         .bind(&row.currency)
         .execute(&mut *tx)
         .await
@@ -382,6 +424,113 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
         Ok(row.into())
     }
 
+    async fn extend_hold(
+        &self,
+        hold_id: Uuid,
+        amount: i64,
+        expires_at: DateTime<Utc>,
+        limits: &SpendLimits,
+    ) -> Result<(CreditHoldEntity, Vec<i64>), AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        // Lock the hold so concurrent captures see the new amount
+        let existing: Option<CreditHoldRow> = sqlx::query_as(
+            r#"
+            SELECT id, user_id, amount, currency, idempotency_key,
+                   reference_type, reference_id, status, expires_at,
+                   metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                   captured_amount
+            FROM credit_holds WHERE id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(hold_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        let hold = match existing {
+            None => return Err(AppError::NotFound(format!("Hold {} not found", hold_id))),
+            Some(h) if h.status != "pending" => {
+                return Err(AppError::Validation(format!(
+                    "Hold cannot be extended, status: {}",
+                    h.status
+                )))
+            }
+            Some(h) if h.expires_at <= Utc::now() => {
+                return Err(AppError::Validation("Hold has expired".into()))
+            }
+            Some(h) => h,
+        };
+
+        // Reserve the increase atomically against the available balance (SRV-01)
+        let increase = amount - hold.amount;
+        let mut budget_usage = Vec::new();
+        if increase > 0 {
+            let (table, owner_col, owner_id) = balance_target(hold.org_id, hold.user_id);
+            let result = sqlx::query(&format!(
+                r#"
+                UPDATE {table}
+                SET held_balance = held_balance + $2,
+                    updated_at = NOW()
+                WHERE {owner_col} = $1 AND currency = $3
+                  AND (balance - held_balance) >= $2
+                "#
+            ))
+            .bind(owner_id)
+            .bind(increase)
+            .bind(&hold.currency)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+            if result.rows_affected() == 0 {
+                return Err(AppError::Validation(
+                    "Insufficient available balance".into(),
+                ));
+            }
+
+            if let (Some(org_id), Some(cap)) = (hold.org_id, &limits.member_cap) {
+                let used =
+                    member_spend_since(&mut tx, org_id, hold.user_id, &hold.currency, cap.since)
+                        .await?;
+                cap.check(used, increase)?;
+            }
+
+            budget_usage = consume_budget_charges(&mut tx, &limits.budgets, increase).await?;
+        }
+
+        let row: CreditHoldRow = sqlx::query_as(
+            r#"
+            UPDATE credit_holds
+            SET amount = GREATEST(amount, $2),
+                expires_at = GREATEST(expires_at, $3),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, user_id, amount, currency, idempotency_key,
+                      reference_type, reference_id, status, expires_at,
+                      metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                      captured_amount
+            "#,
+        )
+        .bind(hold_id)
+        .bind(amount)
+        .bind(expires_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok((row.into(), budget_usage))
+    }
+
     async fn get_pending_holds(
         &self,
         user_id: Uuid,
@@ -392,9 +541,10 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
                 r#"
                 SELECT id, user_id, amount, currency, idempotency_key,
                        reference_type, reference_id, status, expires_at,
-                       metadata, created_at, updated_at, captured_transaction_id
+                       metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                       captured_amount
                 FROM credit_holds
-                WHERE user_id = $1 AND currency = $2 AND status = 'pending'
+                WHERE user_id = $1 AND org_id IS NULL AND currency = $2 AND status = 'pending'
                 ORDER BY created_at DESC
                 "#,
             )
@@ -408,9 +558,10 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
                 r#"
                 SELECT id, user_id, amount, currency, idempotency_key,
                        reference_type, reference_id, status, expires_at,
-                       metadata, created_at, updated_at, captured_transaction_id
+                       metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                       captured_amount
                 FROM credit_holds
-                WHERE user_id = $1 AND status = 'pending'
+                WHERE user_id = $1 AND org_id IS NULL AND status = 'pending'
                 ORDER BY created_at DESC
                 "#,
             )
@@ -423,6 +574,31 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
         Ok(rows.into_iter().map(Into::into).collect())
     }
 
+    async fn get_pending_org_holds(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+    ) -> Result<Vec<CreditHoldEntity>, AppError> {
+        let rows: Vec<CreditHoldRow> = sqlx::query_as(
+            r#"
+            SELECT id, user_id, amount, currency, idempotency_key,
+                   reference_type, reference_id, status, expires_at,
+                   metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                   captured_amount
+            FROM credit_holds
+            WHERE org_id = $1 AND ($2::TEXT IS NULL OR currency = $2) AND status = 'pending'
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(org_id)
+        .bind(currency)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
     async fn expire_holds(&self) -> Result<u64, AppError> {
         let mut tx = self
             .pool
@@ -439,7 +615,8 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
             WHERE status = 'pending' AND expires_at <= NOW()
             RETURNING id, user_id, amount, currency, idempotency_key,
                       reference_type, reference_id, status, expires_at,
-                      metadata, created_at, updated_at, captured_transaction_id
+                      metadata, created_at, updated_at, captured_transaction_id, owner_type, org_id,
+                      captured_amount
             "#,
         )
         .fetch_all(&mut *tx)
@@ -453,7 +630,7 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
             tracing::info!(
                 hold_id = %hold.id,
                 user_id = %hold.user_id,
-                amount_lamports = hold.amount,
+                amount_lamports = hold.amount - hold.captured_amount,
                 currency = %hold.currency,
                 reference_type = ?hold.reference_type,
                 reference_id = ?hold.reference_id,
@@ -463,14 +640,16 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
             );
         }
 
-        // R-07: Batch update held_balances in a single query using aggregated amounts
+        // R-07: Batch update held_balances in a single query per balance table
+        // using aggregated amounts
         if !expired.is_empty() {
+            let expired_ids = expired.iter().map(|h| h.id).collect::<Vec<uuid::Uuid>>();
             sqlx::query(
                 r#"
                 WITH expired_totals AS (
-                    SELECT user_id, currency, SUM(amount) AS total_amount
+                    SELECT user_id, currency, SUM(amount - captured_amount) AS total_amount
                     FROM credit_holds
-                    WHERE status = 'expired' AND id = ANY($1)
+                    WHERE status = 'expired' AND id = ANY($1) AND org_id IS NULL
                     GROUP BY user_id, currency
                 )
                 UPDATE credit_balances cb
@@ -480,15 +659,32 @@ impl CreditHoldRepository for PostgresCreditHoldRepository {
                 WHERE cb.user_id = et.user_id AND cb.currency = et.currency
                 "#,
             )
-            .bind(
-                expired
-                    .iter()
-                    .map(|h| h.id)
-                    .collect::<Vec<uuid::Uuid>>(),
-            )
+            .bind(&expired_ids)
             .execute(&mut *tx)
             .await
             .map_err(|e| AppError::Internal(e.into()))?;
+
+            if expired.iter().any(|h| h.org_id.is_some()) {
+                sqlx::query(
+                    r#"
+                    WITH expired_totals AS (
+                        SELECT org_id, currency, SUM(amount - captured_amount) AS total_amount
+                        FROM credit_holds
+                        WHERE status = 'expired' AND id = ANY($1) AND org_id IS NOT NULL
+                        GROUP BY org_id, currency
+                    )
+                    UPDATE org_credit_balances ob
+                    SET held_balance = GREATEST(0, ob.held_balance - et.total_amount),
+                        updated_at = NOW()
+                    FROM expired_totals et
+                    WHERE ob.org_id = et.org_id AND ob.currency = et.currency
+                    "#,
+                )
+                .bind(&expired_ids)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::Internal(e.into()))?;
+            }
         }
 
         tx.commit()
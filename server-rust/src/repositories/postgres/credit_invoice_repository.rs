@@ -0,0 +1,149 @@
+//! PostgreSQL credit invoice repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::credit_invoice_repository::sort_for_numbering;
+use crate::repositories::{
+    CreditInvoiceEntity, CreditInvoiceRepository, CreditOwnerType, CreditTransactionEntity,
+};
+
+/// PostgreSQL credit invoice repository
+pub struct PostgresCreditInvoiceRepository {
+    pool: PgPool,
+}
+
+impl PostgresCreditInvoiceRepository {
+    /// Create a new Postgres credit invoice repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct InvoiceRow {
+    number: i64,
+    transaction_id: Uuid,
+    owner_type: String,
+    user_id: Uuid,
+    org_id: Option<Uuid>,
+    amount: i64,
+    currency: String,
+    deposited_at: DateTime<Utc>,
+    issued_at: DateTime<Utc>,
+}
+
+impl From<InvoiceRow> for CreditInvoiceEntity {
+    fn from(row: InvoiceRow) -> Self {
+        Self {
+            number: row.number,
+            transaction_id: row.transaction_id,
+            owner_type: CreditOwnerType::from_str(&row.owner_type).unwrap_or(CreditOwnerType::User),
+            user_id: row.user_id,
+            org_id: row.org_id,
+            amount: row.amount,
+            currency: row.currency,
+            deposited_at: row.deposited_at,
+            issued_at: row.issued_at,
+        }
+    }
+}
+
+#[async_trait]
+impl CreditInvoiceRepository for PostgresCreditInvoiceRepository {
+    async fn issue(
+        &self,
+        deposits: &[CreditTransactionEntity],
+    ) -> Result<Vec<CreditInvoiceEntity>, AppError> {
+        if deposits.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids: Vec<Uuid> = deposits.iter().map(|d| d.id).collect();
+
+        let mut db_tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        // The counter row serializes numbering, so numbers have no gaps
+        let mut last_number: i64 = sqlx::query_scalar(
+            "SELECT last_number FROM credit_invoice_counter WHERE id = TRUE FOR UPDATE",
+        )
+        .fetch_one(&mut *db_tx)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let invoiced: HashSet<Uuid> = sqlx::query_scalar(
+            "SELECT transaction_id FROM credit_invoices WHERE transaction_id = ANY($1)",
+        )
+        .bind(&ids)
+        .fetch_all(&mut *db_tx)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?
+        .into_iter()
+        .collect();
+
+        let mut missing: Vec<_> = deposits
+            .iter()
+            .filter(|d| !invoiced.contains(&d.id))
+            .collect();
+        sort_for_numbering(&mut missing);
+        missing.dedup_by_key(|d| d.id);
+
+        if !missing.is_empty() {
+            for deposit in missing {
+                last_number += 1;
+                sqlx::query(
+                    r#"
+                    INSERT INTO credit_invoices
+                        (number, transaction_id, owner_type, user_id, org_id, amount, currency,
+                         deposited_at, issued_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())
+                    "#,
+                )
+                .bind(last_number)
+                .bind(deposit.id)
+                .bind(deposit.owner_type.as_str())
+                .bind(deposit.user_id)
+                .bind(deposit.org_id)
+                .bind(deposit.amount)
+                .bind(&deposit.currency)
+                .bind(deposit.created_at)
+                .execute(&mut *db_tx)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            }
+            sqlx::query("UPDATE credit_invoice_counter SET last_number = $1 WHERE id = TRUE")
+                .bind(last_number)
+                .execute(&mut *db_tx)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        let rows: Vec<InvoiceRow> = sqlx::query_as(
+            r#"
+            SELECT number, transaction_id, owner_type, user_id, org_id, amount, currency,
+                   deposited_at, issued_at
+            FROM credit_invoices
+            WHERE transaction_id = ANY($1)
+            ORDER BY number
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&mut *db_tx)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        db_tx
+            .commit()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
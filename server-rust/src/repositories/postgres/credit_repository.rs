@@ -2,17 +2,24 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use super::credit_budget_repository::consume_budget_charges;
 use crate::errors::AppError;
 use crate::repositories::pagination::{cap_limit, cap_offset};
 use crate::repositories::{
-    CreditBalanceEntity, CreditRepository, CreditStats, CreditTransactionEntity, CreditTxType,
-    CurrencyCreditStats, UserCreditStats,
+    allocate_draw, promotional_draw, BalanceReconciliation, CreditBalanceDrift,
+    CreditBalanceEntity, CreditGrantEntity, CreditGrantSource, CreditGrantStatus,
+    CreditJournalEntry, CreditOwnerType, CreditRepository, CreditStats, CreditTransactionEntity,
+    CreditTxType, CurrencyCreditStats, LedgerAccount, LedgerAccountTotals, OrgCreditBalanceEntity,
+    OrgCreditMemberSettings, SpendLimits, UserCreditStats,
 };
 
+const GRANT_COLUMNS: &str = "id, user_id, currency, source, amount, remaining, priority, \
+    expires_at, status, transaction_id, reason, created_by, created_at, updated_at";
+
 /// SRV-16: Maximum metadata JSON size in bytes
 const MAX_METADATA_BYTES: usize = 10_000;
 
@@ -30,6 +37,170 @@ fn validate_metadata(metadata: &Option<serde_json::Value>) -> Result<(), AppErro
     Ok(())
 }
 
+/// Balance table and owner column a transaction applies to
+pub(crate) fn balance_target(
+    tx_org_id: Option<Uuid>,
+    user_id: Uuid,
+) -> (&'static str, &'static str, Uuid) {
+    match tx_org_id {
+        Some(org_id) => ("org_credit_balances", "org_id", org_id),
+        None => ("credit_balances", "user_id", user_id),
+    }
+}
+
+/// What a member has spent from an organization's balance since `since`,
+/// including the remainder of their pending holds
+///
+/// Called under the organization's balance row lock, which every spend,
+/// hold and capture on that balance takes, so the total cannot move until
+/// the transaction ends.
+pub(crate) async fn member_spend_since(
+    conn: &mut PgConnection,
+    org_id: Uuid,
+    user_id: Uuid,
+    currency: &str,
+    since: DateTime<Utc>,
+) -> Result<i64, AppError> {
+    sqlx::query_scalar(
+        r#"
+        SELECT (
+            SELECT COALESCE(SUM(ABS(amount)), 0)
+            FROM credit_transactions
+            WHERE org_id = $1 AND user_id = $2 AND currency = $3
+              AND tx_type = 'spend' AND created_at >= $4
+        )::BIGINT + (
+            SELECT COALESCE(SUM(amount - captured_amount), 0)
+            FROM credit_holds
+            WHERE org_id = $1 AND user_id = $2 AND currency = $3 AND status = 'pending'
+        )::BIGINT
+        "#,
+    )
+    .bind(org_id)
+    .bind(user_id)
+    .bind(currency)
+    .bind(since)
+    .fetch_one(conn)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))
+}
+
+/// Insert a journal entry inside an open DB transaction
+pub(super) async fn insert_journal_entry(
+    conn: &mut PgConnection,
+    entry: &CreditJournalEntry,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO credit_journal_entries (id, transaction_id, currency, amount,
+            debit_account, debit_owner_id, credit_account, credit_owner_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+    )
+    .bind(entry.id)
+    .bind(entry.transaction_id)
+    .bind(&entry.currency)
+    .bind(entry.amount)
+    .bind(entry.debit.account.as_str())
+    .bind(entry.debit.owner_id)
+    .bind(entry.credit.account.as_str())
+    .bind(entry.credit.owner_id)
+    .bind(entry.created_at)
+    .execute(conn)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))?;
+    Ok(())
+}
+
+/// Insert a credit transaction record and its journal entries inside an
+/// open DB transaction
+pub(crate) async fn insert_credit_transaction(
+    conn: &mut PgConnection,
+    tx: &CreditTransactionEntity,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO credit_transactions (id, user_id, amount, currency, tx_type,
+            deposit_session_id, privacy_note_id, idempotency_key, reference_type,
+            reference_id, hold_id, metadata, created_at, owner_type, org_id, grant_amount)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+        "#,
+    )
+    .bind(tx.id)
+    .bind(tx.user_id)
+    .bind(tx.amount)
+    .bind(&tx.currency)
+    .bind(tx.tx_type.as_str())
+    .bind(tx.deposit_session_id)
+    .bind(tx.privacy_note_id)
+    .bind(&tx.idempotency_key)
+    .bind(&tx.reference_type)
+    .bind(tx.reference_id)
+    .bind(tx.hold_id)
+    .bind(&tx.metadata)
+    .bind(tx.created_at)
+    .bind(tx.owner_type.as_str())
+    .bind(tx.org_id)
+    .bind(tx.grant_amount)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))?;
+
+    for entry in CreditJournalEntry::for_transaction(tx) {
+        insert_journal_entry(conn, &entry).await?;
+    }
+    Ok(())
+}
+
+/// Draw the promotional part of a personal debit from the user's grants
+/// (locked in spend order) and record it on the transaction.
+///
+/// `balance_before` is the owner's balance before the debit.
+pub(crate) async fn draw_grants(
+    conn: &mut PgConnection,
+    tx: &mut CreditTransactionEntity,
+    amount: i64,
+    balance_before: i64,
+) -> Result<(), AppError> {
+    if tx.org_id.is_some() {
+        return Ok(());
+    }
+
+    let grants: Vec<(Uuid, i64)> = sqlx::query_as(
+        r#"
+        SELECT id, remaining FROM credit_grants
+        WHERE user_id = $1 AND currency = $2 AND status = 'active' AND remaining > 0
+        ORDER BY priority DESC, expires_at ASC NULLS LAST, created_at ASC
+        FOR UPDATE
+        "#,
+    )
+    .bind(tx.user_id)
+    .bind(tx.currency.to_uppercase())
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| AppError::Internal(e.into()))?;
+
+    let promotional = grants.iter().map(|(_, remaining)| remaining).sum();
+    let draw = promotional_draw(tx, amount, balance_before, promotional);
+    for (grant_id, take) in allocate_draw(grants, draw) {
+        sqlx::query(
+            r#"
+            UPDATE credit_grants
+            SET remaining = remaining - $2,
+                status = CASE WHEN remaining - $2 <= 0 THEN 'depleted' ELSE status END,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(grant_id)
+        .bind(take)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    }
+    tx.grant_amount = -draw;
+    Ok(())
+}
+
 /// PostgreSQL credit repository
 pub struct PostgresCreditRepository {
     pool: PgPool,
@@ -39,6 +210,104 @@ impl PostgresCreditRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Expire a grant at `now`, forfeiting as much of its unspent credit as
+    /// is not reserved by holds
+    async fn forfeit_grant(
+        &self,
+        grant_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<CreditGrantEntity, AppError> {
+        let mut db_tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        let owner: Option<(Uuid, String)> =
+            sqlx::query_as("SELECT user_id, currency FROM credit_grants WHERE id = $1")
+                .bind(grant_id)
+                .fetch_optional(&mut *db_tx)
+                .await
+                .map_err(|e| AppError::Internal(e.into()))?;
+        let (user_id, currency) =
+            owner.ok_or_else(|| AppError::NotFound(format!("Grant {} not found", grant_id)))?;
+
+        // Lock the balance before the grant, in the same order as debits
+        let balance: Option<(i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT balance, held_balance FROM credit_balances
+            WHERE user_id = $1 AND currency = $2
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .bind(&currency)
+        .fetch_optional(&mut *db_tx)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        let row: CreditGrantRow = sqlx::query_as(&format!(
+            "SELECT {GRANT_COLUMNS} FROM credit_grants WHERE id = $1 FOR UPDATE"
+        ))
+        .bind(grant_id)
+        .fetch_one(&mut *db_tx)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+        let mut grant = CreditGrantEntity::from(row);
+        if !grant.is_active() {
+            return Err(AppError::Validation(format!(
+                "Grant is not active, status: {}",
+                grant.status.as_str()
+            )));
+        }
+        grant.expires_at = Some(grant.expires_at.map_or(now, |e| e.min(now)));
+
+        let available = balance.map_or(0, |(total, held)| (total - held).max(0));
+        let forfeit = grant.remaining.min(available);
+        if forfeit > 0 {
+            sqlx::query(
+                r#"
+                UPDATE credit_balances
+                SET balance = balance - $1, updated_at = NOW()
+                WHERE user_id = $2 AND currency = $3
+                "#,
+            )
+            .bind(forfeit)
+            .bind(user_id)
+            .bind(&currency)
+            .execute(&mut *db_tx)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+            let tx = CreditTransactionEntity::new_grant_expiration(&grant, forfeit);
+            insert_credit_transaction(&mut db_tx, &tx).await?;
+        }
+        grant.draw(forfeit, CreditGrantStatus::Expired);
+
+        sqlx::query(
+            r#"
+            UPDATE credit_grants
+            SET remaining = $2, status = $3, expires_at = $4, updated_at = $5
+            WHERE id = $1
+            "#,
+        )
+        .bind(grant.id)
+        .bind(grant.remaining)
+        .bind(grant.status.as_str())
+        .bind(grant.expires_at)
+        .bind(grant.updated_at)
+        .execute(&mut *db_tx)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        db_tx
+            .commit()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(grant)
+    }
 }
 
 /// Row type for credit balance queries
@@ -65,6 +334,94 @@ impl From<CreditBalanceRow> for CreditBalanceEntity {
     }
 }
 
+/// Row type for organization balance queries
+#[derive(sqlx::FromRow)]
+struct OrgCreditBalanceRow {
+    id: Uuid,
+    org_id: Uuid,
+    balance: i64,
+    held_balance: i64,
+    currency: String,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<OrgCreditBalanceRow> for OrgCreditBalanceEntity {
+    fn from(row: OrgCreditBalanceRow) -> Self {
+        Self {
+            id: row.id,
+            org_id: row.org_id,
+            balance: row.balance,
+            held_balance: row.held_balance,
+            currency: row.currency,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Row type for organization member credit settings
+#[derive(sqlx::FromRow)]
+struct OrgCreditMemberSettingsRow {
+    org_id: Uuid,
+    user_id: Uuid,
+    spend_allowed: Option<bool>,
+    monthly_cap: Option<i64>,
+    updated_by: Option<Uuid>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<OrgCreditMemberSettingsRow> for OrgCreditMemberSettings {
+    fn from(row: OrgCreditMemberSettingsRow) -> Self {
+        Self {
+            org_id: row.org_id,
+            user_id: row.user_id,
+            spend_allowed: row.spend_allowed,
+            monthly_cap: row.monthly_cap,
+            updated_by: row.updated_by,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Row type for credit grant queries
+#[derive(sqlx::FromRow)]
+struct CreditGrantRow {
+    id: Uuid,
+    user_id: Uuid,
+    currency: String,
+    source: String,
+    amount: i64,
+    remaining: i64,
+    priority: i32,
+    expires_at: Option<DateTime<Utc>>,
+    status: String,
+    transaction_id: Uuid,
+    reason: Option<String>,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CreditGrantRow> for CreditGrantEntity {
+    fn from(row: CreditGrantRow) -> Self {
+        Self {
+            id: row.id,
+            user_id: row.user_id,
+            currency: row.currency,
+            source: CreditGrantSource::from_str(&row.source).unwrap_or(CreditGrantSource::Manual),
+            amount: row.amount,
+            remaining: row.remaining,
+            priority: row.priority,
+            expires_at: row.expires_at,
+            status: CreditGrantStatus::from_str(&row.status).unwrap_or(CreditGrantStatus::Expired),
+            transaction_id: row.transaction_id,
+            reason: row.reason,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
 /// Row type for credit transaction queries
 #[derive(sqlx::FromRow)]
 struct CreditTransactionRow {
@@ -81,6 +438,9 @@ struct CreditTransactionRow {
     hold_id: Option<Uuid>,
     metadata: Option<serde_json::Value>,
     created_at: DateTime<Utc>,
+    owner_type: String,
+    org_id: Option<Uuid>,
+    grant_amount: i64,
 }
 
 impl From<CreditTransactionRow> for CreditTransactionEntity {
@@ -89,6 +449,7 @@ impl From<CreditTransactionRow> for CreditTransactionEntity {
             id: row.id,
             user_id: row.user_id,
             amount: row.amount,
+            grant_amount: row.grant_amount,
             currency: row.currency,
             tx_type: CreditTxType::from_str(&row.tx_type).unwrap_or(CreditTxType::Adjustment),
             deposit_session_id: row.deposit_session_id,
@@ -99,10 +460,21 @@ impl From<CreditTransactionRow> for CreditTransactionEntity {
             hold_id: row.hold_id,
             metadata: row.metadata,
             created_at: row.created_at,
+            owner_type: CreditOwnerType::from_str(&row.owner_type).unwrap_or(CreditOwnerType::User),
+            org_id: row.org_id,
         }
     }
 }
 
+/// `WHERE` condition selecting the transactions of one balance (`$1` is the
+/// owner ID)
+fn owner_filter(owner_type: CreditOwnerType) -> &'static str {
+    match owner_type {
+        CreditOwnerType::User => "user_id = $1 AND org_id IS NULL",
+        CreditOwnerType::Org => "org_id = $1",
+    }
+}
+
 #[async_trait]
 impl CreditRepository for PostgresCreditRepository {
     async fn get_balance(&self, user_id: Uuid, currency: &str) -> Result<i64, AppError> {
@@ -187,18 +559,20 @@ impl CreditRepository for PostgresCreditRepository {
             .await
             .map_err(|e| AppError::Internal(e.into()))?;
 
-        // Upsert balance with atomic increment
-        let new_balance: i64 = sqlx::query_scalar(
+        // Upsert balance with atomic increment (org-owned transactions credit
+        // the organization's balance)
+        let (table, owner_col, owner_id) = balance_target(tx.org_id, user_id);
+        let new_balance: i64 = sqlx::query_scalar(&format!(
             r#"
-            INSERT INTO credit_balances (user_id, balance, held_balance, currency, updated_at)
+            INSERT INTO {table} ({owner_col}, balance, held_balance, currency, updated_at)
             VALUES ($1, $2, 0, $3, NOW())
-            ON CONFLICT (user_id, currency) DO UPDATE
-            SET balance = credit_balances.balance + $2,
+            ON CONFLICT ({owner_col}, currency) DO UPDATE
+            SET balance = {table}.balance + $2,
                 updated_at = NOW()
             RETURNING balance
-            "#,
-        )
-        .bind(user_id)
+            "#
+        ))
+        .bind(owner_id)
         .bind(amount)
         .bind(currency)
         .fetch_one(&mut *db_tx)
@@ -206,30 +580,7 @@ impl CreditRepository for PostgresCreditRepository {
         .map_err(|e| AppError::Internal(e.into()))?;
 
         // Insert transaction record
-        sqlx::query(
-            r#"
-            INSERT INTO credit_transactions (id, user_id, amount, currency, tx_type,
-                deposit_session_id, privacy_note_id, idempotency_key, reference_type,
-                reference_id, hold_id, metadata, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
-            "#,
-        )
-        .bind(tx.id)
-        .bind(tx.user_id)
-        .bind(tx.amount)
-        .bind(&tx.currency)
-        .bind(tx.tx_type.as_str())
-        .bind(tx.deposit_session_id)
-        .bind(tx.privacy_note_id)
-        .bind(&tx.idempotency_key)
-        .bind(&tx.reference_type)
-        .bind(tx.reference_id)
-        .bind(tx.hold_id)
-        .bind(&tx.metadata)
-        .bind(tx.created_at)
-        .execute(&mut *db_tx)
-        .await
-        .map_err(|e| AppError::Internal(e.into()))?;
+        insert_credit_transaction(&mut db_tx, &tx).await?;
 
         db_tx
             .commit()
@@ -246,6 +597,20 @@ impl CreditRepository for PostgresCreditRepository {
         currency: &str,
         tx: CreditTransactionEntity,
     ) -> Result<i64, AppError> {
+        let (balance, _) = self
+            .deduct_credit_limited(user_id, amount, currency, tx, &SpendLimits::default())
+            .await?;
+        Ok(balance)
+    }
+
+    async fn deduct_credit_limited(
+        &self,
+        user_id: Uuid,
+        amount: i64,
+        currency: &str,
+        mut tx: CreditTransactionEntity,
+        limits: &SpendLimits,
+    ) -> Result<(i64, Vec<i64>), AppError> {
         if amount <= 0 {
             return Err(AppError::Validation(
                 "Deduction amount must be positive".into(),
@@ -262,16 +627,17 @@ impl CreditRepository for PostgresCreditRepository {
 
         // Atomic check-and-deduct checking available balance (balance - held_balance)
         // This ensures we don't spend held credits and returns the new balance
-        let new_balance: Option<i64> = sqlx::query_scalar(
+        let (table, owner_col, owner_id) = balance_target(tx.org_id, user_id);
+        let new_balance: Option<i64> = sqlx::query_scalar(&format!(
             r#"
-            UPDATE credit_balances
+            UPDATE {table}
             SET balance = balance - $1, updated_at = NOW()
-            WHERE user_id = $2 AND currency = $3 AND (balance - held_balance) >= $1
+            WHERE {owner_col} = $2 AND currency = $3 AND (balance - held_balance) >= $1
             RETURNING balance
-            "#,
-        )
+            "#
+        ))
         .bind(amount)
-        .bind(user_id)
+        .bind(owner_id)
         .bind(&currency)
         .fetch_optional(&mut *db_tx)
         .await
@@ -281,10 +647,10 @@ impl CreditRepository for PostgresCreditRepository {
             Some(b) => b,
             None => {
                 // Check current balances to give better error
-                let row: Option<(i64, i64)> = sqlx::query_as(
-                    "SELECT balance, held_balance FROM credit_balances WHERE user_id = $1 AND currency = $2",
-                )
-                .bind(user_id)
+                let row: Option<(i64, i64)> = sqlx::query_as(&format!(
+                    "SELECT balance, held_balance FROM {table} WHERE {owner_col} = $1 AND currency = $2"
+                ))
+                .bind(owner_id)
                 .bind(&currency)
                 .fetch_optional(&mut *db_tx)
                 .await
@@ -299,38 +665,25 @@ impl CreditRepository for PostgresCreditRepository {
             }
         };
 
+        // The balance row is locked now, so the member's usage is stable
+        if let (Some(org_id), Some(cap)) = (tx.org_id, &limits.member_cap) {
+            let used =
+                member_spend_since(&mut db_tx, org_id, user_id, &currency, cap.since).await?;
+            cap.check(used, amount)?;
+        }
+        let budget_usage = consume_budget_charges(&mut db_tx, &limits.budgets, amount).await?;
+
+        draw_grants(&mut db_tx, &mut tx, amount, new_balance + amount).await?;
+
         // Insert transaction record
-        sqlx::query(
-            r#"
-            INSERT INTO credit_transactions (id, user_id, amount, currency, tx_type,
-                deposit_session_id, privacy_note_id, idempotency_key, reference_type,
-                reference_id, hold_id, metadata, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
-            "#,
-        )
-        .bind(tx.id)
-        .bind(tx.user_id)
-        .bind(tx.amount)
-        .bind(&tx.currency)
-        .bind(tx.tx_type.as_str())
-        .bind(tx.deposit_session_id)
-        .bind(tx.privacy_note_id)
-        .bind(&tx.idempotency_key)
-        .bind(&tx.reference_type)
-        .bind(tx.reference_id)
-        .bind(tx.hold_id)
-        .bind(&tx.metadata)
-        .bind(tx.created_at)
-        .execute(&mut *db_tx)
-        .await
-        .map_err(|e| AppError::Internal(e.into()))?;
+        insert_credit_transaction(&mut db_tx, &tx).await?;
 
         db_tx
             .commit()
             .await
             .map_err(|e| AppError::Internal(e.into()))?;
 
-        Ok(new_balance)
+        Ok((new_balance, budget_usage))
     }
 
     async fn get_transactions(
@@ -348,9 +701,9 @@ impl CreditRepository for PostgresCreditRepository {
         let mut sql = String::from(
             r#"SELECT id, user_id, amount, currency, tx_type, deposit_session_id,
                privacy_note_id, idempotency_key, reference_type, reference_id,
-               hold_id, metadata, created_at
+               hold_id, metadata, created_at, owner_type, org_id, grant_amount
                FROM credit_transactions
-               WHERE user_id = $1"#,
+               WHERE user_id = $1 AND org_id IS NULL"#,
         );
 
         let mut param_idx = 2;
@@ -392,7 +745,9 @@ impl CreditRepository for PostgresCreditRepository {
         tx_type: Option<&str>,
     ) -> Result<u64, AppError> {
         // Build query dynamically based on filters
-        let mut sql = String::from("SELECT COUNT(*) FROM credit_transactions WHERE user_id = $1");
+        let mut sql = String::from(
+            "SELECT COUNT(*) FROM credit_transactions WHERE user_id = $1 AND org_id IS NULL",
+        );
 
         let mut param_idx = 2;
         if currency.is_some() {
@@ -427,11 +782,11 @@ impl CreditRepository for PostgresCreditRepository {
                 currency,
                 COALESCE(SUM(CASE WHEN tx_type = 'deposit' THEN amount ELSE 0 END)::BIGINT, 0),
                 COALESCE(SUM(CASE WHEN tx_type = 'spend' THEN ABS(amount) ELSE 0 END)::BIGINT, 0),
-                COALESCE(SUM(CASE WHEN tx_type = 'adjustment' AND amount > 0 THEN amount ELSE 0 END)::BIGINT, 0),
-                COALESCE(SUM(CASE WHEN tx_type = 'adjustment' AND amount < 0 THEN ABS(amount) ELSE 0 END)::BIGINT, 0),
+                COALESCE(SUM(CASE WHEN tx_type IN ('adjustment', 'grant', 'expiration') AND amount > 0 THEN amount ELSE 0 END)::BIGINT, 0),
+                COALESCE(SUM(CASE WHEN tx_type IN ('adjustment', 'grant', 'expiration') AND amount < 0 THEN ABS(amount) ELSE 0 END)::BIGINT, 0),
                 COUNT(*) FILTER (WHERE tx_type = 'deposit'),
                 COUNT(*) FILTER (WHERE tx_type = 'spend'),
-                COUNT(*) FILTER (WHERE tx_type = 'adjustment')
+                COUNT(*) FILTER (WHERE tx_type IN ('adjustment', 'grant', 'expiration'))
             FROM credit_transactions
             GROUP BY currency
             "#,
@@ -444,7 +799,8 @@ impl CreditRepository for PostgresCreditRepository {
         let sol_row = tx_rows.iter().find(|r| r.0 == "SOL").unwrap_or(&default_tx);
         let usd_row = tx_rows.iter().find(|r| r.0 == "USD").unwrap_or(&default_tx);
 
-        // Query balance stats (separate table)
+        // Query balance stats (separate tables); organization balances count
+        // towards outstanding but not towards users
         let balance_row: (i64, i64, i64, i64) = sqlx::query_as(
             r#"
             SELECT
@@ -452,7 +808,11 @@ impl CreditRepository for PostgresCreditRepository {
                 COALESCE(SUM(balance)::BIGINT, 0) as total_outstanding,
                 COALESCE(SUM(CASE WHEN currency = 'SOL' THEN balance ELSE 0 END)::BIGINT, 0) as sol_outstanding,
                 COALESCE(SUM(CASE WHEN currency = 'USD' THEN balance ELSE 0 END)::BIGINT, 0) as usd_outstanding
-            FROM credit_balances WHERE balance > 0
+            FROM (
+                SELECT user_id, balance, currency FROM credit_balances WHERE balance > 0
+                UNION ALL
+                SELECT NULL, balance, currency FROM org_credit_balances WHERE balance > 0
+            ) b
             "#,
         )
         .fetch_one(&self.pool)
@@ -512,7 +872,7 @@ impl CreditRepository for PostgresCreditRepository {
                 COUNT(*) FILTER (WHERE tx_type = 'deposit') as deposit_count,
                 COUNT(*) FILTER (WHERE tx_type = 'spend') as spend_count
             FROM credit_transactions
-            WHERE user_id = $1 AND currency = $2
+            WHERE user_id = $1 AND currency = $2 AND org_id IS NULL
             "#,
         )
         .bind(user_id)
@@ -556,7 +916,7 @@ impl CreditRepository for PostgresCreditRepository {
             r#"
             SELECT id, user_id, amount, currency, tx_type, deposit_session_id,
                    privacy_note_id, idempotency_key, reference_type, reference_id,
-                   hold_id, metadata, created_at
+                   hold_id, metadata, created_at, owner_type, org_id, grant_amount
             FROM credit_transactions
             WHERE id = $1
             "#,
@@ -578,7 +938,7 @@ impl CreditRepository for PostgresCreditRepository {
             r#"
             SELECT id, user_id, amount, currency, tx_type, deposit_session_id,
                    privacy_note_id, idempotency_key, reference_type, reference_id,
-                   hold_id, metadata, created_at
+                   hold_id, metadata, created_at, owner_type, org_id, grant_amount
             FROM credit_transactions
             WHERE user_id = $1 AND idempotency_key = $2
             "#,
@@ -605,6 +965,7 @@ impl CreditRepository for PostgresCreditRepository {
             SELECT COALESCE(SUM(amount)::BIGINT, 0)
             FROM credit_transactions
             WHERE user_id = $1
+              AND org_id IS NULL
               AND currency = $2
               AND tx_type = 'adjustment'
               AND amount > 0
@@ -622,4 +983,578 @@ impl CreditRepository for PostgresCreditRepository {
 
         Ok(sum)
     }
+
+    async fn get_or_create_org_balance(
+        &self,
+        org_id: Uuid,
+        currency: &str,
+    ) -> Result<OrgCreditBalanceEntity, AppError> {
+        let currency = currency.to_uppercase();
+        let row: OrgCreditBalanceRow = sqlx::query_as(
+            r#"
+            INSERT INTO org_credit_balances (org_id, balance, held_balance, currency, updated_at)
+            VALUES ($1, 0, 0, $2, NOW())
+            ON CONFLICT (org_id, currency) DO UPDATE SET updated_at = org_credit_balances.updated_at
+            RETURNING id, org_id, balance, held_balance, currency, updated_at
+            "#,
+        )
+        .bind(org_id)
+        .bind(currency)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(row.into())
+    }
+
+    async fn get_org_transactions(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+        tx_type: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<CreditTransactionEntity>, AppError> {
+        let limit = cap_limit(limit);
+        let offset = cap_offset(offset);
+
+        let mut sql = String::from(
+            r#"SELECT id, user_id, amount, currency, tx_type, deposit_session_id,
+               privacy_note_id, idempotency_key, reference_type, reference_id,
+               hold_id, metadata, created_at, owner_type, org_id, grant_amount
+               FROM credit_transactions
+               WHERE org_id = $1"#,
+        );
+
+        let mut param_idx = 2;
+        if currency.is_some() {
+            sql.push_str(&format!(" AND currency = ${}", param_idx));
+            param_idx += 1;
+        }
+        if tx_type.is_some() {
+            sql.push_str(&format!(" AND tx_type = ${}", param_idx));
+            param_idx += 1;
+        }
+        sql.push_str(&format!(
+            " ORDER BY created_at DESC LIMIT ${} OFFSET ${}",
+            param_idx,
+            param_idx + 1
+        ));
+
+        let mut query = sqlx::query_as::<_, CreditTransactionRow>(&sql).bind(org_id);
+        if let Some(c) = currency {
+            query = query.bind(c);
+        }
+        if let Some(t) = tx_type {
+            query = query.bind(t);
+        }
+        query = query.bind(limit as i64).bind(offset as i64);
+
+        let rows: Vec<CreditTransactionRow> = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn count_org_transactions(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+        tx_type: Option<&str>,
+    ) -> Result<u64, AppError> {
+        let mut sql = String::from("SELECT COUNT(*) FROM credit_transactions WHERE org_id = $1");
+
+        let mut param_idx = 2;
+        if currency.is_some() {
+            sql.push_str(&format!(" AND currency = ${}", param_idx));
+            param_idx += 1;
+        }
+        if tx_type.is_some() {
+            sql.push_str(&format!(" AND tx_type = ${}", param_idx));
+        }
+
+        let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(org_id);
+        if let Some(c) = currency {
+            query = query.bind(c);
+        }
+        if let Some(t) = tx_type {
+            query = query.bind(t);
+        }
+
+        let count: i64 = query
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(count as u64)
+    }
+
+    async fn sum_org_member_spend(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        currency: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let sum: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(ABS(amount))::BIGINT, 0)
+            FROM credit_transactions
+            WHERE org_id = $1
+              AND user_id = $2
+              AND currency = $3
+              AND tx_type = 'spend'
+              AND created_at >= $4
+            "#,
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .bind(currency.to_uppercase())
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(sum)
+    }
+
+    async fn sum_transfers_out(
+        &self,
+        user_id: Uuid,
+        currency: &str,
+        since: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let sum: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(ABS(amount))::BIGINT, 0)
+            FROM credit_transactions
+            WHERE user_id = $1
+              AND currency = $2
+              AND tx_type = 'transfer'
+              AND amount < 0
+              AND created_at >= $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(currency.to_uppercase())
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(sum)
+    }
+
+    async fn sum_transactions_before(
+        &self,
+        owner_type: CreditOwnerType,
+        owner_id: Uuid,
+        currency: &str,
+        before: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let sql = format!(
+            r#"
+            SELECT COALESCE(SUM(amount)::BIGINT, 0)
+            FROM credit_transactions
+            WHERE {}
+              AND currency = $2
+              AND created_at < $3
+            "#,
+            owner_filter(owner_type)
+        );
+        let sum: i64 = sqlx::query_scalar(&sql)
+            .bind(owner_id)
+            .bind(currency.to_uppercase())
+            .bind(before)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(sum)
+    }
+
+    async fn get_transactions_between(
+        &self,
+        owner_type: CreditOwnerType,
+        owner_id: Uuid,
+        currency: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<CreditTransactionEntity>, AppError> {
+        let sql = format!(
+            r#"
+            SELECT id, user_id, amount, currency, tx_type, deposit_session_id,
+                   privacy_note_id, idempotency_key, reference_type, reference_id,
+                   hold_id, metadata, created_at, owner_type, org_id, grant_amount
+            FROM credit_transactions
+            WHERE {}
+              AND currency = $2
+              AND created_at >= $3
+              AND created_at < $4
+            ORDER BY created_at ASC, id ASC
+            LIMIT $5
+            "#,
+            owner_filter(owner_type)
+        );
+        let rows: Vec<CreditTransactionRow> = sqlx::query_as(&sql)
+            .bind(owner_id)
+            .bind(currency.to_uppercase())
+            .bind(from)
+            .bind(to)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn get_org_member_settings(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<OrgCreditMemberSettings>, AppError> {
+        let row: Option<OrgCreditMemberSettingsRow> = sqlx::query_as(
+            r#"
+            SELECT org_id, user_id, spend_allowed, monthly_cap, updated_by, updated_at
+            FROM org_credit_member_settings
+            WHERE org_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_org_member_settings(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Vec<OrgCreditMemberSettings>, AppError> {
+        let rows: Vec<OrgCreditMemberSettingsRow> = sqlx::query_as(
+            r#"
+            SELECT org_id, user_id, spend_allowed, monthly_cap, updated_by, updated_at
+            FROM org_credit_member_settings
+            WHERE org_id = $1
+            ORDER BY user_id
+            "#,
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn upsert_org_member_settings(
+        &self,
+        settings: OrgCreditMemberSettings,
+    ) -> Result<OrgCreditMemberSettings, AppError> {
+        let row: OrgCreditMemberSettingsRow = sqlx::query_as(
+            r#"
+            INSERT INTO org_credit_member_settings
+                (org_id, user_id, spend_allowed, monthly_cap, updated_by, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (org_id, user_id) DO UPDATE
+            SET spend_allowed = EXCLUDED.spend_allowed,
+                monthly_cap = EXCLUDED.monthly_cap,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = EXCLUDED.updated_at
+            RETURNING org_id, user_id, spend_allowed, monthly_cap, updated_by, updated_at
+            "#,
+        )
+        .bind(settings.org_id)
+        .bind(settings.user_id)
+        .bind(settings.spend_allowed)
+        .bind(settings.monthly_cap)
+        .bind(settings.updated_by)
+        .bind(settings.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(row.into())
+    }
+
+    async fn delete_org_member_settings(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "DELETE FROM org_credit_member_settings WHERE org_id = $1 AND user_id = $2",
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_org_credit_data(&self, _org_id: Uuid) -> Result<(), AppError> {
+        // org_credit_balances and org_credit_member_settings cascade with the
+        // organizations row
+        Ok(())
+    }
+
+    async fn create_grant(&self, grant: CreditGrantEntity) -> Result<i64, AppError> {
+        let mut db_tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        let new_balance: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO credit_balances (user_id, balance, held_balance, currency, updated_at)
+            VALUES ($1, $2, 0, $3, NOW())
+            ON CONFLICT (user_id, currency) DO UPDATE
+            SET balance = credit_balances.balance + $2,
+                updated_at = NOW()
+            RETURNING balance
+            "#,
+        )
+        .bind(grant.user_id)
+        .bind(grant.amount)
+        .bind(&grant.currency)
+        .fetch_one(&mut *db_tx)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO credit_grants ({GRANT_COLUMNS})
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            "#
+        ))
+        .bind(grant.id)
+        .bind(grant.user_id)
+        .bind(&grant.currency)
+        .bind(grant.source.as_str())
+        .bind(grant.amount)
+        .bind(grant.remaining)
+        .bind(grant.priority)
+        .bind(grant.expires_at)
+        .bind(grant.status.as_str())
+        .bind(grant.transaction_id)
+        .bind(&grant.reason)
+        .bind(grant.created_by)
+        .bind(grant.created_at)
+        .bind(grant.updated_at)
+        .execute(&mut *db_tx)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        insert_credit_transaction(&mut db_tx, &CreditTransactionEntity::new_grant(&grant)).await?;
+
+        db_tx
+            .commit()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(new_balance)
+    }
+
+    async fn get_grant(&self, grant_id: Uuid) -> Result<Option<CreditGrantEntity>, AppError> {
+        let row: Option<CreditGrantRow> = sqlx::query_as(&format!(
+            "SELECT {GRANT_COLUMNS} FROM credit_grants WHERE id = $1"
+        ))
+        .bind(grant_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn get_grants(
+        &self,
+        user_id: Uuid,
+        currency: Option<&str>,
+        active_only: bool,
+    ) -> Result<Vec<CreditGrantEntity>, AppError> {
+        let rows: Vec<CreditGrantRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {GRANT_COLUMNS} FROM credit_grants
+            WHERE user_id = $1
+              AND ($2::TEXT IS NULL OR currency = $2)
+              AND (NOT $3 OR (status = 'active' AND remaining > 0))
+            ORDER BY priority DESC, expires_at ASC NULLS LAST, created_at ASC
+            "#
+        ))
+        .bind(user_id)
+        .bind(currency.map(|c| c.to_uppercase()))
+        .bind(active_only)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn expire_grant(&self, grant_id: Uuid) -> Result<CreditGrantEntity, AppError> {
+        self.forfeit_grant(grant_id, Utc::now()).await
+    }
+
+    async fn expire_grants(&self, now: DateTime<Utc>, limit: u32) -> Result<u64, AppError> {
+        let due: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM credit_grants
+            WHERE status = 'active' AND remaining > 0 AND expires_at <= $1
+            ORDER BY expires_at
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(i64::from(limit))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        let mut expired = 0;
+        for grant_id in due {
+            if self.forfeit_grant(grant_id, now).await?.status == CreditGrantStatus::Expired {
+                expired += 1;
+            }
+        }
+        Ok(expired)
+    }
+
+    async fn get_ledger_totals(
+        &self,
+        currency: Option<&str>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LedgerAccountTotals>, AppError> {
+        let rows: Vec<(String, String, i64, i64)> = sqlx::query_as(
+            r#"
+            WITH entries AS (
+                SELECT * FROM credit_journal_entries
+                WHERE ($1::TEXT IS NULL OR currency = $1)
+                  AND ($2::TIMESTAMPTZ IS NULL OR created_at >= $2)
+                  AND ($3::TIMESTAMPTZ IS NULL OR created_at < $3)
+            )
+            SELECT account, currency,
+                   COALESCE(SUM(debits), 0)::BIGINT,
+                   COALESCE(SUM(credits), 0)::BIGINT
+            FROM (
+                SELECT debit_account AS account, currency, amount AS debits, 0::BIGINT AS credits
+                FROM entries
+                UNION ALL
+                SELECT credit_account, currency, 0::BIGINT, amount
+                FROM entries
+            ) postings
+            GROUP BY account, currency
+            "#,
+        )
+        .bind(currency.map(|c| c.to_uppercase()))
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        let mut totals: Vec<LedgerAccountTotals> = rows
+            .into_iter()
+            .filter_map(|(account, currency, debits, credits)| {
+                Some(LedgerAccountTotals {
+                    account: LedgerAccount::from_str(&account)?,
+                    currency,
+                    debits,
+                    credits,
+                })
+            })
+            .collect();
+        totals.sort_by_key(|t| (t.currency.clone(), t.account));
+        Ok(totals)
+    }
+
+    async fn reconcile_balances(&self, limit: u32) -> Result<BalanceReconciliation, AppError> {
+        let rows: Vec<(String, Uuid, String, i64, i64)> = sqlx::query_as(
+            r#"
+            WITH ledger AS (
+                -- Promotional credit is part of the user's stored balance
+                SELECT account, owner_id, currency, SUM(delta)::BIGINT AS balance
+                FROM (
+                    SELECT REPLACE(credit_account, 'promotional_liability', 'user_balance')
+                               AS account,
+                           credit_owner_id AS owner_id, currency, amount AS delta
+                    FROM credit_journal_entries WHERE credit_owner_id IS NOT NULL
+                    UNION ALL
+                    SELECT REPLACE(debit_account, 'promotional_liability', 'user_balance'),
+                           debit_owner_id, currency, -amount
+                    FROM credit_journal_entries WHERE debit_owner_id IS NOT NULL
+                ) postings
+                GROUP BY account, owner_id, currency
+            ),
+            stored AS (
+                SELECT 'user_balance' AS account, user_id AS owner_id, currency, balance
+                FROM credit_balances
+                UNION ALL
+                SELECT 'org_balance', org_id, currency, balance
+                FROM org_credit_balances
+            )
+            SELECT COALESCE(s.account, l.account),
+                   COALESCE(s.owner_id, l.owner_id),
+                   COALESCE(s.currency, l.currency),
+                   COALESCE(s.balance, 0),
+                   COALESCE(l.balance, 0)
+            FROM stored s
+            FULL OUTER JOIN ledger l
+              ON l.account = s.account AND l.owner_id = s.owner_id AND l.currency = s.currency
+            WHERE COALESCE(s.balance, 0) <> COALESCE(l.balance, 0)
+            LIMIT $1
+            "#,
+        )
+        .bind(i64::from(limit))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        let (balances_checked, unposted_transactions): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT (SELECT COUNT(*) FROM credit_balances)
+                     + (SELECT COUNT(*) FROM org_credit_balances),
+                   (SELECT COUNT(*) FROM credit_transactions t
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM credit_journal_entries j WHERE j.transaction_id = t.id
+                    ))
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(BalanceReconciliation {
+            balances_checked: balances_checked as u64,
+            drift: rows
+                .into_iter()
+                .map(
+                    |(account, owner_id, currency, stored_balance, ledger_balance)| {
+                        CreditBalanceDrift {
+                            owner_type: if account == LedgerAccount::OrgBalance.as_str() {
+                                CreditOwnerType::Org
+                            } else {
+                                CreditOwnerType::User
+                            },
+                            owner_id,
+                            currency,
+                            stored_balance,
+                            ledger_balance,
+                        }
+                    },
+                )
+                .collect(),
+            unposted_transactions: unposted_transactions as u64,
+        })
+    }
 }
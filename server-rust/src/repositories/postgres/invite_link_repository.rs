@@ -0,0 +1,224 @@
+//! PostgreSQL invite link repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::pagination::{cap_limit, cap_offset};
+use crate::repositories::{InviteLinkEntity, InviteLinkRepository, OrgRole};
+
+const LINK_COLUMNS: &str = "id, org_id, role, token_hash, max_uses, use_count, allowed_domains, \
+     created_by, created_at, expires_at, revoked_at";
+
+/// PostgreSQL invite link repository
+pub struct PostgresInviteLinkRepository {
+    pool: PgPool,
+}
+
+impl PostgresInviteLinkRepository {
+    /// Create a new Postgres invite link repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct InviteLinkRow {
+    id: Uuid,
+    org_id: Uuid,
+    role: String,
+    token_hash: String,
+    max_uses: Option<i32>,
+    use_count: i32,
+    allowed_domains: Vec<String>,
+    created_by: Uuid,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<InviteLinkRow> for InviteLinkEntity {
+    type Error = AppError;
+
+    fn try_from(row: InviteLinkRow) -> Result<Self, Self::Error> {
+        let role = OrgRole::from_str(&row.role)
+            .ok_or_else(|| AppError::Database(format!("Invalid role: {}", row.role)))?;
+
+        Ok(Self {
+            id: row.id,
+            org_id: row.org_id,
+            role,
+            token_hash: row.token_hash,
+            max_uses: row.max_uses.map(|m| m.max(0) as u32),
+            use_count: row.use_count.max(0) as u32,
+            allowed_domains: row.allowed_domains,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+            revoked_at: row.revoked_at,
+        })
+    }
+}
+
+fn map_link_error(e: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e {
+        if db_err.is_unique_violation() {
+            return AppError::Validation("Invite token collision - please retry".into());
+        }
+    }
+    AppError::Database(e.to_string())
+}
+
+#[async_trait]
+impl InviteLinkRepository for PostgresInviteLinkRepository {
+    async fn create(&self, link: InviteLinkEntity) -> Result<InviteLinkEntity, AppError> {
+        let row: InviteLinkRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO invite_links (
+                id, org_id, role, token_hash, max_uses, use_count, allowed_domains,
+                created_by, created_at, expires_at, revoked_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING {}
+            "#,
+            LINK_COLUMNS
+        ))
+        .bind(link.id)
+        .bind(link.org_id)
+        .bind(link.role.as_str())
+        .bind(&link.token_hash)
+        .bind(link.max_uses.map(|m| m.min(i32::MAX as u32) as i32))
+        .bind(link.use_count as i32)
+        .bind(&link.allowed_domains)
+        .bind(link.created_by)
+        .bind(link.created_at)
+        .bind(link.expires_at)
+        .bind(link.revoked_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_link_error)?;
+
+        row.try_into()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<InviteLinkEntity>, AppError> {
+        let row: Option<InviteLinkRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM invite_links WHERE id = $1",
+            LINK_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<InviteLinkEntity>, AppError> {
+        let row: Option<InviteLinkRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM invite_links WHERE token_hash = $1",
+            LINK_COLUMNS
+        ))
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_by_org_paged(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<InviteLinkEntity>, AppError> {
+        let rows: Vec<InviteLinkRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM invite_links
+            WHERE org_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            LINK_COLUMNS
+        ))
+        .bind(org_id)
+        .bind(cap_limit(limit) as i64)
+        .bind(cap_offset(offset) as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM invite_links WHERE org_id = $1")
+            .bind(org_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(count.0.max(0) as u64)
+    }
+
+    async fn consume_use(&self, id: Uuid) -> Result<Option<InviteLinkEntity>, AppError> {
+        // Single conditional UPDATE so concurrent redemptions cannot exceed max_uses
+        let row: Option<InviteLinkRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE invite_links
+            SET use_count = use_count + 1
+            WHERE id = $1
+              AND revoked_at IS NULL
+              AND expires_at > NOW()
+              AND (max_uses IS NULL OR use_count < max_uses)
+            RETURNING {}
+            "#,
+            LINK_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn release_use(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query("UPDATE invite_links SET use_count = GREATEST(use_count - 1, 0) WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE invite_links SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM invite_links WHERE org_id = $1")
+            .bind(org_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
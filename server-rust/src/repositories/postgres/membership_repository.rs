@@ -150,6 +150,14 @@ impl MembershipRepository for PostgresMembershipRepository {
         }
     }
 
+    async fn find_by_user_and_org_including_pending(
+        &self,
+        user_id: Uuid,
+        org_id: Uuid,
+    ) -> Result<Option<MembershipEntity>, AppError> {
+        self.find_by_user_and_org(user_id, org_id).await
+    }
+
     async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<MembershipEntity>, AppError> {
         // PERF-003: Cap at 100 to match paged method's MAX_PAGE_SIZE
         let rows: Vec<MembershipRow> = sqlx::query_as(
@@ -168,6 +176,13 @@ impl MembershipRepository for PostgresMembershipRepository {
         rows.into_iter().map(TryInto::try_into).collect()
     }
 
+    async fn find_by_user_including_pending(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<MembershipEntity>, AppError> {
+        self.find_by_user(user_id).await
+    }
+
     async fn find_by_user_paged(
         &self,
         user_id: Uuid,
@@ -342,6 +357,16 @@ impl MembershipRepository for PostgresMembershipRepository {
         Ok(result.rows_affected())
     }
 
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM memberships WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
     async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
         let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM memberships WHERE org_id = $1")
             .bind(org_id)
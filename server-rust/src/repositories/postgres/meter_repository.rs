@@ -0,0 +1,529 @@
+//! PostgreSQL meter catalog and usage event repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{
+    MeterEntity, MeterPriceOverrideEntity, MeterRepository, MeterUsageTotals, PriceSchedule,
+    UsageEventEntity,
+};
+
+const METER_COLUMNS: &str =
+    "id, name, display_name, unit, currency, pricing, active, created_at, updated_at";
+
+const OVERRIDE_COLUMNS: &str = "meter_id, org_id, pricing, created_by, created_at, updated_at";
+
+const EVENT_COLUMNS: &str = "id, meter_id, user_id, org_id, quantity, idempotency_key, \
+     occurred_at, status, batch_id, cost, transaction_id, error, created_at, billed_at";
+
+/// PostgreSQL meter repository
+pub struct PostgresMeterRepository {
+    pool: PgPool,
+}
+
+impl PostgresMeterRepository {
+    /// Create a new Postgres meter repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn parse_pricing(value: Value) -> Result<PriceSchedule, AppError> {
+    serde_json::from_value(value)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to parse meter pricing: {}", e)))
+}
+
+fn pricing_value(pricing: &PriceSchedule) -> Result<Value, AppError> {
+    serde_json::to_value(pricing).map_err(|e| AppError::Internal(e.into()))
+}
+
+fn map_meter_error(e: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e {
+        if db_err.is_unique_violation() {
+            return AppError::Validation("A meter with this name already exists".into());
+        }
+    }
+    AppError::Database(e.to_string())
+}
+
+#[derive(sqlx::FromRow)]
+struct MeterRow {
+    id: Uuid,
+    name: String,
+    display_name: String,
+    unit: String,
+    currency: String,
+    pricing: Value,
+    active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<MeterRow> for MeterEntity {
+    type Error = AppError;
+
+    fn try_from(row: MeterRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            name: row.name,
+            display_name: row.display_name,
+            unit: row.unit,
+            currency: row.currency,
+            pricing: parse_pricing(row.pricing)?,
+            active: row.active,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct OverrideRow {
+    meter_id: Uuid,
+    org_id: Uuid,
+    pricing: Value,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<OverrideRow> for MeterPriceOverrideEntity {
+    type Error = AppError;
+
+    fn try_from(row: OverrideRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            meter_id: row.meter_id,
+            org_id: row.org_id,
+            pricing: parse_pricing(row.pricing)?,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EventRow {
+    id: Uuid,
+    meter_id: Uuid,
+    user_id: Uuid,
+    org_id: Option<Uuid>,
+    quantity: i64,
+    idempotency_key: String,
+    occurred_at: DateTime<Utc>,
+    status: String,
+    batch_id: Option<Uuid>,
+    cost: Option<i64>,
+    transaction_id: Option<Uuid>,
+    error: Option<String>,
+    created_at: DateTime<Utc>,
+    billed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<EventRow> for UsageEventEntity {
+    type Error = AppError;
+
+    fn try_from(row: EventRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            meter_id: row.meter_id,
+            user_id: row.user_id,
+            org_id: row.org_id,
+            quantity: row.quantity,
+            idempotency_key: row.idempotency_key,
+            occurred_at: row.occurred_at,
+            status: row.status.parse()?,
+            batch_id: row.batch_id,
+            cost: row.cost,
+            transaction_id: row.transaction_id,
+            error: row.error,
+            created_at: row.created_at,
+            billed_at: row.billed_at,
+        })
+    }
+}
+
+#[async_trait]
+impl MeterRepository for PostgresMeterRepository {
+    async fn create_meter(&self, meter: MeterEntity) -> Result<MeterEntity, AppError> {
+        let row: MeterRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO meters (
+                id, name, display_name, unit, currency, pricing, active, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING {}
+            "#,
+            METER_COLUMNS
+        ))
+        .bind(meter.id)
+        .bind(&meter.name)
+        .bind(&meter.display_name)
+        .bind(&meter.unit)
+        .bind(&meter.currency)
+        .bind(pricing_value(&meter.pricing)?)
+        .bind(meter.active)
+        .bind(meter.created_at)
+        .bind(meter.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_meter_error)?;
+
+        row.try_into()
+    }
+
+    async fn find_meter(&self, id: Uuid) -> Result<Option<MeterEntity>, AppError> {
+        let row: Option<MeterRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM meters WHERE id = $1",
+            METER_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_meter_by_name(&self, name: &str) -> Result<Option<MeterEntity>, AppError> {
+        let row: Option<MeterRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM meters WHERE name = $1",
+            METER_COLUMNS
+        ))
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn list_meters(&self) -> Result<Vec<MeterEntity>, AppError> {
+        let rows: Vec<MeterRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM meters ORDER BY name ASC",
+            METER_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn update_meter(&self, meter: MeterEntity) -> Result<MeterEntity, AppError> {
+        let row: Option<MeterRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE meters
+            SET display_name = $2, unit = $3, pricing = $4, active = $5, updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            METER_COLUMNS
+        ))
+        .bind(meter.id)
+        .bind(&meter.display_name)
+        .bind(&meter.unit)
+        .bind(pricing_value(&meter.pricing)?)
+        .bind(meter.active)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.ok_or(AppError::NotFound("Meter not found".into()))?
+            .try_into()
+    }
+
+    async fn upsert_price_override(
+        &self,
+        price_override: MeterPriceOverrideEntity,
+    ) -> Result<MeterPriceOverrideEntity, AppError> {
+        let row: OverrideRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO meter_price_overrides (
+                meter_id, org_id, pricing, created_by, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (meter_id, org_id)
+            DO UPDATE SET pricing = EXCLUDED.pricing, updated_at = NOW()
+            RETURNING {}
+            "#,
+            OVERRIDE_COLUMNS
+        ))
+        .bind(price_override.meter_id)
+        .bind(price_override.org_id)
+        .bind(pricing_value(&price_override.pricing)?)
+        .bind(price_override.created_by)
+        .bind(price_override.created_at)
+        .bind(price_override.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.try_into()
+    }
+
+    async fn find_price_override(
+        &self,
+        meter_id: Uuid,
+        org_id: Uuid,
+    ) -> Result<Option<MeterPriceOverrideEntity>, AppError> {
+        let row: Option<OverrideRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM meter_price_overrides WHERE meter_id = $1 AND org_id = $2",
+            OVERRIDE_COLUMNS
+        ))
+        .bind(meter_id)
+        .bind(org_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn list_price_overrides(
+        &self,
+        meter_id: Uuid,
+    ) -> Result<Vec<MeterPriceOverrideEntity>, AppError> {
+        let rows: Vec<OverrideRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM meter_price_overrides
+            WHERE meter_id = $1
+            ORDER BY created_at ASC
+            "#,
+            OVERRIDE_COLUMNS
+        ))
+        .bind(meter_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn delete_price_override(&self, meter_id: Uuid, org_id: Uuid) -> Result<bool, AppError> {
+        let result =
+            sqlx::query("DELETE FROM meter_price_overrides WHERE meter_id = $1 AND org_id = $2")
+                .bind(meter_id)
+                .bind(org_id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_usage_events(
+        &self,
+        events: Vec<UsageEventEntity>,
+    ) -> Result<Vec<bool>, AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut inserted = Vec::with_capacity(events.len());
+        for event in &events {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO usage_events (
+                    id, meter_id, user_id, org_id, quantity, idempotency_key,
+                    occurred_at, status, created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT (user_id, idempotency_key) DO NOTHING
+                "#,
+            )
+            .bind(event.id)
+            .bind(event.meter_id)
+            .bind(event.user_id)
+            .bind(event.org_id)
+            .bind(event.quantity)
+            .bind(&event.idempotency_key)
+            .bind(event.occurred_at)
+            .bind(event.status.as_str())
+            .bind(event.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+            inserted.push(result.rows_affected() > 0);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(inserted)
+    }
+
+    async fn list_pending_events(&self, limit: u32) -> Result<Vec<UsageEventEntity>, AppError> {
+        let rows: Vec<EventRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM usage_events
+            WHERE status = 'pending'
+            ORDER BY occurred_at ASC, created_at ASC
+            LIMIT $1
+            "#,
+            EVENT_COLUMNS
+        ))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn list_batch_events(&self, batch_id: Uuid) -> Result<Vec<UsageEventEntity>, AppError> {
+        let rows: Vec<EventRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM usage_events
+            WHERE batch_id = $1 AND status = 'pending'
+            ORDER BY occurred_at ASC, created_at ASC
+            "#,
+            EVENT_COLUMNS
+        ))
+        .bind(batch_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn claim_events(
+        &self,
+        ids: &[Uuid],
+        batch_id: Uuid,
+    ) -> Result<Vec<UsageEventEntity>, AppError> {
+        let rows: Vec<EventRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE usage_events
+            SET batch_id = $1
+            WHERE id = ANY($2) AND batch_id IS NULL AND status = 'pending'
+            RETURNING {}
+            "#,
+            EVENT_COLUMNS
+        ))
+        .bind(batch_id)
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn billed_quantity(
+        &self,
+        meter_id: Uuid,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let total: (Option<i64>,) = sqlx::query_as(
+            r#"
+            SELECT SUM(quantity)::BIGINT
+            FROM usage_events
+            WHERE meter_id = $1
+              AND status = 'billed'
+              AND occurred_at >= $4 AND occurred_at < $5
+              AND CASE WHEN $3::UUID IS NULL
+                       THEN org_id IS NULL AND user_id = $2
+                       ELSE org_id = $3
+                  END
+            "#,
+        )
+        .bind(meter_id)
+        .bind(user_id)
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(total.0.unwrap_or(0))
+    }
+
+    async fn mark_billed(
+        &self,
+        batch_id: Uuid,
+        costs: &[(Uuid, i64)],
+        transaction_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        let ids: Vec<Uuid> = costs.iter().map(|(id, _)| *id).collect();
+        let amounts: Vec<i64> = costs.iter().map(|(_, cost)| *cost).collect();
+
+        sqlx::query(
+            r#"
+            UPDATE usage_events e
+            SET status = 'billed', cost = c.cost, transaction_id = $4, billed_at = NOW()
+            FROM UNNEST($2::UUID[], $3::BIGINT[]) AS c(id, cost)
+            WHERE e.id = c.id AND e.batch_id = $1 AND e.status = 'pending'
+            "#,
+        )
+        .bind(batch_id)
+        .bind(&ids)
+        .bind(&amounts)
+        .bind(transaction_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(&self, batch_id: Uuid, error: &str) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE usage_events
+            SET status = 'failed', error = $2
+            WHERE batch_id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(batch_id)
+        .bind(error)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn usage_by_meter(&self, user_id: Uuid) -> Result<Vec<MeterUsageTotals>, AppError> {
+        let rows: Vec<(Uuid, Option<i64>, Option<i64>, Option<i64>)> = sqlx::query_as(
+            r#"
+            SELECT meter_id,
+                   SUM(quantity) FILTER (WHERE status = 'billed')::BIGINT,
+                   SUM(cost) FILTER (WHERE status = 'billed')::BIGINT,
+                   SUM(quantity) FILTER (WHERE status = 'pending')::BIGINT
+            FROM usage_events
+            WHERE user_id = $1 AND org_id IS NULL
+            GROUP BY meter_id
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(meter_id, quantity, cost, pending)| MeterUsageTotals {
+                meter_id,
+                billed_quantity: quantity.unwrap_or(0),
+                billed_cost: cost.unwrap_or(0),
+                pending_quantity: pending.unwrap_or(0),
+            })
+            .collect())
+    }
+}
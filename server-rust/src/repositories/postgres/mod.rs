@@ -3,23 +3,31 @@
 mod api_key_repository;
 mod audit_repository;
 mod credential_repository;
+mod credit_budget_repository;
 mod credit_hold_repository;
+mod credit_invoice_repository;
 mod credit_refund_request_repository;
 mod credit_repository;
 mod custom_role_repository;
 mod deposit_repository;
 mod derived_wallet_repository;
+mod invite_link_repository;
 mod invite_repository;
 mod login_attempt_repository;
 mod membership_repository;
+mod meter_repository;
 mod nonce_repository;
 mod org_repository;
 mod outbox_repository;
 mod pending_wallet_recovery_repository;
 mod policy_repository;
 mod privacy_note_repository;
+mod relationship_repository;
+mod service_account_repository;
 mod session_repository;
 mod sso_repository;
+mod stripe_topup_repository;
+mod subscription_repository;
 mod system_settings_repository;
 mod totp_repository;
 mod treasury_config_repository;
@@ -29,28 +37,38 @@ mod verification_repository;
 mod wallet_material_repository;
 mod wallet_rotation_history_repository;
 mod webauthn_repository;
+mod webhook_repository;
 mod withdrawal_history_repository;
 
 pub use api_key_repository::PostgresApiKeyRepository;
 pub use audit_repository::PostgresAuditLogRepository;
 pub use credential_repository::PostgresCredentialRepository;
+pub use credit_budget_repository::PostgresCreditBudgetRepository;
 pub use credit_hold_repository::PostgresCreditHoldRepository;
+pub use credit_invoice_repository::PostgresCreditInvoiceRepository;
 pub use credit_refund_request_repository::PostgresCreditRefundRequestRepository;
 pub use credit_repository::PostgresCreditRepository;
+pub(crate) use credit_repository::{balance_target, draw_grants, insert_credit_transaction};
 pub use custom_role_repository::PostgresCustomRoleRepository;
 pub use deposit_repository::PostgresDepositRepository;
 pub use derived_wallet_repository::PostgresDerivedWalletRepository;
+pub use invite_link_repository::PostgresInviteLinkRepository;
 pub use invite_repository::PostgresInviteRepository;
 pub use login_attempt_repository::PostgresLoginAttemptRepository;
 pub use membership_repository::PostgresMembershipRepository;
+pub use meter_repository::PostgresMeterRepository;
 pub use nonce_repository::PostgresNonceRepository;
 pub use org_repository::PostgresOrgRepository;
 pub use outbox_repository::PostgresOutboxRepository;
 pub use pending_wallet_recovery_repository::PostgresPendingWalletRecoveryRepository;
 pub use policy_repository::PostgresPolicyRepository;
 pub use privacy_note_repository::PostgresPrivacyNoteRepository;
+pub use relationship_repository::PostgresRelationshipRepository;
+pub use service_account_repository::PostgresServiceAccountRepository;
 pub use session_repository::PostgresSessionRepository;
 pub use sso_repository::PostgresSsoRepository;
+pub use stripe_topup_repository::PostgresStripeTopupRepository;
+pub use subscription_repository::PostgresSubscriptionRepository;
 pub use system_settings_repository::PostgresSystemSettingsRepository;
 pub use totp_repository::PostgresTotpRepository;
 pub use treasury_config_repository::PostgresTreasuryConfigRepository;
@@ -60,4 +78,5 @@ pub use verification_repository::PostgresVerificationRepository;
 pub use wallet_material_repository::PostgresWalletMaterialRepository;
 pub use wallet_rotation_history_repository::PostgresWalletRotationHistoryRepository;
 pub use webauthn_repository::PostgresWebAuthnRepository;
+pub use webhook_repository::PostgresWebhookRepository;
 pub use withdrawal_history_repository::PostgresWithdrawalHistoryRepository;
@@ -47,6 +47,8 @@ struct OrgRow {
     owner_id: Uuid,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+    purge_after: Option<DateTime<Utc>>,
 }
 
 impl From<OrgRow> for OrgEntity {
@@ -60,6 +62,8 @@ impl From<OrgRow> for OrgEntity {
             owner_id: row.owner_id,
             created_at: row.created_at,
             updated_at: row.updated_at,
+            deleted_at: row.deleted_at,
+            purge_after: row.purge_after,
         }
     }
 }
@@ -69,7 +73,8 @@ impl OrgRepository for PostgresOrgRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<OrgEntity>, AppError> {
         let row: Option<OrgRow> = sqlx::query_as(
             r#"
-            SELECT id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at
+            SELECT id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at,
+                   deleted_at, purge_after
             FROM organizations WHERE id = $1
             "#,
         )
@@ -88,7 +93,8 @@ impl OrgRepository for PostgresOrgRepository {
 
         let rows: Vec<OrgRow> = sqlx::query_as(
             r#"
-            SELECT id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at
+            SELECT id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at,
+                   deleted_at, purge_after
             FROM organizations WHERE id = ANY($1)
             "#,
         )
@@ -103,7 +109,8 @@ impl OrgRepository for PostgresOrgRepository {
     async fn find_by_slug(&self, slug: &str) -> Result<Option<OrgEntity>, AppError> {
         let row: Option<OrgRow> = sqlx::query_as(
             r#"
-            SELECT id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at
+            SELECT id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at,
+                   deleted_at, purge_after
             FROM organizations WHERE slug = $1
             "#,
         )
@@ -131,7 +138,8 @@ impl OrgRepository for PostgresOrgRepository {
     async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<OrgEntity>, AppError> {
         let rows: Vec<OrgRow> = sqlx::query_as(
             r#"
-            SELECT o.id, o.name, o.slug, o.logo_url, o.is_personal, o.owner_id, o.created_at, o.updated_at
+            SELECT o.id, o.name, o.slug, o.logo_url, o.is_personal, o.owner_id, o.created_at, o.updated_at,
+                   o.deleted_at, o.purge_after
             FROM organizations o
             JOIN memberships m ON o.id = m.org_id
             WHERE m.user_id = $1
@@ -152,7 +160,8 @@ impl OrgRepository for PostgresOrgRepository {
             r#"
             INSERT INTO organizations (id, name, slug, logo_url, is_personal, owner_id)
             VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at
+            RETURNING id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at,
+                      deleted_at, purge_after
             "#,
         )
         .bind(org.id)
@@ -174,7 +183,8 @@ impl OrgRepository for PostgresOrgRepository {
             UPDATE organizations
             SET name = $2, slug = $3, logo_url = $4, updated_at = NOW()
             WHERE id = $1
-            RETURNING id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at
+            RETURNING id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at,
+                      deleted_at, purge_after
             "#,
         )
         .bind(org.id)
@@ -220,7 +230,8 @@ impl OrgRepository for PostgresOrgRepository {
 
         let rows: Vec<OrgRow> = sqlx::query_as(
             r#"
-            SELECT id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at
+            SELECT id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at,
+                   deleted_at, purge_after
             FROM organizations
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
@@ -243,4 +254,69 @@ impl OrgRepository for PostgresOrgRepository {
 
         Ok(count.max(0) as u64)
     }
+
+    async fn schedule_deletion(
+        &self,
+        id: Uuid,
+        purge_after: DateTime<Utc>,
+    ) -> Result<Option<OrgEntity>, AppError> {
+        let row: Option<OrgRow> = sqlx::query_as(
+            r#"
+            UPDATE organizations
+            SET deleted_at = NOW(), purge_after = $2, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at,
+                      deleted_at, purge_after
+            "#,
+        )
+        .bind(id)
+        .bind(purge_after)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<Option<OrgEntity>, AppError> {
+        let row: Option<OrgRow> = sqlx::query_as(
+            r#"
+            UPDATE organizations
+            SET deleted_at = NULL, purge_after = NULL, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NOT NULL
+            RETURNING id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at,
+                      deleted_at, purge_after
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_due_for_purge(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<OrgEntity>, AppError> {
+        let rows: Vec<OrgRow> = sqlx::query_as(
+            r#"
+            SELECT id, name, slug, logo_url, is_personal, owner_id, created_at, updated_at,
+                   deleted_at, purge_after
+            FROM organizations
+            WHERE purge_after IS NOT NULL AND purge_after <= $1
+            ORDER BY purge_after ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(cap_limit(limit) as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
 }
@@ -76,11 +76,24 @@ fn parse_event_type(s: &str) -> Result<OutboxEventType, AppError> {
         "email.invite" => Ok(OutboxEventType::EmailInvite),
         "email.instant_link" => Ok(OutboxEventType::EmailInstantLink),
         "email.security_alert" => Ok(OutboxEventType::EmailSecurityAlert),
+        "email.email_change" => Ok(OutboxEventType::EmailChange),
+        "email.email_change_notice" => Ok(OutboxEventType::EmailChangeNotice),
+        "email.credit_statement" => Ok(OutboxEventType::EmailCreditStatement),
         "notify.login_threshold" => Ok(OutboxEventType::NotifyLoginThreshold),
         "notify.token_reuse" => Ok(OutboxEventType::NotifyTokenReuse),
         "notify.role_change" => Ok(OutboxEventType::NotifyRoleChange),
         "notify.owner_transfer" => Ok(OutboxEventType::NotifyOwnerTransfer),
         "notify.admin_action" => Ok(OutboxEventType::NotifyAdminAction),
+        "credit.low_balance" => Ok(OutboxEventType::CreditLowBalance),
+        "credit.spend" => Ok(OutboxEventType::CreditSpend),
+        "credit.hold_expired" => Ok(OutboxEventType::CreditHoldExpired),
+        "credit.refund_requested" => Ok(OutboxEventType::CreditRefundRequested),
+        "credit.subscription_past_due" => Ok(OutboxEventType::CreditSubscriptionPastDue),
+        "credit.subscription_canceled" => Ok(OutboxEventType::CreditSubscriptionCanceled),
+        "credit.transfer_sent" => Ok(OutboxEventType::CreditTransferSent),
+        "credit.transfer_received" => Ok(OutboxEventType::CreditTransferReceived),
+        "credit.budget_threshold" => Ok(OutboxEventType::CreditBudgetThreshold),
+        "webhook.delivery" => Ok(OutboxEventType::WebhookDelivery),
         _ => Err(AppError::Internal(anyhow::anyhow!(
             "Unknown event type: {}",
             s
@@ -0,0 +1,304 @@
+//! PostgreSQL relationship tuple repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::pagination::{cap_limit, cap_offset};
+use crate::repositories::{
+    NamespaceDefinition, ObjectRef, RelationTuple, RelationshipRepository, RelationshipSchema,
+    SubjectRef, TupleFilter, TupleKey,
+};
+
+const TUPLE_COLUMNS: &str = "id, org_id, object_type, object_id, relation, subject_type, \
+     subject_id, subject_relation, created_by, created_at";
+
+/// PostgreSQL relationship repository
+pub struct PostgresRelationshipRepository {
+    pool: PgPool,
+}
+
+impl PostgresRelationshipRepository {
+    /// Create a new Postgres relationship repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RelationTupleRow {
+    id: Uuid,
+    org_id: Uuid,
+    object_type: String,
+    object_id: String,
+    relation: String,
+    subject_type: String,
+    subject_id: String,
+    subject_relation: String,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<RelationTupleRow> for RelationTuple {
+    fn from(row: RelationTupleRow) -> Self {
+        Self {
+            id: row.id,
+            org_id: row.org_id,
+            key: TupleKey {
+                object: ObjectRef::new(row.object_type, row.object_id),
+                relation: row.relation,
+                subject: SubjectRef {
+                    subject_type: row.subject_type,
+                    subject_id: row.subject_id,
+                    relation: (!row.subject_relation.is_empty()).then_some(row.subject_relation),
+                },
+            },
+            created_by: row.created_by,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RelationshipSchemaRow {
+    org_id: Uuid,
+    definition: serde_json::Value,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<RelationshipSchemaRow> for RelationshipSchema {
+    type Error = AppError;
+
+    fn try_from(row: RelationshipSchemaRow) -> Result<Self, Self::Error> {
+        let namespaces: BTreeMap<String, NamespaceDefinition> =
+            serde_json::from_value(row.definition)
+                .map_err(|e| AppError::Internal(anyhow::anyhow!("Invalid schema: {}", e)))?;
+        Ok(Self {
+            org_id: row.org_id,
+            namespaces,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+fn subject_relation(subject: &SubjectRef) -> &str {
+    subject.relation.as_deref().unwrap_or("")
+}
+
+/// Appends WHERE conditions for the org and tuple filter
+fn append_filter<'a>(
+    builder: &mut QueryBuilder<'a, Postgres>,
+    org_id: Uuid,
+    filter: &'a TupleFilter,
+) {
+    builder.push(" WHERE org_id = ").push_bind(org_id);
+    if let Some(ref object_type) = filter.object_type {
+        builder.push(" AND object_type = ").push_bind(object_type);
+    }
+    if let Some(ref object_id) = filter.object_id {
+        builder.push(" AND object_id = ").push_bind(object_id);
+    }
+    if let Some(ref relation) = filter.relation {
+        builder.push(" AND relation = ").push_bind(relation);
+    }
+    if let Some(ref subject) = filter.subject {
+        builder
+            .push(" AND subject_type = ")
+            .push_bind(&subject.subject_type)
+            .push(" AND subject_id = ")
+            .push_bind(&subject.subject_id)
+            .push(" AND subject_relation = ")
+            .push_bind(subject_relation(subject));
+    }
+}
+
+#[async_trait]
+impl RelationshipRepository for PostgresRelationshipRepository {
+    async fn write(&self, tuple: RelationTuple) -> Result<RelationTuple, AppError> {
+        let inserted: Option<RelationTupleRow> = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO relation_tuples (
+                id, org_id, object_type, object_id, relation,
+                subject_type, subject_id, subject_relation, created_by, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (org_id, object_type, object_id, relation, subject_type, subject_id, subject_relation)
+            DO NOTHING
+            RETURNING {}
+            "#,
+            TUPLE_COLUMNS
+        ))
+        .bind(tuple.id)
+        .bind(tuple.org_id)
+        .bind(&tuple.key.object.object_type)
+        .bind(&tuple.key.object.object_id)
+        .bind(&tuple.key.relation)
+        .bind(&tuple.key.subject.subject_type)
+        .bind(&tuple.key.subject.subject_id)
+        .bind(subject_relation(&tuple.key.subject))
+        .bind(tuple.created_by)
+        .bind(tuple.created_at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        if let Some(row) = inserted {
+            return Ok(row.into());
+        }
+
+        // Tuple already existed; return the stored copy
+        let filter = TupleFilter {
+            object_type: Some(tuple.key.object.object_type.clone()),
+            object_id: Some(tuple.key.object.object_id.clone()),
+            relation: Some(tuple.key.relation.clone()),
+            subject: Some(tuple.key.subject.clone()),
+        };
+        self.find(tuple.org_id, &filter)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Tuple vanished after conflict")))
+    }
+
+    async fn delete(
+        &self,
+        org_id: Uuid,
+        key: &TupleKey,
+    ) -> Result<Option<RelationTuple>, AppError> {
+        let row: Option<RelationTupleRow> = sqlx::query_as(&format!(
+            r#"
+            DELETE FROM relation_tuples
+            WHERE org_id = $1 AND object_type = $2 AND object_id = $3 AND relation = $4
+              AND subject_type = $5 AND subject_id = $6 AND subject_relation = $7
+            RETURNING {}
+            "#,
+            TUPLE_COLUMNS
+        ))
+        .bind(org_id)
+        .bind(&key.object.object_type)
+        .bind(&key.object.object_id)
+        .bind(&key.relation)
+        .bind(&key.subject.subject_type)
+        .bind(&key.subject.subject_id)
+        .bind(subject_relation(&key.subject))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn find(
+        &self,
+        org_id: Uuid,
+        filter: &TupleFilter,
+    ) -> Result<Vec<RelationTuple>, AppError> {
+        let mut builder: QueryBuilder<Postgres> =
+            QueryBuilder::new(format!("SELECT {} FROM relation_tuples", TUPLE_COLUMNS));
+        append_filter(&mut builder, org_id, filter);
+        builder.push(" ORDER BY created_at ASC, id ASC");
+
+        let rows: Vec<RelationTupleRow> = builder
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn find_paged(
+        &self,
+        org_id: Uuid,
+        filter: &TupleFilter,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<RelationTuple>, AppError> {
+        let mut builder: QueryBuilder<Postgres> =
+            QueryBuilder::new(format!("SELECT {} FROM relation_tuples", TUPLE_COLUMNS));
+        append_filter(&mut builder, org_id, filter);
+        builder.push(" ORDER BY created_at ASC, id ASC LIMIT ");
+        builder.push_bind(cap_limit(limit) as i64);
+        builder.push(" OFFSET ");
+        builder.push_bind(cap_offset(offset) as i64);
+
+        let rows: Vec<RelationTupleRow> = builder
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn count(&self, org_id: Uuid, filter: &TupleFilter) -> Result<u64, AppError> {
+        let mut builder: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT COUNT(*) FROM relation_tuples");
+        append_filter(&mut builder, org_id, filter);
+
+        let count: (i64,) = builder
+            .build_query_as()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(count.0.max(0) as u64)
+    }
+
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM relation_tuples WHERE org_id = $1")
+            .bind(org_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        sqlx::query("DELETE FROM relationship_schemas WHERE org_id = $1")
+            .bind(org_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn get_schema(&self, org_id: Uuid) -> Result<Option<RelationshipSchema>, AppError> {
+        let row: Option<RelationshipSchemaRow> = sqlx::query_as(
+            r#"
+            SELECT org_id, definition, updated_at
+            FROM relationship_schemas
+            WHERE org_id = $1
+            "#,
+        )
+        .bind(org_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn put_schema(&self, schema: RelationshipSchema) -> Result<RelationshipSchema, AppError> {
+        let definition =
+            serde_json::to_value(&schema.namespaces).map_err(|e| AppError::Internal(e.into()))?;
+
+        let row: RelationshipSchemaRow = sqlx::query_as(
+            r#"
+            INSERT INTO relationship_schemas (org_id, definition, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (org_id) DO UPDATE
+            SET definition = EXCLUDED.definition, updated_at = NOW()
+            RETURNING org_id, definition, updated_at
+            "#,
+        )
+        .bind(schema.org_id)
+        .bind(definition)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        row.try_into()
+    }
+}
@@ -0,0 +1,253 @@
+//! PostgreSQL service account repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::pagination::{cap_limit, cap_offset};
+use crate::repositories::{ServiceAccountEntity, ServiceAccountRepository};
+
+const SERVICE_ACCOUNT_COLUMNS: &str = "id, org_id, name, description, custom_role_id, client_id, \
+     client_secret_hash, created_by, created_at, updated_at, last_used_at";
+
+/// PostgreSQL service account repository
+pub struct PostgresServiceAccountRepository {
+    pool: PgPool,
+}
+
+impl PostgresServiceAccountRepository {
+    /// Create a new Postgres service account repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ServiceAccountRow {
+    id: Uuid,
+    org_id: Uuid,
+    name: String,
+    description: Option<String>,
+    custom_role_id: Option<Uuid>,
+    client_id: String,
+    client_secret_hash: String,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<ServiceAccountRow> for ServiceAccountEntity {
+    fn from(row: ServiceAccountRow) -> Self {
+        Self {
+            id: row.id,
+            org_id: row.org_id,
+            name: row.name,
+            description: row.description,
+            custom_role_id: row.custom_role_id,
+            client_id: row.client_id,
+            client_secret_hash: row.client_secret_hash,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            last_used_at: row.last_used_at,
+        }
+    }
+}
+
+fn map_service_account_error(e: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e {
+        if db_err.is_unique_violation() {
+            return AppError::Validation("A service account with this name already exists".into());
+        }
+    }
+    AppError::Database(e.to_string())
+}
+
+#[async_trait]
+impl ServiceAccountRepository for PostgresServiceAccountRepository {
+    async fn create(
+        &self,
+        account: ServiceAccountEntity,
+    ) -> Result<ServiceAccountEntity, AppError> {
+        let row: ServiceAccountRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO service_accounts (
+                id, org_id, name, description, custom_role_id, client_id,
+                client_secret_hash, created_by, created_at, updated_at, last_used_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING {}
+            "#,
+            SERVICE_ACCOUNT_COLUMNS
+        ))
+        .bind(account.id)
+        .bind(account.org_id)
+        .bind(&account.name)
+        .bind(&account.description)
+        .bind(account.custom_role_id)
+        .bind(&account.client_id)
+        .bind(&account.client_secret_hash)
+        .bind(account.created_by)
+        .bind(account.created_at)
+        .bind(account.updated_at)
+        .bind(account.last_used_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_service_account_error)?;
+
+        Ok(row.into())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ServiceAccountEntity>, AppError> {
+        let row: Option<ServiceAccountRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM service_accounts WHERE id = $1",
+            SERVICE_ACCOUNT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_by_client_id(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<ServiceAccountEntity>, AppError> {
+        let row: Option<ServiceAccountRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM service_accounts WHERE client_id = $1",
+            SERVICE_ACCOUNT_COLUMNS
+        ))
+        .bind(client_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_by_org_paged(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<ServiceAccountEntity>, AppError> {
+        let rows: Vec<ServiceAccountRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM service_accounts
+            WHERE org_id = $1
+            ORDER BY created_at ASC
+            LIMIT $2 OFFSET $3
+            "#,
+            SERVICE_ACCOUNT_COLUMNS
+        ))
+        .bind(org_id)
+        .bind(cap_limit(limit) as i64)
+        .bind(cap_offset(offset) as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn find_by_org(&self, org_id: Uuid) -> Result<Vec<ServiceAccountEntity>, AppError> {
+        let rows: Vec<ServiceAccountRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM service_accounts WHERE org_id = $1",
+            SERVICE_ACCOUNT_COLUMNS
+        ))
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM service_accounts WHERE org_id = $1")
+                .bind(org_id)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(count.0.max(0) as u64)
+    }
+
+    async fn update(
+        &self,
+        account: ServiceAccountEntity,
+    ) -> Result<ServiceAccountEntity, AppError> {
+        let row: Option<ServiceAccountRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE service_accounts
+            SET name = $2, description = $3, custom_role_id = $4, updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            SERVICE_ACCOUNT_COLUMNS
+        ))
+        .bind(account.id)
+        .bind(&account.name)
+        .bind(&account.description)
+        .bind(account.custom_role_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_service_account_error)?;
+
+        row.map(Into::into)
+            .ok_or(AppError::NotFound("Service account not found".into()))
+    }
+
+    async fn update_secret_hash(&self, id: Uuid, secret_hash: &str) -> Result<(), AppError> {
+        let result = sqlx::query(
+            "UPDATE service_accounts SET client_secret_hash = $2, updated_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .bind(secret_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Service account not found".into()));
+        }
+        Ok(())
+    }
+
+    async fn update_last_used(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query("UPDATE service_accounts SET last_used_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM service_accounts WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM service_accounts WHERE org_id = $1")
+            .bind(org_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
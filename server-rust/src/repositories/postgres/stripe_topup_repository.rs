@@ -0,0 +1,273 @@
+//! PostgreSQL Stripe top-up repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{StripeTopupEntity, StripeTopupLock, StripeTopupRepository};
+
+const COLUMNS: &str = "id, user_id, org_id, flow, amount_cents, currency, credit_amount, \
+     credit_currency, status, checkout_session_id, payment_intent_id, transaction_id, \
+     refunded_cents, dispute_id, dispute_status, disputed_cents, disputed_amount, \
+     reversed_amount, unrecovered_amount, created_at, updated_at, completed_at";
+
+/// PostgreSQL Stripe top-up repository
+pub struct PostgresStripeTopupRepository {
+    pool: PgPool,
+}
+
+impl PostgresStripeTopupRepository {
+    /// Create a new Postgres Stripe top-up repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn find_where(
+        &self,
+        condition: &str,
+        value: &str,
+    ) -> Result<Option<StripeTopupEntity>, AppError> {
+        let row: Option<TopupRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM stripe_topups WHERE {} = $1",
+            COLUMNS, condition
+        ))
+        .bind(value)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+}
+
+fn map_unique_error(e: sqlx::Error, message: &str) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e {
+        if db_err.is_unique_violation() {
+            return AppError::Validation(message.into());
+        }
+    }
+    AppError::Database(e.to_string())
+}
+
+#[derive(sqlx::FromRow)]
+struct TopupRow {
+    id: Uuid,
+    user_id: Uuid,
+    org_id: Option<Uuid>,
+    flow: String,
+    amount_cents: i64,
+    currency: String,
+    credit_amount: i64,
+    credit_currency: String,
+    status: String,
+    checkout_session_id: Option<String>,
+    payment_intent_id: Option<String>,
+    transaction_id: Option<Uuid>,
+    refunded_cents: i64,
+    dispute_id: Option<String>,
+    dispute_status: Option<String>,
+    disputed_cents: i64,
+    disputed_amount: i64,
+    reversed_amount: i64,
+    unrecovered_amount: i64,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<TopupRow> for StripeTopupEntity {
+    type Error = AppError;
+
+    fn try_from(row: TopupRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            user_id: row.user_id,
+            org_id: row.org_id,
+            flow: row.flow.parse()?,
+            amount_cents: row.amount_cents,
+            currency: row.currency,
+            credit_amount: row.credit_amount,
+            credit_currency: row.credit_currency,
+            status: row.status.parse()?,
+            checkout_session_id: row.checkout_session_id,
+            payment_intent_id: row.payment_intent_id,
+            transaction_id: row.transaction_id,
+            refunded_cents: row.refunded_cents,
+            dispute_id: row.dispute_id,
+            dispute_status: row.dispute_status,
+            disputed_cents: row.disputed_cents,
+            disputed_amount: row.disputed_amount,
+            reversed_amount: row.reversed_amount,
+            unrecovered_amount: row.unrecovered_amount,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            completed_at: row.completed_at,
+        })
+    }
+}
+
+#[async_trait]
+impl StripeTopupRepository for PostgresStripeTopupRepository {
+    async fn create(&self, topup: StripeTopupEntity) -> Result<StripeTopupEntity, AppError> {
+        let row: TopupRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO stripe_topups (
+                id, user_id, org_id, flow, amount_cents, currency, credit_amount,
+                credit_currency, status, checkout_session_id, payment_intent_id,
+                created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(topup.id)
+        .bind(topup.user_id)
+        .bind(topup.org_id)
+        .bind(topup.flow.as_str())
+        .bind(topup.amount_cents)
+        .bind(&topup.currency)
+        .bind(topup.credit_amount)
+        .bind(&topup.credit_currency)
+        .bind(topup.status.as_str())
+        .bind(&topup.checkout_session_id)
+        .bind(&topup.payment_intent_id)
+        .bind(topup.created_at)
+        .bind(topup.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| map_unique_error(e, "Stripe payment already linked to a top-up"))?;
+
+        row.try_into()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<StripeTopupEntity>, AppError> {
+        let row: Option<TopupRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM stripe_topups WHERE id = $1",
+            COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_by_checkout_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<StripeTopupEntity>, AppError> {
+        self.find_where("checkout_session_id", session_id).await
+    }
+
+    async fn find_by_payment_intent(
+        &self,
+        payment_intent_id: &str,
+    ) -> Result<Option<StripeTopupEntity>, AppError> {
+        self.find_where("payment_intent_id", payment_intent_id)
+            .await
+    }
+
+    async fn list_for_user(
+        &self,
+        user_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<StripeTopupEntity>, AppError> {
+        let rows: Vec<TopupRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM stripe_topups
+            WHERE user_id = $1 AND org_id IS NULL
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+            COLUMNS
+        ))
+        .bind(user_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn list_for_org(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<StripeTopupEntity>, AppError> {
+        let rows: Vec<TopupRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM stripe_topups
+            WHERE org_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+            COLUMNS
+        ))
+        .bind(org_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn update(&self, topup: StripeTopupEntity) -> Result<StripeTopupEntity, AppError> {
+        let row: Option<TopupRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE stripe_topups
+            SET status = $2, checkout_session_id = $3, payment_intent_id = $4,
+                transaction_id = $5, refunded_cents = $6, dispute_id = $7,
+                dispute_status = $8, disputed_cents = $9, disputed_amount = $10,
+                reversed_amount = $11, unrecovered_amount = $12, completed_at = $13,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            COLUMNS
+        ))
+        .bind(topup.id)
+        .bind(topup.status.as_str())
+        .bind(&topup.checkout_session_id)
+        .bind(&topup.payment_intent_id)
+        .bind(topup.transaction_id)
+        .bind(topup.refunded_cents)
+        .bind(&topup.dispute_id)
+        .bind(&topup.dispute_status)
+        .bind(topup.disputed_cents)
+        .bind(topup.disputed_amount)
+        .bind(topup.reversed_amount)
+        .bind(topup.unrecovered_amount)
+        .bind(topup.completed_at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| map_unique_error(e, "Stripe payment already linked to a top-up"))?;
+
+        row.ok_or(AppError::NotFound("Top-up not found".into()))?
+            .try_into()
+    }
+
+    async fn lock(&self, id: Uuid) -> Result<StripeTopupLock, AppError> {
+        // A transaction-scoped advisory lock rather than a row lock, so the
+        // holder can still update the row from other connections; dropping
+        // the transaction rolls it back and releases the lock
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))")
+            .bind(format!("stripe_topup:{}", id))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(StripeTopupLock::new(tx))
+    }
+}
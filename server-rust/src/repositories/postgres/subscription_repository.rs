@@ -0,0 +1,397 @@
+//! PostgreSQL subscription plan and subscription repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{SubscriptionEntity, SubscriptionPlanEntity, SubscriptionRepository};
+
+const PLAN_COLUMNS: &str = "id, name, display_name, price_lamports, currency, interval, \
+     interval_count, trial_days, grace_days, entitlements, active, created_at, updated_at";
+
+const SUBSCRIPTION_COLUMNS: &str = "id, plan_id, user_id, org_id, status, \
+     current_period_start, current_period_end, trial_end, cancel_at_period_end, past_due_since, \
+     dunning_attempts, next_attempt_at, canceled_at, cancel_reason, last_transaction_id, \
+     created_at, updated_at";
+
+/// PostgreSQL subscription repository
+pub struct PostgresSubscriptionRepository {
+    pool: PgPool,
+}
+
+impl PostgresSubscriptionRepository {
+    /// Create a new Postgres subscription repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn map_unique_error(e: sqlx::Error, message: &str) -> AppError {
+    if let sqlx::Error::Database(db_err) = &e {
+        if db_err.is_unique_violation() {
+            return AppError::Validation(message.into());
+        }
+    }
+    AppError::Database(e.to_string())
+}
+
+#[derive(sqlx::FromRow)]
+struct PlanRow {
+    id: Uuid,
+    name: String,
+    display_name: String,
+    price_lamports: i64,
+    currency: String,
+    interval: String,
+    interval_count: i32,
+    trial_days: i32,
+    grace_days: i32,
+    entitlements: Vec<String>,
+    active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<PlanRow> for SubscriptionPlanEntity {
+    type Error = AppError;
+
+    fn try_from(row: PlanRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            name: row.name,
+            display_name: row.display_name,
+            price_lamports: row.price_lamports,
+            currency: row.currency,
+            interval: row.interval.parse()?,
+            interval_count: row.interval_count,
+            trial_days: row.trial_days,
+            grace_days: row.grace_days,
+            entitlements: row.entitlements,
+            active: row.active,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SubscriptionRow {
+    id: Uuid,
+    plan_id: Uuid,
+    user_id: Uuid,
+    org_id: Option<Uuid>,
+    status: String,
+    current_period_start: DateTime<Utc>,
+    current_period_end: DateTime<Utc>,
+    trial_end: Option<DateTime<Utc>>,
+    cancel_at_period_end: bool,
+    past_due_since: Option<DateTime<Utc>>,
+    dunning_attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    canceled_at: Option<DateTime<Utc>>,
+    cancel_reason: Option<String>,
+    last_transaction_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<SubscriptionRow> for SubscriptionEntity {
+    type Error = AppError;
+
+    fn try_from(row: SubscriptionRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            plan_id: row.plan_id,
+            user_id: row.user_id,
+            org_id: row.org_id,
+            status: row.status.parse()?,
+            current_period_start: row.current_period_start,
+            current_period_end: row.current_period_end,
+            trial_end: row.trial_end,
+            cancel_at_period_end: row.cancel_at_period_end,
+            past_due_since: row.past_due_since,
+            dunning_attempts: row.dunning_attempts,
+            next_attempt_at: row.next_attempt_at,
+            canceled_at: row.canceled_at,
+            cancel_reason: row.cancel_reason,
+            last_transaction_id: row.last_transaction_id,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+#[async_trait]
+impl SubscriptionRepository for PostgresSubscriptionRepository {
+    async fn create_plan(
+        &self,
+        plan: SubscriptionPlanEntity,
+    ) -> Result<SubscriptionPlanEntity, AppError> {
+        let row: PlanRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO subscription_plans (
+                id, name, display_name, price_lamports, currency, interval, interval_count,
+                trial_days, grace_days, entitlements, active, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING {}
+            "#,
+            PLAN_COLUMNS
+        ))
+        .bind(plan.id)
+        .bind(&plan.name)
+        .bind(&plan.display_name)
+        .bind(plan.price_lamports)
+        .bind(&plan.currency)
+        .bind(plan.interval.as_str())
+        .bind(plan.interval_count)
+        .bind(plan.trial_days)
+        .bind(plan.grace_days)
+        .bind(&plan.entitlements)
+        .bind(plan.active)
+        .bind(plan.created_at)
+        .bind(plan.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| map_unique_error(e, "A plan with this name already exists"))?;
+
+        row.try_into()
+    }
+
+    async fn find_plan(&self, id: Uuid) -> Result<Option<SubscriptionPlanEntity>, AppError> {
+        let row: Option<PlanRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM subscription_plans WHERE id = $1",
+            PLAN_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn find_plan_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<SubscriptionPlanEntity>, AppError> {
+        let row: Option<PlanRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM subscription_plans WHERE name = $1",
+            PLAN_COLUMNS
+        ))
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn list_plans(&self) -> Result<Vec<SubscriptionPlanEntity>, AppError> {
+        let rows: Vec<PlanRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM subscription_plans ORDER BY name ASC",
+            PLAN_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn update_plan(
+        &self,
+        plan: SubscriptionPlanEntity,
+    ) -> Result<SubscriptionPlanEntity, AppError> {
+        let row: Option<PlanRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE subscription_plans
+            SET display_name = $2, trial_days = $3, grace_days = $4, entitlements = $5,
+                active = $6, updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            PLAN_COLUMNS
+        ))
+        .bind(plan.id)
+        .bind(&plan.display_name)
+        .bind(plan.trial_days)
+        .bind(plan.grace_days)
+        .bind(&plan.entitlements)
+        .bind(plan.active)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.ok_or(AppError::NotFound("Subscription plan not found".into()))?
+            .try_into()
+    }
+
+    async fn create_subscription(
+        &self,
+        subscription: SubscriptionEntity,
+    ) -> Result<SubscriptionEntity, AppError> {
+        let row: SubscriptionRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO subscriptions (
+                id, plan_id, user_id, org_id, status, current_period_start, current_period_end,
+                trial_end, cancel_at_period_end, past_due_since, dunning_attempts,
+                next_attempt_at, canceled_at, cancel_reason, last_transaction_id,
+                created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            RETURNING {}
+            "#,
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(subscription.id)
+        .bind(subscription.plan_id)
+        .bind(subscription.user_id)
+        .bind(subscription.org_id)
+        .bind(subscription.status.as_str())
+        .bind(subscription.current_period_start)
+        .bind(subscription.current_period_end)
+        .bind(subscription.trial_end)
+        .bind(subscription.cancel_at_period_end)
+        .bind(subscription.past_due_since)
+        .bind(subscription.dunning_attempts)
+        .bind(subscription.next_attempt_at)
+        .bind(subscription.canceled_at)
+        .bind(&subscription.cancel_reason)
+        .bind(subscription.last_transaction_id)
+        .bind(subscription.created_at)
+        .bind(subscription.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| map_unique_error(e, "Already subscribed to this plan"))?;
+
+        row.try_into()
+    }
+
+    async fn find_subscription(&self, id: Uuid) -> Result<Option<SubscriptionEntity>, AppError> {
+        let row: Option<SubscriptionRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM subscriptions WHERE id = $1",
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<SubscriptionEntity>, AppError> {
+        let rows: Vec<SubscriptionRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM subscriptions
+            WHERE user_id = $1 AND org_id IS NULL
+            ORDER BY created_at DESC
+            "#,
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn list_for_org(&self, org_id: Uuid) -> Result<Vec<SubscriptionEntity>, AppError> {
+        let rows: Vec<SubscriptionRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM subscriptions
+            WHERE org_id = $1
+            ORDER BY created_at DESC
+            "#,
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn list_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<SubscriptionEntity>, AppError> {
+        let rows: Vec<SubscriptionRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM subscriptions
+            WHERE status <> 'canceled' AND next_attempt_at <= $1
+            ORDER BY next_attempt_at ASC
+            LIMIT $2
+            "#,
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn update_subscription(
+        &self,
+        subscription: SubscriptionEntity,
+    ) -> Result<Option<SubscriptionEntity>, AppError> {
+        let row: Option<SubscriptionRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE subscriptions
+            SET status = $2, current_period_start = $3, current_period_end = $4,
+                trial_end = $5, cancel_at_period_end = $6, past_due_since = $7,
+                dunning_attempts = $8, next_attempt_at = $9, canceled_at = $10,
+                cancel_reason = $11, last_transaction_id = $12, updated_at = NOW()
+            WHERE id = $1 AND updated_at = $13
+            RETURNING {}
+            "#,
+            SUBSCRIPTION_COLUMNS
+        ))
+        .bind(subscription.id)
+        .bind(subscription.status.as_str())
+        .bind(subscription.current_period_start)
+        .bind(subscription.current_period_end)
+        .bind(subscription.trial_end)
+        .bind(subscription.cancel_at_period_end)
+        .bind(subscription.past_due_since)
+        .bind(subscription.dunning_attempts)
+        .bind(subscription.next_attempt_at)
+        .bind(subscription.canceled_at)
+        .bind(&subscription.cancel_reason)
+        .bind(subscription.last_transaction_id)
+        .bind(subscription.updated_at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        match row {
+            Some(row) => Ok(Some(row.try_into()?)),
+            None => match self.find_subscription(subscription.id).await? {
+                Some(_) => Ok(None),
+                None => Err(AppError::NotFound("Subscription not found".into())),
+            },
+        }
+    }
+
+    async fn delete_subscription(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
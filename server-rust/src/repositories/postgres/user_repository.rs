@@ -1,12 +1,14 @@
 //! PostgreSQL user repository implementation
 
 use async_trait::async_trait;
+use serde_json::{Map, Value};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::models::AuthMethod;
-use crate::repositories::{normalize_email, UserEntity, UserRepository};
+use crate::repositories::{normalize_email, UserEntity, UserRepository, UserStatus};
+use crate::utils::metadata::apply_metadata_patch;
 
 /// PostgreSQL user repository
 pub struct PostgresUserRepository {
@@ -44,15 +46,26 @@ mod tests {
                 "sso".to_string(),
             ],
             is_system_admin: false,
+            is_service_account: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_login_at: None,
+            status: "suspended".to_string(),
+            status_reason: Some("chargeback".to_string()),
+            status_expires_at: None,
+            public_metadata: serde_json::json!({ "plan": "pro" }),
+            private_metadata: Value::Null,
+            unsafe_metadata: serde_json::json!({}),
         };
 
         let entity: UserEntity = row.into();
         assert!(entity.auth_methods.contains(&AuthMethod::Email));
         assert!(entity.auth_methods.contains(&AuthMethod::WebAuthn));
         assert!(entity.auth_methods.contains(&AuthMethod::Sso));
+        assert_eq!(entity.status, UserStatus::Suspended);
+        assert_eq!(entity.status_reason.as_deref(), Some("chargeback"));
+        assert_eq!(entity.public_metadata["plan"], "pro");
+        assert!(entity.private_metadata.is_empty());
     }
 }
 
@@ -71,9 +84,16 @@ struct UserRow {
     stripe_customer_id: Option<String>,
     auth_methods: Vec<String>,
     is_system_admin: bool,
+    is_service_account: bool,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
     last_login_at: Option<chrono::DateTime<chrono::Utc>>,
+    status: String,
+    status_reason: Option<String>,
+    status_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    public_metadata: Value,
+    private_metadata: Value,
+    unsafe_metadata: Value,
 }
 
 impl From<UserRow> for UserEntity {
@@ -103,13 +123,37 @@ impl From<UserRow> for UserEntity {
                 })
                 .collect(),
             is_system_admin: row.is_system_admin,
+            is_service_account: row.is_service_account,
             created_at: row.created_at,
             updated_at: row.updated_at,
             last_login_at: row.last_login_at,
+            status: UserStatus::from_str(&row.status).unwrap_or_default(),
+            status_reason: row.status_reason,
+            status_expires_at: row.status_expires_at,
+            public_metadata: metadata_object(row.public_metadata),
+            private_metadata: metadata_object(row.private_metadata),
+            unsafe_metadata: metadata_object(row.unsafe_metadata),
         }
     }
 }
 
+/// Metadata columns always hold objects; anything else reads as empty
+fn metadata_object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Filter on effective status (`$1`): suspensions and bans whose expiry has
+/// passed count as active
+const STATUS_FILTER: &str = r#"
+    CASE
+        WHEN status IN ('suspended', 'banned') AND status_expires_at <= NOW() THEN 'active'
+        ELSE status
+    END = $1
+"#;
+
 fn auth_methods_to_strings(methods: &[AuthMethod]) -> Vec<String> {
     methods
         .iter()
@@ -131,7 +175,8 @@ impl UserRepository for PostgresUserRepository {
             r#"
             SELECT id, email, email_verified, password_hash, name, picture,
                    wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                   created_at, updated_at, last_login_at
+                   is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                   status_expires_at, public_metadata, private_metadata, unsafe_metadata
             FROM users WHERE id = $1
             "#,
         )
@@ -153,7 +198,8 @@ impl UserRepository for PostgresUserRepository {
             r#"
             SELECT id, email, email_verified, password_hash, name, picture,
                    wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                   created_at, updated_at, last_login_at
+                   is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                   status_expires_at, public_metadata, private_metadata, unsafe_metadata
             FROM users WHERE email = $1
             "#,
         )
@@ -170,7 +216,8 @@ impl UserRepository for PostgresUserRepository {
             r#"
             SELECT id, email, email_verified, password_hash, name, picture,
                    wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                   created_at, updated_at, last_login_at
+                   is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                   status_expires_at, public_metadata, private_metadata, unsafe_metadata
             FROM users WHERE wallet_address = $1
             "#,
         )
@@ -187,7 +234,8 @@ impl UserRepository for PostgresUserRepository {
             r#"
             SELECT id, email, email_verified, password_hash, name, picture,
                    wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                   created_at, updated_at, last_login_at
+                   is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                   status_expires_at, public_metadata, private_metadata, unsafe_metadata
             FROM users WHERE google_id = $1
             "#,
         )
@@ -204,7 +252,8 @@ impl UserRepository for PostgresUserRepository {
             r#"
             SELECT id, email, email_verified, password_hash, name, picture,
                    wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                   created_at, updated_at, last_login_at
+                   is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                   status_expires_at, public_metadata, private_metadata, unsafe_metadata
             FROM users WHERE apple_id = $1
             "#,
         )
@@ -224,7 +273,8 @@ impl UserRepository for PostgresUserRepository {
             r#"
             SELECT id, email, email_verified, password_hash, name, picture,
                    wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                   created_at, updated_at, last_login_at
+                   is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                   status_expires_at, public_metadata, private_metadata, unsafe_metadata
             FROM users WHERE stripe_customer_id = $1
             "#,
         )
@@ -243,11 +293,12 @@ impl UserRepository for PostgresUserRepository {
             r#"
             INSERT INTO users (id, email, email_verified, password_hash, name, picture,
                               wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                              created_at, updated_at, last_login_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                              is_service_account, created_at, updated_at, last_login_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
             RETURNING id, email, email_verified, password_hash, name, picture,
                       wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                      created_at, updated_at, last_login_at
+                      is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                      status_expires_at, public_metadata, private_metadata, unsafe_metadata
             "#,
         )
         .bind(user.id)
@@ -262,6 +313,7 @@ impl UserRepository for PostgresUserRepository {
         .bind(&user.stripe_customer_id)
         .bind(&auth_methods)
         .bind(user.is_system_admin)
+        .bind(user.is_service_account)
         .bind(user.created_at)
         .bind(user.updated_at)
         .bind(user.last_login_at)
@@ -294,7 +346,8 @@ impl UserRepository for PostgresUserRepository {
             WHERE id = $1
             RETURNING id, email, email_verified, password_hash, name, picture,
                       wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                      created_at, updated_at, last_login_at
+                      is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                      status_expires_at, public_metadata, private_metadata, unsafe_metadata
             "#,
         )
         .bind(user.id)
@@ -388,7 +441,8 @@ impl UserRepository for PostgresUserRepository {
             r#"
             SELECT id, email, email_verified, password_hash, name, picture,
                    wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
-                   created_at, updated_at, last_login_at
+                   is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                   status_expires_at, public_metadata, private_metadata, unsafe_metadata
             FROM users
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
@@ -509,4 +563,192 @@ impl UserRepository for PostgresUserRepository {
 
         Ok(())
     }
+
+    async fn set_status(
+        &self,
+        id: Uuid,
+        status: UserStatus,
+        reason: Option<String>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET status = $2, status_reason = $3, status_expires_at = $4, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(status.as_str())
+        .bind(reason)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("User not found".into()));
+        }
+        Ok(())
+    }
+
+    async fn list_by_status(
+        &self,
+        status: UserStatus,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<UserEntity>, AppError> {
+        const MAX_PAGE_SIZE: u32 = 100;
+        const MAX_OFFSET: u32 = 1_000_000;
+
+        let query = format!(
+            r#"
+            SELECT id, email, email_verified, password_hash, name, picture,
+                   wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
+                   is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                   status_expires_at, public_metadata, private_metadata, unsafe_metadata
+            FROM users
+            WHERE {}
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            STATUS_FILTER
+        );
+        let rows: Vec<UserRow> = sqlx::query_as(&query)
+            .bind(status.as_str())
+            .bind(limit.min(MAX_PAGE_SIZE) as i64)
+            .bind(offset.min(MAX_OFFSET) as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn count_by_status(&self, status: UserStatus) -> Result<u64, AppError> {
+        let query = format!("SELECT COUNT(*) FROM users WHERE {}", STATUS_FILTER);
+        let count: i64 = sqlx::query_scalar(&query)
+            .bind(status.as_str())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(count.max(0) as u64)
+    }
+
+    async fn find_due_for_purge(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> Result<Vec<UserEntity>, AppError> {
+        let rows: Vec<UserRow> = sqlx::query_as(
+            r#"
+            SELECT id, email, email_verified, password_hash, name, picture,
+                   wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
+                   is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                   status_expires_at, public_metadata, private_metadata, unsafe_metadata
+            FROM users
+            WHERE status = 'pending_deletion' AND status_expires_at <= $1
+            ORDER BY status_expires_at
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn update_metadata(
+        &self,
+        id: Uuid,
+        public: Option<Map<String, Value>>,
+        private: Option<Map<String, Value>>,
+        unsafe_: Option<Map<String, Value>>,
+    ) -> Result<UserEntity, AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        // Lock the row so concurrent patches apply one after the other
+        let current: Option<(Value, Value, Value)> = sqlx::query_as(
+            r#"
+            SELECT public_metadata, private_metadata, unsafe_metadata
+            FROM users WHERE id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+        let (current_public, current_private, current_unsafe) =
+            current.ok_or_else(|| AppError::NotFound("User not found".into()))?;
+
+        let patch = |current: Value, patch: Option<Map<String, Value>>, field: &str| {
+            patch
+                .map(|p| apply_metadata_patch(&metadata_object(current), p, field))
+                .transpose()
+        };
+        let public = patch(current_public, public, "publicMetadata")?;
+        let private = patch(current_private, private, "privateMetadata")?;
+        let unsafe_ = patch(current_unsafe, unsafe_, "unsafeMetadata")?;
+
+        let row: UserRow = sqlx::query_as(
+            r#"
+            UPDATE users SET
+                public_metadata = COALESCE($2, public_metadata),
+                private_metadata = COALESCE($3, private_metadata),
+                unsafe_metadata = COALESCE($4, unsafe_metadata),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, email, email_verified, password_hash, name, picture,
+                      wallet_address, google_id, apple_id, stripe_customer_id, auth_methods, is_system_admin,
+                      is_service_account, created_at, updated_at, last_login_at, status, status_reason,
+                      status_expires_at, public_metadata, private_metadata, unsafe_metadata
+            "#,
+        )
+        .bind(id)
+        .bind(public.map(Value::Object))
+        .bind(private.map(Value::Object))
+        .bind(unsafe_.map(Value::Object))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+
+        Ok(row.into())
+    }
+
+    async fn anonymize(&self, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET email = NULL, email_verified = FALSE, password_hash = NULL, name = NULL,
+                picture = NULL, wallet_address = NULL, google_id = NULL, apple_id = NULL,
+                stripe_customer_id = NULL, auth_methods = '{}',
+                public_metadata = '{}', private_metadata = '{}', unsafe_metadata = '{}',
+                status = 'deleted', status_reason = NULL, status_expires_at = NULL,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("User not found".into()));
+        }
+        Ok(())
+    }
 }
@@ -19,6 +19,39 @@ impl PostgresVerificationRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    async fn insert(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        token_type: TokenType,
+        expires_at: DateTime<Utc>,
+        payload: Option<&str>,
+    ) -> Result<VerificationToken, RepositoryError> {
+        // D-13: Use CTE to atomically delete old unused tokens before creating new one.
+        // This prevents accumulation of unused tokens from multiple reset requests.
+        let row: VerificationTokenRow = sqlx::query_as(
+            r#"
+            WITH deleted AS (
+                DELETE FROM verification_tokens
+                WHERE user_id = $1 AND token_type = $3 AND used_at IS NULL
+            )
+            INSERT INTO verification_tokens (user_id, token_hash, token_type, expires_at, payload)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, user_id, token_hash, token_type, created_at, expires_at, used_at, payload
+            "#,
+        )
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(token_type.as_str())
+        .bind(expires_at)
+        .bind(payload)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+
+        row.try_into()
+    }
 }
 
 /// Row type for verification token queries
@@ -31,6 +64,7 @@ struct VerificationTokenRow {
     created_at: DateTime<Utc>,
     expires_at: DateTime<Utc>,
     used_at: Option<DateTime<Utc>>,
+    payload: Option<String>,
 }
 
 impl TryFrom<VerificationTokenRow> for VerificationToken {
@@ -49,6 +83,7 @@ impl TryFrom<VerificationTokenRow> for VerificationToken {
             created_at: row.created_at,
             expires_at: row.expires_at,
             used_at: row.used_at,
+            payload: row.payload,
         })
     }
 }
@@ -62,28 +97,20 @@ impl VerificationRepository for PostgresVerificationRepository {
         token_type: TokenType,
         expires_at: DateTime<Utc>,
     ) -> Result<VerificationToken, RepositoryError> {
-        // D-13: Use CTE to atomically delete old unused tokens before creating new one.
-        // This prevents accumulation of unused tokens from multiple reset requests.
-        let row: VerificationTokenRow = sqlx::query_as(
-            r#"
-            WITH deleted AS (
-                DELETE FROM verification_tokens
-                WHERE user_id = $1 AND token_type = $3 AND used_at IS NULL
-            )
-            INSERT INTO verification_tokens (user_id, token_hash, token_type, expires_at)
-            VALUES ($1, $2, $3, $4)
-            RETURNING id, user_id, token_hash, token_type, created_at, expires_at, used_at
-            "#,
-        )
-        .bind(user_id)
-        .bind(token_hash)
-        .bind(token_type.as_str())
-        .bind(expires_at)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| RepositoryError::Database(e.to_string()))?;
+        self.insert(user_id, token_hash, token_type, expires_at, None)
+            .await
+    }
 
-        row.try_into()
+    async fn create_with_payload(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        token_type: TokenType,
+        expires_at: DateTime<Utc>,
+        payload: &str,
+    ) -> Result<VerificationToken, RepositoryError> {
+        self.insert(user_id, token_hash, token_type, expires_at, Some(payload))
+            .await
     }
 
     async fn find_by_hash(
@@ -92,7 +119,7 @@ impl VerificationRepository for PostgresVerificationRepository {
     ) -> Result<Option<VerificationToken>, RepositoryError> {
         let row: Option<VerificationTokenRow> = sqlx::query_as(
             r#"
-            SELECT id, user_id, token_hash, token_type, created_at, expires_at, used_at
+            SELECT id, user_id, token_hash, token_type, created_at, expires_at, used_at, payload
             FROM verification_tokens WHERE token_hash = $1
             "#,
         )
@@ -135,7 +162,7 @@ impl VerificationRepository for PostgresVerificationRepository {
             WHERE token_hash = $1
               AND used_at IS NULL
               AND expires_at > NOW()
-            RETURNING id, user_id, token_hash, token_type, created_at, expires_at, used_at
+            RETURNING id, user_id, token_hash, token_type, created_at, expires_at, used_at, payload
             "#,
         )
         .bind(token_hash)
@@ -0,0 +1,393 @@
+//! PostgreSQL webhook endpoint and delivery log repository implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::pagination::{cap_limit, cap_offset};
+use crate::repositories::webhook_repository::auto_disable_reason;
+use crate::repositories::{WebhookDeliveryEntity, WebhookEndpointEntity, WebhookRepository};
+
+const ENDPOINT_COLUMNS: &str = "id, url, description, secret_encrypted, events, enabled, \
+     consecutive_failures, disabled_reason, created_by, created_at, updated_at";
+
+const DELIVERY_COLUMNS: &str = "id, endpoint_id, event_type, payload, status, attempts, \
+     response_status, last_error, replay_of, created_at, last_attempt_at, delivered_at";
+
+/// PostgreSQL webhook repository
+pub struct PostgresWebhookRepository {
+    pool: PgPool,
+}
+
+impl PostgresWebhookRepository {
+    /// Create a new Postgres webhook repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EndpointRow {
+    id: Uuid,
+    url: String,
+    description: Option<String>,
+    secret_encrypted: String,
+    events: Vec<String>,
+    enabled: bool,
+    consecutive_failures: i32,
+    disabled_reason: Option<String>,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<EndpointRow> for WebhookEndpointEntity {
+    fn from(row: EndpointRow) -> Self {
+        Self {
+            id: row.id,
+            url: row.url,
+            description: row.description,
+            secret_encrypted: row.secret_encrypted,
+            events: row.events,
+            enabled: row.enabled,
+            consecutive_failures: row.consecutive_failures.max(0) as u32,
+            disabled_reason: row.disabled_reason,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DeliveryRow {
+    id: Uuid,
+    endpoint_id: Uuid,
+    event_type: String,
+    payload: Value,
+    status: String,
+    attempts: i32,
+    response_status: Option<i32>,
+    last_error: Option<String>,
+    replay_of: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    last_attempt_at: Option<DateTime<Utc>>,
+    delivered_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<DeliveryRow> for WebhookDeliveryEntity {
+    type Error = AppError;
+
+    fn try_from(row: DeliveryRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            endpoint_id: row.endpoint_id,
+            event_type: row.event_type,
+            payload: row.payload,
+            status: row.status.parse()?,
+            attempts: row.attempts.max(0) as u32,
+            response_status: row.response_status.map(|s| s as u16),
+            last_error: row.last_error,
+            replay_of: row.replay_of,
+            created_at: row.created_at,
+            last_attempt_at: row.last_attempt_at,
+            delivered_at: row.delivered_at,
+        })
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for PostgresWebhookRepository {
+    async fn create_endpoint(
+        &self,
+        endpoint: WebhookEndpointEntity,
+    ) -> Result<WebhookEndpointEntity, AppError> {
+        let row: EndpointRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO webhook_endpoints (
+                id, url, description, secret_encrypted, events, enabled,
+                consecutive_failures, disabled_reason, created_by, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING {}
+            "#,
+            ENDPOINT_COLUMNS
+        ))
+        .bind(endpoint.id)
+        .bind(&endpoint.url)
+        .bind(&endpoint.description)
+        .bind(&endpoint.secret_encrypted)
+        .bind(&endpoint.events)
+        .bind(endpoint.enabled)
+        .bind(endpoint.consecutive_failures as i32)
+        .bind(&endpoint.disabled_reason)
+        .bind(endpoint.created_by)
+        .bind(endpoint.created_at)
+        .bind(endpoint.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.into())
+    }
+
+    async fn find_endpoint(&self, id: Uuid) -> Result<Option<WebhookEndpointEntity>, AppError> {
+        let row: Option<EndpointRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM webhook_endpoints WHERE id = $1",
+            ENDPOINT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_endpoints(
+        &self,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<WebhookEndpointEntity>, AppError> {
+        let rows: Vec<EndpointRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM webhook_endpoints
+            ORDER BY created_at ASC
+            LIMIT $1 OFFSET $2
+            "#,
+            ENDPOINT_COLUMNS
+        ))
+        .bind(cap_limit(limit) as i64)
+        .bind(cap_offset(offset) as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn count_endpoints(&self) -> Result<u64, AppError> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM webhook_endpoints")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(count.0.max(0) as u64)
+    }
+
+    async fn find_subscribed(&self, event: &str) -> Result<Vec<WebhookEndpointEntity>, AppError> {
+        let rows: Vec<EndpointRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM webhook_endpoints
+            WHERE enabled AND (cardinality(events) = 0 OR $1 = ANY(events))
+            "#,
+            ENDPOINT_COLUMNS
+        ))
+        .bind(event)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn update_endpoint(
+        &self,
+        endpoint: WebhookEndpointEntity,
+    ) -> Result<WebhookEndpointEntity, AppError> {
+        let row: Option<EndpointRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE webhook_endpoints
+            SET url = $2, description = $3, secret_encrypted = $4, events = $5,
+                consecutive_failures = CASE WHEN $6 AND NOT enabled
+                    THEN 0 ELSE consecutive_failures END,
+                disabled_reason = CASE WHEN $6 AND NOT enabled
+                    THEN NULL ELSE disabled_reason END,
+                enabled = $6, updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            ENDPOINT_COLUMNS
+        ))
+        .bind(endpoint.id)
+        .bind(&endpoint.url)
+        .bind(&endpoint.description)
+        .bind(&endpoint.secret_encrypted)
+        .bind(&endpoint.events)
+        .bind(endpoint.enabled)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(Into::into)
+            .ok_or(AppError::NotFound("Webhook endpoint not found".into()))
+    }
+
+    async fn delete_endpoint(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_success(&self, id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE webhook_endpoints SET consecutive_failures = 0 \
+             WHERE id = $1 AND consecutive_failures <> 0",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: Uuid,
+        disable_after: u32,
+    ) -> Result<Option<WebhookEndpointEntity>, AppError> {
+        // The reason is built in Rust so both backends word it the same way
+        let row: Option<EndpointRow> = sqlx::query_as(&format!(
+            r#"
+            UPDATE webhook_endpoints
+            SET consecutive_failures = consecutive_failures + 1,
+                enabled = enabled AND consecutive_failures + 1 < $2,
+                disabled_reason = CASE WHEN enabled AND consecutive_failures + 1 >= $2
+                    THEN $3 ELSE disabled_reason END,
+                updated_at = CASE WHEN enabled AND consecutive_failures + 1 >= $2
+                    THEN NOW() ELSE updated_at END
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            ENDPOINT_COLUMNS
+        ))
+        .bind(id)
+        .bind(disable_after as i32)
+        .bind(auto_disable_reason(disable_after))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn create_delivery(
+        &self,
+        delivery: WebhookDeliveryEntity,
+    ) -> Result<WebhookDeliveryEntity, AppError> {
+        let row: DeliveryRow = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO webhook_deliveries (
+                id, endpoint_id, event_type, payload, status, attempts, response_status,
+                last_error, replay_of, created_at, last_attempt_at, delivered_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING {}
+            "#,
+            DELIVERY_COLUMNS
+        ))
+        .bind(delivery.id)
+        .bind(delivery.endpoint_id)
+        .bind(&delivery.event_type)
+        .bind(&delivery.payload)
+        .bind(delivery.status.as_str())
+        .bind(delivery.attempts as i32)
+        .bind(delivery.response_status.map(|s| s as i32))
+        .bind(&delivery.last_error)
+        .bind(delivery.replay_of)
+        .bind(delivery.created_at)
+        .bind(delivery.last_attempt_at)
+        .bind(delivery.delivered_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.try_into()
+    }
+
+    async fn find_delivery(&self, id: Uuid) -> Result<Option<WebhookDeliveryEntity>, AppError> {
+        let row: Option<DeliveryRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM webhook_deliveries WHERE id = $1",
+            DELIVERY_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        row.map(TryInto::try_into).transpose()
+    }
+
+    async fn update_delivery(&self, delivery: &WebhookDeliveryEntity) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2, attempts = $3, response_status = $4, last_error = $5,
+                last_attempt_at = $6, delivered_at = $7
+            WHERE id = $1
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(delivery.status.as_str())
+        .bind(delivery.attempts as i32)
+        .bind(delivery.response_status.map(|s| s as i32))
+        .bind(&delivery.last_error)
+        .bind(delivery.last_attempt_at)
+        .bind(delivery.delivered_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Webhook delivery not found".into()));
+        }
+        Ok(())
+    }
+
+    async fn list_deliveries(
+        &self,
+        endpoint_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<WebhookDeliveryEntity>, AppError> {
+        let rows: Vec<DeliveryRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM webhook_deliveries
+            WHERE endpoint_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            DELIVERY_COLUMNS
+        ))
+        .bind(endpoint_id)
+        .bind(cap_limit(limit) as i64)
+        .bind(cap_offset(offset) as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    async fn count_deliveries(&self, endpoint_id: Uuid) -> Result<u64, AppError> {
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM webhook_deliveries WHERE endpoint_id = $1")
+                .bind(endpoint_id)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(count.0.max(0) as u64)
+    }
+}
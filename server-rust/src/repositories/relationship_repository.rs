@@ -0,0 +1,705 @@
+//! Relationship tuple repository for resource-level (Zanzibar-style) authorization
+//!
+//! A tuple states that a subject has a relation to an object, e.g.
+//! `document:readme#editor@user:42` or, with a userset subject,
+//! `document:readme#viewer@folder:docs#viewer`. A per-org schema defines
+//! which relations exist and how they are computed from other relations.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::pagination::{cap_limit, cap_offset};
+
+/// Subject type used for end users (`user:<uuid>`)
+pub const USER_SUBJECT_TYPE: &str = "user";
+
+/// Maximum length of namespace and relation names
+const MAX_NAME_LEN: usize = 64;
+
+/// Maximum length of object and subject IDs
+const MAX_ID_LEN: usize = 255;
+
+/// Validate a namespace or relation name (`[a-z][a-z0-9_]*`)
+pub fn validate_relation_name(kind: &str, name: &str) -> Result<(), AppError> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "Invalid {} '{}': use lowercase letters, digits and underscores",
+            kind, name
+        )))
+    }
+}
+
+fn validate_object_id(id: &str) -> Result<(), AppError> {
+    let valid = !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '@' | '|' | '/'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!("Invalid object id '{}'", id)))
+    }
+}
+
+/// Reference to an object, written as `type:id`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectRef {
+    pub object_type: String,
+    pub object_id: String,
+}
+
+impl ObjectRef {
+    pub fn new(object_type: impl Into<String>, object_id: impl Into<String>) -> Self {
+        Self {
+            object_type: object_type.into(),
+            object_id: object_id.into(),
+        }
+    }
+
+    /// Parse `type:id`
+    pub fn parse(s: &str) -> Result<Self, AppError> {
+        let (object_type, object_id) = s.split_once(':').ok_or_else(|| {
+            AppError::Validation(format!("Invalid object '{}', expected type:id", s))
+        })?;
+        validate_relation_name("object type", object_type)?;
+        validate_object_id(object_id)?;
+        Ok(Self::new(object_type, object_id))
+    }
+}
+
+impl fmt::Display for ObjectRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.object_type, self.object_id)
+    }
+}
+
+/// Subject of a tuple: a concrete object (`user:42`) or a userset (`group:eng#member`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubjectRef {
+    pub subject_type: String,
+    pub subject_id: String,
+    /// Relation on the subject object when the subject is a userset
+    pub relation: Option<String>,
+}
+
+impl SubjectRef {
+    pub fn user(user_id: Uuid) -> Self {
+        Self {
+            subject_type: USER_SUBJECT_TYPE.to_string(),
+            subject_id: user_id.to_string(),
+            relation: None,
+        }
+    }
+
+    /// Parse `type:id` or `type:id#relation`
+    pub fn parse(s: &str) -> Result<Self, AppError> {
+        let (object, relation) = match s.split_once('#') {
+            Some((object, relation)) => {
+                validate_relation_name("relation", relation)?;
+                (object, Some(relation.to_string()))
+            }
+            None => (s, None),
+        };
+        let object = ObjectRef::parse(object)?;
+        Ok(Self {
+            subject_type: object.object_type,
+            subject_id: object.object_id,
+            relation,
+        })
+    }
+
+    /// The object part of the subject, ignoring any userset relation
+    pub fn object(&self) -> ObjectRef {
+        ObjectRef::new(&self.subject_type, &self.subject_id)
+    }
+}
+
+impl fmt::Display for SubjectRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.relation {
+            Some(relation) => write!(f, "{}:{}#{}", self.subject_type, self.subject_id, relation),
+            None => write!(f, "{}:{}", self.subject_type, self.subject_id),
+        }
+    }
+}
+
+/// Identity of a tuple: `object#relation@subject`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TupleKey {
+    pub object: ObjectRef,
+    pub relation: String,
+    pub subject: SubjectRef,
+}
+
+impl TupleKey {
+    pub fn new(object: ObjectRef, relation: impl Into<String>, subject: SubjectRef) -> Self {
+        Self {
+            object,
+            relation: relation.into(),
+            subject,
+        }
+    }
+}
+
+impl fmt::Display for TupleKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}@{}", self.object, self.relation, self.subject)
+    }
+}
+
+/// Stored relationship tuple
+#[derive(Debug, Clone)]
+pub struct RelationTuple {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub key: TupleKey,
+    /// User who wrote the tuple
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RelationTuple {
+    pub fn new(org_id: Uuid, key: TupleKey, created_by: Option<Uuid>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            org_id,
+            key,
+            created_by,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Filter for tuple lookups; `None` fields match anything
+#[derive(Debug, Clone, Default)]
+pub struct TupleFilter {
+    pub object_type: Option<String>,
+    pub object_id: Option<String>,
+    pub relation: Option<String>,
+    /// Exact subject match (a direct subject does not match a userset and vice versa)
+    pub subject: Option<SubjectRef>,
+}
+
+impl TupleFilter {
+    pub fn object_relation(object: &ObjectRef, relation: &str) -> Self {
+        Self {
+            object_type: Some(object.object_type.clone()),
+            object_id: Some(object.object_id.clone()),
+            relation: Some(relation.to_string()),
+            subject: None,
+        }
+    }
+
+    pub fn matches(&self, key: &TupleKey) -> bool {
+        self.object_type
+            .as_ref()
+            .map_or(true, |t| *t == key.object.object_type)
+            && self
+                .object_id
+                .as_ref()
+                .map_or(true, |id| *id == key.object.object_id)
+            && self.relation.as_ref().map_or(true, |r| *r == key.relation)
+            && self.subject.as_ref().map_or(true, |s| *s == key.subject)
+    }
+}
+
+/// How a relation's members are computed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UsersetRewrite {
+    /// Subjects written directly for this relation
+    This,
+    /// Everyone with another relation on the same object (e.g. editors are viewers)
+    ComputedUserset { relation: String },
+    /// Everyone with `computed_relation` on the objects related via `tupleset`
+    /// (e.g. viewers of the document's parent folder)
+    TupleToUserset {
+        tupleset: String,
+        computed_relation: String,
+    },
+}
+
+/// Definition of a single relation; an empty union means direct tuples only
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationDefinition {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub union: Vec<UsersetRewrite>,
+}
+
+impl RelationDefinition {
+    /// Effective rewrites (an empty definition is `this`)
+    pub fn rewrites(&self) -> Vec<UsersetRewrite> {
+        if self.union.is_empty() {
+            vec![UsersetRewrite::This]
+        } else {
+            self.union.clone()
+        }
+    }
+
+    /// Whether tuples may be written directly for this relation
+    pub fn allows_direct(&self) -> bool {
+        self.union.is_empty() || self.union.contains(&UsersetRewrite::This)
+    }
+}
+
+/// Relations available on one object type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceDefinition {
+    #[serde(default)]
+    pub relations: BTreeMap<String, RelationDefinition>,
+}
+
+/// Per-organization relationship schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipSchema {
+    pub org_id: Uuid,
+    /// Object type name -> namespace definition
+    pub namespaces: BTreeMap<String, NamespaceDefinition>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RelationshipSchema {
+    pub fn new(org_id: Uuid, namespaces: BTreeMap<String, NamespaceDefinition>) -> Self {
+        Self {
+            org_id,
+            namespaces,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Look up a relation definition
+    pub fn relation(&self, object_type: &str, relation: &str) -> Option<&RelationDefinition> {
+        self.namespaces
+            .get(object_type)
+            .and_then(|ns| ns.relations.get(relation))
+    }
+
+    /// Validate names and that every rewrite references a defined relation
+    pub fn validate(&self) -> Result<(), AppError> {
+        for (ns_name, ns) in &self.namespaces {
+            validate_relation_name("namespace", ns_name)?;
+            if ns_name == USER_SUBJECT_TYPE {
+                return Err(AppError::Validation(format!(
+                    "Namespace '{}' is reserved",
+                    USER_SUBJECT_TYPE
+                )));
+            }
+            for (rel_name, rel) in &ns.relations {
+                validate_relation_name("relation", rel_name)?;
+                for rewrite in &rel.union {
+                    match rewrite {
+                        UsersetRewrite::This => {}
+                        UsersetRewrite::ComputedUserset { relation } => {
+                            if !ns.relations.contains_key(relation) {
+                                return Err(AppError::Validation(format!(
+                                    "{}#{} references undefined relation '{}'",
+                                    ns_name, rel_name, relation
+                                )));
+                            }
+                        }
+                        UsersetRewrite::TupleToUserset {
+                            tupleset,
+                            computed_relation,
+                        } => {
+                            if !ns.relations.contains_key(tupleset) {
+                                return Err(AppError::Validation(format!(
+                                    "{}#{} references undefined tupleset '{}'",
+                                    ns_name, rel_name, tupleset
+                                )));
+                            }
+                            validate_relation_name("relation", computed_relation)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate a tuple against the schema before writing it
+    pub fn validate_tuple(&self, key: &TupleKey) -> Result<(), AppError> {
+        let definition = self
+            .relation(&key.object.object_type, &key.relation)
+            .ok_or_else(|| {
+                AppError::Validation(format!(
+                    "Relation '{}#{}' is not defined in the schema",
+                    key.object.object_type, key.relation
+                ))
+            })?;
+        if !definition.allows_direct() {
+            return Err(AppError::Validation(format!(
+                "Relation '{}#{}' is computed and cannot be written directly",
+                key.object.object_type, key.relation
+            )));
+        }
+        if key.subject.subject_type != USER_SUBJECT_TYPE
+            && !self.namespaces.contains_key(&key.subject.subject_type)
+        {
+            return Err(AppError::Validation(format!(
+                "Subject type '{}' is not defined in the schema",
+                key.subject.subject_type
+            )));
+        }
+        if let Some(relation) = &key.subject.relation {
+            if self.relation(&key.subject.subject_type, relation).is_none() {
+                return Err(AppError::Validation(format!(
+                    "Subject relation '{}#{}' is not defined in the schema",
+                    key.subject.subject_type, relation
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Relationship repository trait
+#[async_trait]
+pub trait RelationshipRepository: Send + Sync {
+    /// Write a tuple (idempotent: returns the existing tuple if already present)
+    async fn write(&self, tuple: RelationTuple) -> Result<RelationTuple, AppError>;
+
+    /// Delete a tuple, returning whether it existed
+    async fn delete(&self, org_id: Uuid, key: &TupleKey)
+        -> Result<Option<RelationTuple>, AppError>;
+
+    /// Find all tuples matching the filter (unpaginated, for graph traversal)
+    async fn find(
+        &self,
+        org_id: Uuid,
+        filter: &TupleFilter,
+    ) -> Result<Vec<RelationTuple>, AppError>;
+
+    /// Find tuples matching the filter with pagination
+    async fn find_paged(
+        &self,
+        org_id: Uuid,
+        filter: &TupleFilter,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<RelationTuple>, AppError>;
+
+    /// Count tuples matching the filter
+    async fn count(&self, org_id: Uuid, filter: &TupleFilter) -> Result<u64, AppError>;
+
+    /// Delete all tuples and the schema for an organization
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
+
+    /// Get the relationship schema for an organization
+    async fn get_schema(&self, org_id: Uuid) -> Result<Option<RelationshipSchema>, AppError>;
+
+    /// Create or replace the relationship schema for an organization
+    async fn put_schema(&self, schema: RelationshipSchema) -> Result<RelationshipSchema, AppError>;
+}
+
+/// In-memory relationship repository for development/testing
+pub struct InMemoryRelationshipRepository {
+    tuples: RwLock<HashMap<(Uuid, TupleKey), RelationTuple>>,
+    schemas: RwLock<HashMap<Uuid, RelationshipSchema>>,
+}
+
+impl InMemoryRelationshipRepository {
+    pub fn new() -> Self {
+        Self {
+            tuples: RwLock::new(HashMap::new()),
+            schemas: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn matching(&self, org_id: Uuid, filter: &TupleFilter) -> Vec<RelationTuple> {
+        let tuples = self.tuples.read().await;
+        let mut results: Vec<_> = tuples
+            .values()
+            .filter(|t| t.org_id == org_id && filter.matches(&t.key))
+            .cloned()
+            .collect();
+        results.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        results
+    }
+}
+
+impl Default for InMemoryRelationshipRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RelationshipRepository for InMemoryRelationshipRepository {
+    async fn write(&self, tuple: RelationTuple) -> Result<RelationTuple, AppError> {
+        let mut tuples = self.tuples.write().await;
+        let entry = tuples
+            .entry((tuple.org_id, tuple.key.clone()))
+            .or_insert(tuple);
+        Ok(entry.clone())
+    }
+
+    async fn delete(
+        &self,
+        org_id: Uuid,
+        key: &TupleKey,
+    ) -> Result<Option<RelationTuple>, AppError> {
+        let mut tuples = self.tuples.write().await;
+        Ok(tuples.remove(&(org_id, key.clone())))
+    }
+
+    async fn find(
+        &self,
+        org_id: Uuid,
+        filter: &TupleFilter,
+    ) -> Result<Vec<RelationTuple>, AppError> {
+        Ok(self.matching(org_id, filter).await)
+    }
+
+    async fn find_paged(
+        &self,
+        org_id: Uuid,
+        filter: &TupleFilter,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<RelationTuple>, AppError> {
+        let results = self.matching(org_id, filter).await;
+        Ok(results
+            .into_iter()
+            .skip(cap_offset(offset) as usize)
+            .take(cap_limit(limit) as usize)
+            .collect())
+    }
+
+    async fn count(&self, org_id: Uuid, filter: &TupleFilter) -> Result<u64, AppError> {
+        let tuples = self.tuples.read().await;
+        Ok(tuples
+            .values()
+            .filter(|t| t.org_id == org_id && filter.matches(&t.key))
+            .count() as u64)
+    }
+
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let mut tuples = self.tuples.write().await;
+        let before = tuples.len();
+        tuples.retain(|(tuple_org, _), _| *tuple_org != org_id);
+        self.schemas.write().await.remove(&org_id);
+        Ok((before - tuples.len()) as u64)
+    }
+
+    async fn get_schema(&self, org_id: Uuid) -> Result<Option<RelationshipSchema>, AppError> {
+        Ok(self.schemas.read().await.get(&org_id).cloned())
+    }
+
+    async fn put_schema(&self, schema: RelationshipSchema) -> Result<RelationshipSchema, AppError> {
+        let mut schemas = self.schemas.write().await;
+        let mut stored = schema;
+        stored.updated_at = Utc::now();
+        schemas.insert(stored.org_id, stored.clone());
+        Ok(stored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(object: &str, relation: &str, subject: &str) -> TupleKey {
+        TupleKey::new(
+            ObjectRef::parse(object).unwrap(),
+            relation,
+            SubjectRef::parse(subject).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_parse_refs() {
+        let object = ObjectRef::parse("document:readme.md").unwrap();
+        assert_eq!(object.object_type, "document");
+        assert_eq!(object.object_id, "readme.md");
+
+        let subject = SubjectRef::parse("folder:docs#viewer").unwrap();
+        assert_eq!(subject.relation.as_deref(), Some("viewer"));
+        assert_eq!(subject.to_string(), "folder:docs#viewer");
+
+        assert!(ObjectRef::parse("document").is_err());
+        assert!(ObjectRef::parse("Document:1").is_err());
+        assert!(ObjectRef::parse("document:a b").is_err());
+        assert!(SubjectRef::parse("folder:docs#").is_err());
+    }
+
+    #[test]
+    fn test_tuple_key_display() {
+        let k = key("document:1", "viewer", "user:42");
+        assert_eq!(k.to_string(), "document:1#viewer@user:42");
+    }
+
+    #[test]
+    fn test_schema_validation() {
+        let json = r#"{
+            "folder": {"relations": {"viewer": {}}},
+            "document": {"relations": {
+                "parent": {},
+                "editor": {},
+                "viewer": {"union": [
+                    {"type": "this"},
+                    {"type": "computed_userset", "relation": "editor"},
+                    {"type": "tuple_to_userset", "tupleset": "parent", "computed_relation": "viewer"}
+                ]}
+            }}
+        }"#;
+        let namespaces: BTreeMap<String, NamespaceDefinition> = serde_json::from_str(json).unwrap();
+        let schema = RelationshipSchema::new(Uuid::new_v4(), namespaces);
+        assert!(schema.validate().is_ok());
+
+        assert!(schema
+            .validate_tuple(&key("document:1", "viewer", "user:42"))
+            .is_ok());
+        assert!(schema
+            .validate_tuple(&key("document:1", "parent", "folder:f"))
+            .is_ok());
+        assert!(schema
+            .validate_tuple(&key("document:1", "owner", "user:42"))
+            .is_err());
+        assert!(schema
+            .validate_tuple(&key("document:1", "viewer", "team:t"))
+            .is_err());
+        assert!(schema
+            .validate_tuple(&key("document:1", "viewer", "folder:f#owner"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_schema_rejects_undefined_rewrite() {
+        let mut relations = BTreeMap::new();
+        relations.insert(
+            "viewer".to_string(),
+            RelationDefinition {
+                union: vec![UsersetRewrite::ComputedUserset {
+                    relation: "editor".to_string(),
+                }],
+            },
+        );
+        let mut namespaces = BTreeMap::new();
+        namespaces.insert("document".to_string(), NamespaceDefinition { relations });
+        let schema = RelationshipSchema::new(Uuid::new_v4(), namespaces);
+        assert!(schema.validate().is_err());
+    }
+
+    #[test]
+    fn test_computed_relation_cannot_be_written() {
+        let mut relations = BTreeMap::new();
+        relations.insert("editor".to_string(), RelationDefinition::default());
+        relations.insert(
+            "viewer".to_string(),
+            RelationDefinition {
+                union: vec![UsersetRewrite::ComputedUserset {
+                    relation: "editor".to_string(),
+                }],
+            },
+        );
+        let mut namespaces = BTreeMap::new();
+        namespaces.insert("document".to_string(), NamespaceDefinition { relations });
+        let schema = RelationshipSchema::new(Uuid::new_v4(), namespaces);
+        assert!(schema
+            .validate_tuple(&key("document:1", "viewer", "user:1"))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_is_idempotent() {
+        let repo = InMemoryRelationshipRepository::new();
+        let org_id = Uuid::new_v4();
+
+        let first = repo
+            .write(RelationTuple::new(
+                org_id,
+                key("document:1", "viewer", "user:1"),
+                None,
+            ))
+            .await
+            .unwrap();
+        let second = repo
+            .write(RelationTuple::new(
+                org_id,
+                key("document:1", "viewer", "user:1"),
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(first.id, second.id);
+        assert_eq!(
+            repo.count(org_id, &TupleFilter::default()).await.unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_and_delete() {
+        let repo = InMemoryRelationshipRepository::new();
+        let org_id = Uuid::new_v4();
+        let other_org = Uuid::new_v4();
+
+        repo.write(RelationTuple::new(
+            org_id,
+            key("document:1", "viewer", "user:1"),
+            None,
+        ))
+        .await
+        .unwrap();
+        repo.write(RelationTuple::new(
+            org_id,
+            key("document:1", "editor", "user:2"),
+            None,
+        ))
+        .await
+        .unwrap();
+        repo.write(RelationTuple::new(
+            other_org,
+            key("document:1", "viewer", "user:1"),
+            None,
+        ))
+        .await
+        .unwrap();
+
+        let object = ObjectRef::parse("document:1").unwrap();
+        let viewers = repo
+            .find(org_id, &TupleFilter::object_relation(&object, "viewer"))
+            .await
+            .unwrap();
+        assert_eq!(viewers.len(), 1);
+
+        let by_subject = TupleFilter {
+            subject: Some(SubjectRef::parse("user:1").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(repo.count(org_id, &by_subject).await.unwrap(), 1);
+
+        let deleted = repo
+            .delete(org_id, &key("document:1", "viewer", "user:1"))
+            .await
+            .unwrap();
+        assert!(deleted.is_some());
+        assert_eq!(
+            repo.count(org_id, &TupleFilter::default()).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            repo.count(other_org, &TupleFilter::default())
+                .await
+                .unwrap(),
+            1
+        );
+
+        assert_eq!(repo.delete_by_org(org_id).await.unwrap(), 1);
+    }
+}
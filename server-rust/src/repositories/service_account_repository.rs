@@ -0,0 +1,335 @@
+//! Service account repository trait and implementations
+//!
+//! A service account is an org-owned, non-human principal. Each one is backed
+//! by a credential-less row in `users` sharing the same ID, so memberships,
+//! API keys and sessions work unchanged; this table holds what makes it a
+//! service account (owning org, client credentials, optional custom role).
+//! The org role lives in the service account's membership.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use subtle::ConstantTimeEq;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::pagination::{cap_limit, cap_offset};
+
+/// Prefix for service account client IDs
+pub const CLIENT_ID_PREFIX: &str = "sa_";
+
+/// Prefix for service account client secrets
+pub const CLIENT_SECRET_PREFIX: &str = "sas_";
+
+/// Service account entity for storage
+#[derive(Debug, Clone)]
+pub struct ServiceAccountEntity {
+    /// Same as the backing user ID
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    /// Custom role whose permissions are granted on top of the membership role
+    pub custom_role_id: Option<Uuid>,
+    /// Public identifier used in the client-credentials exchange
+    pub client_id: String,
+    /// SHA256 hex of the client secret
+    pub client_secret_hash: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ServiceAccountEntity {
+    /// Create a new service account with freshly generated client credentials.
+    ///
+    /// Returns the entity and the raw client secret (only shown once).
+    pub fn new(id: Uuid, org_id: Uuid, name: String, created_by: Option<Uuid>) -> (Self, String) {
+        let now = Utc::now();
+        let secret = generate_client_secret();
+        let entity = Self {
+            id,
+            org_id,
+            name,
+            description: None,
+            custom_role_id: None,
+            client_id: generate_client_id(),
+            client_secret_hash: hash_client_secret(&secret),
+            created_by,
+            created_at: now,
+            updated_at: now,
+            last_used_at: None,
+        };
+        (entity, secret)
+    }
+
+    /// Check a raw client secret against the stored hash in constant time
+    pub fn verify_secret(&self, secret: &str) -> bool {
+        let hash = hash_client_secret(secret);
+        hash.as_bytes()
+            .ct_eq(self.client_secret_hash.as_bytes())
+            .into()
+    }
+}
+
+fn random_alphanumeric(len: usize) -> String {
+    // SEC-08: Use OsRng for cryptographic random generation
+    OsRng
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// Generate a client ID (prefix + 24 alphanumeric chars)
+pub fn generate_client_id() -> String {
+    format!("{}{}", CLIENT_ID_PREFIX, random_alphanumeric(24))
+}
+
+/// Generate a client secret (prefix + 43 alphanumeric chars)
+pub fn generate_client_secret() -> String {
+    format!("{}{}", CLIENT_SECRET_PREFIX, random_alphanumeric(43))
+}
+
+/// Hash a client secret using SHA256
+pub fn hash_client_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+/// Service account repository trait
+#[async_trait]
+pub trait ServiceAccountRepository: Send + Sync {
+    /// Create a new service account
+    async fn create(&self, account: ServiceAccountEntity)
+        -> Result<ServiceAccountEntity, AppError>;
+
+    /// Find a service account by ID (same as its user ID)
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ServiceAccountEntity>, AppError>;
+
+    /// Find a service account by client ID
+    async fn find_by_client_id(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<ServiceAccountEntity>, AppError>;
+
+    /// Find service accounts for an organization (oldest first, paged)
+    async fn find_by_org_paged(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<ServiceAccountEntity>, AppError>;
+
+    /// Find all service accounts for an organization
+    async fn find_by_org(&self, org_id: Uuid) -> Result<Vec<ServiceAccountEntity>, AppError>;
+
+    /// Count service accounts for an organization
+    async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
+
+    /// Update name, description and custom role
+    async fn update(&self, account: ServiceAccountEntity)
+        -> Result<ServiceAccountEntity, AppError>;
+
+    /// Replace the client secret hash
+    async fn update_secret_hash(&self, id: Uuid, secret_hash: &str) -> Result<(), AppError>;
+
+    /// Update last_used_at timestamp
+    async fn update_last_used(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Delete a service account. Returns false if it didn't exist.
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError>;
+
+    /// Delete all service accounts for an organization
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError>;
+}
+
+/// In-memory service account repository for development/testing
+pub struct InMemoryServiceAccountRepository {
+    accounts: RwLock<HashMap<Uuid, ServiceAccountEntity>>,
+}
+
+impl InMemoryServiceAccountRepository {
+    pub fn new() -> Self {
+        Self {
+            accounts: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryServiceAccountRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ServiceAccountRepository for InMemoryServiceAccountRepository {
+    async fn create(
+        &self,
+        account: ServiceAccountEntity,
+    ) -> Result<ServiceAccountEntity, AppError> {
+        let mut accounts = self.accounts.write().await;
+        if accounts
+            .values()
+            .any(|a| a.org_id == account.org_id && a.name == account.name)
+        {
+            return Err(AppError::Validation(
+                "A service account with this name already exists".into(),
+            ));
+        }
+        accounts.insert(account.id, account.clone());
+        Ok(account)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ServiceAccountEntity>, AppError> {
+        Ok(self.accounts.read().await.get(&id).cloned())
+    }
+
+    async fn find_by_client_id(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<ServiceAccountEntity>, AppError> {
+        let accounts = self.accounts.read().await;
+        Ok(accounts
+            .values()
+            .find(|a| a.client_id == client_id)
+            .cloned())
+    }
+
+    async fn find_by_org_paged(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<ServiceAccountEntity>, AppError> {
+        let mut result = self.find_by_org(org_id).await?;
+        result.sort_by_key(|a| a.created_at);
+        Ok(result
+            .into_iter()
+            .skip(cap_offset(offset) as usize)
+            .take(cap_limit(limit) as usize)
+            .collect())
+    }
+
+    async fn find_by_org(&self, org_id: Uuid) -> Result<Vec<ServiceAccountEntity>, AppError> {
+        let accounts = self.accounts.read().await;
+        Ok(accounts
+            .values()
+            .filter(|a| a.org_id == org_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn count_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let accounts = self.accounts.read().await;
+        Ok(accounts.values().filter(|a| a.org_id == org_id).count() as u64)
+    }
+
+    async fn update(
+        &self,
+        account: ServiceAccountEntity,
+    ) -> Result<ServiceAccountEntity, AppError> {
+        let mut accounts = self.accounts.write().await;
+        if accounts
+            .values()
+            .any(|a| a.id != account.id && a.org_id == account.org_id && a.name == account.name)
+        {
+            return Err(AppError::Validation(
+                "A service account with this name already exists".into(),
+            ));
+        }
+        let existing = accounts
+            .get_mut(&account.id)
+            .ok_or(AppError::NotFound("Service account not found".into()))?;
+        existing.name = account.name;
+        existing.description = account.description;
+        existing.custom_role_id = account.custom_role_id;
+        existing.updated_at = Utc::now();
+        Ok(existing.clone())
+    }
+
+    async fn update_secret_hash(&self, id: Uuid, secret_hash: &str) -> Result<(), AppError> {
+        let mut accounts = self.accounts.write().await;
+        let account = accounts
+            .get_mut(&id)
+            .ok_or(AppError::NotFound("Service account not found".into()))?;
+        account.client_secret_hash = secret_hash.to_string();
+        account.updated_at = Utc::now();
+        Ok(())
+    }
+
+    async fn update_last_used(&self, id: Uuid) -> Result<(), AppError> {
+        if let Some(account) = self.accounts.write().await.get_mut(&id) {
+            account.last_used_at = Some(Utc::now());
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError> {
+        Ok(self.accounts.write().await.remove(&id).is_some())
+    }
+
+    async fn delete_by_org(&self, org_id: Uuid) -> Result<u64, AppError> {
+        let mut accounts = self.accounts.write().await;
+        let before = accounts.len();
+        accounts.retain(|_, a| a.org_id != org_id);
+        Ok((before - accounts.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(org_id: Uuid, name: &str) -> (ServiceAccountEntity, String) {
+        ServiceAccountEntity::new(Uuid::new_v4(), org_id, name.to_string(), None)
+    }
+
+    #[test]
+    fn test_credentials_format_and_verify() {
+        let (sa, secret) = account(Uuid::new_v4(), "ci");
+        assert!(sa.client_id.starts_with(CLIENT_ID_PREFIX));
+        assert!(secret.starts_with(CLIENT_SECRET_PREFIX));
+        assert_eq!(sa.client_secret_hash.len(), 64);
+        assert!(sa.verify_secret(&secret));
+        assert!(!sa.verify_secret("sas_wrong"));
+    }
+
+    #[tokio::test]
+    async fn test_create_find_and_unique_name() {
+        let repo = InMemoryServiceAccountRepository::new();
+        let org_id = Uuid::new_v4();
+        let (sa, _) = account(org_id, "ci");
+        repo.create(sa.clone()).await.unwrap();
+
+        let found = repo.find_by_client_id(&sa.client_id).await.unwrap();
+        assert_eq!(found.unwrap().id, sa.id);
+        assert!(repo.create(account(org_id, "ci").0).await.is_err());
+        // Same name in another org is fine
+        repo.create(account(Uuid::new_v4(), "ci").0).await.unwrap();
+        assert_eq!(repo.count_by_org(org_id).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_secret_and_delete_by_org() {
+        let repo = InMemoryServiceAccountRepository::new();
+        let org_id = Uuid::new_v4();
+        let (sa, old_secret) = account(org_id, "ci");
+        repo.create(sa.clone()).await.unwrap();
+
+        let new_secret = generate_client_secret();
+        repo.update_secret_hash(sa.id, &hash_client_secret(&new_secret))
+            .await
+            .unwrap();
+        let updated = repo.find_by_id(sa.id).await.unwrap().unwrap();
+        assert!(!updated.verify_secret(&old_secret));
+        assert!(updated.verify_secret(&new_secret));
+
+        assert_eq!(repo.delete_by_org(org_id).await.unwrap(), 1);
+        assert!(repo.find_by_id(sa.id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,442 @@
+//! Stripe top-up repository
+//!
+//! A top-up is a card payment through Stripe Checkout or a PaymentIntent
+//! that credits a user's or organization's balance once Stripe reports it
+//! paid. Refunds and disputes reported later take the credits back; the
+//! top-up keeps running totals so repeated webhook deliveries are no-ops.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Reference type of the transaction that credits a top-up
+pub const STRIPE_TOPUP_REFERENCE_TYPE: &str = "stripe_topup";
+
+/// Reference type of transactions that take back refunded credits
+pub const STRIPE_REFUND_REFERENCE_TYPE: &str = "stripe_refund";
+
+/// Reference type of transactions that take back or reinstate disputed credits
+pub const STRIPE_DISPUTE_REFERENCE_TYPE: &str = "stripe_dispute";
+
+/// How the client collects the payment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StripeTopupFlow {
+    /// Redirect to a Stripe-hosted Checkout page
+    #[default]
+    Checkout,
+    /// Confirm a PaymentIntent client-side (Stripe Elements, mobile SDKs)
+    PaymentIntent,
+}
+
+impl StripeTopupFlow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Checkout => "checkout",
+            Self::PaymentIntent => "payment_intent",
+        }
+    }
+}
+
+impl std::str::FromStr for StripeTopupFlow {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "checkout" => Ok(Self::Checkout),
+            "payment_intent" => Ok(Self::PaymentIntent),
+            _ => Err(AppError::Internal(anyhow::anyhow!(
+                "Unknown Stripe top-up flow: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Payment state of a top-up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StripeTopupStatus {
+    /// Waiting for the payment
+    Pending,
+    /// Paid and credited
+    Succeeded,
+    /// Checkout expired or the payment failed
+    Failed,
+}
+
+impl StripeTopupStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for StripeTopupStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            _ => Err(AppError::Internal(anyhow::anyhow!(
+                "Unknown Stripe top-up status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Stripe top-up entity for storage
+#[derive(Debug, Clone)]
+pub struct StripeTopupEntity {
+    pub id: Uuid,
+    /// User who paid; the credited account unless `org_id` is set
+    pub user_id: Uuid,
+    /// Organization whose balance is credited
+    pub org_id: Option<Uuid>,
+    pub flow: StripeTopupFlow,
+    /// Amount charged in the payment currency's minor unit (cents)
+    pub amount_cents: i64,
+    /// Payment currency (ISO code, lowercase)
+    pub currency: String,
+    /// Credits added once paid (lamports of `credit_currency`)
+    pub credit_amount: i64,
+    pub credit_currency: String,
+    pub status: StripeTopupStatus,
+    pub checkout_session_id: Option<String>,
+    pub payment_intent_id: Option<String>,
+    /// Transaction that credited the top-up
+    pub transaction_id: Option<Uuid>,
+    /// Cumulative amount refunded by Stripe (cents)
+    pub refunded_cents: i64,
+    pub dispute_id: Option<String>,
+    /// Latest Stripe dispute status (e.g. `needs_response`, `won`, `lost`)
+    pub dispute_status: Option<String>,
+    /// Amount under dispute (cents)
+    pub disputed_cents: i64,
+    /// Credits taken back for the dispute
+    pub disputed_amount: i64,
+    /// Credits currently taken back by refunds and disputes
+    pub reversed_amount: i64,
+    /// Credits that could not be taken back because they were already spent
+    pub unrecovered_amount: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl StripeTopupEntity {
+    /// A pending top-up of `amount_cents`, crediting `credit_amount`
+    pub fn new(
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        flow: StripeTopupFlow,
+        amount_cents: i64,
+        currency: &str,
+        credit_amount: i64,
+        credit_currency: &str,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            org_id,
+            flow,
+            amount_cents,
+            currency: currency.to_lowercase(),
+            credit_amount,
+            credit_currency: credit_currency.to_uppercase(),
+            status: StripeTopupStatus::Pending,
+            checkout_session_id: None,
+            payment_intent_id: None,
+            transaction_id: None,
+            refunded_cents: 0,
+            dispute_id: None,
+            dispute_status: None,
+            disputed_cents: 0,
+            disputed_amount: 0,
+            reversed_amount: 0,
+            unrecovered_amount: 0,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        }
+    }
+
+    /// Credits corresponding to `cents` of this top-up's payment
+    pub fn credits_for(&self, cents: i64) -> i64 {
+        if self.amount_cents <= 0 {
+            return 0;
+        }
+        let cents = cents.clamp(0, self.amount_cents) as i128;
+        (cents * self.credit_amount as i128 / self.amount_cents as i128) as i64
+    }
+}
+
+/// Lock on a top-up, released when dropped
+///
+/// Refund and dispute handling reads a top-up, reverses credits and writes
+/// the totals back; holding the lock keeps concurrent events for the same
+/// top-up from acting on the same totals.
+pub struct StripeTopupLock {
+    _guard: Box<dyn Send>,
+}
+
+impl StripeTopupLock {
+    pub fn new(guard: impl Send + 'static) -> Self {
+        Self {
+            _guard: Box::new(guard),
+        }
+    }
+}
+
+/// Stripe top-up repository trait
+#[async_trait]
+pub trait StripeTopupRepository: Send + Sync {
+    async fn create(&self, topup: StripeTopupEntity) -> Result<StripeTopupEntity, AppError>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<StripeTopupEntity>, AppError>;
+
+    async fn find_by_checkout_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<StripeTopupEntity>, AppError>;
+
+    async fn find_by_payment_intent(
+        &self,
+        payment_intent_id: &str,
+    ) -> Result<Option<StripeTopupEntity>, AppError>;
+
+    /// Personal top-ups of a user, newest first
+    async fn list_for_user(
+        &self,
+        user_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<StripeTopupEntity>, AppError>;
+
+    /// Top-ups of an organization's balance, newest first
+    async fn list_for_org(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<StripeTopupEntity>, AppError>;
+
+    /// Save a top-up's Stripe IDs, status and refund/dispute totals
+    async fn update(&self, topup: StripeTopupEntity) -> Result<StripeTopupEntity, AppError>;
+
+    /// Wait for and take the lock on a top-up
+    async fn lock(&self, id: Uuid) -> Result<StripeTopupLock, AppError>;
+}
+
+/// In-memory Stripe top-up repository for development/testing
+pub struct InMemoryStripeTopupRepository {
+    topups: RwLock<HashMap<Uuid, StripeTopupEntity>>,
+    locks: Mutex<HashMap<Uuid, Arc<Mutex<()>>>>,
+}
+
+impl InMemoryStripeTopupRepository {
+    pub fn new() -> Self {
+        Self {
+            topups: RwLock::new(HashMap::new()),
+            locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn find_where(
+        &self,
+        filter: impl Fn(&StripeTopupEntity) -> bool,
+    ) -> Option<StripeTopupEntity> {
+        self.topups
+            .read()
+            .await
+            .values()
+            .find(|t| filter(t))
+            .cloned()
+    }
+
+    async fn list_where(
+        &self,
+        filter: impl Fn(&StripeTopupEntity) -> bool,
+        limit: u32,
+    ) -> Vec<StripeTopupEntity> {
+        let mut topups: Vec<_> = self
+            .topups
+            .read()
+            .await
+            .values()
+            .filter(|t| filter(t))
+            .cloned()
+            .collect();
+        topups.sort_by_key(|t| Reverse(t.created_at));
+        topups.truncate(limit as usize);
+        topups
+    }
+}
+
+impl Default for InMemoryStripeTopupRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl StripeTopupRepository for InMemoryStripeTopupRepository {
+    async fn create(&self, topup: StripeTopupEntity) -> Result<StripeTopupEntity, AppError> {
+        self.topups.write().await.insert(topup.id, topup.clone());
+        Ok(topup)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<StripeTopupEntity>, AppError> {
+        Ok(self.topups.read().await.get(&id).cloned())
+    }
+
+    async fn find_by_checkout_session(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<StripeTopupEntity>, AppError> {
+        Ok(self
+            .find_where(|t| t.checkout_session_id.as_deref() == Some(session_id))
+            .await)
+    }
+
+    async fn find_by_payment_intent(
+        &self,
+        payment_intent_id: &str,
+    ) -> Result<Option<StripeTopupEntity>, AppError> {
+        Ok(self
+            .find_where(|t| t.payment_intent_id.as_deref() == Some(payment_intent_id))
+            .await)
+    }
+
+    async fn list_for_user(
+        &self,
+        user_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<StripeTopupEntity>, AppError> {
+        Ok(self
+            .list_where(|t| t.user_id == user_id && t.org_id.is_none(), limit)
+            .await)
+    }
+
+    async fn list_for_org(
+        &self,
+        org_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<StripeTopupEntity>, AppError> {
+        Ok(self.list_where(|t| t.org_id == Some(org_id), limit).await)
+    }
+
+    async fn update(&self, mut topup: StripeTopupEntity) -> Result<StripeTopupEntity, AppError> {
+        let mut topups = self.topups.write().await;
+        if !topups.contains_key(&topup.id) {
+            return Err(AppError::NotFound("Top-up not found".into()));
+        }
+        topup.updated_at = Utc::now();
+        topups.insert(topup.id, topup.clone());
+        Ok(topup)
+    }
+
+    async fn lock(&self, id: Uuid) -> Result<StripeTopupLock, AppError> {
+        let lock = self.locks.lock().await.entry(id).or_default().clone();
+        Ok(StripeTopupLock::new(lock.lock_owned().await))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credits_for_is_proportional_and_clamped() {
+        let topup = StripeTopupEntity::new(
+            Uuid::new_v4(),
+            None,
+            StripeTopupFlow::Checkout,
+            2_000,
+            "USD",
+            20_000_000,
+            "usd",
+        );
+        assert_eq!(topup.currency, "usd");
+        assert_eq!(topup.credit_currency, "USD");
+        assert_eq!(topup.credits_for(500), 5_000_000);
+        assert_eq!(topup.credits_for(2_000), 20_000_000);
+        assert_eq!(topup.credits_for(5_000), 20_000_000);
+        assert_eq!(topup.credits_for(-1), 0);
+    }
+
+    #[tokio::test]
+    async fn test_find_by_stripe_ids_and_list() {
+        let repo = InMemoryStripeTopupRepository::new();
+        let user_id = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+
+        let mut personal = StripeTopupEntity::new(
+            user_id,
+            None,
+            StripeTopupFlow::Checkout,
+            1_000,
+            "usd",
+            10_000_000,
+            "USD",
+        );
+        personal.checkout_session_id = Some("cs_test_1".into());
+        let personal = repo.create(personal).await.unwrap();
+        let org = repo
+            .create(StripeTopupEntity::new(
+                user_id,
+                Some(org_id),
+                StripeTopupFlow::PaymentIntent,
+                1_000,
+                "usd",
+                10_000_000,
+                "USD",
+            ))
+            .await
+            .unwrap();
+
+        let found = repo.find_by_checkout_session("cs_test_1").await.unwrap();
+        assert_eq!(found.map(|t| t.id), Some(personal.id));
+        assert!(repo.find_by_payment_intent("pi_1").await.unwrap().is_none());
+
+        let mut paid = personal.clone();
+        paid.payment_intent_id = Some("pi_1".into());
+        repo.update(paid).await.unwrap();
+        let found = repo.find_by_payment_intent("pi_1").await.unwrap();
+        assert_eq!(found.map(|t| t.id), Some(personal.id));
+
+        let listed = repo.list_for_user(user_id, 10).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, personal.id);
+        let listed = repo.list_for_org(org_id, 10).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, org.id);
+    }
+
+    #[tokio::test]
+    async fn test_lock_waits_for_the_holder() {
+        let repo = InMemoryStripeTopupRepository::new();
+        let id = Uuid::new_v4();
+        let held = repo.lock(id).await.unwrap();
+
+        let wait = std::time::Duration::from_millis(20);
+        assert!(tokio::time::timeout(wait, repo.lock(id)).await.is_err());
+        assert!(tokio::time::timeout(wait, repo.lock(Uuid::new_v4()))
+            .await
+            .is_ok());
+
+        drop(held);
+        assert!(tokio::time::timeout(wait, repo.lock(id)).await.is_ok());
+    }
+}
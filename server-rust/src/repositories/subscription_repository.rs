@@ -0,0 +1,626 @@
+//! Subscription plan and subscription repository
+//!
+//! A plan is a recurring credit price (e.g. 1 SOL per month) with an
+//! optional free trial, a grace period for failed payments and the
+//! entitlements it grants. Users and organizations subscribe to plans; the
+//! subscription renewal worker debits each period's price from the payer's
+//! credits.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Months, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Reference type of transactions that pay for a subscription period
+pub const SUBSCRIPTION_REFERENCE_TYPE: &str = "subscription";
+
+/// Maximum number of entitlements a plan can grant
+pub const MAX_PLAN_ENTITLEMENTS: usize = 32;
+
+/// Billing interval unit of a plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanInterval {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl PlanInterval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+
+    /// `from` plus `count` intervals
+    ///
+    /// Months and years are calendar-based; a period starting on the 31st
+    /// ends on the last day of shorter months.
+    pub fn advance(&self, from: DateTime<Utc>, count: u32) -> DateTime<Utc> {
+        let advanced = match self {
+            Self::Day => from.checked_add_signed(Duration::days(count as i64)),
+            Self::Week => from.checked_add_signed(Duration::weeks(count as i64)),
+            Self::Month => from.checked_add_months(Months::new(count)),
+            Self::Year => from.checked_add_months(Months::new(count.saturating_mul(12))),
+        };
+        advanced.unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+impl std::str::FromStr for PlanInterval {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            "month" => Ok(Self::Month),
+            "year" => Ok(Self::Year),
+            _ => Err(AppError::Internal(anyhow::anyhow!(
+                "Unknown plan interval: {}",
+                s
+            ))),
+        }
+    }
+}
+
+fn is_valid_key(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 64
+        && value.starts_with(|c: char| c.is_ascii_lowercase())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Validate a plan name: 1-64 chars of `a-z`, `0-9`, `_`, `.` or `-`,
+/// starting with a letter
+pub fn validate_plan_name(name: &str) -> Result<(), AppError> {
+    if !is_valid_key(name) {
+        return Err(AppError::Validation(
+            "Plan name must be 1-64 characters of a-z, 0-9, '_', '.' or '-' and start with a letter"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate a plan's entitlements (same rules as plan names, no duplicates)
+pub fn validate_entitlements(entitlements: &[String]) -> Result<(), AppError> {
+    if entitlements.len() > MAX_PLAN_ENTITLEMENTS {
+        return Err(AppError::Validation(format!(
+            "A plan can grant at most {} entitlements",
+            MAX_PLAN_ENTITLEMENTS
+        )));
+    }
+    for (i, entitlement) in entitlements.iter().enumerate() {
+        if !is_valid_key(entitlement) {
+            return Err(AppError::Validation(format!(
+                "Invalid entitlement '{}': must be 1-64 characters of a-z, 0-9, '_', '.' or '-' and start with a letter",
+                entitlement
+            )));
+        }
+        if entitlements[..i].contains(entitlement) {
+            return Err(AppError::Validation(format!(
+                "Duplicate entitlement '{}'",
+                entitlement
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Subscription plan entity for storage
+#[derive(Debug, Clone)]
+pub struct SubscriptionPlanEntity {
+    pub id: Uuid,
+    /// Stable key (e.g. `pro_monthly`); unique
+    pub name: String,
+    pub display_name: String,
+    /// Price per billing period in lamports
+    pub price_lamports: i64,
+    /// Credit currency the plan is billed in
+    pub currency: String,
+    pub interval: PlanInterval,
+    /// Number of intervals per billing period (e.g. 3 months)
+    pub interval_count: i32,
+    /// Free days before the first charge; only for first-time subscribers
+    pub trial_days: i32,
+    /// Days a past-due subscription keeps its entitlements before it is canceled
+    pub grace_days: i32,
+    /// Entitlements granted while subscribed, checked via `/authorize`
+    pub entitlements: Vec<String>,
+    /// Inactive plans accept no new subscribers; existing ones keep renewing
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SubscriptionPlanEntity {
+    /// A plan billed every `interval`, without trial or grace period
+    pub fn new(
+        name: String,
+        display_name: String,
+        price_lamports: i64,
+        currency: String,
+        interval: PlanInterval,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            display_name,
+            price_lamports,
+            currency,
+            interval,
+            interval_count: 1,
+            trial_days: 0,
+            grace_days: 0,
+            entitlements: Vec::new(),
+            active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// End of a billing period starting at `start`
+    pub fn period_end(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        self.interval
+            .advance(start, self.interval_count.max(1) as u32)
+    }
+}
+
+/// Lifecycle state of a subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionStatus {
+    /// In the free trial; the first charge is due at `trial_end`
+    Trialing,
+    /// Paid through `current_period_end`
+    Active,
+    /// The renewal charge failed; retried until the grace period runs out
+    PastDue,
+    Canceled,
+}
+
+impl SubscriptionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Trialing => "trialing",
+            Self::Active => "active",
+            Self::PastDue => "past_due",
+            Self::Canceled => "canceled",
+        }
+    }
+}
+
+impl std::str::FromStr for SubscriptionStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "trialing" => Ok(Self::Trialing),
+            "active" => Ok(Self::Active),
+            "past_due" => Ok(Self::PastDue),
+            "canceled" => Ok(Self::Canceled),
+            _ => Err(AppError::Internal(anyhow::anyhow!(
+                "Unknown subscription status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// A user's or organization's subscription to a plan
+#[derive(Debug, Clone)]
+pub struct SubscriptionEntity {
+    pub id: Uuid,
+    pub plan_id: Uuid,
+    /// Subscriber; for org subscriptions, the member whose spend permission
+    /// is used for renewals
+    pub user_id: Uuid,
+    /// Organization whose balance pays; `None` bills the user's own balance
+    pub org_id: Option<Uuid>,
+    pub status: SubscriptionStatus,
+    pub current_period_start: DateTime<Utc>,
+    pub current_period_end: DateTime<Utc>,
+    pub trial_end: Option<DateTime<Utc>>,
+    /// Cancel instead of renewing when the current period ends
+    pub cancel_at_period_end: bool,
+    /// When the failed renewal charge was due
+    pub past_due_since: Option<DateTime<Utc>>,
+    /// Failed renewal attempts since the last successful charge
+    pub dunning_attempts: i32,
+    /// When the renewal worker next processes the subscription
+    pub next_attempt_at: DateTime<Utc>,
+    pub canceled_at: Option<DateTime<Utc>>,
+    pub cancel_reason: Option<String>,
+    /// Credit transaction of the most recent charge
+    pub last_transaction_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SubscriptionEntity {
+    /// A subscription whose first period is `[period_start, period_end)`
+    pub fn new(
+        plan_id: Uuid,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        status: SubscriptionStatus,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            plan_id,
+            user_id,
+            org_id,
+            status,
+            current_period_start: period_start,
+            current_period_end: period_end,
+            trial_end: (status == SubscriptionStatus::Trialing).then_some(period_end),
+            cancel_at_period_end: false,
+            past_due_since: None,
+            dunning_attempts: 0,
+            next_attempt_at: period_end,
+            canceled_at: None,
+            cancel_reason: None,
+            last_transaction_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether the subscription currently grants its plan's entitlements
+    ///
+    /// Past-due subscriptions keep them for `grace_days` after the missed
+    /// charge was due.
+    pub fn is_entitled(&self, grace_days: i32, now: DateTime<Utc>) -> bool {
+        match self.status {
+            SubscriptionStatus::Trialing | SubscriptionStatus::Active => true,
+            SubscriptionStatus::PastDue => self
+                .past_due_since
+                .is_some_and(|since| now < since + Duration::days(grace_days as i64)),
+            SubscriptionStatus::Canceled => false,
+        }
+    }
+
+    /// Whether this subscription is billed to the given user or organization
+    fn same_payer(&self, user_id: Uuid, org_id: Option<Uuid>) -> bool {
+        match org_id {
+            Some(org_id) => self.org_id == Some(org_id),
+            None => self.org_id.is_none() && self.user_id == user_id,
+        }
+    }
+}
+
+/// Subscription plan and subscription repository trait
+#[async_trait]
+pub trait SubscriptionRepository: Send + Sync {
+    /// Create a plan; fails if the name is taken
+    async fn create_plan(
+        &self,
+        plan: SubscriptionPlanEntity,
+    ) -> Result<SubscriptionPlanEntity, AppError>;
+
+    async fn find_plan(&self, id: Uuid) -> Result<Option<SubscriptionPlanEntity>, AppError>;
+
+    async fn find_plan_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<SubscriptionPlanEntity>, AppError>;
+
+    /// All plans, by name
+    async fn list_plans(&self) -> Result<Vec<SubscriptionPlanEntity>, AppError>;
+
+    /// Update display name, trial, grace period, entitlements and active state
+    async fn update_plan(
+        &self,
+        plan: SubscriptionPlanEntity,
+    ) -> Result<SubscriptionPlanEntity, AppError>;
+
+    /// Create a subscription; fails if the payer already has an uncanceled
+    /// subscription to the plan
+    async fn create_subscription(
+        &self,
+        subscription: SubscriptionEntity,
+    ) -> Result<SubscriptionEntity, AppError>;
+
+    async fn find_subscription(&self, id: Uuid) -> Result<Option<SubscriptionEntity>, AppError>;
+
+    /// A user's personal subscriptions, newest first
+    async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<SubscriptionEntity>, AppError>;
+
+    /// An organization's subscriptions, newest first
+    async fn list_for_org(&self, org_id: Uuid) -> Result<Vec<SubscriptionEntity>, AppError>;
+
+    /// Uncanceled subscriptions with `next_attempt_at <= now`, oldest first
+    async fn list_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<SubscriptionEntity>, AppError>;
+
+    /// Save a subscription's status, period and dunning state
+    ///
+    /// Only applies if the stored row is unchanged since `subscription` was
+    /// read (its `updated_at` matches). Returns `Ok(None)` when a concurrent
+    /// update won, so a renewal can't overwrite a cancellation or vice versa.
+    async fn update_subscription(
+        &self,
+        subscription: SubscriptionEntity,
+    ) -> Result<Option<SubscriptionEntity>, AppError>;
+
+    /// Remove a subscription whose first charge was rejected
+    async fn delete_subscription(&self, id: Uuid) -> Result<(), AppError>;
+}
+
+/// In-memory subscription repository for development/testing
+pub struct InMemorySubscriptionRepository {
+    plans: RwLock<HashMap<Uuid, SubscriptionPlanEntity>>,
+    subscriptions: RwLock<HashMap<Uuid, SubscriptionEntity>>,
+}
+
+impl InMemorySubscriptionRepository {
+    pub fn new() -> Self {
+        Self {
+            plans: RwLock::new(HashMap::new()),
+            subscriptions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn list_where(
+        &self,
+        filter: impl Fn(&SubscriptionEntity) -> bool,
+    ) -> Vec<SubscriptionEntity> {
+        let mut subscriptions: Vec<_> = self
+            .subscriptions
+            .read()
+            .await
+            .values()
+            .filter(|s| filter(s))
+            .cloned()
+            .collect();
+        subscriptions.sort_by_key(|s| Reverse(s.created_at));
+        subscriptions
+    }
+}
+
+impl Default for InMemorySubscriptionRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SubscriptionRepository for InMemorySubscriptionRepository {
+    async fn create_plan(
+        &self,
+        plan: SubscriptionPlanEntity,
+    ) -> Result<SubscriptionPlanEntity, AppError> {
+        let mut plans = self.plans.write().await;
+        if plans.values().any(|p| p.name == plan.name) {
+            return Err(AppError::Validation(
+                "A plan with this name already exists".into(),
+            ));
+        }
+        plans.insert(plan.id, plan.clone());
+        Ok(plan)
+    }
+
+    async fn find_plan(&self, id: Uuid) -> Result<Option<SubscriptionPlanEntity>, AppError> {
+        Ok(self.plans.read().await.get(&id).cloned())
+    }
+
+    async fn find_plan_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Option<SubscriptionPlanEntity>, AppError> {
+        let plans = self.plans.read().await;
+        Ok(plans.values().find(|p| p.name == name).cloned())
+    }
+
+    async fn list_plans(&self) -> Result<Vec<SubscriptionPlanEntity>, AppError> {
+        let mut plans: Vec<_> = self.plans.read().await.values().cloned().collect();
+        plans.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(plans)
+    }
+
+    async fn update_plan(
+        &self,
+        plan: SubscriptionPlanEntity,
+    ) -> Result<SubscriptionPlanEntity, AppError> {
+        let mut plans = self.plans.write().await;
+        let existing = plans
+            .get_mut(&plan.id)
+            .ok_or(AppError::NotFound("Subscription plan not found".into()))?;
+        existing.display_name = plan.display_name;
+        existing.trial_days = plan.trial_days;
+        existing.grace_days = plan.grace_days;
+        existing.entitlements = plan.entitlements;
+        existing.active = plan.active;
+        existing.updated_at = Utc::now();
+        Ok(existing.clone())
+    }
+
+    async fn create_subscription(
+        &self,
+        subscription: SubscriptionEntity,
+    ) -> Result<SubscriptionEntity, AppError> {
+        let mut subscriptions = self.subscriptions.write().await;
+        let duplicate = subscriptions.values().any(|s| {
+            s.plan_id == subscription.plan_id
+                && s.status != SubscriptionStatus::Canceled
+                && s.same_payer(subscription.user_id, subscription.org_id)
+        });
+        if duplicate {
+            return Err(AppError::Validation(
+                "Already subscribed to this plan".into(),
+            ));
+        }
+        subscriptions.insert(subscription.id, subscription.clone());
+        Ok(subscription)
+    }
+
+    async fn find_subscription(&self, id: Uuid) -> Result<Option<SubscriptionEntity>, AppError> {
+        Ok(self.subscriptions.read().await.get(&id).cloned())
+    }
+
+    async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<SubscriptionEntity>, AppError> {
+        Ok(self
+            .list_where(|s| s.user_id == user_id && s.org_id.is_none())
+            .await)
+    }
+
+    async fn list_for_org(&self, org_id: Uuid) -> Result<Vec<SubscriptionEntity>, AppError> {
+        Ok(self.list_where(|s| s.org_id == Some(org_id)).await)
+    }
+
+    async fn list_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<SubscriptionEntity>, AppError> {
+        let mut due = self
+            .list_where(|s| s.status != SubscriptionStatus::Canceled && s.next_attempt_at <= now)
+            .await;
+        due.sort_by_key(|s| s.next_attempt_at);
+        due.truncate(limit as usize);
+        Ok(due)
+    }
+
+    async fn update_subscription(
+        &self,
+        subscription: SubscriptionEntity,
+    ) -> Result<Option<SubscriptionEntity>, AppError> {
+        let mut subscriptions = self.subscriptions.write().await;
+        let existing = subscriptions
+            .get_mut(&subscription.id)
+            .ok_or(AppError::NotFound("Subscription not found".into()))?;
+        if existing.updated_at != subscription.updated_at {
+            return Ok(None);
+        }
+        *existing = SubscriptionEntity {
+            id: existing.id,
+            plan_id: existing.plan_id,
+            user_id: existing.user_id,
+            org_id: existing.org_id,
+            created_at: existing.created_at,
+            updated_at: Utc::now(),
+            ..subscription
+        };
+        Ok(Some(existing.clone()))
+    }
+
+    async fn delete_subscription(&self, id: Uuid) -> Result<(), AppError> {
+        self.subscriptions.write().await.remove(&id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_interval_advance() {
+        let jan_31 = Utc.with_ymd_and_hms(2026, 1, 31, 12, 0, 0).unwrap();
+        assert_eq!(
+            PlanInterval::Month.advance(jan_31, 1),
+            Utc.with_ymd_and_hms(2026, 2, 28, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            PlanInterval::Year.advance(jan_31, 1),
+            Utc.with_ymd_and_hms(2027, 1, 31, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            PlanInterval::Week.advance(jan_31, 2),
+            Utc.with_ymd_and_hms(2026, 2, 14, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            PlanInterval::Day.advance(jan_31, 1),
+            Utc.with_ymd_and_hms(2026, 2, 1, 12, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_validate_entitlements() {
+        assert!(validate_entitlements(&["pro".into(), "api.v2".into()]).is_ok());
+        assert!(validate_entitlements(&["Pro".into()]).is_err());
+        assert!(validate_entitlements(&["pro".into(), "pro".into()]).is_err());
+        assert!(validate_plan_name("pro_monthly").is_ok());
+        assert!(validate_plan_name("1pro").is_err());
+    }
+
+    #[test]
+    fn test_entitlement_grace_period() {
+        let now = Utc::now();
+        let mut subscription = SubscriptionEntity::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            None,
+            SubscriptionStatus::Active,
+            now,
+            now + Duration::days(30),
+        );
+        assert!(subscription.is_entitled(3, now));
+
+        subscription.status = SubscriptionStatus::PastDue;
+        subscription.past_due_since = Some(now - Duration::days(2));
+        assert!(subscription.is_entitled(3, now));
+        assert!(!subscription.is_entitled(2, now));
+
+        subscription.status = SubscriptionStatus::Canceled;
+        assert!(!subscription.is_entitled(3, now));
+    }
+
+    #[tokio::test]
+    async fn test_one_open_subscription_per_payer_and_plan() {
+        let repo = InMemorySubscriptionRepository::new();
+        let now = Utc::now();
+        let plan_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let subscription = |org_id| {
+            SubscriptionEntity::new(
+                plan_id,
+                user_id,
+                org_id,
+                SubscriptionStatus::Active,
+                now,
+                now + Duration::days(30),
+            )
+        };
+
+        let mut first = repo.create_subscription(subscription(None)).await.unwrap();
+        assert!(repo.create_subscription(subscription(None)).await.is_err());
+        // The same user can also subscribe on behalf of an organization
+        repo.create_subscription(subscription(Some(Uuid::new_v4())))
+            .await
+            .unwrap();
+
+        first.status = SubscriptionStatus::Canceled;
+        let stale = first.clone();
+        assert!(repo.update_subscription(first).await.unwrap().is_some());
+        // A write based on the pre-cancellation row is rejected
+        assert!(repo.update_subscription(stale).await.unwrap().is_none());
+        repo.create_subscription(subscription(None)).await.unwrap();
+        assert_eq!(repo.list_for_user(user_id).await.unwrap().len(), 2);
+
+        let due = repo.list_due(now + Duration::days(31), 10).await.unwrap();
+        assert_eq!(due.len(), 2);
+        assert!(repo.list_due(now, 10).await.unwrap().is_empty());
+    }
+}
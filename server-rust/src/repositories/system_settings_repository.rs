@@ -287,6 +287,54 @@ impl InMemorySystemSettingsRepository {
                 "server".to_string(),
             )
             .with_description("API key for Prometheus metrics endpoint"),
+            // Organizations
+            SystemSetting::new(
+                "org_deletion_grace_days".to_string(),
+                "30".to_string(),
+                "org".to_string(),
+            )
+            .with_description("Days a deleted organization can be restored before it is purged"),
+            // Accounts
+            SystemSetting::new(
+                "account_deletion_grace_days".to_string(),
+                "30".to_string(),
+                "account".to_string(),
+            )
+            .with_description("Days a deleted account can be restored before it is purged"),
+            SystemSetting::new(
+                "jwt_metadata_claims".to_string(),
+                "[]".to_string(),
+                "account".to_string(),
+            )
+            .with_description(
+                "JSON array of public_metadata keys copied into access tokens as the metadata claim",
+            ),
+            // Impersonation
+            SystemSetting::new(
+                "impersonation_duration_mins".to_string(),
+                "15".to_string(),
+                "impersonation".to_string(),
+            )
+            .with_description("Lifetime of an impersonation session in minutes"),
+            SystemSetting::new(
+                "impersonation_org_owners_enabled".to_string(),
+                "false".to_string(),
+                "impersonation".to_string(),
+            )
+            .with_description("Allow organization owners to impersonate their members"),
+            // Credit transfers
+            SystemSetting::new(
+                "credit_transfer_daily_limit_lamports".to_string(),
+                "10000000000".to_string(),
+                "credit".to_string(),
+            )
+            .with_description("Maximum credits a user may transfer per UTC day (0 = no limit)"),
+            SystemSetting::new(
+                "credit_transfer_step_up_lamports".to_string(),
+                "1000000000".to_string(),
+                "credit".to_string(),
+            )
+            .with_description("Transfers above this amount require step-up authentication"),
         ]
     }
 }
@@ -359,7 +407,7 @@ mod tests {
     async fn test_with_defaults() {
         let repo = InMemorySystemSettingsRepository::with_defaults();
         let settings = repo.get_all().await.unwrap();
-        assert_eq!(settings.len(), 20); // All default settings (14 original + 5 server/logging/metrics + feature_cedros_pay)
+        assert_eq!(settings.len(), 27); // All default settings (14 original + 5 server/logging/metrics + feature_cedros_pay + org_deletion_grace_days + 2 impersonation + account_deletion_grace_days + jwt_metadata_claims + 2 credit transfer)
     }
 
     #[tokio::test]
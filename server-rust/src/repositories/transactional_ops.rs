@@ -9,9 +9,11 @@
 //! implementations for the critical operations:
 //! - Invite acceptance (invite + membership)
 //! - User registration with org membership (user + membership)
+//! - Credit transfers (debit + credit of two balances)
 //!
 //! These operations use raw SQL within a transaction to ensure atomicity.
 
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -19,7 +21,12 @@ use serde_json::Value as JsonValue;
 
 use crate::errors::AppError;
 use crate::models::AuthMethod;
-use crate::repositories::{CreateWalletMaterial, MembershipEntity, OrgRole, UserEntity};
+use crate::repositories::postgres::{balance_target, draw_grants, insert_credit_transaction};
+use crate::repositories::{
+    CreateWalletMaterial, CreditTransactionEntity, MembershipEntity, OrgRole, UserEntity,
+    UserStatus,
+};
+use crate::services::check_daily_limit;
 
 /// Transaction-based operations for critical multi-entity workflows
 pub struct TransactionalOps;
@@ -320,9 +327,16 @@ impl TransactionalOps {
             stripe_customer_id: user_row.stripe_customer_id,
             auth_methods: user.auth_methods.clone(),
             is_system_admin: user_row.is_system_admin,
+            is_service_account: false,
             created_at: user_row.created_at,
             updated_at: user_row.updated_at,
             last_login_at: user_row.last_login_at,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
 
         let membership = MembershipEntity {
@@ -405,6 +419,181 @@ impl TransactionalOps {
 
         Ok(())
     }
+
+    /// Atomically move credits from one balance to another
+    ///
+    /// `debit` and `credit` are the two legs of the transfer; each moves the
+    /// organization's balance when its `org_id` is set, otherwise its
+    /// user's. Only paid credit moves: a debit that would dip into the
+    /// sender's promotional grants is refused. Both balance rows are locked
+    /// in a fixed order first, so opposing transfers cannot deadlock.
+    ///
+    /// With a `daily_limit`, the sender's transfers since `day_start` are
+    /// summed under a per-sender lock, so concurrent transfers cannot
+    /// together exceed it.
+    ///
+    /// # Returns
+    /// The sender's and the recipient's new balances
+    ///
+    /// # Errors
+    /// - `AppError::Validation` if the sender's available paid credit is too low
+    /// - `AppError::Forbidden` if the transfer exceeds the daily limit
+    /// - `AppError::Database` if the transaction fails
+    pub async fn transfer_credits_atomic(
+        pool: &PgPool,
+        mut debit: CreditTransactionEntity,
+        credit: CreditTransactionEntity,
+        daily_limit: Option<i64>,
+        day_start: DateTime<Utc>,
+    ) -> Result<(i64, i64), AppError> {
+        let amount = debit.amount.abs();
+        let currency = debit.currency.to_uppercase();
+        let source = balance_target(debit.org_id, debit.user_id);
+        let target = balance_target(credit.org_id, credit.user_id);
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to begin transaction: {}", e)))?;
+
+        // Step 1: Make sure the recipient has a balance, then lock both
+        let (table, owner_col, owner_id) = target;
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO {table} ({owner_col}, balance, held_balance, currency, updated_at)
+            VALUES ($1, 0, 0, $2, NOW())
+            ON CONFLICT ({owner_col}, currency) DO NOTHING
+            "#
+        ))
+        .bind(owner_id)
+        .bind(&currency)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to create balance: {}", e)))?;
+
+        let mut lock_order = [source, target];
+        lock_order.sort_by_key(|(table, _, owner_id)| (*table, *owner_id));
+        for (table, owner_col, owner_id) in lock_order {
+            sqlx::query(&format!(
+                "SELECT 1 FROM {table} WHERE {owner_col} = $1 AND currency = $2 FOR UPDATE"
+            ))
+            .bind(owner_id)
+            .bind(&currency)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to lock balance: {}", e)))?;
+        }
+
+        // The limit spans every balance the sender transfers from, so it is
+        // checked under a lock on the sender rather than on one balance
+        if let Some(limit) = daily_limit {
+            sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))")
+                .bind(format!("credit_transfer:{}", debit.user_id))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::Database(format!("Failed to lock sender: {}", e)))?;
+            let sent: i64 = sqlx::query_scalar(
+                r#"
+                SELECT COALESCE(SUM(ABS(amount))::BIGINT, 0)
+                FROM credit_transactions
+                WHERE user_id = $1
+                  AND currency = $2
+                  AND tx_type = 'transfer'
+                  AND amount < 0
+                  AND created_at >= $3
+                "#,
+            )
+            .bind(debit.user_id)
+            .bind(&currency)
+            .bind(day_start)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to sum transfers: {}", e)))?;
+            if let Err(e) = check_daily_limit(sent, limit, amount) {
+                if let Err(rollback) = tx.rollback().await {
+                    tracing::error!(
+                        error = %rollback,
+                        operation = "transfer_credits_atomic",
+                        step = "daily_limit",
+                        "Failed to rollback transaction after daily limit check"
+                    );
+                }
+                return Err(e);
+            }
+        }
+
+        // Step 2: Debit the sender's available balance
+        let (table, owner_col, owner_id) = source;
+        let sender_balance: Option<i64> = sqlx::query_scalar(&format!(
+            r#"
+            UPDATE {table}
+            SET balance = balance - $1, updated_at = NOW()
+            WHERE {owner_col} = $2 AND currency = $3 AND (balance - held_balance) >= $1
+            RETURNING balance
+            "#
+        ))
+        .bind(amount)
+        .bind(owner_id)
+        .bind(&currency)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to debit sender: {}", e)))?;
+
+        let Some(sender_balance) = sender_balance else {
+            if let Err(e) = tx.rollback().await {
+                tracing::error!(
+                    error = %e,
+                    operation = "transfer_credits_atomic",
+                    step = "insufficient_balance",
+                    "Failed to rollback transaction after insufficient balance"
+                );
+            }
+            return Err(AppError::Validation(
+                "Insufficient credit balance for transfer".into(),
+            ));
+        };
+
+        // Step 3: Record the debit, refusing to move promotional credit
+        draw_grants(&mut tx, &mut debit, amount, sender_balance + amount).await?;
+        if debit.grant_amount != 0 {
+            if let Err(e) = tx.rollback().await {
+                tracing::error!(
+                    error = %e,
+                    operation = "transfer_credits_atomic",
+                    step = "promotional_credit",
+                    "Failed to rollback transaction after promotional draw"
+                );
+            }
+            return Err(AppError::Validation(
+                "Promotional credit cannot be transferred".into(),
+            ));
+        }
+        insert_credit_transaction(&mut tx, &debit).await?;
+
+        // Step 4: Credit the recipient and record it
+        let (table, owner_col, owner_id) = target;
+        let recipient_balance: i64 = sqlx::query_scalar(&format!(
+            r#"
+            UPDATE {table}
+            SET balance = balance + $1, updated_at = NOW()
+            WHERE {owner_col} = $2 AND currency = $3
+            RETURNING balance
+            "#
+        ))
+        .bind(amount)
+        .bind(owner_id)
+        .bind(&currency)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to credit recipient: {}", e)))?;
+        insert_credit_transaction(&mut tx, &credit).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to commit: {}", e)))?;
+
+        Ok((sender_balance, recipient_balance))
+    }
 }
 
 // Row types for SQL queries
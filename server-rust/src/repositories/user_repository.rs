@@ -37,6 +37,8 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 use tokio::sync::RwLock;
 use unicode_normalization::UnicodeNormalization;
@@ -44,6 +46,7 @@ use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::models::AuthMethod;
+use crate::utils::metadata::apply_metadata_patch;
 
 /// Normalize an email address for consistent lookups.
 ///
@@ -92,6 +95,52 @@ pub fn validate_email_ascii_local(email: &str) -> Result<(), crate::errors::AppE
     Ok(())
 }
 
+/// Account lifecycle state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserStatus {
+    #[default]
+    Active,
+    /// Temporarily blocked, usually with an expiry
+    Suspended,
+    /// Permanently blocked unless an expiry is set
+    Banned,
+    /// Scheduled for deletion; `status_expires_at` is when it is purged
+    PendingDeletion,
+    /// Purged: personal data removed, row kept for financial records
+    Deleted,
+}
+
+impl UserStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Suspended => "suspended",
+            Self::Banned => "banned",
+            Self::PendingDeletion => "pending_deletion",
+            Self::Deleted => "deleted",
+        }
+    }
+
+    /// Parse status from string (returns None for invalid values)
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "active" => Some(Self::Active),
+            "suspended" => Some(Self::Suspended),
+            "banned" => Some(Self::Banned),
+            "pending_deletion" => Some(Self::PendingDeletion),
+            "deleted" => Some(Self::Deleted),
+            _ => None,
+        }
+    }
+
+    /// Whether an expiry lifts this status (as opposed to completing it)
+    pub fn lifts_on_expiry(&self) -> bool {
+        matches!(self, Self::Suspended | Self::Banned)
+    }
+}
+
 /// User entity for storage
 #[derive(Debug, Clone)]
 pub struct UserEntity {
@@ -107,10 +156,24 @@ pub struct UserEntity {
     pub stripe_customer_id: Option<String>,
     pub auth_methods: Vec<AuthMethod>,
     pub is_system_admin: bool,
+    /// Backing user of an organization service account
+    pub is_service_account: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     /// Last successful login timestamp
     pub last_login_at: Option<DateTime<Utc>>,
+    /// Lifecycle state as stored; use [`UserEntity::effective_status`] for checks
+    pub status: UserStatus,
+    /// Reason recorded with the current status
+    pub status_reason: Option<String>,
+    /// When a suspension or ban lifts, or when a pending deletion is purged
+    pub status_expires_at: Option<DateTime<Utc>>,
+    /// Host-app data the user can read; writable by admins and API keys
+    pub public_metadata: Map<String, Value>,
+    /// Host-app data visible only to admins and API keys
+    pub private_metadata: Map<String, Value>,
+    /// Host-app data the user can read and write; never trust it server-side
+    pub unsafe_metadata: Map<String, Value>,
 }
 
 impl UserEntity {
@@ -130,9 +193,78 @@ impl UserEntity {
             stripe_customer_id: None,
             auth_methods: vec![AuthMethod::Email],
             is_system_admin: false,
+            is_service_account: false,
+            created_at: now,
+            updated_at: now,
+            last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Map::new(),
+            private_metadata: Map::new(),
+            unsafe_metadata: Map::new(),
+        }
+    }
+
+    /// Status after applying expiry: lapsed suspensions and bans are active
+    pub fn effective_status(&self) -> UserStatus {
+        match self.status_expires_at {
+            Some(expires_at) if self.status.lifts_on_expiry() && expires_at <= Utc::now() => {
+                UserStatus::Active
+            }
+            _ => self.status,
+        }
+    }
+
+    /// Return an error unless the account may sign in or use credentials
+    pub fn ensure_active(&self) -> Result<(), AppError> {
+        let until = self
+            .status_expires_at
+            .map(|at| format!(" until {}", at.to_rfc3339()))
+            .unwrap_or_default();
+        match self.effective_status() {
+            UserStatus::Active => Ok(()),
+            UserStatus::Suspended => Err(AppError::AccountSuspended(format!(
+                "Account is suspended{}",
+                until
+            ))),
+            UserStatus::Banned => Err(AppError::AccountBanned(format!(
+                "Account is banned{}",
+                until
+            ))),
+            UserStatus::PendingDeletion => Err(AppError::AccountPendingDeletion(
+                "Account is scheduled for deletion".into(),
+            )),
+            UserStatus::Deleted => Err(AppError::Unauthorized("Account has been deleted".into())),
+        }
+    }
+
+    /// Create the credential-less user backing a service account
+    pub fn new_service_account(id: Uuid, name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            email: None,
+            email_verified: false,
+            password_hash: None,
+            name: Some(name),
+            picture: None,
+            wallet_address: None,
+            google_id: None,
+            apple_id: None,
+            stripe_customer_id: None,
+            auth_methods: Vec::new(),
+            is_system_admin: false,
+            is_service_account: true,
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Map::new(),
+            private_metadata: Map::new(),
+            unsafe_metadata: Map::new(),
         }
     }
 }
@@ -213,6 +345,58 @@ pub trait UserRepository: Send + Sync {
 
     /// Update last login timestamp for a user
     async fn update_last_login(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Set a user's lifecycle status, reason and expiry
+    ///
+    /// `update()` never writes these columns, so status changes cannot be
+    /// lost to a concurrent profile update.
+    async fn set_status(
+        &self,
+        id: Uuid,
+        status: UserStatus,
+        reason: Option<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError>;
+
+    /// List users by effective status (lapsed suspensions and bans are active)
+    async fn list_by_status(
+        &self,
+        status: UserStatus,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<UserEntity>, AppError>;
+
+    /// Count users by effective status
+    async fn count_by_status(&self, status: UserStatus) -> Result<u64, AppError>;
+
+    /// Find users pending deletion whose cooling-off period ended before `now`
+    async fn find_due_for_purge(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<UserEntity>, AppError>;
+
+    /// Remove a user's personal data and mark the account deleted
+    ///
+    /// The row itself is kept because deposits, withdrawals and credit
+    /// transactions reference it.
+    async fn anonymize(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Apply merge patches to the metadata blobs that are `Some`, leaving the
+    /// others as is
+    ///
+    /// Each patch is applied to the stored blob while the user is locked, so
+    /// concurrent patches don't lose each other's keys. Fails with a
+    /// validation error if a resulting blob exceeds the metadata limits.
+    /// `update()` never writes metadata, so a profile update cannot clobber a
+    /// concurrent metadata change.
+    async fn update_metadata(
+        &self,
+        id: Uuid,
+        public: Option<Map<String, Value>>,
+        private: Option<Map<String, Value>>,
+        unsafe_: Option<Map<String, Value>>,
+    ) -> Result<UserEntity, AppError>;
 }
 
 /// In-memory user repository for development/testing
@@ -429,6 +613,18 @@ impl UserRepository for InMemoryUserRepository {
             }
         }
 
+        // Status is only changed through set_status(), metadata through
+        // update_metadata()
+        let mut user = user;
+        if let Some(old_user) = users.get(&user.id) {
+            user.status = old_user.status;
+            user.status_reason = old_user.status_reason.clone();
+            user.status_expires_at = old_user.status_expires_at;
+            user.public_metadata = old_user.public_metadata.clone();
+            user.private_metadata = old_user.private_metadata.clone();
+            user.unsafe_metadata = old_user.unsafe_metadata.clone();
+        }
+
         users.insert(user.id, user.clone());
         Ok(user)
     }
@@ -600,6 +796,129 @@ impl UserRepository for InMemoryUserRepository {
         }
         Ok(())
     }
+
+    async fn set_status(
+        &self,
+        id: Uuid,
+        status: UserStatus,
+        reason: Option<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        let mut users = self.users.write().await;
+        let user = users
+            .get_mut(&id)
+            .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+        user.status = status;
+        user.status_reason = reason;
+        user.status_expires_at = expires_at;
+        user.updated_at = Utc::now();
+        Ok(())
+    }
+
+    async fn list_by_status(
+        &self,
+        status: UserStatus,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<UserEntity>, AppError> {
+        const MAX_PAGE_SIZE: u32 = 100;
+
+        let users = self.users.read().await;
+        let mut matching: Vec<_> = users
+            .values()
+            .filter(|u| u.effective_status() == status)
+            .cloned()
+            .collect();
+        matching.sort_by_key(|u| std::cmp::Reverse(u.created_at));
+        Ok(matching
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit.min(MAX_PAGE_SIZE) as usize)
+            .collect())
+    }
+
+    async fn count_by_status(&self, status: UserStatus) -> Result<u64, AppError> {
+        let users = self.users.read().await;
+        Ok(users
+            .values()
+            .filter(|u| u.effective_status() == status)
+            .count() as u64)
+    }
+
+    async fn find_due_for_purge(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<UserEntity>, AppError> {
+        let users = self.users.read().await;
+        Ok(users
+            .values()
+            .filter(|u| {
+                u.status == UserStatus::PendingDeletion
+                    && u.status_expires_at.is_some_and(|at| at <= now)
+            })
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn anonymize(&self, id: Uuid) -> Result<(), AppError> {
+        let mut users = self.users.write().await;
+        let user = users
+            .get_mut(&id)
+            .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+        user.email = None;
+        user.email_verified = false;
+        user.password_hash = None;
+        user.name = None;
+        user.picture = None;
+        user.wallet_address = None;
+        user.google_id = None;
+        user.apple_id = None;
+        user.stripe_customer_id = None;
+        user.auth_methods.clear();
+        user.public_metadata.clear();
+        user.private_metadata.clear();
+        user.unsafe_metadata.clear();
+        user.status = UserStatus::Deleted;
+        user.status_reason = None;
+        user.status_expires_at = None;
+        user.updated_at = Utc::now();
+        Ok(())
+    }
+
+    async fn update_metadata(
+        &self,
+        id: Uuid,
+        public: Option<Map<String, Value>>,
+        private: Option<Map<String, Value>>,
+        unsafe_: Option<Map<String, Value>>,
+    ) -> Result<UserEntity, AppError> {
+        let mut users = self.users.write().await;
+        let user = users
+            .get_mut(&id)
+            .ok_or_else(|| AppError::NotFound("User not found".into()))?;
+        let public = public
+            .map(|p| apply_metadata_patch(&user.public_metadata, p, "publicMetadata"))
+            .transpose()?;
+        let private = private
+            .map(|p| apply_metadata_patch(&user.private_metadata, p, "privateMetadata"))
+            .transpose()?;
+        let unsafe_ = unsafe_
+            .map(|p| apply_metadata_patch(&user.unsafe_metadata, p, "unsafeMetadata"))
+            .transpose()?;
+        if let Some(public) = public {
+            user.public_metadata = public;
+        }
+        if let Some(private) = private {
+            user.private_metadata = private;
+        }
+        if let Some(unsafe_) = unsafe_ {
+            user.unsafe_metadata = unsafe_;
+        }
+        user.updated_at = Utc::now();
+        Ok(user.clone())
+    }
 }
 
 #[cfg(test)]
@@ -739,4 +1058,112 @@ mod tests {
         assert!(found.is_some());
         assert_eq!(found.unwrap().id, user_id);
     }
+
+    #[tokio::test]
+    async fn test_status_filtering_and_expiry() {
+        let repo = InMemoryUserRepository::new();
+        let suspended =
+            UserEntity::new_email_user("s@example.com".to_string(), "hash".to_string(), None);
+        let lapsed =
+            UserEntity::new_email_user("l@example.com".to_string(), "hash".to_string(), None);
+        repo.create(suspended.clone()).await.unwrap();
+        repo.create(lapsed.clone()).await.unwrap();
+
+        repo.set_status(
+            suspended.id,
+            UserStatus::Suspended,
+            Some("abuse".into()),
+            None,
+        )
+        .await
+        .unwrap();
+        repo.set_status(
+            lapsed.id,
+            UserStatus::Suspended,
+            None,
+            Some(Utc::now() - chrono::Duration::minutes(1)),
+        )
+        .await
+        .unwrap();
+
+        let found = repo.find_by_id(suspended.id).await.unwrap().unwrap();
+        assert!(matches!(
+            found.ensure_active(),
+            Err(AppError::AccountSuspended(_))
+        ));
+        let found = repo.find_by_id(lapsed.id).await.unwrap().unwrap();
+        assert_eq!(found.effective_status(), UserStatus::Active);
+        assert!(found.ensure_active().is_ok());
+
+        assert_eq!(
+            repo.count_by_status(UserStatus::Suspended).await.unwrap(),
+            1
+        );
+        assert_eq!(repo.count_by_status(UserStatus::Active).await.unwrap(), 1);
+        let listed = repo
+            .list_by_status(UserStatus::Suspended, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(listed[0].id, suspended.id);
+    }
+
+    #[tokio::test]
+    async fn test_update_does_not_overwrite_status() {
+        let repo = InMemoryUserRepository::new();
+        let user =
+            UserEntity::new_email_user("u@example.com".to_string(), "hash".to_string(), None);
+        repo.create(user.clone()).await.unwrap();
+        repo.set_status(user.id, UserStatus::Banned, None, None)
+            .await
+            .unwrap();
+
+        // Stale copy still says active
+        repo.update(user.clone()).await.unwrap();
+        let found = repo.find_by_id(user.id).await.unwrap().unwrap();
+        assert_eq!(found.status, UserStatus::Banned);
+    }
+
+    #[tokio::test]
+    async fn test_update_metadata_patches_only_given_blobs() {
+        let repo = InMemoryUserRepository::new();
+        let user =
+            UserEntity::new_email_user("m@example.com".to_string(), "hash".to_string(), None);
+        repo.create(user.clone()).await.unwrap();
+
+        let mut public = Map::new();
+        public.insert("plan".to_string(), Value::from("pro"));
+        let mut private = Map::new();
+        private.insert("crm".to_string(), Value::from(42));
+        repo.update_metadata(user.id, Some(public.clone()), Some(private), None)
+            .await
+            .unwrap();
+
+        // Patches merge into the stored blob rather than a caller's copy
+        let mut seats = Map::new();
+        seats.insert("seats".to_string(), Value::from(5));
+        let mut remove_crm = Map::new();
+        remove_crm.insert("crm".to_string(), Value::Null);
+        let updated = repo
+            .update_metadata(user.id, Some(seats), Some(remove_crm), None)
+            .await
+            .unwrap();
+        public.insert("seats".to_string(), Value::from(5));
+        assert_eq!(updated.public_metadata, public);
+        assert!(updated.private_metadata.is_empty());
+
+        let mut too_big = Map::new();
+        too_big.insert(
+            "blob".to_string(),
+            Value::from("x".repeat(crate::utils::metadata::MAX_METADATA_BYTES)),
+        );
+        let result = repo
+            .update_metadata(user.id, None, None, Some(too_big))
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        // Profile updates from a stale copy keep the stored metadata
+        repo.update(user.clone()).await.unwrap();
+        let found = repo.find_by_id(user.id).await.unwrap().unwrap();
+        assert_eq!(found.public_metadata, public);
+    }
 }
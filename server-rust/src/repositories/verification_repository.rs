@@ -1,4 +1,5 @@
-//! Verification token repository for email verification and password reset
+//! Verification token repository for email verification, password reset and
+//! email address changes
 
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
@@ -15,6 +16,10 @@ pub enum TokenType {
     InstantLink,
     /// Pending MFA verification during login (short-lived, 5 minutes)
     MfaPending,
+    /// Confirmation link sent to a new email address (payload: new email)
+    EmailChange,
+    /// Revert link sent to the previous email address (payload: old email)
+    EmailChangeRevert,
 }
 
 impl TokenType {
@@ -24,6 +29,8 @@ impl TokenType {
             TokenType::PasswordReset => "password_reset",
             TokenType::InstantLink => "instant_link",
             TokenType::MfaPending => "mfa_pending",
+            TokenType::EmailChange => "email_change",
+            TokenType::EmailChangeRevert => "email_change_revert",
         }
     }
 
@@ -35,6 +42,8 @@ impl TokenType {
             "password_reset" => Some(TokenType::PasswordReset),
             "instant_link" => Some(TokenType::InstantLink),
             "mfa_pending" => Some(TokenType::MfaPending),
+            "email_change" => Some(TokenType::EmailChange),
+            "email_change_revert" => Some(TokenType::EmailChangeRevert),
             _ => None,
         }
     }
@@ -50,6 +59,8 @@ pub struct VerificationToken {
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
     pub used_at: Option<DateTime<Utc>>,
+    /// Opaque data bound to the token (e.g. the address an email change targets)
+    pub payload: Option<String>,
 }
 
 impl VerificationToken {
@@ -71,6 +82,17 @@ pub trait VerificationRepository: Send + Sync {
         expires_at: DateTime<Utc>,
     ) -> Result<VerificationToken, RepositoryError>;
 
+    /// Create a new verification token carrying a payload. Like `create`,
+    /// this replaces the user's unused tokens of the same type.
+    async fn create_with_payload(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        token_type: TokenType,
+        expires_at: DateTime<Utc>,
+        payload: &str,
+    ) -> Result<VerificationToken, RepositoryError>;
+
     /// Find a token by its hash
     async fn find_by_hash(
         &self,
@@ -116,16 +138,14 @@ impl InMemoryVerificationRepository {
     pub fn new() -> Self {
         Self::default()
     }
-}
 
-#[async_trait]
-impl VerificationRepository for InMemoryVerificationRepository {
-    async fn create(
+    async fn insert(
         &self,
         user_id: Uuid,
         token_hash: &str,
         token_type: TokenType,
         expires_at: DateTime<Utc>,
+        payload: Option<String>,
     ) -> Result<VerificationToken, RepositoryError> {
         let token = VerificationToken {
             id: Uuid::new_v4(),
@@ -135,6 +155,7 @@ impl VerificationRepository for InMemoryVerificationRepository {
             created_at: Utc::now(),
             expires_at,
             used_at: None,
+            payload,
         };
 
         let mut tokens = self.tokens.write().await;
@@ -145,6 +166,38 @@ impl VerificationRepository for InMemoryVerificationRepository {
         tokens.insert(token.id, token.clone());
         Ok(token)
     }
+}
+
+#[async_trait]
+impl VerificationRepository for InMemoryVerificationRepository {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        token_type: TokenType,
+        expires_at: DateTime<Utc>,
+    ) -> Result<VerificationToken, RepositoryError> {
+        self.insert(user_id, token_hash, token_type, expires_at, None)
+            .await
+    }
+
+    async fn create_with_payload(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        token_type: TokenType,
+        expires_at: DateTime<Utc>,
+        payload: &str,
+    ) -> Result<VerificationToken, RepositoryError> {
+        self.insert(
+            user_id,
+            token_hash,
+            token_type,
+            expires_at,
+            Some(payload.to_string()),
+        )
+        .await
+    }
 
     async fn find_by_hash(
         &self,
@@ -238,6 +291,8 @@ pub fn default_expiry(token_type: TokenType) -> DateTime<Utc> {
         TokenType::PasswordReset => Utc::now() + Duration::hours(1),
         TokenType::InstantLink => Utc::now() + Duration::minutes(15),
         TokenType::MfaPending => Utc::now() + Duration::minutes(5),
+        TokenType::EmailChange => Utc::now() + Duration::hours(24),
+        TokenType::EmailChangeRevert => Utc::now() + Duration::days(7),
     }
 }
 
@@ -258,6 +313,10 @@ mod tests {
             TokenType::from_str("password_reset"),
             Some(TokenType::PasswordReset)
         );
+        assert_eq!(
+            TokenType::from_str("email_change_revert"),
+            Some(TokenType::EmailChangeRevert)
+        );
         assert_eq!(TokenType::from_str("invalid"), None);
     }
 
@@ -271,6 +330,7 @@ mod tests {
             created_at: Utc::now(),
             expires_at: Utc::now() + Duration::hours(1),
             used_at: None,
+            payload: None,
         };
         assert!(valid_token.is_valid());
 
@@ -384,4 +444,24 @@ mod tests {
         assert!(repo.find_by_hash("hash1").await.unwrap().is_none());
         assert!(repo.find_by_hash("hash2").await.unwrap().is_some());
     }
+
+    #[tokio::test]
+    async fn test_in_memory_payload_round_trip() {
+        let repo = InMemoryVerificationRepository::new();
+        let user_id = Uuid::new_v4();
+
+        repo.create_with_payload(
+            user_id,
+            "hash",
+            TokenType::EmailChange,
+            default_expiry(TokenType::EmailChange),
+            "new@example.com",
+        )
+        .await
+        .unwrap();
+
+        let consumed = repo.consume_if_valid("hash").await.unwrap().unwrap();
+        assert_eq!(consumed.token_type, TokenType::EmailChange);
+        assert_eq!(consumed.payload.as_deref(), Some("new@example.com"));
+    }
 }
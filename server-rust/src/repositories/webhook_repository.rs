@@ -0,0 +1,507 @@
+//! Webhook endpoint and delivery log repository
+//!
+//! Endpoints are managed by system admins. Each has its own signing secret
+//! (stored encrypted) and an event filter. Every event sent to an endpoint
+//! gets a delivery row that records the outcome of the latest attempt.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::pagination::{cap_limit, cap_offset};
+
+/// Webhook endpoint entity for storage
+#[derive(Debug, Clone)]
+pub struct WebhookEndpointEntity {
+    pub id: Uuid,
+    pub url: String,
+    pub description: Option<String>,
+    /// Signing secret encrypted with `TokenCipher`
+    pub secret_encrypted: String,
+    /// Subscribed event names; empty means every event
+    pub events: Vec<String>,
+    pub enabled: bool,
+    /// Failed attempts since the last successful delivery
+    pub consecutive_failures: u32,
+    /// Why the endpoint was disabled automatically, if it was
+    pub disabled_reason: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookEndpointEntity {
+    pub fn new(
+        url: String,
+        description: Option<String>,
+        secret_encrypted: String,
+        events: Vec<String>,
+        created_by: Option<Uuid>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            url,
+            description,
+            secret_encrypted,
+            events,
+            enabled: true,
+            consecutive_failures: 0,
+            disabled_reason: None,
+            created_by,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether this endpoint wants `event`
+    pub fn subscribes_to(&self, event: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e == event)
+    }
+}
+
+/// Outcome of a webhook delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookDeliveryStatus {
+    /// Not yet delivered; retries may still be scheduled
+    Pending,
+    Succeeded,
+    /// Given up on (retries exhausted or endpoint disabled)
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookDeliveryStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            _ => Err(AppError::Internal(anyhow::anyhow!(
+                "Unknown webhook delivery status: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Webhook delivery log entry
+#[derive(Debug, Clone)]
+pub struct WebhookDeliveryEntity {
+    pub id: Uuid,
+    pub endpoint_id: Uuid,
+    pub event_type: String,
+    /// Exact JSON body sent to the endpoint
+    pub payload: Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    /// HTTP status of the latest attempt, if a response was received
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    /// Delivery this one replays, if any
+    pub replay_of: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookDeliveryEntity {
+    pub fn new(endpoint_id: Uuid, event_type: &str, payload: Value) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            endpoint_id,
+            event_type: event_type.to_string(),
+            payload,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            response_status: None,
+            last_error: None,
+            replay_of: None,
+            created_at: Utc::now(),
+            last_attempt_at: None,
+            delivered_at: None,
+        }
+    }
+
+    /// A fresh delivery of the same payload to the same endpoint
+    pub fn replay(&self) -> Self {
+        let mut replay = Self::new(self.endpoint_id, &self.event_type, self.payload.clone());
+        replay.replay_of = Some(self.id);
+        replay
+    }
+}
+
+/// Webhook repository trait
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn create_endpoint(
+        &self,
+        endpoint: WebhookEndpointEntity,
+    ) -> Result<WebhookEndpointEntity, AppError>;
+
+    async fn find_endpoint(&self, id: Uuid) -> Result<Option<WebhookEndpointEntity>, AppError>;
+
+    /// List endpoints, oldest first
+    async fn list_endpoints(
+        &self,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<WebhookEndpointEntity>, AppError>;
+
+    async fn count_endpoints(&self) -> Result<u64, AppError>;
+
+    /// Enabled endpoints subscribed to `event`
+    async fn find_subscribed(&self, event: &str) -> Result<Vec<WebhookEndpointEntity>, AppError>;
+
+    /// Update URL, description, secret, event filter and enabled state.
+    ///
+    /// Re-enabling an endpoint clears its failure count and disabled reason.
+    async fn update_endpoint(
+        &self,
+        endpoint: WebhookEndpointEntity,
+    ) -> Result<WebhookEndpointEntity, AppError>;
+
+    async fn delete_endpoint(&self, id: Uuid) -> Result<bool, AppError>;
+
+    /// Reset the consecutive failure count after a successful delivery
+    async fn record_success(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Count a failed attempt, disabling the endpoint once `disable_after`
+    /// consecutive failures are reached. Returns the updated endpoint.
+    async fn record_failure(
+        &self,
+        id: Uuid,
+        disable_after: u32,
+    ) -> Result<Option<WebhookEndpointEntity>, AppError>;
+
+    async fn create_delivery(
+        &self,
+        delivery: WebhookDeliveryEntity,
+    ) -> Result<WebhookDeliveryEntity, AppError>;
+
+    async fn find_delivery(&self, id: Uuid) -> Result<Option<WebhookDeliveryEntity>, AppError>;
+
+    /// Record the outcome of an attempt (status, attempts, response and timestamps)
+    async fn update_delivery(&self, delivery: &WebhookDeliveryEntity) -> Result<(), AppError>;
+
+    /// List an endpoint's deliveries, newest first
+    async fn list_deliveries(
+        &self,
+        endpoint_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<WebhookDeliveryEntity>, AppError>;
+
+    async fn count_deliveries(&self, endpoint_id: Uuid) -> Result<u64, AppError>;
+}
+
+/// Prefix for generated endpoint signing secrets
+pub const WEBHOOK_SECRET_PREFIX: &str = "whsec_";
+
+/// Generate an endpoint signing secret (prefix + 43 alphanumeric chars)
+pub fn generate_webhook_secret() -> String {
+    // SEC-08: Use OsRng for cryptographic random generation
+    let suffix: String = OsRng
+        .sample_iter(&Alphanumeric)
+        .take(43)
+        .map(char::from)
+        .collect();
+    format!("{}{}", WEBHOOK_SECRET_PREFIX, suffix)
+}
+
+/// Reason recorded when an endpoint is disabled automatically
+pub fn auto_disable_reason(failures: u32) -> String {
+    format!("Disabled after {} consecutive failed deliveries", failures)
+}
+
+/// In-memory webhook repository for development/testing
+pub struct InMemoryWebhookRepository {
+    endpoints: RwLock<HashMap<Uuid, WebhookEndpointEntity>>,
+    deliveries: RwLock<HashMap<Uuid, WebhookDeliveryEntity>>,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new() -> Self {
+        Self {
+            endpoints: RwLock::new(HashMap::new()),
+            deliveries: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryWebhookRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for InMemoryWebhookRepository {
+    async fn create_endpoint(
+        &self,
+        endpoint: WebhookEndpointEntity,
+    ) -> Result<WebhookEndpointEntity, AppError> {
+        self.endpoints
+            .write()
+            .await
+            .insert(endpoint.id, endpoint.clone());
+        Ok(endpoint)
+    }
+
+    async fn find_endpoint(&self, id: Uuid) -> Result<Option<WebhookEndpointEntity>, AppError> {
+        Ok(self.endpoints.read().await.get(&id).cloned())
+    }
+
+    async fn list_endpoints(
+        &self,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<WebhookEndpointEntity>, AppError> {
+        let mut endpoints: Vec<_> = self.endpoints.read().await.values().cloned().collect();
+        endpoints.sort_by_key(|e| e.created_at);
+        Ok(endpoints
+            .into_iter()
+            .skip(cap_offset(offset) as usize)
+            .take(cap_limit(limit) as usize)
+            .collect())
+    }
+
+    async fn count_endpoints(&self) -> Result<u64, AppError> {
+        Ok(self.endpoints.read().await.len() as u64)
+    }
+
+    async fn find_subscribed(&self, event: &str) -> Result<Vec<WebhookEndpointEntity>, AppError> {
+        let endpoints = self.endpoints.read().await;
+        Ok(endpoints
+            .values()
+            .filter(|e| e.enabled && e.subscribes_to(event))
+            .cloned()
+            .collect())
+    }
+
+    async fn update_endpoint(
+        &self,
+        endpoint: WebhookEndpointEntity,
+    ) -> Result<WebhookEndpointEntity, AppError> {
+        let mut endpoints = self.endpoints.write().await;
+        let existing = endpoints
+            .get_mut(&endpoint.id)
+            .ok_or(AppError::NotFound("Webhook endpoint not found".into()))?;
+        if endpoint.enabled && !existing.enabled {
+            existing.consecutive_failures = 0;
+            existing.disabled_reason = None;
+        }
+        existing.url = endpoint.url;
+        existing.description = endpoint.description;
+        existing.secret_encrypted = endpoint.secret_encrypted;
+        existing.events = endpoint.events;
+        existing.enabled = endpoint.enabled;
+        existing.updated_at = Utc::now();
+        Ok(existing.clone())
+    }
+
+    async fn delete_endpoint(&self, id: Uuid) -> Result<bool, AppError> {
+        let removed = self.endpoints.write().await.remove(&id).is_some();
+        if removed {
+            self.deliveries
+                .write()
+                .await
+                .retain(|_, d| d.endpoint_id != id);
+        }
+        Ok(removed)
+    }
+
+    async fn record_success(&self, id: Uuid) -> Result<(), AppError> {
+        if let Some(endpoint) = self.endpoints.write().await.get_mut(&id) {
+            endpoint.consecutive_failures = 0;
+        }
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: Uuid,
+        disable_after: u32,
+    ) -> Result<Option<WebhookEndpointEntity>, AppError> {
+        let mut endpoints = self.endpoints.write().await;
+        let Some(endpoint) = endpoints.get_mut(&id) else {
+            return Ok(None);
+        };
+        endpoint.consecutive_failures = endpoint.consecutive_failures.saturating_add(1);
+        if endpoint.enabled && endpoint.consecutive_failures >= disable_after {
+            endpoint.enabled = false;
+            endpoint.disabled_reason = Some(auto_disable_reason(endpoint.consecutive_failures));
+            endpoint.updated_at = Utc::now();
+        }
+        Ok(Some(endpoint.clone()))
+    }
+
+    async fn create_delivery(
+        &self,
+        delivery: WebhookDeliveryEntity,
+    ) -> Result<WebhookDeliveryEntity, AppError> {
+        self.deliveries
+            .write()
+            .await
+            .insert(delivery.id, delivery.clone());
+        Ok(delivery)
+    }
+
+    async fn find_delivery(&self, id: Uuid) -> Result<Option<WebhookDeliveryEntity>, AppError> {
+        Ok(self.deliveries.read().await.get(&id).cloned())
+    }
+
+    async fn update_delivery(&self, delivery: &WebhookDeliveryEntity) -> Result<(), AppError> {
+        let mut deliveries = self.deliveries.write().await;
+        let existing = deliveries
+            .get_mut(&delivery.id)
+            .ok_or(AppError::NotFound("Webhook delivery not found".into()))?;
+        existing.status = delivery.status;
+        existing.attempts = delivery.attempts;
+        existing.response_status = delivery.response_status;
+        existing.last_error = delivery.last_error.clone();
+        existing.last_attempt_at = delivery.last_attempt_at;
+        existing.delivered_at = delivery.delivered_at;
+        Ok(())
+    }
+
+    async fn list_deliveries(
+        &self,
+        endpoint_id: Uuid,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<WebhookDeliveryEntity>, AppError> {
+        let mut deliveries: Vec<_> = self
+            .deliveries
+            .read()
+            .await
+            .values()
+            .filter(|d| d.endpoint_id == endpoint_id)
+            .cloned()
+            .collect();
+        deliveries.sort_by_key(|d| std::cmp::Reverse(d.created_at));
+        Ok(deliveries
+            .into_iter()
+            .skip(cap_offset(offset) as usize)
+            .take(cap_limit(limit) as usize)
+            .collect())
+    }
+
+    async fn count_deliveries(&self, endpoint_id: Uuid) -> Result<u64, AppError> {
+        let deliveries = self.deliveries.read().await;
+        Ok(deliveries
+            .values()
+            .filter(|d| d.endpoint_id == endpoint_id)
+            .count() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint(events: &[&str]) -> WebhookEndpointEntity {
+        WebhookEndpointEntity::new(
+            "https://hooks.example.com/cedros".to_string(),
+            None,
+            "encrypted".to_string(),
+            events.iter().map(|e| e.to_string()).collect(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_find_subscribed_honours_filter_and_enabled() {
+        let repo = InMemoryWebhookRepository::new();
+        let all = repo.create_endpoint(endpoint(&[])).await.unwrap();
+        let orgs = repo
+            .create_endpoint(endpoint(&["org_created"]))
+            .await
+            .unwrap();
+        let mut disabled = endpoint(&["org_created"]);
+        disabled.enabled = false;
+        repo.create_endpoint(disabled).await.unwrap();
+
+        let mut ids: Vec<Uuid> = repo
+            .find_subscribed("org_created")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        ids.sort();
+        let mut expected = vec![all.id, orgs.id];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        let members = repo.find_subscribed("member_added").await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].id, all.id);
+    }
+
+    #[tokio::test]
+    async fn test_record_failure_disables_and_reenable_resets() {
+        let repo = InMemoryWebhookRepository::new();
+        let created = repo.create_endpoint(endpoint(&[])).await.unwrap();
+
+        for _ in 0..2 {
+            let e = repo.record_failure(created.id, 3).await.unwrap().unwrap();
+            assert!(e.enabled);
+        }
+        let disabled = repo.record_failure(created.id, 3).await.unwrap().unwrap();
+        assert!(!disabled.enabled);
+        assert!(disabled.disabled_reason.is_some());
+
+        let mut reenable = disabled.clone();
+        reenable.enabled = true;
+        let reenabled = repo.update_endpoint(reenable).await.unwrap();
+        assert!(reenabled.enabled);
+        assert_eq!(reenabled.consecutive_failures, 0);
+        assert!(reenabled.disabled_reason.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_log_and_replay() {
+        let repo = InMemoryWebhookRepository::new();
+        let created = repo.create_endpoint(endpoint(&[])).await.unwrap();
+        let mut delivery = repo
+            .create_delivery(WebhookDeliveryEntity::new(
+                created.id,
+                "org_created",
+                serde_json::json!({ "event": "org_created" }),
+            ))
+            .await
+            .unwrap();
+
+        delivery.status = WebhookDeliveryStatus::Failed;
+        delivery.attempts = 10;
+        delivery.response_status = Some(500);
+        repo.update_delivery(&delivery).await.unwrap();
+
+        let replay = repo.create_delivery(delivery.replay()).await.unwrap();
+        assert_eq!(replay.replay_of, Some(delivery.id));
+        assert_eq!(replay.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(repo.count_deliveries(created.id).await.unwrap(), 2);
+
+        assert!(repo.delete_endpoint(created.id).await.unwrap());
+        assert!(repo.find_delivery(delivery.id).await.unwrap().is_none());
+    }
+}
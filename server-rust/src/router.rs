@@ -2,7 +2,7 @@
 
 use axum::http::{header, HeaderValue, Method};
 use axum::{
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::sync::Arc;
@@ -20,7 +20,7 @@ use crate::middleware::rate_limit::{
     RateLimitStore,
 };
 use crate::middleware::request_id::RequestIdLayer;
-use crate::middleware::CsrfLayer;
+use crate::middleware::{impersonation_guard, peer_ip_scope, CsrfLayer};
 use crate::services::EmailService;
 use crate::AppState;
 
@@ -196,6 +196,11 @@ pub fn create_router<C: AuthCallback + 'static, E: EmailService + 'static>(
     } else {
         auth_routes.merge(general_routes).merge(credit_routes)
     };
+    // Read-only enforcement and audit tagging for impersonation sessions
+    let base_router = base_router.layer(axum::middleware::from_fn_with_state(
+        state.clone(),
+        impersonation_guard::<C, E>,
+    ));
 
     let base_path = state.config.server.auth_base_path.trim_end_matches('/');
     let base_path = if base_path.is_empty() { "/" } else { base_path };
@@ -215,6 +220,7 @@ pub fn create_router<C: AuthCallback + 'static, E: EmailService + 'static>(
 
     // Apply common middleware layers
     let router = routed
+        .layer(axum::middleware::from_fn(peer_ip_scope))
         .layer(CsrfLayer::new(state.config.cookie.clone()))
         .layer(RequestIdLayer::new())
         .layer(TraceLayer::new_for_http());
@@ -284,6 +290,11 @@ fn auth_sensitive_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             "/api-key/validate",
             post(handlers::validate_api_key::<C, E>),
         )
+        // Service account client-credentials exchange (public endpoint)
+        .route(
+            "/service-accounts/token",
+            post(handlers::service_account_token::<C, E>),
+        )
         // S-08: MFA code verification endpoints need strict rate limiting
         // to prevent brute-force attacks on TOTP codes
         .route("/mfa/verify", post(handlers::verify_mfa::<C, E>))
@@ -379,7 +390,20 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
         // M-02: Granular logout - revoke all sessions at once
         .route("/logout-all", post(handlers::logout_all::<C, E>))
         .route("/user", get(handlers::get_user::<C, E>))
-        .route("/me", patch(handlers::update_profile::<C, E>))
+        .route(
+            "/me",
+            patch(handlers::update_profile::<C, E>).delete(handlers::delete_account::<C, E>),
+        )
+        .route("/me/export", get(handlers::export_account_data::<C, E>))
+        .route("/me/email", post(handlers::request_email_change::<C, E>))
+        .route(
+            "/me/email/confirm",
+            post(handlers::confirm_email_change::<C, E>),
+        )
+        .route(
+            "/me/email/revert",
+            post(handlers::revert_email_change::<C, E>),
+        )
         .route(
             "/send-verification",
             post(handlers::send_verification::<C, E>),
@@ -412,6 +436,10 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
                 .delete(handlers::delete_org::<C, E>),
         )
         .route("/orgs/{org_id}/switch", post(handlers::switch_org::<C, E>))
+        .route(
+            "/orgs/{org_id}/restore",
+            post(handlers::restore_org::<C, E>),
+        )
         // Member routes
         .route(
             "/orgs/{org_id}/members",
@@ -421,6 +449,10 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             "/orgs/{org_id}/members/{user_id}",
             patch(handlers::update_member_role::<C, E>).delete(handlers::remove_member::<C, E>),
         )
+        .route(
+            "/orgs/{org_id}/members/{user_id}/impersonate",
+            post(handlers::impersonate_member::<C, E>),
+        )
         // Custom role routes
         .route(
             "/orgs/{org_id}/roles",
@@ -436,6 +468,41 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             "/orgs/{org_id}/roles/{role_id}/default",
             post(handlers::set_default_role::<C, E>),
         )
+        // Organization credit balance settings
+        .route(
+            "/orgs/{org_id}/credits/members",
+            get(handlers::list_org_credit_members::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/credits/members/{user_id}",
+            delete(handlers::delete_org_credit_member::<C, E>)
+                .put(handlers::update_org_credit_member::<C, E>),
+        )
+        // Service account routes
+        .route(
+            "/orgs/{org_id}/service-accounts",
+            get(handlers::list_service_accounts::<C, E>)
+                .post(handlers::create_service_account::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/service-accounts/{id}",
+            get(handlers::get_service_account::<C, E>)
+                .patch(handlers::update_service_account::<C, E>)
+                .delete(handlers::delete_service_account::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/service-accounts/{id}/rotate-secret",
+            post(handlers::rotate_service_account_secret::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/service-accounts/{id}/api-keys",
+            get(handlers::list_service_account_api_keys::<C, E>)
+                .post(handlers::create_service_account_api_key::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/service-accounts/{id}/api-keys/{key_id}",
+            delete(handlers::delete_service_account_api_key::<C, E>),
+        )
         // ABAC Policy routes
         .route(
             "/orgs/{org_id}/policies",
@@ -447,11 +514,41 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
                 .patch(handlers::update_policy::<C, E>)
                 .delete(handlers::delete_policy::<C, E>),
         )
+        // Relationship (resource-level authorization) routes
+        .route(
+            "/orgs/{org_id}/relationships",
+            get(handlers::list_relationships::<C, E>).post(handlers::write_relationships::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/relationships/delete",
+            post(handlers::delete_relationships::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/relationships/schema",
+            get(handlers::get_relationship_schema::<C, E>)
+                .put(handlers::put_relationship_schema::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/relationships/check",
+            post(handlers::check_relationship::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/relationships/expand",
+            post(handlers::expand_relationship::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/relationships/list-objects",
+            post(handlers::list_related_objects::<C, E>),
+        )
         // Invite routes
         .route(
             "/orgs/{org_id}/invites",
             get(handlers::list_invites::<C, E>).post(handlers::create_invite::<C, E>),
         )
+        .route(
+            "/orgs/{org_id}/invites/bulk",
+            post(handlers::bulk_create_invites::<C, E>),
+        )
         .route(
             "/orgs/{org_id}/invites/{invite_id}",
             delete(handlers::cancel_invite::<C, E>),
@@ -461,6 +558,19 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             post(handlers::resend_invite::<C, E>),
         )
         .route("/invites/accept", post(handlers::accept_invite::<C, E>))
+        // Invite link routes
+        .route(
+            "/orgs/{org_id}/invite-links",
+            get(handlers::list_invite_links::<C, E>).post(handlers::create_invite_link::<C, E>),
+        )
+        .route(
+            "/orgs/{org_id}/invite-links/{link_id}",
+            delete(handlers::revoke_invite_link::<C, E>),
+        )
+        .route(
+            "/invite-links/accept",
+            post(handlers::accept_invite_link::<C, E>),
+        )
         // Authorization routes
         .route("/authorize", post(handlers::authorize::<C, E>))
         .route("/permissions", post(handlers::get_permissions::<C, E>))
@@ -469,6 +579,11 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             "/sessions",
             get(handlers::list_sessions::<C, E>).delete(handlers::revoke_all_sessions::<C, E>),
         )
+        // Impersonation (started from admin or org member routes)
+        .route(
+            "/impersonation/end",
+            post(handlers::end_impersonation::<C, E>),
+        )
         // MFA routes (management only - verify/recovery in auth routes for stricter rate limiting)
         .route("/mfa/setup", post(handlers::setup_mfa::<C, E>))
         .route("/mfa/enable", post(handlers::enable_mfa::<C, E>))
@@ -484,6 +599,10 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             get(handlers::get_org_audit_logs::<C, E>),
         )
         .route("/admin/audit", get(handlers::get_system_audit_logs::<C, E>))
+        .route(
+            "/admin/audit/verify",
+            get(handlers::verify_audit_chain::<C, E>),
+        )
         // Admin management routes
         .route("/admin/users", get(handlers::list_users::<C, E>))
         .route("/admin/users/stats", get(handlers::get_user_stats::<C, E>))
@@ -493,10 +612,18 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
                 .patch(handlers::update_user::<C, E>)
                 .delete(handlers::delete_user::<C, E>),
         )
+        .route(
+            "/admin/users/{user_id}/impersonate",
+            post(handlers::impersonate_user::<C, E>),
+        )
         .route(
             "/admin/users/{user_id}/system-admin",
             patch(handlers::set_system_admin::<C, E>),
         )
+        .route(
+            "/admin/users/{user_id}/status",
+            post(handlers::set_user_status::<C, E>),
+        )
         .route(
             "/admin/users/{user_id}/force-password-reset",
             post(handlers::force_password_reset::<C, E>),
@@ -505,6 +632,14 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             "/admin/users/{user_id}/credits",
             get(handlers::get_user_credits::<C, E>).post(handlers::adjust_credits::<C, E>),
         )
+        .route(
+            "/admin/users/{user_id}/credits/grants",
+            get(handlers::list_credit_grants::<C, E>).post(handlers::create_credit_grant::<C, E>),
+        )
+        .route(
+            "/admin/users/{user_id}/credits/grants/{grant_id}",
+            delete(handlers::revoke_credit_grant::<C, E>),
+        )
         .route(
             "/admin/users/{user_id}/deposits",
             get(handlers::get_user_deposits::<C, E>),
@@ -515,6 +650,10 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
         )
         .route("/admin/orgs", get(handlers::list_admin_orgs::<C, E>))
         .route("/admin/orgs/{org_id}", get(handlers::get_admin_org::<C, E>))
+        .route(
+            "/admin/orgs/{org_id}/credits",
+            post(handlers::adjust_org_credits::<C, E>),
+        )
         // SSO provider management routes (system admin)
         .route(
             "/admin/sso-providers",
@@ -526,6 +665,59 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
                 .put(handlers::update_sso_provider::<C, E>)
                 .delete(handlers::delete_sso_provider::<C, E>),
         )
+        // Webhook endpoint management routes (system admin)
+        .route(
+            "/admin/webhooks/events",
+            get(handlers::list_webhook_events::<C, E>),
+        )
+        .route(
+            "/admin/webhooks",
+            get(handlers::list_webhook_endpoints::<C, E>)
+                .post(handlers::create_webhook_endpoint::<C, E>),
+        )
+        .route(
+            "/admin/webhooks/{id}",
+            get(handlers::get_webhook_endpoint::<C, E>)
+                .patch(handlers::update_webhook_endpoint::<C, E>)
+                .delete(handlers::delete_webhook_endpoint::<C, E>),
+        )
+        .route(
+            "/admin/webhooks/{id}/deliveries",
+            get(handlers::list_webhook_deliveries::<C, E>),
+        )
+        .route(
+            "/admin/webhooks/deliveries/{delivery_id}/replay",
+            post(handlers::replay_webhook_delivery::<C, E>),
+        )
+        // Usage meter catalog routes (system admin)
+        .route(
+            "/admin/meters",
+            get(handlers::list_meters::<C, E>).post(handlers::create_meter::<C, E>),
+        )
+        .route(
+            "/admin/meters/{meter_id}",
+            get(handlers::get_meter::<C, E>).patch(handlers::update_meter::<C, E>),
+        )
+        .route(
+            "/admin/meters/{meter_id}/overrides",
+            get(handlers::list_meter_price_overrides::<C, E>),
+        )
+        .route(
+            "/admin/meters/{meter_id}/overrides/{org_id}",
+            put(handlers::set_meter_price_override::<C, E>)
+                .delete(handlers::delete_meter_price_override::<C, E>),
+        )
+        // Subscription plan catalog routes (system admin)
+        .route(
+            "/admin/subscription-plans",
+            get(handlers::list_subscription_plans_admin::<C, E>)
+                .post(handlers::create_subscription_plan::<C, E>),
+        )
+        .route(
+            "/admin/subscription-plans/{plan_id}",
+            get(handlers::get_subscription_plan::<C, E>)
+                .patch(handlers::update_subscription_plan::<C, E>),
+        )
         // Admin system settings routes (system admin)
         .route(
             "/admin/settings",
@@ -585,6 +777,26 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             "/admin/credits/refund-requests/{id}/reject",
             post(handlers::reject_credit_refund_request::<C, E>),
         )
+        .route(
+            "/admin/credits/ledger/trial-balance",
+            get(handlers::get_trial_balance::<C, E>),
+        )
+        .route(
+            "/admin/credits/ledger/statement",
+            get(handlers::get_ledger_statement::<C, E>),
+        )
+        .route(
+            "/admin/credits/ledger/reconciliation",
+            get(handlers::get_credit_reconciliation::<C, E>),
+        )
+        .route(
+            "/admin/credits/budgets",
+            get(handlers::list_credit_budgets::<C, E>).put(handlers::set_credit_budget::<C, E>),
+        )
+        .route(
+            "/admin/credits/budgets/{id}",
+            delete(handlers::delete_credit_budget::<C, E>),
+        )
         // Admin privacy status route (system admin)
         .route(
             "/admin/privacy/status",
@@ -685,6 +897,39 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             "/credits/refund-request",
             post(handlers::request_refund::<C, E>),
         )
+        .route(
+            "/credits/transfers",
+            post(handlers::create_credit_transfer::<C, E>),
+        )
+        .route(
+            "/credits/statements",
+            get(handlers::get_credit_statement::<C, E>),
+        )
+        .route(
+            "/credits/statements/email",
+            post(handlers::email_credit_statement::<C, E>),
+        )
+        // Subscription routes (user JWT)
+        .route(
+            "/credits/plans",
+            get(handlers::list_subscription_plans::<C, E>),
+        )
+        .route(
+            "/credits/subscriptions",
+            get(handlers::list_subscriptions::<C, E>).post(handlers::create_subscription::<C, E>),
+        )
+        .route(
+            "/credits/subscriptions/{id}/cancel",
+            post(handlers::cancel_subscription::<C, E>),
+        )
+        .route(
+            "/credits/top-ups",
+            get(handlers::list_stripe_topups::<C, E>),
+        )
+        .route(
+            "/credits/top-ups/stripe",
+            post(handlers::create_stripe_topup::<C, E>),
+        )
         // User lookup routes (admin API key)
         .route(
             "/users/by-wallet/{wallet_address}",
@@ -698,11 +943,19 @@ fn general_routes<C: AuthCallback + 'static, E: EmailService + 'static>(
             "/users/by-stripe-customer/{stripe_customer_id}/link",
             post(handlers::link_stripe_customer::<C, E>),
         )
+        .route(
+            "/users/{user_id}/metadata",
+            get(handlers::get_user_metadata::<C, E>).patch(handlers::update_user_metadata::<C, E>),
+        )
         // Webhook routes (external service callbacks)
         .route(
             "/webhook/deposit",
             post(handlers::handle_deposit_webhook::<C, E>),
         )
+        .route(
+            "/webhook/stripe",
+            post(handlers::handle_stripe_webhook::<C, E>),
+        )
 }
 
 /// Credit operations routes with dedicated rate limiting
@@ -724,10 +977,18 @@ fn credit_operations_routes<C: AuthCallback + 'static, E: EmailService + 'static
             "/credits/capture/{hold_id}",
             post(handlers::capture_hold::<C, E>),
         )
+        .route(
+            "/credits/extend/{hold_id}",
+            post(handlers::extend_hold::<C, E>),
+        )
         .route(
             "/credits/release/{hold_id}",
             post(handlers::release_hold::<C, E>),
         )
+        .route(
+            "/credits/usage-events",
+            post(handlers::record_usage::<C, E>),
+        )
 }
 
 /// Create the appropriate rate limit backend based on configuration
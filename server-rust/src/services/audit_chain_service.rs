@@ -0,0 +1,461 @@
+//! Audit hash chain checkpoints and verification
+//!
+//! The audit repository links every entry to its predecessor (see
+//! `AuditChainLink`). That alone detects edits and deletions in the middle of
+//! the log, but someone with database access could rewrite the tail and
+//! recompute every hash after it. Checkpoints close that gap: the chain head is
+//! periodically signed with an Ed25519 key derived from the server secret, and
+//! verification requires every checkpoint to still match the stored entry.
+
+use chrono::Utc;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{
+    compute_entry_hash, AuditChainLink, AuditCheckpoint, AuditLogEntry, AuditLogRepository,
+    AUDIT_GENESIS_HASH,
+};
+
+/// Key derivation info for the checkpoint signing key
+const CHECKPOINT_KEY_INFO: &[u8] = b"cedros:audit_checkpoint_ed25519:v1";
+
+/// Entries fetched per batch while walking the chain
+const VERIFY_BATCH_SIZE: u32 = 1000;
+
+/// First point at which the chain fails verification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditChainBreak {
+    /// Sequence number of the entry (or checkpoint) that failed
+    pub seq: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<Uuid>,
+    pub reason: String,
+}
+
+/// Result of verifying the audit hash chain
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditChainReport {
+    pub valid: bool,
+    /// Org whose chain was verified; `None` for the global chain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
+    pub entries_checked: u64,
+    /// Entries whose personal fields were cleared; everything but those
+    /// fields is still re-hashed
+    pub redacted_entries: u64,
+    pub first_seq: Option<i64>,
+    pub last_seq: Option<i64>,
+    /// Set when the oldest entries were removed by retention, so the chain
+    /// starts part-way through
+    pub pruned_before_seq: Option<i64>,
+    pub checkpoints_verified: u64,
+    pub last_checkpoint_seq: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken_link: Option<AuditChainBreak>,
+}
+
+impl AuditChainReport {
+    fn new(org_id: Option<Uuid>) -> Self {
+        Self {
+            valid: false,
+            org_id,
+            entries_checked: 0,
+            redacted_entries: 0,
+            first_seq: None,
+            last_seq: None,
+            pruned_before_seq: None,
+            checkpoints_verified: 0,
+            last_checkpoint_seq: None,
+            broken_link: None,
+        }
+    }
+
+    fn broken(mut self, seq: i64, entry_id: Option<Uuid>, reason: String) -> Self {
+        self.valid = false;
+        self.broken_link = Some(AuditChainBreak {
+            seq,
+            entry_id,
+            reason,
+        });
+        self
+    }
+}
+
+/// Service for signing checkpoints of the audit chain and verifying it
+pub struct AuditChainService {
+    repo: Arc<dyn AuditLogRepository>,
+    signing_key: SigningKey,
+}
+
+impl AuditChainService {
+    /// Create the service, deriving the checkpoint signing key from `secret`
+    ///
+    /// Uses the same HMAC-based derivation with domain separation as
+    /// `TokenCipher`, so the key changes if the server secret is rotated;
+    /// checkpoints signed under the old key then fail verification.
+    pub fn new(repo: Arc<dyn AuditLogRepository>, secret: &str) -> Self {
+        let mut mac: Hmac<Sha256> =
+            Mac::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+        mac.update(CHECKPOINT_KEY_INFO);
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&mac.finalize().into_bytes());
+
+        Self {
+            repo,
+            signing_key: SigningKey::from_bytes(&seed),
+        }
+    }
+
+    /// Hex Ed25519 public key that checkpoints are verified against
+    pub fn public_key(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().to_bytes())
+    }
+
+    /// Sign the current chain head, unless it is already covered by the
+    /// latest checkpoint
+    pub async fn checkpoint(&self) -> Result<Option<AuditCheckpoint>, AppError> {
+        let Some(head) = self.repo.chain_head().await? else {
+            return Ok(None);
+        };
+        let checkpoints = self.repo.list_checkpoints().await?;
+        if checkpoints.last().is_some_and(|c| c.seq >= head.seq) {
+            return Ok(None);
+        }
+
+        let signature = self
+            .signing_key
+            .sign(checkpoint_message(head.seq, &head.entry_hash).as_bytes());
+        let checkpoint = AuditCheckpoint {
+            id: Uuid::new_v4(),
+            seq: head.seq,
+            entry_hash: head.entry_hash,
+            public_key: self.public_key(),
+            signature: hex::encode(signature.to_bytes()),
+            created_at: Utc::now(),
+        };
+        self.repo.create_checkpoint(checkpoint).await.map(Some)
+    }
+
+    /// Walk the chain (globally, or for one org) and report the first broken
+    /// link, if any
+    pub async fn verify(&self, org_id: Option<Uuid>) -> Result<AuditChainReport, AppError> {
+        let mut report = AuditChainReport::new(org_id);
+
+        let mut signed: BTreeMap<i64, String> = BTreeMap::new();
+        for checkpoint in self.repo.list_checkpoints().await? {
+            if let Err(reason) = self.verify_checkpoint(&checkpoint) {
+                return Ok(report.broken(checkpoint.seq, None, reason));
+            }
+            if let Some(existing) = signed.get(&checkpoint.seq) {
+                if *existing != checkpoint.entry_hash {
+                    let reason = "Conflicting checkpoints for the same entry".to_string();
+                    return Ok(report.broken(checkpoint.seq, None, reason));
+                }
+            }
+            report.checkpoints_verified += 1;
+            report.last_checkpoint_seq = Some(checkpoint.seq);
+            signed.insert(checkpoint.seq, checkpoint.entry_hash);
+        }
+
+        let mut prev: Option<AuditChainLink> = None;
+        let mut after_seq = 0;
+        loop {
+            let batch = self
+                .repo
+                .list_chain(org_id, after_seq, VERIFY_BATCH_SIZE)
+                .await?;
+            if batch.is_empty() {
+                break;
+            }
+
+            for entry in &batch {
+                let Some(link) = entry.chain.as_ref() else {
+                    continue;
+                };
+                after_seq = link.seq;
+
+                if let Err(reason) = check_entry(org_id, prev.as_ref(), entry, link) {
+                    return Ok(report.broken(link.seq, Some(entry.id), reason));
+                }
+                if let Some(expected) = signed.get(&link.seq) {
+                    if *expected != link.entry_hash {
+                        let reason = "Entry does not match its signed checkpoint".to_string();
+                        return Ok(report.broken(link.seq, Some(entry.id), reason));
+                    }
+                }
+
+                if prev.is_none() {
+                    report.first_seq = Some(link.seq);
+                    if starts_mid_chain(org_id, link) {
+                        report.pruned_before_seq = Some(link.seq);
+                    }
+                }
+                report.last_seq = Some(link.seq);
+                report.entries_checked += 1;
+                if link.redacted {
+                    report.redacted_entries += 1;
+                }
+                prev = Some(link.clone());
+            }
+        }
+
+        // A signed head that is no longer present means the tail was cut off
+        if org_id.is_none() {
+            if let Some(&seq) = signed.keys().next_back() {
+                if report.last_seq.map_or(true, |last| last < seq) {
+                    let reason = "Entries covered by a signed checkpoint are missing".to_string();
+                    return Ok(report.broken(seq, None, reason));
+                }
+            }
+        }
+
+        report.valid = true;
+        Ok(report)
+    }
+
+    fn verify_checkpoint(&self, checkpoint: &AuditCheckpoint) -> Result<(), String> {
+        if checkpoint.public_key != self.public_key() {
+            return Err("Checkpoint was signed with an unknown key".to_string());
+        }
+        let signature = hex::decode(&checkpoint.signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| "Checkpoint signature is malformed".to_string())?;
+        let key: VerifyingKey = self.signing_key.verifying_key();
+        key.verify(
+            checkpoint_message(checkpoint.seq, &checkpoint.entry_hash).as_bytes(),
+            &signature,
+        )
+        .map_err(|_| "Checkpoint signature is invalid".to_string())
+    }
+}
+
+/// Message covered by a checkpoint signature
+fn checkpoint_message(seq: i64, entry_hash: &str) -> String {
+    format!("cedros-audit-checkpoint:v1:{}:{}", seq, entry_hash)
+}
+
+/// Check an entry's own hashes and its link to the previous entry in the
+/// chain being walked
+fn check_entry(
+    org_id: Option<Uuid>,
+    prev: Option<&AuditChainLink>,
+    entry: &AuditLogEntry,
+    link: &AuditChainLink,
+) -> Result<(), String> {
+    match (org_id, prev) {
+        (None, Some(prev)) if link.seq != prev.seq + 1 => {
+            return Err(format!(
+                "Entries {} to {} are missing",
+                prev.seq + 1,
+                link.seq - 1
+            ));
+        }
+        (None, Some(prev)) if link.prev_hash != prev.entry_hash => {
+            return Err(format!("Previous hash does not match entry {}", prev.seq));
+        }
+        (None, None) if link.seq == 1 && link.prev_hash != AUDIT_GENESIS_HASH => {
+            return Err("First entry does not start from the genesis hash".to_string());
+        }
+        (Some(_), Some(prev))
+            if link.org_prev_hash.as_deref() != Some(prev.entry_hash.as_str()) =>
+        {
+            return Err(format!(
+                "Org previous hash does not match entry {}; an org entry is missing or modified",
+                prev.seq
+            ));
+        }
+        _ => {}
+    }
+
+    // The kept fields are re-hashed on every entry, redacted or not
+    if entry.compute_content_hash(link.seq, &link.personal_hash) != link.content_hash {
+        return Err("Entry content does not match its hash".to_string());
+    }
+    if link.redacted {
+        // The flag alone proves nothing; the personal fields must really be
+        // cleared, since they can no longer be checked against the hash
+        if !entry.has_redacted_shape() {
+            return Err("Entry is marked redacted but still has personal fields".to_string());
+        }
+    } else if entry.compute_personal_hash() != link.personal_hash {
+        return Err("Entry content does not match its hash".to_string());
+    }
+    let entry_hash = compute_entry_hash(
+        &link.prev_hash,
+        link.org_prev_hash.as_deref(),
+        &link.content_hash,
+    );
+    if entry_hash != link.entry_hash {
+        return Err("Entry hash does not match its content and predecessors".to_string());
+    }
+    Ok(())
+}
+
+/// Whether the first entry found links to something that is no longer stored
+fn starts_mid_chain(org_id: Option<Uuid>, link: &AuditChainLink) -> bool {
+    match org_id {
+        None => link.seq != 1,
+        Some(_) => link.org_prev_hash.as_deref() != Some(AUDIT_GENESIS_HASH),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{AuditEventType, AuditLogBuilder, InMemoryAuditLogRepository};
+
+    const SECRET: &str = "test-secret-key-that-is-long-enough";
+
+    async fn seeded(org_id: Uuid) -> (AuditChainService, Arc<InMemoryAuditLogRepository>) {
+        let repo = Arc::new(InMemoryAuditLogRepository::new());
+        for i in 0..5 {
+            let mut builder = AuditLogBuilder::new(AuditEventType::UserLogin)
+                .metadata(serde_json::json!({ "i": i }));
+            if i % 2 == 0 {
+                builder = builder.org(org_id);
+            }
+            repo.create(builder.build()).await.unwrap();
+        }
+        (AuditChainService::new(repo.clone(), SECRET), repo)
+    }
+
+    async fn entry_at(repo: &InMemoryAuditLogRepository, seq: i64) -> AuditLogEntry {
+        repo.list_chain(None, seq - 1, 1).await.unwrap().remove(0)
+    }
+
+    #[tokio::test]
+    async fn test_intact_chain_verifies() {
+        let org_id = Uuid::new_v4();
+        let (service, _repo) = seeded(org_id).await;
+        service.checkpoint().await.unwrap().unwrap();
+        // Nothing new to sign
+        assert!(service.checkpoint().await.unwrap().is_none());
+
+        let report = service.verify(None).await.unwrap();
+        assert!(report.valid, "{:?}", report.broken_link);
+        assert_eq!(report.entries_checked, 5);
+        assert_eq!((report.first_seq, report.last_seq), (Some(1), Some(5)));
+        assert_eq!(report.last_checkpoint_seq, Some(5));
+
+        let report = service.verify(Some(org_id)).await.unwrap();
+        assert!(report.valid);
+        assert_eq!(report.entries_checked, 3);
+    }
+
+    #[tokio::test]
+    async fn test_detects_modified_entry() {
+        let (service, repo) = seeded(Uuid::new_v4()).await;
+        let mut entry = entry_at(&repo, 3).await;
+        entry.metadata = serde_json::json!({ "i": 99 });
+        repo.overwrite(entry.clone()).await;
+
+        let report = service.verify(None).await.unwrap();
+        assert!(!report.valid);
+        let broken = report.broken_link.unwrap();
+        assert_eq!(broken.seq, 3);
+        assert_eq!(broken.entry_id, Some(entry.id));
+    }
+
+    #[tokio::test]
+    async fn test_detects_deleted_entry_globally_and_per_org() {
+        let org_id = Uuid::new_v4();
+        let (service, repo) = seeded(org_id).await;
+        repo.remove(entry_at(&repo, 3).await.id).await;
+
+        let broken = service.verify(None).await.unwrap().broken_link.unwrap();
+        assert_eq!(broken.seq, 4);
+        assert_eq!(broken.reason, "Entries 3 to 3 are missing");
+
+        let broken = service
+            .verify(Some(org_id))
+            .await
+            .unwrap()
+            .broken_link
+            .unwrap();
+        assert_eq!(broken.seq, 5);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_detects_rewritten_tail() {
+        let (service, repo) = seeded(Uuid::new_v4()).await;
+        service.checkpoint().await.unwrap();
+
+        // Rewrite the last entry and recompute its hashes consistently
+        let mut entry = entry_at(&repo, 5).await;
+        let link = entry.chain.clone().unwrap();
+        entry.metadata = serde_json::json!({ "i": 99 });
+        entry.link(link.seq, &link.prev_hash, link.org_prev_hash.as_deref());
+        repo.overwrite(entry).await;
+
+        let broken = service.verify(None).await.unwrap().broken_link.unwrap();
+        assert_eq!(broken.seq, 5);
+        assert_eq!(broken.reason, "Entry does not match its signed checkpoint");
+
+        // Truncating the tail instead is caught as well
+        repo.remove(entry_at(&repo, 5).await.id).await;
+        let broken = service.verify(None).await.unwrap().broken_link.unwrap();
+        assert_eq!(broken.seq, 5);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_checkpoint_from_other_key() {
+        let (service, repo) = seeded(Uuid::new_v4()).await;
+        let other = AuditChainService::new(repo.clone(), "a-different-secret-key-entirely");
+        other.checkpoint().await.unwrap();
+
+        let broken = service.verify(None).await.unwrap().broken_link.unwrap();
+        assert_eq!(broken.reason, "Checkpoint was signed with an unknown key");
+    }
+
+    #[tokio::test]
+    async fn test_pruned_prefix_and_redaction_still_verify() {
+        let org_id = Uuid::new_v4();
+        let (service, repo) = seeded(org_id).await;
+        repo.remove(entry_at(&repo, 1).await.id).await;
+        repo.redact_by_org(org_id).await.unwrap();
+
+        let report = service.verify(None).await.unwrap();
+        assert!(report.valid, "{:?}", report.broken_link);
+        assert_eq!(report.pruned_before_seq, Some(2));
+        assert_eq!(report.redacted_entries, 2);
+    }
+
+    #[tokio::test]
+    async fn test_redacted_flag_does_not_hide_edits() {
+        let org_id = Uuid::new_v4();
+        let (service, repo) = seeded(org_id).await;
+
+        // Flagging an entry as redacted does not exempt its kept fields
+        let mut entry = entry_at(&repo, 2).await;
+        entry.redact();
+        entry.event_type = AuditEventType::UserLogout;
+        repo.overwrite(entry).await;
+        let broken = service.verify(None).await.unwrap().broken_link.unwrap();
+        assert_eq!(broken.seq, 2);
+        assert_eq!(broken.reason, "Entry content does not match its hash");
+
+        // Nor can the flag be set while personal fields are rewritten
+        let (service, repo) = seeded(org_id).await;
+        let mut entry = entry_at(&repo, 3).await;
+        entry.metadata = serde_json::json!({ "i": 99 });
+        if let Some(chain) = entry.chain.as_mut() {
+            chain.redacted = true;
+        }
+        repo.overwrite(entry).await;
+        let broken = service.verify(None).await.unwrap().broken_link.unwrap();
+        assert_eq!(broken.seq, 3);
+        assert_eq!(
+            broken.reason,
+            "Entry is marked redacted but still has personal fields"
+        );
+    }
+}
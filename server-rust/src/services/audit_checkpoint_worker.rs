@@ -0,0 +1,98 @@
+//! Background worker for signing audit chain checkpoints
+//!
+//! Periodically signs the head of the audit hash chain so that a rewritten
+//! or truncated tail is detected by verification. A poll is a no-op when no
+//! entries were written since the last checkpoint.
+//!
+//! Default poll interval: 15 minutes
+
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info};
+
+use crate::errors::AppError;
+use crate::services::AuditChainService;
+
+/// Default poll interval for the checkpoint worker (15 minutes)
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 900;
+
+/// Configuration for the audit checkpoint worker
+#[derive(Debug, Clone)]
+pub struct AuditCheckpointConfig {
+    /// How often to sign the chain head (seconds)
+    pub poll_interval_secs: u64,
+}
+
+impl Default for AuditCheckpointConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+        }
+    }
+}
+
+/// Background worker that signs audit chain checkpoints
+pub struct AuditCheckpointWorker {
+    chain_service: AuditChainService,
+    config: AuditCheckpointConfig,
+}
+
+impl AuditCheckpointWorker {
+    /// Create a new audit checkpoint worker
+    pub fn new(chain_service: AuditChainService, config: AuditCheckpointConfig) -> Self {
+        Self {
+            chain_service,
+            config,
+        }
+    }
+
+    /// Start the worker as a background task with graceful shutdown support
+    pub fn start(self, cancel_token: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
+
+            info!(
+                poll_interval_secs = self.config.poll_interval_secs,
+                "Audit checkpoint worker started"
+            );
+
+            loop {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        // Sign whatever was written since the last poll
+                        if let Err(e) = self.checkpoint().await {
+                            error!(error = %e, "Failed to sign audit checkpoint on shutdown");
+                        }
+                        info!("Audit checkpoint worker shutting down");
+                        break;
+                    }
+                    _ = tokio::time::sleep(poll_interval) => {
+                        if let Err(e) = self.checkpoint().await {
+                            error!(error = %e, "Failed to sign audit checkpoint");
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    async fn checkpoint(&self) -> Result<(), AppError> {
+        match self.chain_service.checkpoint().await? {
+            Some(checkpoint) => info!(seq = checkpoint.seq, "Signed audit chain checkpoint"),
+            None => debug!("No new audit entries to checkpoint"),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = AuditCheckpointConfig::default();
+        assert_eq!(config.poll_interval_secs, 900);
+    }
+}
@@ -1,36 +1,146 @@
 //! Audit service for logging events
 
 use axum::http::HeaderMap;
+use std::future::Future;
 use std::sync::Arc;
+use tokio::sync::Notify;
 use uuid::Uuid;
 
+use crate::callback::WebhookEvent;
 use crate::errors::AppError;
-use crate::repositories::{AuditEventType, AuditLogBuilder, AuditLogEntry, AuditLogRepository};
+use crate::repositories::{
+    AuditActorType, AuditEventType, AuditLogBuilder, AuditLogEntry, AuditLogRepository,
+    ServiceAccountRepository,
+};
+use crate::services::{webhook_event_for_audit, WebhookService};
 use crate::utils::extract_client_ip;
 
+tokio::task_local! {
+    static IMPERSONATION: ImpersonationTag;
+}
+
+/// Impersonation context of the request being handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImpersonationTag {
+    /// User ID of the impersonator
+    pub impersonator_id: Uuid,
+    /// Impersonation session ID
+    pub session_id: Uuid,
+}
+
+/// Run `fut` with every audit entry it writes tagged with `tag`.
+///
+/// Used by the impersonation middleware so that events logged deep inside
+/// handlers record who was really acting.
+pub async fn with_impersonation<F: Future>(tag: ImpersonationTag, fut: F) -> F::Output {
+    IMPERSONATION.scope(tag, fut).await
+}
+
 /// Service for audit logging
 pub struct AuditService {
     repo: Arc<dyn AuditLogRepository>,
     trust_proxy: bool,
+    service_account_repo: Option<Arc<dyn ServiceAccountRepository>>,
+    webhooks: Option<Arc<WebhookService>>,
+    sink_notify: Option<Arc<Notify>>,
 }
 
 impl AuditService {
     pub fn new(repo: Arc<dyn AuditLogRepository>, trust_proxy: bool) -> Self {
-        Self { repo, trust_proxy }
+        Self {
+            repo,
+            trust_proxy,
+            service_account_repo: None,
+            webhooks: None,
+            sink_notify: None,
+        }
+    }
+
+    /// Record actors that are service accounts with `AuditActorType::ServiceAccount`
+    pub fn with_service_accounts(mut self, repo: Arc<dyn ServiceAccountRepository>) -> Self {
+        self.service_account_repo = Some(repo);
+        self
+    }
+
+    /// Queue webhook deliveries for audited events that have a webhook
+    /// counterpart (org, member, invite, MFA, wallet and session events)
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookService>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Wake audit sink workers after each write instead of waiting for
+    /// their next poll
+    pub fn with_sink_notify(mut self, notify: Arc<Notify>) -> Self {
+        self.sink_notify = Some(notify);
+        self
     }
 
     /// Log an event with full details
     pub async fn log(&self, entry: AuditLogEntry) -> Result<(), AppError> {
+        let entry = tag_impersonation(self.resolve_actor_type(entry).await);
+        let webhook = self.webhook_for(&entry);
         self.repo.create(entry).await?;
+        self.notify_sinks();
+        self.send_webhook(webhook).await;
         Ok(())
     }
 
     /// M-01: Fire-and-forget log helper that logs warnings on failure.
     /// Use when audit logging failures should not block the main operation.
     pub async fn log_or_warn(&self, entry: AuditLogEntry) {
+        let entry = tag_impersonation(self.resolve_actor_type(entry).await);
+        let webhook = self.webhook_for(&entry);
         if let Err(e) = self.repo.create(entry).await {
             tracing::warn!(error = %e, "Audit log write failed (non-fatal)");
+            return;
         }
+        self.notify_sinks();
+        self.send_webhook(webhook).await;
+    }
+
+    /// Webhook event and data for an entry, if webhooks are configured and
+    /// the event type has a webhook counterpart
+    fn webhook_for(&self, entry: &AuditLogEntry) -> Option<(WebhookEvent, serde_json::Value)> {
+        self.webhooks.as_ref()?;
+        let event = webhook_event_for_audit(entry.event_type)?;
+        let data = serde_json::json!({
+            "actor_id": entry.actor_user_id,
+            "org_id": entry.org_id,
+            "target_type": entry.target_type,
+            "target_id": entry.target_id,
+            "metadata": entry.metadata,
+        });
+        Some((event, data))
+    }
+
+    fn notify_sinks(&self) {
+        if let Some(notify) = &self.sink_notify {
+            notify.notify_waiters();
+        }
+    }
+
+    async fn send_webhook(&self, webhook: Option<(WebhookEvent, serde_json::Value)>) {
+        if let (Some(webhooks), Some((event, data))) = (&self.webhooks, webhook) {
+            webhooks.dispatch_or_warn(event, data).await;
+        }
+    }
+
+    /// Mark the entry as a service account action when the actor is one.
+    ///
+    /// Call sites only know the actor's ID, so the lookup happens here rather
+    /// than at every handler. Lookup failures keep the `User` default.
+    async fn resolve_actor_type(&self, mut entry: AuditLogEntry) -> AuditLogEntry {
+        if let (Some(repo), Some(actor_id), AuditActorType::User) = (
+            &self.service_account_repo,
+            entry.actor_user_id,
+            entry.actor_type,
+        ) {
+            if let Ok(Some(_)) = repo.find_by_id(actor_id).await {
+                entry.actor_type = AuditActorType::ServiceAccount;
+            }
+        }
+        entry
     }
 
     /// Log a user event
@@ -148,6 +258,31 @@ impl AuditService {
         self.log(builder.build()).await
     }
 
+    /// Log a relationship tuple event (target is the tuple, metadata carries its key)
+    pub async fn log_relationship_event(
+        &self,
+        event_type: AuditEventType,
+        actor_id: Uuid,
+        org_id: Uuid,
+        tuple_id: Uuid,
+        tuple: &str,
+        headers: Option<&HeaderMap>,
+    ) -> Result<(), AppError> {
+        let (ip, ua) = extract_request_info(headers, self.trust_proxy);
+        let mut builder = AuditLogBuilder::new(event_type)
+            .actor(actor_id)
+            .org(org_id)
+            .target("relation_tuple", tuple_id)
+            .metadata(serde_json::json!({ "tuple": tuple }));
+        if let Some(ip) = ip {
+            builder = builder.ip(&ip);
+        }
+        if let Some(ua) = ua {
+            builder = builder.user_agent(&ua);
+        }
+        self.log(builder.build()).await
+    }
+
     /// Log a session event
     pub async fn log_session_event(
         &self,
@@ -226,6 +361,25 @@ fn extract_request_info(
     (ip, user_agent)
 }
 
+/// Add `impersonatedBy` metadata when logging inside an impersonation scope
+fn tag_impersonation(mut entry: AuditLogEntry) -> AuditLogEntry {
+    if let Ok(tag) = IMPERSONATION.try_with(|tag| *tag) {
+        if !entry.metadata.is_object() {
+            entry.metadata = serde_json::json!({});
+        }
+        if let Some(map) = entry.metadata.as_object_mut() {
+            map.insert(
+                "impersonatedBy".into(),
+                serde_json::json!({
+                    "userId": tag.impersonator_id,
+                    "sessionId": tag.session_id,
+                }),
+            );
+        }
+    }
+    entry
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -274,6 +428,56 @@ mod tests {
         assert_eq!(entries[0].org_id, Some(org_id));
     }
 
+    #[tokio::test]
+    async fn test_mapped_events_queue_webhooks() {
+        use crate::repositories::{
+            InMemoryOutboxRepository, InMemoryWebhookRepository, WebhookEndpointEntity,
+            WebhookRepository,
+        };
+
+        let webhook_repo = Arc::new(InMemoryWebhookRepository::new());
+        let endpoint = webhook_repo
+            .create_endpoint(WebhookEndpointEntity::new(
+                "https://example.com/hook".into(),
+                None,
+                "enc".into(),
+                vec![],
+                None,
+            ))
+            .await
+            .unwrap();
+        let webhooks = Arc::new(WebhookService::new(
+            webhook_repo.clone(),
+            Arc::new(InMemoryOutboxRepository::new()),
+            crate::utils::TokenCipher::new("test-secret-key-that-is-long-enough"),
+        ));
+        let service = AuditService::new(Arc::new(InMemoryAuditLogRepository::new()), false)
+            .with_webhooks(webhooks);
+
+        let user_id = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+        service
+            .log_user_event(AuditEventType::UserLogin, user_id, None)
+            .await
+            .unwrap();
+        service
+            .log_org_event(AuditEventType::OrgCreated, user_id, org_id, None)
+            .await
+            .unwrap();
+
+        let deliveries = webhook_repo
+            .list_deliveries(endpoint.id, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].event_type, "org_created");
+        assert_eq!(deliveries[0].payload["data"]["org_id"], org_id.to_string());
+        assert_eq!(
+            deliveries[0].payload["data"]["actor_id"],
+            user_id.to_string()
+        );
+    }
+
     #[tokio::test]
     async fn test_log_user_event_respects_trust_proxy() {
         let repo = Arc::new(InMemoryAuditLogRepository::new());
@@ -325,4 +529,88 @@ mod tests {
 
         assert_eq!(entries[0].ip_address.as_deref(), Some("192.168.1.1"));
     }
+
+    #[tokio::test]
+    async fn test_service_account_actor_type() {
+        use crate::repositories::{InMemoryServiceAccountRepository, ServiceAccountEntity};
+
+        let repo = Arc::new(InMemoryAuditLogRepository::new());
+        let sa_repo = Arc::new(InMemoryServiceAccountRepository::new());
+        let service = AuditService::new(repo.clone(), false).with_service_accounts(sa_repo.clone());
+
+        let org_id = Uuid::new_v4();
+        let (account, _) = ServiceAccountEntity::new(Uuid::new_v4(), org_id, "ci".into(), None);
+        sa_repo.create(account.clone()).await.unwrap();
+        let human_id = Uuid::new_v4();
+
+        service
+            .log_org_event(AuditEventType::OrgUpdated, account.id, org_id, None)
+            .await
+            .unwrap();
+        service
+            .log_org_event(AuditEventType::OrgUpdated, human_id, org_id, None)
+            .await
+            .unwrap();
+
+        let entries = repo
+            .query(crate::repositories::AuditLogQuery {
+                org_id: Some(org_id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let actor_type = |id| {
+            entries
+                .iter()
+                .find(|e| e.actor_user_id == Some(id))
+                .unwrap()
+                .actor_type
+        };
+        assert_eq!(actor_type(account.id), AuditActorType::ServiceAccount);
+        assert_eq!(actor_type(human_id), AuditActorType::User);
+    }
+
+    #[tokio::test]
+    async fn test_entries_tagged_inside_impersonation_scope() {
+        let repo = Arc::new(InMemoryAuditLogRepository::new());
+        let service = AuditService::new(repo.clone(), false);
+        let user_id = Uuid::new_v4();
+        let tag = ImpersonationTag {
+            impersonator_id: Uuid::new_v4(),
+            session_id: Uuid::new_v4(),
+        };
+
+        with_impersonation(tag, async {
+            service
+                .log_user_event(AuditEventType::UserLogin, user_id, None)
+                .await
+                .unwrap();
+        })
+        .await;
+        service
+            .log_user_event(AuditEventType::UserLogout, user_id, None)
+            .await
+            .unwrap();
+
+        let entries = repo
+            .query(crate::repositories::AuditLogQuery {
+                actor_user_id: Some(user_id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let tagged = entries
+            .iter()
+            .find(|e| e.event_type == AuditEventType::UserLogin)
+            .unwrap();
+        assert_eq!(
+            tagged.metadata["impersonatedBy"]["userId"],
+            serde_json::json!(tag.impersonator_id)
+        );
+        let untagged = entries
+            .iter()
+            .find(|e| e.event_type == AuditEventType::UserLogout)
+            .unwrap();
+        assert!(untagged.metadata.get("impersonatedBy").is_none());
+    }
 }
@@ -0,0 +1,356 @@
+//! Audit log streaming to external collectors (SIEM)
+//!
+//! Sinks are fed from the audit hash chain rather than from the write path.
+//! Each sink has a cursor (the last delivered `seq`) stored with the audit
+//! log, and its worker sends everything after the cursor in order, advancing
+//! it only once a batch has been accepted. Delivery is therefore
+//! at-least-once: a collector outage delays entries instead of dropping them,
+//! and a crash between sending a batch and saving the cursor re-sends that
+//! batch. Every record carries `seq` so consumers can drop duplicates.
+//!
+//! `AuditService` wakes the workers after each write; they also poll, which
+//! picks up entries written by other instances.
+//!
+//! A sink seen for the first time starts at the current chain head instead
+//! of replaying the whole history.
+
+mod ndjson;
+mod otlp;
+mod syslog;
+
+pub use ndjson::NdjsonFileSink;
+pub use otlp::OtlpLogSink;
+pub use syslog::{SyslogSink, SyslogTransport};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::config::AuditSinkConfig;
+use crate::errors::AppError;
+use crate::models::AuditLogResponse;
+use crate::repositories::{AuditLogEntry, AuditLogRepository};
+
+/// Longest wait between retries while a sink is failing
+const MAX_RETRY_DELAY_SECS: u64 = 300;
+
+/// Destination that audit entries are streamed to
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    /// Stable name, used as the cursor key
+    fn name(&self) -> &str;
+
+    /// Deliver a batch of entries in order. Returning `Ok` means the whole
+    /// batch was accepted and the cursor may move past it.
+    async fn send(&self, entries: &[AuditLogEntry]) -> Result<(), AppError>;
+}
+
+/// JSON record sent to every sink: the API representation of the entry plus
+/// its chain position
+pub fn audit_sink_record(entry: &AuditLogEntry) -> Value {
+    let mut record = serde_json::to_value(AuditLogResponse::from(entry)).unwrap_or_default();
+    if let (Some(link), Value::Object(map)) = (&entry.chain, &mut record) {
+        map.insert("seq".to_string(), link.seq.into());
+        map.insert("entryHash".to_string(), link.entry_hash.clone().into());
+    }
+    record
+}
+
+/// Build the sinks enabled in `config`
+pub fn audit_sinks_from_config(
+    config: &AuditSinkConfig,
+) -> Result<Vec<Arc<dyn AuditSink>>, AppError> {
+    let mut sinks: Vec<Arc<dyn AuditSink>> = Vec::new();
+
+    if let Some(addr) = &config.syslog_addr {
+        let transport = match config.syslog_protocol.as_str() {
+            "udp" => SyslogTransport::Udp,
+            _ => SyslogTransport::Tcp,
+        };
+        sinks.push(Arc::new(SyslogSink::new(
+            addr.clone(),
+            transport,
+            config.app_name.clone(),
+        )));
+    }
+    if let Some(path) = &config.ndjson_path {
+        sinks.push(Arc::new(NdjsonFileSink::new(
+            path.into(),
+            config.ndjson_max_bytes,
+            config.ndjson_max_files,
+        )));
+    }
+    if let Some(endpoint) = &config.otlp_endpoint {
+        sinks.push(Arc::new(OtlpLogSink::new(
+            endpoint,
+            config.otlp_headers.as_deref(),
+            config.app_name.clone(),
+        )?));
+    }
+
+    Ok(sinks)
+}
+
+/// Background worker that streams new audit entries to one sink
+pub struct AuditSinkWorker {
+    repo: Arc<dyn AuditLogRepository>,
+    sink: Arc<dyn AuditSink>,
+    notify: Arc<Notify>,
+    poll_interval_secs: u64,
+    batch_size: u32,
+}
+
+impl AuditSinkWorker {
+    /// Create a new audit sink worker
+    pub fn new(
+        repo: Arc<dyn AuditLogRepository>,
+        sink: Arc<dyn AuditSink>,
+        notify: Arc<Notify>,
+        config: &AuditSinkConfig,
+    ) -> Self {
+        Self {
+            repo,
+            sink,
+            notify,
+            poll_interval_secs: config.poll_interval_secs,
+            batch_size: config.batch_size,
+        }
+    }
+
+    /// Start the worker as a background task with graceful shutdown support
+    pub fn start(self, cancel_token: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            info!(
+                sink = self.sink.name(),
+                poll_interval_secs = self.poll_interval_secs,
+                "Audit sink worker started"
+            );
+
+            let mut failures: u32 = 0;
+            loop {
+                match self.deliver_pending().await {
+                    Ok(delivered) => {
+                        if delivered > 0 {
+                            debug!(sink = self.sink.name(), delivered, "Streamed audit entries");
+                        }
+                        failures = 0;
+                    }
+                    Err(e) => {
+                        failures = failures.saturating_add(1);
+                        warn!(
+                            sink = self.sink.name(),
+                            error = %e,
+                            failures,
+                            "Audit sink delivery failed; will retry"
+                        );
+                    }
+                }
+
+                let delay =
+                    Duration::from_secs(retry_delay_secs(self.poll_interval_secs, failures));
+                // While the sink is failing, writes don't cut the backoff short
+                let woken = async {
+                    if failures == 0 {
+                        self.notify.notified().await
+                    } else {
+                        std::future::pending::<()>().await
+                    }
+                };
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        info!(sink = self.sink.name(), "Audit sink worker shutting down");
+                        break;
+                    }
+                    _ = woken => {}
+                    _ = tokio::time::sleep(delay) => {}
+                }
+            }
+        })
+    }
+
+    /// Send every entry after the sink's cursor, advancing the cursor after
+    /// each accepted batch. Returns the number of entries delivered.
+    pub async fn deliver_pending(&self) -> Result<usize, AppError> {
+        let name = self.sink.name();
+        let mut cursor = match self.repo.get_sink_cursor(name).await? {
+            Some(seq) => seq,
+            None => {
+                let head = self.repo.chain_head().await?.map_or(0, |link| link.seq);
+                self.repo.set_sink_cursor(name, head).await?;
+                info!(
+                    sink = name,
+                    seq = head,
+                    "Audit sink starting at current chain head"
+                );
+                head
+            }
+        };
+
+        let mut delivered = 0;
+        loop {
+            let batch = self.repo.list_chain(None, cursor, self.batch_size).await?;
+            let Some(last_seq) = batch.last().and_then(|e| e.chain.as_ref()).map(|l| l.seq) else {
+                break;
+            };
+
+            self.sink.send(&batch).await?;
+            cursor = last_seq;
+            self.repo.set_sink_cursor(name, cursor).await?;
+            delivered += batch.len();
+
+            if batch.len() < self.batch_size as usize {
+                break;
+            }
+        }
+
+        Ok(delivered)
+    }
+}
+
+/// Delay before the next poll: the poll interval, doubled per consecutive
+/// failure up to `MAX_RETRY_DELAY_SECS`
+fn retry_delay_secs(poll_interval_secs: u64, failures: u32) -> u64 {
+    if failures == 0 {
+        return poll_interval_secs;
+    }
+    let factor = 1u64 << failures.min(16);
+    poll_interval_secs
+        .saturating_mul(factor)
+        .min(MAX_RETRY_DELAY_SECS.max(poll_interval_secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{AuditEventType, AuditLogBuilder, InMemoryAuditLogRepository};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tokio::sync::Mutex;
+
+    /// Sink that records what it receives and can be switched to fail
+    #[derive(Default)]
+    struct RecordingSink {
+        received: Mutex<Vec<i64>>,
+        failing: AtomicBool,
+    }
+
+    #[async_trait]
+    impl AuditSink for RecordingSink {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn send(&self, entries: &[AuditLogEntry]) -> Result<(), AppError> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err(AppError::Internal(anyhow::anyhow!("collector down")));
+            }
+            let mut received = self.received.lock().await;
+            received.extend(
+                entries
+                    .iter()
+                    .filter_map(|e| e.chain.as_ref().map(|l| l.seq)),
+            );
+            Ok(())
+        }
+    }
+
+    async fn write(repo: &InMemoryAuditLogRepository, count: usize) {
+        for _ in 0..count {
+            repo.create(AuditLogBuilder::new(AuditEventType::UserLogin).build())
+                .await
+                .unwrap();
+        }
+    }
+
+    fn worker(
+        repo: Arc<InMemoryAuditLogRepository>,
+        sink: Arc<RecordingSink>,
+        batch_size: u32,
+    ) -> AuditSinkWorker {
+        let config = AuditSinkConfig {
+            batch_size,
+            ..Default::default()
+        };
+        AuditSinkWorker::new(repo, sink, Arc::new(Notify::new()), &config)
+    }
+
+    #[tokio::test]
+    async fn test_new_sink_starts_at_head_and_streams_in_batches() {
+        let repo = Arc::new(InMemoryAuditLogRepository::new());
+        let sink = Arc::new(RecordingSink::default());
+        write(&repo, 2).await;
+
+        let worker = worker(repo.clone(), sink.clone(), 2);
+        assert_eq!(worker.deliver_pending().await.unwrap(), 0);
+        assert_eq!(repo.get_sink_cursor("recording").await.unwrap(), Some(2));
+
+        write(&repo, 5).await;
+        assert_eq!(worker.deliver_pending().await.unwrap(), 5);
+        assert_eq!(*sink.received.lock().await, vec![3, 4, 5, 6, 7]);
+        assert_eq!(repo.get_sink_cursor("recording").await.unwrap(), Some(7));
+
+        // A stale worker finishing late doesn't rewind the cursor
+        repo.set_sink_cursor("recording", 3).await.unwrap();
+        assert_eq!(repo.get_sink_cursor("recording").await.unwrap(), Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_outage_keeps_cursor_and_redelivers() {
+        let repo = Arc::new(InMemoryAuditLogRepository::new());
+        let sink = Arc::new(RecordingSink::default());
+        let worker = worker(repo.clone(), sink.clone(), 100);
+        worker.deliver_pending().await.unwrap();
+
+        write(&repo, 3).await;
+        sink.failing.store(true, Ordering::SeqCst);
+        assert!(worker.deliver_pending().await.is_err());
+        assert_eq!(repo.get_sink_cursor("recording").await.unwrap(), Some(0));
+
+        sink.failing.store(false, Ordering::SeqCst);
+        assert_eq!(worker.deliver_pending().await.unwrap(), 3);
+        assert_eq!(*sink.received.lock().await, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_and_caps() {
+        assert_eq!(retry_delay_secs(5, 0), 5);
+        assert_eq!(retry_delay_secs(5, 1), 10);
+        assert_eq!(retry_delay_secs(5, 3), 40);
+        assert_eq!(retry_delay_secs(5, 20), MAX_RETRY_DELAY_SECS);
+    }
+
+    #[test]
+    fn test_record_includes_chain_position() {
+        let mut entry = AuditLogBuilder::new(AuditEventType::UserLogin).build();
+        entry.link(7, crate::repositories::AUDIT_GENESIS_HASH, None);
+
+        let record = audit_sink_record(&entry);
+        assert_eq!(record["seq"], 7);
+        assert_eq!(record["eventType"], "user.login");
+        assert_eq!(record["entryHash"], entry.chain.unwrap().entry_hash);
+    }
+
+    #[test]
+    fn test_sinks_from_config() {
+        assert!(audit_sinks_from_config(&AuditSinkConfig::default())
+            .unwrap()
+            .is_empty());
+
+        let config = AuditSinkConfig {
+            syslog_addr: Some("127.0.0.1:6514".into()),
+            ndjson_path: Some("/tmp/audit.ndjson".into()),
+            otlp_endpoint: Some("http://127.0.0.1:4318".into()),
+            ..Default::default()
+        };
+        let names: Vec<String> = audit_sinks_from_config(&config)
+            .unwrap()
+            .iter()
+            .map(|s| s.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["syslog", "ndjson", "otlp"]);
+    }
+}
@@ -0,0 +1,135 @@
+//! Newline-delimited JSON file sink
+//!
+//! Appends one JSON record per line for log shippers that tail files
+//! (Filebeat, Vector, Fluent Bit). Each batch is fsynced before it counts as
+//! delivered. When the file grows past `max_bytes` it is rotated to
+//! `<path>.1`, shifting older files up and dropping anything beyond
+//! `max_files`.
+
+use async_trait::async_trait;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::{audit_sink_record, AuditSink};
+use crate::errors::AppError;
+use crate::repositories::AuditLogEntry;
+
+/// Sink that appends entries to a rotating NDJSON file
+pub struct NdjsonFileSink {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: u32,
+}
+
+impl NdjsonFileSink {
+    pub fn new(path: PathBuf, max_bytes: u64, max_files: u32) -> Self {
+        Self {
+            path,
+            max_bytes,
+            max_files,
+        }
+    }
+}
+
+#[async_trait]
+impl AuditSink for NdjsonFileSink {
+    fn name(&self) -> &str {
+        "ndjson"
+    }
+
+    async fn send(&self, entries: &[AuditLogEntry]) -> Result<(), AppError> {
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&audit_sink_record(entry).to_string());
+            lines.push('\n');
+        }
+
+        let path = self.path.clone();
+        let (max_bytes, max_files) = (self.max_bytes, self.max_files);
+        tokio::task::spawn_blocking(move || append(&path, lines.as_bytes(), max_bytes, max_files))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("NDJSON sink task failed: {}", e)))?
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("NDJSON sink write failed: {}", e)))
+    }
+}
+
+/// Append `data`, rotating first if the current file is already full
+fn append(path: &Path, data: &[u8], max_bytes: u64, max_files: u32) -> std::io::Result<()> {
+    let size = match fs::metadata(path) {
+        Ok(meta) => meta.len(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+        Err(e) => return Err(e),
+    };
+    if size > 0 && size + data.len() as u64 > max_bytes {
+        rotate(path, max_files)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(data)?;
+    file.sync_data()
+}
+
+/// Shift `<path>.N-1` to `<path>.N` down to `<path>` to `<path>.1`
+fn rotate(path: &Path, max_files: u32) -> std::io::Result<()> {
+    if max_files == 0 {
+        return fs::remove_file(path);
+    }
+    for n in (1..max_files).rev() {
+        let from = rotated_path(path, n);
+        if from.exists() {
+            fs::rename(&from, rotated_path(path, n + 1))?;
+        }
+    }
+    fs::rename(path, rotated_path(path, 1))
+}
+
+fn rotated_path(path: &Path, n: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{AuditEventType, AuditLogBuilder, AUDIT_GENESIS_HASH};
+    use uuid::Uuid;
+
+    fn entry(seq: i64) -> AuditLogEntry {
+        let mut entry = AuditLogBuilder::new(AuditEventType::UserLogin).build();
+        entry.link(seq, AUDIT_GENESIS_HASH, None);
+        entry
+    }
+
+    #[tokio::test]
+    async fn test_appends_lines_and_rotates() {
+        let dir = std::env::temp_dir().join(format!("audit-ndjson-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("audit.ndjson");
+
+        // Small enough that every batch after the first rotates
+        let sink = NdjsonFileSink::new(path.clone(), 10, 2);
+        for seq in 1..=4 {
+            sink.send(&[entry(seq)]).await.unwrap();
+        }
+
+        let seq_of = |p: PathBuf| -> Vec<i64> {
+            fs::read_to_string(p)
+                .unwrap()
+                .lines()
+                .map(|l| {
+                    serde_json::from_str::<serde_json::Value>(l).unwrap()["seq"]
+                        .as_i64()
+                        .unwrap()
+                })
+                .collect()
+        };
+        assert_eq!(seq_of(path.clone()), vec![4]);
+        assert_eq!(seq_of(rotated_path(&path, 1)), vec![3]);
+        assert_eq!(seq_of(rotated_path(&path, 2)), vec![2]);
+        assert!(!rotated_path(&path, 3).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+//! OpenTelemetry logs sink (OTLP/HTTP with JSON encoding)
+//!
+//! Posts batches to `<endpoint>/v1/logs`, the standard path served by the
+//! OpenTelemetry Collector and most vendors' OTLP intakes. Each entry is a
+//! log record whose body is the JSON record; the event type and sequence
+//! number are also attached as attributes for filtering. Any non-2xx
+//! response fails the batch so it is retried.
+
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use super::{audit_sink_record, AuditSink};
+use crate::errors::AppError;
+use crate::repositories::AuditLogEntry;
+
+/// Request timeout for the collector
+const OTLP_TIMEOUT_SECS: u64 = 10;
+
+/// Instrumentation scope reported with every record
+const OTLP_SCOPE_NAME: &str = "cedros_login.audit";
+
+/// OTLP severity number for INFO
+const OTLP_SEVERITY_INFO: u8 = 9;
+
+/// Sink that exports entries as OpenTelemetry log records
+pub struct OtlpLogSink {
+    url: String,
+    client: reqwest::Client,
+    service_name: String,
+}
+
+impl OtlpLogSink {
+    /// `headers` is a comma-separated `key=value` list, as in
+    /// `OTEL_EXPORTER_OTLP_HEADERS`
+    pub fn new(
+        endpoint: &str,
+        headers: Option<&str>,
+        service_name: String,
+    ) -> Result<Self, AppError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(OTLP_TIMEOUT_SECS))
+            .default_headers(parse_headers(headers.unwrap_or_default())?)
+            .build()
+            .map_err(|e| AppError::Config(format!("Failed to build OTLP client: {}", e)))?;
+
+        Ok(Self {
+            url: format!("{}/v1/logs", endpoint.trim_end_matches('/')),
+            client,
+            service_name,
+        })
+    }
+
+    /// Build the `ExportLogsServiceRequest` body for a batch
+    fn payload(&self, entries: &[AuditLogEntry]) -> Value {
+        let records: Vec<Value> = entries
+            .iter()
+            .map(|entry| {
+                let time = entry
+                    .created_at
+                    .timestamp_nanos_opt()
+                    .unwrap_or_default()
+                    .to_string();
+                let seq = entry.chain.as_ref().map_or(0, |link| link.seq);
+                json!({
+                    "timeUnixNano": time,
+                    "observedTimeUnixNano": time,
+                    "severityNumber": OTLP_SEVERITY_INFO,
+                    "severityText": "INFO",
+                    "body": { "stringValue": audit_sink_record(entry).to_string() },
+                    "attributes": [
+                        { "key": "event.name", "value": { "stringValue": entry.event_type.as_str() } },
+                        // int64 values are strings in the OTLP JSON encoding
+                        { "key": "audit.seq", "value": { "intValue": seq.to_string() } },
+                    ],
+                })
+            })
+            .collect();
+
+        json!({
+            "resourceLogs": [{
+                "resource": {
+                    "attributes": [
+                        { "key": "service.name", "value": { "stringValue": self.service_name } },
+                    ],
+                },
+                "scopeLogs": [{
+                    "scope": { "name": OTLP_SCOPE_NAME },
+                    "logRecords": records,
+                }],
+            }],
+        })
+    }
+}
+
+#[async_trait]
+impl AuditSink for OtlpLogSink {
+    fn name(&self) -> &str {
+        "otlp"
+    }
+
+    async fn send(&self, entries: &[AuditLogEntry]) -> Result<(), AppError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&self.payload(entries))
+            .send()
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("OTLP export failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(AppError::Internal(anyhow::anyhow!(
+                "OTLP collector returned {}",
+                status
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn parse_headers(raw: &str) -> Result<HeaderMap, AppError> {
+    let mut headers = HeaderMap::new();
+    for pair in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').ok_or_else(|| {
+            AppError::Config(format!("Invalid AUDIT_OTLP_HEADERS entry: {}", pair))
+        })?;
+        let name = HeaderName::from_bytes(key.trim().as_bytes())
+            .map_err(|_| AppError::Config(format!("Invalid OTLP header name: {}", key)))?;
+        let value = HeaderValue::from_str(value.trim())
+            .map_err(|_| AppError::Config(format!("Invalid OTLP header value for {}", key)))?;
+        headers.insert(name, value);
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{AuditEventType, AuditLogBuilder, AUDIT_GENESIS_HASH};
+
+    #[test]
+    fn test_payload_shape() {
+        let sink = OtlpLogSink::new("http://collector:4318/", None, "cedros-login".into()).unwrap();
+        assert_eq!(sink.url, "http://collector:4318/v1/logs");
+
+        let mut entry = AuditLogBuilder::new(AuditEventType::UserLogin).build();
+        entry.link(42, AUDIT_GENESIS_HASH, None);
+        let payload = sink.payload(&[entry]);
+
+        let resource = &payload["resourceLogs"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "cedros-login"
+        );
+        let record = &resource["scopeLogs"][0]["logRecords"][0];
+        assert_eq!(record["severityNumber"], 9);
+        assert_eq!(
+            record["attributes"][0]["value"]["stringValue"],
+            "user.login"
+        );
+        assert_eq!(record["attributes"][1]["value"]["intValue"], "42");
+        let body: Value =
+            serde_json::from_str(record["body"]["stringValue"].as_str().unwrap()).unwrap();
+        assert_eq!(body["seq"], 42);
+    }
+
+    #[test]
+    fn test_parse_headers() {
+        let headers = parse_headers("api-key=secret, x-tenant = acme").unwrap();
+        assert_eq!(headers["api-key"], "secret");
+        assert_eq!(headers["x-tenant"], "acme");
+        assert!(parse_headers("").unwrap().is_empty());
+        assert!(parse_headers("missing-equals").is_err());
+    }
+}
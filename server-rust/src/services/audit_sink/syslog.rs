@@ -0,0 +1,207 @@
+//! RFC 5424 syslog sink
+//!
+//! Each entry becomes one message with facility `log audit` (13) and
+//! severity `informational`, the event type as MSGID and the JSON record as
+//! MSG. Over TCP messages use octet-counting framing (RFC 6587), and a batch
+//! only counts as delivered once it has been written in full. UDP has no
+//! acknowledgement, so delivery is only as reliable as the network.
+
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+
+use super::{audit_sink_record, AuditSink};
+use crate::errors::AppError;
+use crate::repositories::AuditLogEntry;
+
+/// PRI value: facility 13 (log audit) * 8 + severity 6 (informational)
+const SYSLOG_PRI: u8 = 13 * 8 + 6;
+
+/// Timeout for connecting and writing a batch
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Transport used to reach the syslog collector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogTransport {
+    Tcp,
+    Udp,
+}
+
+/// Sink that sends entries to a syslog collector
+pub struct SyslogSink {
+    addr: String,
+    transport: SyslogTransport,
+    app_name: String,
+    hostname: String,
+}
+
+impl SyslogSink {
+    pub fn new(addr: String, transport: SyslogTransport, app_name: String) -> Self {
+        let hostname = std::env::var("HOSTNAME")
+            .ok()
+            .filter(|h| !h.is_empty())
+            .unwrap_or_else(|| "-".to_string());
+        Self {
+            addr,
+            transport,
+            app_name,
+            hostname,
+        }
+    }
+
+    /// Format an entry as an RFC 5424 message (without transport framing)
+    fn format(&self, entry: &AuditLogEntry) -> String {
+        format!(
+            "<{}>1 {} {} {} {} {} - {}",
+            SYSLOG_PRI,
+            entry
+                .created_at
+                .to_rfc3339_opts(SecondsFormat::Micros, true),
+            header_field(&self.hostname, 255),
+            header_field(&self.app_name, 48),
+            std::process::id(),
+            header_field(entry.event_type.as_str(), 32),
+            audit_sink_record(entry),
+        )
+    }
+
+    async fn send_tcp(&self, messages: &[String]) -> Result<(), AppError> {
+        let mut stream = TcpStream::connect(&self.addr)
+            .await
+            .map_err(|e| sink_error("connect", e))?;
+        let mut buf = Vec::new();
+        for message in messages {
+            buf.extend_from_slice(format!("{} {}", message.len(), message).as_bytes());
+        }
+        stream
+            .write_all(&buf)
+            .await
+            .map_err(|e| sink_error("write", e))?;
+        stream.flush().await.map_err(|e| sink_error("flush", e))?;
+        stream.shutdown().await.map_err(|e| sink_error("close", e))
+    }
+
+    async fn send_udp(&self, messages: &[String]) -> Result<(), AppError> {
+        let socket = UdpSocket::bind(if self.addr.starts_with('[') {
+            "[::]:0"
+        } else {
+            "0.0.0.0:0"
+        })
+        .await
+        .map_err(|e| sink_error("bind", e))?;
+        socket
+            .connect(&self.addr)
+            .await
+            .map_err(|e| sink_error("connect", e))?;
+        for message in messages {
+            socket
+                .send(message.as_bytes())
+                .await
+                .map_err(|e| sink_error("send", e))?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditSink for SyslogSink {
+    fn name(&self) -> &str {
+        "syslog"
+    }
+
+    async fn send(&self, entries: &[AuditLogEntry]) -> Result<(), AppError> {
+        let messages: Vec<String> = entries.iter().map(|e| self.format(e)).collect();
+        let send = async {
+            match self.transport {
+                SyslogTransport::Tcp => self.send_tcp(&messages).await,
+                SyslogTransport::Udp => self.send_udp(&messages).await,
+            }
+        };
+        tokio::time::timeout(SEND_TIMEOUT, send)
+            .await
+            .map_err(|_| AppError::Internal(anyhow::anyhow!("Syslog send timed out")))?
+    }
+}
+
+/// Header fields are printable US-ASCII without spaces, with a maximum length
+fn header_field(value: &str, max_len: usize) -> String {
+    let field: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect();
+    if field.is_empty() {
+        "-".to_string()
+    } else {
+        field
+    }
+}
+
+fn sink_error(action: &str, e: std::io::Error) -> AppError {
+    AppError::Internal(anyhow::anyhow!("Syslog {} failed: {}", action, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{AuditEventType, AuditLogBuilder, AUDIT_GENESIS_HASH};
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    fn entry() -> AuditLogEntry {
+        let mut entry = AuditLogBuilder::new(AuditEventType::OrgCreated).build();
+        entry.link(1, AUDIT_GENESIS_HASH, None);
+        entry
+    }
+
+    #[test]
+    fn test_format_is_rfc5424() {
+        let sink = SyslogSink::new(
+            "127.0.0.1:514".into(),
+            SyslogTransport::Udp,
+            "my app".into(),
+        );
+        let message = sink.format(&entry());
+
+        assert!(message.starts_with("<110>1 "));
+        let fields: Vec<&str> = message.splitn(8, ' ').collect();
+        assert_eq!(fields[3], "myapp");
+        assert_eq!(fields[5], "org.created");
+        assert_eq!(fields[6], "-");
+        let record: serde_json::Value = serde_json::from_str(fields[7]).unwrap();
+        assert_eq!(record["seq"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_tcp_uses_octet_counting() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = String::new();
+            socket.read_to_string(&mut received).await.unwrap();
+            received
+        });
+
+        let sink = SyslogSink::new(addr, SyslogTransport::Tcp, "cedros-login".into());
+        sink.send(&[entry(), entry()]).await.unwrap();
+
+        let received = server.await.unwrap();
+        let (len, rest) = received.split_once(' ').unwrap();
+        let len: usize = len.parse().unwrap();
+        assert!(rest[..len].starts_with("<110>1 "));
+        assert!(rest[len..].split_once(' ').is_some());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_send_fails_when_collector_is_down() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        let sink = SyslogSink::new(addr, SyslogTransport::Tcp, "cedros-login".into());
+        assert!(sink.send(&[entry()]).await.is_err());
+    }
+}
@@ -44,6 +44,11 @@ pub enum Permission {
 
     // Audit permissions
     AuditRead,
+
+    // Organization credit balance permissions
+    CreditsRead,
+    CreditsSpend,
+    CreditsManage,
 }
 
 impl Permission {
@@ -61,6 +66,9 @@ impl Permission {
             Permission::InviteCreate => "invite:create",
             Permission::InviteCancel => "invite:cancel",
             Permission::AuditRead => "audit:read",
+            Permission::CreditsRead => "credits:read",
+            Permission::CreditsSpend => "credits:spend",
+            Permission::CreditsManage => "credits:manage",
         }
     }
 
@@ -79,6 +87,9 @@ impl Permission {
             "invite:create" => Some(Permission::InviteCreate),
             "invite:cancel" => Some(Permission::InviteCancel),
             "audit:read" => Some(Permission::AuditRead),
+            "credits:read" => Some(Permission::CreditsRead),
+            "credits:spend" => Some(Permission::CreditsSpend),
+            "credits:manage" => Some(Permission::CreditsManage),
             _ => None,
         }
     }
@@ -98,6 +109,9 @@ impl Permission {
                 Permission::InviteCreate,
                 Permission::InviteCancel,
                 Permission::AuditRead,
+                Permission::CreditsRead,
+                Permission::CreditsSpend,
+                Permission::CreditsManage,
             ],
             OrgRole::Admin => vec![
                 Permission::OrgRead,
@@ -110,11 +124,15 @@ impl Permission {
                 Permission::InviteCreate,
                 Permission::InviteCancel,
                 Permission::AuditRead,
+                Permission::CreditsRead,
+                Permission::CreditsSpend,
+                Permission::CreditsManage,
             ],
             OrgRole::Member => vec![
                 Permission::OrgRead,
                 Permission::MemberRead,
                 Permission::InviteRead,
+                Permission::CreditsRead,
             ],
         }
     }
@@ -126,7 +144,10 @@ impl Permission {
             OrgRole::Admin => !matches!(self, Permission::OrgDelete),
             OrgRole::Member => matches!(
                 self,
-                Permission::OrgRead | Permission::MemberRead | Permission::InviteRead
+                Permission::OrgRead
+                    | Permission::MemberRead
+                    | Permission::InviteRead
+                    | Permission::CreditsRead
             ),
         }
     }
@@ -416,6 +437,10 @@ mod tests {
         assert!(Permission::MemberInvite.is_allowed_for(OrgRole::Owner));
         assert!(Permission::MemberInvite.is_allowed_for(OrgRole::Admin));
         assert!(!Permission::MemberInvite.is_allowed_for(OrgRole::Member));
+
+        assert!(Permission::CreditsSpend.is_allowed_for(OrgRole::Admin));
+        assert!(Permission::CreditsRead.is_allowed_for(OrgRole::Member));
+        assert!(!Permission::CreditsSpend.is_allowed_for(OrgRole::Member));
     }
 
     #[test]
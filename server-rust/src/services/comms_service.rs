@@ -1,11 +1,16 @@
 //! Communications service - unified interface for queueing emails and notifications
 
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::errors::AppError;
-use crate::repositories::{OutboxEvent, OutboxEventType, OutboxRepository};
-use crate::services::NotificationSeverity;
+use crate::repositories::{
+    OutboxEvent, OutboxEventType, OutboxRepository, SubscriptionEntity, SubscriptionPlanEntity,
+};
+use crate::services::{
+    BudgetAlert, CreditAccount, CreditStatement, CreditTransfer, NotificationSeverity,
+};
 use crate::utils::TokenCipher;
 
 /// Service for queueing communications (emails and notifications)
@@ -184,6 +189,89 @@ impl CommsService {
         Ok(created.id)
     }
 
+    /// Queue an email change confirmation to the new address
+    pub async fn queue_email_change_email(
+        &self,
+        to: &str,
+        user_name: Option<&str>,
+        token: &str,
+        user_id: Uuid,
+    ) -> Result<Uuid, AppError> {
+        let token_enc = self.token_cipher.encrypt(token)?;
+
+        let event = OutboxEvent::new(
+            OutboxEventType::EmailChange,
+            serde_json::json!({
+                "to": to,
+                "user_name": user_name,
+                "new_email": to,
+                "token_enc": token_enc,
+                "expires_in_hours": 24
+            }),
+        )
+        .with_user_id(user_id);
+
+        let created = self.outbox_repo.create(event).await?;
+        Ok(created.id)
+    }
+
+    /// Queue an email change notice with a revert link to the previous address
+    pub async fn queue_email_change_notice_email(
+        &self,
+        to: &str,
+        user_name: Option<&str>,
+        new_email: &str,
+        token: &str,
+        user_id: Uuid,
+    ) -> Result<Uuid, AppError> {
+        let token_enc = self.token_cipher.encrypt(token)?;
+
+        let event = OutboxEvent::new(
+            OutboxEventType::EmailChangeNotice,
+            serde_json::json!({
+                "to": to,
+                "user_name": user_name,
+                "new_email": new_email,
+                "token_enc": token_enc,
+                "expires_in_days": 7
+            }),
+        )
+        .with_user_id(user_id);
+
+        let created = self.outbox_repo.create(event).await?;
+        Ok(created.id)
+    }
+
+    /// Queue a credit statement email
+    ///
+    /// The rendered statement travels in the event payload, so the email
+    /// shows the statement exactly as it was generated.
+    pub async fn queue_credit_statement_email(
+        &self,
+        to: &str,
+        user_name: Option<&str>,
+        user_id: Uuid,
+        statement: &CreditStatement,
+    ) -> Result<Uuid, AppError> {
+        let mut event = OutboxEvent::new(
+            OutboxEventType::EmailCreditStatement,
+            serde_json::json!({
+                "to": to,
+                "user_name": user_name,
+                "period": statement.period_label(),
+                "statement_html": statement.to_html(),
+                "statement_text": statement.to_text()
+            }),
+        )
+        .with_user_id(user_id);
+        if let CreditAccount::Org(org_id) = statement.account {
+            event = event.with_org_id(org_id);
+        }
+
+        let created = self.outbox_repo.create(event).await?;
+        Ok(created.id)
+    }
+
     // ==================== Admin Notification Methods ====================
 
     /// Queue a notification for failed login threshold
@@ -527,6 +615,202 @@ impl CommsService {
         let created = self.outbox_repo.create(event).await?;
         Ok(created.id)
     }
+
+    /// Queue a notification when a subscription renewal charge fails
+    ///
+    /// The subscription keeps its entitlements until `cancel_at`, when it is
+    /// canceled unless a retry succeeds.
+    pub async fn notify_subscription_past_due(
+        &self,
+        subscription: &SubscriptionEntity,
+        plan: &SubscriptionPlanEntity,
+        reason: &str,
+        cancel_at: DateTime<Utc>,
+    ) -> Result<Uuid, AppError> {
+        let amount_sol = plan.price_lamports as f64 / 1_000_000_000.0;
+
+        let event = OutboxEvent::new(
+            OutboxEventType::CreditSubscriptionPastDue,
+            serde_json::json!({
+                "severity": "warn",
+                "title": "Subscription Payment Failed",
+                "body": format!(
+                    "Renewal of '{}' ({:.4} {}) failed (attempt {}): {}",
+                    plan.name,
+                    amount_sol,
+                    plan.currency,
+                    subscription.dunning_attempts,
+                    reason
+                ),
+                "metadata": {
+                    "subscription_id": subscription.id.to_string(),
+                    "plan": plan.name,
+                    "user_id": subscription.user_id.to_string(),
+                    "org_id": subscription.org_id.map(|id| id.to_string()),
+                    "amount_lamports": plan.price_lamports,
+                    "currency": plan.currency,
+                    "attempt": subscription.dunning_attempts,
+                    "reason": reason,
+                    "cancel_at": cancel_at.to_rfc3339()
+                }
+            }),
+        )
+        .with_user_id(subscription.user_id)
+        .with_correlation_id(&subscription.id.to_string());
+
+        let created = self.outbox_repo.create(event).await?;
+        Ok(created.id)
+    }
+
+    /// Queue a notification when a subscription is canceled for non-payment
+    pub async fn notify_subscription_canceled(
+        &self,
+        subscription: &SubscriptionEntity,
+        plan: &SubscriptionPlanEntity,
+    ) -> Result<Uuid, AppError> {
+        let event = OutboxEvent::new(
+            OutboxEventType::CreditSubscriptionCanceled,
+            serde_json::json!({
+                "severity": "warn",
+                "title": "Subscription Canceled",
+                "body": format!(
+                    "Subscription to '{}' was canceled after {} failed payment attempts",
+                    plan.name,
+                    subscription.dunning_attempts
+                ),
+                "metadata": {
+                    "subscription_id": subscription.id.to_string(),
+                    "plan": plan.name,
+                    "user_id": subscription.user_id.to_string(),
+                    "org_id": subscription.org_id.map(|id| id.to_string()),
+                    "reason": subscription.cancel_reason
+                }
+            }),
+        )
+        .with_user_id(subscription.user_id)
+        .with_correlation_id(&subscription.id.to_string());
+
+        let created = self.outbox_repo.create(event).await?;
+        Ok(created.id)
+    }
+
+    /// Queue notifications to both sides of a credit transfer
+    ///
+    /// Returns the IDs of the sender's and the recipient's events.
+    pub async fn notify_credit_transfer(
+        &self,
+        transfer: &CreditTransfer,
+    ) -> Result<(Uuid, Uuid), AppError> {
+        let amount_sol = transfer.amount as f64 / 1_000_000_000.0;
+        let metadata = serde_json::json!({
+            "transfer_id": transfer.id.to_string(),
+            "sender_id": transfer.sender_id.to_string(),
+            "from_type": transfer.from.owner_type(),
+            "from_id": transfer.from.id().to_string(),
+            "to_type": transfer.to.owner_type(),
+            "to_id": transfer.to.id().to_string(),
+            "amount_lamports": transfer.amount,
+            "currency": transfer.currency,
+            "note": transfer.note
+        });
+
+        let mut sent = OutboxEvent::new(
+            OutboxEventType::CreditTransferSent,
+            serde_json::json!({
+                "severity": "info",
+                "title": "Credits Sent",
+                "body": format!(
+                    "Transferred {:.4} {} to {} {}",
+                    amount_sol,
+                    transfer.currency,
+                    transfer.to.owner_type(),
+                    transfer.to.id()
+                ),
+                "metadata": metadata
+            }),
+        )
+        .with_user_id(transfer.sender_id)
+        .with_correlation_id(&transfer.id.to_string());
+        if let CreditAccount::Org(org_id) = transfer.from {
+            sent = sent.with_org_id(org_id);
+        }
+
+        let mut received = OutboxEvent::new(
+            OutboxEventType::CreditTransferReceived,
+            serde_json::json!({
+                "severity": "info",
+                "title": "Credits Received",
+                "body": format!(
+                    "Received {:.4} {} from {} {}",
+                    amount_sol,
+                    transfer.currency,
+                    transfer.from.owner_type(),
+                    transfer.from.id()
+                ),
+                "metadata": metadata
+            }),
+        )
+        .with_correlation_id(&transfer.id.to_string());
+        received = match transfer.to {
+            CreditAccount::User(user_id) => received.with_user_id(user_id),
+            CreditAccount::Org(org_id) => received.with_org_id(org_id),
+        };
+
+        let sent = self.outbox_repo.create(sent).await?;
+        let received = self.outbox_repo.create(received).await?;
+        Ok((sent.id, received.id))
+    }
+
+    /// Queue a notification when spending crosses a budget's soft limit
+    pub async fn notify_credit_budget_threshold(
+        &self,
+        alert: &BudgetAlert,
+    ) -> Result<Uuid, AppError> {
+        let budget = &alert.budget;
+        let used_sol = alert.used_lamports as f64 / 1_000_000_000.0;
+        let soft_limit = budget.soft_limit.unwrap_or_default();
+        let soft_limit_sol = soft_limit as f64 / 1_000_000_000.0;
+
+        let mut event = OutboxEvent::new(
+            OutboxEventType::CreditBudgetThreshold,
+            serde_json::json!({
+                "severity": "warn",
+                "title": "Spending Budget Threshold Reached",
+                "body": format!(
+                    "{} {} spending ({:.4} {}) reached the alert threshold ({:.4} {})",
+                    budget.period.as_str(),
+                    budget.scope.as_str(),
+                    used_sol,
+                    budget.currency,
+                    soft_limit_sol,
+                    budget.currency
+                ),
+                "metadata": {
+                    "budget_id": budget.id.to_string(),
+                    "scope": budget.scope.as_str(),
+                    "user_id": budget.user_id.map(|id| id.to_string()),
+                    "org_id": budget.org_id.map(|id| id.to_string()),
+                    "api_key_id": budget.api_key_id.map(|id| id.to_string()),
+                    "period": budget.period.as_str(),
+                    "period_start": alert.period_start.to_rfc3339(),
+                    "used_lamports": alert.used_lamports,
+                    "soft_limit_lamports": soft_limit,
+                    "hard_limit_lamports": budget.hard_limit,
+                    "currency": budget.currency
+                }
+            }),
+        )
+        .with_correlation_id(&format!("{}:{}", budget.id, alert.period_start.timestamp()));
+        if let Some(user_id) = budget.user_id {
+            event = event.with_user_id(user_id);
+        }
+        if let Some(org_id) = budget.org_id {
+            event = event.with_org_id(org_id);
+        }
+
+        let created = self.outbox_repo.create(event).await?;
+        Ok(created.id)
+    }
 }
 
 #[cfg(test)]
@@ -756,6 +1040,52 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_notify_credit_budget_threshold() {
+        use crate::repositories::{CreditBudgetEntity, CreditBudgetPeriod, CreditBudgetScope};
+
+        let repo = Arc::new(InMemoryOutboxRepository::new());
+        let service = CommsService::new(
+            repo.clone(),
+            "https://example.com".to_string(),
+            TokenCipher::new("test-secret"),
+        );
+
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+        let alert = BudgetAlert {
+            budget: CreditBudgetEntity {
+                id: Uuid::new_v4(),
+                scope: CreditBudgetScope::User,
+                user_id: Some(user_id),
+                org_id: None,
+                api_key_id: None,
+                currency: "SOL".into(),
+                period: CreditBudgetPeriod::Daily,
+                soft_limit: Some(800_000_000),
+                hard_limit: Some(1_000_000_000),
+                created_by: None,
+                created_at: now,
+                updated_at: now,
+            },
+            period_start: CreditBudgetPeriod::Daily.start(now),
+            used_lamports: 900_000_000,
+        };
+        let event_id = service
+            .notify_credit_budget_threshold(&alert)
+            .await
+            .unwrap();
+
+        let event = repo.find_by_id(event_id).await.unwrap().unwrap();
+        assert_eq!(event.event_type, OutboxEventType::CreditBudgetThreshold);
+        assert_eq!(event.user_id, Some(user_id));
+        assert_eq!(
+            event.payload["metadata"]["soft_limit_lamports"].as_i64(),
+            Some(800_000_000)
+        );
+        assert_eq!(event.payload["metadata"]["scope"].as_str(), Some("user"));
+    }
+
     #[tokio::test]
     async fn test_notify_credit_spend() {
         let repo = Arc::new(InMemoryOutboxRepository::new());
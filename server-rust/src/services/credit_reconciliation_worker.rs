@@ -0,0 +1,132 @@
+//! Background worker for credit ledger reconciliation
+//!
+//! Periodically recomputes every credit balance from the double-entry
+//! journal and logs any balance that has drifted from it, any transaction
+//! that was never posted, and any currency whose journal does not balance.
+//! The same report is available on demand from the admin API.
+//!
+//! Default poll interval: 1 hour
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::errors::AppError;
+use crate::repositories::{CreditHoldRepository, CreditRepository};
+use crate::services::CreditService;
+
+/// Default poll interval for reconciliation (1 hour)
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 3600;
+
+/// Maximum drifted balances logged individually per run
+const MAX_LOGGED_DRIFT: usize = 20;
+
+/// Configuration for the credit reconciliation worker
+#[derive(Debug, Clone)]
+pub struct CreditReconciliationConfig {
+    /// How often to reconcile (seconds)
+    pub poll_interval_secs: u64,
+}
+
+impl Default for CreditReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+        }
+    }
+}
+
+/// Background worker that reconciles credit balances against the journal
+pub struct CreditReconciliationWorker {
+    credit_repo: Arc<dyn CreditRepository>,
+    hold_repo: Arc<dyn CreditHoldRepository>,
+    config: CreditReconciliationConfig,
+}
+
+impl CreditReconciliationWorker {
+    /// Create a new credit reconciliation worker
+    pub fn new(
+        credit_repo: Arc<dyn CreditRepository>,
+        hold_repo: Arc<dyn CreditHoldRepository>,
+        config: CreditReconciliationConfig,
+    ) -> Self {
+        Self {
+            credit_repo,
+            hold_repo,
+            config,
+        }
+    }
+
+    /// Start the worker as a background task with graceful shutdown support
+    pub fn start(self, cancel_token: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
+
+            info!(
+                poll_interval_secs = self.config.poll_interval_secs,
+                "Credit reconciliation worker started"
+            );
+
+            loop {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        info!("Credit reconciliation worker shutting down");
+                        break;
+                    }
+                    _ = tokio::time::sleep(poll_interval) => {
+                        if let Err(e) = self.reconcile().await {
+                            error!(error = %e, "Failed to reconcile credit ledger");
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Run one reconciliation and log what it found
+    async fn reconcile(&self) -> Result<(), AppError> {
+        let service = CreditService::new(self.credit_repo.clone(), self.hold_repo.clone());
+        let report = service.reconcile().await?;
+
+        if report.is_clean() {
+            info!(
+                balances_checked = report.balances_checked,
+                "Credit ledger reconciled"
+            );
+            return Ok(());
+        }
+
+        for drift in report.drift.iter().take(MAX_LOGGED_DRIFT) {
+            warn!(
+                owner_type = drift.owner_type.as_str(),
+                owner_id = %drift.owner_id,
+                currency = %drift.currency,
+                stored_balance = drift.stored_balance,
+                ledger_balance = drift.ledger_balance,
+                "Credit balance drifted from the ledger"
+            );
+        }
+        error!(
+            balances_checked = report.balances_checked,
+            drifted_balances = report.drift.len(),
+            unposted_transactions = report.unposted_transactions,
+            unbalanced_currencies = ?report.unbalanced_currencies,
+            "Credit ledger reconciliation found discrepancies"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = CreditReconciliationConfig::default();
+        assert_eq!(config.poll_interval_secs, 3600);
+    }
+}
@@ -3,32 +3,84 @@
 //! Provides a professional-grade credit system with:
 //! - Balance queries with held amount tracking
 //! - Direct spend operations with idempotency
-//! - Hold/capture pattern for two-phase commits
+//! - Hold/capture pattern for two-phase commits, with partial captures
 //! - Full audit trail for all transactions
+//! - Promotional grants that are spent first and expire unused
+//! - Double-entry journal reports and reconciliation
+//! - Daily and monthly spending budgets with soft and hard limits
 
-use chrono::Duration;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::repositories::{
+    CreditBudgetCharge, CreditBudgetRepository, CreditGrantEntity, CreditGrantSource,
     CreditHoldEntity, CreditHoldRepository, CreditRepository, CreditTransactionEntity,
+    CreditTxType, HoldStatus, MemberSpendCap, SpendLimits,
 };
 
 // Re-export types for external consumers
 pub use super::credit_types::{
-    AdjustResult, CreditBalance, CreditHistory, CreditHistoryItem, HoldResult, SpendResult,
+    AdjustResult, BudgetAlert, CaptureResult, CreditBalance, CreditBucket, CreditHistory,
+    CreditHistoryItem, GrantResult, HoldResult, LedgerAccountLine, LedgerReport, OrgSpender,
+    ReconciliationReport, SpendResult,
 };
 
 /// Default hold TTL (15 minutes)
 const DEFAULT_HOLD_TTL_MINUTES: i64 = 15;
 
+/// Maximum number of grants expired per run of the expiry job
+const MAX_GRANTS_EXPIRED_PER_RUN: u32 = 1000;
+
+/// Maximum number of drifted balances returned by a reconciliation run
+const MAX_RECONCILIATION_DRIFT: u32 = 1000;
+
+/// Maximum number of settled holds whose budget reservations are returned
+/// per run of the expiry job
+const MAX_BUDGET_HOLDS_SETTLED_PER_RUN: u32 = 1000;
+
+/// Midnight (UTC) on the first day of `now`'s month
+fn month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Soft limits crossed by consuming `amount` from `charges`, given the usage
+/// of each afterwards
+fn budget_alerts(
+    charges: Vec<CreditBudgetCharge>,
+    used_after: Vec<i64>,
+    amount: i64,
+) -> Vec<BudgetAlert> {
+    charges
+        .into_iter()
+        .zip(used_after)
+        .filter(|(charge, used)| {
+            charge
+                .budget
+                .soft_limit
+                .is_some_and(|soft| used - amount < soft && *used >= soft)
+        })
+        .map(|(charge, used)| BudgetAlert {
+            budget: charge.budget,
+            period_start: charge.period_start,
+            used_lamports: used,
+        })
+        .collect()
+}
+
 /// Credit service for balance, spending, and hold operations
 pub struct CreditService {
     credit_repo: Arc<dyn CreditRepository>,
     hold_repo: Arc<dyn CreditHoldRepository>,
     /// Maximum spend per transaction in lamports (0 = no limit)
     max_spend_per_transaction_lamports: u64,
+    /// Spending budgets enforced on spends and holds (None = not enforced)
+    budget_repo: Option<Arc<dyn CreditBudgetRepository>>,
+    /// API key making the spends, for API key budgets
+    api_key_id: Option<Uuid>,
 }
 
 impl CreditService {
@@ -50,9 +102,23 @@ impl CreditService {
             credit_repo,
             hold_repo,
             max_spend_per_transaction_lamports,
+            budget_repo: None,
+            api_key_id: None,
         }
     }
 
+    /// Enforce spending budgets on spends and holds
+    pub fn with_budgets(mut self, budget_repo: Arc<dyn CreditBudgetRepository>) -> Self {
+        self.budget_repo = Some(budget_repo);
+        self
+    }
+
+    /// Count spends and holds against the budgets of `api_key_id`
+    pub fn for_api_key(mut self, api_key_id: Option<Uuid>) -> Self {
+        self.api_key_id = api_key_id;
+        self
+    }
+
     /// Get user's credit balance for a specific currency
     pub async fn get_balance(
         &self,
@@ -63,7 +129,11 @@ impl CreditService {
             .credit_repo
             .get_or_create_balance(user_id, currency)
             .await?;
-        Ok(CreditBalance::from_entity(balance_entity))
+        let grants = self
+            .credit_repo
+            .get_grants(user_id, Some(currency), true)
+            .await?;
+        Ok(CreditBalance::from_entity(balance_entity).with_grants(&grants))
     }
 
     /// Get user's credit balance in lamports (simple lookup)
@@ -78,11 +148,7 @@ impl CreditService {
     /// Get all balances for a user (currently just SOL)
     pub async fn get_all_balances(&self, user_id: Uuid) -> Result<Vec<CreditBalance>, AppError> {
         // Currently we only support SOL
-        let sol_balance = self
-            .credit_repo
-            .get_or_create_balance(user_id, "SOL")
-            .await?;
-        Ok(vec![CreditBalance::from_entity(sol_balance)])
+        Ok(vec![self.get_balance(user_id, "SOL").await?])
     }
 
     /// Get transaction history for a user
@@ -166,6 +232,34 @@ impl CreditService {
         reference_type: &str,
         reference_id: Uuid,
         metadata: Option<serde_json::Value>,
+    ) -> Result<SpendResult, AppError> {
+        self.spend_inner(
+            user_id,
+            None,
+            SpendLimits::default(),
+            amount,
+            currency,
+            idempotency_key,
+            reference_type,
+            reference_id,
+            metadata,
+        )
+        .await
+    }
+
+    /// Debit `org_id`'s balance when set, otherwise `user_id`'s
+    #[allow(clippy::too_many_arguments)]
+    async fn spend_inner(
+        &self,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        mut limits: SpendLimits,
+        amount: i64,
+        currency: &str,
+        idempotency_key: String,
+        reference_type: &str,
+        reference_id: Uuid,
+        metadata: Option<serde_json::Value>,
     ) -> Result<SpendResult, AppError> {
         if amount <= 0 {
             return Err(AppError::Validation("Amount must be positive".into()));
@@ -187,7 +281,7 @@ impl CreditService {
             )));
         }
 
-        let tx = CreditTransactionEntity::new_spend_with_reference(
+        let mut tx = CreditTransactionEntity::new_spend_with_reference(
             user_id,
             amount,
             currency,
@@ -196,18 +290,27 @@ impl CreditService {
             reference_id,
             metadata,
         );
+        if let Some(org_id) = org_id {
+            tx = tx.for_org(org_id);
+        }
         let tx_id = tx.id;
 
-        let new_balance = self
+        // Budgets are consumed in the same transaction as the debit
+        limits.budgets = self.budget_charges(user_id, org_id, currency).await?;
+        let (new_balance, budget_usage) = self
             .credit_repo
-            .deduct_credit(user_id, amount, currency, tx)
+            .deduct_credit_limited(user_id, amount, currency, tx, &limits)
             .await?;
+        let budget_alerts = budget_alerts(limits.budgets, budget_usage, amount);
 
         Ok(SpendResult {
             transaction_id: tx_id,
+            user_id,
             new_balance_lamports: new_balance,
             amount_lamports: amount,
             currency: currency.to_string(),
+            org_id,
+            budget_alerts,
         })
     }
 
@@ -240,6 +343,36 @@ impl CreditService {
         reference_type: Option<&str>,
         reference_id: Option<Uuid>,
         metadata: Option<serde_json::Value>,
+    ) -> Result<HoldResult, AppError> {
+        self.hold_inner(
+            user_id,
+            None,
+            SpendLimits::default(),
+            amount,
+            currency,
+            idempotency_key,
+            ttl_minutes,
+            reference_type,
+            reference_id,
+            metadata,
+        )
+        .await
+    }
+
+    /// Reserve `org_id`'s balance when set, otherwise `user_id`'s
+    #[allow(clippy::too_many_arguments)]
+    async fn hold_inner(
+        &self,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        mut limits: SpendLimits,
+        amount: i64,
+        currency: &str,
+        idempotency_key: String,
+        ttl_minutes: Option<i64>,
+        reference_type: Option<&str>,
+        reference_id: Option<Uuid>,
+        metadata: Option<serde_json::Value>,
     ) -> Result<HoldResult, AppError> {
         if amount <= 0 {
             return Err(AppError::Validation("Amount must be positive".into()));
@@ -264,20 +397,27 @@ impl CreditService {
         // Fast-path balance check — rejects most insufficient-balance requests
         // without touching the hold table. The authoritative atomic check is in
         // the repository's create_hold transaction (SRV-01).
-        let balance = self
-            .credit_repo
-            .get_or_create_balance(user_id, currency)
-            .await?;
-        if balance.available() < amount {
+        let available = match org_id {
+            Some(org_id) => self
+                .credit_repo
+                .get_or_create_org_balance(org_id, currency)
+                .await?
+                .available(),
+            None => self
+                .credit_repo
+                .get_or_create_balance(user_id, currency)
+                .await?
+                .available(),
+        };
+        if available < amount {
             return Err(AppError::Validation(format!(
                 "Insufficient available balance: have {}, need {}",
-                balance.available(),
-                amount
+                available, amount
             )));
         }
 
         let ttl = Duration::minutes(ttl_minutes.unwrap_or(DEFAULT_HOLD_TTL_MINUTES));
-        let hold = CreditHoldEntity::new(
+        let mut hold = CreditHoldEntity::new(
             user_id,
             amount,
             currency,
@@ -287,14 +427,22 @@ impl CreditService {
             reference_id,
             metadata,
         );
+        if let Some(org_id) = org_id {
+            hold = hold.for_org(org_id);
+        }
 
-        let result = self.hold_repo.create_hold(hold).await?;
+        // A new hold consumes from its budgets in the same transaction; an
+        // existing one was counted when created
+        limits.budgets = self.budget_charges(user_id, org_id, currency).await?;
+        let (result, budget_usage) = self.hold_repo.create_hold(hold, &limits).await?;
+        let budget_alerts = budget_alerts(limits.budgets, budget_usage, amount);
 
         Ok(HoldResult {
             hold_id: result.hold().id,
             is_new: result.is_new(),
             amount_lamports: result.hold().amount,
             expires_at: result.hold().expires_at,
+            budget_alerts,
         })
     }
 
@@ -303,6 +451,25 @@ impl CreditService {
     /// This finalizes the two-phase commit, deducting the held credits.
     /// Returns an error if the hold has expired.
     pub async fn capture(&self, hold_id: Uuid) -> Result<SpendResult, AppError> {
+        Ok(self.capture_partial(hold_id, None, None, true).await?.spend)
+    }
+
+    /// Capture part of a hold
+    ///
+    /// Captures `amount` (default: everything left on the hold). With
+    /// `release_remainder` the hold is settled and the rest returns to the
+    /// available balance; otherwise it stays reserved for further captures.
+    ///
+    /// Each capture is keyed by `idempotency_key` (default: the hold's own
+    /// key, so a hold has at most one unkeyed capture). Repeating a key
+    /// returns the original capture instead of spending again.
+    pub async fn capture_partial(
+        &self,
+        hold_id: Uuid,
+        amount: Option<i64>,
+        idempotency_key: Option<&str>,
+        release_remainder: bool,
+    ) -> Result<CaptureResult, AppError> {
         // Get the hold first to know the details
         let hold = self
             .hold_repo
@@ -310,6 +477,56 @@ impl CreditService {
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Hold {} not found", hold_id)))?;
 
+        if idempotency_key.is_none() && !release_remainder {
+            return Err(AppError::Validation(
+                "idempotencyKey is required for captures that keep the hold open".into(),
+            ));
+        }
+        let tx_key = match idempotency_key {
+            Some(key) => format!("{}:{}", hold.idempotency_key, key),
+            None => hold.idempotency_key.clone(),
+        };
+
+        // Replay: the capture already happened, report it again
+        if let Some(existing) = self
+            .credit_repo
+            .find_transaction_by_idempotency_key(hold.user_id, &tx_key)
+            .await?
+        {
+            if existing.hold_id != Some(hold_id) {
+                return Err(AppError::Validation(format!(
+                    "Idempotency key already used for transaction {}",
+                    existing.id
+                )));
+            }
+            let new_balance = match hold.org_id {
+                Some(org_id) => {
+                    self.credit_repo
+                        .get_or_create_org_balance(org_id, &hold.currency)
+                        .await?
+                        .balance
+                }
+                None => {
+                    self.credit_repo
+                        .get_balance(hold.user_id, &hold.currency)
+                        .await?
+                }
+            };
+            return Ok(CaptureResult {
+                spend: SpendResult {
+                    transaction_id: existing.id,
+                    user_id: hold.user_id,
+                    new_balance_lamports: new_balance,
+                    amount_lamports: -existing.amount,
+                    currency: hold.currency.clone(),
+                    org_id: hold.org_id,
+                    budget_alerts: Vec::new(),
+                },
+                hold,
+                replayed: true,
+            });
+        }
+
         // CRITICAL: Validate hold can be captured (prevents race condition with expiry job)
         if !hold.can_capture() {
             if hold.is_expired() {
@@ -337,37 +554,151 @@ impl CreditService {
             )));
         }
 
+        let amount = amount.unwrap_or_else(|| hold.remaining());
+        if amount <= 0 || amount > hold.remaining() {
+            return Err(AppError::Validation(format!(
+                "Capture amount must be between 1 and {} lamports",
+                hold.remaining()
+            )));
+        }
+
         // Create the transaction from the hold
-        let tx = CreditTransactionEntity::from_captured_hold(
+        let mut tx = CreditTransactionEntity::from_captured_hold(
             hold.user_id,
-            hold.amount,
+            amount,
             &hold.currency,
             hold_id,
-            &hold.idempotency_key,
+            &tx_key,
             hold.reference_type.as_deref(),
             hold.reference_id,
             hold.metadata.clone(),
         );
+        if let Some(org_id) = hold.org_id {
+            tx = tx.for_org(org_id);
+        }
         let tx_id = tx.id;
 
         // SRV-02: Capture hold + deduct balance + insert transaction record
         // in a single DB transaction to prevent inconsistency on crash.
-        let (_captured, new_balance) =
-            self.hold_repo.capture_hold(hold_id, tx_id, tx).await?;
+        let (captured, new_balance) = self
+            .hold_repo
+            .capture_hold(hold_id, tx, release_remainder)
+            .await?;
+        self.settle_hold_budgets(&captured).await;
 
-        Ok(SpendResult {
-            transaction_id: tx_id,
-            new_balance_lamports: new_balance,
-            amount_lamports: hold.amount,
-            currency: hold.currency,
+        Ok(CaptureResult {
+            spend: SpendResult {
+                transaction_id: tx_id,
+                user_id: hold.user_id,
+                new_balance_lamports: new_balance,
+                amount_lamports: amount,
+                currency: hold.currency,
+                org_id: hold.org_id,
+                budget_alerts: Vec::new(),
+            },
+            hold: captured,
+            replayed: false,
         })
     }
 
+    /// Increase a pending hold and/or push back its expiry
+    ///
+    /// `amount` is the new total authorized (including anything already
+    /// captured). Values below the current ones are ignored, so retries are
+    /// harmless. An increase must fit in the available balance.
+    pub async fn extend_hold(
+        &self,
+        hold_id: Uuid,
+        amount: Option<i64>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<CreditHoldEntity, AppError> {
+        let hold = self
+            .hold_repo
+            .get_hold(hold_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Hold {} not found", hold_id)))?;
+
+        let amount = amount.unwrap_or(hold.amount).max(hold.amount);
+        let expires_at = expires_at.unwrap_or(hold.expires_at).max(hold.expires_at);
+        if amount == hold.amount && expires_at == hold.expires_at {
+            return Ok(hold);
+        }
+
+        if !hold.can_capture() {
+            return Err(AppError::Validation(format!(
+                "Hold {} cannot be extended, status: {}",
+                hold_id,
+                if hold.is_expired() {
+                    "expired"
+                } else {
+                    hold.status.as_str()
+                }
+            )));
+        }
+
+        if self.max_spend_per_transaction_lamports > 0
+            && amount > self.max_spend_per_transaction_lamports as i64
+        {
+            return Err(AppError::Validation(format!(
+                "Maximum hold per transaction is {} lamports",
+                self.max_spend_per_transaction_lamports
+            )));
+        }
+
+        // Fast-path balance check; the repository reserves the increase atomically
+        let increase = amount - hold.amount;
+        let mut limits = SpendLimits::default();
+        if increase > 0 {
+            if let Some(org_id) = hold.org_id {
+                limits.member_cap = self
+                    .check_org_member_cap(org_id, hold.user_id, &hold.currency, increase)
+                    .await?;
+            }
+            let available = match hold.org_id {
+                Some(org_id) => self
+                    .credit_repo
+                    .get_or_create_org_balance(org_id, &hold.currency)
+                    .await?
+                    .available(),
+                None => self
+                    .credit_repo
+                    .get_or_create_balance(hold.user_id, &hold.currency)
+                    .await?
+                    .available(),
+            };
+            if available < increase {
+                return Err(AppError::Validation(format!(
+                    "Insufficient available balance: have {}, need {}",
+                    available, increase
+                )));
+            }
+        }
+
+        // The increase counts against the budgets the hold was placed under
+        if increase > 0 {
+            limits.budgets = self.hold_budget_charges(hold_id).await?;
+        }
+        let (hold, budget_usage) = self
+            .hold_repo
+            .extend_hold(hold_id, amount, expires_at, &limits)
+            .await?;
+        for alert in budget_alerts(limits.budgets, budget_usage, increase) {
+            tracing::warn!(
+                budget_id = %alert.budget.id,
+                hold_id = %hold_id,
+                used_lamports = alert.used_lamports,
+                "Hold increase crossed a budget's soft limit"
+            );
+        }
+        Ok(hold)
+    }
+
     /// Release a hold, returning credits to available balance
     ///
     /// Use this when an operation is cancelled or fails.
     pub async fn release(&self, hold_id: Uuid) -> Result<(), AppError> {
-        self.hold_repo.release_hold(hold_id).await?;
+        let hold = self.hold_repo.release_hold(hold_id).await?;
+        self.settle_hold_budgets(&hold).await;
         Ok(())
     }
 
@@ -384,7 +715,9 @@ impl CreditService {
     ///
     /// Returns the number of holds expired. Called by the background task.
     pub async fn expire_holds(&self) -> Result<u64, AppError> {
-        self.hold_repo.expire_holds().await
+        let expired = self.hold_repo.expire_holds().await?;
+        self.settle_budget_holds().await?;
+        Ok(expired)
     }
 
     // =========================================================================
@@ -393,8 +726,9 @@ impl CreditService {
 
     /// Adjust a user's credit balance (admin operation)
     ///
-    /// Use for refunds, bonuses, promotional credits, or manual corrections.
-    /// Positive amounts add credits, negative amounts remove credits.
+    /// Use for refunds, bonuses, or manual corrections; promotional credit
+    /// that should expire belongs in [`Self::grant`]. Positive amounts add
+    /// credits, negative amounts remove credits.
     ///
     /// # Arguments
     /// * `admin_id` - ID of the admin performing the adjustment
@@ -465,84 +799,1150 @@ impl CreditService {
             amount_lamports: amount,
         })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::repositories::{InMemoryCreditHoldRepository, InMemoryCreditRepository};
+    // =========================================================================
+    // PROMOTIONAL GRANTS
+    // =========================================================================
 
-    fn create_service() -> CreditService {
-        let credit_repo: Arc<dyn CreditRepository> = Arc::new(InMemoryCreditRepository::new());
-        let hold_repo: Arc<dyn CreditHoldRepository> =
-            Arc::new(InMemoryCreditHoldRepository::new());
-        CreditService::new(credit_repo, hold_repo)
-    }
+    /// Grant promotional credit to a user
+    ///
+    /// Grant credit is spent before paid credit (highest `priority` first,
+    /// then soonest expiry) and any of it left at `expires_at` is forfeited.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn grant(
+        &self,
+        admin_id: Option<Uuid>,
+        user_id: Uuid,
+        amount: i64,
+        currency: &str,
+        source: CreditGrantSource,
+        priority: i32,
+        expires_at: Option<DateTime<Utc>>,
+        reason: Option<&str>,
+    ) -> Result<GrantResult, AppError> {
+        if amount <= 0 {
+            return Err(AppError::Validation("Grant amount must be positive".into()));
+        }
+        if expires_at.is_some_and(|e| e <= Utc::now()) {
+            return Err(AppError::Validation(
+                "Grant expiry must be in the future".into(),
+            ));
+        }
 
-    #[tokio::test]
-    async fn test_get_balance_new_user() {
-        let service = create_service();
+        let grant = CreditGrantEntity::new(
+            user_id,
+            amount,
+            currency,
+            source,
+            priority,
+            expires_at,
+            reason
+                .map(str::trim)
+                .filter(|r| !r.is_empty())
+                .map(String::from),
+            admin_id,
+        );
+        let new_balance = self.credit_repo.create_grant(grant.clone()).await?;
 
-        let balance = service.get_balance(Uuid::new_v4(), "SOL").await.unwrap();
-        assert_eq!(balance.balance_lamports, 0);
-        assert_eq!(balance.available_lamports, 0);
-        assert_eq!(balance.currency, "SOL");
+        Ok(GrantResult {
+            grant,
+            new_balance_lamports: new_balance,
+        })
     }
 
-    #[tokio::test]
-    async fn test_has_sufficient_balance() {
-        let service = create_service();
-        let user_id = Uuid::new_v4();
-
-        // New user has zero balance
-        let has_balance = service
-            .has_sufficient_balance(user_id, "SOL", 1000)
+    /// List a user's grants in spend order
+    pub async fn get_grants(
+        &self,
+        user_id: Uuid,
+        currency: Option<&str>,
+        active_only: bool,
+    ) -> Result<Vec<CreditGrantEntity>, AppError> {
+        self.credit_repo
+            .get_grants(user_id, currency, active_only)
             .await
-            .unwrap();
-        assert!(!has_balance);
     }
 
-    #[tokio::test]
-    async fn test_spend_insufficient_balance() {
-        let service = create_service();
-        let user_id = Uuid::new_v4();
-        let order_id = Uuid::new_v4();
+    /// Revoke a user's grant, forfeiting its unspent credit now
+    ///
+    /// Credit reserved by pending holds is forfeited by the expiry job once
+    /// the holds settle.
+    pub async fn revoke_grant(
+        &self,
+        user_id: Uuid,
+        grant_id: Uuid,
+    ) -> Result<CreditGrantEntity, AppError> {
+        match self.credit_repo.get_grant(grant_id).await? {
+            Some(grant) if grant.user_id == user_id => {}
+            _ => return Err(AppError::NotFound(format!("Grant {} not found", grant_id))),
+        }
+        self.credit_repo.expire_grant(grant_id).await
+    }
 
-        // Try to spend without balance
-        let result = service
-            .spend(
-                user_id,
-                1000,
-                "SOL",
-                "order-123".to_string(),
-                "order",
-                order_id,
-                None,
-            )
-            .await;
+    /// Forfeit unspent credit of expired grants
+    ///
+    /// Returns the number of grants expired. Called by the background task.
+    pub async fn expire_grants(&self) -> Result<u64, AppError> {
+        self.credit_repo
+            .expire_grants(Utc::now(), MAX_GRANTS_EXPIRED_PER_RUN)
+            .await
+    }
 
-        assert!(result.is_err());
+    // =========================================================================
+    // ORGANIZATION BALANCES
+    // =========================================================================
+
+    /// Get an organization's credit balance for a specific currency
+    pub async fn get_org_balance(
+        &self,
+        org_id: Uuid,
+        currency: &str,
+    ) -> Result<CreditBalance, AppError> {
+        let balance = self
+            .credit_repo
+            .get_or_create_org_balance(org_id, currency)
+            .await?;
+        Ok(CreditBalance::from_org_entity(balance))
     }
 
-    #[tokio::test]
-    async fn test_hold_insufficient_balance() {
-        let service = create_service();
-        let user_id = Uuid::new_v4();
+    /// Get transaction history of an organization's balance
+    pub async fn get_org_history(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+        tx_type: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<CreditHistory, AppError> {
+        let (transactions, total) = tokio::join!(
+            self.credit_repo
+                .get_org_transactions(org_id, currency, tx_type, limit, offset),
+            self.credit_repo
+                .count_org_transactions(org_id, currency, tx_type)
+        );
 
-        // Try to hold without balance
-        let result = service
-            .hold(
-                user_id,
-                1000,
-                "SOL",
-                "order-123".to_string(),
-                None,
-                Some("order"),
-                Some(Uuid::new_v4()),
-                None,
-            )
-            .await;
+        Ok(CreditHistory {
+            items: transactions?
+                .into_iter()
+                .map(CreditHistoryItem::from)
+                .collect(),
+            total: total?,
+            limit,
+            offset,
+        })
+    }
 
-        assert!(result.is_err());
+    /// Get pending holds on an organization's balance
+    pub async fn get_pending_org_holds(
+        &self,
+        org_id: Uuid,
+        currency: Option<&str>,
+    ) -> Result<Vec<CreditHoldEntity>, AppError> {
+        self.hold_repo.get_pending_org_holds(org_id, currency).await
+    }
+
+    /// What a member has spent from the org balance this calendar month
+    /// (UTC), including their pending holds
+    pub async fn org_member_month_spend(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        currency: &str,
+    ) -> Result<i64, AppError> {
+        let month_start = month_start(Utc::now());
+
+        let spent = self
+            .credit_repo
+            .sum_org_member_spend(org_id, user_id, currency, month_start)
+            .await?;
+        let held: i64 = self
+            .hold_repo
+            .get_pending_org_holds(org_id, Some(currency))
+            .await?
+            .iter()
+            .filter(|h| h.user_id == user_id)
+            .map(|h| h.remaining())
+            .sum();
+        Ok(spent + held)
+    }
+
+    /// Check that a member may spend `amount` from the org balance
+    ///
+    /// The member's `spend_allowed` setting overrides their role, and the
+    /// monthly cap (if any) covers captured spends plus pending holds. The
+    /// cap check here is a fast path; the returned limits carry the cap to
+    /// the repository, which enforces it under the org balance lock.
+    async fn authorize_org_spend(
+        &self,
+        spender: &OrgSpender,
+        currency: &str,
+        amount: i64,
+    ) -> Result<SpendLimits, AppError> {
+        let settings = self
+            .credit_repo
+            .get_org_member_settings(spender.org_id, spender.user_id)
+            .await?;
+
+        let can_spend = settings
+            .as_ref()
+            .and_then(|s| s.spend_allowed)
+            .unwrap_or(spender.role_can_spend);
+        if !can_spend {
+            return Err(AppError::Forbidden(
+                "Member is not allowed to spend organization credits".into(),
+            ));
+        }
+
+        let member_cap = settings
+            .and_then(|s| s.monthly_cap)
+            .map(|cap| MemberSpendCap {
+                cap,
+                since: month_start(Utc::now()),
+            });
+        if let Some(cap) = &member_cap {
+            self.enforce_org_member_cap(spender.org_id, spender.user_id, currency, amount, cap)
+                .await?;
+        }
+
+        Ok(SpendLimits {
+            member_cap,
+            ..Default::default()
+        })
+    }
+
+    /// Check a member's monthly cap (if any) before reserving `amount` more,
+    /// returning it for the repository to enforce atomically
+    async fn check_org_member_cap(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        currency: &str,
+        amount: i64,
+    ) -> Result<Option<MemberSpendCap>, AppError> {
+        let settings = self
+            .credit_repo
+            .get_org_member_settings(org_id, user_id)
+            .await?;
+        let Some(cap) = settings.and_then(|s| s.monthly_cap) else {
+            return Ok(None);
+        };
+        let cap = MemberSpendCap {
+            cap,
+            since: month_start(Utc::now()),
+        };
+        self.enforce_org_member_cap(org_id, user_id, currency, amount, &cap)
+            .await?;
+        Ok(Some(cap))
+    }
+
+    async fn enforce_org_member_cap(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        currency: &str,
+        amount: i64,
+        cap: &MemberSpendCap,
+    ) -> Result<(), AppError> {
+        let used = self
+            .org_member_month_spend(org_id, user_id, currency)
+            .await?;
+        cap.check(used, amount)
+    }
+
+    /// Spend credits from an organization's balance on behalf of a member
+    #[allow(clippy::too_many_arguments)]
+    pub async fn spend_from_org(
+        &self,
+        spender: OrgSpender,
+        amount: i64,
+        currency: &str,
+        idempotency_key: String,
+        reference_type: &str,
+        reference_id: Uuid,
+        metadata: Option<serde_json::Value>,
+    ) -> Result<SpendResult, AppError> {
+        // A retried request returns the original spend without counting it
+        // against the member's cap again
+        if let Some(existing) = self
+            .credit_repo
+            .find_transaction_by_idempotency_key(spender.user_id, &idempotency_key)
+            .await?
+        {
+            if existing.org_id != Some(spender.org_id) || existing.tx_type != CreditTxType::Spend {
+                return Err(AppError::Validation(
+                    "Idempotency key was already used for a different transaction".into(),
+                ));
+            }
+            let balance = self
+                .credit_repo
+                .get_or_create_org_balance(spender.org_id, &existing.currency)
+                .await?;
+            return Ok(SpendResult {
+                transaction_id: existing.id,
+                user_id: spender.user_id,
+                new_balance_lamports: balance.balance,
+                amount_lamports: -existing.amount,
+                currency: existing.currency,
+                org_id: existing.org_id,
+                budget_alerts: Vec::new(),
+            });
+        }
+
+        let limits = self.authorize_org_spend(&spender, currency, amount).await?;
+        self.spend_inner(
+            spender.user_id,
+            Some(spender.org_id),
+            limits,
+            amount,
+            currency,
+            idempotency_key,
+            reference_type,
+            reference_id,
+            metadata,
+        )
+        .await
+    }
+
+    /// Reserve credits from an organization's balance on behalf of a member
+    #[allow(clippy::too_many_arguments)]
+    pub async fn hold_from_org(
+        &self,
+        spender: OrgSpender,
+        amount: i64,
+        currency: &str,
+        idempotency_key: String,
+        ttl_minutes: Option<i64>,
+        reference_type: Option<&str>,
+        reference_id: Option<Uuid>,
+        metadata: Option<serde_json::Value>,
+    ) -> Result<HoldResult, AppError> {
+        // A retried request returns the existing hold without counting it twice
+        if let Some(existing) = self
+            .hold_repo
+            .get_hold_by_idempotency_key(spender.user_id, &idempotency_key)
+            .await?
+        {
+            return Ok(HoldResult {
+                hold_id: existing.id,
+                is_new: false,
+                amount_lamports: existing.amount,
+                expires_at: existing.expires_at,
+                budget_alerts: Vec::new(),
+            });
+        }
+
+        let limits = self.authorize_org_spend(&spender, currency, amount).await?;
+        self.hold_inner(
+            spender.user_id,
+            Some(spender.org_id),
+            limits,
+            amount,
+            currency,
+            idempotency_key,
+            ttl_minutes,
+            reference_type,
+            reference_id,
+            metadata,
+        )
+        .await
+    }
+
+    /// Adjust an organization's credit balance (admin operation, e.g. a
+    /// company credit purchase)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn adjust_org(
+        &self,
+        admin_id: Uuid,
+        org_id: Uuid,
+        amount: i64,
+        currency: &str,
+        reason: &str,
+        reference_type: Option<&str>,
+        reference_id: Option<Uuid>,
+    ) -> Result<AdjustResult, AppError> {
+        if amount == 0 {
+            return Err(AppError::Validation("Amount cannot be zero".into()));
+        }
+
+        if reason.trim().is_empty() {
+            return Err(AppError::Validation("Reason is required".into()));
+        }
+
+        let tx = CreditTransactionEntity::new_adjustment(
+            admin_id,
+            amount,
+            currency,
+            admin_id,
+            reason,
+            reference_type,
+            reference_id,
+        )
+        .for_org(org_id);
+        let tx_id = tx.id;
+
+        let new_balance = if amount > 0 {
+            self.credit_repo
+                .add_credit(admin_id, amount, currency, tx)
+                .await?
+        } else {
+            self.credit_repo
+                .deduct_credit(admin_id, amount.abs(), currency, tx)
+                .await?
+        };
+
+        Ok(AdjustResult {
+            transaction_id: tx_id,
+            new_balance_lamports: new_balance,
+            amount_lamports: amount,
+        })
+    }
+
+    // =========================================================================
+    // BUDGETS
+    // =========================================================================
+
+    /// Budgets covering a spend by `user_id` from `org_id`'s balance (or
+    /// their own), for the repository to consume from atomically
+    async fn budget_charges(
+        &self,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        currency: &str,
+    ) -> Result<Vec<CreditBudgetCharge>, AppError> {
+        let Some(budget_repo) = &self.budget_repo else {
+            return Ok(Vec::new());
+        };
+        let now = Utc::now();
+        Ok(budget_repo
+            .find_applicable(user_id, org_id, self.api_key_id, currency)
+            .await?
+            .into_iter()
+            .map(|budget| CreditBudgetCharge {
+                period_start: budget.period.start(now),
+                budget,
+            })
+            .collect())
+    }
+
+    /// Budgets a hold was placed under, which its increases count against
+    async fn hold_budget_charges(
+        &self,
+        hold_id: Uuid,
+    ) -> Result<Vec<CreditBudgetCharge>, AppError> {
+        let Some(budget_repo) = &self.budget_repo else {
+            return Ok(Vec::new());
+        };
+        let mut charges = Vec::new();
+        for key in budget_repo.hold_reservations(hold_id).await? {
+            if let Some(budget) = budget_repo.find_by_id(key.budget_id).await? {
+                charges.push(CreditBudgetCharge {
+                    budget,
+                    period_start: key.period_start,
+                });
+            }
+        }
+        Ok(charges)
+    }
+
+    /// Give back the uncaptured part of a settled hold to its budgets
+    async fn settle_hold_budgets(&self, hold: &CreditHoldEntity) {
+        let Some(budget_repo) = &self.budget_repo else {
+            return;
+        };
+        if hold.status == HoldStatus::Pending {
+            return;
+        }
+        let result = match budget_repo.take_hold_reservations(hold.id).await {
+            Ok(keys) if !keys.is_empty() => budget_repo.release(&keys, hold.remaining()).await,
+            Ok(_) => Ok(()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::warn!(
+                error = %e,
+                hold_id = %hold.id,
+                "Failed to release budget usage of settled hold"
+            );
+        }
+    }
+
+    /// Settle the budget reservations of holds that are no longer pending
+    /// (e.g. expired)
+    async fn settle_budget_holds(&self) -> Result<(), AppError> {
+        let Some(budget_repo) = &self.budget_repo else {
+            return Ok(());
+        };
+        for hold_id in budget_repo
+            .list_reserved_holds(MAX_BUDGET_HOLDS_SETTLED_PER_RUN)
+            .await?
+        {
+            match self.hold_repo.get_hold(hold_id).await? {
+                Some(hold) => self.settle_hold_budgets(&hold).await,
+                None => {
+                    budget_repo.take_hold_reservations(hold_id).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // =========================================================================
+    // LEDGER
+    // =========================================================================
+
+    /// Trial balance: every ledger account's balance as of `as_of` (now if
+    /// None), per currency
+    pub async fn trial_balance(
+        &self,
+        currency: Option<&str>,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LedgerReport>, AppError> {
+        let totals = self
+            .credit_repo
+            .get_ledger_totals(currency, None, as_of)
+            .await?;
+        Ok(LedgerReport::build(&[], &totals))
+    }
+
+    /// Statement for `[from, to)`: opening balance, activity and closing
+    /// balance of every ledger account, per currency
+    pub async fn ledger_statement(
+        &self,
+        currency: Option<&str>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<LedgerReport>, AppError> {
+        if from >= to {
+            return Err(AppError::Validation(
+                "Statement start must be before its end".into(),
+            ));
+        }
+
+        let opening = self
+            .credit_repo
+            .get_ledger_totals(currency, None, Some(from))
+            .await?;
+        let period = self
+            .credit_repo
+            .get_ledger_totals(currency, Some(from), Some(to))
+            .await?;
+        Ok(LedgerReport::build(&opening, &period))
+    }
+
+    /// Recompute every balance from the journal and check that the journal
+    /// itself balances
+    pub async fn reconcile(&self) -> Result<ReconciliationReport, AppError> {
+        let checked_at = Utc::now();
+        let reconciliation = self
+            .credit_repo
+            .reconcile_balances(MAX_RECONCILIATION_DRIFT)
+            .await?;
+        let unbalanced_currencies = self
+            .trial_balance(None, None)
+            .await?
+            .into_iter()
+            .filter(|report| !report.is_balanced())
+            .map(|report| report.currency)
+            .collect();
+
+        Ok(ReconciliationReport {
+            checked_at,
+            balances_checked: reconciliation.balances_checked,
+            drift: reconciliation.drift,
+            unposted_transactions: reconciliation.unposted_transactions,
+            unbalanced_currencies,
+        })
+    }
+}
+
+/// Credit setup shared by the tests of services that charge credits
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use crate::repositories::{
+        InMemoryCreditBudgetRepository, InMemoryCreditHoldRepository, InMemoryCreditRepository,
+        InMemoryMembershipRepository,
+    };
+
+    /// In-memory credit, hold, budget and membership repositories wired together
+    pub(crate) struct CreditFixture {
+        pub credit_repo: Arc<dyn CreditRepository>,
+        pub hold_repo: Arc<dyn CreditHoldRepository>,
+        pub budget_repo: Arc<dyn CreditBudgetRepository>,
+        pub membership_repo: Arc<InMemoryMembershipRepository>,
+        pub credits: CreditService,
+    }
+
+    impl CreditFixture {
+        pub fn new() -> Self {
+            let budget_repo: Arc<dyn CreditBudgetRepository> =
+                Arc::new(InMemoryCreditBudgetRepository::new());
+            let credit_repo: Arc<dyn CreditRepository> =
+                Arc::new(InMemoryCreditRepository::new().with_budgets(budget_repo.clone()));
+            let hold_repo: Arc<dyn CreditHoldRepository> = Arc::new(
+                InMemoryCreditHoldRepository::with_credit_repo(credit_repo.clone())
+                    .with_budgets(budget_repo.clone()),
+            );
+            Self {
+                credits: CreditService::new(credit_repo.clone(), hold_repo.clone())
+                    .with_budgets(budget_repo.clone()),
+                credit_repo,
+                hold_repo,
+                budget_repo,
+                membership_repo: Arc::new(InMemoryMembershipRepository::new()),
+            }
+        }
+    }
+
+    /// Grant a user `amount` SOL
+    pub(crate) async fn fund(credits: &CreditService, user_id: Uuid, amount: i64) {
+        credits
+            .adjust(
+                Uuid::new_v4(),
+                user_id,
+                amount,
+                "SOL",
+                "purchase",
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    /// Grant an organization `amount` SOL
+    pub(crate) async fn fund_org(credits: &CreditService, org_id: Uuid, amount: i64) {
+        credits
+            .adjust_org(
+                Uuid::new_v4(),
+                org_id,
+                amount,
+                "SOL",
+                "purchase",
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        BudgetUsageKey, CreditBudgetEntity, CreditBudgetPeriod, CreditBudgetScope, HoldStatus,
+        InMemoryCreditBudgetRepository, InMemoryCreditHoldRepository, InMemoryCreditRepository,
+        LedgerAccount,
+    };
+
+    fn create_service() -> CreditService {
+        let credit_repo: Arc<dyn CreditRepository> = Arc::new(InMemoryCreditRepository::new());
+        let hold_repo: Arc<dyn CreditHoldRepository> =
+            Arc::new(InMemoryCreditHoldRepository::new());
+        CreditService::new(credit_repo, hold_repo)
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_new_user() {
+        let service = create_service();
+
+        let balance = service.get_balance(Uuid::new_v4(), "SOL").await.unwrap();
+        assert_eq!(balance.balance_lamports, 0);
+        assert_eq!(balance.available_lamports, 0);
+        assert_eq!(balance.currency, "SOL");
+    }
+
+    #[tokio::test]
+    async fn test_has_sufficient_balance() {
+        let service = create_service();
+        let user_id = Uuid::new_v4();
+
+        // New user has zero balance
+        let has_balance = service
+            .has_sufficient_balance(user_id, "SOL", 1000)
+            .await
+            .unwrap();
+        assert!(!has_balance);
+    }
+
+    #[tokio::test]
+    async fn test_spend_insufficient_balance() {
+        let service = create_service();
+        let user_id = Uuid::new_v4();
+        let order_id = Uuid::new_v4();
+
+        // Try to spend without balance
+        let result = service
+            .spend(
+                user_id,
+                1000,
+                "SOL",
+                "order-123".to_string(),
+                "order",
+                order_id,
+                None,
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hold_insufficient_balance() {
+        let service = create_service();
+        let user_id = Uuid::new_v4();
+
+        // Try to hold without balance
+        let result = service
+            .hold(
+                user_id,
+                1000,
+                "SOL",
+                "order-123".to_string(),
+                None,
+                Some("order"),
+                Some(Uuid::new_v4()),
+                None,
+            )
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_org_spend_respects_role_override_and_cap() {
+        let credit_repo: Arc<dyn CreditRepository> = Arc::new(InMemoryCreditRepository::new());
+        let hold_repo: Arc<dyn CreditHoldRepository> = Arc::new(
+            InMemoryCreditHoldRepository::with_credit_repo(credit_repo.clone()),
+        );
+        let service = CreditService::new(credit_repo.clone(), hold_repo);
+        let org_id = Uuid::new_v4();
+        let member_id = Uuid::new_v4();
+
+        service
+            .adjust_org(
+                Uuid::new_v4(),
+                org_id,
+                10_000,
+                "SOL",
+                "purchase",
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let spender = OrgSpender {
+            org_id,
+            user_id: member_id,
+            role_can_spend: false,
+        };
+        let spend = |key: &str, amount: i64| {
+            service.spend_from_org(
+                spender,
+                amount,
+                "SOL",
+                key.to_string(),
+                "order",
+                Uuid::new_v4(),
+                None,
+            )
+        };
+
+        // Plain members may not spend by default
+        assert!(matches!(spend("a", 100).await, Err(AppError::Forbidden(_))));
+
+        credit_repo
+            .upsert_org_member_settings(crate::repositories::OrgCreditMemberSettings {
+                org_id,
+                user_id: member_id,
+                spend_allowed: Some(true),
+                monthly_cap: Some(1_000),
+                updated_by: None,
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let result = spend("b", 600).await.unwrap();
+        assert_eq!(result.org_id, Some(org_id));
+        assert_eq!(result.new_balance_lamports, 9_400);
+
+        // Pending holds count towards the cap
+        service
+            .hold_from_org(spender, 300, "SOL", "c".into(), None, None, None, None)
+            .await
+            .unwrap();
+        assert!(matches!(spend("d", 200).await, Err(AppError::Forbidden(_))));
+        spend("e", 100).await.unwrap();
+
+        // The member's personal balance is untouched
+        assert_eq!(
+            service
+                .get_balance(member_id, "SOL")
+                .await
+                .unwrap()
+                .balance_lamports,
+            0
+        );
+        let org_balance = service.get_org_balance(org_id, "SOL").await.unwrap();
+        assert_eq!(org_balance.balance_lamports, 9_300);
+        let history = service
+            .get_org_history(org_id, None, None, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(history.total, 3);
+
+        // Partial captures move the hold's amount from held to spent
+        let hold_id = service.get_pending_org_holds(org_id, None).await.unwrap()[0].id;
+        service
+            .capture_partial(hold_id, Some(100), Some("part"), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            service
+                .org_member_month_spend(org_id, member_id, "SOL")
+                .await
+                .unwrap(),
+            1_000
+        );
+
+        // Increasing the hold is subject to the cap as well
+        assert!(matches!(
+            service.extend_hold(hold_id, Some(400), None).await,
+            Err(AppError::Forbidden(_))
+        ));
+
+        // A retried spend returns the original one, even with the cap used up
+        let replay = spend("b", 600).await.unwrap();
+        assert_eq!(replay.transaction_id, result.transaction_id);
+        assert_eq!(replay.amount_lamports, 600);
+        assert_eq!(replay.new_balance_lamports, 9_200);
+    }
+
+    #[tokio::test]
+    async fn test_ledger_statement_and_reconciliation() {
+        let credit_repo: Arc<dyn CreditRepository> = Arc::new(InMemoryCreditRepository::new());
+        let hold_repo: Arc<dyn CreditHoldRepository> = Arc::new(
+            InMemoryCreditHoldRepository::with_credit_repo(credit_repo.clone()),
+        );
+        let service = CreditService::new(credit_repo.clone(), hold_repo);
+        let user_id = Uuid::new_v4();
+
+        let tx = CreditTransactionEntity::new_privacy_deposit(user_id, 1000, "SOL", Uuid::new_v4());
+        credit_repo
+            .add_credit(user_id, 1000, "SOL", tx)
+            .await
+            .unwrap();
+        let before_spend = Utc::now();
+        service
+            .spend(
+                user_id,
+                400,
+                "SOL",
+                "order:1".into(),
+                "order",
+                Uuid::new_v4(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let line = |report: &LedgerReport, account: LedgerAccount| {
+            report
+                .lines
+                .iter()
+                .find(|l| l.account == account)
+                .cloned()
+                .unwrap()
+        };
+
+        let trial = service.trial_balance(Some("SOL"), None).await.unwrap();
+        assert_eq!(trial.len(), 1);
+        assert!(trial[0].is_balanced());
+        assert_eq!(
+            line(&trial[0], LedgerAccount::UserBalance).closing_balance,
+            600
+        );
+        assert_eq!(line(&trial[0], LedgerAccount::Revenue).closing_balance, 400);
+
+        // The deposit falls before the statement period, the spend inside it
+        let statement = service
+            .ledger_statement(None, before_spend, Utc::now() + Duration::seconds(1))
+            .await
+            .unwrap();
+        let user = line(&statement[0], LedgerAccount::UserBalance);
+        assert_eq!(user.opening_balance, 1000);
+        assert_eq!(user.debits, 400);
+        assert_eq!(user.closing_balance, 600);
+        assert_eq!(
+            line(&statement[0], LedgerAccount::DepositClearing).debits,
+            0
+        );
+
+        assert!(service
+            .ledger_statement(None, before_spend, before_spend)
+            .await
+            .is_err());
+
+        let report = service.reconcile().await.unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.balances_checked, 1);
+    }
+
+    #[tokio::test]
+    async fn test_partial_capture_replay_and_extend() {
+        let credit_repo: Arc<dyn CreditRepository> = Arc::new(InMemoryCreditRepository::new());
+        let hold_repo: Arc<dyn CreditHoldRepository> = Arc::new(
+            InMemoryCreditHoldRepository::with_credit_repo(credit_repo.clone()),
+        );
+        let service = CreditService::new(credit_repo.clone(), hold_repo);
+        let user_id = Uuid::new_v4();
+
+        let tx = CreditTransactionEntity::new_privacy_deposit(user_id, 1000, "SOL", Uuid::new_v4());
+        credit_repo
+            .add_credit(user_id, 1000, "SOL", tx)
+            .await
+            .unwrap();
+        let hold = service
+            .hold(user_id, 300, "SOL", "job:1".into(), None, None, None, None)
+            .await
+            .unwrap();
+
+        // Incremental captures need their own key
+        assert!(service
+            .capture_partial(hold.hold_id, Some(100), None, false)
+            .await
+            .is_err());
+        let first = service
+            .capture_partial(hold.hold_id, Some(100), Some("step-1"), false)
+            .await
+            .unwrap();
+        assert!(!first.replayed);
+        assert_eq!(first.spend.new_balance_lamports, 900);
+        assert_eq!(first.hold.remaining(), 200);
+
+        // Retrying the same key does not spend again
+        let retry = service
+            .capture_partial(hold.hold_id, Some(100), Some("step-1"), false)
+            .await
+            .unwrap();
+        assert!(retry.replayed);
+        assert_eq!(retry.spend.transaction_id, first.spend.transaction_id);
+        assert_eq!(retry.spend.new_balance_lamports, 900);
+
+        // The job needs more than it reserved
+        let extended = service
+            .extend_hold(hold.hold_id, Some(500), None)
+            .await
+            .unwrap();
+        assert_eq!(extended.amount, 500);
+        assert_eq!(extended.remaining(), 400);
+        assert!(service
+            .capture_partial(hold.hold_id, Some(401), Some("step-2"), true)
+            .await
+            .is_err());
+
+        // Final capture of a smaller amount settles the hold
+        let last = service
+            .capture_partial(hold.hold_id, Some(150), Some("step-2"), true)
+            .await
+            .unwrap();
+        assert_eq!(last.hold.status, HoldStatus::Captured);
+        assert_eq!(last.hold.captured_amount, 250);
+        assert_eq!(last.spend.new_balance_lamports, 750);
+        assert!(service
+            .get_pending_holds(user_id, None)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(service
+            .capture_partial(hold.hold_id, Some(10), Some("step-3"), true)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_budgets_cap_spends_and_holds() {
+        let budget_repo: Arc<dyn CreditBudgetRepository> =
+            Arc::new(InMemoryCreditBudgetRepository::new());
+        let credit_repo: Arc<dyn CreditRepository> =
+            Arc::new(InMemoryCreditRepository::new().with_budgets(budget_repo.clone()));
+        let hold_repo: Arc<dyn CreditHoldRepository> = Arc::new(
+            InMemoryCreditHoldRepository::with_credit_repo(credit_repo.clone())
+                .with_budgets(budget_repo.clone()),
+        );
+        let service =
+            CreditService::new(credit_repo.clone(), hold_repo).with_budgets(budget_repo.clone());
+        let user_id = Uuid::new_v4();
+
+        let tx = CreditTransactionEntity::new_privacy_deposit(user_id, 1000, "SOL", Uuid::new_v4());
+        credit_repo
+            .add_credit(user_id, 1000, "SOL", tx)
+            .await
+            .unwrap();
+        let now = Utc::now();
+        let budget = budget_repo
+            .upsert(CreditBudgetEntity {
+                id: Uuid::new_v4(),
+                scope: CreditBudgetScope::User,
+                user_id: Some(user_id),
+                org_id: None,
+                api_key_id: None,
+                currency: "SOL".into(),
+                period: CreditBudgetPeriod::Daily,
+                soft_limit: Some(200),
+                hard_limit: Some(300),
+                created_by: None,
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+        let key = BudgetUsageKey {
+            budget_id: budget.id,
+            period_start: CreditBudgetPeriod::Daily.start(now),
+        };
+
+        let first = service
+            .spend(
+                user_id,
+                150,
+                "SOL",
+                "s1".into(),
+                "job",
+                Uuid::new_v4(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(first.budget_alerts.is_empty());
+
+        // Crossing the soft limit alerts once
+        let hold = service
+            .hold(user_id, 100, "SOL", "h1".into(), None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(hold.budget_alerts.len(), 1);
+        assert_eq!(hold.budget_alerts[0].used_lamports, 250);
+
+        // The hard cap rejects without touching the balance
+        let err = service
+            .spend(
+                user_id,
+                100,
+                "SOL",
+                "s2".into(),
+                "job",
+                Uuid::new_v4(),
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::BudgetExceeded(_)));
+        assert_eq!(budget_repo.get_usage(key).await.unwrap(), 250);
+        assert_eq!(
+            service.get_balance_lamports(user_id, "SOL").await.unwrap(),
+            850
+        );
+
+        // Capturing part of the hold gives the rest back
+        service
+            .capture_partial(hold.hold_id, Some(40), None, true)
+            .await
+            .unwrap();
+        assert_eq!(budget_repo.get_usage(key).await.unwrap(), 190);
+        service
+            .spend(
+                user_id,
+                100,
+                "SOL",
+                "s2".into(),
+                "job",
+                Uuid::new_v4(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(budget_repo.get_usage(key).await.unwrap(), 290);
+    }
+
+    #[tokio::test]
+    async fn test_grant_balance_breakdown_and_revoke() {
+        let credit_repo: Arc<dyn CreditRepository> = Arc::new(InMemoryCreditRepository::new());
+        let hold_repo: Arc<dyn CreditHoldRepository> =
+            Arc::new(InMemoryCreditHoldRepository::new());
+        let service = CreditService::new(credit_repo.clone(), hold_repo);
+        let user_id = Uuid::new_v4();
+        let admin_id = Uuid::new_v4();
+
+        service
+            .adjust(admin_id, user_id, 1000, "SOL", "top-up", None, None)
+            .await
+            .unwrap();
+        let result = service
+            .grant(
+                Some(admin_id),
+                user_id,
+                300,
+                "SOL",
+                CreditGrantSource::SignupBonus,
+                0,
+                Some(Utc::now() + Duration::days(7)),
+                Some("welcome"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.new_balance_lamports, 1300);
+
+        let balance = service.get_balance(user_id, "SOL").await.unwrap();
+        assert_eq!(balance.paid_lamports, 1000);
+        assert_eq!(balance.promotional_lamports, 300);
+        assert_eq!(balance.buckets.len(), 2);
+        assert_eq!(balance.buckets[0].source, "signup_bonus");
+        assert_eq!(balance.buckets[0].grant_id, Some(result.grant.id));
+        assert_eq!(balance.buckets[1].source, CreditBucket::PAID);
+
+        // Expiry in the past and non-positive amounts are rejected
+        assert!(service
+            .grant(
+                None,
+                user_id,
+                100,
+                "SOL",
+                CreditGrantSource::Referral,
+                0,
+                Some(Utc::now() - Duration::days(1)),
+                None,
+            )
+            .await
+            .is_err());
+        assert!(service
+            .grant(
+                None,
+                user_id,
+                0,
+                "SOL",
+                CreditGrantSource::Referral,
+                0,
+                None,
+                None
+            )
+            .await
+            .is_err());
+
+        // Only the grant's owner can have it revoked
+        assert!(matches!(
+            service.revoke_grant(Uuid::new_v4(), result.grant.id).await,
+            Err(AppError::NotFound(_))
+        ));
+        let revoked = service
+            .revoke_grant(user_id, result.grant.id)
+            .await
+            .unwrap();
+        assert_eq!(revoked.remaining, 0);
+        let balance = service.get_balance(user_id, "SOL").await.unwrap();
+        assert_eq!(balance.balance_lamports, 1000);
+        assert_eq!(balance.promotional_lamports, 0);
     }
 }
@@ -0,0 +1,657 @@
+//! Credit statements and deposit invoices
+//!
+//! A statement covers one balance (a user's own or an organization's) in one
+//! currency over `[from, to)`. It shows the opening balance, deposits, spends
+//! grouped by `reference_type`, refunds, the other movements and the closing
+//! balance, then every transaction with the running balance. Deposits on a
+//! statement are invoiced with sequential numbers that never change
+//! afterwards (see [`crate::repositories::CreditInvoiceRepository`]).
+//!
+//! Statements render as CSV and as a standalone HTML page that prints
+//! cleanly to PDF from a browser.
+
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use crate::errors::AppError;
+use crate::repositories::{
+    CreditInvoiceEntity, CreditInvoiceRepository, CreditOwnerType, CreditRepository,
+    CreditTransactionEntity, CreditTxType, REFUND_REFERENCE_TYPE,
+};
+use crate::services::CreditAccount;
+
+/// Most transactions a single statement may contain
+pub const MAX_STATEMENT_TRANSACTIONS: u32 = 5_000;
+
+/// Longest period a statement may cover
+pub const MAX_STATEMENT_DAYS: i64 = 366;
+
+/// Group for spends without a `reference_type`
+const UNSPECIFIED_REFERENCE_TYPE: &str = "unspecified";
+
+/// How a transaction is reported on a statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementCategory {
+    Deposit,
+    /// A deposit taken back, e.g. a card refund or dispute
+    DepositReversal,
+    Spend,
+    /// An adjustment marked as a refund
+    Refund,
+    Grant,
+    Expiration,
+    Adjustment,
+    TransferIn,
+    TransferOut,
+}
+
+impl StatementCategory {
+    pub fn of(tx: &CreditTransactionEntity) -> Self {
+        match tx.tx_type {
+            CreditTxType::Deposit if tx.amount < 0 => Self::DepositReversal,
+            CreditTxType::Deposit => Self::Deposit,
+            CreditTxType::Spend => Self::Spend,
+            CreditTxType::Adjustment
+                if tx.reference_type.as_deref() == Some(REFUND_REFERENCE_TYPE) =>
+            {
+                Self::Refund
+            }
+            CreditTxType::Adjustment => Self::Adjustment,
+            CreditTxType::Grant => Self::Grant,
+            CreditTxType::Expiration => Self::Expiration,
+            CreditTxType::Transfer if tx.amount < 0 => Self::TransferOut,
+            CreditTxType::Transfer => Self::TransferIn,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deposit => "deposit",
+            Self::DepositReversal => "deposit_reversal",
+            Self::Spend => "spend",
+            Self::Refund => "refund",
+            Self::Grant => "grant",
+            Self::Expiration => "expiration",
+            Self::Adjustment => "adjustment",
+            Self::TransferIn => "transfer_in",
+            Self::TransferOut => "transfer_out",
+        }
+    }
+}
+
+/// Spends sharing a `reference_type`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementSpendGroup {
+    pub reference_type: String,
+    pub count: u64,
+    /// Total spent (positive)
+    pub amount: i64,
+}
+
+/// One transaction on a statement
+#[derive(Debug, Clone)]
+pub struct StatementLine {
+    pub transaction: CreditTransactionEntity,
+    pub category: StatementCategory,
+    /// Set for deposits
+    pub invoice_number: Option<String>,
+    /// Balance after this transaction
+    pub balance_after: i64,
+}
+
+/// A balance's statement for a period
+///
+/// Totals are positive in the direction their name implies, except
+/// `adjustments`, which is the signed net of manual adjustments. The closing
+/// balance is the opening balance plus every transaction in the period.
+#[derive(Debug, Clone)]
+pub struct CreditStatement {
+    pub account: CreditAccount,
+    /// Name shown on rendered statements (e.g. the user's or org's name)
+    pub holder_name: Option<String>,
+    pub currency: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub opening_balance: i64,
+    pub deposits: i64,
+    pub deposit_reversals: i64,
+    pub spends: Vec<StatementSpendGroup>,
+    pub total_spent: i64,
+    pub refunds: i64,
+    pub grants: i64,
+    pub expirations: i64,
+    pub adjustments: i64,
+    pub transfers_in: i64,
+    pub transfers_out: i64,
+    pub closing_balance: i64,
+    /// Invoices for the period's deposits, by number
+    pub invoices: Vec<CreditInvoiceEntity>,
+    /// Transactions, oldest first
+    pub lines: Vec<StatementLine>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl CreditStatement {
+    /// Build a statement from the period's transactions (oldest first)
+    pub fn build(
+        account: CreditAccount,
+        currency: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        opening_balance: i64,
+        transactions: Vec<CreditTransactionEntity>,
+        invoices: Vec<CreditInvoiceEntity>,
+    ) -> Self {
+        let mut statement = Self {
+            account,
+            holder_name: None,
+            currency: currency.to_string(),
+            from,
+            to,
+            opening_balance,
+            deposits: 0,
+            deposit_reversals: 0,
+            spends: Vec::new(),
+            total_spent: 0,
+            refunds: 0,
+            grants: 0,
+            expirations: 0,
+            adjustments: 0,
+            transfers_in: 0,
+            transfers_out: 0,
+            closing_balance: opening_balance,
+            invoices,
+            lines: Vec::with_capacity(transactions.len()),
+            generated_at: Utc::now(),
+        };
+
+        let invoice_numbers: HashMap<_, _> = statement
+            .invoices
+            .iter()
+            .map(|i| (i.transaction_id, i.invoice_number()))
+            .collect();
+        let mut spends: BTreeMap<String, StatementSpendGroup> = BTreeMap::new();
+
+        for tx in transactions {
+            let category = StatementCategory::of(&tx);
+            let amount = tx.amount;
+            match category {
+                StatementCategory::Deposit => statement.deposits += amount,
+                StatementCategory::DepositReversal => statement.deposit_reversals += -amount,
+                StatementCategory::Spend => {
+                    let reference_type = tx
+                        .reference_type
+                        .clone()
+                        .unwrap_or_else(|| UNSPECIFIED_REFERENCE_TYPE.to_string());
+                    let group =
+                        spends
+                            .entry(reference_type.clone())
+                            .or_insert(StatementSpendGroup {
+                                reference_type,
+                                count: 0,
+                                amount: 0,
+                            });
+                    group.count += 1;
+                    group.amount += -amount;
+                    statement.total_spent += -amount;
+                }
+                StatementCategory::Refund => statement.refunds += amount,
+                StatementCategory::Grant => statement.grants += amount,
+                StatementCategory::Expiration => statement.expirations += -amount,
+                StatementCategory::Adjustment => statement.adjustments += amount,
+                StatementCategory::TransferIn => statement.transfers_in += amount,
+                StatementCategory::TransferOut => statement.transfers_out += -amount,
+            }
+            statement.closing_balance += amount;
+            statement.lines.push(StatementLine {
+                invoice_number: invoice_numbers.get(&tx.id).cloned(),
+                balance_after: statement.closing_balance,
+                category,
+                transaction: tx,
+            });
+        }
+
+        statement.spends = spends.into_values().collect();
+        statement
+    }
+
+    /// Summary rows shared by every rendering: label and amount
+    pub fn summary(&self) -> Vec<(String, i64)> {
+        let mut rows = vec![
+            ("Opening balance".to_string(), self.opening_balance),
+            ("Deposits".to_string(), self.deposits),
+        ];
+        if self.deposit_reversals != 0 {
+            rows.push(("Deposit reversals".to_string(), -self.deposit_reversals));
+        }
+        for group in &self.spends {
+            rows.push((
+                format!("Spent: {} ({})", group.reference_type, group.count),
+                -group.amount,
+            ));
+        }
+        rows.push(("Total spent".to_string(), -self.total_spent));
+        rows.push(("Refunds".to_string(), self.refunds));
+        for (label, amount) in [
+            ("Promotional grants", self.grants),
+            ("Expired grants", -self.expirations),
+            ("Adjustments", self.adjustments),
+            ("Transfers in", self.transfers_in),
+            ("Transfers out", -self.transfers_out),
+        ] {
+            if amount != 0 {
+                rows.push((label.to_string(), amount));
+            }
+        }
+        rows.push(("Closing balance".to_string(), self.closing_balance));
+        rows
+    }
+
+    /// Human-readable period, e.g. `2026-09-01 00:00 UTC to 2026-10-01 00:00 UTC`
+    pub fn period_label(&self) -> String {
+        format!(
+            "{} to {}",
+            self.from.format("%Y-%m-%d %H:%M UTC"),
+            self.to.format("%Y-%m-%d %H:%M UTC")
+        )
+    }
+
+    /// Download file name without extension
+    pub fn file_stem(&self) -> String {
+        format!(
+            "statement-{}-{}-{}-{}",
+            self.account.owner_type(),
+            self.currency.to_lowercase(),
+            self.from.format("%Y%m%d"),
+            self.to.format("%Y%m%d")
+        )
+    }
+
+    /// Render as CSV: a summary block, then one row per transaction
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "Statement,{},{},{},{},{}",
+            self.account.owner_type(),
+            self.account.id(),
+            csv_text(&self.currency),
+            self.from.to_rfc3339(),
+            self.to.to_rfc3339()
+        );
+        out.push('\n');
+        out.push_str("Summary,Amount (lamports)\n");
+        for (label, amount) in self.summary() {
+            let _ = writeln!(out, "{},{}", csv_text(&label), amount);
+        }
+        out.push('\n');
+        out.push_str(
+            "Date,Transaction ID,Category,Reference type,Reference ID,Invoice,Amount (lamports),Balance (lamports)\n",
+        );
+        for line in &self.lines {
+            let tx = &line.transaction;
+            let _ = writeln!(
+                out,
+                "{},{},{},{},{},{},{},{}",
+                tx.created_at.to_rfc3339(),
+                tx.id,
+                line.category.as_str(),
+                csv_text(tx.reference_type.as_deref().unwrap_or("")),
+                tx.reference_id.map(|id| id.to_string()).unwrap_or_default(),
+                line.invoice_number.as_deref().unwrap_or(""),
+                tx.amount,
+                line.balance_after
+            );
+        }
+        out
+    }
+
+    /// Render as a standalone HTML page
+    pub fn to_html(&self) -> String {
+        let title = format!("{} credit statement", escape_html(&self.currency));
+        let holder = self
+            .holder_name
+            .as_deref()
+            .map(escape_html)
+            .unwrap_or_else(|| self.account.id().to_string());
+
+        let mut summary = String::new();
+        for (label, amount) in self.summary() {
+            let strong = label.ends_with("balance");
+            let _ = writeln!(
+                summary,
+                "<tr{}><td>{}</td><td class=\"num\">{}</td></tr>",
+                if strong { " class=\"total\"" } else { "" },
+                escape_html(&label),
+                amount
+            );
+        }
+
+        let mut invoices = String::new();
+        for invoice in &self.invoices {
+            let _ = writeln!(
+                invoices,
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td></tr>",
+                invoice.invoice_number(),
+                invoice.deposited_at.format("%Y-%m-%d %H:%M"),
+                invoice.amount
+            );
+        }
+        let invoices = if invoices.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<h2>Deposit invoices</h2>\n<table>\n<tr><th>Invoice</th><th>Date (UTC)</th><th class=\"num\">Amount</th></tr>\n{invoices}</table>\n"
+            )
+        };
+
+        let mut lines = String::new();
+        for line in &self.lines {
+            let tx = &line.transaction;
+            let _ = writeln!(
+                lines,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+                tx.created_at.format("%Y-%m-%d %H:%M"),
+                line.category.as_str(),
+                escape_html(tx.reference_type.as_deref().unwrap_or("")),
+                line.invoice_number.as_deref().unwrap_or(""),
+                tx.amount,
+                line.balance_after
+            );
+        }
+
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: Arial, sans-serif; max-width: 800px; margin: 0 auto; padding: 20px; color: #333; }}
+table {{ width: 100%; border-collapse: collapse; margin-bottom: 24px; }}
+th, td {{ text-align: left; padding: 6px 8px; border-bottom: 1px solid #E5E7EB; font-size: 14px; }}
+.num {{ text-align: right; font-variant-numeric: tabular-nums; }}
+.total td {{ font-weight: bold; }}
+.meta {{ color: #666; font-size: 14px; }}
+@media print {{ body {{ padding: 0; }} }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p class="meta">{holder} ({owner_type} {owner_id})<br>{period}<br>Amounts in lamports</p>
+<h2>Summary</h2>
+<table>
+{summary}</table>
+{invoices}<h2>Transactions</h2>
+<table>
+<tr><th>Date (UTC)</th><th>Category</th><th>Reference</th><th>Invoice</th><th class="num">Amount</th><th class="num">Balance</th></tr>
+{lines}</table>
+<p class="meta">Generated {generated}</p>
+</body>
+</html>"#,
+            owner_type = self.account.owner_type(),
+            owner_id = self.account.id(),
+            period = escape_html(&self.period_label()),
+            generated = self.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        )
+    }
+
+    /// Render the summary as plain text (for email)
+    pub fn to_text(&self) -> String {
+        let mut out = format!(
+            "{} credit statement\n{}\nAmounts in lamports\n\n",
+            self.currency,
+            self.period_label()
+        );
+        for (label, amount) in self.summary() {
+            let _ = writeln!(out, "{}: {}", label, amount);
+        }
+        if !self.invoices.is_empty() {
+            out.push_str("\nDeposit invoices\n");
+            for invoice in &self.invoices {
+                let _ = writeln!(
+                    out,
+                    "{} ({}): {}",
+                    invoice.invoice_number(),
+                    invoice.deposited_at.format("%Y-%m-%d"),
+                    invoice.amount
+                );
+            }
+        }
+        out
+    }
+}
+
+/// Escape HTML special characters
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#x27;")
+}
+
+/// Quote a free-text CSV field, neutralising spreadsheet formulas
+fn csv_text(s: &str) -> String {
+    let s = if s.starts_with(['=', '+', '-', '@']) {
+        format!("'{s}")
+    } else {
+        s.to_string()
+    };
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s
+    }
+}
+
+/// Generates statements and issues invoices for their deposits
+pub struct CreditStatementService {
+    credit_repo: Arc<dyn CreditRepository>,
+    invoice_repo: Arc<dyn CreditInvoiceRepository>,
+}
+
+impl CreditStatementService {
+    pub fn new(
+        credit_repo: Arc<dyn CreditRepository>,
+        invoice_repo: Arc<dyn CreditInvoiceRepository>,
+    ) -> Self {
+        Self {
+            credit_repo,
+            invoice_repo,
+        }
+    }
+
+    /// Statement for `account` in `currency` over `[from, to)`
+    pub async fn generate(
+        &self,
+        account: CreditAccount,
+        currency: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<CreditStatement, AppError> {
+        if from >= to {
+            return Err(AppError::Validation(
+                "Statement period must end after it starts".into(),
+            ));
+        }
+        if to - from > chrono::Duration::days(MAX_STATEMENT_DAYS) {
+            return Err(AppError::Validation(format!(
+                "Statement period cannot exceed {} days",
+                MAX_STATEMENT_DAYS
+            )));
+        }
+
+        let owner_type = match account {
+            CreditAccount::User(_) => CreditOwnerType::User,
+            CreditAccount::Org(_) => CreditOwnerType::Org,
+        };
+        let currency = currency.to_uppercase();
+        let opening_balance = self
+            .credit_repo
+            .sum_transactions_before(owner_type, account.id(), &currency, from)
+            .await?;
+        let transactions = self
+            .credit_repo
+            .get_transactions_between(
+                owner_type,
+                account.id(),
+                &currency,
+                from,
+                to,
+                MAX_STATEMENT_TRANSACTIONS + 1,
+            )
+            .await?;
+        if transactions.len() > MAX_STATEMENT_TRANSACTIONS as usize {
+            return Err(AppError::Validation(format!(
+                "Statement period has more than {} transactions; choose a shorter period",
+                MAX_STATEMENT_TRANSACTIONS
+            )));
+        }
+
+        let deposits: Vec<_> = transactions
+            .iter()
+            .filter(|tx| StatementCategory::of(tx) == StatementCategory::Deposit)
+            .cloned()
+            .collect();
+        let invoices = self.invoice_repo.issue(&deposits).await?;
+
+        Ok(CreditStatement::build(
+            account,
+            &currency,
+            from,
+            to,
+            opening_balance,
+            transactions,
+            invoices,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{InMemoryCreditInvoiceRepository, InMemoryCreditRepository};
+    use chrono::{Duration, TimeZone};
+    use uuid::Uuid;
+
+    async fn record(
+        repo: &InMemoryCreditRepository,
+        mut tx: CreditTransactionEntity,
+        at: DateTime<Utc>,
+    ) {
+        tx.created_at = at;
+        let amount = tx.amount;
+        if amount >= 0 {
+            repo.add_credit(tx.user_id, amount, "SOL", tx)
+                .await
+                .unwrap();
+        } else {
+            repo.deduct_credit(tx.user_id, -amount, "SOL", tx)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_statement_totals_invoices_and_renderings() {
+        let credit_repo = Arc::new(InMemoryCreditRepository::new());
+        let service = CreditStatementService::new(
+            credit_repo.clone(),
+            Arc::new(InMemoryCreditInvoiceRepository::new()),
+        );
+        let user_id = Uuid::new_v4();
+        let from = Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+
+        let deposit = |amount| {
+            CreditTransactionEntity::new_privacy_deposit(user_id, amount, "SOL", Uuid::new_v4())
+        };
+        let spend = |amount, reference_type: &str| {
+            CreditTransactionEntity::new_spend_with_reference(
+                user_id,
+                amount,
+                "SOL",
+                Uuid::new_v4().to_string(),
+                reference_type,
+                Uuid::new_v4(),
+                None,
+            )
+        };
+        record(&credit_repo, deposit(1_000), from - Duration::days(3)).await;
+        record(&credit_repo, deposit(500), from + Duration::days(1)).await;
+        record(&credit_repo, spend(200, "order"), from + Duration::days(2)).await;
+        record(&credit_repo, spend(100, "order"), from + Duration::days(3)).await;
+        record(&credit_repo, spend(50, "=cmd"), from + Duration::days(4)).await;
+        record(
+            &credit_repo,
+            CreditTransactionEntity::new_refund_adjustment(
+                user_id,
+                75,
+                "SOL",
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                "duplicate charge",
+            ),
+            from + Duration::days(5),
+        )
+        .await;
+        record(&credit_repo, deposit(300), to).await;
+
+        let account = CreditAccount::User(user_id);
+        let statement = service.generate(account, "sol", from, to).await.unwrap();
+        assert_eq!(statement.opening_balance, 1_000);
+        assert_eq!(statement.deposits, 500);
+        assert_eq!(statement.total_spent, 350);
+        assert_eq!(
+            statement.spends,
+            vec![
+                StatementSpendGroup {
+                    reference_type: "=cmd".into(),
+                    count: 1,
+                    amount: 50,
+                },
+                StatementSpendGroup {
+                    reference_type: "order".into(),
+                    count: 2,
+                    amount: 300,
+                },
+            ]
+        );
+        assert_eq!(statement.refunds, 75);
+        assert_eq!(statement.closing_balance, 1_225);
+        assert_eq!(statement.lines.len(), 5);
+        assert_eq!(statement.lines[4].balance_after, 1_225);
+        assert_eq!(statement.invoices.len(), 1);
+        assert_eq!(
+            statement.lines[0].invoice_number.as_deref(),
+            Some("INV-000001")
+        );
+
+        // Regenerating keeps the invoice number
+        let again = service.generate(account, "SOL", from, to).await.unwrap();
+        assert_eq!(again.invoices, statement.invoices);
+
+        let csv = statement.to_csv();
+        assert!(csv.contains("Opening balance,1000\n"));
+        assert!(csv.contains("'=cmd"));
+        assert!(csv.contains("Closing balance,1225\n"));
+        let html = statement.to_html();
+        assert!(html.contains("INV-000001"));
+        assert!(html.contains("<td>Closing balance</td><td class=\"num\">1225</td>"));
+        assert!(statement.to_text().contains("Total spent: -350"));
+        assert_eq!(
+            statement.file_stem(),
+            "statement-user-sol-20260901-20261001"
+        );
+
+        assert!(service.generate(account, "SOL", to, from).await.is_err());
+        assert!(service
+            .generate(account, "SOL", from, from + Duration::days(400))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,573 @@
+//! Credit transfers between users and organizations
+//!
+//! A transfer moves paid credit from the sender's balance (or, for a member
+//! with `credits:manage`, an organization's) to another user's or
+//! organization's balance. It posts two `transfer` transactions sharing the
+//! transfer ID: a debit leg carrying the sender's idempotency key and a
+//! credit leg. With Postgres both legs are written in one DB transaction by
+//! [`crate::repositories::TransactionalOps::transfer_credits_atomic`].
+//!
+//! Runtime settings:
+//! - `credit_transfer_daily_limit_lamports`: Credits a user may send per UTC
+//!   day, per currency (default: 10 SOL, 0 = no limit)
+//! - `credit_transfer_step_up_lamports`: Larger transfers need recent strong
+//!   authentication (default: 1 SOL)
+
+use chrono::{DateTime, TimeZone, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{CreditRepository, CreditTransactionEntity, CreditTxType};
+use crate::services::SettingsService;
+
+/// Setting: credits a user may transfer per UTC day (0 = no limit)
+pub const TRANSFER_DAILY_LIMIT_SETTING: &str = "credit_transfer_daily_limit_lamports";
+
+/// Setting: transfers above this amount require step-up authentication
+pub const TRANSFER_STEP_UP_SETTING: &str = "credit_transfer_step_up_lamports";
+
+/// Default daily transfer limit (10 SOL)
+const DEFAULT_DAILY_LIMIT_LAMPORTS: u64 = 10_000_000_000;
+
+/// Default step-up threshold (1 SOL)
+const DEFAULT_STEP_UP_LAMPORTS: u64 = 1_000_000_000;
+
+/// Maximum length of the note attached to a transfer
+pub const MAX_TRANSFER_NOTE_LEN: usize = 280;
+
+/// Balance credits are transferred from or to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreditAccount {
+    User(Uuid),
+    Org(Uuid),
+}
+
+impl CreditAccount {
+    pub fn owner_type(&self) -> &'static str {
+        match self {
+            Self::User(_) => "user",
+            Self::Org(_) => "org",
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        match self {
+            Self::User(id) | Self::Org(id) => *id,
+        }
+    }
+
+    fn parse(owner_type: &str, id: Uuid) -> Option<Self> {
+        match owner_type {
+            "user" => Some(Self::User(id)),
+            "org" => Some(Self::Org(id)),
+            _ => None,
+        }
+    }
+
+    /// Point a transfer leg at this balance (`user_id` is the sender for
+    /// organization balances)
+    fn apply_to(&self, tx: CreditTransactionEntity) -> CreditTransactionEntity {
+        match self {
+            Self::User(_) => tx,
+            Self::Org(org_id) => tx.for_org(*org_id),
+        }
+    }
+}
+
+/// A transfer request from an authenticated sender
+#[derive(Debug, Clone)]
+pub struct TransferRequest {
+    /// User making the transfer
+    pub sender_id: Uuid,
+    pub from: CreditAccount,
+    pub to: CreditAccount,
+    /// Amount in lamports (must be positive)
+    pub amount: i64,
+    pub currency: String,
+    pub idempotency_key: String,
+    pub note: Option<String>,
+}
+
+/// A completed (or prepared) transfer
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditTransfer {
+    pub id: Uuid,
+    pub sender_id: Uuid,
+    pub from: CreditAccount,
+    pub to: CreditAccount,
+    pub amount: i64,
+    pub currency: String,
+    pub note: Option<String>,
+    /// The sender's (debit) leg
+    pub transaction_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CreditTransfer {
+    /// Rebuild a transfer from its debit leg
+    fn from_debit_leg(tx: &CreditTransactionEntity) -> Option<Self> {
+        if tx.tx_type != CreditTxType::Transfer || tx.amount >= 0 {
+            return None;
+        }
+        let metadata = tx.metadata.as_ref()?;
+        let to = CreditAccount::parse(
+            metadata.get("to_type")?.as_str()?,
+            metadata.get("to_id")?.as_str()?.parse().ok()?,
+        )?;
+        let from = match tx.org_id {
+            Some(org_id) => CreditAccount::Org(org_id),
+            None => CreditAccount::User(tx.user_id),
+        };
+        Some(Self {
+            id: tx.reference_id?,
+            sender_id: tx.user_id,
+            from,
+            to,
+            amount: tx.amount.abs(),
+            currency: tx.currency.clone(),
+            note: metadata
+                .get("note")
+                .and_then(|n| n.as_str())
+                .map(String::from),
+            transaction_id: tx.id,
+            created_at: tx.created_at,
+        })
+    }
+}
+
+/// A checked transfer ready to be written
+#[derive(Debug, Clone)]
+pub struct PreparedTransfer {
+    pub transfer: CreditTransfer,
+    pub debit: CreditTransactionEntity,
+    pub credit: CreditTransactionEntity,
+    /// The amount is above the step-up threshold
+    pub step_up_required: bool,
+    /// Sender's daily transfer limit, re-checked when the transfer is
+    /// written (`None` when unlimited)
+    pub daily_limit: Option<i64>,
+    /// Start of the day the daily limit covers
+    pub day_start: DateTime<Utc>,
+}
+
+/// Checks transfer requests and builds their ledger legs
+pub struct CreditTransferService {
+    credit_repo: Arc<dyn CreditRepository>,
+    settings: Arc<SettingsService>,
+}
+
+impl CreditTransferService {
+    pub fn new(credit_repo: Arc<dyn CreditRepository>, settings: Arc<SettingsService>) -> Self {
+        Self {
+            credit_repo,
+            settings,
+        }
+    }
+
+    /// The transfer a sender already made with `idempotency_key`, if any
+    pub async fn find_existing(
+        &self,
+        sender_id: Uuid,
+        idempotency_key: &str,
+    ) -> Result<Option<CreditTransfer>, AppError> {
+        match self
+            .credit_repo
+            .find_transaction_by_idempotency_key(sender_id, idempotency_key)
+            .await?
+        {
+            Some(tx) => CreditTransfer::from_debit_leg(&tx)
+                .map(Some)
+                .ok_or_else(|| {
+                    AppError::Validation(
+                        "Idempotency key was already used for another operation".into(),
+                    )
+                }),
+            None => Ok(None),
+        }
+    }
+
+    /// Check a transfer against the sender's balance and daily limit and
+    /// build its two legs
+    pub async fn prepare(&self, request: TransferRequest) -> Result<PreparedTransfer, AppError> {
+        if request.amount <= 0 {
+            return Err(AppError::Validation("Amount must be positive".into()));
+        }
+        if request.from == request.to {
+            return Err(AppError::Validation(
+                "Cannot transfer credits to the same balance".into(),
+            ));
+        }
+        let note = request
+            .note
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(String::from);
+        if note
+            .as_ref()
+            .is_some_and(|n| n.chars().count() > MAX_TRANSFER_NOTE_LEN)
+        {
+            return Err(AppError::Validation(format!(
+                "Note must be {} characters or less",
+                MAX_TRANSFER_NOTE_LEN
+            )));
+        }
+
+        let transferable = self.transferable(request.from, &request.currency).await?;
+        if transferable < request.amount {
+            return Err(AppError::Validation(format!(
+                "Insufficient transferable credit: available {}, need {} (promotional credit cannot be transferred)",
+                transferable, request.amount
+            )));
+        }
+
+        let daily_limit = match self
+            .settings
+            .get_u64(TRANSFER_DAILY_LIMIT_SETTING)
+            .await?
+            .unwrap_or(DEFAULT_DAILY_LIMIT_LAMPORTS)
+        {
+            0 => None,
+            limit => Some(i64::try_from(limit).unwrap_or(i64::MAX)),
+        };
+        let now = Utc::now();
+        let day_start =
+            Utc.from_utc_datetime(&now.date_naive().and_hms_opt(0, 0, 0).unwrap_or_default());
+        if let Some(limit) = daily_limit {
+            let sent = self
+                .credit_repo
+                .sum_transfers_out(request.sender_id, &request.currency, day_start)
+                .await?;
+            check_daily_limit(sent, limit, request.amount)?;
+        }
+
+        let step_up_threshold = self
+            .settings
+            .get_u64(TRANSFER_STEP_UP_SETTING)
+            .await?
+            .unwrap_or(DEFAULT_STEP_UP_LAMPORTS);
+
+        let transfer_id = Uuid::new_v4();
+        let debit = request
+            .from
+            .apply_to(CreditTransactionEntity::new_transfer_out(
+                request.sender_id,
+                request.amount,
+                &request.currency,
+                request.idempotency_key,
+                transfer_id,
+                Some(serde_json::json!({
+                    "to_type": request.to.owner_type(),
+                    "to_id": request.to.id().to_string(),
+                    "note": note,
+                })),
+            ));
+        let recipient_user = match request.to {
+            CreditAccount::User(user_id) => user_id,
+            CreditAccount::Org(_) => request.sender_id,
+        };
+        let credit = request
+            .to
+            .apply_to(CreditTransactionEntity::new_transfer_in(
+                recipient_user,
+                request.amount,
+                &request.currency,
+                transfer_id,
+                Some(serde_json::json!({
+                    "from_type": request.from.owner_type(),
+                    "from_id": request.from.id().to_string(),
+                    "sender_id": request.sender_id.to_string(),
+                    "note": note,
+                })),
+            ));
+
+        Ok(PreparedTransfer {
+            transfer: CreditTransfer {
+                id: transfer_id,
+                sender_id: request.sender_id,
+                from: request.from,
+                to: request.to,
+                amount: request.amount,
+                currency: request.currency,
+                note,
+                transaction_id: debit.id,
+                created_at: debit.created_at,
+            },
+            debit,
+            credit,
+            step_up_required: request.amount as u64 > step_up_threshold,
+            daily_limit,
+            day_start,
+        })
+    }
+
+    /// Write a prepared transfer through the repository: debit, then credit
+    ///
+    /// Used when no Postgres pool is available (in-memory storage, for
+    /// development and tests). The two writes are not atomic: if the credit
+    /// fails after the debit succeeded, the sender stays debited and the
+    /// error is returned. Returns the sender's new balance.
+    pub async fn apply(&self, prepared: PreparedTransfer) -> Result<i64, AppError> {
+        let PreparedTransfer {
+            transfer,
+            debit,
+            credit,
+            ..
+        } = prepared;
+        let new_balance = self
+            .credit_repo
+            .deduct_credit(
+                transfer.sender_id,
+                transfer.amount,
+                &transfer.currency,
+                debit,
+            )
+            .await?;
+        self.credit_repo
+            .add_credit(credit.user_id, transfer.amount, &transfer.currency, credit)
+            .await?;
+        Ok(new_balance)
+    }
+
+    /// Paid credit available to transfer out of `account`
+    ///
+    /// Promotional grant credit stays with the user it was granted to.
+    async fn transferable(&self, account: CreditAccount, currency: &str) -> Result<i64, AppError> {
+        match account {
+            CreditAccount::User(user_id) => {
+                let balance = self
+                    .credit_repo
+                    .get_or_create_balance(user_id, currency)
+                    .await?;
+                let promotional: i64 = self
+                    .credit_repo
+                    .get_grants(user_id, Some(currency), true)
+                    .await?
+                    .iter()
+                    .filter(|g| g.is_active())
+                    .map(|g| g.remaining)
+                    .sum();
+                Ok(balance.available().min(balance.balance - promotional))
+            }
+            CreditAccount::Org(org_id) => Ok(self
+                .credit_repo
+                .get_or_create_org_balance(org_id, currency)
+                .await?
+                .available()),
+        }
+    }
+}
+
+/// Return Forbidden if sending `amount` on top of `sent` today exceeds `limit`
+pub fn check_daily_limit(sent: i64, limit: i64, amount: i64) -> Result<(), AppError> {
+    if sent.saturating_add(amount) > limit {
+        return Err(AppError::Forbidden(format!(
+            "Daily transfer limit exceeded: sent {}, limit {}, need {}",
+            sent, limit, amount
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        CreditGrantEntity, CreditGrantSource, InMemoryCreditRepository,
+        InMemorySystemSettingsRepository,
+    };
+
+    fn service(repo: &Arc<InMemoryCreditRepository>) -> CreditTransferService {
+        let settings = Arc::new(SettingsService::new(Arc::new(
+            InMemorySystemSettingsRepository::with_defaults(),
+        )));
+        CreditTransferService::new(repo.clone(), settings)
+    }
+
+    async fn fund(repo: &InMemoryCreditRepository, user_id: Uuid, amount: i64) {
+        let tx =
+            CreditTransactionEntity::new_privacy_deposit(user_id, amount, "SOL", Uuid::new_v4());
+        repo.add_credit(user_id, amount, "SOL", tx).await.unwrap();
+    }
+
+    fn request(sender_id: Uuid, to: CreditAccount, amount: i64, key: &str) -> TransferRequest {
+        TransferRequest {
+            sender_id,
+            from: CreditAccount::User(sender_id),
+            to,
+            amount,
+            currency: "SOL".into(),
+            idempotency_key: key.into(),
+            note: Some(" thanks! ".into()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_to_user_and_org() {
+        let repo = Arc::new(InMemoryCreditRepository::new());
+        let service = service(&repo);
+        let sender = Uuid::new_v4();
+        let teammate = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+        fund(&repo, sender, 1_000).await;
+
+        let prepared = service
+            .prepare(request(
+                sender,
+                CreditAccount::User(teammate),
+                300,
+                "gift-1",
+            ))
+            .await
+            .unwrap();
+        assert!(!prepared.step_up_required);
+        assert_eq!(prepared.transfer.note.as_deref(), Some("thanks!"));
+        let transfer = prepared.transfer.clone();
+        assert_eq!(service.apply(prepared).await.unwrap(), 700);
+
+        let prepared = service
+            .prepare(request(sender, CreditAccount::Org(org_id), 200, "move-1"))
+            .await
+            .unwrap();
+        assert_eq!(service.apply(prepared).await.unwrap(), 500);
+
+        assert_eq!(repo.get_balance(teammate, "SOL").await.unwrap(), 300);
+        let org = repo.get_or_create_org_balance(org_id, "SOL").await.unwrap();
+        assert_eq!(org.balance, 200);
+
+        // Retrying with the same key finds the original transfer
+        let existing = service.find_existing(sender, "gift-1").await.unwrap();
+        assert_eq!(existing, Some(transfer));
+        assert_eq!(
+            repo.sum_transfers_out(sender, "SOL", Utc::now() - chrono::Duration::days(1))
+                .await
+                .unwrap(),
+            500
+        );
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_of_other_operation_is_rejected() {
+        let repo = Arc::new(InMemoryCreditRepository::new());
+        let service = service(&repo);
+        let sender = Uuid::new_v4();
+        fund(&repo, sender, 1_000).await;
+        let spend = CreditTransactionEntity::new_spend_with_reference(
+            sender,
+            10,
+            "SOL",
+            "order-1".into(),
+            "order",
+            Uuid::new_v4(),
+            None,
+        );
+        repo.deduct_credit(sender, 10, "SOL", spend).await.unwrap();
+
+        assert!(service.find_existing(sender, "order-1").await.is_err());
+        assert_eq!(service.find_existing(sender, "new").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_promotional_credit_is_not_transferable() {
+        let repo = Arc::new(InMemoryCreditRepository::new());
+        let service = service(&repo);
+        let sender = Uuid::new_v4();
+        fund(&repo, sender, 100).await;
+        let grant = CreditGrantEntity::new(
+            sender,
+            500,
+            "SOL",
+            CreditGrantSource::SignupBonus,
+            0,
+            None,
+            None,
+            None,
+        );
+        repo.create_grant(grant).await.unwrap();
+
+        let err = service
+            .prepare(request(
+                sender,
+                CreditAccount::User(Uuid::new_v4()),
+                101,
+                "k",
+            ))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(service
+            .prepare(request(
+                sender,
+                CreditAccount::User(Uuid::new_v4()),
+                100,
+                "k"
+            ))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_daily_limit_and_step_up_threshold() {
+        let repo = Arc::new(InMemoryCreditRepository::new());
+        let service = service(&repo);
+        service
+            .settings
+            .set(TRANSFER_DAILY_LIMIT_SETTING, "500", "credit", None)
+            .await
+            .unwrap();
+        service
+            .settings
+            .set(TRANSFER_STEP_UP_SETTING, "250", "credit", None)
+            .await
+            .unwrap();
+        let sender = Uuid::new_v4();
+        let recipient = CreditAccount::User(Uuid::new_v4());
+        fund(&repo, sender, 1_000).await;
+
+        let prepared = service
+            .prepare(request(sender, recipient, 300, "a"))
+            .await
+            .unwrap();
+        assert!(prepared.step_up_required);
+        service.apply(prepared).await.unwrap();
+
+        let err = service
+            .prepare(request(sender, recipient, 201, "b"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Forbidden(_)));
+        let prepared = service
+            .prepare(request(sender, recipient, 200, "b"))
+            .await
+            .unwrap();
+        assert!(!prepared.step_up_required);
+        // The limit travels with the transfer to be re-checked on write
+        assert_eq!(prepared.daily_limit, Some(500));
+        assert!(check_daily_limit(300, 500, 200).is_ok());
+        assert!(check_daily_limit(300, 500, 201).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_transfers_are_rejected() {
+        let repo = Arc::new(InMemoryCreditRepository::new());
+        let service = service(&repo);
+        let sender = Uuid::new_v4();
+        fund(&repo, sender, 1_000).await;
+
+        for (to, amount) in [
+            (CreditAccount::User(sender), 10),
+            (CreditAccount::User(Uuid::new_v4()), 0),
+            (CreditAccount::User(Uuid::new_v4()), 2_000),
+        ] {
+            assert!(service
+                .prepare(request(sender, to, amount, "k"))
+                .await
+                .is_err());
+        }
+
+        let mut long_note = request(sender, CreditAccount::User(Uuid::new_v4()), 10, "k");
+        long_note.note = Some("x".repeat(MAX_TRANSFER_NOTE_LEN + 1));
+        assert!(service.prepare(long_note).await.is_err());
+    }
+}
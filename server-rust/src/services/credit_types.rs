@@ -1,13 +1,19 @@
 //! Credit system types and DTOs
 //!
 //! Contains data transfer objects for the credit service, including:
-//! - Balance representations
+//! - Balance representations (with paid / promotional buckets)
 //! - Transaction history items
-//! - Operation results (spend, hold, adjust)
+//! - Operation results (spend, hold, capture, adjust)
+//! - Ledger reports (trial balance, statements, reconciliation)
 
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
-use crate::repositories::{CreditBalanceEntity, CreditTransactionEntity};
+use crate::repositories::{
+    CreditBalanceDrift, CreditBalanceEntity, CreditBudgetEntity, CreditGrantEntity,
+    CreditHoldEntity, CreditTransactionEntity, LedgerAccount, LedgerAccountTotals,
+    OrgCreditBalanceEntity,
+};
 
 /// Credit balance with formatted display
 #[derive(Debug)]
@@ -18,24 +24,86 @@ pub struct CreditBalance {
     pub held_lamports: i64,
     /// Available balance (total - held)
     pub available_lamports: i64,
+    /// Part of the balance that is paid credit
+    pub paid_lamports: i64,
+    /// Part of the balance that is unspent promotional grants
+    pub promotional_lamports: i64,
+    /// Balance broken down by bucket, in the order credits are spent
+    pub buckets: Vec<CreditBucket>,
     /// Currency (e.g., "SOL")
     pub currency: String,
     /// User-friendly display (e.g., "0.5 SOL")
     pub display: String,
 }
 
+/// Part of a balance by where the credit came from
+#[derive(Debug, Clone)]
+pub struct CreditBucket {
+    /// "paid", or the grant source (e.g., "signup_bonus")
+    pub source: String,
+    /// Grant the credit belongs to (None for paid credit)
+    pub grant_id: Option<Uuid>,
+    pub amount_lamports: i64,
+    /// When unspent grant credit is forfeited
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl CreditBucket {
+    pub const PAID: &'static str = "paid";
+
+    fn paid(amount: i64) -> Self {
+        Self {
+            source: Self::PAID.to_string(),
+            grant_id: None,
+            amount_lamports: amount,
+            expires_at: None,
+        }
+    }
+}
+
 impl CreditBalance {
     pub(crate) fn from_entity(entity: CreditBalanceEntity) -> Self {
-        let available = entity.available();
+        Self::from_parts(entity.balance, entity.held_balance, entity.currency)
+    }
+
+    pub(crate) fn from_org_entity(entity: OrgCreditBalanceEntity) -> Self {
+        Self::from_parts(entity.balance, entity.held_balance, entity.currency)
+    }
+
+    fn from_parts(balance: i64, held: i64, currency: String) -> Self {
+        let available = balance - held;
         let sol_amount = available as f64 / 1_000_000_000.0;
         Self {
-            balance_lamports: entity.balance,
-            held_lamports: entity.held_balance,
+            balance_lamports: balance,
+            held_lamports: held,
             available_lamports: available,
-            currency: entity.currency,
+            paid_lamports: balance,
+            promotional_lamports: 0,
+            buckets: vec![CreditBucket::paid(balance)],
+            currency,
             display: format!("{:.4} SOL", sol_amount),
         }
     }
+
+    /// Split the balance into the user's active grants (in spend order)
+    /// and paid credit
+    pub(crate) fn with_grants(mut self, grants: &[CreditGrantEntity]) -> Self {
+        let mut buckets: Vec<CreditBucket> = grants
+            .iter()
+            .filter(|g| g.is_active())
+            .map(|g| CreditBucket {
+                source: g.source.as_str().to_string(),
+                grant_id: Some(g.id),
+                amount_lamports: g.remaining,
+                expires_at: g.expires_at,
+            })
+            .collect();
+        self.promotional_lamports = buckets.iter().map(|b| b.amount_lamports).sum();
+        self.paid_lamports = self.balance_lamports - self.promotional_lamports;
+        buckets.push(CreditBucket::paid(self.paid_lamports));
+        self.buckets = buckets;
+        self
+    }
 }
 
 /// Credit transaction history item
@@ -47,6 +115,12 @@ pub struct CreditHistoryItem {
     pub tx_type: String,
     pub deposit_session_id: Option<Uuid>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// "user" or "org"
+    pub owner_type: String,
+    /// Organization whose balance moved (org-owned entries)
+    pub org_id: Option<Uuid>,
+    /// Member or admin who made the transaction
+    pub user_id: Uuid,
 }
 
 impl From<CreditTransactionEntity> for CreditHistoryItem {
@@ -58,6 +132,9 @@ impl From<CreditTransactionEntity> for CreditHistoryItem {
             tx_type: entity.tx_type.as_str().to_string(),
             deposit_session_id: entity.deposit_session_id,
             created_at: entity.created_at,
+            owner_type: entity.owner_type.as_str().to_string(),
+            org_id: entity.org_id,
+            user_id: entity.user_id,
         }
     }
 }
@@ -75,12 +152,37 @@ pub struct CreditHistory {
 pub struct SpendResult {
     /// Transaction ID
     pub transaction_id: Uuid,
+    /// User whose credits were spent
+    pub user_id: Uuid,
     /// New balance after spend
     pub new_balance_lamports: i64,
     /// Amount spent
     pub amount_lamports: i64,
     /// S-14: Currency from the captured hold (avoids double-fetch in handler)
     pub currency: String,
+    /// Organization whose balance was spent (org-owned spends)
+    pub org_id: Option<Uuid>,
+    /// Budgets whose soft limit this spend crossed
+    pub budget_alerts: Vec<BudgetAlert>,
+}
+
+/// A spend or hold that took a budget's usage past its soft limit
+#[derive(Debug, Clone)]
+pub struct BudgetAlert {
+    pub budget: CreditBudgetEntity,
+    pub period_start: chrono::DateTime<chrono::Utc>,
+    /// Usage after the spend or hold
+    pub used_lamports: i64,
+}
+
+/// A member spending from an organization's balance
+#[derive(Debug, Clone, Copy)]
+pub struct OrgSpender {
+    pub org_id: Uuid,
+    pub user_id: Uuid,
+    /// Whether the member's role grants `credits:spend`; member settings
+    /// can override this either way
+    pub role_can_spend: bool,
 }
 
 /// Result of a hold operation
@@ -94,6 +196,19 @@ pub struct HoldResult {
     pub amount_lamports: i64,
     /// When the hold expires
     pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// Budgets whose soft limit this hold crossed
+    pub budget_alerts: Vec<BudgetAlert>,
+}
+
+/// Result of a full or partial hold capture
+#[derive(Debug)]
+pub struct CaptureResult {
+    /// The spend recorded for this capture
+    pub spend: SpendResult,
+    /// The hold after the capture
+    pub hold: CreditHoldEntity,
+    /// Whether this repeats an earlier capture with the same key
+    pub replayed: bool,
 }
 
 /// Result of an adjustment operation
@@ -106,3 +221,116 @@ pub struct AdjustResult {
     /// Amount adjusted (positive = credit, negative = debit)
     pub amount_lamports: i64,
 }
+
+/// Result of granting promotional credit
+#[derive(Debug)]
+pub struct GrantResult {
+    pub grant: CreditGrantEntity,
+    /// New balance after the grant
+    pub new_balance_lamports: i64,
+}
+
+/// One account's activity in a ledger report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerAccountLine {
+    pub account: LedgerAccount,
+    /// Balance on the account's normal side at the start of the period
+    pub opening_balance: i64,
+    pub debits: i64,
+    pub credits: i64,
+    /// Balance on the account's normal side at the end of the period
+    pub closing_balance: i64,
+}
+
+/// Trial balance or period statement for one currency
+#[derive(Debug, Clone)]
+pub struct LedgerReport {
+    pub currency: String,
+    pub lines: Vec<LedgerAccountLine>,
+    /// Debits posted in the period, across all accounts
+    pub total_debits: i64,
+    /// Credits posted in the period, across all accounts
+    pub total_credits: i64,
+}
+
+impl LedgerReport {
+    /// Build per-currency reports from totals before the period (`opening`)
+    /// and within it (`period`)
+    pub fn build(opening: &[LedgerAccountTotals], period: &[LedgerAccountTotals]) -> Vec<Self> {
+        let mut lines: BTreeMap<(String, LedgerAccount), LedgerAccountLine> = BTreeMap::new();
+
+        for totals in opening {
+            let entry = lines
+                .entry((totals.currency.clone(), totals.account))
+                .or_insert_with(|| LedgerAccountLine::empty(totals.account));
+            entry.opening_balance += totals.account.normal_balance(totals.debits, totals.credits);
+        }
+        for totals in period {
+            let entry = lines
+                .entry((totals.currency.clone(), totals.account))
+                .or_insert_with(|| LedgerAccountLine::empty(totals.account));
+            entry.debits += totals.debits;
+            entry.credits += totals.credits;
+        }
+
+        let mut reports: Vec<Self> = Vec::new();
+        for ((currency, account), mut line) in lines {
+            line.closing_balance =
+                line.opening_balance + account.normal_balance(line.debits, line.credits);
+            if reports.last().map_or(true, |r| r.currency != currency) {
+                reports.push(Self {
+                    currency,
+                    lines: Vec::new(),
+                    total_debits: 0,
+                    total_credits: 0,
+                });
+            }
+            if let Some(report) = reports.last_mut() {
+                report.total_debits += line.debits;
+                report.total_credits += line.credits;
+                report.lines.push(line);
+            }
+        }
+        reports
+    }
+
+    /// Whether debits equal credits, as they must in a double-entry journal
+    pub fn is_balanced(&self) -> bool {
+        self.total_debits == self.total_credits
+    }
+}
+
+impl LedgerAccountLine {
+    fn empty(account: LedgerAccount) -> Self {
+        Self {
+            account,
+            opening_balance: 0,
+            debits: 0,
+            credits: 0,
+            closing_balance: 0,
+        }
+    }
+}
+
+/// Result of a reconciliation run
+#[derive(Debug, Clone)]
+pub struct ReconciliationReport {
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+    /// Number of stored balances recomputed from the journal
+    pub balances_checked: u64,
+    /// Stored balances that disagree with the journal
+    pub drift: Vec<CreditBalanceDrift>,
+    /// Transactions that were never posted to the journal
+    pub unposted_transactions: u64,
+    /// Currencies whose journal debits and credits differ
+    pub unbalanced_currencies: Vec<String>,
+}
+
+impl ReconciliationReport {
+    /// Whether the run found nothing to investigate
+    pub fn is_clean(&self) -> bool {
+        self.drift.is_empty()
+            && self.unposted_transactions == 0
+            && self.unbalanced_currencies.is_empty()
+    }
+}
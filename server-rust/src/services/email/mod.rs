@@ -21,6 +21,9 @@ pub enum EmailType {
     Invite,
     InstantLink,
     SecurityAlert,
+    EmailChange,
+    EmailChangeNotice,
+    CreditStatement,
 }
 
 /// Email to be sent
@@ -79,6 +82,36 @@ pub struct SecurityAlertEmailData {
     pub action_url: Option<String>,
 }
 
+/// Email template data for the confirmation sent to a new email address
+#[derive(Debug, Clone)]
+pub struct EmailChangeEmailData {
+    pub user_name: Option<String>,
+    pub new_email: String,
+    pub confirm_url: String,
+    pub expires_in_hours: u32,
+}
+
+/// Email template data for the notice sent to the previous email address
+#[derive(Debug, Clone)]
+pub struct EmailChangeNoticeEmailData {
+    pub user_name: Option<String>,
+    pub new_email: String,
+    pub revert_url: String,
+    pub expires_in_days: u32,
+}
+
+/// Email template data for credit statements
+#[derive(Debug, Clone)]
+pub struct CreditStatementEmailData {
+    pub user_name: Option<String>,
+    /// Statement period, e.g. `2026-09-01 00:00 UTC to 2026-10-01 00:00 UTC`
+    pub period: String,
+    /// Rendered statement page (already escaped)
+    pub statement_html: String,
+    /// Plain-text statement summary
+    pub statement_text: String,
+}
+
 /// Trait for email service implementations
 #[async_trait]
 pub trait EmailService: Send + Sync {
@@ -130,4 +163,34 @@ pub trait EmailService: Send + Sync {
         let email = templates::security_alert_email(to, data);
         self.send(email).await
     }
+
+    /// Send email change confirmation to the new address
+    async fn send_email_change(
+        &self,
+        to: &str,
+        data: EmailChangeEmailData,
+    ) -> Result<(), AppError> {
+        let email = templates::email_change_email(to, data);
+        self.send(email).await
+    }
+
+    /// Send email change notice (with revert link) to the previous address
+    async fn send_email_change_notice(
+        &self,
+        to: &str,
+        data: EmailChangeNoticeEmailData,
+    ) -> Result<(), AppError> {
+        let email = templates::email_change_notice_email(to, data);
+        self.send(email).await
+    }
+
+    /// Send a credit statement
+    async fn send_credit_statement(
+        &self,
+        to: &str,
+        data: CreditStatementEmailData,
+    ) -> Result<(), AppError> {
+        let email = templates::credit_statement_email(to, data);
+        self.send(email).await
+    }
 }
@@ -1,8 +1,9 @@
 //! Email HTML/text template generation
 
 use super::{
-    Email, EmailType, InstantLinkEmailData, InviteEmailData, PasswordResetEmailData,
-    SecurityAlertEmailData, VerificationEmailData,
+    CreditStatementEmailData, Email, EmailChangeEmailData, EmailChangeNoticeEmailData, EmailType,
+    InstantLinkEmailData, InviteEmailData, PasswordResetEmailData, SecurityAlertEmailData,
+    VerificationEmailData,
 };
 
 /// Escape HTML special characters to prevent injection attacks.
@@ -205,3 +206,87 @@ pub fn security_alert_email(to: &str, data: SecurityAlertEmailData) -> Email {
         email_type: EmailType::SecurityAlert,
     }
 }
+
+/// Generate email change confirmation email (sent to the new address)
+pub fn email_change_email(to: &str, data: EmailChangeEmailData) -> Email {
+    let name = escape_html(data.user_name.as_deref().unwrap_or("there"));
+    let new_email = escape_html(&data.new_email);
+    Email {
+        to: to.to_string(),
+        subject: "Confirm your new email address".to_string(),
+        html_body: format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: Arial, sans-serif; max-width: 600px; margin: 0 auto; padding: 20px;">
+<h1 style="color: #333;">Confirm your new email</h1>
+<p>Hi {name},</p>
+<p>We received a request to change your account email to <strong>{new_email}</strong>. Click the button below to confirm:</p>
+<p style="text-align: center;">
+<a href="{}" rel="noreferrer noopener" referrerpolicy="no-referrer" style="display: inline-block; background-color: #4F46E5; color: white; padding: 12px 24px; text-decoration: none; border-radius: 6px; font-weight: bold;">Confirm Email</a>
+</p>
+<p>Or copy and paste this link into your browser:</p>
+<p style="word-break: break-all; color: #666;">{}</p>
+<p style="color: #666; font-size: 14px;">This link expires in {} hours.</p>
+<p style="color: #999; font-size: 12px;">If you didn't request this change, you can safely ignore this email.</p>
+</body>
+</html>"#,
+            data.confirm_url, data.confirm_url, data.expires_in_hours
+        ),
+        text_body: format!(
+            "Hi {},\n\nWe received a request to change your account email to {}.\n\nConfirm the change: {}\n\nThis link expires in {} hours.\n\nIf you didn't request this, you can safely ignore this email.",
+            name, new_email, data.confirm_url, data.expires_in_hours
+        ),
+        email_type: EmailType::EmailChange,
+    }
+}
+
+/// Generate email change notice (sent to the previous address)
+pub fn email_change_notice_email(to: &str, data: EmailChangeNoticeEmailData) -> Email {
+    let name = escape_html(data.user_name.as_deref().unwrap_or("there"));
+    let new_email = escape_html(&data.new_email);
+    Email {
+        to: to.to_string(),
+        subject: "Your account email was changed".to_string(),
+        html_body: format!(
+            r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"></head>
+<body style="font-family: Arial, sans-serif; max-width: 600px; margin: 0 auto; padding: 20px;">
+<h1 style="color: #333;">Your email was changed</h1>
+<p>Hi {name},</p>
+<p>The email address on your account was changed to <strong>{new_email}</strong>.</p>
+<p>If this was you, you can safely ignore this email.</p>
+<p style="color: #DC2626;"><strong>If this wasn't you</strong>, click the button below to restore this address and sign out every session:</p>
+<p style="text-align: center;">
+<a href="{}" rel="noreferrer noopener" referrerpolicy="no-referrer" style="display: inline-block; background-color: #DC2626; color: white; padding: 12px 24px; text-decoration: none; border-radius: 6px; font-weight: bold;">Revert Change</a>
+</p>
+<p>Or copy and paste this link into your browser:</p>
+<p style="word-break: break-all; color: #666;">{}</p>
+<p style="color: #666; font-size: 14px;">This link expires in {} days.</p>
+</body>
+</html>"#,
+            data.revert_url, data.revert_url, data.expires_in_days
+        ),
+        text_body: format!(
+            "Hi {},\n\nThe email address on your account was changed to {}.\n\nIf this was you, you can safely ignore this email.\n\nIf this wasn't you, restore this address and sign out every session: {}\n\nThis link expires in {} days.",
+            name, new_email, data.revert_url, data.expires_in_days
+        ),
+        email_type: EmailType::EmailChangeNotice,
+    }
+}
+
+/// Generate credit statement email (the body is the rendered statement)
+pub fn credit_statement_email(to: &str, data: CreditStatementEmailData) -> Email {
+    let name = data.user_name.as_deref().unwrap_or("there");
+    Email {
+        to: to.to_string(),
+        subject: "Your credit statement".to_string(),
+        html_body: data.statement_html,
+        text_body: format!(
+            "Hi {},\n\nHere is your credit statement for {}.\n\n{}",
+            name, data.period, data.statement_text
+        ),
+        email_type: EmailType::CreditStatement,
+    }
+}
@@ -176,3 +176,26 @@ async fn test_log_email_service_security_alert_minimal() {
     // Should not have action button without URL
     assert!(!sent[0].html_body.contains("Secure My Account"));
 }
+
+#[tokio::test]
+async fn test_log_email_service_email_change_notice() {
+    let service = LogEmailService::new();
+    let data = EmailChangeNoticeEmailData {
+        user_name: Some("John".to_string()),
+        new_email: "<new>@example.com".to_string(),
+        revert_url: "https://example.com/revert-email-change?token=abc".to_string(),
+        expires_in_days: 7,
+    };
+
+    service
+        .send_email_change_notice("old@example.com", data)
+        .await
+        .unwrap();
+
+    let sent = service.get_sent_emails().await;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "old@example.com");
+    assert!(sent[0].html_body.contains("revert-email-change?token=abc"));
+    assert!(sent[0].html_body.contains("&lt;new&gt;@example.com"));
+    assert!(sent[0].text_body.contains("7 days"));
+}
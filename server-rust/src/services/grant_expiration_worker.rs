@@ -0,0 +1,107 @@
+//! Background worker for expiring promotional credit grants
+//!
+//! Periodically scans for grants that have passed their expiry and forfeits
+//! their unspent credit. Credit reserved by pending holds is left in place
+//! and forfeited on a later run once the holds settle.
+//!
+//! Default poll interval: 5 minutes
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info};
+
+use crate::repositories::{CreditHoldRepository, CreditRepository};
+use crate::services::CreditService;
+
+/// Default poll interval for grant expiration (5 minutes)
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 300;
+
+/// Configuration for the grant expiration worker
+#[derive(Debug, Clone)]
+pub struct GrantExpirationConfig {
+    /// How often to check for expired grants (seconds)
+    pub poll_interval_secs: u64,
+}
+
+impl Default for GrantExpirationConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+        }
+    }
+}
+
+/// Background worker that expires promotional credit grants
+pub struct GrantExpirationWorker {
+    credit_repo: Arc<dyn CreditRepository>,
+    hold_repo: Arc<dyn CreditHoldRepository>,
+    config: GrantExpirationConfig,
+}
+
+impl GrantExpirationWorker {
+    /// Create a new grant expiration worker
+    pub fn new(
+        credit_repo: Arc<dyn CreditRepository>,
+        hold_repo: Arc<dyn CreditHoldRepository>,
+        config: GrantExpirationConfig,
+    ) -> Self {
+        Self {
+            credit_repo,
+            hold_repo,
+            config,
+        }
+    }
+
+    /// Start the worker as a background task with graceful shutdown support
+    pub fn start(self, cancel_token: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
+
+            info!(
+                poll_interval_secs = self.config.poll_interval_secs,
+                "Grant expiration worker started"
+            );
+
+            loop {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        info!("Grant expiration worker shutting down");
+                        break;
+                    }
+                    _ = tokio::time::sleep(poll_interval) => {
+                        if let Err(e) = self.expire_grants().await {
+                            error!(error = %e, "Failed to expire credit grants");
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Expire grants past their expiry
+    async fn expire_grants(&self) -> Result<(), crate::errors::AppError> {
+        let service = CreditService::new(self.credit_repo.clone(), self.hold_repo.clone());
+        let expired_count = service.expire_grants().await?;
+
+        if expired_count > 0 {
+            info!(count = expired_count, "Expired promotional credit grants");
+        } else {
+            debug!("No grants to expire");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = GrantExpirationConfig::default();
+        assert_eq!(config.poll_interval_secs, 300);
+    }
+}
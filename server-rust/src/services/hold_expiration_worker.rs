@@ -1,7 +1,8 @@
 //! Background worker for expiring stale credit holds
 //!
 //! Periodically scans for holds that have exceeded their TTL and marks them
-//! as expired, releasing the held credits back to available balance.
+//! as expired, releasing the held credits back to available balance and
+//! their uncaptured amount back to the spending budgets they counted against.
 //!
 //! Default poll interval: 60 seconds
 
@@ -11,7 +12,7 @@ use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info};
 
-use crate::repositories::{CreditHoldRepository, CreditRepository};
+use crate::repositories::{CreditBudgetRepository, CreditHoldRepository, CreditRepository};
 use crate::services::CreditService;
 
 /// Default poll interval for hold expiration (60 seconds)
@@ -36,6 +37,7 @@ impl Default for HoldExpirationConfig {
 pub struct HoldExpirationWorker {
     credit_repo: Arc<dyn CreditRepository>,
     hold_repo: Arc<dyn CreditHoldRepository>,
+    budget_repo: Option<Arc<dyn CreditBudgetRepository>>,
     config: HoldExpirationConfig,
 }
 
@@ -49,10 +51,17 @@ impl HoldExpirationWorker {
         Self {
             credit_repo,
             hold_repo,
+            budget_repo: None,
             config,
         }
     }
 
+    /// Return the budget usage of expired holds
+    pub fn with_budgets(mut self, budget_repo: Arc<dyn CreditBudgetRepository>) -> Self {
+        self.budget_repo = Some(budget_repo);
+        self
+    }
+
     /// Start the worker as a background task with graceful shutdown support
     pub fn start(self, cancel_token: CancellationToken) -> JoinHandle<()> {
         tokio::spawn(async move {
@@ -81,7 +90,10 @@ impl HoldExpirationWorker {
 
     /// Expire stale holds
     async fn expire_holds(&self) -> Result<(), crate::errors::AppError> {
-        let service = CreditService::new(self.credit_repo.clone(), self.hold_repo.clone());
+        let mut service = CreditService::new(self.credit_repo.clone(), self.hold_repo.clone());
+        if let Some(budget_repo) = &self.budget_repo {
+            service = service.with_budgets(budget_repo.clone());
+        }
         let expired_count = service.expire_holds().await?;
 
         if expired_count > 0 {
@@ -20,6 +20,32 @@ use crate::models::TokenPair;
 /// RSA key size in bits (2048 is minimum for RS256)
 const RSA_KEY_BITS: usize = 2048;
 
+/// Maximum serialized size of the host-app claims under `ext` (4 KiB)
+pub const MAX_CUSTOM_CLAIMS_BYTES: usize = 4 * 1024;
+
+/// Claim names the host app may not set, even inside the `ext` namespace
+///
+/// Covers the registered JWT claims and every top-level claim issued here, so
+/// consumers that flatten `ext` cannot be tricked into trusting a forged value.
+pub const RESERVED_CLAIMS: &[&str] = &[
+    "sub",
+    "sid",
+    "org_id",
+    "role",
+    "is_system_admin",
+    "email_verified",
+    "service_account",
+    "act",
+    "metadata",
+    "ext",
+    "iat",
+    "exp",
+    "nbf",
+    "iss",
+    "aud",
+    "jti",
+];
+
 /// Access token claims
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AccessTokenClaims {
@@ -39,6 +65,18 @@ pub struct AccessTokenClaims {
     /// Whether user's email address has been verified
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email_verified: Option<bool>,
+    /// Set on service account tokens, which are pinned to `org_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_account: Option<bool>,
+    /// Actor acting on behalf of `sub` (RFC 8693 `act`), set on impersonation tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<ActorClaim>,
+    /// Public metadata keys listed in the `jwt_metadata_claims` setting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Host-app claims returned by `AuthCallback::enrich_claims`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ext: Option<serde_json::Map<String, serde_json::Value>>,
     /// Issued at (Unix timestamp)
     pub iat: i64,
     /// Expiration (Unix timestamp)
@@ -60,6 +98,59 @@ pub struct TokenContext {
     pub is_system_admin: Option<bool>,
     /// Whether user's email address has been verified
     pub email_verified: Option<bool>,
+    /// Whether the subject is a service account
+    pub service_account: Option<bool>,
+    /// Impersonating actor (impersonation sessions only)
+    pub act: Option<ActorClaim>,
+    /// Claims projected from the user's public metadata
+    pub metadata: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Host-app claims (already passed through `sanitize_custom_claims`)
+    pub ext: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Drop reserved claim names and enforce the size cap on host-app claims
+///
+/// Returns `None` when nothing usable is left. Oversized claims are dropped
+/// as a whole rather than truncated, and never fail the sign-in.
+pub fn sanitize_custom_claims(
+    mut claims: serde_json::Map<String, serde_json::Value>,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    claims.retain(|key, _| {
+        let reserved = RESERVED_CLAIMS.contains(&key.as_str());
+        if reserved {
+            tracing::warn!(claim = %key, "Dropping reserved claim returned by claims hook");
+        }
+        !reserved
+    });
+    if claims.is_empty() {
+        return None;
+    }
+
+    let size = serde_json::to_vec(&claims)
+        .map(|bytes| bytes.len())
+        .unwrap_or(usize::MAX);
+    if size > MAX_CUSTOM_CLAIMS_BYTES {
+        tracing::warn!(
+            size,
+            max = MAX_CUSTOM_CLAIMS_BYTES,
+            "Dropping oversized claims returned by claims hook"
+        );
+        return None;
+    }
+    Some(claims)
+}
+
+/// Actor claim for impersonation tokens (RFC 8693 section 4.1)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActorClaim {
+    /// User ID of the impersonator
+    pub sub: Uuid,
+    /// Whether the impersonation may perform writes (read-only when false)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub write: bool,
+    /// Org the impersonation is confined to (org-owner impersonation)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<Uuid>,
 }
 
 /// JWKS (JSON Web Key Set) response
@@ -303,9 +394,25 @@ impl JwtService {
         user_id: Uuid,
         session_id: Uuid,
         context: &TokenContext,
+    ) -> Result<String, AppError> {
+        self.generate_access_token_with_expiry(
+            user_id,
+            session_id,
+            context,
+            self.access_expiry_secs,
+        )
+    }
+
+    /// Generate an access token with a custom lifetime (e.g. impersonation)
+    pub fn generate_access_token_with_expiry(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+        context: &TokenContext,
+        expiry_secs: u64,
     ) -> Result<String, AppError> {
         let now = Utc::now();
-        let exp = now + Duration::seconds(self.access_expiry_secs as i64);
+        let exp = now + Duration::seconds(expiry_secs as i64);
 
         let claims = AccessTokenClaims {
             sub: user_id,
@@ -314,6 +421,10 @@ impl JwtService {
             role: context.role.clone(),
             is_system_admin: context.is_system_admin,
             email_verified: context.email_verified,
+            service_account: context.service_account,
+            act: context.act.clone(),
+            metadata: context.metadata.clone(),
+            ext: context.ext.clone(),
             iat: now.timestamp(),
             exp: exp.timestamp(),
             iss: self.issuer.clone(),
@@ -393,6 +504,11 @@ impl JwtService {
         })
     }
 
+    /// Get the access token expiry duration in seconds
+    pub fn access_expiry_secs(&self) -> u64 {
+        self.access_expiry_secs
+    }
+
     /// Get the refresh token expiry duration in seconds
     pub fn refresh_expiry_secs(&self) -> u64 {
         self.refresh_expiry_secs
@@ -505,4 +621,93 @@ mod tests {
 
         assert_eq!(header.alg, Algorithm::RS256);
     }
+
+    #[test]
+    fn test_impersonation_token_carries_act_claim() {
+        let service = JwtService::new(&test_config());
+        let admin_id = Uuid::new_v4();
+        let context = TokenContext {
+            act: Some(ActorClaim {
+                sub: admin_id,
+                write: false,
+                org_id: None,
+            }),
+            ..Default::default()
+        };
+
+        let token = service
+            .generate_access_token_with_expiry(Uuid::new_v4(), Uuid::new_v4(), &context, 60)
+            .unwrap();
+        let claims = service.validate_access_token(&token).unwrap();
+
+        let act = claims.act.unwrap();
+        assert_eq!(act.sub, admin_id);
+        assert!(!act.write);
+        assert_eq!(claims.exp - claims.iat, 60);
+    }
+
+    #[test]
+    fn test_regular_token_has_no_act_claim() {
+        let service = JwtService::new(&test_config());
+        let token = service
+            .generate_access_token(Uuid::new_v4(), Uuid::new_v4())
+            .unwrap();
+        assert!(service.validate_access_token(&token).unwrap().act.is_none());
+    }
+
+    #[test]
+    fn test_metadata_claim_round_trip() {
+        let service = JwtService::new(&test_config());
+        let mut metadata = serde_json::Map::new();
+        metadata.insert("plan".into(), serde_json::json!("pro"));
+        let context = TokenContext {
+            metadata: Some(metadata),
+            ..Default::default()
+        };
+
+        let token = service
+            .generate_access_token_with_expiry(Uuid::new_v4(), Uuid::new_v4(), &context, 60)
+            .unwrap();
+        let claims = service.validate_access_token(&token).unwrap();
+        assert_eq!(claims.metadata.unwrap()["plan"], "pro");
+    }
+
+    #[test]
+    fn test_ext_claims_round_trip() {
+        let service = JwtService::new(&test_config());
+        let mut ext = serde_json::Map::new();
+        ext.insert("tier".into(), serde_json::json!("gold"));
+        let context = TokenContext {
+            ext: Some(ext),
+            ..Default::default()
+        };
+
+        let token = service
+            .generate_access_token_with_expiry(Uuid::new_v4(), Uuid::new_v4(), &context, 60)
+            .unwrap();
+        let claims = service.validate_access_token(&token).unwrap();
+        assert_eq!(claims.ext.unwrap()["tier"], "gold");
+    }
+
+    #[test]
+    fn test_sanitize_custom_claims() {
+        let claims = serde_json::json!({ "sub": "forged", "exp": 0, "tier": "gold" });
+        let serde_json::Value::Object(claims) = claims else {
+            unreachable!()
+        };
+        let sanitized = sanitize_custom_claims(claims).unwrap();
+        assert_eq!(sanitized.len(), 1);
+        assert_eq!(sanitized["tier"], "gold");
+
+        let mut only_reserved = serde_json::Map::new();
+        only_reserved.insert("aud".into(), serde_json::json!("other"));
+        assert!(sanitize_custom_claims(only_reserved).is_none());
+
+        let mut oversized = serde_json::Map::new();
+        oversized.insert(
+            "blob".into(),
+            serde_json::json!("x".repeat(MAX_CUSTOM_CLAIMS_BYTES)),
+        );
+        assert!(sanitize_custom_claims(oversized).is_none());
+    }
 }
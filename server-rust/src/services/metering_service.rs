@@ -0,0 +1,625 @@
+//! Usage metering: event ingestion and batch billing
+//!
+//! Callers report usage of catalog meters with idempotency keys. The usage
+//! billing worker groups pending events into batches of one meter, payer
+//! (user, or org and member) and calendar month, prices each batch against
+//! the usage already billed that month, and debits it through
+//! [`CreditService`] with the idempotency key `usage_batch:<batch_id>`, so a
+//! batch is never charged twice even if the worker stops mid-run.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{
+    billing_period, CreditHoldRepository, CreditRepository, MembershipRepository, MeterEntity,
+    MeterRepository, MeterUsageTotals, PriceSchedule, UsageEventEntity, USAGE_REFERENCE_TYPE,
+};
+use crate::services::{CreditService, OrgSpender, Permission};
+
+/// Maximum events accepted per ingestion request
+pub const MAX_USAGE_EVENTS_PER_REQUEST: usize = 1000;
+
+/// How far back usage may be reported (days)
+const MAX_USAGE_BACKDATE_DAYS: i64 = 31;
+
+/// Allowed clock skew for `occurred_at` in the future (minutes)
+const MAX_USAGE_FUTURE_SKEW_MINUTES: i64 = 5;
+
+/// Meter, user, org and billing period start shared by a batch's events
+type BatchKey = (Uuid, Uuid, Option<Uuid>, DateTime<Utc>);
+
+/// A usage event as reported by a caller
+#[derive(Debug, Clone)]
+pub struct UsageReport {
+    /// Meter name (e.g. `api_call`)
+    pub meter: String,
+    pub user_id: Uuid,
+    /// Bill the organization's balance instead of the user's
+    pub org_id: Option<Uuid>,
+    pub quantity: i64,
+    pub idempotency_key: String,
+    /// Defaults to now
+    pub occurred_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of one billing run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageBillingSummary {
+    pub batches: u64,
+    pub billed_events: u64,
+    pub failed_events: u64,
+    pub billed_lamports: i64,
+}
+
+/// A user's usage of one meter
+#[derive(Debug, Clone)]
+pub struct MeterUsage {
+    pub meter: MeterEntity,
+    pub totals: MeterUsageTotals,
+}
+
+/// Usage metering service
+pub struct MeteringService {
+    meter_repo: Arc<dyn MeterRepository>,
+    credit_repo: Arc<dyn CreditRepository>,
+    hold_repo: Arc<dyn CreditHoldRepository>,
+    membership_repo: Arc<dyn MembershipRepository>,
+}
+
+impl MeteringService {
+    pub fn new(
+        meter_repo: Arc<dyn MeterRepository>,
+        credit_repo: Arc<dyn CreditRepository>,
+        hold_repo: Arc<dyn CreditHoldRepository>,
+        membership_repo: Arc<dyn MembershipRepository>,
+    ) -> Self {
+        Self {
+            meter_repo,
+            credit_repo,
+            hold_repo,
+            membership_repo,
+        }
+    }
+
+    /// Price schedule for `meter`, using the org's override when it has one
+    pub async fn price_schedule(
+        &self,
+        meter: &MeterEntity,
+        org_id: Option<Uuid>,
+    ) -> Result<PriceSchedule, AppError> {
+        if let Some(org_id) = org_id {
+            if let Some(price_override) = self
+                .meter_repo
+                .find_price_override(meter.id, org_id)
+                .await?
+            {
+                return Ok(price_override.pricing);
+            }
+        }
+        Ok(meter.pricing.clone())
+    }
+
+    /// Record usage events
+    ///
+    /// The request is validated as a whole before anything is stored.
+    /// Returns, per report, whether it was stored (`false` for an
+    /// idempotency key already used by that user).
+    pub async fn ingest(&self, reports: Vec<UsageReport>) -> Result<Vec<bool>, AppError> {
+        if reports.is_empty() {
+            return Err(AppError::Validation(
+                "At least one usage event is required".into(),
+            ));
+        }
+        if reports.len() > MAX_USAGE_EVENTS_PER_REQUEST {
+            return Err(AppError::Validation(format!(
+                "At most {} usage events can be reported per request",
+                MAX_USAGE_EVENTS_PER_REQUEST
+            )));
+        }
+
+        let now = Utc::now();
+        let oldest = now - Duration::days(MAX_USAGE_BACKDATE_DAYS);
+        let newest = now + Duration::minutes(MAX_USAGE_FUTURE_SKEW_MINUTES);
+        let mut meters: HashMap<String, MeterEntity> = HashMap::new();
+        let mut members: HashMap<(Uuid, Uuid), bool> = HashMap::new();
+        let mut events = Vec::with_capacity(reports.len());
+
+        for report in reports {
+            if report.quantity <= 0 {
+                return Err(AppError::Validation(
+                    "Usage quantity must be positive".into(),
+                ));
+            }
+            if report.idempotency_key.is_empty() || report.idempotency_key.len() > 255 {
+                return Err(AppError::Validation(
+                    "Usage idempotency key must be 1-255 characters".into(),
+                ));
+            }
+            let occurred_at = report.occurred_at.unwrap_or(now);
+            if occurred_at < oldest || occurred_at > newest {
+                return Err(AppError::Validation(format!(
+                    "Usage occurredAt must be within the last {} days",
+                    MAX_USAGE_BACKDATE_DAYS
+                )));
+            }
+
+            if !meters.contains_key(&report.meter) {
+                let meter = self
+                    .meter_repo
+                    .find_meter_by_name(&report.meter)
+                    .await?
+                    .filter(|m| m.active)
+                    .ok_or_else(|| {
+                        AppError::Validation(format!(
+                            "Unknown or inactive meter '{}'",
+                            report.meter
+                        ))
+                    })?;
+                meters.insert(report.meter.clone(), meter);
+            }
+            let meter_id = meters[&report.meter].id;
+
+            if let Some(org_id) = report.org_id {
+                let key = (report.user_id, org_id);
+                let is_member = match members.get(&key) {
+                    Some(is_member) => *is_member,
+                    None => {
+                        let is_member = self
+                            .membership_repo
+                            .find_by_user_and_org(report.user_id, org_id)
+                            .await?
+                            .is_some();
+                        members.insert(key, is_member);
+                        is_member
+                    }
+                };
+                if !is_member {
+                    return Err(AppError::Forbidden(
+                        "Usage can only be billed to an organization the user belongs to".into(),
+                    ));
+                }
+            }
+
+            events.push(UsageEventEntity::new(
+                meter_id,
+                report.user_id,
+                report.org_id,
+                report.quantity,
+                report.idempotency_key,
+                occurred_at,
+            ));
+        }
+
+        self.meter_repo.insert_usage_events(events).await
+    }
+
+    /// Bill up to `limit` pending events
+    ///
+    /// Batches left claimed by an interrupted run are finished first.
+    pub async fn bill_pending(&self, limit: u32) -> Result<UsageBillingSummary, AppError> {
+        let pending = self.meter_repo.list_pending_events(limit).await?;
+
+        let mut claimed: BTreeSet<Uuid> = BTreeSet::new();
+        let mut groups: HashMap<BatchKey, Vec<Uuid>> = HashMap::new();
+        for event in &pending {
+            match event.batch_id {
+                Some(batch_id) => {
+                    claimed.insert(batch_id);
+                }
+                None => {
+                    let (period_start, _) = billing_period(event.occurred_at);
+                    groups
+                        .entry((event.meter_id, event.user_id, event.org_id, period_start))
+                        .or_default()
+                        .push(event.id);
+                }
+            }
+        }
+
+        let mut batches = Vec::new();
+        for batch_id in claimed {
+            batches.push((batch_id, self.meter_repo.list_batch_events(batch_id).await?));
+        }
+        for ids in groups.into_values() {
+            let batch_id = Uuid::new_v4();
+            let events = self.meter_repo.claim_events(&ids, batch_id).await?;
+            batches.push((batch_id, events));
+        }
+
+        let mut summary = UsageBillingSummary::default();
+        for (batch_id, events) in batches {
+            if events.is_empty() {
+                continue;
+            }
+            let count = events.len() as u64;
+            summary.batches += 1;
+            match self.bill_batch(batch_id, events).await? {
+                Some(amount) => {
+                    summary.billed_events += count;
+                    summary.billed_lamports += amount;
+                }
+                None => summary.failed_events += count,
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Price and debit one batch. Returns the amount charged, or `None` if
+    /// the debit was rejected and the events were marked failed.
+    async fn bill_batch(
+        &self,
+        batch_id: Uuid,
+        mut events: Vec<UsageEventEntity>,
+    ) -> Result<Option<i64>, AppError> {
+        events.sort_by_key(|e| (e.occurred_at, e.created_at));
+        let first = &events[0];
+        let (user_id, org_id) = (first.user_id, first.org_id);
+
+        let Some(meter) = self.meter_repo.find_meter(first.meter_id).await? else {
+            self.meter_repo
+                .mark_failed(batch_id, "Meter no longer exists")
+                .await?;
+            return Ok(None);
+        };
+        let schedule = self.price_schedule(&meter, org_id).await?;
+        let (from, to) = billing_period(first.occurred_at);
+        let mut prior = self
+            .meter_repo
+            .billed_quantity(meter.id, user_id, org_id, from, to)
+            .await?;
+
+        let mut costs = Vec::with_capacity(events.len());
+        let mut quantity = 0i64;
+        let mut total = 0i64;
+        for event in &events {
+            let cost = schedule.cost(prior, event.quantity);
+            prior = prior.saturating_add(event.quantity);
+            quantity = quantity.saturating_add(event.quantity);
+            total = total.saturating_add(cost);
+            costs.push((event.id, cost));
+        }
+
+        let idempotency_key = format!("usage_batch:{}", batch_id);
+        if let Some(existing) = self
+            .credit_repo
+            .find_transaction_by_idempotency_key(user_id, &idempotency_key)
+            .await?
+        {
+            // Charged by an earlier run that stopped before marking events
+            self.meter_repo
+                .mark_billed(batch_id, &costs, Some(existing.id))
+                .await?;
+            return Ok(Some(-existing.amount));
+        }
+        if total == 0 {
+            self.meter_repo.mark_billed(batch_id, &costs, None).await?;
+            return Ok(Some(0));
+        }
+
+        let credit_service = CreditService::new(self.credit_repo.clone(), self.hold_repo.clone());
+        let metadata = serde_json::json!({
+            "meter": meter.name,
+            "quantity": quantity,
+            "events": events.len(),
+        });
+        let result = match org_id {
+            Some(org_id) => {
+                let Some(membership) = self
+                    .membership_repo
+                    .find_by_user_and_org(user_id, org_id)
+                    .await?
+                else {
+                    self.meter_repo
+                        .mark_failed(batch_id, "User is no longer a member of the organization")
+                        .await?;
+                    return Ok(None);
+                };
+                let spender = OrgSpender {
+                    org_id,
+                    user_id,
+                    role_can_spend: Permission::CreditsSpend.is_allowed_for(membership.role),
+                };
+                credit_service
+                    .spend_from_org(
+                        spender,
+                        total,
+                        &meter.currency,
+                        idempotency_key,
+                        USAGE_REFERENCE_TYPE,
+                        batch_id,
+                        Some(metadata),
+                    )
+                    .await
+            }
+            None => {
+                credit_service
+                    .spend(
+                        user_id,
+                        total,
+                        &meter.currency,
+                        idempotency_key,
+                        USAGE_REFERENCE_TYPE,
+                        batch_id,
+                        Some(metadata),
+                    )
+                    .await
+            }
+        };
+
+        match result {
+            Ok(spend) => {
+                self.meter_repo
+                    .mark_billed(batch_id, &costs, Some(spend.transaction_id))
+                    .await?;
+                Ok(Some(total))
+            }
+            // Rejected debits (insufficient credits, member caps) are final
+            Err(AppError::Validation(reason)) | Err(AppError::Forbidden(reason)) => {
+                tracing::warn!(
+                    batch_id = %batch_id,
+                    user_id = %user_id,
+                    meter = %meter.name,
+                    error = %reason,
+                    "Usage batch could not be billed"
+                );
+                self.meter_repo.mark_failed(batch_id, &reason).await?;
+                Ok(None)
+            }
+            // Anything else is retried with the same batch on the next run
+            Err(e) => Err(e),
+        }
+    }
+
+    /// A user's personal usage per meter in `currency`, by meter name
+    pub async fn usage_by_meter(
+        &self,
+        user_id: Uuid,
+        currency: &str,
+    ) -> Result<Vec<MeterUsage>, AppError> {
+        let totals = self.meter_repo.usage_by_meter(user_id).await?;
+        let meters: HashMap<Uuid, MeterEntity> = self
+            .meter_repo
+            .list_meters()
+            .await?
+            .into_iter()
+            .map(|m| (m.id, m))
+            .collect();
+
+        let mut usage: Vec<MeterUsage> = totals
+            .into_iter()
+            .filter_map(|totals| {
+                let meter = meters.get(&totals.meter_id)?;
+                (meter.currency == currency).then(|| MeterUsage {
+                    meter: meter.clone(),
+                    totals,
+                })
+            })
+            .collect();
+        usage.sort_by(|a, b| a.meter.name.cmp(&b.meter.name));
+        Ok(usage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        InMemoryMembershipRepository, InMemoryMeterRepository, MembershipEntity,
+        MeterPriceOverrideEntity, OrgRole, PriceTier, PricingModel,
+    };
+    use crate::services::credit_service::test_support::{fund, fund_org, CreditFixture};
+
+    struct Fixture {
+        service: MeteringService,
+        credits: CreditService,
+        meter_repo: Arc<InMemoryMeterRepository>,
+        membership_repo: Arc<InMemoryMembershipRepository>,
+        meter: MeterEntity,
+    }
+
+    async fn fixture() -> Fixture {
+        let meter_repo = Arc::new(InMemoryMeterRepository::new());
+        let CreditFixture {
+            credit_repo,
+            hold_repo,
+            membership_repo,
+            credits,
+            ..
+        } = CreditFixture::new();
+        let pricing = PriceSchedule {
+            model: PricingModel::Tiered,
+            tiers: vec![
+                PriceTier {
+                    up_to: Some(100),
+                    unit_price_lamports: 10,
+                    flat_fee_lamports: 0,
+                },
+                PriceTier {
+                    up_to: None,
+                    unit_price_lamports: 2,
+                    flat_fee_lamports: 0,
+                },
+            ],
+        };
+        let meter = meter_repo
+            .create_meter(MeterEntity::new(
+                "api_call".into(),
+                "API calls".into(),
+                "call".into(),
+                "SOL".into(),
+                pricing,
+            ))
+            .await
+            .unwrap();
+        Fixture {
+            service: MeteringService::new(
+                meter_repo.clone(),
+                credit_repo,
+                hold_repo,
+                membership_repo.clone(),
+            ),
+            credits,
+            meter_repo,
+            membership_repo,
+            meter,
+        }
+    }
+
+    fn report(user_id: Uuid, key: &str, quantity: i64) -> UsageReport {
+        UsageReport {
+            meter: "api_call".into(),
+            user_id,
+            org_id: None,
+            quantity,
+            idempotency_key: key.to_string(),
+            occurred_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ingest_validates_and_dedupes() {
+        let f = fixture().await;
+        let user_id = Uuid::new_v4();
+
+        let stored = f
+            .service
+            .ingest(vec![report(user_id, "a", 5), report(user_id, "b", 5)])
+            .await
+            .unwrap();
+        assert_eq!(stored, [true, true]);
+        let stored = f
+            .service
+            .ingest(vec![report(user_id, "a", 5)])
+            .await
+            .unwrap();
+        assert_eq!(stored, [false]);
+
+        let mut unknown = report(user_id, "c", 1);
+        unknown.meter = "gpu_second".into();
+        assert!(f.service.ingest(vec![unknown]).await.is_err());
+        assert!(f
+            .service
+            .ingest(vec![report(user_id, "d", 0)])
+            .await
+            .is_err());
+
+        // Only members can bill an organization
+        let mut org_report = report(user_id, "e", 1);
+        org_report.org_id = Some(Uuid::new_v4());
+        assert!(matches!(
+            f.service.ingest(vec![org_report]).await,
+            Err(AppError::Forbidden(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_billing_prices_tiers_across_runs() {
+        let f = fixture().await;
+        let user_id = Uuid::new_v4();
+        fund(&f.credits, user_id, 10_000).await;
+
+        f.service
+            .ingest(vec![report(user_id, "a", 60), report(user_id, "b", 60)])
+            .await
+            .unwrap();
+        let summary = f.service.bill_pending(100).await.unwrap();
+        // 100 units at 10, then 20 at 2
+        assert_eq!(summary.batches, 1);
+        assert_eq!(summary.billed_events, 2);
+        assert_eq!(summary.billed_lamports, 1040);
+
+        // Later usage continues from the month-to-date total
+        f.service
+            .ingest(vec![report(user_id, "c", 10)])
+            .await
+            .unwrap();
+        let summary = f.service.bill_pending(100).await.unwrap();
+        assert_eq!(summary.billed_lamports, 20);
+
+        let balance = f.credits.get_balance(user_id, "SOL").await.unwrap();
+        assert_eq!(balance.balance_lamports, 10_000 - 1060);
+
+        let usage = f.service.usage_by_meter(user_id, "SOL").await.unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].meter.name, "api_call");
+        assert_eq!(usage[0].totals.billed_quantity, 130);
+        assert_eq!(usage[0].totals.billed_cost, 1060);
+        assert!(f
+            .service
+            .usage_by_meter(user_id, "USD")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_billing_marks_rejected_batches_failed() {
+        let f = fixture().await;
+        let user_id = Uuid::new_v4();
+        f.service
+            .ingest(vec![report(user_id, "a", 5)])
+            .await
+            .unwrap();
+
+        let summary = f.service.bill_pending(100).await.unwrap();
+        assert_eq!(summary.failed_events, 1);
+        let events = f.meter_repo.list_pending_events(100).await.unwrap();
+        assert!(events.is_empty());
+
+        let usage = f.meter_repo.usage_by_meter(user_id).await.unwrap();
+        assert_eq!(usage[0].billed_quantity, 0);
+        assert_eq!(usage[0].pending_quantity, 0);
+    }
+
+    #[tokio::test]
+    async fn test_org_usage_uses_price_override() {
+        let f = fixture().await;
+        let org_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        f.membership_repo
+            .create(MembershipEntity::new(user_id, org_id, OrgRole::Admin))
+            .await
+            .unwrap();
+        fund_org(&f.credits, org_id, 10_000).await;
+        let now = Utc::now();
+        f.meter_repo
+            .upsert_price_override(MeterPriceOverrideEntity {
+                meter_id: f.meter.id,
+                org_id,
+                pricing: PriceSchedule::per_unit(1),
+                created_by: None,
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+
+        let mut org_report = report(user_id, "a", 50);
+        org_report.org_id = Some(org_id);
+        f.service.ingest(vec![org_report]).await.unwrap();
+        let summary = f.service.bill_pending(100).await.unwrap();
+        assert_eq!(summary.billed_lamports, 50);
+
+        // Org usage is tracked per organization, not in the member's own usage
+        let (from, to) = billing_period(now);
+        let billed = f
+            .meter_repo
+            .billed_quantity(f.meter.id, Uuid::new_v4(), Some(org_id), from, to)
+            .await
+            .unwrap();
+        assert_eq!(billed, 50);
+        assert!(f
+            .meter_repo
+            .usage_by_meter(user_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let balance = f.credits.get_org_balance(org_id, "SOL").await.unwrap();
+        assert_eq!(balance.balance_lamports, 10_000 - 50);
+    }
+}
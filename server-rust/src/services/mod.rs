@@ -32,11 +32,17 @@
 //! - Add `/metrics` endpoint in router for scraping
 
 mod apple_service;
+mod audit_chain_service;
+mod audit_checkpoint_worker;
 mod audit_service;
+mod audit_sink;
 mod authorization_service;
 mod circuit_breaker;
 mod comms_service;
+mod credit_reconciliation_worker;
 mod credit_service;
+mod credit_statement_service;
+mod credit_transfer_service;
 mod credit_types;
 mod deposit_credit_service;
 mod deposit_fee_service;
@@ -46,40 +52,67 @@ mod email;
 mod encrypted_payload;
 mod encryption_service;
 mod google_service;
+mod grant_expiration_worker;
 mod hold_expiration_worker;
 mod jupiter_swap_service;
 mod jwt_service;
 mod logging_service;
+mod metering_service;
 mod metrics_service;
 mod mfa_attempt_service;
 mod micro_batch_worker;
 mod note_encryption_service;
 mod notification_service;
 pub mod oidc_service;
+mod org_purge_worker;
 mod outbox_worker;
 mod password_service;
 mod policy_service;
 mod privacy_sidecar_client;
+mod relationship_service;
 mod settings_service;
 mod sidecar_types;
 mod sol_price_service;
 mod solana_service;
 mod step_up_service;
+mod stripe_client;
+mod stripe_topup_service;
+mod subscription_renewal_worker;
+mod subscription_service;
 mod totp_service;
+mod usage_billing_worker;
+mod user_purge_worker;
 mod wallet_signing_service;
 mod wallet_unlock_cache;
 pub mod webauthn_service;
+mod webhook_service;
 mod withdrawal_worker;
 
 pub use apple_service::{AppleService, AppleTokenClaims};
-pub use audit_service::AuditService;
+pub use audit_chain_service::{AuditChainBreak, AuditChainReport, AuditChainService};
+pub use audit_checkpoint_worker::{AuditCheckpointConfig, AuditCheckpointWorker};
+pub use audit_service::{with_impersonation, AuditService, ImpersonationTag};
+pub use audit_sink::{
+    audit_sink_record, audit_sinks_from_config, AuditSink, AuditSinkWorker, NdjsonFileSink,
+    OtlpLogSink, SyslogSink, SyslogTransport,
+};
 pub use authorization_service::{
     AuthContext, AuthorizationResult, AuthorizationService, Permission,
 };
 pub use comms_service::CommsService;
+pub use credit_reconciliation_worker::{CreditReconciliationConfig, CreditReconciliationWorker};
 pub use credit_service::{
-    AdjustResult, CreditBalance, CreditHistory, CreditHistoryItem, CreditService, HoldResult,
-    SpendResult,
+    AdjustResult, BudgetAlert, CaptureResult, CreditBalance, CreditBucket, CreditHistory,
+    CreditHistoryItem, CreditService, GrantResult, HoldResult, LedgerAccountLine, LedgerReport,
+    OrgSpender, ReconciliationReport, SpendResult,
+};
+pub use credit_statement_service::{
+    CreditStatement, CreditStatementService, StatementCategory, StatementLine, StatementSpendGroup,
+    MAX_STATEMENT_DAYS, MAX_STATEMENT_TRANSACTIONS,
+};
+pub use credit_transfer_service::{
+    check_daily_limit, CreditAccount, CreditTransfer, CreditTransferService, PreparedTransfer,
+    TransferRequest, MAX_TRANSFER_NOTE_LEN, TRANSFER_DAILY_LIMIT_SETTING, TRANSFER_STEP_UP_SETTING,
 };
 pub use deposit_credit_service::{CreditParams, CreditResult, DepositCreditService};
 pub use deposit_fee_service::{CalculatedFees, DepositFeeService, FeeConfig, FeePolicy};
@@ -88,20 +121,28 @@ pub use deposit_tiered_service::{
     execute_admin_withdrawal, MicroDepositResult, PublicDepositResult, TieredDepositService,
 };
 pub use email::{
-    Email, EmailService, EmailType, InstantLinkEmailData, InviteEmailData, LogEmailService,
+    CreditStatementEmailData, Email, EmailChangeEmailData, EmailChangeNoticeEmailData,
+    EmailService, EmailType, InstantLinkEmailData, InviteEmailData, LogEmailService,
     NoopEmailService, PasswordResetEmailData, PostmarkEmailService, SecurityAlertEmailData,
     VerificationEmailData,
 };
 pub(crate) use encrypted_payload::decrypt_base64_payload;
 pub use encryption_service::EncryptionService;
 pub use google_service::{GoogleService, GoogleTokenClaims};
+pub use grant_expiration_worker::{GrantExpirationConfig, GrantExpirationWorker};
 pub use hold_expiration_worker::{HoldExpirationConfig, HoldExpirationWorker};
 pub use jupiter_swap_service::{
     ExecuteResult as JupiterExecuteResult, JupiterSwapService, OrderParams as JupiterOrderParams,
     SwapOrder as JupiterSwapOrder,
 };
-pub use jwt_service::{AccessTokenClaims, JwtService, TokenContext};
+pub use jwt_service::{
+    sanitize_custom_claims, AccessTokenClaims, ActorClaim, JwtService, TokenContext,
+    MAX_CUSTOM_CLAIMS_BYTES, RESERVED_CLAIMS,
+};
 pub use logging_service::{init_logging, LogLevel, LoggingService};
+pub use metering_service::{
+    MeterUsage, MeteringService, UsageBillingSummary, UsageReport, MAX_USAGE_EVENTS_PER_REQUEST,
+};
 pub use metrics_service::{
     get_prometheus_handle, init_metrics, record_auth_duration, record_auth_failure,
     record_auth_success, record_credits_spent, record_deposit, record_error, record_http_request,
@@ -118,6 +159,7 @@ pub use notification_service::{
     NotificationService, NotificationSeverity, TelegramNotificationService,
 };
 pub use oidc_service::OidcService;
+pub use org_purge_worker::{OrgPurgeConfig, OrgPurgeWorker, DEFAULT_ORG_DELETION_GRACE_DAYS};
 pub use outbox_worker::{OutboxWorker, OutboxWorkerConfig};
 pub use password_service::{PasswordRules, PasswordService};
 pub use policy_service::{PolicyContext, PolicyEvaluationResult, PolicyService};
@@ -125,11 +167,23 @@ pub use privacy_sidecar_client::{
     BalanceResponse as SidecarBalanceResponse, DepositResponse as SidecarDepositResponse,
     PrivacySidecarClient, SidecarClientConfig, WithdrawResponse as SidecarWithdrawResponse,
 };
+pub use relationship_service::{ExpandNode, RelationshipService};
 pub use settings_service::SettingsService;
 pub use sol_price_service::SolPriceService;
 pub use solana_service::SolanaService;
 pub use step_up_service::{StepUpService, DEFAULT_STEP_UP_MAX_AGE_SECS};
+pub use stripe_client::{verify_stripe_signature, StripeClient, StripeEvent, StripePaymentParams};
+pub use stripe_topup_service::{StripeEventOutcome, StripeTopupCheckout, StripeTopupService};
+pub use subscription_renewal_worker::{SubscriptionRenewalConfig, SubscriptionRenewalWorker};
+pub use subscription_service::{
+    SubscriptionRenewalSummary, SubscriptionService, CANCEL_REASON_PAYMENT_FAILED,
+    CANCEL_REASON_USER, ENTITLEMENT_PERMISSION_PREFIX,
+};
 pub use totp_service::TotpService;
+pub use usage_billing_worker::{UsageBillingConfig, UsageBillingWorker};
+pub use user_purge_worker::{
+    UserPurgeConfig, UserPurgeWorker, DEFAULT_ACCOUNT_DELETION_GRACE_DAYS,
+};
 pub use wallet_signing_service::{
     derive_child_seed_from_bytes, derive_pubkey_at_index,
     UnlockCredential as WalletUnlockCredential, WalletSigningService,
@@ -138,4 +192,5 @@ pub use wallet_unlock_cache::{
     create_wallet_unlock_cache, WalletUnlockCache, WalletUnlockCacheConfig,
 };
 pub use webauthn_service::WebAuthnService;
+pub use webhook_service::{webhook_event_for_audit, WebhookService};
 pub use withdrawal_worker::{WithdrawalWorker, WithdrawalWorkerConfig};
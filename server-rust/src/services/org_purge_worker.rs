@@ -0,0 +1,217 @@
+//! Background worker for purging soft-deleted organizations
+//!
+//! `DELETE /orgs/:org_id` only schedules deletion; the org stays restorable
+//! until its `purge_after` timestamp. This worker periodically finds orgs
+//! whose grace period has ended and removes them permanently.
+//!
+//! Default poll interval: 1 hour
+
+use chrono::Utc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::storage::Storage;
+
+/// Default poll interval for the purge worker (1 hour)
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 3600;
+
+/// Default number of orgs purged per poll
+const DEFAULT_BATCH_SIZE: u32 = 50;
+
+/// Default grace period before a deleted org is purged (days)
+pub const DEFAULT_ORG_DELETION_GRACE_DAYS: u32 = 30;
+
+/// Configuration for the org purge worker
+#[derive(Debug, Clone)]
+pub struct OrgPurgeConfig {
+    /// How often to check for orgs past their grace period (seconds)
+    pub poll_interval_secs: u64,
+    /// Maximum orgs purged per poll
+    pub batch_size: u32,
+    /// Delete org-scoped rows explicitly (in-memory storage has no FK cascades)
+    pub cascade_in_memory: bool,
+}
+
+impl Default for OrgPurgeConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+            batch_size: DEFAULT_BATCH_SIZE,
+            cascade_in_memory: false,
+        }
+    }
+}
+
+/// Background worker that permanently removes orgs past their grace period
+pub struct OrgPurgeWorker {
+    storage: Storage,
+    config: OrgPurgeConfig,
+}
+
+impl OrgPurgeWorker {
+    /// Create a new org purge worker
+    pub fn new(storage: Storage, config: OrgPurgeConfig) -> Self {
+        Self { storage, config }
+    }
+
+    /// Start the worker as a background task with graceful shutdown support
+    pub fn start(self, cancel_token: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
+
+            info!(
+                poll_interval_secs = self.config.poll_interval_secs,
+                "Org purge worker started"
+            );
+
+            loop {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        info!("Org purge worker shutting down");
+                        break;
+                    }
+                    _ = tokio::time::sleep(poll_interval) => {
+                        if let Err(e) = self.purge_due().await {
+                            error!(error = %e, "Failed to purge deleted organizations");
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Purge every org whose grace period has ended. Returns the number purged.
+    pub async fn purge_due(&self) -> Result<usize, AppError> {
+        let due = self
+            .storage
+            .org_repo
+            .find_due_for_purge(Utc::now(), self.config.batch_size)
+            .await?;
+
+        let mut purged = 0;
+        for org in due {
+            match self.purge_org(org.id).await {
+                Ok(()) => {
+                    info!(org_id = %org.id, "Purged deleted organization");
+                    purged += 1;
+                }
+                Err(e) => error!(org_id = %org.id, error = %e, "Failed to purge organization"),
+            }
+        }
+
+        if purged == 0 {
+            debug!("No organizations to purge");
+        }
+
+        Ok(purged)
+    }
+
+    /// Permanently delete an organization and its org-scoped data
+    async fn purge_org(&self, org_id: Uuid) -> Result<(), AppError> {
+        let storage = &self.storage;
+
+        // Service accounts are backed by users rows, which the org FK cascade
+        // does not reach.
+        for account in storage.service_account_repo.find_by_org(org_id).await? {
+            storage.session_repo.revoke_all_for_user(account.id).await?;
+            storage.service_account_repo.delete(account.id).await?;
+            storage.user_repo.delete(account.id).await?;
+        }
+
+        if self.config.cascade_in_memory {
+            // In-memory storage doesn't enforce FK cascades.
+            storage.invite_repo.delete_by_org(org_id).await?;
+            storage.invite_link_repo.delete_by_org(org_id).await?;
+            storage.membership_repo.delete_by_org(org_id).await?;
+            storage.custom_role_repo.delete_by_org(org_id).await?;
+            storage.policy_repo.delete_by_org(org_id).await?;
+            storage.relationship_repo.delete_by_org(org_id).await?;
+            storage.audit_repo.redact_by_org(org_id).await?;
+            storage.outbox_repo.delete_by_org(org_id).await?;
+            storage.api_key_repo.delete_by_org(org_id).await?;
+            storage.credit_repo.delete_org_credit_data(org_id).await?;
+        }
+
+        storage.org_repo.delete(org_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{MembershipEntity, OrgEntity, OrgRole};
+
+    #[test]
+    fn test_default_config() {
+        let config = OrgPurgeConfig::default();
+        assert_eq!(config.poll_interval_secs, 3600);
+        assert_eq!(config.batch_size, 50);
+    }
+
+    #[tokio::test]
+    async fn test_purge_due_only_removes_expired() {
+        let storage = Storage::in_memory();
+        let owner = Uuid::new_v4();
+
+        let expired = storage
+            .org_repo
+            .create(OrgEntity::new("A".into(), "a".into(), owner, false))
+            .await
+            .unwrap();
+        let pending = storage
+            .org_repo
+            .create(OrgEntity::new("B".into(), "b".into(), owner, false))
+            .await
+            .unwrap();
+        storage
+            .membership_repo
+            .create(MembershipEntity::new(owner, expired.id, OrgRole::Owner))
+            .await
+            .unwrap();
+
+        storage
+            .org_repo
+            .schedule_deletion(expired.id, Utc::now() - chrono::Duration::seconds(1))
+            .await
+            .unwrap();
+        storage
+            .org_repo
+            .schedule_deletion(pending.id, Utc::now() + chrono::Duration::days(1))
+            .await
+            .unwrap();
+
+        let worker = OrgPurgeWorker::new(
+            storage.clone(),
+            OrgPurgeConfig {
+                cascade_in_memory: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(worker.purge_due().await.unwrap(), 1);
+
+        assert!(storage
+            .org_repo
+            .find_by_id(expired.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            storage
+                .membership_repo
+                .count_by_org(expired.id)
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(storage
+            .org_repo
+            .find_by_id(pending.id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
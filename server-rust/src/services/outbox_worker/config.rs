@@ -11,6 +11,10 @@ pub struct OutboxWorkerConfig {
     pub lock_timeout_secs: u64,
     /// Timeout for individual delivery operations (in seconds)
     pub delivery_timeout_secs: u64,
+    /// Timeout for a single webhook HTTP request (in seconds)
+    pub webhook_request_timeout_secs: u64,
+    /// Consecutive failed deliveries after which a webhook endpoint is disabled
+    pub webhook_disable_after_failures: u32,
 }
 
 impl Default for OutboxWorkerConfig {
@@ -20,6 +24,8 @@ impl Default for OutboxWorkerConfig {
             batch_size: 20,
             lock_timeout_secs: 300,
             delivery_timeout_secs: 30,
+            webhook_request_timeout_secs: 10,
+            webhook_disable_after_failures: 25,
         }
     }
 }
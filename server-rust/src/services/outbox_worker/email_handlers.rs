@@ -3,8 +3,9 @@
 use crate::errors::AppError;
 use crate::repositories::{OutboxEvent, OutboxEventType};
 use crate::services::{
-    EmailService, InstantLinkEmailData, InviteEmailData, PasswordResetEmailData,
-    SecurityAlertEmailData, VerificationEmailData,
+    CreditStatementEmailData, EmailChangeEmailData, EmailChangeNoticeEmailData, EmailService,
+    InstantLinkEmailData, InviteEmailData, PasswordResetEmailData, SecurityAlertEmailData,
+    VerificationEmailData,
 };
 use crate::utils::TokenCipher;
 
@@ -31,6 +32,15 @@ pub async fn process_email_event(
         OutboxEventType::EmailSecurityAlert => {
             process_security_alert_email(event, email_service).await
         }
+        OutboxEventType::EmailChange => {
+            process_email_change_email(event, email_service, base_url, token_cipher).await
+        }
+        OutboxEventType::EmailChangeNotice => {
+            process_email_change_notice_email(event, email_service, base_url, token_cipher).await
+        }
+        OutboxEventType::EmailCreditStatement => {
+            process_credit_statement_email(event, email_service).await
+        }
         _ => Err(AppError::Internal(anyhow::anyhow!(
             "Unknown email event type: {}",
             event.event_type.as_str()
@@ -189,3 +199,81 @@ async fn process_security_alert_email(
 
     email_service.send_security_alert(to, data).await
 }
+
+async fn process_credit_statement_email(
+    event: &OutboxEvent,
+    email_service: &dyn EmailService,
+) -> Result<(), AppError> {
+    let to = event.payload["to"]
+        .as_str()
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Missing 'to' field")))?;
+    let field = |name: &str| {
+        event.payload[name]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Missing '{}' field", name)))
+    };
+
+    let data = CreditStatementEmailData {
+        user_name: event.payload["user_name"].as_str().map(String::from),
+        period: field("period")?,
+        statement_html: field("statement_html")?,
+        statement_text: field("statement_text")?,
+    };
+
+    email_service.send_credit_statement(to, data).await
+}
+
+async fn process_email_change_email(
+    event: &OutboxEvent,
+    email_service: &dyn EmailService,
+    base_url: &str,
+    token_cipher: &TokenCipher,
+) -> Result<(), AppError> {
+    let to = event.payload["to"]
+        .as_str()
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Missing 'to' field")))?;
+    let token_enc = event.payload["token_enc"]
+        .as_str()
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Missing 'token_enc' field")))?;
+    let token = token_cipher.decrypt(token_enc)?;
+
+    let data = EmailChangeEmailData {
+        user_name: event.payload["user_name"].as_str().map(String::from),
+        new_email: event.payload["new_email"]
+            .as_str()
+            .unwrap_or(to)
+            .to_string(),
+        confirm_url: format!("{}/confirm-email-change?token={}", base_url, token),
+        expires_in_hours: event.payload["expires_in_hours"].as_u64().unwrap_or(24) as u32,
+    };
+
+    email_service.send_email_change(to, data).await
+}
+
+async fn process_email_change_notice_email(
+    event: &OutboxEvent,
+    email_service: &dyn EmailService,
+    base_url: &str,
+    token_cipher: &TokenCipher,
+) -> Result<(), AppError> {
+    let to = event.payload["to"]
+        .as_str()
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Missing 'to' field")))?;
+    let new_email = event.payload["new_email"]
+        .as_str()
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Missing 'new_email' field")))?;
+    let token_enc = event.payload["token_enc"]
+        .as_str()
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Missing 'token_enc' field")))?;
+    let token = token_cipher.decrypt(token_enc)?;
+
+    let data = EmailChangeNoticeEmailData {
+        user_name: event.payload["user_name"].as_str().map(String::from),
+        new_email: new_email.to_string(),
+        revert_url: format!("{}/revert-email-change?token={}", base_url, token),
+        expires_in_days: event.payload["expires_in_days"].as_u64().unwrap_or(7) as u32,
+    };
+
+    email_service.send_email_change_notice(to, data).await
+}
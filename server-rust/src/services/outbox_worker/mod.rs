@@ -1,4 +1,4 @@
-//! Outbox worker for processing async email and notification delivery
+//! Outbox worker for processing async email, notification and webhook delivery
 //!
 //! # Idempotency
 //!
@@ -14,6 +14,7 @@
 mod config;
 mod email_handlers;
 mod notification_handlers;
+mod webhook_handlers;
 
 #[cfg(test)]
 mod tests;
@@ -27,12 +28,13 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 use crate::errors::AppError;
-use crate::repositories::{OutboxEvent, OutboxRepository};
+use crate::repositories::{OutboxEvent, OutboxRepository, WebhookRepository};
 use crate::services::{AdminNotification, EmailService, NotificationService, NotificationSeverity};
 use crate::utils::TokenCipher;
 
 use email_handlers::process_email_event;
 use notification_handlers::process_notification_event;
+use webhook_handlers::process_webhook_event;
 
 /// Sanitize error message for safe logging (truncate and redact potential PII)
 fn sanitize_error_for_log(error: &str) -> String {
@@ -90,6 +92,8 @@ pub struct OutboxWorker {
     config: OutboxWorkerConfig,
     base_url: String,
     token_cipher: TokenCipher,
+    webhook_repo: Option<Arc<dyn WebhookRepository>>,
+    http_client: reqwest::Client,
 }
 
 impl OutboxWorker {
@@ -108,9 +112,31 @@ impl OutboxWorker {
             config,
             base_url,
             token_cipher,
+            webhook_repo: None,
+            http_client: reqwest::Client::new(),
         }
     }
 
+    /// Deliver `webhook.delivery` events to admin-managed endpoints
+    ///
+    /// Endpoint secrets are decrypted with the worker's token cipher, so it
+    /// must be built from the same secret as the `WebhookService`.
+    pub fn with_webhooks(mut self, webhook_repo: Arc<dyn WebhookRepository>) -> Self {
+        self.webhook_repo = Some(webhook_repo);
+        // Redirects are not followed so an endpoint cannot bounce requests to
+        // an address that skipped destination validation. A default client
+        // would follow them, so there is no fallback: building only fails
+        // when the TLS backend can't be initialized.
+        self.http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(
+                self.config.webhook_request_timeout_secs,
+            ))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build webhook HTTP client");
+        self
+    }
+
     /// Start the worker as a background task with graceful shutdown support
     ///
     /// The worker will process events until the cancellation token is triggered.
@@ -225,7 +251,27 @@ impl OutboxWorker {
 
         // Process based on type with timeout to prevent stuck workers
         let delivery_timeout = Duration::from_secs(self.config.delivery_timeout_secs);
-        let result = if event.event_type.is_email() {
+        let result = if event.event_type.is_webhook() {
+            match self.webhook_repo.as_ref() {
+                Some(webhook_repo) => {
+                    tokio::time::timeout(
+                        delivery_timeout,
+                        process_webhook_event(
+                            event,
+                            webhook_repo.as_ref(),
+                            &self.http_client,
+                            &self.token_cipher,
+                            self.config.webhook_disable_after_failures,
+                            current_attempts >= event.max_attempts,
+                        ),
+                    )
+                    .await
+                }
+                None => Ok(Err(AppError::Config(
+                    "Outbox worker has no webhook repository".into(),
+                ))),
+            }
+        } else if event.event_type.is_email() {
             tokio::time::timeout(
                 delivery_timeout,
                 process_email_event(
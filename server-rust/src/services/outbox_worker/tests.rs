@@ -246,6 +246,46 @@ async fn test_process_instant_link_event() {
     assert!(emails[0].html_body.contains("15 minutes"));
 }
 
+#[tokio::test]
+async fn test_process_email_change_notice_event() {
+    let outbox_repo = Arc::new(InMemoryOutboxRepository::new());
+    let email_service = Arc::new(LogEmailService::new());
+    let notification_service = Arc::new(LogNotificationService::new());
+    let token_cipher = TokenCipher::new("test-secret");
+
+    let worker = OutboxWorker::new(
+        outbox_repo.clone(),
+        email_service.clone(),
+        notification_service,
+        OutboxWorkerConfig::default(),
+        "https://example.com".to_string(),
+        token_cipher.clone(),
+    );
+
+    let token_enc = token_cipher.encrypt("abc").unwrap();
+    let event = crate::repositories::OutboxEvent::new(
+        OutboxEventType::EmailChangeNotice,
+        serde_json::json!({
+            "to": "old@example.com",
+            "user_name": "Test User",
+            "new_email": "new@example.com",
+            "token_enc": token_enc,
+            "expires_in_days": 7
+        }),
+    );
+    outbox_repo.create(event.clone()).await.unwrap();
+
+    worker.process_event(&event).await.unwrap();
+
+    let emails = email_service.get_sent_emails().await;
+    assert_eq!(emails.len(), 1);
+    assert_eq!(emails[0].to, "old@example.com");
+    assert!(emails[0].html_body.contains("new@example.com"));
+    assert!(emails[0]
+        .html_body
+        .contains("https://example.com/revert-email-change?token=abc"));
+}
+
 #[tokio::test]
 async fn test_process_event_marks_failed_on_max_attempts() {
     let outbox_repo = Arc::new(InMemoryOutboxRepository::new());
@@ -276,6 +316,116 @@ async fn test_process_event_marks_failed_on_max_attempts() {
     assert_eq!(updated.status, crate::repositories::OutboxStatus::Failed);
 }
 
+/// Worker with webhooks enabled and a queued delivery to an endpoint that
+/// always fails destination validation
+async fn webhook_fixture(
+    disable_after_failures: u32,
+) -> (
+    OutboxWorker,
+    Arc<InMemoryOutboxRepository>,
+    Arc<crate::repositories::InMemoryWebhookRepository>,
+    crate::repositories::OutboxEvent,
+    uuid::Uuid,
+) {
+    use crate::repositories::{
+        InMemoryWebhookRepository, WebhookDeliveryEntity, WebhookEndpointEntity, WebhookRepository,
+    };
+
+    let outbox_repo = Arc::new(InMemoryOutboxRepository::new());
+    let webhook_repo = Arc::new(InMemoryWebhookRepository::new());
+    let token_cipher = TokenCipher::new("test-secret");
+    let config = OutboxWorkerConfig {
+        webhook_disable_after_failures: disable_after_failures,
+        ..Default::default()
+    };
+    let worker = OutboxWorker::new(
+        outbox_repo.clone(),
+        Arc::new(LogEmailService::new()),
+        Arc::new(LogNotificationService::new()),
+        config,
+        "https://example.com".to_string(),
+        token_cipher.clone(),
+    )
+    .with_webhooks(webhook_repo.clone());
+
+    let endpoint = webhook_repo
+        .create_endpoint(WebhookEndpointEntity::new(
+            "http://127.0.0.1:9/hook".to_string(),
+            None,
+            token_cipher.encrypt(&"s".repeat(32)).unwrap(),
+            vec![],
+            None,
+        ))
+        .await
+        .unwrap();
+    let delivery = webhook_repo
+        .create_delivery(WebhookDeliveryEntity::new(
+            endpoint.id,
+            "org_created",
+            serde_json::json!({"event": "org_created"}),
+        ))
+        .await
+        .unwrap();
+    let event = crate::repositories::OutboxEvent::new(
+        OutboxEventType::WebhookDelivery,
+        serde_json::json!({ "delivery_id": delivery.id }),
+    );
+    outbox_repo.create(event.clone()).await.unwrap();
+
+    (worker, outbox_repo, webhook_repo, event, delivery.id)
+}
+
+#[tokio::test]
+async fn test_webhook_failure_is_logged_and_retried() {
+    use crate::repositories::{WebhookDeliveryStatus, WebhookRepository};
+
+    let (worker, outbox_repo, webhook_repo, event, delivery_id) = webhook_fixture(25).await;
+
+    assert!(worker.process_event(&event).await.is_err());
+
+    let delivery = webhook_repo
+        .find_delivery(delivery_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(delivery.attempts, 1);
+    assert!(delivery.last_error.unwrap().contains("private IP"));
+
+    let outbox_event = outbox_repo.find_by_id(event.id).await.unwrap().unwrap();
+    assert_eq!(
+        outbox_event.status,
+        crate::repositories::OutboxStatus::Pending
+    );
+}
+
+#[tokio::test]
+async fn test_webhook_endpoint_disabled_after_repeated_failures() {
+    use crate::repositories::{WebhookDeliveryStatus, WebhookRepository};
+
+    let (worker, outbox_repo, webhook_repo, event, delivery_id) = webhook_fixture(1).await;
+
+    // Disabling the endpoint ends the outbox event instead of retrying
+    worker.process_event(&event).await.unwrap();
+
+    let delivery = webhook_repo
+        .find_delivery(delivery_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+    let endpoint = webhook_repo
+        .find_endpoint(delivery.endpoint_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!endpoint.enabled);
+    assert!(endpoint.disabled_reason.is_some());
+
+    let outbox_event = outbox_repo.find_by_id(event.id).await.unwrap().unwrap();
+    assert_eq!(outbox_event.status, crate::repositories::OutboxStatus::Done);
+}
+
 #[test]
 fn test_sanitize_error_for_log() {
     // Email addresses should be redacted
@@ -0,0 +1,173 @@
+//! Webhook delivery handlers for the outbox worker
+//!
+//! Each `webhook.delivery` event points at a row in the delivery log. The
+//! stored payload is signed with the endpoint's secret and POSTed; the
+//! outcome of every attempt is written back to the log. Returning an error
+//! leaves the outbox event to be retried with backoff.
+
+use chrono::Utc;
+use reqwest::Client;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::callback::{sign_webhook_payload, validate_webhook_destination};
+use crate::errors::AppError;
+use crate::repositories::{
+    OutboxEvent, WebhookDeliveryEntity, WebhookDeliveryStatus, WebhookEndpointEntity,
+    WebhookRepository,
+};
+use crate::utils::TokenCipher;
+
+/// Maximum number of response body characters kept in the delivery log
+const MAX_LOGGED_BODY_CHARS: usize = 500;
+
+/// Process a webhook delivery event
+///
+/// `final_attempt` is true when the outbox will not retry a failure, in which
+/// case the delivery is marked failed instead of left pending.
+pub async fn process_webhook_event(
+    event: &OutboxEvent,
+    webhook_repo: &dyn WebhookRepository,
+    client: &Client,
+    token_cipher: &TokenCipher,
+    disable_after_failures: u32,
+    final_attempt: bool,
+) -> Result<(), AppError> {
+    let delivery_id = event.payload["delivery_id"]
+        .as_str()
+        .and_then(|s| Uuid::parse_str(s).ok())
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("Missing delivery_id in payload")))?;
+
+    // The delivery disappears when its endpoint is deleted
+    let Some(mut delivery) = webhook_repo.find_delivery(delivery_id).await? else {
+        debug!(delivery_id = %delivery_id, "Webhook delivery no longer exists; skipping");
+        return Ok(());
+    };
+    if delivery.status != WebhookDeliveryStatus::Pending {
+        return Ok(());
+    }
+
+    let endpoint = match webhook_repo.find_endpoint(delivery.endpoint_id).await? {
+        Some(endpoint) if endpoint.enabled => endpoint,
+        _ => {
+            delivery.status = WebhookDeliveryStatus::Failed;
+            delivery.last_error = Some("Endpoint is disabled".to_string());
+            webhook_repo.update_delivery(&delivery).await?;
+            return Ok(());
+        }
+    };
+
+    delivery.attempts = delivery.attempts.saturating_add(1);
+    delivery.last_attempt_at = Some(Utc::now());
+
+    match send(&delivery, &endpoint, client, token_cipher).await {
+        Ok(status) => {
+            delivery.status = WebhookDeliveryStatus::Succeeded;
+            delivery.response_status = Some(status);
+            delivery.last_error = None;
+            delivery.delivered_at = Some(Utc::now());
+            webhook_repo.update_delivery(&delivery).await?;
+            webhook_repo.record_success(endpoint.id).await?;
+            Ok(())
+        }
+        Err(failure) => {
+            delivery.response_status = failure.status;
+            delivery.last_error = Some(failure.message.clone());
+
+            let disabled = webhook_repo
+                .record_failure(endpoint.id, disable_after_failures)
+                .await?
+                .map(|e| !e.enabled)
+                .unwrap_or(true);
+            if disabled {
+                warn!(
+                    endpoint_id = %endpoint.id,
+                    failures = disable_after_failures,
+                    "Webhook endpoint disabled after repeated failures"
+                );
+            }
+
+            if disabled || final_attempt {
+                delivery.status = WebhookDeliveryStatus::Failed;
+            }
+            webhook_repo.update_delivery(&delivery).await?;
+
+            // No point retrying against an endpoint that was just switched off
+            if disabled {
+                return Ok(());
+            }
+            Err(AppError::Internal(anyhow::anyhow!(
+                "Webhook delivery {} failed: {}",
+                delivery.id,
+                failure.message
+            )))
+        }
+    }
+}
+
+/// Why an attempt failed, and the HTTP status if a response was received
+struct DeliveryFailure {
+    status: Option<u16>,
+    message: String,
+}
+
+impl DeliveryFailure {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            status: None,
+            message: message.into(),
+        }
+    }
+}
+
+/// Sign and POST the stored payload, returning the response status on 2xx
+async fn send(
+    delivery: &WebhookDeliveryEntity,
+    endpoint: &WebhookEndpointEntity,
+    client: &Client,
+    token_cipher: &TokenCipher,
+) -> Result<u16, DeliveryFailure> {
+    let secret = token_cipher
+        .decrypt(&endpoint.secret_encrypted)
+        .map_err(|_| DeliveryFailure::new("Failed to decrypt endpoint secret"))?;
+    validate_webhook_destination(&endpoint.url)
+        .await
+        .map_err(|e| match e {
+            // Display for Internal hides the detail, which is what the log is for
+            AppError::Internal(e) => DeliveryFailure::new(e.to_string()),
+            other => DeliveryFailure::new(other.to_string()),
+        })?;
+
+    let body = serde_json::to_string(&delivery.payload)
+        .map_err(|e| DeliveryFailure::new(format!("Failed to serialize payload: {}", e)))?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+        .to_string();
+    let signature = sign_webhook_payload(&secret, &format!("{}.{}", timestamp, body));
+
+    let response = client
+        .post(&endpoint.url)
+        .header("Content-Type", "application/json")
+        .header("X-Cedros-Signature", signature)
+        .header("X-Cedros-Timestamp", &timestamp)
+        .header("X-Cedros-Delivery", delivery.id.to_string())
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| DeliveryFailure::new(format!("Request failed: {}", e)))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(status.as_u16());
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    let body: String = body.chars().take(MAX_LOGGED_BODY_CHARS).collect();
+    Err(DeliveryFailure {
+        status: Some(status.as_u16()),
+        message: format!("Endpoint returned {}: {}", status, body),
+    })
+}
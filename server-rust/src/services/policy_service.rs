@@ -20,6 +20,7 @@
 //! A full fix would require architectural changes to integrate permission
 //! checks within the same database transaction as the protected operation.
 
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -28,7 +29,8 @@ use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::repositories::{
-    AbacPolicy, MembershipRepository, OrgRepository, PolicyEffect, PolicyRepository, UserRepository,
+    AbacPolicy, AttributeMatcher, MembershipRepository, OrgRepository, PolicyConditions,
+    PolicyEffect, PolicyRepository, UserRepository,
 };
 
 /// Context for policy evaluation containing all attributes
@@ -147,15 +149,21 @@ impl PolicyService {
     }
 
     /// Evaluate policies for a permission check
+    ///
+    /// `client_ip` is the address of the request being authorized, as
+    /// determined by the server; it replaces any `environment.ip` provided.
     pub async fn evaluate(
         &self,
         user_id: Uuid,
         org_id: Uuid,
         permission: &str,
         context: Option<PolicyContext>,
+        client_ip: Option<String>,
     ) -> Result<PolicyEvaluationResult, AppError> {
         // Build full context with subject attributes
-        let context = self.build_full_context(user_id, org_id, context).await?;
+        let context = self
+            .build_full_context(user_id, org_id, context, client_ip)
+            .await?;
 
         // Get applicable policies (sorted by priority)
         let policies = self
@@ -185,6 +193,7 @@ impl PolicyService {
         user_id: Uuid,
         org_id: Uuid,
         provided_context: Option<PolicyContext>,
+        client_ip: Option<String>,
     ) -> Result<PolicyContext, AppError> {
         let mut context = provided_context.unwrap_or_default();
 
@@ -227,83 +236,28 @@ impl PolicyService {
                 .insert("org_id".to_string(), Value::String(org_id.to_string()));
         }
 
-        Ok(context)
-    }
-
-    /// Evaluate a single policy against the context
-    fn evaluate_policy(&self, policy: &AbacPolicy, context: &PolicyContext) -> bool {
-        // Check subject conditions
-        for (key, matcher) in &policy.conditions.subject {
-            let value = context.subject.get(key);
-            if !self.match_with_interpolation(matcher, value, context) {
-                return false;
-            }
-        }
-
-        // Check resource conditions
-        for (key, matcher) in &policy.conditions.resource {
-            let value = context.resource.get(key);
-            if !self.match_with_interpolation(matcher, value, context) {
-                return false;
-            }
-        }
-
-        // Check environment conditions
-        for (key, matcher) in &policy.conditions.environment {
-            let value = context.environment.get(key);
-            if !self.match_with_interpolation(matcher, value, context) {
-                return false;
+        // Time and IP conditions only see server-side values, so a caller
+        // cannot satisfy them by supplying its own
+        context
+            .environment
+            .insert("time".to_string(), Value::String(Utc::now().to_rfc3339()));
+        match client_ip {
+            Some(ip) => {
+                context
+                    .environment
+                    .insert("ip".to_string(), Value::String(ip));
             }
-        }
-
-        true
-    }
-
-    /// Match with variable interpolation (e.g., ${subject.user_id})
-    fn match_with_interpolation(
-        &self,
-        matcher: &crate::repositories::AttributeMatcher,
-        value: Option<&Value>,
-        context: &PolicyContext,
-    ) -> bool {
-        use crate::repositories::AttributeMatcher;
-
-        match matcher {
-            AttributeMatcher::Equals(expected) => {
-                let resolved = self.resolve_value(expected, context);
-                value.map(|v| *v == resolved).unwrap_or(false)
+            None => {
+                context.environment.remove("ip");
             }
-            _ => matcher.matches(value),
         }
-    }
 
-    /// Resolve variable references in a value
-    fn resolve_value(&self, value: &Value, context: &PolicyContext) -> Value {
-        if let Some(s) = value.as_str() {
-            if s.starts_with("${") && s.ends_with('}') {
-                let path = &s[2..s.len() - 1];
-                if let Some(resolved) = self.get_context_value(path, context) {
-                    return resolved.clone();
-                }
-            }
-        }
-        value.clone()
+        Ok(context)
     }
 
-    /// Get a value from context by path (e.g., "subject.user_id")
-    fn get_context_value<'a>(&self, path: &str, context: &'a PolicyContext) -> Option<&'a Value> {
-        let parts: Vec<&str> = path.split('.').collect();
-        if parts.len() != 2 {
-            return None;
-        }
-
-        let (section, key) = (parts[0], parts[1]);
-        match section {
-            "subject" => context.subject.get(key),
-            "resource" => context.resource.get(key),
-            "environment" => context.environment.get(key),
-            _ => None,
-        }
+    /// Evaluate a single policy against the context
+    fn evaluate_policy(&self, policy: &AbacPolicy, context: &PolicyContext) -> bool {
+        conditions_match(&policy.conditions, context)
     }
 
     /// Fall back to RBAC when no ABAC policy matches
@@ -375,10 +329,63 @@ impl PolicyService {
     }
 }
 
+/// Evaluate a condition tree against the context
+///
+/// Attribute maps are ANDed with the nested `all`/`any`/`not` groups.
+pub fn conditions_match(conditions: &PolicyConditions, context: &PolicyContext) -> bool {
+    let sections = [
+        (&conditions.subject, &context.subject),
+        (&conditions.resource, &context.resource),
+        (&conditions.environment, &context.environment),
+    ];
+    for (matchers, attributes) in sections {
+        for (key, matcher) in matchers {
+            if !match_with_interpolation(matcher, attributes.get(key), context) {
+                return false;
+            }
+        }
+    }
+
+    if !conditions.all.iter().all(|c| conditions_match(c, context)) {
+        return false;
+    }
+    if !conditions.any.is_empty() && !conditions.any.iter().any(|c| conditions_match(c, context)) {
+        return false;
+    }
+    if let Some(not) = &conditions.not {
+        if conditions_match(not, context) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Match with variable interpolation (e.g., ${subject.user_id})
+fn match_with_interpolation(
+    matcher: &AttributeMatcher,
+    value: Option<&Value>,
+    context: &PolicyContext,
+) -> bool {
+    matcher
+        .resolve_references(&|path| get_context_value(path, context).cloned())
+        .matches(value)
+}
+
+/// Get a value from context by path (e.g., "subject.user_id")
+fn get_context_value<'a>(path: &str, context: &'a PolicyContext) -> Option<&'a Value> {
+    let (section, key) = path.split_once('.')?;
+    match section {
+        "subject" => context.subject.get(key),
+        "resource" => context.resource.get(key),
+        "environment" => context.environment.get(key),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repositories::{AttributeMatcher, PolicyConditions};
 
     #[test]
     fn test_policy_context_builder() {
@@ -437,4 +444,76 @@ mod tests {
         assert!(conditions.subject.contains_key("role"));
         assert!(conditions.resource.contains_key("owner_id"));
     }
+
+    #[test]
+    fn test_conditions_match_cross_attribute_reference() {
+        let conditions = PolicyConditions::new().with_resource(
+            "owner_id",
+            AttributeMatcher::Equals(Value::String("${subject.user_id}".to_string())),
+        );
+        let owner = PolicyContext::new()
+            .with_subject("user_id", Value::String("u-1".to_string()))
+            .with_resource("owner_id", Value::String("u-1".to_string()));
+        let other = PolicyContext::new()
+            .with_subject("user_id", Value::String("u-2".to_string()))
+            .with_resource("owner_id", Value::String("u-1".to_string()));
+
+        assert!(conditions_match(&conditions, &owner));
+        assert!(!conditions_match(&conditions, &other));
+    }
+
+    #[test]
+    fn test_conditions_match_date_reference() {
+        let conditions = PolicyConditions::new().with_resource(
+            "expires_at",
+            AttributeMatcher::After("${environment.time}".to_string()),
+        );
+        let context = PolicyContext::new()
+            .with_resource(
+                "expires_at",
+                Value::String("2030-01-01T00:00:00Z".to_string()),
+            )
+            .with_environment("time", Value::String("2029-06-01T00:00:00Z".to_string()));
+        assert!(conditions_match(&conditions, &context));
+
+        let expired =
+            context.with_environment("time", Value::String("2031-01-01T00:00:00Z".to_string()));
+        assert!(!conditions_match(&conditions, &expired));
+    }
+
+    #[test]
+    fn test_conditions_match_any_all_not() {
+        let conditions = PolicyConditions::new()
+            .with_any(PolicyConditions::new().with_environment(
+                "ip",
+                AttributeMatcher::InCidr(vec!["10.0.0.0/8".to_string()]),
+            ))
+            .with_any(PolicyConditions::new().with_subject(
+                "role",
+                AttributeMatcher::Equals(Value::String("owner".to_string())),
+            ))
+            .with_not(PolicyConditions::new().with_resource(
+                "status",
+                AttributeMatcher::Equals(Value::String("archived".to_string())),
+            ));
+
+        let internal_member = PolicyContext::new()
+            .with_subject("role", Value::String("member".to_string()))
+            .with_environment("ip", Value::String("10.2.3.4".to_string()));
+        assert!(conditions_match(&conditions, &internal_member));
+
+        let external_owner = PolicyContext::new()
+            .with_subject("role", Value::String("owner".to_string()))
+            .with_environment("ip", Value::String("8.8.8.8".to_string()));
+        assert!(conditions_match(&conditions, &external_owner));
+
+        let external_member = PolicyContext::new()
+            .with_subject("role", Value::String("member".to_string()))
+            .with_environment("ip", Value::String("8.8.8.8".to_string()));
+        assert!(!conditions_match(&conditions, &external_member));
+
+        let archived =
+            internal_member.with_resource("status", Value::String("archived".to_string()));
+        assert!(!conditions_match(&conditions, &archived));
+    }
 }
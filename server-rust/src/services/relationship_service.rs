@@ -0,0 +1,568 @@
+//! Relationship-based (Zanzibar-style) authorization service
+//!
+//! Complements `AuthorizationService` (org-level roles) with per-resource
+//! relationships. Relations are resolved against the org's schema:
+//!
+//! - `this`: tuples written directly for the relation (a userset subject such
+//!   as `group:eng#member` grants the relation to every member of that userset)
+//! - `computed_userset`: another relation on the same object
+//! - `tuple_to_userset`: a relation on objects linked via a tupleset relation
+//!
+//! Without a schema every relation is treated as `this` only.
+//!
+//! Traversal is bounded by `MAX_TRAVERSAL_NODES` (check/list-objects) and
+//! `MAX_EXPAND_DEPTH` (expand) so pathological graphs cannot exhaust the server.
+
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{
+    ObjectRef, RelationTuple, RelationshipRepository, RelationshipSchema, SubjectRef, TupleFilter,
+    TupleKey, UsersetRewrite,
+};
+
+/// Maximum (object, relation) nodes visited by a single check or list-objects call
+const MAX_TRAVERSAL_NODES: usize = 2_000;
+
+/// Maximum nesting depth of an expand tree
+const MAX_EXPAND_DEPTH: usize = 8;
+
+/// Node of an expanded userset tree
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpandNode {
+    /// Object the relation is evaluated on (`type:id`)
+    pub object: String,
+    pub relation: String,
+    /// Subjects written directly for this relation (excluding usersets)
+    pub subjects: Vec<String>,
+    /// Nested usersets contributing to this relation
+    pub children: Vec<ExpandNode>,
+    /// True when expansion stopped early (depth limit or cycle)
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+/// Relationship authorization service
+pub struct RelationshipService {
+    repo: Arc<dyn RelationshipRepository>,
+}
+
+impl RelationshipService {
+    pub fn new(repo: Arc<dyn RelationshipRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Write tuples after validating them against the org schema (if any).
+    ///
+    /// Returns only newly created tuples; tuples that already existed are skipped.
+    pub async fn write_tuples(
+        &self,
+        org_id: Uuid,
+        keys: Vec<TupleKey>,
+        created_by: Option<Uuid>,
+    ) -> Result<Vec<RelationTuple>, AppError> {
+        if let Some(schema) = self.repo.get_schema(org_id).await? {
+            for key in &keys {
+                schema.validate_tuple(key)?;
+            }
+        }
+
+        let mut created = Vec::new();
+        for key in keys {
+            let tuple = RelationTuple::new(org_id, key, created_by);
+            let tuple_id = tuple.id;
+            let stored = self.repo.write(tuple).await?;
+            if stored.id == tuple_id {
+                created.push(stored);
+            }
+        }
+        Ok(created)
+    }
+
+    /// Delete tuples, returning those that existed
+    pub async fn delete_tuples(
+        &self,
+        org_id: Uuid,
+        keys: Vec<TupleKey>,
+    ) -> Result<Vec<RelationTuple>, AppError> {
+        let mut deleted = Vec::new();
+        for key in keys {
+            if let Some(tuple) = self.repo.delete(org_id, &key).await? {
+                deleted.push(tuple);
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Check whether `subject` has `relation` on `object`
+    pub async fn check(
+        &self,
+        org_id: Uuid,
+        object: &ObjectRef,
+        relation: &str,
+        subject: &SubjectRef,
+    ) -> Result<bool, AppError> {
+        let schema = self.repo.get_schema(org_id).await?;
+
+        let mut queue = VecDeque::from([(object.clone(), relation.to_string())]);
+        let mut visited = HashSet::new();
+
+        while let Some((object, relation)) = queue.pop_front() {
+            if !visited.insert((object.clone(), relation.clone())) {
+                continue;
+            }
+            if visited.len() > MAX_TRAVERSAL_NODES {
+                return Err(traversal_limit_error());
+            }
+
+            // A userset subject trivially has its own relation
+            if subject.relation.as_deref() == Some(relation.as_str()) && subject.object() == object
+            {
+                return Ok(true);
+            }
+
+            for rewrite in rewrites(schema.as_ref(), &object.object_type, &relation) {
+                match rewrite {
+                    UsersetRewrite::This => {
+                        let filter = TupleFilter::object_relation(&object, &relation);
+                        for tuple in self.repo.find(org_id, &filter).await? {
+                            if tuple.key.subject == *subject {
+                                return Ok(true);
+                            }
+                            if let Some(ref userset_relation) = tuple.key.subject.relation {
+                                queue.push_back((
+                                    tuple.key.subject.object(),
+                                    userset_relation.clone(),
+                                ));
+                            }
+                        }
+                    }
+                    UsersetRewrite::ComputedUserset { relation } => {
+                        queue.push_back((object.clone(), relation));
+                    }
+                    UsersetRewrite::TupleToUserset {
+                        tupleset,
+                        computed_relation,
+                    } => {
+                        let filter = TupleFilter::object_relation(&object, &tupleset);
+                        for tuple in self.repo.find(org_id, &filter).await? {
+                            queue
+                                .push_back((tuple.key.subject.object(), computed_relation.clone()));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Expand the userset tree for `relation` on `object`
+    pub async fn expand(
+        &self,
+        org_id: Uuid,
+        object: &ObjectRef,
+        relation: &str,
+    ) -> Result<ExpandNode, AppError> {
+        let schema = self.repo.get_schema(org_id).await?;
+        let mut path = Vec::new();
+        self.expand_node(
+            org_id,
+            schema.as_ref(),
+            object.clone(),
+            relation.to_string(),
+            &mut path,
+        )
+        .await
+    }
+
+    fn expand_node<'a>(
+        &'a self,
+        org_id: Uuid,
+        schema: Option<&'a RelationshipSchema>,
+        object: ObjectRef,
+        relation: String,
+        path: &'a mut Vec<(ObjectRef, String)>,
+    ) -> Pin<Box<dyn Future<Output = Result<ExpandNode, AppError>> + Send + 'a>> {
+        Box::pin(async move {
+            let mut node = ExpandNode {
+                object: object.to_string(),
+                relation: relation.clone(),
+                subjects: Vec::new(),
+                children: Vec::new(),
+                truncated: false,
+            };
+
+            let key = (object.clone(), relation.clone());
+            if path.len() >= MAX_EXPAND_DEPTH || path.contains(&key) {
+                node.truncated = true;
+                return Ok(node);
+            }
+            path.push(key);
+
+            for rewrite in rewrites(schema, &object.object_type, &relation) {
+                match rewrite {
+                    UsersetRewrite::This => {
+                        let filter = TupleFilter::object_relation(&object, &relation);
+                        for tuple in self.repo.find(org_id, &filter).await? {
+                            match tuple.key.subject.relation {
+                                Some(ref userset_relation) => {
+                                    let child = self
+                                        .expand_node(
+                                            org_id,
+                                            schema,
+                                            tuple.key.subject.object(),
+                                            userset_relation.clone(),
+                                            path,
+                                        )
+                                        .await?;
+                                    node.children.push(child);
+                                }
+                                None => node.subjects.push(tuple.key.subject.to_string()),
+                            }
+                        }
+                    }
+                    UsersetRewrite::ComputedUserset { relation } => {
+                        let child = self
+                            .expand_node(org_id, schema, object.clone(), relation, path)
+                            .await?;
+                        node.children.push(child);
+                    }
+                    UsersetRewrite::TupleToUserset {
+                        tupleset,
+                        computed_relation,
+                    } => {
+                        let filter = TupleFilter::object_relation(&object, &tupleset);
+                        for tuple in self.repo.find(org_id, &filter).await? {
+                            let child = self
+                                .expand_node(
+                                    org_id,
+                                    schema,
+                                    tuple.key.subject.object(),
+                                    computed_relation.clone(),
+                                    path,
+                                )
+                                .await?;
+                            node.children.push(child);
+                        }
+                    }
+                }
+            }
+
+            path.pop();
+            Ok(node)
+        })
+    }
+
+    /// List IDs of objects of `object_type` on which `subject` has `relation`.
+    ///
+    /// Walks the graph backwards from the subject: direct tuples, tuples whose
+    /// subject is a userset the subject belongs to, then computed and
+    /// tuple-to-userset rewrites that derive new relations from reached ones.
+    pub async fn list_objects(
+        &self,
+        org_id: Uuid,
+        object_type: &str,
+        target_relation: &str,
+        subject: &SubjectRef,
+    ) -> Result<Vec<String>, AppError> {
+        let schema = self.repo.get_schema(org_id).await?;
+
+        let mut reached: HashSet<(ObjectRef, String)> = HashSet::new();
+        let mut queue: VecDeque<(ObjectRef, String)> = VecDeque::new();
+        let mut results = BTreeSet::new();
+
+        // Tuples naming the subject directly
+        let direct = TupleFilter {
+            subject: Some(subject.clone()),
+            ..Default::default()
+        };
+        for tuple in self.repo.find(org_id, &direct).await? {
+            queue.push_back((tuple.key.object, tuple.key.relation));
+        }
+        if let Some(ref userset_relation) = subject.relation {
+            queue.push_back((subject.object(), userset_relation.clone()));
+        }
+
+        while let Some((object, relation)) = queue.pop_front() {
+            if !reached.insert((object.clone(), relation.clone())) {
+                continue;
+            }
+            if reached.len() > MAX_TRAVERSAL_NODES {
+                return Err(traversal_limit_error());
+            }
+            if object.object_type == object_type && relation == target_relation {
+                results.insert(object.object_id.clone());
+            }
+
+            // Membership in a userset grants whatever that userset was granted
+            let userset = TupleFilter {
+                subject: Some(SubjectRef {
+                    subject_type: object.object_type.clone(),
+                    subject_id: object.object_id.clone(),
+                    relation: Some(relation.clone()),
+                }),
+                ..Default::default()
+            };
+            for tuple in self.repo.find(org_id, &userset).await? {
+                queue.push_back((tuple.key.object, tuple.key.relation));
+            }
+
+            let Some(schema) = schema.as_ref() else {
+                continue;
+            };
+            for (namespace, definition) in &schema.namespaces {
+                for (derived, relation_def) in &definition.relations {
+                    for rewrite in &relation_def.union {
+                        match rewrite {
+                            UsersetRewrite::ComputedUserset { relation: source }
+                                if *namespace == object.object_type && *source == relation =>
+                            {
+                                queue.push_back((object.clone(), derived.clone()));
+                            }
+                            UsersetRewrite::TupleToUserset {
+                                tupleset,
+                                computed_relation,
+                            } if *computed_relation == relation => {
+                                let linked = TupleFilter {
+                                    object_type: Some(namespace.clone()),
+                                    relation: Some(tupleset.clone()),
+                                    subject: Some(SubjectRef {
+                                        subject_type: object.object_type.clone(),
+                                        subject_id: object.object_id.clone(),
+                                        relation: None,
+                                    }),
+                                    ..Default::default()
+                                };
+                                for tuple in self.repo.find(org_id, &linked).await? {
+                                    queue.push_back((tuple.key.object, derived.clone()));
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(results.into_iter().collect())
+    }
+}
+
+fn traversal_limit_error() -> AppError {
+    AppError::Validation("Relationship graph is too large to evaluate".into())
+}
+
+/// Effective rewrites for a relation (schema-less relations are direct only)
+fn rewrites(
+    schema: Option<&RelationshipSchema>,
+    object_type: &str,
+    relation: &str,
+) -> Vec<UsersetRewrite> {
+    match schema {
+        Some(schema) => schema
+            .relation(object_type, relation)
+            .map(|definition| definition.rewrites())
+            .unwrap_or_default(),
+        None => vec![UsersetRewrite::This],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{InMemoryRelationshipRepository, NamespaceDefinition};
+    use std::collections::BTreeMap;
+
+    fn key(object: &str, relation: &str, subject: &str) -> TupleKey {
+        TupleKey::new(
+            ObjectRef::parse(object).unwrap(),
+            relation,
+            SubjectRef::parse(subject).unwrap(),
+        )
+    }
+
+    async fn setup() -> (RelationshipService, Uuid) {
+        let repo = Arc::new(InMemoryRelationshipRepository::new());
+        let org_id = Uuid::new_v4();
+        let json = r#"{
+            "group": {"relations": {"member": {}}},
+            "folder": {"relations": {
+                "owner": {},
+                "viewer": {"union": [
+                    {"type": "this"},
+                    {"type": "computed_userset", "relation": "owner"}
+                ]}
+            }},
+            "document": {"relations": {
+                "parent": {},
+                "editor": {},
+                "viewer": {"union": [
+                    {"type": "this"},
+                    {"type": "computed_userset", "relation": "editor"},
+                    {"type": "tuple_to_userset", "tupleset": "parent", "computed_relation": "viewer"}
+                ]}
+            }}
+        }"#;
+        let namespaces: BTreeMap<String, NamespaceDefinition> = serde_json::from_str(json).unwrap();
+        repo.put_schema(RelationshipSchema::new(org_id, namespaces))
+            .await
+            .unwrap();
+
+        let service = RelationshipService::new(repo);
+        service
+            .write_tuples(
+                org_id,
+                vec![
+                    key("document:readme", "editor", "user:alice"),
+                    key("document:readme", "parent", "folder:docs"),
+                    key("document:plan", "parent", "folder:docs"),
+                    key("folder:docs", "viewer", "group:eng#member"),
+                    key("folder:docs", "owner", "user:carol"),
+                    key("group:eng", "member", "user:bob"),
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+        (service, org_id)
+    }
+
+    #[tokio::test]
+    async fn test_check_resolves_rewrites() {
+        let (service, org_id) = setup().await;
+        let readme = ObjectRef::parse("document:readme").unwrap();
+        let user = |id: &str| SubjectRef::parse(&format!("user:{}", id)).unwrap();
+
+        // Direct tuple
+        assert!(service
+            .check(org_id, &readme, "editor", &user("alice"))
+            .await
+            .unwrap());
+        // Computed userset: editors are viewers
+        assert!(service
+            .check(org_id, &readme, "viewer", &user("alice"))
+            .await
+            .unwrap());
+        // Tuple-to-userset via folder, then a group userset
+        assert!(service
+            .check(org_id, &readme, "viewer", &user("bob"))
+            .await
+            .unwrap());
+        // Folder owner -> folder viewer -> document viewer
+        assert!(service
+            .check(org_id, &readme, "viewer", &user("carol"))
+            .await
+            .unwrap());
+        // Viewer does not imply editor
+        assert!(!service
+            .check(org_id, &readme, "editor", &user("bob"))
+            .await
+            .unwrap());
+        assert!(!service
+            .check(org_id, &readme, "viewer", &user("mallory"))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_write_validates_against_schema() {
+        let (service, org_id) = setup().await;
+        let result = service
+            .write_tuples(
+                org_id,
+                vec![key("document:readme", "owner", "user:alice")],
+                None,
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        // Existing tuples are not reported as created again
+        let created = service
+            .write_tuples(
+                org_id,
+                vec![key("document:readme", "editor", "user:alice")],
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(created.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_objects() {
+        let (service, org_id) = setup().await;
+        let bob = SubjectRef::parse("user:bob").unwrap();
+        let alice = SubjectRef::parse("user:alice").unwrap();
+
+        let objects = service
+            .list_objects(org_id, "document", "viewer", &bob)
+            .await
+            .unwrap();
+        assert_eq!(objects, vec!["plan".to_string(), "readme".to_string()]);
+
+        let objects = service
+            .list_objects(org_id, "document", "viewer", &alice)
+            .await
+            .unwrap();
+        assert_eq!(objects, vec!["readme".to_string()]);
+
+        let objects = service
+            .list_objects(org_id, "document", "editor", &bob)
+            .await
+            .unwrap();
+        assert!(objects.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expand_tree() {
+        let (service, org_id) = setup().await;
+        let readme = ObjectRef::parse("document:readme").unwrap();
+
+        let tree = service.expand(org_id, &readme, "viewer").await.unwrap();
+        assert_eq!(tree.object, "document:readme");
+        assert!(tree.subjects.is_empty());
+        // editor (computed) + folder:docs#viewer (tuple-to-userset)
+        assert_eq!(tree.children.len(), 2);
+        assert_eq!(tree.children[0].subjects, vec!["user:alice".to_string()]);
+        let folder = &tree.children[1];
+        assert_eq!(folder.object, "folder:docs");
+        // group:eng#member userset + owner computed userset
+        assert_eq!(folder.children.len(), 2);
+        assert_eq!(folder.children[0].subjects, vec!["user:bob".to_string()]);
+        assert_eq!(folder.children[1].subjects, vec!["user:carol".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_cycles_terminate() {
+        let repo = Arc::new(InMemoryRelationshipRepository::new());
+        let org_id = Uuid::new_v4();
+        let service = RelationshipService::new(repo);
+        service
+            .write_tuples(
+                org_id,
+                vec![
+                    key("group:a", "member", "group:b#member"),
+                    key("group:b", "member", "group:a#member"),
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let a = ObjectRef::parse("group:a").unwrap();
+        let outsider = SubjectRef::parse("user:x").unwrap();
+        assert!(!service
+            .check(org_id, &a, "member", &outsider)
+            .await
+            .unwrap());
+
+        let tree = service.expand(org_id, &a, "member").await.unwrap();
+        assert!(tree.children[0].children[0].truncated);
+    }
+}
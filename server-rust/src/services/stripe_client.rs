@@ -0,0 +1,455 @@
+//! Minimal Stripe API client for card top-ups
+//!
+//! Only the calls the top-up flow needs: creating Checkout Sessions and
+//! PaymentIntents, plus parsing and verifying webhook events. Requests are
+//! form-encoded as the Stripe API expects and carry an `Idempotency-Key`
+//! so a retried request never creates a second payment.
+//!
+//! Point `STRIPE_API_BASE` at [stripe-mock](https://github.com/stripe/stripe-mock)
+//! (`http://localhost:12111`) to exercise the flow locally.
+
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::{Deserialize, Deserializer};
+use sha2::Sha256;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::config::StripeConfig;
+use crate::errors::AppError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Checkout Session returned by `POST /v1/checkout/sessions`
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeCheckoutSession {
+    pub id: String,
+    /// Hosted payment page to redirect the user to
+    #[serde(default)]
+    pub url: Option<String>,
+    /// `paid`, `unpaid` or `no_payment_required`
+    #[serde(default)]
+    pub payment_status: Option<String>,
+    #[serde(default, deserialize_with = "expandable_id")]
+    pub payment_intent: Option<String>,
+    #[serde(default, deserialize_with = "expandable_id")]
+    pub customer: Option<String>,
+    #[serde(default)]
+    pub amount_total: Option<i64>,
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+/// PaymentIntent returned by `POST /v1/payment_intents` and in events
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripePaymentIntent {
+    pub id: String,
+    /// Secret the client confirms the payment with
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    #[serde(default)]
+    pub amount: i64,
+    #[serde(default)]
+    pub amount_received: Option<i64>,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default, deserialize_with = "expandable_id")]
+    pub customer: Option<String>,
+}
+
+/// Charge object of `charge.refunded` events
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeCharge {
+    pub id: String,
+    #[serde(default, deserialize_with = "expandable_id")]
+    pub payment_intent: Option<String>,
+    /// Cumulative amount refunded so far
+    #[serde(default)]
+    pub amount_refunded: i64,
+}
+
+/// Dispute object of `charge.dispute.*` events
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeDispute {
+    pub id: String,
+    #[serde(default, deserialize_with = "expandable_id")]
+    pub payment_intent: Option<String>,
+    #[serde(default)]
+    pub amount: i64,
+    /// e.g. `needs_response`, `under_review`, `won`, `lost`
+    #[serde(default)]
+    pub status: String,
+}
+
+/// Webhook event envelope
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub data: StripeEventData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StripeEventData {
+    pub object: serde_json::Value,
+}
+
+impl StripeEvent {
+    /// Deserialize the event's object
+    pub fn object<T: serde::de::DeserializeOwned>(&self) -> Result<T, AppError> {
+        serde_json::from_value(self.data.object.clone()).map_err(|e| {
+            AppError::Validation(format!("Invalid {} event object: {}", self.event_type, e))
+        })
+    }
+}
+
+/// Error body of a failed Stripe request
+#[derive(Debug, Deserialize)]
+struct StripeErrorBody {
+    error: StripeErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeErrorDetail {
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// Accept either an ID string or an expanded object carrying an `id`
+fn expandable_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::String(id)) => Some(id),
+        Some(serde_json::Value::Object(object)) => object
+            .get("id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string),
+        _ => None,
+    })
+}
+
+/// Parameters shared by both payment flows
+#[derive(Debug, Clone)]
+pub struct StripePaymentParams<'a> {
+    /// Top-up ID, sent as metadata and used for the idempotency key
+    pub topup_id: uuid::Uuid,
+    pub amount_cents: i64,
+    pub currency: &'a str,
+    /// Existing Stripe customer of the paying user
+    pub customer: Option<&'a str>,
+    /// Line item name shown on the Checkout page
+    pub description: &'a str,
+}
+
+/// Stripe API client
+pub struct StripeClient {
+    http_client: Client,
+    api_base: String,
+    secret_key: String,
+}
+
+impl StripeClient {
+    /// Create a client from config (requires `STRIPE_SECRET_KEY`)
+    pub fn new(config: &StripeConfig) -> Result<Self, AppError> {
+        let secret_key = config
+            .secret_key
+            .clone()
+            .ok_or_else(|| AppError::Config("STRIPE_SECRET_KEY is not configured".into()))?;
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| {
+                AppError::Internal(anyhow::anyhow!("Failed to create HTTP client: {}", e))
+            })?;
+
+        Ok(Self {
+            http_client,
+            api_base: config.api_base.trim_end_matches('/').to_string(),
+            secret_key,
+        })
+    }
+
+    /// Create a Checkout Session in `payment` mode
+    pub async fn create_checkout_session(
+        &self,
+        params: &StripePaymentParams<'_>,
+        success_url: &str,
+        cancel_url: Option<&str>,
+    ) -> Result<StripeCheckoutSession, AppError> {
+        let topup_id = params.topup_id.to_string();
+        let mut form = vec![
+            ("mode", "payment".to_string()),
+            ("client_reference_id", topup_id.clone()),
+            ("success_url", success_url.to_string()),
+            ("line_items[0][quantity]", "1".to_string()),
+            (
+                "line_items[0][price_data][currency]",
+                params.currency.to_string(),
+            ),
+            (
+                "line_items[0][price_data][unit_amount]",
+                params.amount_cents.to_string(),
+            ),
+            (
+                "line_items[0][price_data][product_data][name]",
+                params.description.to_string(),
+            ),
+            ("metadata[topup_id]", topup_id.clone()),
+            ("payment_intent_data[metadata][topup_id]", topup_id),
+        ];
+        if let Some(cancel_url) = cancel_url {
+            form.push(("cancel_url", cancel_url.to_string()));
+        }
+        match params.customer {
+            Some(customer) => form.push(("customer", customer.to_string())),
+            None => form.push(("customer_creation", "always".to_string())),
+        }
+
+        self.post("/v1/checkout/sessions", params.topup_id, &form)
+            .await
+    }
+
+    /// Create a PaymentIntent the client confirms with its `client_secret`
+    pub async fn create_payment_intent(
+        &self,
+        params: &StripePaymentParams<'_>,
+    ) -> Result<StripePaymentIntent, AppError> {
+        let mut form = vec![
+            ("amount", params.amount_cents.to_string()),
+            ("currency", params.currency.to_string()),
+            ("description", params.description.to_string()),
+            ("metadata[topup_id]", params.topup_id.to_string()),
+            ("automatic_payment_methods[enabled]", "true".to_string()),
+        ];
+        if let Some(customer) = params.customer {
+            form.push(("customer", customer.to_string()));
+        }
+
+        self.post("/v1/payment_intents", params.topup_id, &form)
+            .await
+    }
+
+    async fn post<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        topup_id: uuid::Uuid,
+        form: &[(&str, String)],
+    ) -> Result<T, AppError> {
+        let response = self
+            .http_client
+            .post(format!("{}{}", self.api_base, path))
+            .bearer_auth(&self.secret_key)
+            .header("Idempotency-Key", format!("topup:{}", topup_id))
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| {
+                warn!(error = %e, path, "Stripe request failed");
+                AppError::ServiceUnavailable("Payment provider unavailable".into())
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<StripeErrorBody>(&body)
+                .ok()
+                .and_then(|b| b.error.message)
+                .unwrap_or_else(|| body.clone());
+            warn!(status = %status, path, message = %message, "Stripe API error");
+            return Err(if status.is_server_error() || status.as_u16() == 429 {
+                AppError::ServiceUnavailable("Payment provider unavailable".into())
+            } else {
+                AppError::Internal(anyhow::anyhow!(
+                    "Stripe API returned {}: {}",
+                    status,
+                    message
+                ))
+            });
+        }
+
+        response.json().await.map_err(|e| {
+            AppError::Internal(anyhow::anyhow!("Failed to parse Stripe response: {}", e))
+        })
+    }
+}
+
+/// Verify a `Stripe-Signature` header against the raw request body
+///
+/// The header carries a timestamp `t` and one or more `v1` signatures, each
+/// an HMAC-SHA256 of `"{t}.{payload}"` keyed with the endpoint's signing
+/// secret. Events older than `tolerance_secs` are rejected to stop replays.
+pub fn verify_stripe_signature(
+    secret: &str,
+    header: &str,
+    payload: &[u8],
+    now: i64,
+    tolerance_secs: u64,
+) -> bool {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let Some(timestamp) = timestamp else {
+        return false;
+    };
+    if now.abs_diff(timestamp) > tolerance_secs {
+        return false;
+    }
+
+    let mut mac = match HmacSha256::new_from_slice(secret.as_bytes()) {
+        Ok(m) => m,
+        Err(_) => return false,
+    };
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+    let expected = mac.finalize().into_bytes();
+
+    use subtle::ConstantTimeEq;
+    signatures.iter().any(|signature| {
+        hex::decode(signature)
+            .map(|provided| {
+                provided.len() == expected.len()
+                    && bool::from(AsRef::<[u8]>::as_ref(&expected).ct_eq(&provided))
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// Build a `Stripe-Signature` header for `payload` (tests and local tooling)
+#[cfg(test)]
+pub fn sign_stripe_payload(secret: &str, payload: &[u8], timestamp: i64) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload);
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "whsec_test";
+
+    #[test]
+    fn test_verify_stripe_signature() {
+        let payload = br#"{"id":"evt_1"}"#;
+        let header = sign_stripe_payload(SECRET, payload, 1_700_000_000);
+
+        assert!(verify_stripe_signature(
+            SECRET,
+            &header,
+            payload,
+            1_700_000_100,
+            300
+        ));
+        // Wrong secret, tampered body, stale timestamp, malformed header
+        assert!(!verify_stripe_signature(
+            "whsec_other",
+            &header,
+            payload,
+            1_700_000_100,
+            300
+        ));
+        assert!(!verify_stripe_signature(
+            SECRET,
+            &header,
+            br#"{"id":"evt_2"}"#,
+            1_700_000_100,
+            300
+        ));
+        assert!(!verify_stripe_signature(
+            SECRET,
+            &header,
+            payload,
+            1_700_000_301,
+            300
+        ));
+        assert!(!verify_stripe_signature(
+            SECRET,
+            "v1=abc",
+            payload,
+            1_700_000_000,
+            300
+        ));
+    }
+
+    #[test]
+    fn test_verify_accepts_any_v1_signature() {
+        let payload = b"{}";
+        let valid = sign_stripe_payload(SECRET, payload, 1_700_000_000);
+        let rolled = format!("t=1700000000,v1={},{}", "00".repeat(32), &valid[13..]);
+        assert!(verify_stripe_signature(
+            SECRET,
+            &rolled,
+            payload,
+            1_700_000_000,
+            300
+        ));
+    }
+
+    #[test]
+    fn test_event_object_accepts_expanded_ids() {
+        let event: StripeEvent = serde_json::from_value(serde_json::json!({
+            "id": "evt_1",
+            "type": "charge.refunded",
+            "data": {"object": {
+                "id": "ch_1",
+                "payment_intent": {"id": "pi_1", "object": "payment_intent"},
+                "amount": 1000,
+                "amount_refunded": 250
+            }}
+        }))
+        .unwrap();
+        let charge: StripeCharge = event.object().unwrap();
+        assert_eq!(charge.payment_intent.as_deref(), Some("pi_1"));
+        assert_eq!(charge.amount_refunded, 250);
+    }
+
+    /// Runs against stripe-mock when `STRIPE_MOCK_URL` is set
+    /// (e.g. `docker run -p 12111:12111 stripe/stripe-mock`)
+    #[tokio::test]
+    async fn test_create_payments_against_stripe_mock() {
+        let api_base = match std::env::var("STRIPE_MOCK_URL") {
+            Ok(u) => u,
+            Err(_) => return, // skip when not configured
+        };
+        let client = StripeClient::new(&StripeConfig {
+            enabled: true,
+            secret_key: Some("sk_test_123".into()),
+            api_base,
+            ..StripeConfig::default()
+        })
+        .unwrap();
+        let params = StripePaymentParams {
+            topup_id: uuid::Uuid::new_v4(),
+            amount_cents: 1_000,
+            currency: "usd",
+            customer: None,
+            description: "Credit top-up",
+        };
+
+        let session = client
+            .create_checkout_session(&params, "https://example.com/done", None)
+            .await
+            .unwrap();
+        assert!(session.id.starts_with("cs_"));
+
+        let intent = client.create_payment_intent(&params).await.unwrap();
+        assert!(intent.id.starts_with("pi_"));
+        assert!(intent.client_secret.is_some());
+    }
+}
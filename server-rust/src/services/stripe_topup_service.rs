@@ -0,0 +1,1117 @@
+//! Stripe card top-ups of credit balances
+//!
+//! A top-up starts as a pending record plus a Stripe Checkout Session or
+//! PaymentIntent. Credits are only added when Stripe's signed webhook
+//! reports the payment, as a deposit transaction with the idempotency key
+//! `stripe_topup:<topup_id>`, so redelivered or concurrent events credit the
+//! balance exactly once.
+//!
+//! Refunds and disputes post negative deposits (`stripe_refund`,
+//! `stripe_dispute`) that take the credits back through the ledger's deposit
+//! clearing account. Credits already spent cannot be taken back; the
+//! shortfall is recorded on the top-up as unrecovered. A dispute that is
+//! won reinstates what it took back.
+
+use chrono::Utc;
+use serde_json::Value;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::WebhookEvent;
+use crate::config::StripeConfig;
+use crate::errors::AppError;
+use crate::repositories::{
+    CreditRepository, CreditTransactionEntity, StripeTopupEntity, StripeTopupFlow, StripeTopupLock,
+    StripeTopupRepository, StripeTopupStatus, UserRepository, STRIPE_DISPUTE_REFERENCE_TYPE,
+    STRIPE_REFUND_REFERENCE_TYPE, STRIPE_TOPUP_REFERENCE_TYPE,
+};
+use crate::services::stripe_client::{
+    StripeCharge, StripeCheckoutSession, StripeClient, StripeDispute, StripeEvent,
+    StripePaymentIntent, StripePaymentParams,
+};
+use crate::services::WebhookService;
+
+/// Line item name shown on the Stripe Checkout page
+const TOPUP_DESCRIPTION: &str = "Credit top-up";
+
+/// Dispute statuses that leave the merchant with the funds
+const DISPUTE_RESOLVED_STATUSES: &[&str] = &["won", "warning_closed"];
+
+/// A started top-up and what the client needs to complete the payment
+#[derive(Debug, Clone)]
+pub struct StripeTopupCheckout {
+    pub topup: StripeTopupEntity,
+    /// Stripe-hosted Checkout page (checkout flow)
+    pub checkout_url: Option<String>,
+    /// PaymentIntent client secret (payment intent flow)
+    pub client_secret: Option<String>,
+}
+
+/// What a webhook event did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StripeEventOutcome {
+    /// The top-up was credited
+    Credited { topup_id: Uuid, amount: i64 },
+    /// Credits were taken back for a refund or dispute
+    Reversed {
+        topup_id: Uuid,
+        amount: i64,
+        unrecovered: i64,
+    },
+    /// A won dispute gave credits back
+    Reinstated { topup_id: Uuid, amount: i64 },
+    /// The payment failed or the Checkout Session expired
+    Failed { topup_id: Uuid },
+    /// Not a top-up event, or already handled
+    Ignored,
+}
+
+impl StripeEventOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Credited { .. } => "credited",
+            Self::Reversed { .. } => "reversed",
+            Self::Reinstated { .. } => "reinstated",
+            Self::Failed { .. } => "failed",
+            Self::Ignored => "ignored",
+        }
+    }
+}
+
+/// Stripe top-up service
+pub struct StripeTopupService {
+    topup_repo: Arc<dyn StripeTopupRepository>,
+    credit_repo: Arc<dyn CreditRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    config: StripeConfig,
+    webhooks: Option<Arc<WebhookService>>,
+}
+
+impl StripeTopupService {
+    pub fn new(
+        topup_repo: Arc<dyn StripeTopupRepository>,
+        credit_repo: Arc<dyn CreditRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        config: StripeConfig,
+    ) -> Self {
+        Self {
+            topup_repo,
+            credit_repo,
+            user_repo,
+            config,
+            webhooks: None,
+        }
+    }
+
+    /// Queue `deposit_completed` / `deposit_reversed` webhooks
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookService>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Idempotency key of the transaction crediting a top-up
+    pub fn credit_key(topup_id: Uuid) -> String {
+        format!("stripe_topup:{}", topup_id)
+    }
+
+    /// Start a top-up of `amount_cents` paid by `user_id`, crediting the
+    /// organization's balance when `org_id` is set
+    pub async fn create_topup(
+        &self,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        flow: StripeTopupFlow,
+        amount_cents: i64,
+    ) -> Result<StripeTopupCheckout, AppError> {
+        let config = &self.config;
+        if amount_cents < config.min_amount_cents as i64
+            || amount_cents > config.max_amount_cents as i64
+        {
+            return Err(AppError::Validation(format!(
+                "amountCents must be between {} and {}",
+                config.min_amount_cents, config.max_amount_cents
+            )));
+        }
+        let credit_amount = i64::try_from(config.credits_per_cent)
+            .ok()
+            .and_then(|rate| amount_cents.checked_mul(rate))
+            .ok_or_else(|| AppError::Validation("Top-up amount too large".into()))?;
+        if flow == StripeTopupFlow::Checkout && config.success_url.is_none() {
+            return Err(AppError::Config(
+                "STRIPE_SUCCESS_URL is required for Checkout top-ups".into(),
+            ));
+        }
+
+        let client = StripeClient::new(config)?;
+        let customer = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))?
+            .stripe_customer_id;
+
+        let mut topup = self
+            .topup_repo
+            .create(StripeTopupEntity::new(
+                user_id,
+                org_id,
+                flow,
+                amount_cents,
+                &config.currency,
+                credit_amount,
+                &config.credit_currency,
+            ))
+            .await?;
+
+        let currency = topup.currency.clone();
+        let params = StripePaymentParams {
+            topup_id: topup.id,
+            amount_cents,
+            currency: &currency,
+            customer: customer.as_deref(),
+            description: TOPUP_DESCRIPTION,
+        };
+        let created = match flow {
+            StripeTopupFlow::Checkout => client
+                .create_checkout_session(
+                    &params,
+                    config.success_url.as_deref().unwrap_or_default(),
+                    config.cancel_url.as_deref(),
+                )
+                .await
+                .map(|session| {
+                    topup.checkout_session_id = Some(session.id);
+                    topup.payment_intent_id = session.payment_intent;
+                    (session.url, None)
+                }),
+            StripeTopupFlow::PaymentIntent => {
+                client.create_payment_intent(&params).await.map(|intent| {
+                    topup.payment_intent_id = Some(intent.id);
+                    (None, intent.client_secret)
+                })
+            }
+        };
+        let (checkout_url, client_secret) = match created {
+            Ok(created) => created,
+            Err(e) => {
+                topup.status = StripeTopupStatus::Failed;
+                if let Err(update_err) = self.topup_repo.update(topup).await {
+                    tracing::warn!(error = %update_err, "Failed to mark Stripe top-up failed");
+                }
+                return Err(e);
+            }
+        };
+        let topup = self.topup_repo.update(topup).await?;
+
+        Ok(StripeTopupCheckout {
+            topup,
+            checkout_url,
+            client_secret,
+        })
+    }
+
+    /// Apply a verified webhook event
+    ///
+    /// Safe to call any number of times for the same event.
+    pub async fn handle_event(&self, event: &StripeEvent) -> Result<StripeEventOutcome, AppError> {
+        match event.event_type.as_str() {
+            "checkout.session.completed" | "checkout.session.async_payment_succeeded" => {
+                let session: StripeCheckoutSession = event.object()?;
+                let Some(mut topup) = self
+                    .find_topup(
+                        self.topup_repo
+                            .find_by_checkout_session(&session.id)
+                            .await?,
+                        &event.data.object,
+                    )
+                    .await?
+                else {
+                    return Ok(StripeEventOutcome::Ignored);
+                };
+                if session.payment_status.as_deref() != Some("paid") {
+                    // Delayed payment methods report success in a later event
+                    if topup.payment_intent_id.is_none() && session.payment_intent.is_some() {
+                        topup.payment_intent_id = session.payment_intent;
+                        self.topup_repo.update(topup).await?;
+                    }
+                    return Ok(StripeEventOutcome::Ignored);
+                }
+                self.credit(
+                    topup,
+                    session.payment_intent,
+                    session.customer,
+                    session.amount_total,
+                    session.currency.as_deref(),
+                )
+                .await
+            }
+            "payment_intent.succeeded" => {
+                let intent: StripePaymentIntent = event.object()?;
+                let Some(topup) = self
+                    .find_topup(
+                        self.topup_repo.find_by_payment_intent(&intent.id).await?,
+                        &event.data.object,
+                    )
+                    .await?
+                else {
+                    return Ok(StripeEventOutcome::Ignored);
+                };
+                let paid = intent.amount_received.unwrap_or(intent.amount);
+                self.credit(
+                    topup,
+                    Some(intent.id),
+                    intent.customer,
+                    Some(paid),
+                    intent.currency.as_deref(),
+                )
+                .await
+            }
+            "checkout.session.expired" | "checkout.session.async_payment_failed" => {
+                let session: StripeCheckoutSession = event.object()?;
+                let topup = self
+                    .topup_repo
+                    .find_by_checkout_session(&session.id)
+                    .await?;
+                self.fail(topup).await
+            }
+            "payment_intent.canceled" => {
+                let intent: StripePaymentIntent = event.object()?;
+                let topup = self.topup_repo.find_by_payment_intent(&intent.id).await?;
+                self.fail(topup).await
+            }
+            "charge.refunded" => {
+                let charge: StripeCharge = event.object()?;
+                self.refund(charge).await
+            }
+            "charge.dispute.created" => {
+                let dispute: StripeDispute = event.object()?;
+                self.open_dispute(dispute).await
+            }
+            "charge.dispute.updated" | "charge.dispute.closed" => {
+                let dispute: StripeDispute = event.object()?;
+                self.update_dispute(dispute).await
+            }
+            _ => Ok(StripeEventOutcome::Ignored),
+        }
+    }
+
+    /// Fall back to the `metadata.topup_id` set on every Stripe object we create
+    async fn find_topup(
+        &self,
+        found: Option<StripeTopupEntity>,
+        object: &Value,
+    ) -> Result<Option<StripeTopupEntity>, AppError> {
+        if found.is_some() {
+            return Ok(found);
+        }
+        let topup_id = object
+            .get("metadata")
+            .and_then(|m| m.get("topup_id"))
+            .and_then(|id| id.as_str())
+            .and_then(|id| id.parse::<Uuid>().ok());
+        match topup_id {
+            Some(id) => self.topup_repo.find_by_id(id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_payment_intent(
+        &self,
+        payment_intent_id: Option<&str>,
+    ) -> Result<Option<StripeTopupEntity>, AppError> {
+        match payment_intent_id {
+            Some(id) => self.topup_repo.find_by_payment_intent(id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Find a payment's top-up and lock it for refund or dispute handling
+    ///
+    /// The top-up is read again once the lock is held, so the totals reflect
+    /// any event processed while waiting.
+    async fn lock_by_payment_intent(
+        &self,
+        payment_intent_id: Option<&str>,
+    ) -> Result<Option<(StripeTopupLock, StripeTopupEntity)>, AppError> {
+        let Some(topup) = self.find_by_payment_intent(payment_intent_id).await? else {
+            return Ok(None);
+        };
+        let lock = self.topup_repo.lock(topup.id).await?;
+        let topup = self.topup_repo.find_by_id(topup.id).await?;
+        Ok(topup.map(|topup| (lock, topup)))
+    }
+
+    async fn credit(
+        &self,
+        mut topup: StripeTopupEntity,
+        payment_intent_id: Option<String>,
+        customer: Option<String>,
+        paid_cents: Option<i64>,
+        paid_currency: Option<&str>,
+    ) -> Result<StripeEventOutcome, AppError> {
+        if topup.status == StripeTopupStatus::Succeeded {
+            return Ok(StripeEventOutcome::Ignored);
+        }
+        let currency_matches =
+            paid_currency.map_or(true, |c| c.eq_ignore_ascii_case(&topup.currency));
+        if paid_cents != Some(topup.amount_cents) || !currency_matches {
+            tracing::error!(
+                topup_id = %topup.id,
+                expected_cents = topup.amount_cents,
+                paid_cents = ?paid_cents,
+                paid_currency = ?paid_currency,
+                "Stripe payment does not match top-up; not crediting"
+            );
+            return Ok(StripeEventOutcome::Ignored);
+        }
+
+        let key = Self::credit_key(topup.id);
+        let transaction_id = match self.find_transaction(&topup, &key).await? {
+            Some(tx) => tx.id,
+            None => {
+                let mut tx = CreditTransactionEntity::new_deposit_with_reference(
+                    topup.user_id,
+                    topup.credit_amount,
+                    &topup.credit_currency,
+                    key.clone(),
+                    STRIPE_TOPUP_REFERENCE_TYPE,
+                    topup.id,
+                    Some(serde_json::json!({
+                        "stripe_payment_intent": payment_intent_id,
+                        "amount_cents": topup.amount_cents,
+                        "currency": topup.currency,
+                    })),
+                );
+                if let Some(org_id) = topup.org_id {
+                    tx = tx.for_org(org_id);
+                }
+                let tx_id = tx.id;
+                match self
+                    .credit_repo
+                    .add_credit(
+                        topup.user_id,
+                        topup.credit_amount,
+                        &topup.credit_currency,
+                        tx,
+                    )
+                    .await
+                {
+                    Ok(_) => tx_id,
+                    // A concurrent delivery of the same event got there first
+                    Err(e) => match self.find_transaction(&topup, &key).await? {
+                        Some(tx) => tx.id,
+                        None => return Err(e),
+                    },
+                }
+            }
+        };
+
+        topup.status = StripeTopupStatus::Succeeded;
+        topup.transaction_id = Some(transaction_id);
+        topup.completed_at = Some(Utc::now());
+        if topup.payment_intent_id.is_none() {
+            topup.payment_intent_id = payment_intent_id;
+        }
+        let topup = self.topup_repo.update(topup).await?;
+
+        if let Some(customer) = customer {
+            self.link_customer(topup.user_id, &customer).await;
+        }
+
+        tracing::info!(
+            topup_id = %topup.id,
+            user_id = %topup.user_id,
+            org_id = ?topup.org_id,
+            amount = topup.credit_amount,
+            currency = %topup.credit_currency,
+            "Stripe top-up credited"
+        );
+        self.dispatch(
+            WebhookEvent::DepositCompleted,
+            serde_json::json!({
+                "user_id": topup.user_id,
+                "org_id": topup.org_id,
+                "topup_id": topup.id,
+                "deposit_type": "stripe",
+                "amount_cents": topup.amount_cents,
+                "currency": topup.currency,
+                "credit_amount": topup.credit_amount,
+                "credit_currency": topup.credit_currency,
+            }),
+        )
+        .await;
+
+        Ok(StripeEventOutcome::Credited {
+            topup_id: topup.id,
+            amount: topup.credit_amount,
+        })
+    }
+
+    async fn fail(&self, topup: Option<StripeTopupEntity>) -> Result<StripeEventOutcome, AppError> {
+        let Some(mut topup) = topup.filter(|t| t.status == StripeTopupStatus::Pending) else {
+            return Ok(StripeEventOutcome::Ignored);
+        };
+        topup.status = StripeTopupStatus::Failed;
+        topup.completed_at = Some(Utc::now());
+        let topup = self.topup_repo.update(topup).await?;
+        Ok(StripeEventOutcome::Failed { topup_id: topup.id })
+    }
+
+    async fn refund(&self, charge: StripeCharge) -> Result<StripeEventOutcome, AppError> {
+        let Some((_lock, mut topup)) = self
+            .lock_by_payment_intent(charge.payment_intent.as_deref())
+            .await?
+            .filter(|(_, t)| t.status == StripeTopupStatus::Succeeded)
+        else {
+            return Ok(StripeEventOutcome::Ignored);
+        };
+        // `amount_refunded` is cumulative, so older or repeated events are no-ops
+        let refunded_cents = charge.amount_refunded.min(topup.amount_cents);
+        if refunded_cents <= topup.refunded_cents {
+            return Ok(StripeEventOutcome::Ignored);
+        }
+
+        let amount = (topup.credits_for(refunded_cents) - topup.credits_for(topup.refunded_cents))
+            .min(remaining(&topup));
+        let key = format!("stripe_refund:{}:{}", charge.id, refunded_cents);
+        let (recovered, unrecovered) = self
+            .reverse(
+                &topup,
+                amount,
+                key,
+                STRIPE_REFUND_REFERENCE_TYPE,
+                serde_json::json!({
+                    "stripe_charge": charge.id,
+                    "refunded_cents": refunded_cents - topup.refunded_cents,
+                }),
+            )
+            .await?;
+
+        topup.refunded_cents = refunded_cents;
+        topup.reversed_amount += recovered;
+        topup.unrecovered_amount += unrecovered;
+        let topup = self.topup_repo.update(topup).await?;
+
+        self.reversed(&topup, "refund", recovered, unrecovered)
+            .await
+    }
+
+    async fn open_dispute(&self, dispute: StripeDispute) -> Result<StripeEventOutcome, AppError> {
+        let Some((lock, mut topup)) = self
+            .lock_by_payment_intent(dispute.payment_intent.as_deref())
+            .await?
+            .filter(|(_, t)| t.status == StripeTopupStatus::Succeeded)
+        else {
+            return Ok(StripeEventOutcome::Ignored);
+        };
+        if topup.dispute_id.is_some() {
+            drop(lock);
+            return self.update_dispute(dispute).await;
+        }
+
+        let disputed_cents = dispute.amount.min(topup.amount_cents);
+        let amount = topup.credits_for(disputed_cents).min(remaining(&topup));
+        let (recovered, unrecovered) = self
+            .reverse(
+                &topup,
+                amount,
+                dispute_key(&dispute.id),
+                STRIPE_DISPUTE_REFERENCE_TYPE,
+                serde_json::json!({
+                    "stripe_dispute": dispute.id,
+                    "disputed_cents": disputed_cents,
+                }),
+            )
+            .await?;
+
+        topup.dispute_id = Some(dispute.id);
+        topup.dispute_status = Some(dispute.status);
+        topup.disputed_cents = disputed_cents;
+        topup.disputed_amount = amount;
+        topup.reversed_amount += recovered;
+        topup.unrecovered_amount += unrecovered;
+        let topup = self.topup_repo.update(topup).await?;
+
+        self.reversed(&topup, "dispute", recovered, unrecovered)
+            .await
+    }
+
+    /// Record a dispute's status, reinstating the credits if it was won
+    async fn update_dispute(&self, dispute: StripeDispute) -> Result<StripeEventOutcome, AppError> {
+        let Some((_lock, mut topup)) = self
+            .lock_by_payment_intent(dispute.payment_intent.as_deref())
+            .await?
+            .filter(|(_, t)| t.dispute_id.as_deref() == Some(dispute.id.as_str()))
+        else {
+            return Ok(StripeEventOutcome::Ignored);
+        };
+        let was_resolved = topup
+            .dispute_status
+            .as_deref()
+            .is_some_and(|s| DISPUTE_RESOLVED_STATUSES.contains(&s));
+        let resolved = DISPUTE_RESOLVED_STATUSES.contains(&dispute.status.as_str());
+        topup.dispute_status = Some(dispute.status);
+        if was_resolved || !resolved {
+            self.topup_repo.update(topup).await?;
+            return Ok(StripeEventOutcome::Ignored);
+        }
+
+        // Give back only what the dispute actually took
+        let recovered = self
+            .find_transaction(&topup, &dispute_key(&dispute.id))
+            .await?
+            .map_or(0, |tx| -tx.amount);
+        let unrecovered = topup.disputed_amount - recovered;
+        if recovered > 0 {
+            let key = format!("stripe_dispute_won:{}", dispute.id);
+            if self.find_transaction(&topup, &key).await?.is_none() {
+                let mut tx = CreditTransactionEntity::new_deposit_with_reference(
+                    topup.user_id,
+                    recovered,
+                    &topup.credit_currency,
+                    key,
+                    STRIPE_DISPUTE_REFERENCE_TYPE,
+                    topup.id,
+                    Some(serde_json::json!({
+                        "stripe_dispute": dispute.id,
+                        "dispute_status": topup.dispute_status,
+                    })),
+                );
+                if let Some(org_id) = topup.org_id {
+                    tx = tx.for_org(org_id);
+                }
+                self.credit_repo
+                    .add_credit(topup.user_id, recovered, &topup.credit_currency, tx)
+                    .await?;
+            }
+        }
+        topup.reversed_amount -= recovered;
+        topup.unrecovered_amount = (topup.unrecovered_amount - unrecovered).max(0);
+        let topup = self.topup_repo.update(topup).await?;
+
+        tracing::info!(
+            topup_id = %topup.id,
+            dispute_id = %dispute.id,
+            amount = recovered,
+            "Stripe dispute resolved; credits reinstated"
+        );
+        Ok(StripeEventOutcome::Reinstated {
+            topup_id: topup.id,
+            amount: recovered,
+        })
+    }
+
+    /// Take back up to `amount` credits of a top-up, limited to what is
+    /// still available. Returns `(recovered, unrecovered)`.
+    async fn reverse(
+        &self,
+        topup: &StripeTopupEntity,
+        amount: i64,
+        key: String,
+        reference_type: &str,
+        metadata: Value,
+    ) -> Result<(i64, i64), AppError> {
+        if amount <= 0 {
+            return Ok((0, 0));
+        }
+        if let Some(tx) = self.find_transaction(topup, &key).await? {
+            let recovered = -tx.amount;
+            return Ok((recovered, amount - recovered));
+        }
+
+        let available = match topup.org_id {
+            Some(org_id) => self
+                .credit_repo
+                .get_or_create_org_balance(org_id, &topup.credit_currency)
+                .await?
+                .available(),
+            None => self
+                .credit_repo
+                .get_or_create_balance(topup.user_id, &topup.credit_currency)
+                .await?
+                .available(),
+        };
+        let recovered = amount.min(available.max(0));
+        if recovered > 0 {
+            let mut tx = CreditTransactionEntity::new_deposit_with_reference(
+                topup.user_id,
+                -recovered,
+                &topup.credit_currency,
+                key.clone(),
+                reference_type,
+                topup.id,
+                Some(metadata),
+            );
+            if let Some(org_id) = topup.org_id {
+                tx = tx.for_org(org_id);
+            }
+            if let Err(e) = self
+                .credit_repo
+                .deduct_credit(topup.user_id, recovered, &topup.credit_currency, tx)
+                .await
+            {
+                // Either a concurrent delivery already reversed it, or the
+                // balance changed underneath us; Stripe retries the latter
+                return match self.find_transaction(topup, &key).await? {
+                    Some(tx) => Ok((-tx.amount, amount + tx.amount)),
+                    None => Err(e),
+                };
+            }
+        }
+
+        let unrecovered = amount - recovered;
+        if unrecovered > 0 {
+            tracing::warn!(
+                topup_id = %topup.id,
+                user_id = %topup.user_id,
+                org_id = ?topup.org_id,
+                unrecovered,
+                reference_type,
+                "Credits of a reversed Stripe payment were already spent"
+            );
+        }
+        Ok((recovered, unrecovered))
+    }
+
+    async fn reversed(
+        &self,
+        topup: &StripeTopupEntity,
+        reason: &str,
+        recovered: i64,
+        unrecovered: i64,
+    ) -> Result<StripeEventOutcome, AppError> {
+        tracing::info!(
+            topup_id = %topup.id,
+            reason,
+            amount = recovered,
+            unrecovered,
+            "Stripe top-up reversed"
+        );
+        self.dispatch(
+            WebhookEvent::DepositReversed,
+            serde_json::json!({
+                "user_id": topup.user_id,
+                "org_id": topup.org_id,
+                "topup_id": topup.id,
+                "deposit_type": "stripe",
+                "reason": reason,
+                "credit_amount": recovered,
+                "unrecovered_amount": unrecovered,
+                "credit_currency": topup.credit_currency,
+            }),
+        )
+        .await;
+
+        Ok(StripeEventOutcome::Reversed {
+            topup_id: topup.id,
+            amount: recovered,
+            unrecovered,
+        })
+    }
+
+    async fn find_transaction(
+        &self,
+        topup: &StripeTopupEntity,
+        key: &str,
+    ) -> Result<Option<CreditTransactionEntity>, AppError> {
+        self.credit_repo
+            .find_transaction_by_idempotency_key(topup.user_id, key)
+            .await
+    }
+
+    /// Remember the payer's Stripe customer for future top-ups
+    async fn link_customer(&self, user_id: Uuid, customer: &str) {
+        match self.user_repo.find_by_id(user_id).await {
+            Ok(Some(user)) if user.stripe_customer_id.is_none() => {
+                if let Err(e) = self
+                    .user_repo
+                    .set_stripe_customer_id(user_id, customer)
+                    .await
+                {
+                    tracing::warn!(error = %e, user_id = %user_id, "Failed to link Stripe customer");
+                }
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, user_id = %user_id, "Failed to load user"),
+        }
+    }
+
+    async fn dispatch(&self, event: WebhookEvent, data: Value) {
+        if let Some(webhooks) = &self.webhooks {
+            webhooks.dispatch_or_warn(event, data).await;
+        }
+    }
+}
+
+/// Credits of a top-up not yet taken back
+fn remaining(topup: &StripeTopupEntity) -> i64 {
+    (topup.credit_amount - topup.reversed_amount - topup.unrecovered_amount).max(0)
+}
+
+fn dispute_key(dispute_id: &str) -> String {
+    format!("stripe_dispute:{}", dispute_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        InMemoryCreditRepository, InMemoryStripeTopupRepository, InMemoryUserRepository, UserEntity,
+    };
+
+    const CREDITS: i64 = 10_000_000;
+
+    struct Fixture {
+        service: StripeTopupService,
+        credit_repo: Arc<InMemoryCreditRepository>,
+        topup_repo: Arc<InMemoryStripeTopupRepository>,
+        user_repo: Arc<InMemoryUserRepository>,
+        topup: StripeTopupEntity,
+    }
+
+    async fn fixture(org_id: Option<Uuid>) -> Fixture {
+        let credit_repo = Arc::new(InMemoryCreditRepository::new());
+        let topup_repo = Arc::new(InMemoryStripeTopupRepository::new());
+        let user_repo = Arc::new(InMemoryUserRepository::new());
+        let user = user_repo
+            .create(UserEntity::new_email_user(
+                "payer@example.com".into(),
+                "hash".into(),
+                None,
+            ))
+            .await
+            .unwrap();
+
+        let mut topup = StripeTopupEntity::new(
+            user.id,
+            org_id,
+            StripeTopupFlow::Checkout,
+            1_000,
+            "usd",
+            CREDITS,
+            "USD",
+        );
+        topup.checkout_session_id = Some("cs_test_1".into());
+        let topup = topup_repo.create(topup).await.unwrap();
+
+        Fixture {
+            service: StripeTopupService::new(
+                topup_repo.clone(),
+                credit_repo.clone(),
+                user_repo.clone(),
+                StripeConfig::default(),
+            ),
+            credit_repo,
+            topup_repo,
+            user_repo,
+            topup,
+        }
+    }
+
+    fn event(event_type: &str, object: Value) -> StripeEvent {
+        serde_json::from_value(serde_json::json!({
+            "id": format!("evt_{}", Uuid::new_v4().simple()),
+            "type": event_type,
+            "data": {"object": object},
+        }))
+        .unwrap()
+    }
+
+    fn session_completed() -> StripeEvent {
+        event(
+            "checkout.session.completed",
+            serde_json::json!({
+                "id": "cs_test_1",
+                "payment_status": "paid",
+                "payment_intent": "pi_1",
+                "customer": "cus_1",
+                "amount_total": 1_000,
+                "currency": "usd",
+            }),
+        )
+    }
+
+    fn refunded(amount_refunded: i64) -> StripeEvent {
+        event(
+            "charge.refunded",
+            serde_json::json!({
+                "id": "ch_1",
+                "payment_intent": "pi_1",
+                "amount": 1_000,
+                "amount_refunded": amount_refunded,
+            }),
+        )
+    }
+
+    fn dispute(event_type: &str, status: &str) -> StripeEvent {
+        event(
+            event_type,
+            serde_json::json!({
+                "id": "dp_1",
+                "payment_intent": "pi_1",
+                "amount": 1_000,
+                "status": status,
+            }),
+        )
+    }
+
+    async fn balance(f: &Fixture) -> i64 {
+        f.credit_repo
+            .get_balance(f.topup.user_id, "USD")
+            .await
+            .unwrap()
+    }
+
+    async fn spend(f: &Fixture, amount: i64) {
+        let tx = CreditTransactionEntity::new_spend(f.topup.user_id, amount, "USD", None);
+        f.credit_repo
+            .deduct_credit(f.topup.user_id, amount, "USD", tx)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_checkout_completed_credits_once_and_links_customer() {
+        let f = fixture(None).await;
+
+        let outcome = f.service.handle_event(&session_completed()).await.unwrap();
+        assert_eq!(
+            outcome,
+            StripeEventOutcome::Credited {
+                topup_id: f.topup.id,
+                amount: CREDITS
+            }
+        );
+        // Redelivery and the matching payment_intent event change nothing
+        let outcome = f.service.handle_event(&session_completed()).await.unwrap();
+        assert_eq!(outcome, StripeEventOutcome::Ignored);
+        let intent = event(
+            "payment_intent.succeeded",
+            serde_json::json!({"id": "pi_1", "amount": 1_000, "currency": "usd"}),
+        );
+        let outcome = f.service.handle_event(&intent).await.unwrap();
+        assert_eq!(outcome, StripeEventOutcome::Ignored);
+        assert_eq!(balance(&f).await, CREDITS);
+
+        let topup = f.topup_repo.find_by_id(f.topup.id).await.unwrap().unwrap();
+        assert_eq!(topup.status, StripeTopupStatus::Succeeded);
+        assert_eq!(topup.payment_intent_id.as_deref(), Some("pi_1"));
+        let tx = f
+            .credit_repo
+            .find_transaction_by_idempotency_key(
+                f.topup.user_id,
+                &StripeTopupService::credit_key(f.topup.id),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(topup.transaction_id, Some(tx.id));
+        assert_eq!(
+            tx.reference_type.as_deref(),
+            Some(STRIPE_TOPUP_REFERENCE_TYPE)
+        );
+
+        let user = f
+            .user_repo
+            .find_by_id(f.topup.user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(user.stripe_customer_id.as_deref(), Some("cus_1"));
+    }
+
+    #[tokio::test]
+    async fn test_payment_intent_found_by_metadata_credits_org() {
+        let org_id = Uuid::new_v4();
+        let f = fixture(Some(org_id)).await;
+
+        let intent = event(
+            "payment_intent.succeeded",
+            serde_json::json!({
+                "id": "pi_9",
+                "amount": 1_000,
+                "amount_received": 1_000,
+                "currency": "usd",
+                "metadata": {"topup_id": f.topup.id.to_string()},
+            }),
+        );
+        let outcome = f.service.handle_event(&intent).await.unwrap();
+        assert!(matches!(outcome, StripeEventOutcome::Credited { .. }));
+
+        let org_balance = f
+            .credit_repo
+            .get_or_create_org_balance(org_id, "USD")
+            .await
+            .unwrap();
+        assert_eq!(org_balance.balance, CREDITS);
+        assert_eq!(balance(&f).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_unpaid_or_mismatched_payment_is_not_credited() {
+        let f = fixture(None).await;
+
+        let unpaid = event(
+            "checkout.session.completed",
+            serde_json::json!({
+                "id": "cs_test_1",
+                "payment_status": "unpaid",
+                "payment_intent": "pi_1",
+                "amount_total": 1_000,
+            }),
+        );
+        let outcome = f.service.handle_event(&unpaid).await.unwrap();
+        assert_eq!(outcome, StripeEventOutcome::Ignored);
+        let short = event(
+            "checkout.session.async_payment_succeeded",
+            serde_json::json!({
+                "id": "cs_test_1",
+                "payment_status": "paid",
+                "amount_total": 999,
+                "currency": "usd",
+            }),
+        );
+        let outcome = f.service.handle_event(&short).await.unwrap();
+        assert_eq!(outcome, StripeEventOutcome::Ignored);
+        assert_eq!(balance(&f).await, 0);
+
+        // The payment intent was remembered from the unpaid session
+        let topup = f.topup_repo.find_by_id(f.topup.id).await.unwrap().unwrap();
+        assert_eq!(topup.payment_intent_id.as_deref(), Some("pi_1"));
+        assert_eq!(topup.status, StripeTopupStatus::Pending);
+
+        let expired = event(
+            "checkout.session.expired",
+            serde_json::json!({"id": "cs_test_1"}),
+        );
+        let outcome = f.service.handle_event(&expired).await.unwrap();
+        assert_eq!(
+            outcome,
+            StripeEventOutcome::Failed {
+                topup_id: f.topup.id
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_refunds_reverse_proportionally_once() {
+        let f = fixture(None).await;
+        f.service.handle_event(&session_completed()).await.unwrap();
+
+        let outcome = f.service.handle_event(&refunded(250)).await.unwrap();
+        assert_eq!(
+            outcome,
+            StripeEventOutcome::Reversed {
+                topup_id: f.topup.id,
+                amount: CREDITS / 4,
+                unrecovered: 0
+            }
+        );
+        let outcome = f.service.handle_event(&refunded(250)).await.unwrap();
+        assert_eq!(outcome, StripeEventOutcome::Ignored);
+        assert_eq!(balance(&f).await, CREDITS * 3 / 4);
+
+        // Credits already spent cannot be taken back
+        spend(&f, CREDITS / 2).await;
+        let outcome = f.service.handle_event(&refunded(1_000)).await.unwrap();
+        assert_eq!(
+            outcome,
+            StripeEventOutcome::Reversed {
+                topup_id: f.topup.id,
+                amount: CREDITS / 4,
+                unrecovered: CREDITS / 2
+            }
+        );
+        assert_eq!(balance(&f).await, 0);
+
+        let topup = f.topup_repo.find_by_id(f.topup.id).await.unwrap().unwrap();
+        assert_eq!(topup.refunded_cents, 1_000);
+        assert_eq!(topup.reversed_amount, CREDITS / 2);
+        assert_eq!(topup.unrecovered_amount, CREDITS / 2);
+    }
+
+    #[tokio::test]
+    async fn test_won_dispute_reinstates_what_it_took() {
+        let f = fixture(None).await;
+        f.service.handle_event(&session_completed()).await.unwrap();
+        spend(&f, CREDITS / 5).await;
+
+        let outcome = f
+            .service
+            .handle_event(&dispute("charge.dispute.created", "needs_response"))
+            .await
+            .unwrap();
+        assert_eq!(
+            outcome,
+            StripeEventOutcome::Reversed {
+                topup_id: f.topup.id,
+                amount: CREDITS * 4 / 5,
+                unrecovered: CREDITS / 5
+            }
+        );
+        assert_eq!(balance(&f).await, 0);
+        let outcome = f
+            .service
+            .handle_event(&dispute("charge.dispute.created", "needs_response"))
+            .await
+            .unwrap();
+        assert_eq!(outcome, StripeEventOutcome::Ignored);
+
+        let won = dispute("charge.dispute.closed", "won");
+        let outcome = f.service.handle_event(&won).await.unwrap();
+        assert_eq!(
+            outcome,
+            StripeEventOutcome::Reinstated {
+                topup_id: f.topup.id,
+                amount: CREDITS * 4 / 5
+            }
+        );
+        let outcome = f.service.handle_event(&won).await.unwrap();
+        assert_eq!(outcome, StripeEventOutcome::Ignored);
+        assert_eq!(balance(&f).await, CREDITS * 4 / 5);
+
+        let topup = f.topup_repo.find_by_id(f.topup.id).await.unwrap().unwrap();
+        assert_eq!(topup.dispute_status.as_deref(), Some("won"));
+        assert_eq!(topup.reversed_amount, 0);
+        assert_eq!(topup.unrecovered_amount, 0);
+    }
+
+    #[tokio::test]
+    async fn test_lost_dispute_keeps_credits_reversed() {
+        let f = fixture(None).await;
+        f.service.handle_event(&session_completed()).await.unwrap();
+        f.service
+            .handle_event(&dispute("charge.dispute.created", "needs_response"))
+            .await
+            .unwrap();
+
+        let outcome = f
+            .service
+            .handle_event(&dispute("charge.dispute.closed", "lost"))
+            .await
+            .unwrap();
+        assert_eq!(outcome, StripeEventOutcome::Ignored);
+        assert_eq!(balance(&f).await, 0);
+        let topup = f.topup_repo.find_by_id(f.topup.id).await.unwrap().unwrap();
+        assert_eq!(topup.dispute_status.as_deref(), Some("lost"));
+        assert_eq!(topup.reversed_amount, CREDITS);
+    }
+
+    #[tokio::test]
+    async fn test_create_topup_validates_amount() {
+        let f = fixture(None).await;
+        let err = f
+            .service
+            .create_topup(f.topup.user_id, None, StripeTopupFlow::PaymentIntent, 99)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        // Checkout needs a success URL
+        let err = f
+            .service
+            .create_topup(f.topup.user_id, None, StripeTopupFlow::Checkout, 1_000)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Config(_)));
+    }
+}
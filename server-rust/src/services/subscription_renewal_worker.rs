@@ -0,0 +1,114 @@
+//! Background worker for recurring subscription charges
+//!
+//! Periodically processes subscriptions whose period (or trial) has ended or
+//! whose failed renewal is due for a retry: charges the next period, moves
+//! subscriptions with rejected charges into dunning, and cancels those whose
+//! grace period has passed. See [`SubscriptionService`] for the rules.
+//!
+//! Default poll interval: 5 minutes
+
+use chrono::Utc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info};
+
+use crate::services::SubscriptionService;
+
+/// Default poll interval for subscription renewals (5 minutes)
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 300;
+
+/// Default number of subscriptions processed per run
+const DEFAULT_BATCH_LIMIT: u32 = 500;
+
+/// Configuration for the subscription renewal worker
+#[derive(Debug, Clone)]
+pub struct SubscriptionRenewalConfig {
+    /// How often to look for due subscriptions (seconds)
+    pub poll_interval_secs: u64,
+    /// Maximum subscriptions processed per run
+    pub batch_limit: u32,
+}
+
+impl Default for SubscriptionRenewalConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+            batch_limit: DEFAULT_BATCH_LIMIT,
+        }
+    }
+}
+
+/// Background worker that renews subscriptions
+pub struct SubscriptionRenewalWorker {
+    service: SubscriptionService,
+    config: SubscriptionRenewalConfig,
+}
+
+impl SubscriptionRenewalWorker {
+    /// Create a new subscription renewal worker
+    pub fn new(service: SubscriptionService, config: SubscriptionRenewalConfig) -> Self {
+        Self { service, config }
+    }
+
+    /// Start the worker as a background task with graceful shutdown support
+    pub fn start(self, cancel_token: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
+
+            info!(
+                poll_interval_secs = self.config.poll_interval_secs,
+                "Subscription renewal worker started"
+            );
+
+            loop {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        info!("Subscription renewal worker shutting down");
+                        break;
+                    }
+                    _ = tokio::time::sleep(poll_interval) => {
+                        if let Err(e) = self.renew_subscriptions().await {
+                            error!(error = %e, "Failed to renew subscriptions");
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Process due subscriptions
+    async fn renew_subscriptions(&self) -> Result<(), crate::errors::AppError> {
+        let summary = self
+            .service
+            .renew_due(Utc::now(), self.config.batch_limit)
+            .await?;
+
+        if summary != Default::default() {
+            info!(
+                renewed = summary.renewed,
+                past_due = summary.past_due,
+                canceled = summary.canceled,
+                errors = summary.errors,
+                charged_lamports = summary.charged_lamports,
+                "Processed subscription renewals"
+            );
+        } else {
+            debug!("No subscriptions due");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = SubscriptionRenewalConfig::default();
+        assert_eq!(config.poll_interval_secs, 300);
+        assert_eq!(config.batch_limit, 500);
+    }
+}
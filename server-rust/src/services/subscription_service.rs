@@ -0,0 +1,979 @@
+//! Recurring credit subscriptions
+//!
+//! Users and organizations subscribe to plans from the catalog. The first
+//! period is charged on subscribing (or when a free trial ends); the
+//! subscription renewal worker charges each following period through
+//! [`CreditService`] with the idempotency key
+//! `subscription:<subscription_id>:<period_start_unix>`, so a period is never
+//! charged twice even if the worker stops mid-run.
+//!
+//! A rejected charge (insufficient credits, member spend caps, spending
+//! budget hard limits) puts the
+//! subscription past due. It keeps its entitlements while the charge is
+//! retried daily and is canceled once the plan's grace period has passed.
+//! Each failure and the final cancellation queue a dunning notification in
+//! the outbox and a webhook.
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::callback::WebhookEvent;
+use crate::errors::AppError;
+use crate::repositories::{
+    CreditBudgetRepository, CreditHoldRepository, CreditRepository, MembershipRepository,
+    SubscriptionEntity, SubscriptionPlanEntity, SubscriptionRepository, SubscriptionStatus,
+    SUBSCRIPTION_REFERENCE_TYPE,
+};
+use crate::services::{
+    BudgetAlert, CommsService, CreditService, OrgSpender, Permission, WebhookService,
+};
+
+/// `/authorize` permissions with this prefix check a subscription entitlement
+pub const ENTITLEMENT_PERMISSION_PREFIX: &str = "entitlement:";
+
+/// Delay between renewal attempts of a past-due subscription (hours)
+const DUNNING_RETRY_HOURS: i64 = 24;
+
+/// Cancel reason when the subscriber cancels
+pub const CANCEL_REASON_USER: &str = "canceled_by_user";
+
+/// Cancel reason when the grace period ends without a successful charge
+pub const CANCEL_REASON_PAYMENT_FAILED: &str = "payment_failed";
+
+/// Outcome of one renewal run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubscriptionRenewalSummary {
+    pub renewed: u64,
+    pub past_due: u64,
+    pub canceled: u64,
+    /// Subscriptions left for the next run after an unexpected error
+    pub errors: u64,
+    pub charged_lamports: i64,
+}
+
+/// What happened to one due subscription
+enum RenewalOutcome {
+    Renewed(i64),
+    PastDue,
+    Canceled,
+}
+
+/// Subscription lifecycle service
+pub struct SubscriptionService {
+    subscription_repo: Arc<dyn SubscriptionRepository>,
+    credit_repo: Arc<dyn CreditRepository>,
+    hold_repo: Arc<dyn CreditHoldRepository>,
+    budget_repo: Option<Arc<dyn CreditBudgetRepository>>,
+    membership_repo: Arc<dyn MembershipRepository>,
+    comms: Option<CommsService>,
+    webhooks: Option<Arc<WebhookService>>,
+}
+
+impl SubscriptionService {
+    pub fn new(
+        subscription_repo: Arc<dyn SubscriptionRepository>,
+        credit_repo: Arc<dyn CreditRepository>,
+        hold_repo: Arc<dyn CreditHoldRepository>,
+        membership_repo: Arc<dyn MembershipRepository>,
+    ) -> Self {
+        Self {
+            subscription_repo,
+            credit_repo,
+            hold_repo,
+            budget_repo: None,
+            membership_repo,
+            comms: None,
+            webhooks: None,
+        }
+    }
+
+    /// Enforce spending budgets on subscription charges
+    pub fn with_budgets(mut self, budget_repo: Arc<dyn CreditBudgetRepository>) -> Self {
+        self.budget_repo = Some(budget_repo);
+        self
+    }
+
+    /// Queue dunning notifications for failed renewals
+    pub fn with_comms(mut self, comms: CommsService) -> Self {
+        self.comms = Some(comms);
+        self
+    }
+
+    /// Queue `subscription_*` webhooks on lifecycle changes
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookService>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Idempotency key of the charge for the period starting at `period_start`
+    pub fn charge_key(subscription_id: Uuid, period_start: DateTime<Utc>) -> String {
+        format!(
+            "subscription:{}:{}",
+            subscription_id,
+            period_start.timestamp()
+        )
+    }
+
+    async fn find_plan(&self, plan_id: Uuid) -> Result<SubscriptionPlanEntity, AppError> {
+        self.subscription_repo
+            .find_plan(plan_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Subscription plan not found".into()))
+    }
+
+    /// Subscribe a user, or an organization when `org_id` is set
+    ///
+    /// First-time subscribers to a plan with a trial start trialing;
+    /// otherwise the first period is charged immediately. The subscription
+    /// row is created before the charge, so concurrent subscribes are
+    /// stopped by the one-open-subscription constraint before any debit,
+    /// and removed again if the charge is rejected.
+    pub async fn subscribe(
+        &self,
+        plan_id: Uuid,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        now: DateTime<Utc>,
+    ) -> Result<SubscriptionEntity, AppError> {
+        let plan = self.find_plan(plan_id).await?;
+        if !plan.active {
+            return Err(AppError::Validation(
+                "This plan is not available for new subscriptions".into(),
+            ));
+        }
+
+        let existing = match org_id {
+            Some(org_id) => self.subscription_repo.list_for_org(org_id).await?,
+            None => self.subscription_repo.list_for_user(user_id).await?,
+        };
+        if existing
+            .iter()
+            .any(|s| s.plan_id == plan.id && s.status != SubscriptionStatus::Canceled)
+        {
+            return Err(AppError::Validation(
+                "Already subscribed to this plan".into(),
+            ));
+        }
+
+        let trial = plan.trial_days > 0 && existing.iter().all(|s| s.plan_id != plan.id);
+        let subscription = if trial {
+            SubscriptionEntity::new(
+                plan.id,
+                user_id,
+                org_id,
+                SubscriptionStatus::Trialing,
+                now,
+                now + Duration::days(plan.trial_days as i64),
+            )
+        } else {
+            SubscriptionEntity::new(
+                plan.id,
+                user_id,
+                org_id,
+                SubscriptionStatus::Active,
+                now,
+                plan.period_end(now),
+            )
+        };
+
+        let mut created = self
+            .subscription_repo
+            .create_subscription(subscription)
+            .await?;
+        if !trial {
+            match self.charge(&plan, &created, now).await {
+                Ok(None) => {}
+                Ok(Some(transaction_id)) => {
+                    created.last_transaction_id = Some(transaction_id);
+                    created = self.save(created).await?;
+                }
+                Err(e) => {
+                    if let Err(delete_err) =
+                        self.subscription_repo.delete_subscription(created.id).await
+                    {
+                        tracing::error!(
+                            subscription_id = %created.id,
+                            error = %delete_err,
+                            "Failed to remove subscription after rejected charge"
+                        );
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        self.dispatch(WebhookEvent::SubscriptionCreated, &created, &plan)
+            .await;
+        Ok(created)
+    }
+
+    /// Cancel a subscription, immediately or when its current period ends
+    ///
+    /// Past-due subscriptions have no paid period left and are always
+    /// canceled immediately. Charged periods are not refunded.
+    pub async fn cancel(
+        &self,
+        mut subscription: SubscriptionEntity,
+        at_period_end: bool,
+        now: DateTime<Utc>,
+    ) -> Result<SubscriptionEntity, AppError> {
+        if subscription.status == SubscriptionStatus::Canceled {
+            return Err(AppError::Validation(
+                "Subscription is already canceled".into(),
+            ));
+        }
+
+        if at_period_end && subscription.status != SubscriptionStatus::PastDue {
+            subscription.cancel_at_period_end = true;
+            return self.save(subscription).await;
+        }
+
+        subscription.status = SubscriptionStatus::Canceled;
+        subscription.canceled_at = Some(now);
+        subscription.cancel_reason = Some(CANCEL_REASON_USER.to_string());
+        let canceled = self.save(subscription).await?;
+        if let Some(plan) = self.subscription_repo.find_plan(canceled.plan_id).await? {
+            self.dispatch(WebhookEvent::SubscriptionCanceled, &canceled, &plan)
+                .await;
+        }
+        Ok(canceled)
+    }
+
+    /// Renew, retry or cancel every subscription due at `now`
+    pub async fn renew_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<SubscriptionRenewalSummary, AppError> {
+        let due = self.subscription_repo.list_due(now, limit).await?;
+        let mut summary = SubscriptionRenewalSummary::default();
+
+        for subscription in due {
+            let subscription_id = subscription.id;
+            match self.renew(subscription, now).await {
+                Ok(RenewalOutcome::Renewed(amount)) => {
+                    summary.renewed += 1;
+                    summary.charged_lamports = summary.charged_lamports.saturating_add(amount);
+                }
+                Ok(RenewalOutcome::PastDue) => summary.past_due += 1,
+                Ok(RenewalOutcome::Canceled) => summary.canceled += 1,
+                // Retried with the same idempotency key on the next run
+                Err(e) => {
+                    tracing::warn!(
+                        subscription_id = %subscription_id,
+                        error = %e,
+                        "Subscription renewal failed"
+                    );
+                    summary.errors += 1;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn renew(
+        &self,
+        mut subscription: SubscriptionEntity,
+        now: DateTime<Utc>,
+    ) -> Result<RenewalOutcome, AppError> {
+        let plan = self.find_plan(subscription.plan_id).await?;
+
+        if subscription.cancel_at_period_end && !self.renewal_charged(&plan, &subscription).await? {
+            subscription.status = SubscriptionStatus::Canceled;
+            subscription.canceled_at = Some(now);
+            subscription.cancel_reason = Some(CANCEL_REASON_USER.to_string());
+            let canceled = self.save(subscription).await?;
+            self.dispatch(WebhookEvent::SubscriptionCanceled, &canceled, &plan)
+                .await;
+            return Ok(RenewalOutcome::Canceled);
+        }
+
+        // A past-due subscription is still paying for the period it missed
+        let period_start = subscription.current_period_end;
+        match self.charge(&plan, &subscription, period_start).await {
+            Ok(transaction_id) => {
+                subscription.status = SubscriptionStatus::Active;
+                subscription.current_period_start = period_start;
+                subscription.current_period_end = plan.period_end(period_start);
+                subscription.next_attempt_at = subscription.current_period_end;
+                subscription.past_due_since = None;
+                subscription.dunning_attempts = 0;
+                if transaction_id.is_some() {
+                    subscription.last_transaction_id = transaction_id;
+                }
+                let renewed = self.save(subscription).await?;
+                self.dispatch(WebhookEvent::SubscriptionRenewed, &renewed, &plan)
+                    .await;
+                Ok(RenewalOutcome::Renewed(plan.price_lamports))
+            }
+            Err(AppError::Validation(reason))
+            | Err(AppError::Forbidden(reason))
+            | Err(AppError::BudgetExceeded(reason)) => {
+                let past_due_since = *subscription.past_due_since.get_or_insert(period_start);
+                let cancel_at = past_due_since + Duration::days(plan.grace_days as i64);
+                subscription.dunning_attempts += 1;
+
+                tracing::warn!(
+                    subscription_id = %subscription.id,
+                    plan = %plan.name,
+                    attempt = subscription.dunning_attempts,
+                    error = %reason,
+                    "Subscription renewal charge rejected"
+                );
+
+                if now >= cancel_at {
+                    subscription.status = SubscriptionStatus::Canceled;
+                    subscription.canceled_at = Some(now);
+                    subscription.cancel_reason = Some(CANCEL_REASON_PAYMENT_FAILED.to_string());
+                    let canceled = self.save(subscription).await?;
+                    if let Some(comms) = &self.comms {
+                        if let Err(e) = comms.notify_subscription_canceled(&canceled, &plan).await {
+                            tracing::warn!(error = %e, "Failed to queue subscription cancellation notice");
+                        }
+                    }
+                    self.dispatch(WebhookEvent::SubscriptionCanceled, &canceled, &plan)
+                        .await;
+                    return Ok(RenewalOutcome::Canceled);
+                }
+
+                subscription.status = SubscriptionStatus::PastDue;
+                subscription.next_attempt_at =
+                    (now + Duration::hours(DUNNING_RETRY_HOURS)).min(cancel_at);
+                let past_due = self.save(subscription).await?;
+                if let Some(comms) = &self.comms {
+                    if let Err(e) = comms
+                        .notify_subscription_past_due(&past_due, &plan, &reason, cancel_at)
+                        .await
+                    {
+                        tracing::warn!(error = %e, "Failed to queue subscription dunning notice");
+                    }
+                }
+                self.dispatch(WebhookEvent::SubscriptionPastDue, &past_due, &plan)
+                    .await;
+                Ok(RenewalOutcome::PastDue)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Save a subscription, failing if it changed since it was read
+    async fn save(&self, subscription: SubscriptionEntity) -> Result<SubscriptionEntity, AppError> {
+        self.subscription_repo
+            .update_subscription(subscription)
+            .await?
+            .ok_or_else(|| {
+                AppError::Validation("Subscription was modified concurrently; try again".into())
+            })
+    }
+
+    /// Whether the period following the current one was already charged
+    ///
+    /// A cancellation saved while a renewal was charging makes that renewal's
+    /// save fail; the next run then honors the paid period instead of
+    /// canceling right away.
+    async fn renewal_charged(
+        &self,
+        plan: &SubscriptionPlanEntity,
+        subscription: &SubscriptionEntity,
+    ) -> Result<bool, AppError> {
+        if plan.price_lamports == 0 {
+            return Ok(false);
+        }
+        let idempotency_key = Self::charge_key(subscription.id, subscription.current_period_end);
+        Ok(self
+            .credit_repo
+            .find_transaction_by_idempotency_key(subscription.user_id, &idempotency_key)
+            .await?
+            .is_some())
+    }
+
+    /// Charge the plan price for the period starting at `period_start`
+    ///
+    /// Returns the transaction, or `None` for a free plan.
+    async fn charge(
+        &self,
+        plan: &SubscriptionPlanEntity,
+        subscription: &SubscriptionEntity,
+        period_start: DateTime<Utc>,
+    ) -> Result<Option<Uuid>, AppError> {
+        if plan.price_lamports == 0 {
+            return Ok(None);
+        }
+
+        let idempotency_key = Self::charge_key(subscription.id, period_start);
+        if let Some(existing) = self
+            .credit_repo
+            .find_transaction_by_idempotency_key(subscription.user_id, &idempotency_key)
+            .await?
+        {
+            // Charged by an earlier run that stopped before saving the renewal
+            return Ok(Some(existing.id));
+        }
+
+        let mut credit_service =
+            CreditService::new(self.credit_repo.clone(), self.hold_repo.clone());
+        if let Some(budget_repo) = &self.budget_repo {
+            credit_service = credit_service.with_budgets(budget_repo.clone());
+        }
+        let metadata = serde_json::json!({
+            "plan": plan.name,
+            "periodStart": period_start,
+            "periodEnd": plan.period_end(period_start),
+        });
+        let result = match subscription.org_id {
+            Some(org_id) => {
+                let membership = self
+                    .membership_repo
+                    .find_by_user_and_org(subscription.user_id, org_id)
+                    .await?
+                    .ok_or_else(|| {
+                        AppError::Forbidden(
+                            "Subscriber is no longer a member of the organization".into(),
+                        )
+                    })?;
+                let spender = OrgSpender {
+                    org_id,
+                    user_id: subscription.user_id,
+                    role_can_spend: Permission::CreditsSpend.is_allowed_for(membership.role),
+                };
+                credit_service
+                    .spend_from_org(
+                        spender,
+                        plan.price_lamports,
+                        &plan.currency,
+                        idempotency_key,
+                        SUBSCRIPTION_REFERENCE_TYPE,
+                        subscription.id,
+                        Some(metadata),
+                    )
+                    .await?
+            }
+            None => {
+                credit_service
+                    .spend(
+                        subscription.user_id,
+                        plan.price_lamports,
+                        &plan.currency,
+                        idempotency_key,
+                        SUBSCRIPTION_REFERENCE_TYPE,
+                        subscription.id,
+                        Some(metadata),
+                    )
+                    .await?
+            }
+        };
+        self.notify_budget_alerts(&result.budget_alerts).await;
+
+        Ok(Some(result.transaction_id))
+    }
+
+    /// Queue a notification for each budget whose soft limit a charge crossed
+    async fn notify_budget_alerts(&self, alerts: &[BudgetAlert]) {
+        let Some(comms) = &self.comms else {
+            return;
+        };
+        for alert in alerts {
+            if let Err(e) = comms.notify_credit_budget_threshold(alert).await {
+                tracing::warn!(
+                    error = %e,
+                    budget_id = %alert.budget.id,
+                    "Failed to queue budget threshold notification"
+                );
+            }
+        }
+    }
+
+    /// Entitlements a user currently holds through their own subscriptions
+    /// and, if they are a member, the organization's
+    pub async fn entitlements_for(
+        &self,
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<String>, AppError> {
+        let mut subscriptions = self.subscription_repo.list_for_user(user_id).await?;
+        if let Some(org_id) = org_id {
+            if self
+                .membership_repo
+                .find_by_user_and_org(user_id, org_id)
+                .await?
+                .is_some()
+            {
+                subscriptions.extend(self.subscription_repo.list_for_org(org_id).await?);
+            }
+        }
+
+        let mut entitlements = Vec::new();
+        for subscription in subscriptions
+            .iter()
+            .filter(|s| s.status != SubscriptionStatus::Canceled)
+        {
+            let Some(plan) = self
+                .subscription_repo
+                .find_plan(subscription.plan_id)
+                .await?
+            else {
+                continue;
+            };
+            if subscription.is_entitled(plan.grace_days, now) {
+                entitlements.extend(plan.entitlements.iter().cloned());
+            }
+        }
+        entitlements.sort();
+        entitlements.dedup();
+        Ok(entitlements)
+    }
+
+    async fn dispatch(
+        &self,
+        event: WebhookEvent,
+        subscription: &SubscriptionEntity,
+        plan: &SubscriptionPlanEntity,
+    ) {
+        if let Some(webhooks) = &self.webhooks {
+            webhooks
+                .dispatch_or_warn(event, webhook_data(subscription, plan))
+                .await;
+        }
+    }
+}
+
+fn webhook_data(subscription: &SubscriptionEntity, plan: &SubscriptionPlanEntity) -> Value {
+    serde_json::json!({
+        "subscription_id": subscription.id,
+        "plan": plan.name,
+        "user_id": subscription.user_id,
+        "org_id": subscription.org_id,
+        "status": subscription.status.as_str(),
+        "current_period_end": subscription.current_period_end,
+        "dunning_attempts": subscription.dunning_attempts,
+        "cancel_reason": subscription.cancel_reason,
+        "transaction_id": subscription.last_transaction_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        CreditBudgetEntity, CreditBudgetPeriod, CreditBudgetScope, InMemoryMembershipRepository,
+        InMemoryOutboxRepository, InMemorySubscriptionRepository, MembershipEntity, OrgRole,
+        OutboxEventType, OutboxRepository, PlanInterval,
+    };
+    use crate::services::credit_service::test_support::{fund, CreditFixture};
+    use crate::utils::TokenCipher;
+
+    struct Fixture {
+        service: SubscriptionService,
+        credits: CreditService,
+        budget_repo: Arc<dyn CreditBudgetRepository>,
+        subscription_repo: Arc<InMemorySubscriptionRepository>,
+        membership_repo: Arc<InMemoryMembershipRepository>,
+        outbox_repo: Arc<InMemoryOutboxRepository>,
+        plan: SubscriptionPlanEntity,
+    }
+
+    async fn fixture(trial_days: i32, grace_days: i32) -> Fixture {
+        let subscription_repo = Arc::new(InMemorySubscriptionRepository::new());
+        let CreditFixture {
+            credit_repo,
+            hold_repo,
+            budget_repo,
+            membership_repo,
+            credits,
+        } = CreditFixture::new();
+        let outbox_repo = Arc::new(InMemoryOutboxRepository::new());
+
+        let mut plan = SubscriptionPlanEntity::new(
+            "pro_monthly".into(),
+            "Pro".into(),
+            1_000,
+            "SOL".into(),
+            PlanInterval::Month,
+        );
+        plan.trial_days = trial_days;
+        plan.grace_days = grace_days;
+        plan.entitlements = vec!["pro".into()];
+        let plan = subscription_repo.create_plan(plan).await.unwrap();
+
+        let comms = CommsService::new(
+            outbox_repo.clone(),
+            "https://example.com".to_string(),
+            TokenCipher::new("test-secret"),
+        );
+        Fixture {
+            service: SubscriptionService::new(
+                subscription_repo.clone(),
+                credit_repo,
+                hold_repo,
+                membership_repo.clone(),
+            )
+            .with_budgets(budget_repo.clone())
+            .with_comms(comms),
+            credits,
+            budget_repo,
+            subscription_repo,
+            membership_repo,
+            outbox_repo,
+            plan,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_charges_first_period() {
+        let f = fixture(0, 0).await;
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        // Declined charges create no subscription
+        assert!(matches!(
+            f.service.subscribe(f.plan.id, user_id, None, now).await,
+            Err(AppError::Validation(_))
+        ));
+        assert!(f
+            .subscription_repo
+            .list_for_user(user_id)
+            .await
+            .unwrap()
+            .is_empty());
+
+        fund(&f.credits, user_id, 1_500).await;
+        let subscription = f
+            .service
+            .subscribe(f.plan.id, user_id, None, now)
+            .await
+            .unwrap();
+        assert_eq!(subscription.status, SubscriptionStatus::Active);
+        assert!(subscription.last_transaction_id.is_some());
+        assert_eq!(subscription.current_period_end, f.plan.period_end(now));
+        let balance = f.credits.get_balance(user_id, "SOL").await.unwrap();
+        assert_eq!(balance.balance_lamports, 500);
+
+        assert!(f
+            .service
+            .subscribe(f.plan.id, user_id, None, now)
+            .await
+            .is_err());
+        assert_eq!(
+            f.service
+                .entitlements_for(user_id, None, now)
+                .await
+                .unwrap(),
+            ["pro"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_subscribes_charge_once() {
+        let f = fixture(0, 0).await;
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+        fund(&f.credits, user_id, 2_500).await;
+
+        let (first, second) = tokio::join!(
+            f.service.subscribe(f.plan.id, user_id, None, now),
+            f.service.subscribe(f.plan.id, user_id, None, now),
+        );
+        assert_eq!(
+            [first.is_ok(), second.is_ok()]
+                .iter()
+                .filter(|ok| **ok)
+                .count(),
+            1
+        );
+        let balance = f.credits.get_balance(user_id, "SOL").await.unwrap();
+        assert_eq!(balance.balance_lamports, 1_500);
+        assert_eq!(
+            f.subscription_repo
+                .list_for_user(user_id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_trial_converts_and_renews_once_per_period() {
+        let f = fixture(7, 0).await;
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+        fund(&f.credits, user_id, 5_000).await;
+
+        let subscription = f
+            .service
+            .subscribe(f.plan.id, user_id, None, now)
+            .await
+            .unwrap();
+        assert_eq!(subscription.status, SubscriptionStatus::Trialing);
+        let trial_end = subscription.trial_end.unwrap();
+
+        // Nothing is due during the trial
+        let summary = f.service.renew_due(now, 100).await.unwrap();
+        assert_eq!(summary, SubscriptionRenewalSummary::default());
+
+        let summary = f.service.renew_due(trial_end, 100).await.unwrap();
+        assert_eq!(summary.renewed, 1);
+        assert_eq!(summary.charged_lamports, 1_000);
+        let summary = f.service.renew_due(trial_end, 100).await.unwrap();
+        assert_eq!(summary.renewed, 0);
+
+        let renewed = f
+            .subscription_repo
+            .find_subscription(subscription.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(renewed.status, SubscriptionStatus::Active);
+        assert_eq!(renewed.current_period_start, trial_end);
+        assert_eq!(renewed.current_period_end, f.plan.period_end(trial_end));
+        let balance = f.credits.get_balance(user_id, "SOL").await.unwrap();
+        assert_eq!(balance.balance_lamports, 4_000);
+
+        // A canceled trial is not offered again
+        f.service.cancel(renewed, false, now).await.unwrap();
+        let resubscribed = f
+            .service
+            .subscribe(f.plan.id, user_id, None, now)
+            .await
+            .unwrap();
+        assert_eq!(resubscribed.status, SubscriptionStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_failed_renewals_dun_then_cancel() {
+        let f = fixture(7, 3).await;
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        let subscription = f
+            .service
+            .subscribe(f.plan.id, user_id, None, now)
+            .await
+            .unwrap();
+        let due = subscription.next_attempt_at;
+
+        let summary = f.service.renew_due(due, 100).await.unwrap();
+        assert_eq!(summary.past_due, 1);
+        let past_due = f
+            .subscription_repo
+            .find_subscription(subscription.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(past_due.status, SubscriptionStatus::PastDue);
+        assert_eq!(past_due.dunning_attempts, 1);
+        assert_eq!(past_due.next_attempt_at, due + Duration::days(1));
+        // Entitlements are kept during the grace period
+        assert_eq!(
+            f.service
+                .entitlements_for(user_id, None, due + Duration::days(1))
+                .await
+                .unwrap(),
+            ["pro"]
+        );
+
+        f.service
+            .renew_due(due + Duration::days(1), 100)
+            .await
+            .unwrap();
+        f.service
+            .renew_due(due + Duration::days(2), 100)
+            .await
+            .unwrap();
+        let summary = f
+            .service
+            .renew_due(due + Duration::days(3), 100)
+            .await
+            .unwrap();
+        assert_eq!(summary.canceled, 1);
+
+        let canceled = f
+            .subscription_repo
+            .find_subscription(subscription.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(canceled.status, SubscriptionStatus::Canceled);
+        assert_eq!(
+            canceled.cancel_reason.as_deref(),
+            Some(CANCEL_REASON_PAYMENT_FAILED)
+        );
+        assert!(f
+            .service
+            .entitlements_for(user_id, None, due + Duration::days(3))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let events = f.outbox_repo.fetch_pending(100, 60).await.unwrap();
+        let count = |event_type| events.iter().filter(|e| e.event_type == event_type).count();
+        assert_eq!(count(OutboxEventType::CreditSubscriptionPastDue), 3);
+        assert_eq!(count(OutboxEventType::CreditSubscriptionCanceled), 1);
+    }
+
+    #[tokio::test]
+    async fn test_renewal_over_budget_hard_limit_goes_past_due() {
+        let f = fixture(0, 3).await;
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+        fund(&f.credits, user_id, 5_000).await;
+        f.budget_repo
+            .upsert(CreditBudgetEntity {
+                id: Uuid::new_v4(),
+                scope: CreditBudgetScope::User,
+                user_id: Some(user_id),
+                org_id: None,
+                api_key_id: None,
+                currency: "SOL".into(),
+                period: CreditBudgetPeriod::Monthly,
+                soft_limit: None,
+                hard_limit: Some(1_500),
+                created_by: None,
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+
+        let subscription = f
+            .service
+            .subscribe(f.plan.id, user_id, None, now)
+            .await
+            .unwrap();
+        let summary = f
+            .service
+            .renew_due(subscription.next_attempt_at, 100)
+            .await
+            .unwrap();
+        assert_eq!(summary.past_due, 1);
+        assert_eq!(summary.errors, 0);
+        let past_due = f
+            .subscription_repo
+            .find_subscription(subscription.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(past_due.status, SubscriptionStatus::PastDue);
+        let balance = f.credits.get_balance(user_id, "SOL").await.unwrap();
+        assert_eq!(balance.balance_lamports, 4_000);
+    }
+
+    #[tokio::test]
+    async fn test_recovered_payment_and_cancel_at_period_end() {
+        let f = fixture(7, 3).await;
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        let subscription = f
+            .service
+            .subscribe(f.plan.id, user_id, None, now)
+            .await
+            .unwrap();
+        let due = subscription.next_attempt_at;
+        f.service.renew_due(due, 100).await.unwrap();
+
+        // Topping up before the grace period ends renews the missed period
+        fund(&f.credits, user_id, 1_000).await;
+        let summary = f
+            .service
+            .renew_due(due + Duration::days(1), 100)
+            .await
+            .unwrap();
+        assert_eq!(summary.renewed, 1);
+        let renewed = f
+            .subscription_repo
+            .find_subscription(subscription.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(renewed.status, SubscriptionStatus::Active);
+        assert_eq!(renewed.current_period_start, due);
+        assert_eq!(renewed.dunning_attempts, 0);
+        assert!(renewed.past_due_since.is_none());
+
+        let period_end = renewed.current_period_end;
+        let pending = f.service.cancel(renewed, true, now).await.unwrap();
+        assert_eq!(pending.status, SubscriptionStatus::Active);
+        assert!(pending.cancel_at_period_end);
+
+        let summary = f.service.renew_due(period_end, 100).await.unwrap();
+        assert_eq!(summary.canceled, 1);
+        assert_eq!(summary.charged_lamports, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_during_renewal_is_not_overwritten() {
+        let f = fixture(0, 0).await;
+        let user_id = Uuid::new_v4();
+        let now = Utc::now();
+        fund(&f.credits, user_id, 2_000).await;
+
+        let subscription = f
+            .service
+            .subscribe(f.plan.id, user_id, None, now)
+            .await
+            .unwrap();
+        let subscription_id = subscription.id;
+        let period_end = subscription.current_period_end;
+
+        // The worker read the row before the user asked to cancel
+        let in_flight = subscription.clone();
+        f.service.cancel(subscription, true, now).await.unwrap();
+        assert!(f.service.renew(in_flight, period_end).await.is_err());
+
+        // The renewal was charged, so the paid period is honored and the
+        // cancellation takes effect at its end
+        let summary = f.service.renew_due(period_end, 100).await.unwrap();
+        assert_eq!(summary.renewed, 1);
+        let renewed = f
+            .subscription_repo
+            .find_subscription(subscription_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(renewed.current_period_start, period_end);
+        assert!(renewed.cancel_at_period_end);
+        let balance = f.credits.get_balance(user_id, "SOL").await.unwrap();
+        assert_eq!(balance.balance_lamports, 0);
+    }
+
+    #[tokio::test]
+    async fn test_org_entitlements_require_membership() {
+        let f = fixture(7, 0).await;
+        let org_id = Uuid::new_v4();
+        let admin_id = Uuid::new_v4();
+        let now = Utc::now();
+        f.membership_repo
+            .create(MembershipEntity::new(admin_id, org_id, OrgRole::Admin))
+            .await
+            .unwrap();
+
+        f.service
+            .subscribe(f.plan.id, admin_id, Some(org_id), now)
+            .await
+            .unwrap();
+        assert_eq!(
+            f.service
+                .entitlements_for(admin_id, Some(org_id), now)
+                .await
+                .unwrap(),
+            ["pro"]
+        );
+        // Org entitlements are not personal ones
+        assert!(f
+            .service
+            .entitlements_for(admin_id, None, now)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(f
+            .service
+            .entitlements_for(Uuid::new_v4(), Some(org_id), now)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+//! Background worker for billing metered usage
+//!
+//! Periodically bills pending usage events: events are grouped into batches
+//! per meter, payer and month, priced against the catalog, and debited from
+//! the payer's credits. See [`MeteringService`] for the billing rules.
+//!
+//! Default poll interval: 60 seconds
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info};
+
+use crate::repositories::{
+    CreditHoldRepository, CreditRepository, MembershipRepository, MeterRepository,
+};
+use crate::services::MeteringService;
+
+/// Default poll interval for usage billing (60 seconds)
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 60;
+
+/// Default number of events billed per run
+const DEFAULT_BATCH_LIMIT: u32 = 1000;
+
+/// Configuration for the usage billing worker
+#[derive(Debug, Clone)]
+pub struct UsageBillingConfig {
+    /// How often to bill pending usage (seconds)
+    pub poll_interval_secs: u64,
+    /// Maximum events billed per run
+    pub batch_limit: u32,
+}
+
+impl Default for UsageBillingConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+            batch_limit: DEFAULT_BATCH_LIMIT,
+        }
+    }
+}
+
+/// Background worker that bills metered usage
+pub struct UsageBillingWorker {
+    service: MeteringService,
+    config: UsageBillingConfig,
+}
+
+impl UsageBillingWorker {
+    /// Create a new usage billing worker
+    pub fn new(
+        meter_repo: Arc<dyn MeterRepository>,
+        credit_repo: Arc<dyn CreditRepository>,
+        hold_repo: Arc<dyn CreditHoldRepository>,
+        membership_repo: Arc<dyn MembershipRepository>,
+        config: UsageBillingConfig,
+    ) -> Self {
+        Self {
+            service: MeteringService::new(meter_repo, credit_repo, hold_repo, membership_repo),
+            config,
+        }
+    }
+
+    /// Start the worker as a background task with graceful shutdown support
+    pub fn start(self, cancel_token: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
+
+            info!(
+                poll_interval_secs = self.config.poll_interval_secs,
+                "Usage billing worker started"
+            );
+
+            loop {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        info!("Usage billing worker shutting down");
+                        break;
+                    }
+                    _ = tokio::time::sleep(poll_interval) => {
+                        if let Err(e) = self.bill_usage().await {
+                            error!(error = %e, "Failed to bill metered usage");
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Bill pending usage events
+    async fn bill_usage(&self) -> Result<(), crate::errors::AppError> {
+        let summary = self.service.bill_pending(self.config.batch_limit).await?;
+
+        if summary.batches > 0 {
+            info!(
+                batches = summary.batches,
+                billed_events = summary.billed_events,
+                failed_events = summary.failed_events,
+                billed_lamports = summary.billed_lamports,
+                "Billed metered usage"
+            );
+        } else {
+            debug!("No usage to bill");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = UsageBillingConfig::default();
+        assert_eq!(config.poll_interval_secs, 60);
+        assert_eq!(config.batch_limit, 1000);
+    }
+}
@@ -0,0 +1,264 @@
+//! Background worker for purging accounts scheduled for deletion
+//!
+//! `DELETE /me` only marks an account `pending_deletion`; it stays restorable
+//! until its `status_expires_at` timestamp. This worker periodically finds
+//! accounts whose cooling-off period has ended and removes their personal
+//! data. The users row itself is anonymized rather than deleted, because
+//! deposits, withdrawals and credit transactions must keep referencing it.
+//!
+//! Default poll interval: 1 hour
+
+use chrono::Utc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::repositories::{AuditEventType, AuditLogBuilder, CreditTransactionEntity};
+use crate::storage::Storage;
+
+/// Default poll interval for the purge worker (1 hour)
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 3600;
+
+/// Default number of accounts purged per poll
+const DEFAULT_BATCH_SIZE: u32 = 50;
+
+/// Default cooling-off period before a deleted account is purged (days)
+pub const DEFAULT_ACCOUNT_DELETION_GRACE_DAYS: u32 = 30;
+
+/// Reference type recorded on the transaction that forfeits remaining credits
+const FORFEIT_REFERENCE_TYPE: &str = "account_deletion";
+
+/// Configuration for the user purge worker
+#[derive(Debug, Clone)]
+pub struct UserPurgeConfig {
+    /// How often to check for accounts past their cooling-off period (seconds)
+    pub poll_interval_secs: u64,
+    /// Maximum accounts purged per poll
+    pub batch_size: u32,
+}
+
+impl Default for UserPurgeConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+}
+
+/// Background worker that purges accounts past their cooling-off period
+pub struct UserPurgeWorker {
+    storage: Storage,
+    config: UserPurgeConfig,
+}
+
+impl UserPurgeWorker {
+    /// Create a new user purge worker
+    pub fn new(storage: Storage, config: UserPurgeConfig) -> Self {
+        Self { storage, config }
+    }
+
+    /// Start the worker as a background task with graceful shutdown support
+    pub fn start(self, cancel_token: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let poll_interval = Duration::from_secs(self.config.poll_interval_secs);
+
+            info!(
+                poll_interval_secs = self.config.poll_interval_secs,
+                "User purge worker started"
+            );
+
+            loop {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        info!("User purge worker shutting down");
+                        break;
+                    }
+                    _ = tokio::time::sleep(poll_interval) => {
+                        if let Err(e) = self.purge_due().await {
+                            error!(error = %e, "Failed to purge deleted accounts");
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Purge every account whose cooling-off period has ended. Returns the
+    /// number purged.
+    pub async fn purge_due(&self) -> Result<usize, AppError> {
+        let due = self
+            .storage
+            .user_repo
+            .find_due_for_purge(Utc::now(), self.config.batch_size)
+            .await?;
+
+        let mut purged = 0;
+        for user in due {
+            match self.purge_user(user.id).await {
+                Ok(()) => {
+                    info!(user_id = %user.id, "Purged deleted account");
+                    purged += 1;
+                }
+                Err(e) => error!(user_id = %user.id, error = %e, "Failed to purge account"),
+            }
+        }
+
+        if purged == 0 {
+            debug!("No accounts to purge");
+        }
+
+        Ok(purged)
+    }
+
+    /// Remove an account's credentials, memberships, wallet material and
+    /// personal data
+    async fn purge_user(&self, user_id: Uuid) -> Result<(), AppError> {
+        let storage = &self.storage;
+
+        storage.session_repo.revoke_all_for_user(user_id).await?;
+        storage.api_key_repo.delete_for_user(user_id).await?;
+        for credential in storage.credential_repo.find_by_user(user_id).await? {
+            storage.credential_repo.delete(credential.id).await?;
+        }
+        storage.webauthn_repo.delete_by_user(user_id).await?;
+        storage.totp_repo.disable_mfa(user_id).await?;
+        storage.totp_repo.delete_recovery_codes(user_id).await?;
+        storage.wallet_material_repo.delete_by_user(user_id).await?;
+        storage.membership_repo.delete_by_user(user_id).await?;
+
+        // The user agreed to forfeit remaining credits when requesting deletion
+        for balance in storage.credit_repo.get_all_balances(user_id).await? {
+            let available = balance.available();
+            if available <= 0 {
+                continue;
+            }
+            let tx = CreditTransactionEntity::new_adjustment(
+                user_id,
+                -available,
+                &balance.currency,
+                user_id,
+                "Forfeited on account deletion",
+                Some(FORFEIT_REFERENCE_TYPE),
+                None,
+            );
+            storage
+                .credit_repo
+                .deduct_credit(user_id, available, &balance.currency, tx)
+                .await?;
+        }
+
+        storage.user_repo.anonymize(user_id).await?;
+
+        let entry = AuditLogBuilder::new(AuditEventType::UserDeleted)
+            .target("user", user_id)
+            .build();
+        if let Err(e) = storage.audit_repo.create(entry).await {
+            error!(user_id = %user_id, error = %e, "Failed to audit account purge");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{MembershipEntity, OrgEntity, OrgRole, UserEntity, UserStatus};
+
+    #[test]
+    fn test_default_config() {
+        let config = UserPurgeConfig::default();
+        assert_eq!(config.poll_interval_secs, 3600);
+        assert_eq!(config.batch_size, 50);
+    }
+
+    #[tokio::test]
+    async fn test_purge_due_anonymizes_only_expired() {
+        let storage = Storage::in_memory();
+
+        let expired = storage
+            .user_repo
+            .create(UserEntity::new_email_user(
+                "gone@example.com".into(),
+                "hash".into(),
+                Some("Gone".into()),
+            ))
+            .await
+            .unwrap();
+        let pending = storage
+            .user_repo
+            .create(UserEntity::new_email_user(
+                "later@example.com".into(),
+                "hash".into(),
+                None,
+            ))
+            .await
+            .unwrap();
+
+        let org = storage
+            .org_repo
+            .create(OrgEntity::new("A".into(), "a".into(), expired.id, true))
+            .await
+            .unwrap();
+        storage
+            .membership_repo
+            .create(MembershipEntity::new(expired.id, org.id, OrgRole::Owner))
+            .await
+            .unwrap();
+
+        storage
+            .user_repo
+            .set_status(
+                expired.id,
+                UserStatus::PendingDeletion,
+                None,
+                Some(Utc::now() - chrono::Duration::seconds(1)),
+            )
+            .await
+            .unwrap();
+        storage
+            .user_repo
+            .set_status(
+                pending.id,
+                UserStatus::PendingDeletion,
+                None,
+                Some(Utc::now() + chrono::Duration::days(1)),
+            )
+            .await
+            .unwrap();
+
+        let worker = UserPurgeWorker::new(storage.clone(), UserPurgeConfig::default());
+        assert_eq!(worker.purge_due().await.unwrap(), 1);
+
+        let purged = storage
+            .user_repo
+            .find_by_id(expired.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(purged.status, UserStatus::Deleted);
+        assert!(purged.email.is_none());
+        assert!(purged.name.is_none());
+        assert!(purged.password_hash.is_none());
+        assert_eq!(
+            storage
+                .membership_repo
+                .count_by_user(expired.id)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let untouched = storage
+            .user_repo
+            .find_by_id(pending.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(untouched.status, UserStatus::PendingDeletion);
+        assert!(untouched.email.is_some());
+    }
+}
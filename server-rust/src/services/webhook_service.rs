@@ -0,0 +1,235 @@
+//! Webhook service for fanning events out to admin-managed endpoints
+//!
+//! Events are not sent inline. For every subscribed endpoint a delivery row is
+//! written to the log and a `webhook.delivery` outbox event is queued; the
+//! outbox worker signs and POSTs the stored payload, retrying with backoff.
+
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+use crate::callback::WebhookEvent;
+use crate::errors::AppError;
+use crate::repositories::{
+    AuditEventType, OutboxEvent, OutboxEventType, OutboxRepository, WebhookDeliveryEntity,
+    WebhookRepository,
+};
+use crate::utils::TokenCipher;
+
+/// Service for queueing webhook deliveries
+pub struct WebhookService {
+    webhook_repo: Arc<dyn WebhookRepository>,
+    outbox_repo: Arc<dyn OutboxRepository>,
+    token_cipher: TokenCipher,
+}
+
+impl WebhookService {
+    pub fn new(
+        webhook_repo: Arc<dyn WebhookRepository>,
+        outbox_repo: Arc<dyn OutboxRepository>,
+        token_cipher: TokenCipher,
+    ) -> Self {
+        Self {
+            webhook_repo,
+            outbox_repo,
+            token_cipher,
+        }
+    }
+
+    /// Encrypt an endpoint signing secret for storage
+    pub fn encrypt_secret(&self, secret: &str) -> Result<String, AppError> {
+        self.token_cipher.encrypt(secret)
+    }
+
+    /// Queue `event` for every enabled endpoint subscribed to it.
+    ///
+    /// Every endpoint receives the same envelope:
+    /// `{"id", "event", "timestamp", "data"}`. Returns the number of
+    /// deliveries queued.
+    pub async fn dispatch(&self, event: WebhookEvent, data: Value) -> Result<usize, AppError> {
+        let endpoints = self.webhook_repo.find_subscribed(event.as_str()).await?;
+        if endpoints.is_empty() {
+            return Ok(0);
+        }
+
+        let payload = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "event": event.as_str(),
+            "timestamp": current_timestamp(),
+            "data": data,
+        });
+
+        for endpoint in &endpoints {
+            let delivery = WebhookDeliveryEntity::new(endpoint.id, event.as_str(), payload.clone());
+            self.enqueue(delivery).await?;
+        }
+
+        Ok(endpoints.len())
+    }
+
+    /// Fire-and-forget dispatch that logs warnings on failure.
+    /// Webhook delivery must never fail the operation that triggered it.
+    pub async fn dispatch_or_warn(&self, event: WebhookEvent, data: Value) {
+        if let Err(e) = self.dispatch(event, data).await {
+            tracing::warn!(
+                error = %e,
+                event = event.as_str(),
+                "Failed to queue webhook deliveries (non-fatal)"
+            );
+        }
+    }
+
+    /// Send a logged delivery again as a new delivery with the same payload
+    pub async fn replay(&self, delivery_id: Uuid) -> Result<WebhookDeliveryEntity, AppError> {
+        let original = self
+            .webhook_repo
+            .find_delivery(delivery_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Webhook delivery not found".into()))?;
+
+        let endpoint = self
+            .webhook_repo
+            .find_endpoint(original.endpoint_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Webhook endpoint not found".into()))?;
+        if !endpoint.enabled {
+            return Err(AppError::Validation(
+                "Webhook endpoint is disabled; re-enable it before replaying".into(),
+            ));
+        }
+
+        self.enqueue(original.replay()).await
+    }
+
+    async fn enqueue(
+        &self,
+        delivery: WebhookDeliveryEntity,
+    ) -> Result<WebhookDeliveryEntity, AppError> {
+        let delivery = self.webhook_repo.create_delivery(delivery).await?;
+        let event = OutboxEvent::new(
+            OutboxEventType::WebhookDelivery,
+            serde_json::json!({ "delivery_id": delivery.id }),
+        )
+        .with_correlation_id(&delivery.id.to_string());
+        self.outbox_repo.create(event).await?;
+        Ok(delivery)
+    }
+}
+
+/// Webhook event sent when an audit entry of this type is written, if any
+pub fn webhook_event_for_audit(event_type: AuditEventType) -> Option<WebhookEvent> {
+    match event_type {
+        AuditEventType::OrgCreated => Some(WebhookEvent::OrgCreated),
+        AuditEventType::OrgUpdated => Some(WebhookEvent::OrgUpdated),
+        AuditEventType::OrgDeleted => Some(WebhookEvent::OrgDeleted),
+        AuditEventType::MemberJoined => Some(WebhookEvent::MemberAdded),
+        AuditEventType::MemberRoleChanged => Some(WebhookEvent::MemberRoleChanged),
+        AuditEventType::MemberRemoved => Some(WebhookEvent::MemberRemoved),
+        AuditEventType::InviteCreated => Some(WebhookEvent::InviteCreated),
+        AuditEventType::InviteAccepted => Some(WebhookEvent::InviteAccepted),
+        AuditEventType::InviteCanceled => Some(WebhookEvent::InviteRevoked),
+        AuditEventType::MfaEnabled => Some(WebhookEvent::MfaEnabled),
+        AuditEventType::MfaDisabled => Some(WebhookEvent::MfaDisabled),
+        AuditEventType::WalletEnrolled => Some(WebhookEvent::WalletCreated),
+        AuditEventType::WalletRecovered => Some(WebhookEvent::WalletRecovered),
+        AuditEventType::SessionRevoked | AuditEventType::SessionRevokedAll => {
+            Some(WebhookEvent::SessionRevoked)
+        }
+        _ => None,
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        // SAFETY: SystemTime::now() is always after UNIX_EPOCH on any reasonable system
+        .expect("System clock is before Unix epoch")
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        InMemoryOutboxRepository, InMemoryWebhookRepository, WebhookEndpointEntity,
+    };
+
+    fn service() -> (
+        WebhookService,
+        Arc<InMemoryWebhookRepository>,
+        Arc<InMemoryOutboxRepository>,
+    ) {
+        let webhook_repo = Arc::new(InMemoryWebhookRepository::new());
+        let outbox_repo = Arc::new(InMemoryOutboxRepository::new());
+        let service = WebhookService::new(
+            webhook_repo.clone(),
+            outbox_repo.clone(),
+            TokenCipher::new("test-secret-key-that-is-long-enough"),
+        );
+        (service, webhook_repo, outbox_repo)
+    }
+
+    async fn endpoint(repo: &InMemoryWebhookRepository, events: &[&str]) -> Uuid {
+        let endpoint = WebhookEndpointEntity::new(
+            "https://example.com/hook".into(),
+            None,
+            "enc".into(),
+            events.iter().map(|e| e.to_string()).collect(),
+            None,
+        );
+        repo.create_endpoint(endpoint).await.unwrap().id
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_queues_delivery_per_subscribed_endpoint() {
+        let (service, webhook_repo, outbox_repo) = service();
+        let all = endpoint(&webhook_repo, &[]).await;
+        let orgs = endpoint(&webhook_repo, &["org_created"]).await;
+        let mfa = endpoint(&webhook_repo, &["mfa_enabled"]).await;
+
+        let queued = service
+            .dispatch(WebhookEvent::OrgCreated, serde_json::json!({"org_id": "x"}))
+            .await
+            .unwrap();
+        assert_eq!(queued, 2);
+
+        assert_eq!(webhook_repo.count_deliveries(all).await.unwrap(), 1);
+        assert_eq!(webhook_repo.count_deliveries(orgs).await.unwrap(), 1);
+        assert_eq!(webhook_repo.count_deliveries(mfa).await.unwrap(), 0);
+
+        let delivery = &webhook_repo.list_deliveries(orgs, 10, 0).await.unwrap()[0];
+        assert_eq!(delivery.payload["event"], "org_created");
+        assert_eq!(delivery.payload["data"]["org_id"], "x");
+
+        let pending = outbox_repo.fetch_pending(10, 60).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert!(pending
+            .iter()
+            .all(|e| e.event_type == OutboxEventType::WebhookDelivery));
+    }
+
+    #[tokio::test]
+    async fn test_replay_creates_linked_delivery() {
+        let (service, webhook_repo, _outbox_repo) = service();
+        let id = endpoint(&webhook_repo, &[]).await;
+        service
+            .dispatch(WebhookEvent::MfaEnabled, serde_json::json!({}))
+            .await
+            .unwrap();
+        let original = webhook_repo.list_deliveries(id, 10, 0).await.unwrap()[0].clone();
+
+        let replay = service.replay(original.id).await.unwrap();
+        assert_eq!(replay.replay_of, Some(original.id));
+        assert_eq!(replay.payload, original.payload);
+        assert_ne!(replay.id, original.id);
+
+        let mut endpoint = webhook_repo.find_endpoint(id).await.unwrap().unwrap();
+        endpoint.enabled = false;
+        webhook_repo.update_endpoint(endpoint).await.unwrap();
+        assert!(matches!(
+            service.replay(original.id).await,
+            Err(AppError::Validation(_))
+        ));
+    }
+}
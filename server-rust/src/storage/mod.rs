@@ -3,32 +3,41 @@
 use chrono::{Duration as ChronoDuration, Utc};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Notify;
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info};
 
 use crate::config::DatabaseConfig;
 use crate::errors::AppError;
 use crate::repositories::{
-    ApiKeyRepository, AuditLogRepository, CredentialRepository, CreditHoldRepository,
+    ApiKeyRepository, AuditLogRepository, CredentialRepository, CreditBudgetRepository,
+    CreditHoldRepository, CreditInvoiceRepository,
     CreditRefundRequestRepository, CreditRepository, CustomRoleRepository, DepositRepository,
     InMemoryApiKeyRepository, InMemoryAuditLogRepository, InMemoryCredentialRepository,
-    InMemoryCreditHoldRepository, InMemoryCreditRefundRequestRepository, InMemoryCreditRepository,
+    InMemoryCreditBudgetRepository, InMemoryCreditHoldRepository, InMemoryCreditInvoiceRepository, InMemoryCreditRefundRequestRepository, InMemoryCreditRepository,
     InMemoryCustomRoleRepository, InMemoryDerivedWalletRepository, InMemoryDepositRepository,
-    InMemoryInviteRepository,
-    InMemoryLoginAttemptRepository, InMemoryMembershipRepository, InMemoryNonceRepository,
+    InMemoryInviteLinkRepository, InMemoryInviteRepository,
+    InMemoryLoginAttemptRepository, InMemoryMembershipRepository, InMemoryMeterRepository,
+    InMemoryNonceRepository,
     InMemoryOrgRepository, InMemoryOutboxRepository, InMemoryPendingWalletRecoveryRepository,
-    InMemoryPolicyRepository, InMemoryPrivacyNoteRepository, InMemorySessionRepository,
-    InMemorySsoRepository, InMemorySystemSettingsRepository, InMemoryTotpRepository,
+    InMemoryPolicyRepository, InMemoryPrivacyNoteRepository, InMemoryRelationshipRepository,
+    InMemoryServiceAccountRepository, InMemorySessionRepository,
+    InMemorySsoRepository, InMemoryStripeTopupRepository, InMemorySubscriptionRepository,
+    InMemorySystemSettingsRepository, InMemoryTotpRepository,
     InMemoryTreasuryConfigRepository, InMemoryUserRepository, InMemoryUserWithdrawalLogRepository,
     InMemoryVerificationRepository, InMemoryWalletMaterialRepository, InMemoryWebAuthnRepository,
-    InMemoryWalletRotationHistoryRepository, InMemoryWithdrawalHistoryRepository,
-    InviteRepository, LoginAttemptRepository,
-    MembershipRepository, NonceRepository, OrgRepository, OutboxRepository,
+    InMemoryWalletRotationHistoryRepository, InMemoryWebhookRepository,
+    InMemoryWithdrawalHistoryRepository,
+    ActiveOrgMembershipRepository,
+    InviteLinkRepository, InviteRepository, LoginAttemptRepository,
+    MembershipRepository, MeterRepository, NonceRepository, OrgRepository, OutboxRepository,
     DerivedWalletRepository, PendingWalletRecoveryRepository, PolicyRepository,
     WalletRotationHistoryRepository,
-    PrivacyNoteRepository, SessionRepository, SsoRepository, SystemSettingsRepository,
+    PrivacyNoteRepository, RelationshipRepository, ServiceAccountRepository, SessionRepository,
+    SsoRepository, StripeTopupRepository, SubscriptionRepository,
+    SystemSettingsRepository,
     TotpRepository, TreasuryConfigRepository, UserRepository, UserWithdrawalLogRepository,
-    VerificationRepository, WalletMaterialRepository, WebAuthnRepository,
+    VerificationRepository, WalletMaterialRepository, WebAuthnRepository, WebhookRepository,
     WithdrawalHistoryRepository,
 };
 use crate::services::EncryptionService;
@@ -36,17 +45,20 @@ use crate::services::EncryptionService;
 #[cfg(feature = "postgres")]
 use crate::repositories::{
     PostgresApiKeyRepository, PostgresAuditLogRepository, PostgresCredentialRepository,
-    PostgresCreditHoldRepository, PostgresCreditRefundRequestRepository, PostgresCreditRepository,
+    PostgresCreditBudgetRepository, PostgresCreditHoldRepository, PostgresCreditInvoiceRepository, PostgresCreditRefundRequestRepository, PostgresCreditRepository,
     PostgresCustomRoleRepository, PostgresDerivedWalletRepository, PostgresDepositRepository,
-    PostgresInviteRepository,
-    PostgresLoginAttemptRepository, PostgresMembershipRepository, PostgresNonceRepository,
+    PostgresInviteLinkRepository, PostgresInviteRepository,
+    PostgresLoginAttemptRepository, PostgresMembershipRepository, PostgresMeterRepository,
+    PostgresNonceRepository,
     PostgresOrgRepository, PostgresOutboxRepository, PostgresPendingWalletRecoveryRepository,
-    PostgresPolicyRepository, PostgresPrivacyNoteRepository, PostgresSessionRepository,
-    PostgresSsoRepository, PostgresSystemSettingsRepository, PostgresTotpRepository,
+    PostgresPolicyRepository, PostgresPrivacyNoteRepository, PostgresRelationshipRepository,
+    PostgresServiceAccountRepository, PostgresSessionRepository,
+    PostgresSsoRepository, PostgresStripeTopupRepository, PostgresSubscriptionRepository,
+    PostgresSystemSettingsRepository, PostgresTotpRepository,
     PostgresTreasuryConfigRepository, PostgresUserRepository, PostgresUserWithdrawalLogRepository,
     PostgresVerificationRepository, PostgresWalletMaterialRepository,
     PostgresWalletRotationHistoryRepository, PostgresWebAuthnRepository,
-    PostgresWithdrawalHistoryRepository,
+    PostgresWebhookRepository, PostgresWithdrawalHistoryRepository,
 };
 
 #[cfg(feature = "postgres")]
@@ -64,11 +76,15 @@ pub struct Storage {
     pub org_repo: Arc<dyn OrgRepository>,
     pub membership_repo: Arc<dyn MembershipRepository>,
     pub invite_repo: Arc<dyn InviteRepository>,
+    pub invite_link_repo: Arc<dyn InviteLinkRepository>,
     pub audit_repo: Arc<dyn AuditLogRepository>,
     pub login_attempt_repo: Arc<dyn LoginAttemptRepository>,
     pub totp_repo: Arc<dyn TotpRepository>,
     pub custom_role_repo: Arc<dyn CustomRoleRepository>,
     pub policy_repo: Arc<dyn PolicyRepository>,
+    pub relationship_repo: Arc<dyn RelationshipRepository>,
+    pub service_account_repo: Arc<dyn ServiceAccountRepository>,
+    pub webhook_repo: Arc<dyn WebhookRepository>,
     pub outbox_repo: Arc<dyn OutboxRepository>,
     pub api_key_repo: Arc<dyn ApiKeyRepository>,
     pub wallet_material_repo: Arc<dyn WalletMaterialRepository>,
@@ -79,6 +95,11 @@ pub struct Storage {
     pub credit_repo: Arc<dyn CreditRepository>,
     pub credit_hold_repo: Arc<dyn CreditHoldRepository>,
     pub credit_refund_request_repo: Arc<dyn CreditRefundRequestRepository>,
+    pub credit_budget_repo: Arc<dyn CreditBudgetRepository>,
+    pub credit_invoice_repo: Arc<dyn CreditInvoiceRepository>,
+    pub meter_repo: Arc<dyn MeterRepository>,
+    pub subscription_repo: Arc<dyn SubscriptionRepository>,
+    pub stripe_topup_repo: Arc<dyn StripeTopupRepository>,
     pub privacy_note_repo: Arc<dyn PrivacyNoteRepository>,
     pub system_settings_repo: Arc<dyn SystemSettingsRepository>,
     pub treasury_config_repo: Arc<dyn TreasuryConfigRepository>,
@@ -87,6 +108,8 @@ pub struct Storage {
     pub derived_wallet_repo: Arc<dyn DerivedWalletRepository>,
     pub wallet_rotation_history_repo: Arc<dyn WalletRotationHistoryRepository>,
     pub pending_wallet_recovery_repo: Arc<dyn PendingWalletRecoveryRepository>,
+    /// Signalled after audit entries are written, to wake audit sink workers
+    pub audit_notify: Arc<Notify>,
     #[cfg(feature = "postgres")]
     pub pg_pool: Option<PgPool>,
 }
@@ -112,24 +135,37 @@ impl Storage {
         // Build the credit repository first so the hold repository can share it.
         // This allows `capture_hold` to deduct the balance atomically, mirroring
         // what the Postgres implementation does inside a single DB transaction.
-        let credit_repo: Arc<dyn CreditRepository> = Arc::new(InMemoryCreditRepository::new());
+        // Both consume spending budgets from the shared budget repository.
+        let credit_budget_repo: Arc<dyn CreditBudgetRepository> =
+            Arc::new(InMemoryCreditBudgetRepository::new());
+        let credit_repo: Arc<dyn CreditRepository> =
+            Arc::new(InMemoryCreditRepository::new().with_budgets(Arc::clone(&credit_budget_repo)));
         let credit_hold_repo: Arc<dyn CreditHoldRepository> = Arc::new(
-            InMemoryCreditHoldRepository::with_credit_repo(Arc::clone(&credit_repo)),
+            InMemoryCreditHoldRepository::with_credit_repo(Arc::clone(&credit_repo))
+                .with_budgets(Arc::clone(&credit_budget_repo)),
         );
+        let org_repo: Arc<dyn OrgRepository> = Arc::new(InMemoryOrgRepository::new());
 
         Self {
             user_repo: Arc::new(InMemoryUserRepository::new()),
             session_repo: Arc::new(InMemorySessionRepository::new()),
             nonce_repo: Arc::new(InMemoryNonceRepository::new()),
             verification_repo: Arc::new(InMemoryVerificationRepository::new()),
-            org_repo: Arc::new(InMemoryOrgRepository::new()),
-            membership_repo: Arc::new(InMemoryMembershipRepository::new()),
+            org_repo: org_repo.clone(),
+            membership_repo: Arc::new(ActiveOrgMembershipRepository::new(
+                Arc::new(InMemoryMembershipRepository::new()),
+                org_repo,
+            )),
             invite_repo: Arc::new(InMemoryInviteRepository::new()),
+            invite_link_repo: Arc::new(InMemoryInviteLinkRepository::new()),
             audit_repo: Arc::new(InMemoryAuditLogRepository::new()),
             login_attempt_repo: Arc::new(InMemoryLoginAttemptRepository::new()),
             totp_repo: Arc::new(InMemoryTotpRepository::new()),
             custom_role_repo: Arc::new(InMemoryCustomRoleRepository::new()),
             policy_repo: Arc::new(InMemoryPolicyRepository::new()),
+            relationship_repo: Arc::new(InMemoryRelationshipRepository::new()),
+            service_account_repo: Arc::new(InMemoryServiceAccountRepository::new()),
+            webhook_repo: Arc::new(InMemoryWebhookRepository::new()),
             outbox_repo: Arc::new(InMemoryOutboxRepository::new()),
             api_key_repo: Arc::new(InMemoryApiKeyRepository::new()),
             wallet_material_repo: Arc::new(InMemoryWalletMaterialRepository::new()),
@@ -140,6 +176,11 @@ impl Storage {
             credit_repo,
             credit_hold_repo,
             credit_refund_request_repo: Arc::new(InMemoryCreditRefundRequestRepository::new()),
+            credit_budget_repo,
+            credit_invoice_repo: Arc::new(InMemoryCreditInvoiceRepository::new()),
+            meter_repo: Arc::new(InMemoryMeterRepository::new()),
+            subscription_repo: Arc::new(InMemorySubscriptionRepository::new()),
+            stripe_topup_repo: Arc::new(InMemoryStripeTopupRepository::new()),
             privacy_note_repo: Arc::new(InMemoryPrivacyNoteRepository::new()),
             system_settings_repo: Arc::new(InMemorySystemSettingsRepository::with_defaults()),
             treasury_config_repo: Arc::new(InMemoryTreasuryConfigRepository::new()),
@@ -148,6 +189,7 @@ impl Storage {
             derived_wallet_repo: Arc::new(InMemoryDerivedWalletRepository::new()),
             wallet_rotation_history_repo: Arc::new(InMemoryWalletRotationHistoryRepository::new()),
             pending_wallet_recovery_repo: Arc::new(InMemoryPendingWalletRecoveryRepository::new()),
+            audit_notify: Arc::new(Notify::new()),
             #[cfg(feature = "postgres")]
             pg_pool: None,
         }
@@ -217,21 +259,29 @@ impl Storage {
             pool.clone(),
             Arc::new(EncryptionService::from_secret(&totp_secret)),
         ));
+        let org_repo: Arc<dyn OrgRepository> = Arc::new(PostgresOrgRepository::new(pool.clone()));
 
         Ok(Self {
             user_repo: Arc::new(PostgresUserRepository::new(pool.clone())),
             session_repo: Arc::new(PostgresSessionRepository::new(pool.clone())),
             nonce_repo: Arc::new(PostgresNonceRepository::new(pool.clone())),
             verification_repo: Arc::new(PostgresVerificationRepository::new(pool.clone())),
-            org_repo: Arc::new(PostgresOrgRepository::new(pool.clone())),
-            membership_repo: Arc::new(PostgresMembershipRepository::new(pool.clone())),
+            org_repo: org_repo.clone(),
+            membership_repo: Arc::new(ActiveOrgMembershipRepository::new(
+                Arc::new(PostgresMembershipRepository::new(pool.clone())),
+                org_repo,
+            )),
             invite_repo: Arc::new(PostgresInviteRepository::new(pool.clone())),
+            invite_link_repo: Arc::new(PostgresInviteLinkRepository::new(pool.clone())),
             audit_repo: Arc::new(PostgresAuditLogRepository::new(pool.clone())),
             outbox_repo: Arc::new(PostgresOutboxRepository::new(pool.clone())),
             login_attempt_repo: Arc::new(PostgresLoginAttemptRepository::new(pool.clone())),
             totp_repo,
             custom_role_repo: Arc::new(PostgresCustomRoleRepository::new(pool.clone())),
             policy_repo: Arc::new(PostgresPolicyRepository::new(pool.clone())),
+            relationship_repo: Arc::new(PostgresRelationshipRepository::new(pool.clone())),
+            service_account_repo: Arc::new(PostgresServiceAccountRepository::new(pool.clone())),
+            webhook_repo: Arc::new(PostgresWebhookRepository::new(pool.clone())),
             api_key_repo: Arc::new(PostgresApiKeyRepository::new(pool.clone())),
             wallet_material_repo: Arc::new(PostgresWalletMaterialRepository::new(pool.clone())),
             credential_repo: Arc::new(PostgresCredentialRepository::new(pool.clone())),
@@ -243,6 +293,11 @@ impl Storage {
             credit_refund_request_repo: Arc::new(PostgresCreditRefundRequestRepository::new(
                 pool.clone(),
             )),
+            credit_budget_repo: Arc::new(PostgresCreditBudgetRepository::new(pool.clone())),
+            credit_invoice_repo: Arc::new(PostgresCreditInvoiceRepository::new(pool.clone())),
+            meter_repo: Arc::new(PostgresMeterRepository::new(pool.clone())),
+            subscription_repo: Arc::new(PostgresSubscriptionRepository::new(pool.clone())),
+            stripe_topup_repo: Arc::new(PostgresStripeTopupRepository::new(pool.clone())),
             privacy_note_repo: Arc::new(PostgresPrivacyNoteRepository::new(pool.clone())),
             system_settings_repo: Arc::new(PostgresSystemSettingsRepository::new(pool.clone())),
             treasury_config_repo: Arc::new(PostgresTreasuryConfigRepository::new(pool.clone())),
@@ -259,6 +314,7 @@ impl Storage {
             pending_wallet_recovery_repo: Arc::new(PostgresPendingWalletRecoveryRepository::new(
                 pool.clone(),
             )),
+            audit_notify: Arc::new(Notify::new()),
             #[cfg(feature = "postgres")]
             pg_pool: Some(pool),
         })
@@ -452,7 +508,9 @@ fn select_totp_encryption_secret() -> Result<String, AppError> {
 mod tests {
     use super::*;
     use crate::models::sso::SsoAuthState;
-    use crate::repositories::{NonceEntity, SessionEntity, UserEntity, WebAuthnChallenge};
+    use crate::repositories::{
+        NonceEntity, SessionEntity, UserEntity, UserStatus, WebAuthnChallenge,
+    };
     use chrono::{Duration as ChronoDuration, Utc};
     use std::sync::Mutex;
     use std::time::Duration;
@@ -528,9 +586,16 @@ mod tests {
             stripe_customer_id: None,
             auth_methods: vec![crate::models::AuthMethod::Email],
             is_system_admin: false,
+            is_service_account: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
 
         let created = storage.user_repo.create(user.clone()).await.unwrap();
@@ -558,9 +623,16 @@ mod tests {
             stripe_customer_id: None,
             auth_methods: vec![crate::models::AuthMethod::Email],
             is_system_admin: false,
+            is_service_account: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_login_at: None,
+            status: UserStatus::Active,
+            status_reason: None,
+            status_expires_at: None,
+            public_metadata: Default::default(),
+            private_metadata: Default::default(),
+            unsafe_metadata: Default::default(),
         };
         let user = storage.user_repo.create(user).await.unwrap();
 
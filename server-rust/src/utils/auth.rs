@@ -1,16 +1,24 @@
 //! Authentication utilities for JWT and API key authentication
 
 use axum::http::HeaderMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::callback::AuthCallback;
 use crate::errors::AppError;
-use crate::repositories::{MembershipEntity, API_KEY_PREFIX};
-use crate::services::{EmailService, TokenContext};
+use crate::middleware::rate_limit::RateLimitConfig;
+use crate::repositories::{
+    normalize_email, validate_email_ascii_local, MembershipEntity, UserEntity, API_KEY_PREFIX,
+    SCOPE_ADMIN,
+};
+use crate::services::{ActorClaim, EmailService, TokenContext};
 use crate::AppState;
 
-use super::extract_access_token;
+use super::{
+    current_peer_ip, extract_access_token, extract_client_ip_with_fallback, is_disposable_email,
+    is_valid_email, project_metadata_claims,
+};
 
 /// Represents an authenticated user from either JWT or API key
 #[derive(Debug, Clone)]
@@ -19,7 +27,7 @@ pub struct AuthenticatedUser {
     pub user_id: Uuid,
     /// Session ID (None for API key auth)
     pub session_id: Option<Uuid>,
-    /// Current organization ID (from JWT claims or None for API key)
+    /// Current organization ID (from JWT claims, or the org an API key is bound to)
     pub org_id: Option<Uuid>,
     /// Current role in organization (from JWT claims or None for API key)
     pub role: Option<String>,
@@ -34,6 +42,89 @@ pub struct AuthenticatedUser {
     pub is_system_admin: Option<bool>,
     /// Whether user's email address has been verified (from JWT claims)
     pub email_verified: Option<bool>,
+    /// Scopes of a restricted API key (None for JWT auth and unscoped keys)
+    pub api_key_scopes: Option<Vec<String>>,
+    /// Impersonating actor when this is an impersonation session
+    pub impersonation: Option<ActorClaim>,
+    /// Whether the principal is a service account (pinned to `org_id`)
+    pub is_service_account: bool,
+}
+
+impl AuthenticatedUser {
+    /// Check if the credential grants a scope.
+    ///
+    /// JWT sessions and unscoped API keys grant every scope; the `admin`
+    /// scope implies all others.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        match &self.api_key_scopes {
+            Some(scopes) => scopes.iter().any(|s| s == scope || s == SCOPE_ADMIN),
+            None => true,
+        }
+    }
+
+    /// Return Forbidden unless the credential grants `scope`
+    pub fn require_scope(&self, scope: &str) -> Result<(), AppError> {
+        if self.has_scope(scope) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden(format!(
+                "API key is missing the '{}' scope",
+                scope
+            )))
+        }
+    }
+
+    /// Return Forbidden if an org-bound API key, a service account or an
+    /// org-scoped impersonation session is used against another org
+    pub fn ensure_org(&self, org_id: Uuid) -> Result<(), AppError> {
+        if self.is_service_account && self.org_id != Some(org_id) {
+            return Err(AppError::Forbidden(
+                "Service account is not valid for this organization".into(),
+            ));
+        }
+        if let Some(bound) = self.impersonation.as_ref().and_then(|act| act.org_id) {
+            if bound != org_id {
+                return Err(AppError::Forbidden(
+                    "Impersonation session is not valid for this organization".into(),
+                ));
+            }
+        }
+        match self.org_id {
+            Some(bound) if self.is_api_key_auth && bound != org_id => Err(AppError::Forbidden(
+                "API key is not valid for this organization".into(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Return Forbidden unless the credential may act system-wide
+    ///
+    /// Impersonation sessions and org-bound API keys never carry system
+    /// admin power, whatever the user's own flag says.
+    pub fn ensure_system_admin_principal(&self) -> Result<(), AppError> {
+        self.ensure_not_impersonated("access admin endpoints")?;
+        if self.is_api_key_auth && self.org_id.is_some() {
+            return Err(AppError::Forbidden(
+                "Organization-bound API keys cannot access admin endpoints".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Return Forbidden for impersonation sessions.
+    ///
+    /// Guards actions an impersonator must never take on the user's behalf
+    /// (wallet signing, moving money, creating credentials that outlive the
+    /// session), even with writes enabled.
+    pub fn ensure_not_impersonated(&self, action: &str) -> Result<(), AppError> {
+        if self.impersonation.is_some() {
+            return Err(AppError::Forbidden(format!(
+                "Cannot {} during impersonation",
+                action
+            )));
+        }
+        Ok(())
+    }
 }
 
 /// Get the default organization context for a user from their memberships.
@@ -57,6 +148,10 @@ pub fn get_default_org_context(
             role: Some(membership.role.as_str().to_string()),
             is_system_admin: admin_flag,
             email_verified: Some(email_verified),
+            service_account: None,
+            act: None,
+            metadata: None,
+            ext: None,
         };
     }
 
@@ -67,6 +162,77 @@ pub fn get_default_org_context(
     }
 }
 
+/// Check an address a user signs up with or changes to
+///
+/// Applies the format check, the disposable-domain policy (SEC-29) and the
+/// ASCII local-part rule (SRV-10). Returns the normalized address.
+pub async fn validate_account_email<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    email: &str,
+) -> Result<String, AppError> {
+    if !is_valid_email(email) {
+        return Err(AppError::Validation("Invalid email format".to_string()));
+    }
+
+    // SEC-29: Runtime toggle with config fallback
+    let block_disposable = state
+        .settings_service
+        .get_bool("auth_email_block_disposable")
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(state.config.email.block_disposable_emails);
+    if block_disposable {
+        // Custom blocked domains come from config + DB setting
+        let mut custom_domains: HashSet<String> = state
+            .config
+            .email
+            .custom_blocked_domains
+            .iter()
+            .cloned()
+            .collect();
+        if let Ok(Some(db_domains)) = state.settings_service.get("custom_blocked_domains").await {
+            if let Ok(domains) = serde_json::from_str::<Vec<String>>(&db_domains) {
+                custom_domains.extend(domains.into_iter().map(|d| d.to_lowercase()));
+            }
+        }
+        let custom_ref = if custom_domains.is_empty() {
+            None
+        } else {
+            Some(&custom_domains)
+        };
+        if is_disposable_email(email, custom_ref) {
+            return Err(AppError::DisposableEmailBlocked);
+        }
+    }
+
+    // SRV-10: Reject non-ASCII local parts to prevent homograph attacks
+    validate_email_ascii_local(email)?;
+
+    Ok(normalize_email(email))
+}
+
+/// Access token claims projected from a user's public metadata
+///
+/// Copies the keys listed in the `jwt_metadata_claims` setting (a JSON array
+/// of key names). Returns `None` when the setting is unset or nothing matches.
+pub async fn metadata_claims<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    user: &UserEntity,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    if user.public_metadata.is_empty() {
+        return None;
+    }
+    let keys = state
+        .settings_service
+        .get("jwt_metadata_claims")
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_str::<Vec<String>>(&v).ok())?;
+    project_metadata_claims(&user.public_metadata, &keys)
+}
+
 /// Authenticate a request using either JWT or API key
 ///
 /// Checks the Authorization header (and optionally cookies) for:
@@ -74,9 +240,59 @@ pub fn get_default_org_context(
 /// 2. JWT token - validates against jwt_service
 ///
 /// Returns an `AuthenticatedUser` on success.
+///
+/// Restricted API keys are rejected unless they carry the `admin` scope;
+/// endpoints that accept a narrower scope use [`authenticate_with_scope`].
+/// Service accounts are rejected too: they may only call org-scoped
+/// endpoints, through [`authenticate_for_org`].
 pub async fn authenticate<C: AuthCallback, E: EmailService>(
     state: &Arc<AppState<C, E>>,
     headers: &HeaderMap,
+) -> Result<AuthenticatedUser, AppError> {
+    let auth = authenticate_credential(state, headers).await?;
+    ensure_unrestricted(&auth)?;
+    ensure_user_principal(&auth)?;
+    Ok(auth)
+}
+
+/// Authenticate a request for an endpoint restricted API keys may call
+///
+/// Same as [`authenticate`], but accepts any API key that grants `scope`.
+pub async fn authenticate_with_scope<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    scope: &str,
+) -> Result<AuthenticatedUser, AppError> {
+    let auth = authenticate_credential(state, headers).await?;
+    auth.require_scope(scope)?;
+    ensure_user_principal(&auth)?;
+    Ok(auth)
+}
+
+/// Return Forbidden for restricted API keys without the `admin` scope
+fn ensure_unrestricted(auth: &AuthenticatedUser) -> Result<(), AppError> {
+    if !auth.has_scope(SCOPE_ADMIN) {
+        return Err(AppError::Forbidden(
+            "Restricted API keys cannot access this endpoint".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Return Forbidden for service accounts on user-only endpoints
+fn ensure_user_principal(auth: &AuthenticatedUser) -> Result<(), AppError> {
+    if auth.is_service_account {
+        return Err(AppError::Forbidden(
+            "Service accounts cannot access this endpoint".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate the JWT or API key without checking key scopes
+async fn authenticate_credential<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
 ) -> Result<AuthenticatedUser, AppError> {
     // Extract token from Authorization header or cookie
     let token = extract_access_token(headers, &state.config.cookie.access_cookie_name)
@@ -84,12 +300,27 @@ pub async fn authenticate<C: AuthCallback, E: EmailService>(
 
     // Check if it's an API key
     if token.starts_with(API_KEY_PREFIX) {
-        authenticate_api_key(state, &token).await
+        authenticate_api_key(state, headers, &token).await
     } else {
         authenticate_jwt(state, &token).await
     }
 }
 
+/// Authenticate a request for an org-scoped endpoint
+///
+/// Same as [`authenticate`], but rejects API keys bound to a different org
+/// and accepts service accounts for their own org.
+pub async fn authenticate_for_org<C: AuthCallback, E: EmailService>(
+    state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
+    org_id: Uuid,
+) -> Result<AuthenticatedUser, AppError> {
+    let auth = authenticate_credential(state, headers).await?;
+    ensure_unrestricted(&auth)?;
+    auth.ensure_org(org_id)?;
+    Ok(auth)
+}
+
 /// Authenticate using an API key
 ///
 /// # Security (SEC-05)
@@ -102,8 +333,15 @@ pub async fn authenticate<C: AuthCallback, E: EmailService>(
 ///
 /// For environments requiring stricter timing guarantees, consider implementing
 /// a dummy database lookup on invalid keys to normalize response time.
+///
+/// Key restrictions are enforced here: expiry, IP allowlist (the client IP
+/// comes from `X-Forwarded-For` with `TRUST_PROXY`, otherwise from the socket
+/// peer address) and the per-key rate limit. Scopes are checked by [`authenticate`] and
+/// [`authenticate_with_scope`]; org binding is carried on the returned
+/// `AuthenticatedUser` and checked by the endpoints that need it.
 async fn authenticate_api_key<C: AuthCallback, E: EmailService>(
     state: &Arc<AppState<C, E>>,
+    headers: &HeaderMap,
     api_key: &str,
 ) -> Result<AuthenticatedUser, AppError> {
     // R-02: Use find_by_key which performs constant-time hash comparison
@@ -113,6 +351,38 @@ async fn authenticate_api_key<C: AuthCallback, E: EmailService>(
         .await?
         .ok_or(AppError::InvalidToken)?;
 
+    if api_key_entity.is_expired() {
+        return Err(AppError::InvalidToken);
+    }
+
+    let client_ip = extract_client_ip_with_fallback(
+        headers,
+        state.config.server.trust_proxy,
+        current_peer_ip(),
+    );
+    if !api_key_entity.allows_ip(client_ip.as_deref()) {
+        return Err(AppError::Forbidden(
+            "API key is not allowed from this IP address".into(),
+        ));
+    }
+
+    if let Some(limit) = api_key_entity.rate_limit_per_minute {
+        let config = RateLimitConfig {
+            limit,
+            window_secs: 60,
+        };
+        let result = state
+            .api_key_rate_limiter
+            .check_and_record(&format!("api_key:{}", api_key_entity.id), &config)
+            .await;
+        if !result.allowed {
+            return Err(AppError::TooManyRequests(format!(
+                "API key rate limit exceeded; retry in {} seconds",
+                result.reset_secs
+            )));
+        }
+    }
+
     // Update last_used_at
     let _ = state.api_key_repo.update_last_used(api_key_entity.id).await;
 
@@ -126,17 +396,29 @@ async fn authenticate_api_key<C: AuthCallback, E: EmailService>(
     if state.config.email.require_verification && user.email.is_some() && !user.email_verified {
         return Err(AppError::Forbidden("Email not verified".into()));
     }
+    user.ensure_active()?;
 
     Ok(AuthenticatedUser {
         user_id: user.id,
         session_id: None,
-        org_id: None,
+        org_id: api_key_entity.org_id,
         role: None,
         is_api_key_auth: true,
         api_key_id: Some(api_key_entity.id),
         raw_api_key: Some(api_key.to_string()),
-        is_system_admin: if user.is_system_admin { Some(true) } else { None },
+        // Restricted and org-bound keys never carry system admin power
+        is_system_admin: if user.is_system_admin
+            && api_key_entity.org_id.is_none()
+            && api_key_entity.has_scope(SCOPE_ADMIN)
+        {
+            Some(true)
+        } else {
+            None
+        },
         email_verified: Some(user.email_verified),
+        api_key_scopes: (!api_key_entity.scopes.is_empty()).then_some(api_key_entity.scopes),
+        impersonation: None,
+        is_service_account: user.is_service_account,
     })
 }
 
@@ -181,6 +463,9 @@ async fn authenticate_jwt<C: AuthCallback, E: EmailService>(
         raw_api_key: None,
         is_system_admin: claims.is_system_admin,
         email_verified: claims.email_verified,
+        api_key_scopes: None,
+        impersonation: claims.act,
+        is_service_account: claims.service_account == Some(true),
     })
 }
 
@@ -202,6 +487,9 @@ mod tests {
             raw_api_key: None,
             is_system_admin: None,
             email_verified: None,
+            api_key_scopes: None,
+            impersonation: None,
+            is_service_account: false,
         };
         assert!(!user.is_api_key_auth);
         assert!(user.session_id.is_some());
@@ -220,6 +508,9 @@ mod tests {
             raw_api_key: Some("ck_test123".to_string()),
             is_system_admin: None,
             email_verified: None,
+            api_key_scopes: None,
+            impersonation: None,
+            is_service_account: false,
         };
         assert!(user.is_api_key_auth);
         assert!(user.session_id.is_none());
@@ -227,6 +518,88 @@ mod tests {
         assert!(user.raw_api_key.is_some());
     }
 
+    #[test]
+    fn test_service_account_pinned_to_its_org() {
+        let org_id = Uuid::new_v4();
+        let account = AuthenticatedUser {
+            user_id: Uuid::new_v4(),
+            session_id: Some(Uuid::new_v4()),
+            org_id: Some(org_id),
+            role: Some("member".to_string()),
+            is_api_key_auth: false,
+            api_key_id: None,
+            raw_api_key: None,
+            is_system_admin: None,
+            email_verified: None,
+            api_key_scopes: None,
+            impersonation: None,
+            is_service_account: true,
+        };
+        assert!(account.ensure_org(org_id).is_ok());
+        assert!(matches!(
+            account.ensure_org(Uuid::new_v4()),
+            Err(AppError::Forbidden(_))
+        ));
+        assert!(matches!(
+            ensure_user_principal(&account),
+            Err(AppError::Forbidden(_))
+        ));
+
+        // A user session may address any org it is a member of
+        let user = AuthenticatedUser {
+            is_service_account: false,
+            ..account
+        };
+        assert!(user.ensure_org(Uuid::new_v4()).is_ok());
+        assert!(ensure_user_principal(&user).is_ok());
+    }
+
+    #[test]
+    fn test_system_admin_principal_rejects_org_bound_keys_and_impersonation() {
+        let key = AuthenticatedUser {
+            user_id: Uuid::new_v4(),
+            session_id: None,
+            org_id: None,
+            role: None,
+            is_api_key_auth: true,
+            api_key_id: Some(Uuid::new_v4()),
+            raw_api_key: Some("ck_test123".to_string()),
+            is_system_admin: Some(true),
+            email_verified: None,
+            api_key_scopes: None,
+            impersonation: None,
+            is_service_account: false,
+        };
+        assert!(key.ensure_system_admin_principal().is_ok());
+
+        // Unscoped but bound to one org: still no system-wide access
+        let bound = AuthenticatedUser {
+            org_id: Some(Uuid::new_v4()),
+            ..key.clone()
+        };
+        assert!(matches!(
+            bound.ensure_system_admin_principal(),
+            Err(AppError::Forbidden(_))
+        ));
+
+        let impersonated = AuthenticatedUser {
+            is_api_key_auth: false,
+            api_key_id: None,
+            raw_api_key: None,
+            session_id: Some(Uuid::new_v4()),
+            impersonation: Some(ActorClaim {
+                sub: Uuid::new_v4(),
+                write: true,
+                org_id: None,
+            }),
+            ..key
+        };
+        assert!(matches!(
+            impersonated.ensure_system_admin_principal(),
+            Err(AppError::Forbidden(_))
+        ));
+    }
+
     #[test]
     fn test_get_default_org_context_uses_first_membership() {
         let user_id = Uuid::new_v4();
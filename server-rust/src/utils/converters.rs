@@ -15,5 +15,7 @@ pub fn user_entity_to_auth_user(entity: &UserEntity) -> AuthUser {
         email_verified: entity.email_verified,
         created_at: entity.created_at,
         updated_at: entity.updated_at,
+        public_metadata: entity.public_metadata.clone(),
+        unsafe_metadata: entity.unsafe_metadata.clone(),
     }
 }
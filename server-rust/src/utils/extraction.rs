@@ -5,9 +5,13 @@ use axum::{
     http::{header, request::Parts, HeaderMap},
 };
 use std::convert::Infallible;
-use std::future;
+use std::future::{self, Future};
 use std::net::{IpAddr, SocketAddr};
 
+tokio::task_local! {
+    static REQUEST_PEER_IP: Option<IpAddr>;
+}
+
 /// Extract access token from Authorization header or cookie
 pub fn extract_access_token(headers: &HeaderMap, cookie_name: &str) -> Option<String> {
     // First try Authorization header
@@ -78,6 +82,19 @@ where
     }
 }
 
+/// Run `fut` with `peer_ip` as the socket peer address of the request.
+///
+/// Used by the peer IP middleware so that code which only sees headers
+/// (API key IP allowlists) can fall back to the connection address.
+pub async fn with_peer_ip<F: Future>(peer_ip: Option<IpAddr>, fut: F) -> F::Output {
+    REQUEST_PEER_IP.scope(peer_ip, fut).await
+}
+
+/// Socket peer address of the request being handled, if known
+pub fn current_peer_ip() -> Option<IpAddr> {
+    REQUEST_PEER_IP.try_with(|ip| *ip).ok().flatten()
+}
+
 /// Maximum length for IP address strings (IPv6 max is ~45 chars)
 const MAX_IP_LEN: usize = 64;
 
@@ -344,4 +361,13 @@ mod tests {
         let headers = HeaderMap::new();
         assert_eq!(extract_client_ip(&headers, true), None);
     }
+
+    #[tokio::test]
+    async fn test_current_peer_ip_is_scoped_to_the_request() {
+        assert_eq!(current_peer_ip(), None);
+        let peer_ip = "203.0.113.10".parse::<IpAddr>().unwrap();
+        let seen = with_peer_ip(Some(peer_ip), async { current_peer_ip() }).await;
+        assert_eq!(seen, Some(peer_ip));
+        assert_eq!(current_peer_ip(), None);
+    }
 }
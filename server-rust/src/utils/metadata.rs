@@ -0,0 +1,156 @@
+//! User metadata patching and validation
+//!
+//! Metadata blobs are JSON objects updated with merge-patch semantics
+//! (RFC 7396): nested objects merge, `null` removes a key, anything else
+//! replaces it.
+
+use serde_json::{Map, Value};
+
+use crate::errors::AppError;
+
+/// Maximum serialized size of a single metadata blob (8 KiB)
+pub const MAX_METADATA_BYTES: usize = 8 * 1024;
+
+/// Maximum nesting depth of a metadata blob
+const MAX_METADATA_DEPTH: usize = 10;
+
+/// Apply a merge patch to a metadata object and validate the result
+///
+/// `field` names the blob in validation errors (e.g. "publicMetadata").
+pub fn apply_metadata_patch(
+    current: &Map<String, Value>,
+    patch: Map<String, Value>,
+    field: &str,
+) -> Result<Map<String, Value>, AppError> {
+    let mut merged = current.clone();
+    merge_object(&mut merged, patch);
+    validate_metadata(&merged, field)?;
+    Ok(merged)
+}
+
+/// Reject metadata that is too large or too deeply nested
+pub fn validate_metadata(metadata: &Map<String, Value>, field: &str) -> Result<(), AppError> {
+    let size = serde_json::to_vec(metadata)
+        .map_err(|e| AppError::Internal(e.into()))?
+        .len();
+    if size > MAX_METADATA_BYTES {
+        return Err(AppError::Validation(format!(
+            "{} must be at most {} bytes when serialized",
+            field, MAX_METADATA_BYTES
+        )));
+    }
+    if metadata.values().any(|v| depth(v) >= MAX_METADATA_DEPTH) {
+        return Err(AppError::Validation(format!(
+            "{} must be nested at most {} levels deep",
+            field, MAX_METADATA_DEPTH
+        )));
+    }
+    Ok(())
+}
+
+/// Copy the listed public metadata keys into a claims object
+///
+/// Returns `None` when no listed key is present, so tokens stay unchanged
+/// unless the feature is configured.
+pub fn project_metadata_claims(
+    public_metadata: &Map<String, Value>,
+    keys: &[String],
+) -> Option<Map<String, Value>> {
+    let claims: Map<String, Value> = keys
+        .iter()
+        .filter_map(|k| public_metadata.get(k).map(|v| (k.clone(), v.clone())))
+        .collect();
+    if claims.is_empty() {
+        None
+    } else {
+        Some(claims)
+    }
+}
+
+fn merge_object(target: &mut Map<String, Value>, patch: Map<String, Value>) {
+    for (key, value) in patch {
+        match value {
+            Value::Null => {
+                target.remove(&key);
+            }
+            Value::Object(patch_obj) => match target.get_mut(&key) {
+                Some(Value::Object(target_obj)) => merge_object(target_obj, patch_obj),
+                _ => {
+                    let mut fresh = Map::new();
+                    merge_object(&mut fresh, patch_obj);
+                    target.insert(key, Value::Object(fresh));
+                }
+            },
+            other => {
+                target.insert(key, other);
+            }
+        }
+    }
+}
+
+fn depth(value: &Value) -> usize {
+    match value {
+        Value::Object(map) => 1 + map.values().map(depth).max().unwrap_or(0),
+        Value::Array(items) => 1 + items.iter().map(depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn object(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("expected object"),
+        }
+    }
+
+    #[test]
+    fn test_merge_patch_semantics() {
+        let current = object(json!({
+            "theme": "dark",
+            "onboarding": { "step": 2, "skipped": false },
+            "beta": true
+        }));
+        let patch = object(json!({
+            "onboarding": { "step": 3, "skipped": null },
+            "beta": null,
+            "locale": "en"
+        }));
+
+        let merged = apply_metadata_patch(&current, patch, "publicMetadata").unwrap();
+        assert_eq!(
+            Value::Object(merged),
+            json!({ "theme": "dark", "onboarding": { "step": 3 }, "locale": "en" })
+        );
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let big = "x".repeat(MAX_METADATA_BYTES);
+        let err = apply_metadata_patch(
+            &Map::new(),
+            object(json!({ "blob": big })),
+            "unsafeMetadata",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unsafeMetadata"));
+    }
+
+    #[test]
+    fn test_project_metadata_claims() {
+        let public = object(json!({ "plan": "pro", "seats": 5, "note": "x" }));
+        let keys = vec![
+            "plan".to_string(),
+            "seats".to_string(),
+            "missing".to_string(),
+        ];
+
+        let claims = project_metadata_claims(&public, &keys).unwrap();
+        assert_eq!(Value::Object(claims), json!({ "plan": "pro", "seats": 5 }));
+        assert!(project_metadata_claims(&public, &[]).is_none());
+    }
+}
@@ -5,6 +5,7 @@ pub mod converters;
 pub mod cookies;
 pub mod device_detection;
 pub mod extraction;
+pub mod metadata;
 pub mod signup_org;
 pub mod tokens;
 pub mod validation;
@@ -14,6 +15,7 @@ pub use converters::*;
 pub use cookies::*;
 pub use device_detection::*;
 pub use extraction::*;
+pub use metadata::*;
 pub use signup_org::*;
 pub use tokens::*;
 pub use validation::*;